@@ -0,0 +1,124 @@
+/**
+ * AutomationBridge - AppleScript / Shortcuts session controls
+ *
+ * Cocoa scripting command handlers for the commands in Taskerino.sdef. Each
+ * handler forwards to the Rust control commands (control_server.rs) through
+ * the callbacks installed by automation.rs, so scripts and the Shortcuts
+ * "Run AppleScript" action can do e.g.
+ *
+ *   tell application "Taskerino" to start session named "Standup"
+ *
+ * Requirements: macOS 12.3+, NSAppleScriptEnabled + OSAScriptingDefinition in Info.plist
+ */
+
+import Foundation
+
+/// Runs a control command: (command, args JSON) -> response JSON (owned by Rust)
+public typealias AutomationHandler = @convention(c) (UnsafePointer<CChar>, UnsafePointer<CChar>) -> UnsafeMutablePointer<CChar>?
+/// Frees a response returned by the handler
+public typealias AutomationFree = @convention(c) (UnsafeMutablePointer<CChar>?) -> Void
+
+private var automationHandler: AutomationHandler?
+private var automationFree: AutomationFree?
+
+// MARK: - C-Compatible Global Functions (for Rust FFI)
+
+/// Install the Rust callbacks (called once from setup)
+@_cdecl("automation_bridge_install")
+public func automation_bridge_install(handler: AutomationHandler, free: AutomationFree) {
+    automationHandler = handler
+    automationFree = free
+}
+
+// MARK: - Script Commands
+
+/// Base class: runs `controlCommand` and turns the response into a script result or error
+class TaskerinoScriptCommand: NSScriptCommand {
+    /// Command name understood by control_server.rs
+    var controlCommand: String { "" }
+
+    var controlArguments: [String: Any] { [:] }
+
+    /// Script result for a successful command (nothing by default)
+    func scriptResult(from result: Any) -> Any? { nil }
+
+    override func performDefaultImplementation() -> Any? {
+        guard let handler = automationHandler, let free = automationFree else {
+            fail("Taskerino is still starting")
+            return nil
+        }
+
+        let argsData = (try? JSONSerialization.data(withJSONObject: controlArguments)) ?? Data("{}".utf8)
+        let argsJSON = String(data: argsData, encoding: .utf8) ?? "{}"
+        let responsePointer = controlCommand.withCString { command in
+            argsJSON.withCString { args in handler(command, args) }
+        }
+        guard let responsePointer = responsePointer else {
+            fail("No response from Taskerino")
+            return nil
+        }
+        let response = String(cString: responsePointer)
+        free(responsePointer)
+
+        guard let data = response.data(using: .utf8),
+              let object = (try? JSONSerialization.jsonObject(with: data)) as? [String: Any] else {
+            fail("Invalid response from Taskerino")
+            return nil
+        }
+        guard object["ok"] as? Bool == true else {
+            fail(object["error"] as? String ?? "Unknown error")
+            return nil
+        }
+        return scriptResult(from: object["result"] ?? NSNull())
+    }
+
+    private func fail(_ message: String) {
+        print("❌ [AUTOMATION] \(controlCommand) failed: \(message)")
+        scriptErrorNumber = -10000 // errAEEventFailed
+        scriptErrorString = message
+    }
+}
+
+@objc(TaskerinoStartSessionCommand)
+class StartSessionCommand: TaskerinoScriptCommand {
+    override var controlCommand: String { "start" }
+
+    override var controlArguments: [String: Any] {
+        guard let name = evaluatedArguments?["name"] as? String else { return [:] }
+        return ["name": name]
+    }
+}
+
+@objc(TaskerinoStopSessionCommand)
+class StopSessionCommand: TaskerinoScriptCommand {
+    override var controlCommand: String { "stop" }
+}
+
+@objc(TaskerinoPauseSessionCommand)
+class PauseSessionCommand: TaskerinoScriptCommand {
+    override var controlCommand: String { "pause" }
+}
+
+@objc(TaskerinoResumeSessionCommand)
+class ResumeSessionCommand: TaskerinoScriptCommand {
+    override var controlCommand: String { "resume" }
+}
+
+@objc(TaskerinoQuickCaptureCommand)
+class QuickCaptureCommand: TaskerinoScriptCommand {
+    override var controlCommand: String { "capture" }
+}
+
+/// Returns the active session as JSON text (missing value when none)
+@objc(TaskerinoActiveSessionCommand)
+class ActiveSessionCommand: TaskerinoScriptCommand {
+    override var controlCommand: String { "active" }
+
+    override func scriptResult(from result: Any) -> Any? {
+        guard !(result is NSNull),
+              let data = try? JSONSerialization.data(withJSONObject: result, options: [.prettyPrinted]) else {
+            return nil
+        }
+        return String(data: data, encoding: .utf8)
+    }
+}
@@ -0,0 +1,81 @@
+/**
+ * CalendarBridge - EventKit calendar access
+ *
+ * Reads events from the user's calendars for calendar.rs (meeting auto-start).
+ * Exposes C-compatible functions for Rust FFI integration.
+ *
+ * Requirements: macOS 12.3+, NSCalendarsUsageDescription (+ NSCalendarsFullAccessUsageDescription on macOS 14+)
+ */
+
+import Foundation
+import EventKit
+
+private let eventStore = EKEventStore()
+
+// MARK: - C-Compatible Global Functions (for Rust FFI)
+
+/// EKAuthorizationStatus raw value: 0 not determined, 1 restricted, 2 denied,
+/// 3 authorized (full access), 4 write only
+@_cdecl("calendar_authorization_status")
+public func calendar_authorization_status() -> Int32 {
+    return Int32(EKEventStore.authorizationStatus(for: .event).rawValue)
+}
+
+/// Request read access to events (shows the system dialog once)
+@_cdecl("calendar_request_access")
+public func calendar_request_access() -> Bool {
+    let semaphore = DispatchSemaphore(value: 0)
+    var granted = false
+
+    let completion: EKEventStoreRequestAccessCompletionHandler = { result, error in
+        if let error = error {
+            print("❌ Calendar access request failed: \(error)")
+        }
+        granted = result
+        semaphore.signal()
+    }
+
+    if #available(macOS 14.0, *) {
+        eventStore.requestFullAccessToEvents(completion: completion)
+    } else {
+        eventStore.requestAccess(to: .event, completion: completion)
+    }
+
+    semaphore.wait()
+    return granted
+}
+
+/// Events overlapping [start, end] (Unix seconds) as a JSON array
+@_cdecl("calendar_events_json")
+public func calendar_events_json(start: Double, end: Double) -> UnsafePointer<CChar>? {
+    let predicate = eventStore.predicateForEvents(
+        withStart: Date(timeIntervalSince1970: start),
+        end: Date(timeIntervalSince1970: end),
+        calendars: nil
+    )
+
+    let events: [[String: Any]] = eventStore.events(matching: predicate).map { event in
+        var json: [String: Any] = [
+            "id": event.eventIdentifier ?? "",
+            "title": event.title ?? "",
+            "start": event.startDate.timeIntervalSince1970,
+            "end": event.endDate.timeIntervalSince1970,
+            "allDay": event.isAllDay,
+            "calendar": event.calendar?.title ?? "",
+            "attendees": (event.attendees ?? []).compactMap { $0.name },
+        ]
+        if let location = event.location { json["location"] = location }
+        if let notes = event.notes { json["notes"] = notes }
+        if let url = event.url { json["url"] = url.absoluteString }
+        return json
+    }
+
+    guard let data = try? JSONSerialization.data(withJSONObject: events),
+          let string = String(data: data, encoding: .utf8) else {
+        print("❌ Failed to serialize calendar events")
+        return nil
+    }
+
+    // Return as C string (caller must free)
+    return UnsafePointer(strdup(string))
+}
@@ -0,0 +1,89 @@
+/**
+ * NotificationBridge - native user notifications
+ *
+ * Posts UNUserNotificationCenter notifications for notifications.rs.
+ * Exposes C-compatible functions for Rust FFI integration.
+ *
+ * UNUserNotificationCenter only works in a bundled app; unbundled (dev) builds
+ * report notifications as unavailable instead of crashing.
+ */
+
+import Foundation
+import UserNotifications
+
+private func notificationCenter() -> UNUserNotificationCenter? {
+    guard Bundle.main.bundleIdentifier != nil else { return nil }
+    return UNUserNotificationCenter.current()
+}
+
+// MARK: - C-Compatible Global Functions (for Rust FFI)
+
+/// UNAuthorizationStatus raw value: 0 not determined, 1 denied, 2 authorized,
+/// 3 provisional; -1 when notifications are unavailable (unbundled app)
+@_cdecl("notification_authorization_status")
+public func notification_authorization_status() -> Int32 {
+    guard let center = notificationCenter() else { return -1 }
+
+    let semaphore = DispatchSemaphore(value: 0)
+    var status: Int32 = 0
+    center.getNotificationSettings { settings in
+        status = Int32(settings.authorizationStatus.rawValue)
+        semaphore.signal()
+    }
+    semaphore.wait()
+    return status
+}
+
+/// Request permission to show alerts with sound (shows the system dialog once)
+@_cdecl("notification_request_access")
+public func notification_request_access() -> Bool {
+    guard let center = notificationCenter() else { return false }
+
+    let semaphore = DispatchSemaphore(value: 0)
+    var granted = false
+    center.requestAuthorization(options: [.alert, .sound]) { result, error in
+        if let error = error {
+            print("❌ Notification permission request failed: \(error)")
+        }
+        granted = result
+        semaphore.signal()
+    }
+    semaphore.wait()
+    return granted
+}
+
+/// Show a notification now. A notification with the same `identifier` is
+/// replaced; `threadId` groups notifications in Notification Center.
+@_cdecl("notification_post")
+public func notification_post(
+    identifier: UnsafePointer<CChar>,
+    title: UnsafePointer<CChar>,
+    body: UnsafePointer<CChar>,
+    threadId: UnsafePointer<CChar>
+) -> Bool {
+    guard let center = notificationCenter() else { return false }
+
+    let content = UNMutableNotificationContent()
+    content.title = String(cString: title)
+    content.body = String(cString: body)
+    content.threadIdentifier = String(cString: threadId)
+    content.sound = .default
+
+    let request = UNNotificationRequest(
+        identifier: String(cString: identifier),
+        content: content,
+        trigger: nil
+    )
+
+    let semaphore = DispatchSemaphore(value: 0)
+    var posted = false
+    center.add(request) { error in
+        if let error = error {
+            print("❌ Failed to post notification: \(error)")
+        }
+        posted = error == nil
+        semaphore.signal()
+    }
+    semaphore.wait()
+    return posted
+}
@@ -0,0 +1,42 @@
+/**
+ * OcrBridge - Vision text recognition
+ *
+ * Recognizes text in screenshots for ocr.rs (searchable session text).
+ * Exposes C-compatible functions for Rust FFI integration.
+ *
+ * Requirements: macOS 12.3+ (VNRecognizeTextRequest runs fully on-device)
+ */
+
+import Foundation
+import ImageIO
+import Vision
+
+// MARK: - C-Compatible Global Functions (for Rust FFI)
+
+/// Recognize text in encoded image bytes (JPEG/PNG/...); returns the
+/// recognized lines joined by newlines, or nil if the image can't be decoded
+@_cdecl("ocr_recognize_text")
+public func ocr_recognize_text(bytes: UnsafePointer<UInt8>, length: Int) -> UnsafePointer<CChar>? {
+    let data = Data(bytes: bytes, count: length)
+    guard let source = CGImageSourceCreateWithData(data as CFData, nil),
+          let image = CGImageSourceCreateImageAtIndex(source, 0, nil) else {
+        print("❌ OCR: failed to decode image")
+        return nil
+    }
+
+    let request = VNRecognizeTextRequest()
+    request.recognitionLevel = .accurate
+    request.usesLanguageCorrection = true
+
+    do {
+        try VNImageRequestHandler(cgImage: image, options: [:]).perform([request])
+    } catch {
+        print("❌ OCR: text recognition failed: \(error)")
+        return nil
+    }
+
+    let lines = (request.results ?? []).compactMap { $0.topCandidates(1).first?.string }
+
+    // Return as C string (caller must free)
+    return UnsafePointer(strdup(lines.joined(separator: "\n")))
+}
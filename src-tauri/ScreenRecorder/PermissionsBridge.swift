@@ -0,0 +1,63 @@
+/**
+ * PermissionsBridge - Privacy permission checks and requests
+ *
+ * Microphone and camera (AVFoundation), Accessibility (AX) and Input
+ * Monitoring (IOHID) for permissions.rs. Screen recording uses CoreGraphics
+ * directly from Rust.
+ * Exposes C-compatible functions for Rust FFI integration.
+ *
+ * Requirements: macOS 10.15+, NSMicrophoneUsageDescription / NSCameraUsageDescription
+ */
+
+import Foundation
+import AVFoundation
+import ApplicationServices
+import IOKit.hid
+
+private func mediaType(_ media: Int32) -> AVMediaType {
+    return media == 1 ? .video : .audio
+}
+
+// MARK: - C-Compatible Global Functions (for Rust FFI)
+
+/// AVAuthorizationStatus raw value for the microphone (0) or camera (1):
+/// 0 not determined, 1 restricted, 2 denied, 3 authorized
+@_cdecl("capture_authorization_status")
+public func capture_authorization_status(media: Int32) -> Int32 {
+    return Int32(AVCaptureDevice.authorizationStatus(for: mediaType(media)).rawValue)
+}
+
+/// Request microphone (0) or camera (1) access (shows the system dialog once)
+@_cdecl("capture_request_access")
+public func capture_request_access(media: Int32) -> Bool {
+    let semaphore = DispatchSemaphore(value: 0)
+    var granted = false
+
+    AVCaptureDevice.requestAccess(for: mediaType(media)) { result in
+        granted = result
+        semaphore.signal()
+    }
+
+    semaphore.wait()
+    return granted
+}
+
+/// Whether the app is trusted for Accessibility; with `prompt` macOS shows
+/// its dialog pointing to System Settings (until the app is trusted)
+@_cdecl("accessibility_is_trusted")
+public func accessibility_is_trusted(prompt: Bool) -> Bool {
+    let options = [kAXTrustedCheckOptionPrompt.takeUnretainedValue() as String: prompt] as CFDictionary
+    return AXIsProcessTrustedWithOptions(options)
+}
+
+/// IOHIDAccessType raw value for Input Monitoring: 0 granted, 1 denied, 2 unknown
+@_cdecl("input_monitoring_status")
+public func input_monitoring_status() -> Int32 {
+    return Int32(IOHIDCheckAccess(kIOHIDRequestTypeListenEvent).rawValue)
+}
+
+/// Request Input Monitoring (shows the system dialog once)
+@_cdecl("input_monitoring_request")
+public func input_monitoring_request() -> Bool {
+    return IOHIDRequestAccess(kIOHIDRequestTypeListenEvent)
+}
@@ -10,6 +10,9 @@
 import Foundation
 import ScreenCaptureKit
 import AVFoundation
+import VideoToolbox
+import CoreImage
+import AppKit
 
 // MARK: - C-Compatible Global Functions (for Rust FFI)
 
@@ -27,7 +30,10 @@ public func screen_recorder_start(
     path: UnsafePointer<CChar>,
     width: Int32,
     height: Int32,
-    fps: Int32
+    fps: Int32,
+    codec: Int32,
+    bitrate: Int32,
+    displayID: UInt32
 ) -> Bool {
     let instance = Unmanaged<ScreenRecorder>.fromOpaque(recorder).takeUnretainedValue()
     let pathString = String(cString: path)
@@ -40,6 +46,9 @@ public func screen_recorder_start(
     instance.width = width
     instance.height = height
     instance.fps = fps
+    instance.codecPreference = codec
+    instance.bitrate = bitrate
+    instance.displayID = displayID == 0 ? CGMainDisplayID() : displayID
 
     Task {
         do {
@@ -81,6 +90,91 @@ public func screen_recorder_stop(recorder: UnsafeMutableRawPointer) -> Bool {
     return success
 }
 
+/// Adaptive frame rate: while `idle`, keep at most `idleFps` frames per second
+/// (0 disables); playback timing is unaffected since frames carry capture time
+@_cdecl("screen_recorder_set_idle")
+public func screen_recorder_set_idle(recorder: UnsafeMutableRawPointer, idle: Bool, idleFps: Int32) {
+    let instance = Unmanaged<ScreenRecorder>.fromOpaque(recorder).takeUnretainedValue()
+    instance.idleFps = idleFps
+    instance.isIdle = idle
+}
+
+/// Privacy blackout: while `blanked`, every frame is written as solid black
+@_cdecl("screen_recorder_set_blanked")
+public func screen_recorder_set_blanked(recorder: UnsafeMutableRawPointer, blanked: Bool) {
+    let instance = Unmanaged<ScreenRecorder>.fromOpaque(recorder).takeUnretainedValue()
+    instance.isBlanked = blanked
+}
+
+/// Burned-in overlay: `label` (nullable), the wall-clock time when `timestamp`,
+/// and a PNG logo at `logoPath` (nullable), drawn in a corner
+/// (`position`: 0 top-left, 1 top-right, 2 bottom-left, 3 bottom-right).
+/// Returns false if the logo can't be loaded.
+@_cdecl("screen_recorder_set_overlay")
+public func screen_recorder_set_overlay(
+    recorder: UnsafeMutableRawPointer,
+    label: UnsafePointer<CChar>?,
+    timestamp: Bool,
+    logoPath: UnsafePointer<CChar>?,
+    position: Int32
+) -> Bool {
+    let instance = Unmanaged<ScreenRecorder>.fromOpaque(recorder).takeUnretainedValue()
+    var logo: CIImage?
+    if let logoPath = logoPath {
+        guard let image = CIImage(contentsOf: URL(fileURLWithPath: String(cString: logoPath))) else {
+            print("❌ Failed to load overlay logo: \(String(cString: logoPath))")
+            return false
+        }
+        logo = image
+    }
+    instance.setOverlay(
+        RecordingOverlay(
+            label: label.map { String(cString: $0) },
+            timestamp: timestamp,
+            logo: logo,
+            position: position
+        )
+    )
+    return true
+}
+
+/// Show a key combo (e.g. "⌘⇧P") centered at the bottom of the video for
+/// `keystrokeDisplaySeconds`; a newer combo replaces it
+@_cdecl("screen_recorder_show_keystroke")
+public func screen_recorder_show_keystroke(recorder: UnsafeMutableRawPointer, text: UnsafePointer<CChar>) {
+    let instance = Unmanaged<ScreenRecorder>.fromOpaque(recorder).takeUnretainedValue()
+    instance.showKeystroke(String(cString: text))
+}
+
+/// Display being recorded (CGDirectDisplayID)
+@_cdecl("screen_recorder_display_id")
+public func screen_recorder_display_id(recorder: UnsafeMutableRawPointer) -> UInt32 {
+    let instance = Unmanaged<ScreenRecorder>.fromOpaque(recorder).takeUnretainedValue()
+    return instance.displayID
+}
+
+private var displayReconfigurationHandler: (@convention(c) (UInt32, UInt32) -> Void)?
+
+/// Report display changes (display ID, CGDisplayChangeSummaryFlags) to Rust;
+/// called on the main thread once per display after each reconfiguration
+@_cdecl("display_monitor_install")
+public func display_monitor_install(handler: @escaping @convention(c) (UInt32, UInt32) -> Void) {
+    let firstInstall = displayReconfigurationHandler == nil
+    displayReconfigurationHandler = handler
+    guard firstInstall else {
+        return
+    }
+
+    CGDisplayRegisterReconfigurationCallback({ display, flags, _ in
+        // Skip the "about to change" notification; act on the result
+        if flags.contains(.beginConfigurationFlag) {
+            return
+        }
+        displayReconfigurationHandler?(display, flags.rawValue)
+    }, nil)
+    print("🖥️  Display reconfiguration monitor installed")
+}
+
 /// Check if currently recording
 @_cdecl("screen_recorder_is_recording")
 public func screen_recorder_is_recording(recorder: UnsafeMutableRawPointer) -> Bool {
@@ -88,6 +182,32 @@ public func screen_recorder_is_recording(recorder: UnsafeMutableRawPointer) -> B
     return instance.isRecording
 }
 
+/// Frames written / dropped (writer not keeping up or failing) and time spent
+/// processing frames since the recorder was created
+@_cdecl("screen_recorder_frame_stats")
+public func screen_recorder_frame_stats(
+    recorder: UnsafeMutableRawPointer,
+    written: UnsafeMutablePointer<Int64>,
+    dropped: UnsafeMutablePointer<Int64>,
+    busyNanos: UnsafeMutablePointer<Int64>
+) {
+    let instance = Unmanaged<ScreenRecorder>.fromOpaque(recorder).takeUnretainedValue()
+    let stats = instance.frameStats()
+    written.pointee = stats.written
+    dropped.pointee = stats.dropped
+    busyNanos.pointee = stats.busyNanos
+}
+
+/// Last capture or writer error, nil if none (caller must free)
+@_cdecl("screen_recorder_last_error")
+public func screen_recorder_last_error(recorder: UnsafeMutableRawPointer) -> UnsafePointer<CChar>? {
+    let instance = Unmanaged<ScreenRecorder>.fromOpaque(recorder).takeUnretainedValue()
+    guard let error = instance.frameStats().lastError else {
+        return nil
+    }
+    return UnsafePointer(strdup(error))
+}
+
 /// Destroy recorder instance
 @_cdecl("screen_recorder_destroy")
 public func screen_recorder_destroy(recorder: UnsafeMutableRawPointer) {
@@ -136,6 +256,13 @@ public func screen_recorder_request_permission() {
     print("   If denied, user must grant permission in System Settings > Privacy & Security > Screen Recording")
 }
 
+/// Version of this C API; diagnostics.rs compares it with what the Rust side
+/// was built against to catch a stale library
+@_cdecl("screen_recorder_bridge_version")
+public func screen_recorder_bridge_version() -> Int32 {
+    return 1
+}
+
 /// Get video duration in seconds
 @_cdecl("screen_recorder_get_duration")
 public func screen_recorder_get_duration(path: UnsafePointer<CChar>) -> Double {
@@ -191,6 +318,256 @@ public func screen_recorder_generate_thumbnail(
     }
 }
 
+/// Write frames of [start, start + duration) seconds at `fps` as
+/// frame000000.png, frame000001.png, ... into `outputDir`, at most `maxWidth`
+/// wide. Returns the number of frames written (-1 on failure).
+@_cdecl("screen_recorder_extract_frames")
+public func screen_recorder_extract_frames(
+    path: UnsafePointer<CChar>,
+    outputDir: UnsafePointer<CChar>,
+    startSeconds: Double,
+    durationSeconds: Double,
+    fps: Double,
+    maxWidth: Int32
+) -> Int32 {
+    let asset = AVURLAsset(url: URL(fileURLWithPath: String(cString: path)))
+    let directory = URL(fileURLWithPath: String(cString: outputDir))
+
+    let imageGenerator = AVAssetImageGenerator(asset: asset)
+    imageGenerator.appliesPreferredTrackTransform = true
+    imageGenerator.maximumSize = CGSize(width: CGFloat(maxWidth), height: 0)
+    imageGenerator.requestedTimeToleranceBefore = .zero
+    imageGenerator.requestedTimeToleranceAfter = .zero
+
+    let end = min(startSeconds + durationSeconds, CMTimeGetSeconds(asset.duration))
+    var written: Int32 = 0
+    var time = startSeconds
+    while time < end {
+        do {
+            let cgImage = try imageGenerator.copyCGImage(
+                at: CMTime(seconds: time, preferredTimescale: 600),
+                actualTime: nil
+            )
+            guard let pngData = NSBitmapImageRep(cgImage: cgImage).representation(using: .png, properties: [:]) else {
+                print("❌ Failed to generate PNG data")
+                return -1
+            }
+            let name = String(format: "frame%06d.png", written)
+            try pngData.write(to: directory.appendingPathComponent(name))
+            written += 1
+        } catch {
+            print("❌ Failed to extract frame at \(time)s: \(error)")
+            return -1
+        }
+        time += 1.0 / fps
+    }
+
+    print("✅ Extracted \(written) frames")
+    return written
+}
+
+/// Join recording segments (newline-separated paths) into one MP4 without re-encoding
+@_cdecl("screen_recorder_concat_segments")
+public func screen_recorder_concat_segments(
+    paths: UnsafePointer<CChar>,
+    output: UnsafePointer<CChar>
+) -> Bool {
+    let segmentPaths = String(cString: paths).split(separator: "\n").map(String.init)
+    let outputURL = URL(fileURLWithPath: String(cString: output))
+
+    let composition = AVMutableComposition()
+    guard let track = composition.addMutableTrack(
+        withMediaType: .video,
+        preferredTrackID: kCMPersistentTrackID_Invalid
+    ) else {
+        print("❌ Failed to create composition track")
+        return false
+    }
+
+    var cursor = CMTime.zero
+    for path in segmentPaths {
+        let asset = AVURLAsset(url: URL(fileURLWithPath: path))
+        guard let sourceTrack = asset.tracks(withMediaType: .video).first else {
+            // A segment paused before its first frame has no video track
+            print("⚠️  Skipping empty segment: \(path)")
+            continue
+        }
+
+        do {
+            try track.insertTimeRange(
+                CMTimeRange(start: .zero, duration: asset.duration),
+                of: sourceTrack,
+                at: cursor
+            )
+        } catch {
+            print("❌ Failed to add segment \(path): \(error)")
+            return false
+        }
+        cursor = CMTimeAdd(cursor, asset.duration)
+    }
+
+    try? FileManager.default.removeItem(at: outputURL)
+
+    guard let exporter = AVAssetExportSession(
+        asset: composition,
+        presetName: AVAssetExportPresetPassthrough
+    ) else {
+        print("❌ Failed to create export session")
+        return false
+    }
+    exporter.outputURL = outputURL
+    exporter.outputFileType = .mp4
+
+    let semaphore = DispatchSemaphore(value: 0)
+    exporter.exportAsynchronously {
+        semaphore.signal()
+    }
+    semaphore.wait()
+
+    guard exporter.status == .completed else {
+        print("❌ Failed to stitch segments: \(String(describing: exporter.error))")
+        return false
+    }
+
+    print("✅ Stitched \(segmentPaths.count) segments (\(CMTimeGetSeconds(cursor)) seconds)")
+    return true
+}
+
+/// Join audio files (newline-separated paths, any format AVFoundation reads)
+/// into one AAC .m4a file
+@_cdecl("screen_recorder_concat_audio")
+public func screen_recorder_concat_audio(
+    paths: UnsafePointer<CChar>,
+    output: UnsafePointer<CChar>
+) -> Bool {
+    let audioPaths = String(cString: paths).split(separator: "\n").map(String.init)
+    let outputURL = URL(fileURLWithPath: String(cString: output))
+
+    let composition = AVMutableComposition()
+    guard let track = composition.addMutableTrack(
+        withMediaType: .audio,
+        preferredTrackID: kCMPersistentTrackID_Invalid
+    ) else {
+        print("❌ Failed to create composition track")
+        return false
+    }
+
+    var cursor = CMTime.zero
+    for path in audioPaths {
+        let asset = AVURLAsset(url: URL(fileURLWithPath: path))
+        guard let sourceTrack = asset.tracks(withMediaType: .audio).first else {
+            print("❌ No audio track in \(path)")
+            return false
+        }
+
+        do {
+            try track.insertTimeRange(
+                CMTimeRange(start: .zero, duration: asset.duration),
+                of: sourceTrack,
+                at: cursor
+            )
+        } catch {
+            print("❌ Failed to add audio \(path): \(error)")
+            return false
+        }
+        cursor = CMTimeAdd(cursor, asset.duration)
+    }
+
+    try? FileManager.default.removeItem(at: outputURL)
+
+    guard let exporter = AVAssetExportSession(
+        asset: composition,
+        presetName: AVAssetExportPresetAppleM4A
+    ) else {
+        print("❌ Failed to create export session")
+        return false
+    }
+    exporter.outputURL = outputURL
+    exporter.outputFileType = .m4a
+
+    let semaphore = DispatchSemaphore(value: 0)
+    exporter.exportAsynchronously {
+        semaphore.signal()
+    }
+    semaphore.wait()
+
+    guard exporter.status == .completed else {
+        print("❌ Failed to join audio: \(String(describing: exporter.error))")
+        return false
+    }
+
+    print("✅ Joined \(audioPaths.count) audio files (\(CMTimeGetSeconds(cursor)) seconds)")
+    return true
+}
+
+/// Whether the video track has a sync sample (keyframe) at `time`, within one frame
+private func startsOnKeyframe(_ track: AVAssetTrack, at time: CMTime) -> Bool {
+    if time == .zero {
+        return true
+    }
+    guard track.canProvideSampleCursors,
+          let cursor = track.makeSampleCursor(presentationTimeStamp: time) else {
+        return false
+    }
+    let frameDuration = track.minFrameDuration.isValid
+        ? CMTimeGetSeconds(track.minFrameDuration)
+        : 1.0 / 30.0
+    let offset = abs(CMTimeGetSeconds(CMTimeSubtract(cursor.presentationTimeStamp, time)))
+    return cursor.currentSampleSyncInfo.sampleIsFullSync && offset <= frameDuration
+}
+
+/// Export [start, end) seconds of a video to an MP4. Returns 1 if the streams
+/// were copied (clip starts on a keyframe), 2 if it was re-encoded, 0 on failure.
+@_cdecl("screen_recorder_extract_clip")
+public func screen_recorder_extract_clip(
+    path: UnsafePointer<CChar>,
+    output: UnsafePointer<CChar>,
+    startSeconds: Double,
+    endSeconds: Double
+) -> Int32 {
+    let asset = AVURLAsset(url: URL(fileURLWithPath: String(cString: path)))
+    let outputURL = URL(fileURLWithPath: String(cString: output))
+
+    guard let videoTrack = asset.tracks(withMediaType: .video).first else {
+        print("❌ No video track to extract a clip from")
+        return 0
+    }
+    let start = CMTime(seconds: startSeconds, preferredTimescale: 600)
+    let end = CMTimeMinimum(CMTime(seconds: endSeconds, preferredTimescale: 600), asset.duration)
+    guard CMTimeCompare(end, start) > 0 else {
+        print("❌ Clip range is outside the video")
+        return 0
+    }
+
+    let copy = startsOnKeyframe(videoTrack, at: start)
+    try? FileManager.default.removeItem(at: outputURL)
+
+    guard let exporter = AVAssetExportSession(
+        asset: asset,
+        presetName: copy ? AVAssetExportPresetPassthrough : AVAssetExportPresetHighestQuality
+    ) else {
+        print("❌ Failed to create export session")
+        return 0
+    }
+    exporter.outputURL = outputURL
+    exporter.outputFileType = .mp4
+    exporter.timeRange = CMTimeRange(start: start, end: end)
+
+    let semaphore = DispatchSemaphore(value: 0)
+    exporter.exportAsynchronously {
+        semaphore.signal()
+    }
+    semaphore.wait()
+
+    guard exporter.status == .completed else {
+        print("❌ Failed to extract clip: \(String(describing: exporter.error))")
+        return 0
+    }
+
+    print("✅ Extracted \(CMTimeGetSeconds(CMTimeSubtract(end, start))) second clip (\(copy ? "stream copy" : "re-encoded"))")
+    return copy ? 1 : 2
+}
+
 // MARK: - ScreenRecorder Class
 
 @available(macOS 12.3, *)
@@ -204,11 +581,41 @@ public class ScreenRecorder: NSObject {
     private var outputURL: URL?
     private var startTime: CMTime?
     private var frameCount: Int64 = 0
+    private var firstFrameTime: CMTime?
+    private var lastFrameTime: CMTime?
+
+    // Health counters (read from Rust for session health reports)
+    private let statsLock = NSLock()
+    private var framesWritten: Int64 = 0
+    private var framesDropped: Int64 = 0
+    private var busyNanos: Int64 = 0
+    private var lastError: String?
+
+    // Adaptive frame rate (set from Rust when the user goes idle)
+    fileprivate var isIdle = false
+    fileprivate var idleFps: Int32 = 0
+
+    // Privacy blackout (set from Rust while a blocked app is frontmost)
+    fileprivate var isBlanked = false
+    private var blankPixelBuffer: CVPixelBuffer?
+
+    // Burned-in overlay (session name, timestamp, logo)
+    private let overlayLock = NSLock()
+    private var overlay: RecordingOverlay?
+    private var overlayLabelText: String?
+    private var overlayLabelImage: CIImage?
+    private var keystroke: (text: String, shownAt: Date, image: CIImage?)?
+    private lazy var overlayContext = CIContext(options: [.useSoftwareRenderer: false])
 
     // Configuration
     fileprivate var width: Int32 = 1280
     fileprivate var height: Int32 = 720
     fileprivate var fps: Int32 = 15
+    fileprivate var displayID: CGDirectDisplayID = CGMainDisplayID()
+    /// 0 auto (HEVC if available), 1 H.264, 2 HEVC
+    fileprivate var codecPreference: Int32 = 0
+    /// Average bitrate for HEVC (H.264 gets twice this)
+    fileprivate var bitrate: Int32 = 1_200_000
 
     // Codec detection - lazy property to test HEVC availability once
     private lazy var codecConfiguration: (codec: AVVideoCodecType, profile: String) = {
@@ -261,6 +668,30 @@ public class ScreenRecorder: NSObject {
         }
     }()
 
+    fileprivate func frameStats() -> (written: Int64, dropped: Int64, busyNanos: Int64, lastError: String?) {
+        statsLock.lock()
+        defer { statsLock.unlock() }
+        return (framesWritten, framesDropped, busyNanos, lastError)
+    }
+
+    fileprivate func recordFrame(written: Bool, since start: UInt64) {
+        let elapsed = Int64(DispatchTime.now().uptimeNanoseconds - start)
+        statsLock.lock()
+        if written {
+            framesWritten += 1
+        } else {
+            framesDropped += 1
+        }
+        busyNanos += elapsed
+        statsLock.unlock()
+    }
+
+    fileprivate func recordError(_ error: Error) {
+        statsLock.lock()
+        lastError = error.localizedDescription
+        statsLock.unlock()
+    }
+
     fileprivate func startRecording(path: String) async throws {
         guard !isRecording else {
             print("⚠️  Already recording")
@@ -279,7 +710,7 @@ public class ScreenRecorder: NSObject {
         print("📋 Getting shareable content...")
         let content = try await SCShareableContent.excludingDesktopWindows(false, onScreenWindowsOnly: true)
 
-        guard let display = content.displays.first else {
+        guard let display = content.displays.first(where: { $0.displayID == displayID }) else {
             throw ScreenRecorderError.noDisplayFound
         }
 
@@ -331,7 +762,8 @@ public class ScreenRecorder: NSObject {
     }
 
     fileprivate func stopRecording() async throws {
-        guard isRecording else {
+        // The stream may already have died (e.g. display unplugged); still finalize the file
+        guard isRecording || assetWriter != nil else {
             print("⚠️  Not currently recording")
             return
         }
@@ -340,7 +772,11 @@ public class ScreenRecorder: NSObject {
 
         // Stop stream
         if let stream = stream {
-            try await stream.stopCapture()
+            do {
+                try await stream.stopCapture()
+            } catch {
+                print("⚠️  Stream already stopped: \(error)")
+            }
             self.stream = nil
         }
 
@@ -356,6 +792,7 @@ public class ScreenRecorder: NSObject {
                 print("✅ Video saved to: \(outputURL?.path ?? "unknown")")
             } else if let error = assetWriter.error {
                 print("❌ Asset writer failed: \(error)")
+                recordError(error)
                 throw error
             }
         }
@@ -368,6 +805,8 @@ public class ScreenRecorder: NSObject {
         self.startTime = nil
         self.streamOutput = nil // Release output handler
         self.frameCount = 0
+        self.firstFrameTime = nil
+        self.lastFrameTime = nil
 
         print("✅ Recording stopped successfully")
     }
@@ -381,13 +820,28 @@ public class ScreenRecorder: NSObject {
         // Create asset writer
         let writer = try AVAssetWriter(url: url, fileType: .mp4)
 
-        // Configure video settings with detected codec (HEVC or H.264 fallback)
-        let codecConfig = codecConfiguration
-        print("📹 Using codec: \(codecConfig.codec.rawValue)")
+        // Write a fragmented MP4 so a crash loses at most the last fragment
+        // instead of the whole file (recovery.rs finalizes it on next launch)
+        writer.movieFragmentInterval = CMTime(seconds: 10, preferredTimescale: 600)
+
+        // Configure video settings with the requested codec (auto/HEVC fall back to H.264)
+        let codecConfig: (codec: AVVideoCodecType, profile: String)
+        if codecPreference == 1 {
+            codecConfig = (.h264, AVVideoProfileLevelH264HighAutoLevel)
+        } else {
+            codecConfig = codecConfiguration
+            if codecPreference == 2 && codecConfig.codec != .hevc {
+                print("⚠️  HEVC requested but not available - recording H.264 instead")
+            }
+        }
+
+        // H.264 needs roughly twice the bitrate of HEVC for the same quality
+        let averageBitRate = codecConfig.codec == .hevc ? Int(bitrate) : Int(bitrate) * 2
+        print("📹 Using codec: \(codecConfig.codec.rawValue) @ \(averageBitRate / 1000) kbps")
 
         // Build compression properties - only add profile level for H.264
         var compressionProperties: [String: Any] = [
-            AVVideoAverageBitRateKey: 1_200_000, // 1.2 Mbps
+            AVVideoAverageBitRateKey: averageBitRate,
             AVVideoExpectedSourceFrameRateKey: fps
         ]
 
@@ -400,7 +854,11 @@ public class ScreenRecorder: NSObject {
             AVVideoCodecKey: codecConfig.codec,
             AVVideoWidthKey: width,
             AVVideoHeightKey: height,
-            AVVideoCompressionPropertiesKey: compressionProperties
+            AVVideoCompressionPropertiesKey: compressionProperties,
+            // Prefer the VideoToolbox hardware encoder (software is used if there is none)
+            AVVideoEncoderSpecificationKey: [
+                kVTVideoEncoderSpecification_EnableHardwareAcceleratedVideoEncoder as String: true
+            ]
         ]
 
         // Create video input
@@ -444,16 +902,21 @@ public class ScreenRecorder: NSObject {
             return
         }
 
+        let start = DispatchTime.now().uptimeNanoseconds
+
         // Ensure writer is ready
         guard assetWriter.status == .writing else {
             if let error = assetWriter.error {
                 print("❌ Asset writer error: \(error)")
+                recordError(error)
             }
+            recordFrame(written: false, since: start)
             return
         }
 
-        // Wait until input is ready
+        // Encoder is behind; the frame is lost
         guard videoInput.isReadyForMoreMediaData else {
+            recordFrame(written: false, since: start)
             return
         }
 
@@ -474,14 +937,36 @@ public class ScreenRecorder: NSObject {
             return
         }
 
-        // Calculate presentation timestamp
-        let presentationTime = CMTime(value: frameCount, timescale: CMTimeScale(fps))
+        // Calculate presentation timestamp from capture time, so frames dropped while
+        // idle (or never delivered for a static screen) don't shorten the video
+        let captureTime = CMSampleBufferGetPresentationTimeStamp(sampleBuffer)
+        guard captureTime.isValid else {
+            return
+        }
+
+        if isIdle && idleFps > 0, let lastFrameTime = lastFrameTime,
+           CMTimeGetSeconds(CMTimeSubtract(captureTime, lastFrameTime)) < 1.0 / Double(idleFps) {
+            return
+        }
+
+        // Never fall back to the real frame while blanked - drop it instead
+        guard let frame = isBlanked ? blankFrame(adaptor: adaptor) : overlaidFrame(pixelBuffer, adaptor: adaptor) else {
+            return
+        }
+
+        let firstFrameTime = self.firstFrameTime ?? captureTime
+        self.firstFrameTime = firstFrameTime
+        self.lastFrameTime = captureTime
+        let presentationTime = CMTimeSubtract(captureTime, firstFrameTime)
         frameCount += 1
 
         // Append pixel buffer
-        if !adaptor.append(pixelBuffer, withPresentationTime: presentationTime) {
+        let appended = adaptor.append(frame, withPresentationTime: presentationTime)
+        recordFrame(written: appended, since: start)
+        if !appended {
             if let error = assetWriter.error {
                 print("❌ Failed to append pixel buffer: \(error)")
+                recordError(error)
             }
         } else {
             if frameCount % 30 == 0 { // Log every 30 frames to reduce spam
@@ -491,6 +976,195 @@ public class ScreenRecorder: NSObject {
     }
 }
 
+// MARK: - Privacy Blackout
+
+@available(macOS 12.3, *)
+extension ScreenRecorder {
+    /// Solid black BGRA frame from the adaptor's pool (created once, then reused)
+    fileprivate func blankFrame(adaptor: AVAssetWriterInputPixelBufferAdaptor) -> CVPixelBuffer? {
+        if let blank = blankPixelBuffer {
+            return blank
+        }
+        guard let pool = adaptor.pixelBufferPool else {
+            return nil
+        }
+
+        var buffer: CVPixelBuffer?
+        guard CVPixelBufferPoolCreatePixelBuffer(nil, pool, &buffer) == kCVReturnSuccess,
+              let buffer = buffer else {
+            return nil
+        }
+        CVPixelBufferLockBaseAddress(buffer, [])
+        if let base = CVPixelBufferGetBaseAddress(buffer) {
+            memset(base, 0, CVPixelBufferGetDataSize(buffer))
+        }
+        CVPixelBufferUnlockBaseAddress(buffer, [])
+
+        blankPixelBuffer = buffer
+        return buffer
+    }
+}
+
+// MARK: - Overlay
+
+/// What `screen_recorder_set_overlay` burns into every frame
+fileprivate struct RecordingOverlay {
+    var label: String?
+    var timestamp: Bool
+    var logo: CIImage?
+    /// 0 top-left, 1 top-right, 2 bottom-left, 3 bottom-right
+    var position: Int32
+
+    var isEmpty: Bool {
+        return label == nil && !timestamp && logo == nil
+    }
+}
+
+private let keystrokeDisplaySeconds: TimeInterval = 1.2
+
+private let overlayTimestampFormatter: DateFormatter = {
+    let formatter = DateFormatter()
+    formatter.dateFormat = "yyyy-MM-dd HH:mm:ss"
+    return formatter
+}()
+
+@available(macOS 12.3, *)
+extension ScreenRecorder {
+    fileprivate func setOverlay(_ overlay: RecordingOverlay) {
+        overlayLock.lock()
+        self.overlay = overlay.isEmpty ? nil : overlay
+        overlayLabelText = nil
+        overlayLabelImage = nil
+        overlayLock.unlock()
+    }
+
+    fileprivate func showKeystroke(_ text: String) {
+        overlayLock.lock()
+        keystroke = (text, Date(), nil)
+        overlayLock.unlock()
+    }
+
+    /// `source` with the overlay and current keystroke composited on top, in a
+    /// buffer from the adaptor's pool; `source` itself when there is nothing to
+    /// draw (or on failure)
+    fileprivate func overlaidFrame(_ source: CVPixelBuffer, adaptor: AVAssetWriterInputPixelBufferAdaptor) -> CVPixelBuffer? {
+        overlayLock.lock()
+        defer { overlayLock.unlock() }
+        if let shown = keystroke, Date().timeIntervalSince(shown.shownAt) > keystrokeDisplaySeconds {
+            keystroke = nil
+        }
+        guard overlay != nil || keystroke != nil, let pool = adaptor.pixelBufferPool else {
+            return source
+        }
+
+        var output: CVPixelBuffer?
+        guard CVPixelBufferPoolCreatePixelBuffer(nil, pool, &output) == kCVReturnSuccess,
+              let output = output else {
+            return source
+        }
+
+        let frame = CIImage(cvPixelBuffer: source)
+        let bounds = frame.extent
+        let margin = (bounds.height * 0.02).rounded()
+        let spacing = margin / 2
+
+        var composite = frame
+        if let keycap = keystrokeImage(frameHeight: bounds.height) {
+            let extent = keycap.extent
+            let x = bounds.midX - extent.width / 2
+            let y = bounds.minY + bounds.height * 0.08
+            composite = keycap
+                .transformed(by: CGAffineTransform(translationX: x - extent.minX, y: y - extent.minY))
+                .composited(over: composite)
+        }
+        guard let overlay = overlay else {
+            overlayContext.render(composite.cropped(to: bounds), to: output)
+            return output
+        }
+
+        // Logo and label stacked (logo first), anchored to the chosen corner
+        var parts: [CIImage] = []
+        if let logo = overlay.logo, logo.extent.height > 0 {
+            let scale = (bounds.height * 0.08) / logo.extent.height
+            parts.append(logo.transformed(by: CGAffineTransform(scaleX: scale, y: scale)))
+        }
+        if let label = overlayLabel(overlay, frameHeight: bounds.height) {
+            parts.append(label)
+        }
+
+        let top = overlay.position == 0 || overlay.position == 1
+        let right = overlay.position == 1 || overlay.position == 3
+        // Core Image's origin is bottom-left; stack away from the anchored edge
+        var y = top ? bounds.maxY - margin : bounds.minY + margin
+        for part in (top ? parts : parts.reversed()) {
+            let extent = part.extent
+            let x = right ? bounds.maxX - margin - extent.width : bounds.minX + margin
+            let originY = top ? y - extent.height : y
+            let placed = part.transformed(by: CGAffineTransform(translationX: x - extent.minX, y: originY - extent.minY))
+            composite = placed.composited(over: composite)
+            y = top ? originY - spacing : originY + extent.height + spacing
+        }
+
+        overlayContext.render(composite.cropped(to: bounds), to: output)
+        return output
+    }
+
+    /// Current key combo as a large keycap (rendered once per combo)
+    private func keystrokeImage(frameHeight: CGFloat) -> CIImage? {
+        guard let shown = keystroke else {
+            return nil
+        }
+        if let image = shown.image {
+            return image
+        }
+        let fontSize = max(18, (frameHeight * 0.05).rounded())
+        let image = textBox(shown.text, fontSize: fontSize, weight: .medium)
+        keystroke = (shown.text, shown.shownAt, image)
+        return image
+    }
+
+    /// White text on a translucent box
+    private func textBox(_ text: String, fontSize: CGFloat, weight: NSFont.Weight) -> CIImage? {
+        let attributed = NSAttributedString(string: text, attributes: [
+            .font: NSFont.monospacedDigitSystemFont(ofSize: fontSize, weight: weight),
+            .foregroundColor: NSColor.white
+        ])
+        guard let filter = CIFilter(name: "CIAttributedTextImageGenerator") else {
+            return nil
+        }
+        filter.setValue(attributed, forKey: "inputText")
+        filter.setValue(1.0, forKey: "inputScaleFactor")
+        guard let textImage = filter.outputImage else {
+            return nil
+        }
+
+        let padding = (fontSize * 0.4).rounded()
+        let box = textImage.extent.insetBy(dx: -padding, dy: -padding / 2)
+        let background = CIImage(color: CIColor(red: 0, green: 0, blue: 0, alpha: 0.55)).cropped(to: box)
+        return textImage.composited(over: background)
+    }
+
+    /// Overlay label; re-rendered only when the text changes
+    private func overlayLabel(_ overlay: RecordingOverlay, frameHeight: CGFloat) -> CIImage? {
+        let timestamp = overlay.timestamp ? overlayTimestampFormatter.string(from: Date()) : nil
+        let text = [overlay.label, timestamp].compactMap { $0 }.joined(separator: "  ·  ")
+        if text.isEmpty {
+            return nil
+        }
+        if text == overlayLabelText, let image = overlayLabelImage {
+            return image
+        }
+
+        let fontSize = max(12, (frameHeight * 0.025).rounded())
+        guard let image = textBox(text, fontSize: fontSize, weight: .semibold) else {
+            return nil
+        }
+        overlayLabelText = text
+        overlayLabelImage = image
+        return image
+    }
+}
+
 // MARK: - Stream Output Handler
 
 @available(macOS 12.3, *)
@@ -518,6 +1192,7 @@ extension ScreenRecorder: SCStreamDelegate {
     public func stream(_ stream: SCStream, didStopWithError error: Error) {
         print("❌ [STREAM DELEGATE] Stream stopped with error: \(error)")
         print("❌ [STREAM DELEGATE] Error details: \(error.localizedDescription)")
+        recordError(error)
         isRecording = false
     }
 
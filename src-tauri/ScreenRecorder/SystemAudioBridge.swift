@@ -0,0 +1,134 @@
+/**
+ * SystemAudioBridge - system audio capture via ScreenCaptureKit
+ *
+ * Captures what the Mac is playing (this app's own output excluded) as mono
+ * Float32 PCM and hands every buffer to a Rust callback (system_audio.rs).
+ * Exposes C-compatible functions for Rust FFI integration.
+ *
+ * Requirements: macOS 13.0+ (SCStreamConfiguration.capturesAudio), Screen Recording permission
+ */
+
+import Foundation
+import ScreenCaptureKit
+import CoreMedia
+
+/// (context, samples, sample count, sample rate)
+public typealias SystemAudioHandler = @convention(c) (UnsafeRawPointer?, UnsafePointer<Float>?, Int, UInt32) -> Void
+
+@available(macOS 13.0, *)
+class SystemAudioCapture: NSObject, SCStreamOutput, SCStreamDelegate {
+    private let handler: SystemAudioHandler
+    private let context: UnsafeRawPointer?
+    private let queue = DispatchQueue(label: "com.taskerino.system-audio")
+    private var stream: SCStream?
+
+    init(handler: @escaping SystemAudioHandler, context: UnsafeRawPointer?) {
+        self.handler = handler
+        self.context = context
+    }
+
+    func start() async throws {
+        let content = try await SCShareableContent.excludingDesktopWindows(false, onScreenWindowsOnly: true)
+        guard let display = content.displays.first(where: { $0.displayID == CGMainDisplayID() }) ?? content.displays.first else {
+            throw NSError(domain: "SystemAudioCapture", code: 1, userInfo: [NSLocalizedDescriptionKey: "No display available"])
+        }
+
+        let configuration = SCStreamConfiguration()
+        configuration.capturesAudio = true
+        configuration.excludesCurrentProcessAudio = true
+        configuration.sampleRate = 48000
+        configuration.channelCount = 1
+        // Audio needs a display stream; keep its video as cheap as possible
+        configuration.width = 2
+        configuration.height = 2
+        configuration.minimumFrameInterval = CMTime(value: 1, timescale: 1)
+
+        let filter = SCContentFilter(display: display, excludingWindows: [])
+        let stream = SCStream(filter: filter, configuration: configuration, delegate: self)
+        try stream.addStreamOutput(self, type: .audio, sampleHandlerQueue: queue)
+        try await stream.startCapture()
+        self.stream = stream
+        print("🔊 System audio capture started")
+    }
+
+    func stop() async {
+        guard let stream = stream else {
+            return
+        }
+        do {
+            try await stream.stopCapture()
+        } catch {
+            print("⚠️  Failed to stop system audio capture: \(error)")
+        }
+        self.stream = nil
+        print("🔊 System audio capture stopped")
+    }
+
+    func stream(_ stream: SCStream, didOutputSampleBuffer sampleBuffer: CMSampleBuffer, of type: SCStreamOutputType) {
+        guard type == .audio, sampleBuffer.isValid else {
+            return
+        }
+        let sampleRate = sampleBuffer.formatDescription?.audioStreamBasicDescription?.mSampleRate ?? 48000
+
+        // Float32, non-interleaved; with one channel the first buffer holds everything
+        try? sampleBuffer.withAudioBufferList { audioBufferList, _ in
+            guard let buffer = audioBufferList.first, let data = buffer.mData else {
+                return
+            }
+            let count = Int(buffer.mDataByteSize) / MemoryLayout<Float>.size
+            handler(context, data.assumingMemoryBound(to: Float.self), count, UInt32(sampleRate))
+        }
+    }
+
+    func stream(_ stream: SCStream, didStopWithError error: Error) {
+        print("❌ System audio stream stopped with error: \(error)")
+    }
+}
+
+// MARK: - C-Compatible Global Functions (for Rust FFI)
+
+/// Start capturing system audio; returns nil when unsupported or on failure.
+/// `handler` is called on a capture queue until system_audio_stop returns.
+@_cdecl("system_audio_start")
+public func system_audio_start(handler: @escaping SystemAudioHandler, context: UnsafeRawPointer?) -> UnsafeMutableRawPointer? {
+    guard #available(macOS 13.0, *) else {
+        print("❌ System audio capture requires macOS 13.0+")
+        return nil
+    }
+
+    let capture = SystemAudioCapture(handler: handler, context: context)
+    let semaphore = DispatchSemaphore(value: 0)
+    var success = false
+
+    Task {
+        do {
+            try await capture.start()
+            success = true
+        } catch {
+            print("❌ Failed to start system audio capture: \(error)")
+            success = false
+        }
+        semaphore.signal()
+    }
+
+    semaphore.wait()
+    return success ? Unmanaged.passRetained(capture).toOpaque() : nil
+}
+
+/// Stop capturing and release the capture; no callbacks arrive afterwards
+@_cdecl("system_audio_stop")
+public func system_audio_stop(capture: UnsafeMutableRawPointer) {
+    guard #available(macOS 13.0, *) else {
+        return
+    }
+
+    let instance = Unmanaged<SystemAudioCapture>.fromOpaque(capture).takeRetainedValue()
+    let semaphore = DispatchSemaphore(value: 0)
+
+    Task {
+        await instance.stop()
+        semaphore.signal()
+    }
+
+    semaphore.wait()
+}
@@ -0,0 +1,86 @@
+//! Criterion benchmarks for backend hot paths.
+//!
+//! Run with: `cargo bench --features bench --bench hot_paths`
+//! Compare against a saved baseline with `-- --save-baseline <name>` / `-- --baseline <name>`.
+
+use app_lib::bench;
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+
+fn composite_encode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("composite_encode");
+    group.sample_size(20);
+
+    // Single 1080p display (no resize) and a 2x 1440p layout (downscaled)
+    for (label, width, height) in [("1920x1080", 1920, 1080), ("5120x1440", 5120, 1440)] {
+        let frame = bench::synthetic_frame(width, height);
+        group.throughput(Throughput::Bytes((width * height * 4) as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(label), &frame, |b, frame| {
+            b.iter_batched(
+                || frame.clone(),
+                |frame| bench::encode_composite(frame).unwrap(),
+                BatchSize::LargeInput,
+            )
+        });
+    }
+
+    group.finish();
+}
+
+fn audio_chunk(c: &mut Criterion) {
+    let mut group = c.benchmark_group("audio_chunk");
+    let sample_rate = 48_000;
+    let samples = bench::synthetic_audio(120, sample_rate);
+    group.throughput(Throughput::Elements(samples.len() as u64));
+
+    group.bench_function("resample_120s_48k", |b| {
+        b.iter(|| bench::resample(black_box(&samples), sample_rate))
+    });
+    group.bench_function("wav_encode_120s_48k", |b| {
+        b.iter(|| bench::encode_wav_chunk(black_box(&samples), sample_rate).unwrap())
+    });
+    group.bench_function("flac_encode_120s_48k", |b| {
+        b.iter(|| bench::encode_flac_chunk(black_box(&samples), sample_rate).unwrap())
+    });
+    group.bench_function("opus_encode_120s_48k", |b| {
+        b.iter(|| bench::encode_opus_chunk(black_box(&samples), sample_rate).unwrap())
+    });
+    group.bench_function("mp3_encode_120s_48k", |b| {
+        b.iter(|| bench::encode_mp3_chunk(black_box(&samples), sample_rate).unwrap())
+    });
+
+    group.finish();
+}
+
+fn session_json(c: &mut Criterion) {
+    let mut group = c.benchmark_group("session_json");
+
+    for count in [100, 1_000] {
+        let json = bench::synthetic_sessions_json(count);
+        let sessions = bench::parse_sessions(&json).unwrap();
+        group.throughput(Throughput::Bytes(json.len() as u64));
+
+        group.bench_with_input(BenchmarkId::new("deserialize", count), &json, |b, json| {
+            b.iter(|| bench::parse_sessions(black_box(json)).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("serialize", count), &sessions, |b, sessions| {
+            b.iter(|| bench::serialize_sessions(black_box(sessions)).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("index", count), &sessions, |b, sessions| {
+            b.iter_batched(
+                || sessions.clone(),
+                bench::index_sessions,
+                BatchSize::LargeInput,
+            )
+        });
+
+        let index = bench::index_sessions(sessions.clone());
+        group.bench_with_input(BenchmarkId::new("search", count), &index, |b, index| {
+            b.iter(|| bench::search(black_box(index), "roadmap"))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, composite_encode, audio_chunk, session_json);
+criterion_main!(benches);
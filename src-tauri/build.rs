@@ -13,6 +13,12 @@ fn compile_swift_module() {
 
     println!("cargo:rerun-if-changed=ScreenRecorder/ScreenRecorder.swift");
     println!("cargo:rerun-if-changed=ScreenRecorder/ScreenRecorder.h");
+    println!("cargo:rerun-if-changed=ScreenRecorder/AutomationBridge.swift");
+    println!("cargo:rerun-if-changed=ScreenRecorder/CalendarBridge.swift");
+    println!("cargo:rerun-if-changed=ScreenRecorder/SystemAudioBridge.swift");
+    println!("cargo:rerun-if-changed=ScreenRecorder/OcrBridge.swift");
+    println!("cargo:rerun-if-changed=ScreenRecorder/NotificationBridge.swift");
+    println!("cargo:rerun-if-changed=ScreenRecorder/PermissionsBridge.swift");
 
     let out_dir = env::var("OUT_DIR").unwrap();
     let target = env::var("TARGET").unwrap();
@@ -36,6 +42,12 @@ fn compile_swift_module() {
             "-o", &format!("{}/libScreenRecorder.dylib", out_dir),
             "-emit-objc-header-path", &format!("{}/ScreenRecorder-Swift.h", out_dir),
             "ScreenRecorder/ScreenRecorder.swift",
+            "ScreenRecorder/AutomationBridge.swift",
+            "ScreenRecorder/CalendarBridge.swift",
+            "ScreenRecorder/SystemAudioBridge.swift",
+            "ScreenRecorder/OcrBridge.swift",
+            "ScreenRecorder/NotificationBridge.swift",
+            "ScreenRecorder/PermissionsBridge.swift",
             "-target", &format!("{}-apple-macosx12.3", arch),
             "-O", // Optimization
         ])
@@ -64,5 +76,13 @@ fn compile_swift_module() {
     println!("cargo:rustc-link-lib=framework=CoreMedia");
     println!("cargo:rustc-link-lib=framework=CoreGraphics");
     println!("cargo:rustc-link-lib=framework=CoreVideo");
+    println!("cargo:rustc-link-lib=framework=VideoToolbox");
+    println!("cargo:rustc-link-lib=framework=CoreImage");
+    println!("cargo:rustc-link-lib=framework=AppKit");
+    println!("cargo:rustc-link-lib=framework=EventKit");
+    println!("cargo:rustc-link-lib=framework=Vision");
+    println!("cargo:rustc-link-lib=framework=UserNotifications");
+    println!("cargo:rustc-link-lib=framework=ApplicationServices");
+    println!("cargo:rustc-link-lib=framework=IOKit");
     println!("cargo:rustc-link-lib=framework=Foundation");
 }
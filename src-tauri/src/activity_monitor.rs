@@ -4,8 +4,18 @@
  * Implements user activity tracking to determine screenshot timing:
  * - Tracks app switches, mouse clicks, keyboard events, window focus changes
  * - Rolling time window for metrics (configurable, default 60 seconds)
- * - Thread-safe state management using Arc<Mutex<T>>
+ * - Thread-safe state management using Arc<SafeState<T>>
  * - Event storage with timestamps for time-based filtering
+ * - Frontmost app timeline: each change of app or window title from
+ *   macos_events is kept (for `get_app_usage_timeline` over a recent window)
+ *   and queued for the active session's `appTimeline`; `app_usage` turns a
+ *   timeline into per-app durations
+ * - Browser URL timeline (opt-in, settings.activity.captureBrowserUrls): the
+ *   active tab reported by macos_events is filtered (blocked domains, query
+ *   strings) here and queued until the app drains it into the active session
+ * - Degraded reasons: counts macos_events can't measure (e.g. no Input
+ *   Monitoring permission, so no key presses or clicks) are listed in
+ *   `ActivityMetrics.degraded_reasons` rather than silently reading zero
  *
  * Phase 1: Stub implementation with manual event tracking
  * Phase 2 TODO: Integrate macOS NSWorkspace and CGEvent taps for automatic monitoring
@@ -13,9 +23,19 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+use crate::safe_state::SafeState;
+use crate::settings::ActivitySettings;
+
+/// Focus changes kept for recent-window queries (oldest dropped)
+const MAX_FOCUS_HISTORY: usize = 5000;
+
+/// Undelivered URL visits kept at most (oldest dropped)
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+const MAX_PENDING_URL_VISITS: usize = 500;
+
 /// Type of activity event being tracked
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum EventType {
@@ -41,6 +61,17 @@ impl ActivityEvent {
     }
 }
 
+/// Why some activity counts aren't being measured
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DegradedReason {
+    /// Input Monitoring not granted: no key presses or mouse clicks, only
+    /// app switches and focus changes
+    InputMonitoringDenied,
+    /// Automatic tracking is macOS only: counts come from record_* commands
+    UnsupportedPlatform,
+}
+
 /// Activity metrics for a given time window
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -50,6 +81,9 @@ pub struct ActivityMetrics {
     pub keyboard_events: u32,
     pub window_focus_changes: u32,
     pub timestamp: String, // ISO 8601 format
+    /// Empty when every count is measured
+    #[serde(default)]
+    pub degraded_reasons: Vec<DegradedReason>,
 }
 
 impl ActivityMetrics {
@@ -60,6 +94,7 @@ impl ActivityMetrics {
             keyboard_events: 0,
             window_focus_changes: 0,
             timestamp: Utc::now().to_rfc3339(),
+            degraded_reasons: Vec::new(),
         }
     }
 
@@ -79,6 +114,146 @@ impl ActivityMetrics {
     }
 }
 
+/// Frontmost app / window from `timestamp` until the next entry
+/// (session.appTimeline entry)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppFocus {
+    pub bundle_id: String,
+    pub app_name: String,
+    /// Empty when the window has no title or titles are off
+    pub window_title: String,
+    pub timestamp: String, // ISO 8601 format
+}
+
+/// Focus span with its length, clipped to the queried range
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppFocusSpan {
+    #[serde(flatten)]
+    pub focus: AppFocus,
+    pub duration_seconds: f64,
+}
+
+/// Total time in one app over the queried range
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppUsage {
+    pub bundle_id: String,
+    pub app_name: String,
+    pub seconds: f64,
+    /// Times the app came to the front
+    pub activations: u32,
+}
+
+/// Result of `get_app_usage_timeline`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppUsageTimeline {
+    pub from: String,
+    pub to: String,
+    pub spans: Vec<AppFocusSpan>,
+    /// Most used first
+    pub apps: Vec<AppUsage>,
+    pub total_seconds: f64,
+}
+
+/// Per-app time for `timeline` (any order) between `from` and `to`; the app
+/// in front at `from` counts from there, the last entry runs until `to`
+pub fn app_usage(timeline: &[AppFocus], from: DateTime<Utc>, to: DateTime<Utc>) -> AppUsageTimeline {
+    let mut entries: Vec<(DateTime<Utc>, &AppFocus)> = timeline
+        .iter()
+        .filter_map(|focus| {
+            DateTime::parse_from_rfc3339(&focus.timestamp)
+                .ok()
+                .map(|at| (at.with_timezone(&Utc), focus))
+        })
+        .collect();
+    entries.sort_by_key(|(at, _)| *at);
+
+    let mut spans = Vec::new();
+    let mut apps: Vec<AppUsage> = Vec::new();
+    for (index, (start, focus)) in entries.iter().enumerate() {
+        let end = entries.get(index + 1).map_or(to, |(next, _)| *next).min(to);
+        let start = (*start).max(from);
+        if end <= start {
+            continue;
+        }
+        let seconds = (end - start).num_milliseconds() as f64 / 1000.0;
+
+        let activated = spans
+            .last()
+            .map_or(true, |previous: &AppFocusSpan| previous.focus.bundle_id != focus.bundle_id);
+        match apps.iter_mut().find(|app| app.bundle_id == focus.bundle_id) {
+            Some(app) => {
+                app.seconds += seconds;
+                app.activations += activated as u32;
+            }
+            None => apps.push(AppUsage {
+                bundle_id: focus.bundle_id.clone(),
+                app_name: focus.app_name.clone(),
+                seconds,
+                activations: 1,
+            }),
+        }
+        spans.push(AppFocusSpan {
+            focus: AppFocus { timestamp: start.to_rfc3339(), ..(*focus).clone() },
+            duration_seconds: seconds,
+        });
+    }
+    apps.sort_by(|a, b| b.seconds.total_cmp(&a.seconds));
+
+    AppUsageTimeline {
+        from: from.to_rfc3339(),
+        to: to.to_rfc3339(),
+        total_seconds: spans.iter().map(|span| span.duration_seconds).sum(),
+        spans,
+        apps,
+    }
+}
+
+/// Active browser tab at a point in time (session.urlTimeline entry)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UrlVisit {
+    pub browser: String,
+    pub url: String,
+    pub title: String,
+    pub timestamp: String, // ISO 8601 format
+}
+
+/// Privacy filter for recorded URLs (from settings.activity)
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+struct UrlCapture {
+    enabled: bool,
+    blocked_domains: Vec<String>,
+    strip_query: bool,
+}
+
+impl UrlCapture {
+    /// The URL as it should be recorded, or None if it must not be
+    fn filter(&self, raw: &str) -> Option<String> {
+        let mut url = url::Url::parse(raw).ok()?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return None;
+        }
+        let host = url.host_str()?.to_ascii_lowercase();
+        let blocked = self.blocked_domains.iter().any(|domain| {
+            let domain = domain.trim().trim_start_matches("*.").to_ascii_lowercase();
+            !domain.is_empty() && (host == domain || host.ends_with(&format!(".{}", domain)))
+        });
+        if blocked {
+            return None;
+        }
+        if self.strip_query {
+            url.set_query(None);
+            url.set_fragment(None);
+        }
+        Some(url.to_string())
+    }
+}
+
 /// Monitoring state
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MonitoringState {
@@ -92,6 +267,20 @@ struct MonitorState {
     state: MonitoringState,
     window_seconds: u64,
     last_cleanup: Instant,
+    /// Last event, or when monitoring started
+    last_activity: Instant,
+    capture_window_titles: bool,
+    url_capture: UrlCapture,
+    /// URL visits not yet taken by `take_url_visits`
+    url_visits: Vec<UrlVisit>,
+    /// Last reported tab (raw URL), to record only changes
+    last_url: Option<String>,
+    /// Focus changes since monitoring started
+    focus_history: Vec<AppFocus>,
+    /// Focus changes not yet taken by `take_app_focus_changes`
+    focus_changes: Vec<AppFocus>,
+    /// Set by macos_events; kept across start/stop
+    degraded_reasons: Vec<DegradedReason>,
 }
 
 impl MonitorState {
@@ -101,12 +290,21 @@ impl MonitorState {
             state: MonitoringState::Stopped,
             window_seconds,
             last_cleanup: Instant::now(),
+            last_activity: Instant::now(),
+            capture_window_titles: true,
+            url_capture: UrlCapture::default(),
+            url_visits: Vec::new(),
+            last_url: None,
+            focus_history: Vec::new(),
+            focus_changes: Vec::new(),
+            degraded_reasons: Vec::new(),
         }
     }
 
     /// Add an event to the tracking buffer
     fn add_event(&mut self, event_type: EventType) {
         self.events.push(ActivityEvent::new(event_type));
+        self.last_activity = Instant::now();
 
         // Periodically cleanup old events (every 10 seconds)
         if self.last_cleanup.elapsed() > Duration::from_secs(10) {
@@ -138,15 +336,18 @@ impl MonitorState {
     fn clear(&mut self) {
         self.events.clear();
         self.last_cleanup = Instant::now();
+        self.last_activity = Instant::now();
+        // Pending URL visits are left for the final drain of the session
+        self.last_url = None;
     }
 }
 
 /// Global activity monitor with thread-safe state
 pub struct ActivityMonitor {
-    state: Arc<Mutex<MonitorState>>,
+    state: Arc<SafeState<MonitorState>>,
 }
 
-// SAFETY: ActivityMonitor uses Mutex for all internal state synchronization,
+// SAFETY: ActivityMonitor uses SafeState (Mutex) for all internal state synchronization,
 // making it safe to share across threads
 unsafe impl Send for ActivityMonitor {}
 unsafe impl Sync for ActivityMonitor {}
@@ -161,14 +362,13 @@ impl ActivityMonitor {
     pub fn with_window(window_seconds: u64) -> Self {
         println!("📊 [ACTIVITY MONITOR] Creating monitor with {}s window", window_seconds);
         Self {
-            state: Arc::new(Mutex::new(MonitorState::new(window_seconds))),
+            state: Arc::new(SafeState::new("activity_monitor", MonitorState::new(window_seconds))),
         }
     }
 
     /// Start monitoring user activity
     pub fn start_monitoring(&self) -> Result<(), String> {
-        let mut state = self.state.lock()
-            .map_err(|e| format!("Failed to lock state: {}", e))?;
+        let mut state = self.state.lock();
 
         if state.state == MonitoringState::Running {
             println!("⚠️  [ACTIVITY MONITOR] Already running");
@@ -178,6 +378,9 @@ impl ActivityMonitor {
         println!("📊 [ACTIVITY MONITOR] Starting monitoring");
         state.state = MonitoringState::Running;
         state.clear();
+        state.url_visits.clear();
+        state.focus_history.clear();
+        state.focus_changes.clear();
 
         // TODO Phase 2: Initialize macOS event monitoring
         // - Set up NSWorkspace notifications for app switching
@@ -194,8 +397,7 @@ impl ActivityMonitor {
 
     /// Stop monitoring user activity
     pub fn stop_monitoring(&self) -> Result<(), String> {
-        let mut state = self.state.lock()
-            .map_err(|e| format!("Failed to lock state: {}", e))?;
+        let mut state = self.state.lock();
 
         if state.state == MonitoringState::Stopped {
             println!("⚠️  [ACTIVITY MONITOR] Already stopped");
@@ -217,34 +419,41 @@ impl ActivityMonitor {
 
     /// Get activity metrics for the last N seconds
     pub fn get_metrics(&self, window_seconds: u64) -> ActivityMetrics {
-        let mut state = match self.state.lock() {
-            Ok(s) => s,
-            Err(_) => return ActivityMetrics::new(), // Return empty metrics on lock failure
-        };
+        let mut state = self.state.lock();
         let recent_events = state.get_recent_events(window_seconds);
+        // Verbose logging disabled: polled every second for activity-stats events
 
-        println!("📊 [ACTIVITY MONITOR] Getting metrics for last {}s: {} events",
-            window_seconds, recent_events.len());
-
-        ActivityMetrics::from_events(&recent_events)
+        let mut metrics = ActivityMetrics::from_events(&recent_events);
+        metrics.degraded_reasons = state.degraded_reasons.clone();
+        metrics
     }
 
     /// Get metrics using the monitor's default window
     pub fn get_current_metrics(&self) -> ActivityMetrics {
-        let window_seconds = match self.state.lock() {
-            Ok(state) => state.window_seconds,
-            Err(_) => return ActivityMetrics::new(), // Return empty metrics on lock failure
-        };
+        let window_seconds = self.state.lock().window_seconds;
 
         self.get_metrics(window_seconds)
     }
 
+    /// Mark whether `reason` currently applies to the metrics
+    pub fn set_degraded(&self, reason: DegradedReason, degraded: bool) {
+        let mut state = self.state.lock();
+        let listed = state.degraded_reasons.contains(&reason);
+        if degraded && !listed {
+            state.degraded_reasons.push(reason);
+        } else if !degraded && listed {
+            state.degraded_reasons.retain(|r| *r != reason);
+        }
+    }
+
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    pub fn is_degraded(&self, reason: DegradedReason) -> bool {
+        self.state.lock().degraded_reasons.contains(&reason)
+    }
+
     /// Record an app switch event
     pub fn increment_app_switch(&self) {
-        let mut state = match self.state.lock() {
-            Ok(s) => s,
-            Err(_) => return, // Silently fail on lock error
-        };
+        let mut state = self.state.lock();
 
         if state.state != MonitoringState::Running {
             return; // Ignore events when not monitoring
@@ -256,10 +465,7 @@ impl ActivityMonitor {
 
     /// Record a mouse click event
     pub fn increment_mouse_click(&self) {
-        let mut state = match self.state.lock() {
-            Ok(s) => s,
-            Err(_) => return, // Silently fail on lock error
-        };
+        let mut state = self.state.lock();
 
         if state.state != MonitoringState::Running {
             return; // Ignore events when not monitoring
@@ -271,10 +477,7 @@ impl ActivityMonitor {
 
     /// Record a keyboard event
     pub fn increment_keyboard_event(&self) {
-        let mut state = match self.state.lock() {
-            Ok(s) => s,
-            Err(_) => return, // Silently fail on lock error
-        };
+        let mut state = self.state.lock();
 
         if state.state != MonitoringState::Running {
             return; // Ignore events when not monitoring
@@ -286,10 +489,7 @@ impl ActivityMonitor {
 
     /// Record a window focus change event
     pub fn increment_window_focus(&self) {
-        let mut state = match self.state.lock() {
-            Ok(s) => s,
-            Err(_) => return, // Silently fail on lock error
-        };
+        let mut state = self.state.lock();
 
         if state.state != MonitoringState::Running {
             return; // Ignore events when not monitoring
@@ -299,11 +499,123 @@ impl ActivityMonitor {
         println!("📊 [ACTIVITY MONITOR] Window focus change recorded");
     }
 
+    /// Record the frontmost app and window title; unchanged focus is ignored
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    pub fn record_app_focus(&self, bundle_id: &str, app_name: &str, window_title: &str) {
+        let mut state = self.state.lock();
+
+        if state.state != MonitoringState::Running {
+            return;
+        }
+        let window_title = if state.capture_window_titles { window_title } else { "" };
+        let unchanged = state.focus_history.last().is_some_and(|last| {
+            last.bundle_id == bundle_id && last.window_title == window_title
+        });
+        if unchanged {
+            return;
+        }
+
+        let focus = AppFocus {
+            bundle_id: bundle_id.to_string(),
+            app_name: app_name.to_string(),
+            window_title: window_title.to_string(),
+            timestamp: Utc::now().to_rfc3339(),
+        };
+        if state.focus_history.len() >= MAX_FOCUS_HISTORY {
+            state.focus_history.remove(0);
+        }
+        if state.focus_changes.len() >= MAX_FOCUS_HISTORY {
+            state.focus_changes.remove(0);
+        }
+        state.focus_history.push(focus.clone());
+        state.focus_changes.push(focus);
+    }
+
+    /// Whether window titles should be looked up for the app timeline
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    pub fn is_capturing_window_titles(&self) -> bool {
+        self.state.lock().capture_window_titles
+    }
+
+    /// Drain focus changes recorded since the last call
+    pub fn take_app_focus_changes(&self) -> Vec<AppFocus> {
+        std::mem::take(&mut self.state.lock().focus_changes)
+    }
+
+    /// Focus changes since monitoring started (at most MAX_FOCUS_HISTORY)
+    pub fn focus_history(&self) -> Vec<AppFocus> {
+        self.state.lock().focus_history.clone()
+    }
+
+    /// Per-app usage over the last `window_seconds` of this monitoring run
+    pub fn app_usage(&self, window_seconds: u64) -> AppUsageTimeline {
+        let now = Utc::now();
+        app_usage(&self.focus_history(), now - chrono::Duration::seconds(window_seconds as i64), now)
+    }
+
+    /// Whether the browser tab should be reported (monitoring, and URL capture on)
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    pub fn is_capturing_urls(&self) -> bool {
+        let state = self.state.lock();
+        state.state == MonitoringState::Running && state.url_capture.enabled
+    }
+
+    /// Record the active browser tab; unchanged, blocked and non-web URLs are ignored
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    pub fn record_url_visit(&self, browser: &str, url: &str, title: &str) {
+        let mut state = self.state.lock();
+
+        if state.state != MonitoringState::Running || !state.url_capture.enabled {
+            return;
+        }
+        if state.last_url.as_deref() == Some(url) {
+            return;
+        }
+        state.last_url = Some(url.to_string());
+
+        let Some(url) = state.url_capture.filter(url) else {
+            return;
+        };
+        if state.url_visits.len() >= MAX_PENDING_URL_VISITS {
+            state.url_visits.remove(0);
+        }
+        state.url_visits.push(UrlVisit {
+            browser: browser.to_string(),
+            url,
+            title: title.to_string(),
+            timestamp: Utc::now().to_rfc3339(),
+        });
+    }
+
+    /// Drain URL visits recorded since the last call
+    pub fn take_url_visits(&self) -> Vec<UrlVisit> {
+        std::mem::take(&mut self.state.lock().url_visits)
+    }
+
+    /// Apply settings.activity capture and privacy options
+    pub fn set_capture(&self, settings: &ActivitySettings) {
+        let mut state = self.state.lock();
+        state.capture_window_titles = settings.capture_window_titles;
+        state.url_capture = UrlCapture {
+            enabled: settings.capture_browser_urls,
+            blocked_domains: settings.blocked_domains.clone(),
+            strip_query: settings.strip_url_query,
+        };
+        if !settings.capture_browser_urls {
+            state.url_visits.clear();
+            state.last_url = None;
+        }
+    }
+
+    /// Time since the last event (or since monitoring started); None when not monitoring
+    pub fn idle_duration(&self) -> Option<Duration> {
+        let state = self.state.lock();
+        (state.state == MonitoringState::Running).then(|| state.last_activity.elapsed())
+    }
+
     /// Get current monitoring state
     pub fn get_state(&self) -> MonitoringState {
-        self.state.lock()
-            .map(|s| s.state)
-            .unwrap_or(MonitoringState::Stopped)
+        self.state.lock().state
     }
 
     /// Check if currently monitoring
@@ -313,25 +625,19 @@ impl ActivityMonitor {
 
     /// Update the time window for metrics
     pub fn set_window(&self, window_seconds: u64) {
-        if let Ok(mut state) = self.state.lock() {
-            state.window_seconds = window_seconds;
-            println!("📊 [ACTIVITY MONITOR] Window updated to {}s", window_seconds);
-        }
+        self.state.lock().window_seconds = window_seconds;
+        println!("📊 [ACTIVITY MONITOR] Window updated to {}s", window_seconds);
     }
 
     /// Get the current time window setting
     pub fn get_window(&self) -> u64 {
-        self.state.lock()
-            .map(|s| s.window_seconds)
-            .unwrap_or(60) // Default to 60 seconds on error
+        self.state.lock().window_seconds
     }
 
     /// Get total event count (for debugging/testing)
     #[allow(dead_code)]
     pub fn get_event_count(&self) -> usize {
-        self.state.lock()
-            .map(|s| s.events.len())
-            .unwrap_or(0)
+        self.state.lock().events.len()
     }
 }
 
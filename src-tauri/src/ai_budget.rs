@@ -0,0 +1,330 @@
+/**
+ * AI Budget Module
+ *
+ * Per-provider cost tracking and monthly spending budgets:
+ * - Estimates USD cost from token/audio usage reported by each API call
+ * - Tracks spend per provider per calendar month (persisted in ai_budget.json)
+ * - Once a provider's monthly budget is exceeded, background requests
+ *   (e.g. enrichment) are refused; user-initiated requests still go through
+ * - Override (for the rest of the month) and reset commands
+ */
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_store::StoreExt;
+
+use crate::ai_types::RequestPriority;
+use crate::command_metrics;
+
+const BUDGET_STORE: &str = "ai_budget.json";
+
+/// AI providers with separately tracked spend
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AiProvider {
+    Claude,
+    OpenAI,
+    Gemini,
+}
+
+impl AiProvider {
+    fn key(&self) -> &'static str {
+        match self {
+            AiProvider::Claude => "claude",
+            AiProvider::OpenAI => "openai",
+            AiProvider::Gemini => "gemini",
+        }
+    }
+
+    fn display_name(&self) -> &'static str {
+        match self {
+            AiProvider::Claude => "Claude",
+            AiProvider::OpenAI => "OpenAI",
+            AiProvider::Gemini => "Gemini",
+        }
+    }
+
+    const ALL: [AiProvider; 3] = [AiProvider::Claude, AiProvider::OpenAI, AiProvider::Gemini];
+}
+
+/// Usage of a single request, used for cost estimation
+#[derive(Debug, Clone, Default)]
+pub struct UsageRecord {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_creation_input_tokens: u64,
+    pub cache_read_input_tokens: u64,
+    /// Audio input tokens (gpt-4o-audio)
+    pub audio_input_tokens: u64,
+    /// Audio minutes (Whisper)
+    pub audio_minutes: f64,
+}
+
+/// Estimate cost in USD for a request (list prices per million tokens)
+pub fn estimate_cost(model: &str, usage: &UsageRecord) -> f64 {
+    let model = model.to_lowercase();
+    let per_million = |tokens: u64, price: f64| tokens as f64 / 1_000_000.0 * price;
+
+    if model.starts_with("claude") {
+        let (input, output) = if model.contains("opus") {
+            (15.0, 75.0)
+        } else if model.contains("haiku") {
+            (0.8, 4.0)
+        } else {
+            (3.0, 15.0) // Sonnet pricing as the default
+        };
+        per_million(usage.input_tokens, input)
+            + per_million(usage.output_tokens, output)
+            + per_million(usage.cache_creation_input_tokens, input * 1.25)
+            + per_million(usage.cache_read_input_tokens, input * 0.1)
+    } else if model.starts_with("gemini") {
+        let (input, output) = if model.contains("pro") {
+            (1.25, 10.0)
+        } else if model.contains("lite") {
+            (0.1, 0.4)
+        } else {
+            (0.3, 2.5) // Flash pricing as the default
+        };
+        per_million(usage.input_tokens, input) + per_million(usage.output_tokens, output)
+    } else if model.starts_with("whisper") {
+        usage.audio_minutes * 0.006
+    } else if model.ends_with("-transcribe") {
+        usage.audio_minutes * if model.contains("mini") { 0.003 } else { 0.006 }
+    } else if model.contains("audio") {
+        let text_input = usage.input_tokens.saturating_sub(usage.audio_input_tokens);
+        per_million(text_input, 2.5)
+            + per_million(usage.audio_input_tokens, 40.0)
+            + per_million(usage.output_tokens, 10.0)
+    } else {
+        per_million(usage.input_tokens, 2.5) + per_million(usage.output_tokens, 10.0)
+    }
+}
+
+/// Spend accumulated for one provider in one month
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderSpend {
+    pub month: String,
+    pub cost_usd: f64,
+    pub requests: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct BudgetState {
+    /// Monthly budget in USD per provider
+    budgets: HashMap<String, f64>,
+    spend: HashMap<String, ProviderSpend>,
+    /// Provider -> month for which the budget has been overridden
+    overrides: HashMap<String, String>,
+}
+
+/// Budget status reported to the frontend
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderBudgetStatus {
+    pub provider: AiProvider,
+    pub month: String,
+    pub monthly_budget_usd: Option<f64>,
+    pub spend: ProviderSpend,
+    pub exceeded: bool,
+    pub overridden: bool,
+}
+
+fn current_month() -> String {
+    chrono::Utc::now().format("%Y-%m").to_string()
+}
+
+/// Managed budget state
+pub struct BudgetManager {
+    state: Mutex<BudgetState>,
+}
+
+impl BudgetManager {
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(BudgetState::default()),
+        }
+    }
+
+    /// Load persisted budgets and spend
+    pub fn load(&self, app: &AppHandle) -> Result<(), String> {
+        let store = app.store(BUDGET_STORE)
+            .map_err(|e| format!("Failed to access store: {}", e))?;
+
+        let loaded: BudgetState = store
+            .get("state")
+            .and_then(|value| serde_json::from_value(value).ok())
+            .unwrap_or_default();
+
+        *self.state.lock()
+            .map_err(|e| format!("Failed to lock budget state: {}", e))? = loaded;
+        Ok(())
+    }
+
+    fn persist(&self, app: &AppHandle, state: &BudgetState) -> Result<(), String> {
+        let store = app.store(BUDGET_STORE)
+            .map_err(|e| format!("Failed to access store: {}", e))?;
+        store.set("state", serde_json::json!(state));
+        store.save().map_err(|e| format!("Failed to save store: {}", e))
+    }
+
+    fn status_for(state: &BudgetState, provider: AiProvider) -> ProviderBudgetStatus {
+        let month = current_month();
+        let spend = state
+            .spend
+            .get(provider.key())
+            .filter(|s| s.month == month)
+            .cloned()
+            .unwrap_or_else(|| ProviderSpend { month: month.clone(), ..Default::default() });
+        let budget = state.budgets.get(provider.key()).copied();
+        let overridden = state.overrides.get(provider.key()) == Some(&month);
+
+        ProviderBudgetStatus {
+            provider,
+            month,
+            monthly_budget_usd: budget,
+            exceeded: budget.is_some_and(|b| spend.cost_usd >= b),
+            spend,
+            overridden,
+        }
+    }
+
+    /// Refuse background requests once the provider's budget is exceeded
+    pub fn check(&self, provider: AiProvider, priority: RequestPriority) -> Result<(), String> {
+        if priority == RequestPriority::User {
+            return Ok(());
+        }
+
+        let state = self.state.lock()
+            .map_err(|e| format!("Failed to lock budget state: {}", e))?;
+        let status = Self::status_for(&state, provider);
+
+        if status.exceeded && !status.overridden {
+            return Err(format!(
+                "Monthly {} budget of ${:.2} exceeded (${:.2} spent). Background AI requests are paused until next month or until the budget is overridden in Settings.",
+                provider.display_name(),
+                status.monthly_budget_usd.unwrap_or(0.0),
+                status.spend.cost_usd
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Record usage for a completed request and emit `ai-budget-exceeded` on crossing
+    pub fn record(&self, app: &AppHandle, provider: AiProvider, model: &str, usage: &UsageRecord) {
+        let cost = estimate_cost(model, usage);
+
+        let mut state = match self.state.lock() {
+            Ok(s) => s,
+            Err(_) => return,
+        };
+
+        let month = current_month();
+        let was_exceeded = Self::status_for(&state, provider).exceeded;
+
+        let spend = state.spend.entry(provider.key().to_string()).or_default();
+        if spend.month != month {
+            *spend = ProviderSpend { month, ..Default::default() };
+        }
+        spend.cost_usd += cost;
+        spend.requests += 1;
+        spend.input_tokens += usage.input_tokens;
+        spend.output_tokens += usage.output_tokens;
+
+        let status = Self::status_for(&state, provider);
+        if status.exceeded && !was_exceeded {
+            println!("💸 [AI BUDGET] {} monthly budget exceeded (${:.2})", provider.display_name(), status.spend.cost_usd);
+            let _ = app.emit("ai-budget-exceeded", &status);
+        }
+
+        if let Err(e) = self.persist(app, &state) {
+            eprintln!("❌ [AI BUDGET] Failed to persist spend: {}", e);
+        }
+    }
+
+    fn update<F: FnOnce(&mut BudgetState)>(&self, app: &AppHandle, f: F) -> Result<(), String> {
+        let mut state = self.state.lock()
+            .map_err(|e| format!("Failed to lock budget state: {}", e))?;
+        f(&mut state);
+        self.persist(app, &state)
+    }
+
+    pub fn statuses(&self) -> Result<Vec<ProviderBudgetStatus>, String> {
+        let state = self.state.lock()
+            .map_err(|e| format!("Failed to lock budget state: {}", e))?;
+        Ok(AiProvider::ALL.iter().map(|p| Self::status_for(&state, *p)).collect())
+    }
+}
+
+impl Default for BudgetManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Tauri command to get this month's spend and budget status per provider
+#[tauri::command]
+pub fn get_ai_spending(
+    budget: tauri::State<Arc<BudgetManager>>,
+) -> Result<Vec<ProviderBudgetStatus>, String> {
+    command_metrics::track("get_ai_spending", || {
+        budget.statuses()
+    })
+}
+
+/// Tauri command to set (or clear, with None) a provider's monthly budget in USD
+#[tauri::command]
+pub fn set_ai_budget(
+    app: AppHandle,
+    budget: tauri::State<Arc<BudgetManager>>,
+    provider: AiProvider,
+    monthly_usd: Option<f64>,
+) -> Result<(), String> {
+    command_metrics::track("set_ai_budget", || {
+        if let Some(amount) = monthly_usd {
+            if !amount.is_finite() || amount < 0.0 {
+                return Err("Budget must be a positive amount".to_string());
+            }
+        }
+
+        budget.update(&app, |state| match monthly_usd {
+            Some(amount) => { state.budgets.insert(provider.key().to_string(), amount); }
+            None => { state.budgets.remove(provider.key()); }
+        })
+    })
+}
+
+/// Tauri command to allow background requests for the rest of this month
+#[tauri::command]
+pub fn override_ai_budget(
+    app: AppHandle,
+    budget: tauri::State<Arc<BudgetManager>>,
+    provider: AiProvider,
+) -> Result<(), String> {
+    command_metrics::track("override_ai_budget", || {
+        budget.update(&app, |state| {
+            state.overrides.insert(provider.key().to_string(), current_month());
+        })
+    })
+}
+
+/// Tauri command to reset this month's tracked spend (and any override)
+#[tauri::command]
+pub fn reset_ai_spending(
+    app: AppHandle,
+    budget: tauri::State<Arc<BudgetManager>>,
+    provider: AiProvider,
+) -> Result<(), String> {
+    command_metrics::track("reset_ai_spending", || {
+        budget.update(&app, |state| {
+            state.spend.remove(provider.key());
+            state.overrides.remove(provider.key());
+        })
+    })
+}
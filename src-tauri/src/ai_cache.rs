@@ -0,0 +1,287 @@
+/**
+ * AI Cache Module
+ *
+ * Response cache in front of `claude_chat_completion_vision`, so re-analyzing
+ * identical or near-identical screenshots doesn't spend tokens again:
+ * - Exact match: SHA-256 of the whole request (model, prompt, images)
+ * - Near match: same prompt (SHA-256 with image data left out) and every
+ *   image within NEAR_DUPLICATE_BITS of a cached one by 64-bit dHash
+ *   (e.g. a screenshot where only the clock changed)
+ * - Persisted in <app data>/ai_cache.json, loaded on first use; entries
+ *   expire after MAX_AGE_DAYS and the least recently used are evicted
+ *   beyond MAX_ENTRIES
+ * - `forceRefresh` on the vision command bypasses the lookup (the fresh
+ *   response replaces the cached one); `clear_ai_cache` empties the cache
+ */
+
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager, State};
+
+use crate::ai_types::{ClaudeContentBlock, ClaudeMessage, ClaudeMessageContent};
+use crate::command_metrics;
+use crate::safe_state::SafeState;
+
+const CACHE_FILE: &str = "ai_cache.json";
+const MAX_ENTRIES: usize = 500;
+const MAX_AGE_DAYS: i64 = 30;
+
+/// Largest dHash distance (of 64 bits) at which two images count as the same
+const NEAR_DUPLICATE_BITS: u32 = 4;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CacheEntry {
+    key: String,
+    prompt_key: String,
+    /// dHash per image in request order (empty when an image couldn't be decoded)
+    image_hashes: Vec<u64>,
+    response: serde_json::Value,
+    /// Unix millis
+    created_at: i64,
+    last_used: i64,
+    hits: u64,
+}
+
+/// Cache identity of a request
+#[derive(Debug, Clone)]
+pub struct RequestFingerprint {
+    key: String,
+    prompt_key: String,
+    image_hashes: Vec<u64>,
+}
+
+/// Entries created before this (Unix millis) are expired
+fn expiry_cutoff() -> i64 {
+    (chrono::Utc::now() - chrono::Duration::days(MAX_AGE_DAYS)).timestamp_millis()
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// 64-bit difference hash: brightness gradients of a 9x8 grayscale thumbnail
+fn dhash(image_bytes: &[u8]) -> Option<u64> {
+    let thumbnail = image::load_from_memory(image_bytes)
+        .ok()?
+        .resize_exact(9, 8, image::imageops::FilterType::Triangle)
+        .to_luma8();
+
+    let mut hash = 0u64;
+    for y in 0..8 {
+        for x in 0..8 {
+            let left = thumbnail.get_pixel(x, y)[0];
+            let right = thumbnail.get_pixel(x + 1, y)[0];
+            hash = (hash << 1) | (left < right) as u64;
+        }
+    }
+    Some(hash)
+}
+
+/// Fingerprint a vision request; None when it has no images (not cached).
+/// Decodes every image, so call it off the async runtime.
+pub fn fingerprint(
+    model: &str,
+    max_tokens: u32,
+    messages: &[ClaudeMessage],
+    system: Option<&str>,
+    temperature: Option<f32>,
+) -> Option<RequestFingerprint> {
+    let mut image_data: Vec<&str> = Vec::new();
+    let prompt_messages: Vec<serde_json::Value> = messages
+        .iter()
+        .map(|message| {
+            let content = match &message.content {
+                ClaudeMessageContent::Text(text) => serde_json::json!(text),
+                ClaudeMessageContent::Blocks(blocks) => blocks
+                    .iter()
+                    .map(|block| match block {
+                        ClaudeContentBlock::Text { text, .. } => serde_json::json!({ "text": text }),
+                        ClaudeContentBlock::Image { source, .. } => {
+                            image_data.push(&source.data);
+                            serde_json::json!({ "image": source.media_type })
+                        }
+                    })
+                    .collect(),
+            };
+            serde_json::json!({ "role": message.role, "content": content })
+        })
+        .collect();
+
+    if image_data.is_empty() {
+        return None;
+    }
+
+    let prompt = serde_json::json!({
+        "model": model,
+        "maxTokens": max_tokens,
+        "system": system,
+        "temperature": temperature,
+        "messages": prompt_messages,
+    })
+    .to_string();
+
+    let mut exact = Sha256::new();
+    exact.update(prompt.as_bytes());
+    for data in &image_data {
+        exact.update(data.as_bytes());
+    }
+
+    let image_hashes: Option<Vec<u64>> = image_data
+        .iter()
+        .map(|data| {
+            let bytes = base64::engine::general_purpose::STANDARD.decode(data).ok()?;
+            dhash(&bytes)
+        })
+        .collect();
+
+    Some(RequestFingerprint {
+        key: exact.finalize().iter().map(|b| format!("{:02x}", b)).collect(),
+        prompt_key: sha256_hex(prompt.as_bytes()),
+        image_hashes: image_hashes.unwrap_or_default(),
+    })
+}
+
+/// Managed cache state (loaded from disk on first use)
+pub struct AiCache {
+    entries: SafeState<Option<Vec<CacheEntry>>>,
+}
+
+impl AiCache {
+    pub fn new() -> Self {
+        Self {
+            entries: SafeState::new("ai_cache", None),
+        }
+    }
+
+    fn path(app: &AppHandle) -> Result<PathBuf, String> {
+        Ok(app
+            .path()
+            .app_data_dir()
+            .map_err(|e| format!("Failed to get app data dir: {}", e))?
+            .join(CACHE_FILE))
+    }
+
+    fn with_entries<T>(&self, app: &AppHandle, operation: impl FnOnce(&mut Vec<CacheEntry>) -> T) -> T {
+        let mut entries = self.entries.lock();
+        let entries = entries.get_or_insert_with(|| {
+            let loaded: Vec<CacheEntry> = Self::path(app)
+                .ok()
+                .and_then(|path| std::fs::read_to_string(path).ok())
+                .and_then(|content| serde_json::from_str(&content).ok())
+                .unwrap_or_default();
+            let cutoff = expiry_cutoff();
+            loaded.into_iter().filter(|entry| entry.created_at > cutoff).collect()
+        });
+        operation(entries)
+    }
+
+    fn save(app: &AppHandle, entries: &[CacheEntry]) -> Result<(), String> {
+        let path = Self::path(app)?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create app data dir: {}", e))?;
+        }
+        let content = serde_json::to_string(entries)
+            .map_err(|e| format!("Failed to serialize AI cache: {}", e))?;
+        let temp_path = path.with_extension("json.tmp");
+        std::fs::write(&temp_path, content)
+            .map_err(|e| format!("Failed to write AI cache: {}", e))?;
+        std::fs::rename(&temp_path, &path)
+            .map_err(|e| format!("Failed to write AI cache: {}", e))
+    }
+
+    /// Cached response for an exact or near-identical request
+    pub fn lookup(&self, app: &AppHandle, fingerprint: &RequestFingerprint) -> Option<serde_json::Value> {
+        self.with_entries(app, |entries| {
+            let cutoff = expiry_cutoff();
+            let near_distance = |entry: &CacheEntry| -> Option<u32> {
+                if entry.prompt_key != fingerprint.prompt_key
+                    || fingerprint.image_hashes.is_empty()
+                    || entry.image_hashes.len() != fingerprint.image_hashes.len()
+                {
+                    return None;
+                }
+                let distances: Vec<u32> = entry
+                    .image_hashes
+                    .iter()
+                    .zip(&fingerprint.image_hashes)
+                    .map(|(a, b)| (a ^ b).count_ones())
+                    .collect();
+                distances
+                    .iter()
+                    .all(|d| *d <= NEAR_DUPLICATE_BITS)
+                    .then(|| distances.iter().sum())
+            };
+
+            let entry = entries
+                .iter_mut()
+                .filter(|entry| entry.created_at > cutoff)
+                .filter_map(|entry| {
+                    let distance = if entry.key == fingerprint.key { Some(0) } else { near_distance(entry) };
+                    distance.map(|distance| (distance, entry))
+                })
+                .min_by_key(|(distance, _)| *distance)
+                .map(|(_, entry)| entry)?;
+
+            entry.hits += 1;
+            entry.last_used = chrono::Utc::now().timestamp_millis();
+            Some(entry.response.clone())
+        })
+    }
+
+    /// Cache a response, replacing any entry with the same exact key
+    pub fn store(&self, app: &AppHandle, fingerprint: RequestFingerprint, response: serde_json::Value) -> Result<(), String> {
+        self.with_entries(app, |entries| {
+            let now = chrono::Utc::now().timestamp_millis();
+            entries.retain(|entry| entry.key != fingerprint.key);
+            entries.push(CacheEntry {
+                key: fingerprint.key,
+                prompt_key: fingerprint.prompt_key,
+                image_hashes: fingerprint.image_hashes,
+                response,
+                created_at: now,
+                last_used: now,
+                hits: 0,
+            });
+
+            if entries.len() > MAX_ENTRIES {
+                entries.sort_by_key(|entry| std::cmp::Reverse(entry.last_used));
+                entries.truncate(MAX_ENTRIES);
+            }
+
+            Self::save(app, entries)
+        })
+    }
+
+    pub fn clear(&self, app: &AppHandle) -> Result<usize, String> {
+        self.with_entries(app, |entries| {
+            let cleared = entries.len();
+            entries.clear();
+            Self::save(app, entries)?;
+            Ok(cleared)
+        })
+    }
+}
+
+/// Tauri command to empty the AI response cache; returns the number of
+/// entries removed
+#[tauri::command]
+pub async fn clear_ai_cache(
+    app: AppHandle,
+    cache: State<'_, Arc<AiCache>>,
+) -> Result<usize, String> {
+    command_metrics::track_async("clear_ai_cache", async move {
+        let cache = cache.inner().clone();
+        tokio::task::spawn_blocking(move || {
+            let cleared = cache.clear(&app)?;
+            println!("💾 [AI CACHE] Cleared {} entries", cleared);
+            Ok(cleared)
+        })
+        .await
+        .map_err(|e| format!("AI cache task failed: {}", e))?
+    }).await
+}
@@ -0,0 +1,179 @@
+/**
+ * AI HTTP Client Module
+ *
+ * Shared reqwest client for the Claude, OpenAI, Gemini and Ollama commands.
+ * Built lazily on the first AI request (not during startup) and reused
+ * afterwards so connections and TLS sessions are pooled across requests.
+ *
+ * `send_with_retry` wraps cloud API calls:
+ * - At most settings.ai.maxConcurrentRequests requests in flight; the rest wait
+ * - 429s honor `retry-after` / `retry-after-ms` (up to MAX_RETRY_AFTER)
+ * - 5xx and network errors retry with jittered exponential backoff
+ * - Each retry emits `ai-request-retrying` so the UI can show status
+ */
+
+use rand::Rng;
+use reqwest::{Client, RequestBuilder, Response};
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::Notify;
+
+use crate::settings::SettingsManager;
+use crate::startup_profile::LazyInit;
+
+static AI_HTTP_CLIENT: LazyInit<Client> = LazyInit::new("ai-http-client");
+
+/// Longest server-requested wait we sit out; beyond it the 429 is returned
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// Backoff before the first retry (doubles per attempt)
+const BASE_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Shared client for AI provider APIs (cheap to clone)
+pub fn http_client() -> Result<Client, String> {
+    AI_HTTP_CLIENT.get_or_try_init(|| {
+        Client::builder()
+            .timeout(Duration::from_secs(1200))         // 20 min total timeout (large canvas generation, audio analysis)
+            .connect_timeout(Duration::from_secs(30))   // 30 sec to establish connection
+            .read_timeout(Duration::from_secs(900))     // 15 min to read response (large sessions can take 5-10 min)
+            .build()
+            .map_err(|e| format!("Failed to build HTTP client: {}", e))
+    })
+}
+
+/// Concurrency limit whose size can change at runtime (read from settings
+/// on every acquire)
+struct RequestLimiter {
+    in_flight: Mutex<u32>,
+    released: Notify,
+}
+
+static LIMITER: RequestLimiter = RequestLimiter {
+    in_flight: Mutex::new(0),
+    released: Notify::const_new(),
+};
+
+struct RequestPermit;
+
+impl Drop for RequestPermit {
+    fn drop(&mut self) {
+        if let Ok(mut in_flight) = LIMITER.in_flight.lock() {
+            *in_flight = in_flight.saturating_sub(1);
+        }
+        LIMITER.released.notify_waiters();
+    }
+}
+
+async fn acquire(limit: u32) -> RequestPermit {
+    loop {
+        // Register for wakeups before checking, so a release in between isn't missed
+        let released = LIMITER.released.notified();
+        tokio::pin!(released);
+        released.as_mut().enable();
+
+        {
+            let mut in_flight = LIMITER.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+            if *in_flight < limit.max(1) {
+                *in_flight += 1;
+                return RequestPermit;
+            }
+        }
+        released.await;
+    }
+}
+
+/// Payload of `ai-request-retrying`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RetryEvent<'a> {
+    provider: &'a str,
+    /// Attempt about to be made (2 = first retry)
+    attempt: u32,
+    max_attempts: u32,
+    delay_ms: u64,
+    /// "rate_limited", "server_error" or "network_error"
+    reason: &'a str,
+    status: Option<u16>,
+}
+
+/// Server-requested wait from `retry-after-ms` / `retry-after` (seconds or HTTP date)
+fn retry_after(response: &Response) -> Option<Duration> {
+    let header = |name: &str| response.headers().get(name)?.to_str().ok().map(|v| v.trim().to_string());
+
+    if let Some(ms) = header("retry-after-ms").and_then(|v| v.parse::<f64>().ok()) {
+        return Some(Duration::from_secs_f64(ms.max(0.0) / 1000.0));
+    }
+    let value = header("retry-after")?;
+    if let Ok(seconds) = value.parse::<f64>() {
+        return Some(Duration::from_secs_f64(seconds.max(0.0)));
+    }
+    let date = chrono::DateTime::parse_from_rfc2822(&value).ok()?;
+    Some((date.with_timezone(&chrono::Utc) - chrono::Utc::now()).to_std().unwrap_or_default())
+}
+
+/// Jittered exponential backoff before retry number `retry` (1-based)
+fn backoff(retry: u32) -> Duration {
+    let exponential = BASE_BACKOFF.saturating_mul(2u32.saturating_pow(retry.saturating_sub(1))).min(MAX_BACKOFF);
+    exponential.mul_f64(rand::thread_rng().gen_range(0.5..=1.0))
+}
+
+/// Send a cloud AI request with concurrency limiting and retries.
+///
+/// `build` is called once per attempt. The final response is returned
+/// whatever its status (callers map 401/429/5xx to their own messages);
+/// only network errors that outlast the retries become `Err`.
+pub async fn send_with_retry<F>(app: &AppHandle, provider: &str, build: F) -> Result<Response, String>
+where
+    F: Fn(&Client) -> RequestBuilder,
+{
+    let client = http_client()?;
+    let settings = app.state::<Arc<SettingsManager>>().get().ai;
+    let max_attempts = settings.max_retries + 1;
+
+    for attempt in 1..=max_attempts {
+        let result = {
+            let _permit = acquire(settings.max_concurrent_requests).await;
+            build(&client).send().await
+        };
+        let is_last = attempt == max_attempts;
+
+        let (delay, reason, status) = match result {
+            Ok(response) => {
+                let status = response.status();
+                if status.as_u16() == 429 {
+                    match retry_after(&response) {
+                        Some(wait) if wait > MAX_RETRY_AFTER => return Ok(response),
+                        _ if is_last => return Ok(response),
+                        Some(wait) => (wait, "rate_limited", Some(429)),
+                        None => (backoff(attempt), "rate_limited", Some(429)),
+                    }
+                } else if status.is_server_error() && !is_last {
+                    (backoff(attempt), "server_error", Some(status.as_u16()))
+                } else {
+                    return Ok(response);
+                }
+            }
+            Err(e) if is_last => return Err(format!("Network error: {}", e)),
+            Err(_) => (backoff(attempt), "network_error", None),
+        };
+
+        println!(
+            "🔁 [AI CLIENT] {} request {} (status {:?}), retrying in {}ms (attempt {}/{})",
+            provider, reason, status, delay.as_millis(), attempt + 1, max_attempts
+        );
+        let _ = app.emit("ai-request-retrying", RetryEvent {
+            provider,
+            attempt: attempt + 1,
+            max_attempts,
+            delay_ms: delay.as_millis() as u64,
+            reason,
+            status,
+        });
+        tokio::time::sleep(delay).await;
+    }
+
+    unreachable!("the last attempt always returns")
+}
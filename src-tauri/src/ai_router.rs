@@ -0,0 +1,497 @@
+/**
+ * AI Router Module
+ *
+ * One chat completion command across providers, so the frontend doesn't
+ * hard-code Claude vs. OpenAI vs. Gemini vs. Ollama:
+ * - `Provider` trait implemented by thin adapters over claude_api, openai_api,
+ *   gemini_api and ollama_api; requests use Claude-shaped messages
+ * - `ai_chat_completion` picks the provider from the request or
+ *   settings.ai.provider, with the model from settings.ai.models (or the
+ *   active profile's model choices, profiles.rs) unless the request names one
+ * - Rate-limited requests fall back through settings.ai.fallbackProviders
+ *   (fallbacks without an API key are skipped)
+ * - Per-provider usage accounting (requests, failures, rate limits, tokens,
+ *   latency) via `get_ai_provider_usage`; costs stay in ai_budget.rs
+ */
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, State};
+
+use crate::ai_types::*;
+use crate::api_keys;
+use crate::claude_api;
+use crate::command_metrics;
+use crate::gemini_api;
+use crate::ollama_api;
+use crate::openai_api;
+use crate::profiles;
+use crate::redaction;
+use crate::safe_state::SafeState;
+use crate::settings::{AiSettings, SettingsManager};
+
+/// Providers reachable through the router
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProviderId {
+    Claude,
+    OpenAI,
+    Gemini,
+    Ollama,
+}
+
+impl ProviderId {
+    pub const ALL: [ProviderId; 4] = [ProviderId::Claude, ProviderId::OpenAI, ProviderId::Gemini, ProviderId::Ollama];
+
+    fn display_name(&self) -> &'static str {
+        match self {
+            ProviderId::Claude => "Claude",
+            ProviderId::OpenAI => "OpenAI",
+            ProviderId::Gemini => "Gemini",
+            ProviderId::Ollama => "Ollama",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AiChatRequest {
+    pub messages: Vec<ClaudeMessage>,
+    pub system: Option<String>,
+    pub max_tokens: u32,
+    pub temperature: Option<f32>,
+    #[serde(default)]
+    pub priority: RequestPriority,
+    /// Overrides settings.ai.provider
+    pub provider: Option<ProviderId>,
+    /// Model for the selected provider (fallbacks use their settings model)
+    pub model: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AiUsage {
+    pub input_tokens: u32,
+    pub output_tokens: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AiChatResponse {
+    /// Provider that produced the response
+    pub provider: ProviderId,
+    pub model: String,
+    pub text: String,
+    pub stop_reason: Option<String>,
+    pub usage: AiUsage,
+    /// Providers that were rate limited before this one answered
+    pub fallback_from: Vec<ProviderId>,
+}
+
+/// Usage accounting for one provider since app start
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderUsage {
+    pub provider: ProviderId,
+    pub requests: u64,
+    pub failures: u64,
+    pub rate_limited: u64,
+    /// Requests answered by this provider after another was rate limited
+    pub fallbacks_served: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub total_latency_ms: u64,
+    pub last_error: Option<String>,
+}
+
+impl ProviderUsage {
+    fn new(provider: ProviderId) -> Self {
+        Self {
+            provider,
+            requests: 0,
+            failures: 0,
+            rate_limited: 0,
+            fallbacks_served: 0,
+            input_tokens: 0,
+            output_tokens: 0,
+            total_latency_ms: 0,
+            last_error: None,
+        }
+    }
+}
+
+type ChatFuture<'a> = Pin<Box<dyn Future<Output = Result<AiChatResponse, ProviderError>> + Send + 'a>>;
+
+/// A chat completion backend
+pub trait Provider: Send + Sync {
+    fn id(&self) -> ProviderId;
+
+    /// Whether the provider can take requests (e.g. has an API key)
+    fn is_configured(&self, app: &AppHandle) -> bool;
+
+    fn chat<'a>(&'a self, app: &'a AppHandle, request: &'a AiChatRequest, model: &'a str) -> ChatFuture<'a>;
+}
+
+fn has_api_key(app: &AppHandle, key_name: &str) -> bool {
+    matches!(api_keys::get_api_key(app, key_name), Ok(Some(_)))
+}
+
+/// Text and base64 images of a message, in order
+fn message_parts(content: &ClaudeMessageContent) -> (Vec<&str>, Vec<&ClaudeImageSource>) {
+    match content {
+        ClaudeMessageContent::Text(text) => (vec![text.as_str()], vec![]),
+        ClaudeMessageContent::Blocks(blocks) => {
+            let mut texts = Vec::new();
+            let mut images = Vec::new();
+            for block in blocks {
+                match block {
+                    ClaudeContentBlock::Text { text, .. } => texts.push(text.as_str()),
+                    ClaudeContentBlock::Image { source, .. } => images.push(source),
+                }
+            }
+            (texts, images)
+        }
+    }
+}
+
+struct ClaudeProvider;
+
+impl Provider for ClaudeProvider {
+    fn id(&self) -> ProviderId {
+        ProviderId::Claude
+    }
+
+    fn is_configured(&self, app: &AppHandle) -> bool {
+        has_api_key(app, "claude_api_key")
+    }
+
+    fn chat<'a>(&'a self, app: &'a AppHandle, request: &'a AiChatRequest, model: &'a str) -> ChatFuture<'a> {
+        Box::pin(async move {
+            let response = claude_api::send_chat_completion(app.clone(), ClaudeChatRequest {
+                model: model.to_string(),
+                max_tokens: request.max_tokens,
+                messages: request.messages.clone(),
+                system: request.system.clone().map(serde_json::Value::String),
+                temperature: request.temperature,
+                priority: request.priority,
+            })
+            .await?;
+
+            Ok(AiChatResponse {
+                provider: self.id(),
+                model: response.model,
+                text: response
+                    .content
+                    .iter()
+                    .map(|content| match content {
+                        ClaudeResponseContent::Text { text } => text.as_str(),
+                    })
+                    .collect(),
+                stop_reason: response.stop_reason,
+                usage: AiUsage {
+                    input_tokens: response.usage.input_tokens,
+                    output_tokens: response.usage.output_tokens,
+                },
+                fallback_from: Vec::new(),
+            })
+        })
+    }
+}
+
+struct OpenAIProvider;
+
+impl Provider for OpenAIProvider {
+    fn id(&self) -> ProviderId {
+        ProviderId::OpenAI
+    }
+
+    fn is_configured(&self, app: &AppHandle) -> bool {
+        has_api_key(app, "openai_api_key")
+    }
+
+    fn chat<'a>(&'a self, app: &'a AppHandle, request: &'a AiChatRequest, model: &'a str) -> ChatFuture<'a> {
+        Box::pin(async move {
+            let mut messages = Vec::with_capacity(request.messages.len() + 1);
+            if let Some(system) = &request.system {
+                messages.push(serde_json::json!({ "role": "system", "content": system }));
+            }
+            for message in &request.messages {
+                let content = match &message.content {
+                    ClaudeMessageContent::Text(text) => serde_json::json!(text),
+                    ClaudeMessageContent::Blocks(blocks) => blocks
+                        .iter()
+                        .map(|block| match block {
+                            ClaudeContentBlock::Text { text, .. } => serde_json::json!({ "type": "text", "text": text }),
+                            ClaudeContentBlock::Image { source, .. } => serde_json::json!({
+                                "type": "image_url",
+                                "image_url": { "url": format!("data:{};base64,{}", source.media_type, source.data) }
+                            }),
+                        })
+                        .collect(),
+                };
+                messages.push(serde_json::json!({ "role": message.role, "content": content }));
+            }
+
+            let response = openai_api::send_chat_completion(
+                app,
+                model,
+                messages,
+                request.max_tokens,
+                request.temperature,
+                request.priority,
+            )
+            .await?;
+
+            let choice = &response["choices"][0];
+            Ok(AiChatResponse {
+                provider: self.id(),
+                model: response["model"].as_str().unwrap_or(model).to_string(),
+                text: choice["message"]["content"].as_str().unwrap_or_default().to_string(),
+                stop_reason: choice["finish_reason"].as_str().map(|s| s.to_string()),
+                usage: AiUsage {
+                    input_tokens: response["usage"]["prompt_tokens"].as_u64().unwrap_or(0) as u32,
+                    output_tokens: response["usage"]["completion_tokens"].as_u64().unwrap_or(0) as u32,
+                },
+                fallback_from: Vec::new(),
+            })
+        })
+    }
+}
+
+struct GeminiProvider;
+
+impl Provider for GeminiProvider {
+    fn id(&self) -> ProviderId {
+        ProviderId::Gemini
+    }
+
+    fn is_configured(&self, app: &AppHandle) -> bool {
+        has_api_key(app, "gemini_api_key")
+    }
+
+    fn chat<'a>(&'a self, app: &'a AppHandle, request: &'a AiChatRequest, model: &'a str) -> ChatFuture<'a> {
+        Box::pin(async move {
+            let response = gemini_api::send_chat_completion(app.clone(), GeminiChatRequest {
+                model: model.to_string(),
+                max_tokens: request.max_tokens,
+                messages: request.messages.clone(),
+                system: request.system.clone(),
+                temperature: request.temperature,
+                priority: request.priority,
+            })
+            .await?;
+
+            Ok(AiChatResponse {
+                provider: self.id(),
+                model: response.model,
+                text: response.text,
+                stop_reason: response.finish_reason,
+                usage: AiUsage {
+                    input_tokens: response.usage.input_tokens,
+                    output_tokens: response.usage.output_tokens,
+                },
+                fallback_from: Vec::new(),
+            })
+        })
+    }
+}
+
+struct OllamaProvider;
+
+impl Provider for OllamaProvider {
+    fn id(&self) -> ProviderId {
+        ProviderId::Ollama
+    }
+
+    /// Local server, no key; reachability is only known per request
+    fn is_configured(&self, _app: &AppHandle) -> bool {
+        true
+    }
+
+    fn chat<'a>(&'a self, app: &'a AppHandle, request: &'a AiChatRequest, model: &'a str) -> ChatFuture<'a> {
+        Box::pin(async move {
+            let messages = request
+                .messages
+                .iter()
+                .map(|message| {
+                    let (texts, images) = message_parts(&message.content);
+                    OllamaMessage {
+                        role: message.role.clone(),
+                        content: texts.join("\n\n"),
+                        images: (!images.is_empty())
+                            .then(|| images.iter().map(|image| image.data.clone()).collect()),
+                    }
+                })
+                .collect();
+
+            let response = ollama_api::send_chat_completion(app, &OllamaChatRequest {
+                model: model.to_string(),
+                messages,
+                system: request.system.clone(),
+                temperature: request.temperature,
+                max_tokens: Some(request.max_tokens),
+                format: None,
+                base_url: None,
+            })
+            .await?;
+
+            Ok(AiChatResponse {
+                provider: self.id(),
+                model: response.model,
+                text: response.message.content,
+                stop_reason: response.done_reason,
+                usage: AiUsage {
+                    input_tokens: response.prompt_eval_count.unwrap_or(0),
+                    output_tokens: response.eval_count.unwrap_or(0),
+                },
+                fallback_from: Vec::new(),
+            })
+        })
+    }
+}
+
+/// Managed router state
+pub struct AiRouter {
+    providers: Vec<Box<dyn Provider>>,
+    usage: SafeState<HashMap<ProviderId, ProviderUsage>>,
+}
+
+impl AiRouter {
+    pub fn new() -> Self {
+        Self {
+            providers: vec![
+                Box::new(ClaudeProvider),
+                Box::new(OpenAIProvider),
+                Box::new(GeminiProvider),
+                Box::new(OllamaProvider),
+            ],
+            usage: SafeState::new("ai_router_usage", HashMap::new()),
+        }
+    }
+
+    fn provider(&self, id: ProviderId) -> Result<&dyn Provider, String> {
+        self.providers
+            .iter()
+            .find(|provider| provider.id() == id)
+            .map(|provider| provider.as_ref())
+            .ok_or_else(|| format!("Provider {} is not available", id.display_name()))
+    }
+
+    fn record(&self, id: ProviderId, result: &Result<AiChatResponse, ProviderError>, latency: Duration, is_fallback: bool) {
+        let mut usage = self.usage.lock();
+        let entry = usage.entry(id).or_insert_with(|| ProviderUsage::new(id));
+        entry.requests += 1;
+        entry.total_latency_ms += latency.as_millis() as u64;
+        match result {
+            Ok(response) => {
+                entry.input_tokens += response.usage.input_tokens as u64;
+                entry.output_tokens += response.usage.output_tokens as u64;
+                if is_fallback {
+                    entry.fallbacks_served += 1;
+                }
+            }
+            Err(error) => {
+                if matches!(error, ProviderError::RateLimited(_)) {
+                    entry.rate_limited += 1;
+                }
+                entry.failures += 1;
+                entry.last_error = Some(redaction::redact(&error.to_string()));
+            }
+        }
+    }
+
+    /// Send a request to the selected provider, falling back on rate limits
+    pub async fn complete(
+        &self,
+        app: &AppHandle,
+        settings: &AiSettings,
+        request: AiChatRequest,
+    ) -> Result<AiChatResponse, String> {
+        let selected = request.provider.unwrap_or(settings.provider);
+        let mut order = vec![selected];
+        for fallback in &settings.fallback_providers {
+            if !order.contains(fallback) {
+                order.push(*fallback);
+            }
+        }
+
+        let mut rate_limited: Vec<ProviderId> = Vec::new();
+        let mut last_error = String::new();
+
+        for id in order {
+            let provider = self.provider(id)?;
+
+            // The selected provider reports its own setup errors; unconfigured fallbacks are skipped
+            if id != selected && !provider.is_configured(app) {
+                continue;
+            }
+
+            let model = match &request.model {
+                Some(model) if id == selected => model.clone(),
+                _ => settings.models.for_provider(id).to_string(),
+            };
+
+            let started = Instant::now();
+            let result = provider.chat(app, &request, &model).await;
+            self.record(id, &result, started.elapsed(), !rate_limited.is_empty());
+
+            match result {
+                Ok(mut response) => {
+                    if !rate_limited.is_empty() {
+                        println!("🔀 [AI ROUTER] Served by {} after rate limits on {:?}", id.display_name(), rate_limited);
+                    }
+                    response.fallback_from = rate_limited;
+                    return Ok(response);
+                }
+                Err(ProviderError::RateLimited(message)) => {
+                    println!("🔀 [AI ROUTER] {} rate limited, trying next provider", id.display_name());
+                    rate_limited.push(id);
+                    last_error = message;
+                }
+                Err(ProviderError::Failed(message)) => return Err(message),
+            }
+        }
+
+        Err(format!("All AI providers are rate limited. Last error: {}", last_error))
+    }
+
+    pub fn usage(&self) -> Vec<ProviderUsage> {
+        let usage = self.usage.lock();
+        ProviderId::ALL
+            .iter()
+            .map(|id| usage.get(id).cloned().unwrap_or_else(|| ProviderUsage::new(*id)))
+            .collect()
+    }
+}
+
+/// Tauri command for a provider-independent chat completion
+#[tauri::command]
+pub async fn ai_chat_completion(
+    app: AppHandle,
+    router: State<'_, Arc<AiRouter>>,
+    settings: State<'_, Arc<SettingsManager>>,
+    request: AiChatRequest,
+) -> Result<AiChatResponse, String> {
+    command_metrics::track_async("ai_chat_completion", async move {
+        let mut ai_settings = settings.get().ai;
+        profiles::active_profile(&app).apply_model_choices(&mut ai_settings.models);
+        router
+            .complete(&app, &ai_settings, request)
+            .await
+            .map_err(|e| redaction::redact(&e))
+    }).await
+}
+
+/// Tauri command to get per-provider usage since app start
+#[tauri::command]
+pub fn get_ai_provider_usage(
+    router: State<'_, Arc<AiRouter>>,
+) -> Result<Vec<ProviderUsage>, String> {
+    command_metrics::track("get_ai_provider_usage", || {
+        Ok(router.usage())
+    })
+}
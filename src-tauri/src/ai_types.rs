@@ -1,5 +1,48 @@
 use serde::{Deserialize, Serialize};
 
+// ============================================================================
+// Shared Types
+// ============================================================================
+
+/// Who initiated an AI request - background requests are subject to budgets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RequestPriority {
+    /// Explicit user action (chat, manual analysis) - never blocked by budgets
+    #[default]
+    User,
+    /// Background work (enrichment, auto-analysis) - refused when over budget
+    Background,
+}
+
+/// Error from a provider call; the AI router (ai_router.rs) falls back to the
+/// next provider on `RateLimited`
+#[derive(Debug, Clone)]
+pub enum ProviderError {
+    RateLimited(String),
+    Failed(String),
+}
+
+impl std::fmt::Display for ProviderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProviderError::RateLimited(message) | ProviderError::Failed(message) => f.write_str(message),
+        }
+    }
+}
+
+impl From<String> for ProviderError {
+    fn from(message: String) -> Self {
+        ProviderError::Failed(message)
+    }
+}
+
+impl From<&str> for ProviderError {
+    fn from(message: &str) -> Self {
+        ProviderError::Failed(message.to_string())
+    }
+}
+
 // ============================================================================
 // OpenAI Types
 // ============================================================================
@@ -139,6 +182,8 @@ pub struct ClaudeChatRequest {
     pub messages: Vec<ClaudeMessage>,
     pub system: Option<serde_json::Value>,  // Accepts both String and Array with cache_control
     pub temperature: Option<f32>,
+    #[serde(default)]
+    pub priority: RequestPriority,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -263,3 +308,83 @@ pub struct ClaudeStreamError {
     pub error_type: String,
     pub message: String,
 }
+
+// ============================================================================
+// Ollama Types
+// ============================================================================
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OllamaMessage {
+    pub role: String,
+    pub content: String,
+    /// Base64 images (no data URL prefix) for vision models such as llava
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub images: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OllamaChatRequest {
+    pub model: String,
+    pub messages: Vec<OllamaMessage>,
+    /// Sent as a leading system message
+    pub system: Option<String>,
+    pub temperature: Option<f32>,
+    /// Maps to Ollama's `num_predict`
+    pub max_tokens: Option<u32>,
+    /// "json" or a JSON schema for structured output
+    pub format: Option<serde_json::Value>,
+    /// Overrides settings.ollama.baseUrl for this request
+    pub base_url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OllamaChatResponse {
+    pub model: String,
+    pub message: OllamaMessage,
+    #[serde(alias = "done_reason")]
+    pub done_reason: Option<String>,
+    /// Prompt tokens
+    #[serde(alias = "prompt_eval_count")]
+    pub prompt_eval_count: Option<u32>,
+    /// Output tokens
+    #[serde(alias = "eval_count")]
+    pub eval_count: Option<u32>,
+}
+
+// ============================================================================
+// Gemini Types
+// ============================================================================
+
+/// Gemini requests take Claude-shaped messages (role "user"/"assistant",
+/// text and base64 image blocks) so callers can switch providers freely
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiChatRequest {
+    pub model: String,
+    pub max_tokens: u32,
+    pub messages: Vec<ClaudeMessage>,
+    pub system: Option<String>,
+    pub temperature: Option<f32>,
+    #[serde(default)]
+    pub priority: RequestPriority,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiChatResponse {
+    pub model: String,
+    /// Concatenated text parts of the first candidate
+    pub text: String,
+    /// "STOP", "MAX_TOKENS", "SAFETY", ...
+    pub finish_reason: Option<String>,
+    pub usage: GeminiUsage,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiUsage {
+    pub input_tokens: u32,
+    pub output_tokens: u32,
+}
@@ -0,0 +1,256 @@
+/**
+ * Annotation Module
+ *
+ * Marks up a screenshot (e.g. a quick capture) before it is attached to a
+ * session: `annotate_screenshot` applies arrows, rectangles, text labels and
+ * blur regions in order and returns the result as a PNG data URL.
+ *
+ * Coordinates are image pixels from the top-left corner. Colors are CSS-style
+ * hex ("#rgb", "#rrggbb" or "#rrggbbaa"); translucent colors are blended.
+ * Text labels use a system font (Helvetica/Arial, DejaVu Sans on Linux).
+ */
+
+use ab_glyph::{FontVec, PxScale};
+use image::{DynamicImage, ImageFormat, Rgba, RgbaImage};
+use imageproc::drawing::{draw_filled_rect_mut, draw_polygon_mut, draw_text_mut, text_size, Blend};
+use imageproc::point::Point;
+use imageproc::rect::Rect;
+use serde::Deserialize;
+use std::io::Cursor;
+use std::sync::OnceLock;
+
+use crate::command_metrics;
+
+const DEFAULT_COLOR: &str = "#ff3b30";
+const DEFAULT_STROKE_WIDTH: f32 = 4.0;
+const DEFAULT_FONT_SIZE: f32 = 24.0;
+const DEFAULT_BLUR_SIGMA: f32 = 12.0;
+
+/// Padding around text on a label background
+const LABEL_PADDING: i32 = 6;
+
+/// Tried in order for text labels
+const FONT_PATHS: &[&str] = &[
+    "/System/Library/Fonts/Helvetica.ttc",
+    "/System/Library/Fonts/Supplemental/Arial.ttf",
+    "/Library/Fonts/Arial.ttf",
+    "C:\\Windows\\Fonts\\arial.ttf",
+    "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf",
+];
+
+/// One markup operation
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum Annotation {
+    /// Arrow from `(from_x, from_y)` with its head at `(to_x, to_y)`
+    Arrow {
+        from_x: f32,
+        from_y: f32,
+        to_x: f32,
+        to_y: f32,
+        color: Option<String>,
+        stroke_width: Option<f32>,
+    },
+    Rectangle {
+        x: i32,
+        y: i32,
+        width: u32,
+        height: u32,
+        color: Option<String>,
+        stroke_width: Option<f32>,
+        /// Fill instead of outlining (e.g. a translucent highlight)
+        #[serde(default)]
+        filled: bool,
+    },
+    /// Text with its top-left corner at `(x, y)`
+    Text {
+        x: i32,
+        y: i32,
+        text: String,
+        color: Option<String>,
+        font_size: Option<f32>,
+        /// Label background drawn behind the text
+        background: Option<String>,
+    },
+    Blur {
+        x: i32,
+        y: i32,
+        width: u32,
+        height: u32,
+        /// Gaussian blur strength
+        sigma: Option<f32>,
+    },
+}
+
+fn parse_color(color: Option<&str>) -> Result<Rgba<u8>, String> {
+    let color = color.unwrap_or(DEFAULT_COLOR);
+    let hex = color.trim().trim_start_matches('#');
+    let expanded: String = match hex.len() {
+        3 | 4 => hex.chars().flat_map(|c| [c, c]).collect(),
+        6 | 8 => hex.to_string(),
+        _ => return Err(format!("Invalid color: {}", color)),
+    };
+    let channel = |index: usize| {
+        expanded
+            .get(index * 2..index * 2 + 2)
+            .map_or(Ok(255), |pair| u8::from_str_radix(pair, 16))
+            .map_err(|_| format!("Invalid color: {}", color))
+    };
+    Ok(Rgba([channel(0)?, channel(1)?, channel(2)?, channel(3)?]))
+}
+
+/// System font for text (also used for timelapse timestamps)
+pub(crate) fn font() -> Result<&'static FontVec, String> {
+    static FONT: OnceLock<Option<FontVec>> = OnceLock::new();
+    FONT.get_or_init(|| {
+        FONT_PATHS.iter().find_map(|path| {
+            let bytes = std::fs::read(path).ok()?;
+            FontVec::try_from_vec_and_index(bytes, 0).ok()
+        })
+    })
+    .as_ref()
+    .ok_or_else(|| "No font available for text annotations".to_string())
+}
+
+/// Integer polygon for imageproc (which wants it open, without repeated points)
+fn polygon(points: &[(f32, f32)]) -> Vec<Point<i32>> {
+    let mut polygon: Vec<Point<i32>> = Vec::new();
+    for &(x, y) in points {
+        let point = Point::new(x.round() as i32, y.round() as i32);
+        if polygon.last() != Some(&point) {
+            polygon.push(point);
+        }
+    }
+    if polygon.len() > 1 && polygon.first() == polygon.last() {
+        polygon.pop();
+    }
+    polygon
+}
+
+fn draw_arrow(canvas: &mut Blend<RgbaImage>, from: (f32, f32), to: (f32, f32), width: f32, color: Rgba<u8>) {
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    let length = (dx * dx + dy * dy).sqrt();
+    if length < 1.0 {
+        return;
+    }
+    let (ux, uy) = (dx / length, dy / length);
+    let (nx, ny) = (-uy, ux);
+
+    let head_length = (width * 4.0).min(length);
+    let head_half_width = width * 2.5;
+    let base = (to.0 - ux * head_length, to.1 - uy * head_length);
+
+    let half = width / 2.0;
+    let shaft = polygon(&[
+        (from.0 + nx * half, from.1 + ny * half),
+        (base.0 + nx * half, base.1 + ny * half),
+        (base.0 - nx * half, base.1 - ny * half),
+        (from.0 - nx * half, from.1 - ny * half),
+    ]);
+    if shaft.len() >= 3 {
+        draw_polygon_mut(canvas, &shaft, color);
+    }
+    let head = polygon(&[
+        to,
+        (base.0 + nx * head_half_width, base.1 + ny * head_half_width),
+        (base.0 - nx * head_half_width, base.1 - ny * head_half_width),
+    ]);
+    if head.len() >= 3 {
+        draw_polygon_mut(canvas, &head, color);
+    }
+}
+
+fn draw_rectangle(canvas: &mut Blend<RgbaImage>, rect: Rect, width: u32, filled: bool, color: Rgba<u8>) {
+    if filled || width * 2 >= rect.width().min(rect.height()) {
+        draw_filled_rect_mut(canvas, rect, color);
+        return;
+    }
+    let (left, top) = (rect.left(), rect.top());
+    let inner_height = rect.height() - width * 2;
+    draw_filled_rect_mut(canvas, Rect::at(left, top).of_size(rect.width(), width), color);
+    draw_filled_rect_mut(canvas, Rect::at(left, rect.bottom() + 1 - width as i32).of_size(rect.width(), width), color);
+    draw_filled_rect_mut(canvas, Rect::at(left, top + width as i32).of_size(width, inner_height), color);
+    draw_filled_rect_mut(canvas, Rect::at(rect.right() + 1 - width as i32, top + width as i32).of_size(width, inner_height), color);
+}
+
+fn blur_region(image: &mut RgbaImage, x: i32, y: i32, width: u32, height: u32, sigma: f32) {
+    let left = x.max(0) as u32;
+    let top = y.max(0) as u32;
+    let right = (x.saturating_add(width as i32).max(0) as u32).min(image.width());
+    let bottom = (y.saturating_add(height as i32).max(0) as u32).min(image.height());
+    if left >= right || top >= bottom {
+        return;
+    }
+    let region = image::imageops::crop_imm(image, left, top, right - left, bottom - top).to_image();
+    let blurred = imageproc::filter::gaussian_blur_f32(&region, sigma.max(0.1));
+    image::imageops::replace(image, &blurred, left as i64, top as i64);
+}
+
+/// Apply `annotations` in order
+pub fn annotate(image: DynamicImage, annotations: &[Annotation]) -> Result<RgbaImage, String> {
+    let mut canvas = Blend(image.to_rgba8());
+    for annotation in annotations {
+        match annotation {
+            Annotation::Arrow { from_x, from_y, to_x, to_y, color, stroke_width } => {
+                let width = stroke_width.unwrap_or(DEFAULT_STROKE_WIDTH).max(1.0);
+                draw_arrow(&mut canvas, (*from_x, *from_y), (*to_x, *to_y), width, parse_color(color.as_deref())?);
+            }
+            Annotation::Rectangle { x, y, width, height, color, stroke_width, filled } => {
+                if *width == 0 || *height == 0 {
+                    continue;
+                }
+                let stroke = stroke_width.unwrap_or(DEFAULT_STROKE_WIDTH).max(1.0).round() as u32;
+                let rect = Rect::at(*x, *y).of_size(*width, *height);
+                draw_rectangle(&mut canvas, rect, stroke, *filled, parse_color(color.as_deref())?);
+            }
+            Annotation::Text { x, y, text, color, font_size, background } => {
+                if text.trim().is_empty() {
+                    continue;
+                }
+                let font = font()?;
+                let scale = PxScale::from(font_size.unwrap_or(DEFAULT_FONT_SIZE).max(1.0));
+                if let Some(background) = background {
+                    let (text_width, text_height) = text_size(scale, font, text);
+                    let rect = Rect::at(x - LABEL_PADDING, y - LABEL_PADDING)
+                        .of_size(text_width + LABEL_PADDING as u32 * 2, text_height + LABEL_PADDING as u32 * 2);
+                    draw_filled_rect_mut(&mut canvas, rect, parse_color(Some(background))?);
+                }
+                draw_text_mut(&mut canvas, parse_color(color.as_deref())?, *x, *y, scale, font, text);
+            }
+            Annotation::Blur { x, y, width, height, sigma } => {
+                blur_region(&mut canvas.0, *x, *y, *width, *height, sigma.unwrap_or(DEFAULT_BLUR_SIGMA));
+            }
+        }
+    }
+    Ok(canvas.0)
+}
+
+/// Decode a data URL or plain base64 image
+fn decode_image(image: &str) -> Result<DynamicImage, String> {
+    let encoded = match image.split_once(',') {
+        Some((prefix, data)) if prefix.starts_with("data:") => data,
+        _ => image,
+    };
+    let bytes = base64::Engine::decode(&base64::engine::general_purpose::STANDARD, encoded.trim())
+        .map_err(|e| format!("Failed to decode image data: {}", e))?;
+    image::load_from_memory(&bytes).map_err(|e| format!("Failed to decode image: {}", e))
+}
+
+/// Tauri command to annotate a screenshot (data URL or base64); returns the
+/// annotated image as a PNG data URL
+#[tauri::command]
+pub async fn annotate_screenshot(image: String, operations: Vec<Annotation>) -> Result<String, String> {
+    command_metrics::track_async("annotate_screenshot", async move {
+        tokio::task::spawn_blocking(move || {
+            let annotated = annotate(decode_image(&image)?, &operations)?;
+            let mut bytes: Vec<u8> = Vec::new();
+            annotated
+                .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+                .map_err(|e| format!("Failed to encode PNG: {}", e))?;
+            let encoded = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &bytes);
+            Ok(format!("data:image/png;base64,{}", encoded))
+        })
+        .await
+        .map_err(|e| format!("Annotation task failed: {}", e))?
+    }).await
+}
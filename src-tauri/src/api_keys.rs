@@ -1,42 +1,208 @@
+/**
+ * API Keys Module
+ *
+ * Provider API keys per profile, stored in the OS keychain (keychain.rs)
+ * under the account "<profile>/<key name>":
+ * - Keys saved before the keychain move lived in plaintext tauri-plugin-store
+ *   files (api_keys.json / api_keys.<profile>.json). They are moved into the
+ *   keychain once at startup (and on `migrate_api_keys_to_keychain`), then
+ *   removed from the store; until then they are still read from it
+ * - Env var / profile .env fallbacks for development
+ */
+
+use serde::Serialize;
 use tauri_plugin_store::StoreExt;
 
-/// Tauri command to set OpenAI API key
-#[tauri::command]
-pub fn set_openai_api_key(
-    app: tauri::AppHandle,
-    api_key: String,
-) -> Result<(), String> {
+use crate::command_metrics;
+use crate::keychain;
+use crate::profiles::{self, Profile};
+
+/// Keys moved from the legacy store into the keychain
+const KEY_NAMES: [&str; 2] = ["claude_api_key", "openai_api_key"];
+
+/// Where the active API key for a provider came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ApiKeySource {
+    /// Saved via Settings (OS keychain)
+    Keychain,
+    /// Legacy plaintext store, not yet migrated to the keychain
+    Store,
+    /// ANTHROPIC_API_KEY / OPENAI_API_KEY / GEMINI_API_KEY process environment variable
+    Environment,
+    /// .env file in the profile's data directory
+    Dotenv,
+    None,
+}
+
+/// Environment variable used as a fallback for a stored key
+fn env_var_for(key_name: &str) -> Option<&'static str> {
+    match key_name {
+        "claude_api_key" => Some("ANTHROPIC_API_KEY"),
+        "openai_api_key" => Some("OPENAI_API_KEY"),
+        "gemini_api_key" => Some("GEMINI_API_KEY"),
+        _ => None,
+    }
+}
+
+/// Map a provider name from the frontend to its store key
+fn key_name_for_provider(provider: &str) -> Result<&'static str, String> {
+    match provider.to_lowercase().as_str() {
+        "claude" | "anthropic" => Ok("claude_api_key"),
+        "openai" => Ok("openai_api_key"),
+        "gemini" | "google" => Ok("gemini_api_key"),
+        other => Err(format!("Unknown provider: {}", other)),
+    }
+}
+
+/// Look up a variable in <profile data dir>/.env
+/// Supports `KEY=value`, `export KEY=value`, quoted values and # comments
+fn read_dotenv_var(app: &tauri::AppHandle, var: &str) -> Option<String> {
+    let dotenv_path = profiles::profile_data_dir(app).ok()?.join(".env");
+    let content = std::fs::read_to_string(dotenv_path).ok()?;
+
+    content.lines().find_map(|line| {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let (name, value) = line.split_once('=')?;
+        if name.trim() != var {
+            return None;
+        }
+        let value = value.trim().trim_matches('"').trim_matches('\'').to_string();
+        if value.is_empty() { None } else { Some(value) }
+    })
+}
+
+/// Keychain account of a key for a profile
+fn keychain_account(profile: &Profile, key_name: &str) -> String {
+    format!("{}/{}", profile.name, key_name)
+}
+
+/// Resolve a key and report its source: keychain first, then the legacy
+/// store, then env var, then .env
+fn resolve_api_key(
+    app: &tauri::AppHandle,
+    key_name: &str,
+) -> Result<Option<(String, ApiKeySource)>, String> {
+    let profile = profiles::active_profile(app);
+    if let Some(key) = keychain::get(&keychain_account(&profile, key_name))? {
+        return Ok(Some((key, ApiKeySource::Keychain)));
+    }
+
+    let store = app.store(profiles::api_keys_store(&profile))
+        .map_err(|e| format!("Failed to access store: {}", e))?;
+
+    if let Some(key) = store.get(key_name).and_then(|v| v.as_str().map(|s| s.to_string())) {
+        return Ok(Some((key, ApiKeySource::Store)));
+    }
+
+    let var = match env_var_for(key_name) {
+        Some(var) => var,
+        None => return Ok(None),
+    };
+
+    if let Ok(key) = std::env::var(var) {
+        if !key.trim().is_empty() {
+            return Ok(Some((key.trim().to_string(), ApiKeySource::Environment)));
+        }
+    }
+
+    Ok(read_dotenv_var(app, var).map(|key| (key, ApiKeySource::Dotenv)))
+}
+
+/// Store a key in the keychain for the active profile
+pub fn store_api_key(app: &tauri::AppHandle, key_name: &str, api_key: &str) -> Result<(), String> {
     if api_key.trim().is_empty() {
         return Err("API key cannot be empty".to_string());
     }
 
-    let store = app.store("api_keys.json")
-        .map_err(|e| format!("Failed to access store: {}", e))?;
+    let profile = profiles::active_profile(app);
+    keychain::set(&keychain_account(&profile, key_name), api_key.trim())?;
 
-    store.set("openai_api_key", serde_json::json!(api_key.trim()));
-    store.save().map_err(|e| format!("Failed to save store: {}", e))?;
+    // Drop any stale plaintext copy so it can't shadow or outlive the new key
+    let store = app.store(profiles::api_keys_store(&profile))
+        .map_err(|e| format!("Failed to access store: {}", e))?;
+    if store.delete(key_name) {
+        store.save().map_err(|e| format!("Failed to save store: {}", e))?;
+    }
 
     Ok(())
 }
 
+/// Keys moved by a migration run
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyMigrationReport {
+    /// "<profile>/<key name>" of each key moved into the keychain
+    pub migrated: Vec<String>,
+}
+
+/// Move plaintext keys of every profile from the legacy store into the
+/// keychain. Keys already in the keychain win; the plaintext copy is removed
+/// once the keychain holds the key (a failed write keeps it). Idempotent, so
+/// it's safe to run on every startup.
+pub fn migrate_to_keychain(app: &tauri::AppHandle) -> Result<KeyMigrationReport, String> {
+    let mut report = KeyMigrationReport { migrated: Vec::new() };
+
+    for profile in profiles::load_profiles(app)? {
+        let store = app.store(profiles::api_keys_store(&profile))
+            .map_err(|e| format!("Failed to access store: {}", e))?;
+
+        let mut changed = false;
+        for key_name in KEY_NAMES {
+            let Some(value) = store.get(key_name) else {
+                continue;
+            };
+            let account = keychain_account(&profile, key_name);
+            if let Some(key) = value.as_str().filter(|key| !key.trim().is_empty()) {
+                if keychain::get(&account)?.is_none() {
+                    keychain::set(&account, key.trim())?;
+                    report.migrated.push(account);
+                }
+            }
+            store.delete(key_name);
+            changed = true;
+        }
+
+        if changed {
+            store.save().map_err(|e| format!("Failed to save store: {}", e))?;
+        }
+    }
+
+    if !report.migrated.is_empty() {
+        println!("🔑 [API KEYS] Migrated {} key(s) to the keychain", report.migrated.len());
+    }
+    Ok(report)
+}
+
+/// Read a key for the active profile, falling back to env vars / .env
+/// Shared by the AI provider modules so key isolation lives in one place
+pub fn get_api_key(app: &tauri::AppHandle, key_name: &str) -> Result<Option<String>, String> {
+    Ok(resolve_api_key(app, key_name)?.map(|(key, _)| key))
+}
+
+/// Tauri command to set OpenAI API key
+#[tauri::command]
+pub fn set_openai_api_key(
+    app: tauri::AppHandle,
+    api_key: String,
+) -> Result<(), String> {
+    command_metrics::track("set_openai_api_key", || {
+        store_api_key(&app, "openai_api_key", &api_key)
+    })
+}
+
 /// Tauri command to get OpenAI API key
 #[tauri::command]
 pub fn get_openai_api_key(
     app: tauri::AppHandle,
 ) -> Result<Option<String>, String> {
-    let store = app.store("api_keys.json")
-        .map_err(|e| format!("Failed to access store: {}", e))?;
-
-    match store.get("openai_api_key") {
-        Some(value) => {
-            if let Some(key) = value.as_str() {
-                Ok(Some(key.to_string()))
-            } else {
-                Ok(None)
-            }
-        },
-        None => Ok(None)
-    }
+    command_metrics::track("get_openai_api_key", || {
+        get_api_key(&app, "openai_api_key")
+    })
 }
 
 /// Tauri command to set Claude API key
@@ -45,17 +211,9 @@ pub fn set_claude_api_key(
     app: tauri::AppHandle,
     api_key: String,
 ) -> Result<(), String> {
-    if api_key.trim().is_empty() {
-        return Err("API key cannot be empty".to_string());
-    }
-
-    let store = app.store("api_keys.json")
-        .map_err(|e| format!("Failed to access store: {}", e))?;
-
-    store.set("claude_api_key", serde_json::json!(api_key.trim()));
-    store.save().map_err(|e| format!("Failed to save store: {}", e))?;
-
-    Ok(())
+    command_metrics::track("set_claude_api_key", || {
+        store_api_key(&app, "claude_api_key", &api_key)
+    })
 }
 
 /// Tauri command to get Claude API key
@@ -63,19 +221,30 @@ pub fn set_claude_api_key(
 pub fn get_claude_api_key(
     app: tauri::AppHandle,
 ) -> Result<Option<String>, String> {
-    let store = app.store("api_keys.json")
-        .map_err(|e| format!("Failed to access store: {}", e))?;
+    command_metrics::track("get_claude_api_key", || {
+        get_api_key(&app, "claude_api_key")
+    })
+}
 
-    match store.get("claude_api_key") {
-        Some(value) => {
-            if let Some(key) = value.as_str() {
-                Ok(Some(key.to_string()))
-            } else {
-                Ok(None)
-            }
-        },
-        None => Ok(None)
-    }
+/// Tauri command to set Gemini API key
+#[tauri::command]
+pub fn set_gemini_api_key(
+    app: tauri::AppHandle,
+    api_key: String,
+) -> Result<(), String> {
+    command_metrics::track("set_gemini_api_key", || {
+        store_api_key(&app, "gemini_api_key", &api_key)
+    })
+}
+
+/// Tauri command to get Gemini API key
+#[tauri::command]
+pub fn get_gemini_api_key(
+    app: tauri::AppHandle,
+) -> Result<Option<String>, String> {
+    command_metrics::track("get_gemini_api_key", || {
+        get_api_key(&app, "gemini_api_key")
+    })
 }
 
 /// Tauri command to check if OpenAI API key exists
@@ -83,10 +252,9 @@ pub fn get_claude_api_key(
 pub fn has_openai_api_key(
     app: tauri::AppHandle,
 ) -> Result<bool, String> {
-    let store = app.store("api_keys.json")
-        .map_err(|e| format!("Failed to access store: {}", e))?;
-
-    Ok(store.get("openai_api_key").is_some())
+    command_metrics::track("has_openai_api_key", || {
+        Ok(get_api_key(&app, "openai_api_key")?.is_some())
+    })
 }
 
 /// Tauri command to check if Claude API key exists
@@ -94,8 +262,44 @@ pub fn has_openai_api_key(
 pub fn has_claude_api_key(
     app: tauri::AppHandle,
 ) -> Result<bool, String> {
-    let store = app.store("api_keys.json")
-        .map_err(|e| format!("Failed to access store: {}", e))?;
+    command_metrics::track("has_claude_api_key", || {
+        Ok(get_api_key(&app, "claude_api_key")?.is_some())
+    })
+}
 
-    Ok(store.get("claude_api_key").is_some())
+/// Tauri command to check if Gemini API key exists
+#[tauri::command]
+pub fn has_gemini_api_key(
+    app: tauri::AppHandle,
+) -> Result<bool, String> {
+    command_metrics::track("has_gemini_api_key", || {
+        Ok(get_api_key(&app, "gemini_api_key")?.is_some())
+    })
+}
+
+/// Tauri command to report where the active key for a provider came from
+/// (`keychain`, `store`, `environment`, `dotenv` or `none`)
+#[tauri::command]
+pub fn get_api_key_source(
+    app: tauri::AppHandle,
+    provider: String,
+) -> Result<ApiKeySource, String> {
+    command_metrics::track("get_api_key_source", || {
+        let key_name = key_name_for_provider(&provider)?;
+
+        Ok(resolve_api_key(&app, key_name)?
+            .map(|(_, source)| source)
+            .unwrap_or(ApiKeySource::None))
+    })
+}
+
+/// Tauri command to move API keys from the legacy plaintext store into the
+/// keychain (also run automatically at startup)
+#[tauri::command]
+pub fn migrate_api_keys_to_keychain(
+    app: tauri::AppHandle,
+) -> Result<KeyMigrationReport, String> {
+    command_metrics::track("migrate_api_keys_to_keychain", || {
+        migrate_to_keychain(&app)
+    })
 }
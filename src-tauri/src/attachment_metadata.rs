@@ -0,0 +1,260 @@
+/**
+ * Attachment Metadata Module
+ *
+ * Extracts media metadata from imported attachments in Rust:
+ * - Image dimensions (any format supported by the `image` crate)
+ * - EXIF creation date, camera make/model, orientation (JPEG/TIFF/HEIF)
+ * - PDF page count
+ *
+ * Results are written back into `attachments/{id}.meta.json` under
+ * `mediaMetadata` so search and display don't need to parse files in JS.
+ */
+
+use tauri::AppHandle;
+use rayon::prelude::*;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::background_tasks::TaskRegistry;
+use crate::command_metrics;
+use crate::jobs::JobRegistry;
+use crate::profiles;
+use crate::session_models::MediaMetadata;
+
+/// Decode the raw bytes for an attachment.
+/// Prefers the inline `.dat` payload (base64 / data URL), falling back to the
+/// `path` recorded in metadata for file-based attachments.
+pub fn read_attachment_bytes(
+    attachments_dir: &Path,
+    id: &str,
+    meta: &serde_json::Value,
+) -> Result<Vec<u8>, String> {
+    let data_path = attachments_dir.join(format!("{}.dat", id));
+
+    if data_path.exists() {
+        let content = std::fs::read_to_string(&data_path)
+            .map_err(|e| format!("Failed to read attachment data: {}", e))?;
+
+        // Strip "data:<mime>;base64," prefix if present
+        let encoded = match content.split_once(",") {
+            Some((prefix, data)) if prefix.starts_with("data:") => data,
+            _ => content.as_str(),
+        };
+
+        return base64::Engine::decode(&base64::engine::general_purpose::STANDARD, encoded.trim())
+            .map_err(|e| format!("Failed to decode attachment data: {}", e));
+    }
+
+    if let Some(path) = meta.get("path").and_then(|p| p.as_str()) {
+        return std::fs::read(path)
+            .map_err(|e| format!("Failed to read attachment file {}: {}", path, e));
+    }
+
+    Err(format!("No data found for attachment {}", id))
+}
+
+/// Read image dimensions without decoding the full pixel buffer
+fn image_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    image::ImageReader::new(Cursor::new(bytes))
+        .with_guessed_format()
+        .ok()?
+        .into_dimensions()
+        .ok()
+}
+
+/// Parse EXIF fields into the metadata record (silently skips files without EXIF)
+fn apply_exif(bytes: &[u8], metadata: &mut MediaMetadata) {
+    let exif = match exif::Reader::new().read_from_container(&mut Cursor::new(bytes)) {
+        Ok(exif) => exif,
+        Err(_) => return,
+    };
+
+    let ascii_field = |tag: exif::Tag| -> Option<String> {
+        exif.get_field(tag, exif::In::PRIMARY).and_then(|field| match &field.value {
+            exif::Value::Ascii(values) => values
+                .first()
+                .map(|v| String::from_utf8_lossy(v).trim().to_string())
+                .filter(|v| !v.is_empty()),
+            _ => None,
+        })
+    };
+
+    // EXIF dates look like "2024:05:17 14:03:22" - normalize to ISO 8601
+    metadata.created_at = ascii_field(exif::Tag::DateTimeOriginal)
+        .or_else(|| ascii_field(exif::Tag::DateTime))
+        .and_then(|raw| {
+            chrono::NaiveDateTime::parse_from_str(&raw, "%Y:%m:%d %H:%M:%S")
+                .map(|dt| dt.format("%Y-%m-%dT%H:%M:%S").to_string())
+                .ok()
+        });
+    metadata.camera_make = ascii_field(exif::Tag::Make);
+    metadata.camera_model = ascii_field(exif::Tag::Model);
+    metadata.orientation = exif
+        .get_field(exif::Tag::Orientation, exif::In::PRIMARY)
+        .and_then(|field| field.value.get_uint(0));
+}
+
+/// Count pages in a PDF by scanning for page objects.
+/// Avoids pulling in a full PDF parser - "/Type /Pages" (the page tree) is excluded.
+fn pdf_page_count(bytes: &[u8]) -> Option<u32> {
+    let mut count = 0u32;
+    let needle = b"/Type";
+    let mut i = 0;
+
+    while i + needle.len() < bytes.len() {
+        if &bytes[i..i + needle.len()] == needle {
+            let mut j = i + needle.len();
+            while j < bytes.len() && (bytes[j] == b' ' || bytes[j] == b'\r' || bytes[j] == b'\n') {
+                j += 1;
+            }
+            if bytes[j..].starts_with(b"/Page") && !bytes[j..].starts_with(b"/Pages") {
+                count += 1;
+            }
+            i = j;
+        } else {
+            i += 1;
+        }
+    }
+
+    if count > 0 { Some(count) } else { None }
+}
+
+/// Extract metadata for a single attachment based on its mime type
+pub fn extract_metadata(bytes: &[u8], mime_type: &str) -> MediaMetadata {
+    let mut metadata = MediaMetadata::default();
+
+    if mime_type.starts_with("image/") {
+        if let Some((width, height)) = image_dimensions(bytes) {
+            metadata.width = Some(width);
+            metadata.height = Some(height);
+        }
+        apply_exif(bytes, &mut metadata);
+    } else if mime_type == "application/pdf" {
+        metadata.page_count = pdf_page_count(bytes);
+    }
+
+    metadata
+}
+
+/// Extract and persist metadata for one attachment
+fn process_attachment(attachments_dir: &Path, id: &str) -> Result<MediaMetadata, String> {
+    let meta_path = attachments_dir.join(format!("{}.meta.json", id));
+
+    let content = std::fs::read_to_string(&meta_path)
+        .map_err(|e| format!("Failed to read metadata file: {}", e))?;
+    let mut meta: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse metadata: {}", e))?;
+
+    let mime_type = meta
+        .get("mimeType")
+        .and_then(|m| m.as_str())
+        .unwrap_or("")
+        .to_string();
+
+    let bytes = read_attachment_bytes(attachments_dir, id, &meta)?;
+    let metadata = extract_metadata(&bytes, &mime_type);
+
+    // Merge into the existing record, keeping all frontend-owned fields intact
+    meta["mediaMetadata"] = serde_json::to_value(&metadata)
+        .map_err(|e| format!("Failed to serialize metadata: {}", e))?;
+    if meta.get("dimensions").map_or(true, |d| d.is_null()) {
+        if let (Some(width), Some(height)) = (metadata.width, metadata.height) {
+            meta["dimensions"] = serde_json::json!({ "width": width, "height": height });
+        }
+    }
+
+    let serialized = serde_json::to_string(&meta)
+        .map_err(|e| format!("Failed to serialize metadata: {}", e))?;
+    std::fs::write(&meta_path, serialized)
+        .map_err(|e| format!("Failed to write metadata file: {}", e))?;
+
+    Ok(metadata)
+}
+
+/**
+ * Extract media metadata for attachments in parallel and store it
+ * in each attachment's meta.json. Runs as a job (returns the job id);
+ * the `job-finished` result is the (id, metadata) pairs for attachments
+ * that were processed successfully.
+ */
+#[tauri::command]
+pub async fn extract_attachments_metadata(
+    attachment_ids: Vec<String>,
+    app_handle: AppHandle,
+    jobs: tauri::State<'_, Arc<JobRegistry>>,
+    tasks: tauri::State<'_, Arc<TaskRegistry>>,
+) -> Result<String, String> {
+    command_metrics::track_async("extract_attachments_metadata", async move {
+        let data_dir = profiles::profile_data_dir(&app_handle)?;
+        let attachments_dir: PathBuf = data_dir.join("attachments");
+
+        jobs.start(app_handle.clone(), &tasks, "extract-attachments-metadata", move |ctx| async move {
+            println!("🦀 [RUST] Extracting metadata for {} attachments...", attachment_ids.len());
+            let start = Instant::now();
+
+            if !attachments_dir.exists() {
+                println!("⚠️  [RUST] Attachments directory not found");
+                return Ok(serde_json::json!([]));
+            }
+
+            let total = attachment_ids.len();
+            let done = AtomicUsize::new(0);
+            let job = ctx.clone();
+            let results = tokio::task::spawn_blocking(move || {
+                attachment_ids
+                    .into_par_iter()
+                    .filter_map(|id| {
+                        if job.is_cancelled() {
+                            return None;
+                        }
+                        let result = match process_attachment(&attachments_dir, &id) {
+                            Ok(metadata) => Some((id, metadata)),
+                            Err(e) => {
+                                eprintln!("Failed to extract metadata for {}: {}", id, e);
+                                None
+                            }
+                        };
+                        // Report roughly every 5%
+                        let completed = done.fetch_add(1, Ordering::Relaxed) + 1;
+                        if completed * 20 / total != (completed - 1) * 20 / total {
+                            job.progress_items(completed as u64, total as u64, "Extracting attachment metadata");
+                        }
+                        result
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .await
+            .map_err(|e| format!("Metadata extraction task failed: {}", e))?;
+            ctx.check_cancelled()?;
+
+            let elapsed = start.elapsed();
+            println!("✅ [RUST] Extracted metadata for {} attachments in {:?}", results.len(), elapsed);
+
+            serde_json::to_value(results)
+                .map_err(|e| format!("Failed to serialize metadata: {}", e))
+        })
+    }).await
+}
+
+/**
+ * Extract media metadata from a file on disk before it is imported
+ * (e.g. a file picked through the dialog plugin)
+ */
+#[tauri::command]
+pub async fn extract_file_metadata(
+    path: String,
+    mime_type: String,
+) -> Result<MediaMetadata, String> {
+    command_metrics::track_async("extract_file_metadata", async move {
+        let bytes = tokio::fs::read(&path)
+            .await
+            .map_err(|e| format!("Failed to read file {}: {}", path, e))?;
+
+        tokio::task::spawn_blocking(move || extract_metadata(&bytes, &mime_type))
+            .await
+            .map_err(|e| format!("Metadata extraction task failed: {}", e))
+    }).await
+}
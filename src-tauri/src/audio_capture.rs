@@ -4,44 +4,169 @@
  * Implements real-time audio recording with:
  * - System audio capture using cpal
  * - Configurable chunk buffering (matches screenshot interval)
- * - WAV encoding with hound
- * - Base64 transmission to frontend
- * - State management (recording/paused/stopped)
+ * - WAV encoding with hound, lossless FLAC, Ogg Opus or MP3 (settings.audio.chunkFormat)
+ * - Base64 transmission to frontend, binary chunks fetched via `take_audio_chunk`,
+ *   or chunk files written to a per-session directory (event carries the path)
+ * - Optional live transcript: ~1.5s slices are streamed to OpenAI and the text
+ *   is emitted as `transcript-delta` events (settings.audio.streamingTranscription)
+ * - Device hot-plug: if the input device disconnects mid-recording, capture
+ *   moves to the current default device (`audio-device-changed`) and the chunk
+ *   in progress keeps filling
+ * - Optional system audio (settings.audio.systemAudio, macOS 13+): mixed into
+ *   the mic chunks by settings.audio.balance, or with settings.audio.separateTracks
+ *   emitted as separate `mic` / `system` chunks for post-hoc rebalancing.
+ *   ScreenCaptureKit sometimes stops delivering audio without an error; after
+ *   SYSTEM_STALL_AFTER without samples `audio-source-stalled` is emitted and
+ *   the capture restarted until samples flow again (`audio-source-recovered`)
+ * - Optional noise suppression on the mic path (audio_processing.rs,
+ *   settings.audio.noiseSuppression, toggled live with `set_noise_suppression`)
+ * - Optional automatic gain control on mic chunks before VAD/encoding
+ *   (settings.audio.agcEnabled); the applied gain is in the health status
+ * - Profile changes: when the input's rate changes (e.g. AirPods switching to
+ *   HFP) the stream is rebuilt; below MIN_SPEECH_RATE `audio-quality-degraded`
+ *   is emitted and, with settings.audio.preferBuiltInMic, capture moves to the
+ *   built-in mic
+ * - State management (recording/paused/stopped); chunks carry wall-clock
+ *   `startedAt`/`endedAt` and the pauses that fell inside them
  */
 
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use cpal::{Device, SampleFormat, Stream, StreamConfig};
 use hound::{WavSpec, WavWriter};
-use std::sync::{Arc, Mutex};
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tauri::{AppHandle, Emitter};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::audio_encoding::{self, ChunkFormat};
+use crate::audio_processing::{AutomaticGainControl, NoiseSuppressor};
+use crate::background_tasks::TaskRegistry;
+use crate::openai_api;
+use crate::redaction;
+use crate::realtime_emitter::RealtimeEmitter;
+use crate::media_buffers::{self, MediaBufferUsage, OverflowPolicy, SampleSpillFile};
+use crate::safe_state::SafeState;
+use crate::session_models::PauseGap;
+use crate::system_audio::SystemAudioCapture;
 
 /// Audio recording state
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum RecordingState {
     Stopped,
     Recording,
     Paused,
 }
 
+/// Per-recording delivery options for `start_audio_recording`
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AudioRecordingOptions {
+    /// Deliver chunks as raw bytes fetched with `take_audio_chunk`
+    pub binary_chunks: bool,
+    /// Write chunks to files under `audio-chunks/` instead of sending them
+    pub file_chunks: bool,
+    /// Live transcript; None uses settings.audio.streamingTranscription
+    pub streaming_transcription: Option<bool>,
+}
+
+const SAMPLE_BYTES: usize = std::mem::size_of::<f32>();
+
 /// Audio buffer for storing samples
+/// Growth is charged against the global media buffer budget; past the limit
+/// samples are spilled to disk or the oldest samples are dropped
 struct AudioBuffer {
     samples: Vec<f32>,
+    spill: Option<SampleSpillFile>,
+    /// Copy of new samples for the live transcript (None when it's off)
+    tap: Option<Vec<f32>>,
     start_time: Instant,
     chunk_duration: Duration,
+    /// Times samples were dropped because the media buffer budget was full
+    overruns: u64,
+    /// Time capture callbacks spent buffering into this
+    busy: Duration,
 }
 
 impl AudioBuffer {
     fn new(chunk_duration_secs: u64) -> Self {
         Self {
             samples: Vec::new(),
+            spill: None,
+            tap: None,
             start_time: Instant::now(),
             chunk_duration: Duration::from_secs(chunk_duration_secs),
+            overruns: 0,
+            busy: Duration::ZERO,
         }
     }
 
-    fn push_sample(&mut self, sample: f32) {
-        self.samples.push(sample);
+    fn push_samples(&mut self, samples: &[f32]) {
+        if let Some(tap) = self.tap.as_mut() {
+            tap.extend_from_slice(samples);
+        }
+
+        let bytes = samples.len() * SAMPLE_BYTES;
+        let budget = media_buffers::budget();
+
+        if !budget.try_reserve(bytes) {
+            match budget.policy() {
+                OverflowPolicy::SpillToDisk => self.spill_to_disk(),
+                OverflowPolicy::DropOldest => {
+                    self.overruns += 1;
+                    // Reuse the reservation of the oldest samples for the new ones
+                    let dropped = samples.len().min(self.samples.len());
+                    self.samples.drain(..dropped);
+                    budget.record_dropped(dropped * SAMPLE_BYTES);
+                    if dropped == samples.len() {
+                        self.samples.extend_from_slice(samples);
+                        return;
+                    }
+                    budget.release(dropped * SAMPLE_BYTES);
+                }
+            }
+
+            if !budget.try_reserve(bytes) {
+                self.overruns += 1;
+                budget.record_dropped(bytes);
+                return;
+            }
+        }
+
+        self.samples.extend_from_slice(samples);
+    }
+
+    /// Move in-memory samples to the spill file and release their reservation
+    fn spill_to_disk(&mut self) {
+        if self.samples.is_empty() {
+            return;
+        }
+
+        if self.spill.is_none() {
+            match SampleSpillFile::create() {
+                Ok(file) => self.spill = Some(file),
+                Err(e) => {
+                    eprintln!("❌ [AUDIO CAPTURE] {}", e);
+                    return;
+                }
+            }
+        }
+
+        let bytes = self.samples.len() * SAMPLE_BYTES;
+        if let Some(spill) = self.spill.as_mut() {
+            match spill.append(&self.samples) {
+                Ok(()) => {
+                    let budget = media_buffers::budget();
+                    budget.release(bytes);
+                    budget.record_spilled(bytes);
+                    self.samples = Vec::new();
+                }
+                Err(e) => eprintln!("❌ [AUDIO CAPTURE] {}", e),
+            }
+        }
     }
 
     fn is_chunk_ready(&self) -> bool {
@@ -49,29 +174,194 @@ impl AudioBuffer {
     }
 
     fn take_samples(&mut self) -> Vec<f32> {
-        let samples = std::mem::take(&mut self.samples);
+        let in_memory = std::mem::take(&mut self.samples);
+        media_buffers::budget().release(in_memory.len() * SAMPLE_BYTES);
         self.start_time = Instant::now();
-        samples
+
+        // Spilled samples come first - they're older than what's in memory
+        match self.spill.take().map(|spill| spill.drain()) {
+            Some(Ok(mut samples)) => {
+                samples.extend_from_slice(&in_memory);
+                samples
+            }
+            Some(Err(e)) => {
+                eprintln!("❌ [AUDIO CAPTURE] {}", e);
+                in_memory
+            }
+            None => in_memory,
+        }
+    }
+
+    fn set_tap(&mut self, enabled: bool) {
+        self.tap = enabled.then(Vec::new);
+    }
+
+    /// Samples captured since the last call
+    fn take_tap(&mut self) -> Vec<f32> {
+        self.tap.as_mut().map(std::mem::take).unwrap_or_default()
+    }
+
+    fn buffered_samples(&self) -> usize {
+        self.samples.len()
     }
 
     fn clear(&mut self) {
+        media_buffers::budget().release(self.samples.len() * SAMPLE_BYTES);
         self.samples.clear();
+        self.spill = None;
         self.start_time = Instant::now();
     }
 }
 
+/// Binary chunks waiting to be fetched by the frontend; older chunks are
+/// dropped if the frontend stops collecting them
+const MAX_PENDING_CHUNKS: usize = 8;
+
+/// Binary chunks by id, oldest first
+type PendingChunks = VecDeque<(String, Vec<u8>)>;
+
+/// Name of the chunk processor in the background task registry
+const CHUNK_PROCESSOR_TASK: &str = "audio-chunk-processor";
+const STREAM_TRANSCRIBER_TASK: &str = "audio-stream-transcriber";
+const DEVICE_MONITOR_TASK: &str = "audio-device-monitor";
+
+/// How often the input device is checked while recording
+const DEVICE_POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// Input rates below this (Bluetooth HFP runs at 8-16kHz) hurt transcription
+const MIN_SPEECH_RATE: u32 = 22050;
+
+/// System audio delivers buffers even for silence, so none for this long means it stalled
+const SYSTEM_STALL_AFTER: Duration = Duration::from_secs(5);
+/// Minimum time between restarts of a stalled system audio capture
+const SYSTEM_RESTART_BACKOFF: Duration = Duration::from_secs(10);
+
+/// Audio per live transcript request (requests take ~0.5s on top)
+const STREAM_SLICE: Duration = Duration::from_millis(1500);
+/// Preceding transcript sent as the prompt for the next slice
+const STREAM_CONTEXT_CHARS: usize = 400;
+
+/// Payload of `transcript-delta`: `delta` as text arrives, then one event with
+/// `done` and the slice's full `text` (or `error`)
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TranscriptDelta<'a> {
+    session_id: &'a str,
+    slice_index: u64,
+    delta: &'a str,
+    done: bool,
+    text: Option<&'a str>,
+    error: Option<String>,
+}
+
+/// Payload of `audio-device-changed`
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AudioDeviceChanged {
+    session_id: Option<String>,
+    previous_device: String,
+    device: String,
+}
+
+/// Payload of `audio-quality-degraded`
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AudioQualityDegraded {
+    session_id: Option<String>,
+    device: String,
+    sample_rate: u32,
+    previous_sample_rate: u32,
+    /// Device capture moved to (settings.audio.preferBuiltInMic)
+    switched_to: Option<String>,
+}
+
+/// Payload of `audio-source-stalled` / `audio-source-recovered`
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AudioSourceStatus {
+    session_id: Option<String>,
+    /// Always "system" for now
+    source: &'static str,
+    /// How long no samples arrived (stalled) or the stall lasted (recovered)
+    stalled_secs: f64,
+    /// Capture restarts attempted so far
+    restarts: u32,
+}
+
+/// A stall of the system audio capture being worked on
+struct SourceStall {
+    detected_at: Instant,
+    stalled_since: Instant,
+    last_restart: Option<Instant>,
+    restarts: u32,
+}
+
 /// Global audio recorder state
 pub struct AudioRecorder {
-    state: Arc<Mutex<RecordingState>>,
-    buffer: Arc<Mutex<AudioBuffer>>,
-    stream: Arc<Mutex<Option<Stream>>>,
-    session_id: Arc<Mutex<Option<String>>>,
-    app_handle: Arc<Mutex<Option<AppHandle>>>,
+    state: Arc<SafeState<RecordingState>>,
+    buffer: Arc<SafeState<AudioBuffer>>,
+    stream: Arc<SafeState<Option<Stream>>>,
+    session_id: Arc<SafeState<Option<String>>>,
+    app_handle: Arc<SafeState<Option<AppHandle>>>,
     #[allow(dead_code)]
     sample_rate: u32,
+    /// Deliver chunks as raw bytes instead of base64 in the event payload
+    binary_chunks: Arc<AtomicBool>,
+    chunk_format: Arc<SafeState<ChunkFormat>>,
+    /// When set, chunks are written to `<dir>/<session id>/` instead of sent in events
+    chunk_directory: Arc<SafeState<Option<PathBuf>>>,
+    /// RMS below which a chunk is flagged as silent (None = VAD disabled)
+    silence_threshold: Arc<SafeState<Option<f32>>>,
+    pending_chunks: Arc<SafeState<PendingChunks>>,
+    next_chunk_id: Arc<AtomicU64>,
+    /// Unclaimed binary chunks evicted this recording
+    dropped_chunks: Arc<AtomicU64>,
+    /// Bytes of chunk files written this recording (file chunk mode)
+    chunk_bytes_written: Arc<AtomicU64>,
+    /// Most recent capture, encoding or delivery error this recording
+    last_error: Arc<SafeState<Option<String>>>,
+    /// Stream slices for a live transcript (`transcript-delta` events)
+    streaming_transcription: Arc<AtomicBool>,
+    /// Name of the input device being recorded
+    device_name: Arc<SafeState<Option<String>>>,
+    /// Rate samples are buffered at; a fallback device is resampled to it
+    recording_rate: Arc<AtomicU32>,
+    /// Set by the stream error callback when the device goes away
+    device_lost: Arc<AtomicBool>,
+    /// Native rate of the input device when its stream was opened
+    device_rate: Arc<AtomicU32>,
+    /// `audio-quality-degraded` was emitted for the current input
+    quality_degraded: Arc<AtomicBool>,
+    /// Move capture to the built-in mic when the input degrades
+    prefer_built_in_mic: Arc<AtomicBool>,
+    /// Capture system audio alongside the mic in subsequent recordings
+    system_audio: Arc<AtomicBool>,
+    system_buffer: Arc<SafeState<AudioBuffer>>,
+    system_capture: Arc<SafeState<Option<SystemAudioCapture>>>,
+    /// System audio is being captured for the current recording
+    system_active: Arc<AtomicBool>,
+    /// When the system audio capture last delivered samples
+    system_last_samples: Arc<SafeState<Instant>>,
+    /// Set while system audio delivers nothing (watchdog in the device monitor)
+    system_stall: Arc<SafeState<Option<SourceStall>>>,
+    /// Emit mic and system audio as separate chunks instead of mixing them
+    separate_tracks: Arc<AtomicBool>,
+    /// Mic vs. system mix (0.0 = mic only, 1.0 = system only)
+    balance: Arc<SafeState<f32>>,
+    /// Suppress background noise on the mic in subsequent recordings
+    noise_suppression: Arc<AtomicBool>,
+    /// Mic noise suppressor of the current recording (None = off)
+    noise_suppressor: Arc<SafeState<Option<NoiseSuppressor>>>,
+    /// Normalize mic chunk loudness in subsequent recordings
+    agc_enabled: Arc<AtomicBool>,
+    /// Gain control of the current recording (None = off)
+    agc: Arc<SafeState<Option<AutomaticGainControl>>>,
+    /// Start of the current pause
+    paused_at: Arc<SafeState<Option<chrono::DateTime<chrono::Utc>>>>,
+    /// Pauses since the last chunk was emitted
+    pause_gaps: Arc<SafeState<Vec<PauseGap>>>,
 }
 
-// SAFETY: AudioRecorder uses Mutex for all internal state synchronization,
+// SAFETY: AudioRecorder uses SafeState (Mutex) for all internal state synchronization,
 // making it safe to share across threads despite Stream not being Send/Sync on macOS
 unsafe impl Send for AudioRecorder {}
 unsafe impl Sync for AudioRecorder {}
@@ -79,49 +369,329 @@ unsafe impl Sync for AudioRecorder {}
 impl AudioRecorder {
     pub fn new() -> Self {
         Self {
-            state: Arc::new(Mutex::new(RecordingState::Stopped)),
-            buffer: Arc::new(Mutex::new(AudioBuffer::new(120))), // Default 120s, will be reset on start
-            stream: Arc::new(Mutex::new(None)),
-            session_id: Arc::new(Mutex::new(None)),
-            app_handle: Arc::new(Mutex::new(None)),
+            state: Arc::new(SafeState::new("audio.state", RecordingState::Stopped)),
+            buffer: Arc::new(SafeState::new("audio.buffer", AudioBuffer::new(120))), // Default 120s, will be reset on start
+            stream: Arc::new(SafeState::new("audio.stream", None)),
+            session_id: Arc::new(SafeState::new("audio.session_id", None)),
+            app_handle: Arc::new(SafeState::new("audio.app_handle", None)),
             sample_rate: 44100, // Default sample rate
+            binary_chunks: Arc::new(AtomicBool::new(false)),
+            chunk_format: Arc::new(SafeState::new("audio.chunk_format", ChunkFormat::Wav)),
+            chunk_directory: Arc::new(SafeState::new("audio.chunk_directory", None)),
+            silence_threshold: Arc::new(SafeState::new("audio.silence_threshold", None)),
+            pending_chunks: Arc::new(SafeState::new("audio.pending_chunks", VecDeque::new())),
+            next_chunk_id: Arc::new(AtomicU64::new(0)),
+            dropped_chunks: Arc::new(AtomicU64::new(0)),
+            chunk_bytes_written: Arc::new(AtomicU64::new(0)),
+            last_error: Arc::new(SafeState::new("audio.last_error", None)),
+            streaming_transcription: Arc::new(AtomicBool::new(false)),
+            device_name: Arc::new(SafeState::new("audio.device_name", None)),
+            recording_rate: Arc::new(AtomicU32::new(0)),
+            device_lost: Arc::new(AtomicBool::new(false)),
+            device_rate: Arc::new(AtomicU32::new(0)),
+            quality_degraded: Arc::new(AtomicBool::new(false)),
+            prefer_built_in_mic: Arc::new(AtomicBool::new(false)),
+            system_audio: Arc::new(AtomicBool::new(false)),
+            system_buffer: Arc::new(SafeState::new("audio.system_buffer", AudioBuffer::new(120))),
+            system_capture: Arc::new(SafeState::new("audio.system_capture", None)),
+            system_active: Arc::new(AtomicBool::new(false)),
+            system_last_samples: Arc::new(SafeState::new("audio.system_last_samples", Instant::now())),
+            system_stall: Arc::new(SafeState::new("audio.system_stall", None)),
+            separate_tracks: Arc::new(AtomicBool::new(false)),
+            balance: Arc::new(SafeState::new("audio.balance", 0.5)),
+            noise_suppression: Arc::new(AtomicBool::new(false)),
+            noise_suppressor: Arc::new(SafeState::new("audio.noise_suppressor", None)),
+            agc_enabled: Arc::new(AtomicBool::new(false)),
+            agc: Arc::new(SafeState::new("audio.agc", None)),
+            paused_at: Arc::new(SafeState::new("audio.paused_at", None)),
+            pause_gaps: Arc::new(SafeState::new("audio.pause_gaps", Vec::new())),
         }
     }
 
     /// Initialize the audio recorder with app handle
     pub fn init(&self, app_handle: AppHandle) -> Result<(), String> {
-        *self.app_handle.lock()
-            .map_err(|e| format!("Failed to lock app_handle: {}", e))? = Some(app_handle);
+        *self.app_handle.lock() = Some(app_handle);
         Ok(())
     }
 
+    /// Choose how chunks reach the frontend: when enabled, `audio-chunk` carries
+    /// a `chunkId` and the encoded bytes are fetched with `take_audio_chunk`
+    pub fn set_binary_chunks(&self, enabled: bool) {
+        self.binary_chunks.store(enabled, Ordering::SeqCst);
+    }
+
+    /// Encoding for subsequent chunks (WAV, FLAC or Opus)
+    pub fn set_chunk_format(&self, format: ChunkFormat) {
+        self.chunk_format.set(format);
+    }
+
+    /// Write chunks under `directory` (one subdirectory per session); `audio-chunk`
+    /// then carries only the file path. None restores event delivery.
+    pub fn set_chunk_directory(&self, directory: Option<PathBuf>) {
+        self.chunk_directory.set(directory);
+    }
+
+    /// Flag chunks whose RMS is below `threshold` as silent (None disables)
+    pub fn set_silence_threshold(&self, threshold: Option<f32>) {
+        self.silence_threshold.set(threshold);
+    }
+
+    /// Emit a live transcript (`transcript-delta`) for subsequent recordings
+    pub fn set_streaming_transcription(&self, enabled: bool) {
+        self.streaming_transcription.store(enabled, Ordering::SeqCst);
+    }
+
+    /// Capture system audio alongside the mic (macOS 13+) in subsequent recordings
+    pub fn set_system_audio(&self, enabled: bool) {
+        self.system_audio.store(enabled, Ordering::SeqCst);
+    }
+
+    /// Emit mic and system audio as separate `mic` / `system` chunks
+    pub fn set_separate_tracks(&self, enabled: bool) {
+        self.separate_tracks.store(enabled, Ordering::SeqCst);
+    }
+
+    /// Mic vs. system mix for mixed chunks (0.0 = mic only, 1.0 = system only)
+    pub fn set_balance(&self, balance: f32) {
+        self.balance.set(balance.clamp(0.0, 1.0));
+    }
+
+    /// Suppress background noise on the mic; applies immediately when recording
+    pub fn set_noise_suppression(&self, enabled: bool) {
+        self.noise_suppression.store(enabled, Ordering::SeqCst);
+        if self.get_state() != RecordingState::Stopped {
+            let sample_rate = self.recording_rate.load(Ordering::SeqCst);
+            self.noise_suppressor.set(enabled.then(|| NoiseSuppressor::new(sample_rate)));
+            println!("🎤 [AUDIO CAPTURE] Noise suppression {}", if enabled { "enabled" } else { "disabled" });
+        }
+    }
+
+    /// Normalize mic chunk loudness (AGC) in subsequent recordings
+    pub fn set_agc(&self, enabled: bool) {
+        self.agc_enabled.store(enabled, Ordering::SeqCst);
+    }
+
+    /// Switch to the built-in mic when the input drops to a low-rate profile
+    pub fn set_prefer_built_in_mic(&self, enabled: bool) {
+        self.prefer_built_in_mic.store(enabled, Ordering::SeqCst);
+    }
+
+    /// Remove and return a pending binary chunk
+    pub fn take_chunk(&self, chunk_id: &str) -> Result<Vec<u8>, String> {
+        let mut pending = self.pending_chunks.lock();
+        let index = pending
+            .iter()
+            .position(|(id, _)| id == chunk_id)
+            .ok_or_else(|| format!("Audio chunk {} not found", chunk_id))?;
+        let bytes = pending.remove(index).map(|(_, bytes)| bytes).unwrap_or_default();
+        media_buffers::budget().release(bytes.len());
+        Ok(bytes)
+    }
+
     /// Start recording audio
     pub fn start_recording(&self, session_id: String, chunk_duration_secs: u64) -> Result<(), String> {
         println!("🎤 [AUDIO CAPTURE] Starting recording for session: {} (chunk duration: {}s)", session_id, chunk_duration_secs);
 
         // Check if already recording
-        let current_state = self.state.lock()
-            .map_err(|e| format!("Failed to lock state: {}", e))?.clone();
+        let current_state = self.state.get();
         if current_state == RecordingState::Recording {
             println!("⚠️  [AUDIO CAPTURE] Already recording");
             return Ok(());
         }
 
         // Store session ID
-        *self.session_id.lock()
-            .map_err(|e| format!("Failed to lock session_id: {}", e))? = Some(session_id.clone());
+        *self.session_id.lock() = Some(session_id.clone());
+        self.dropped_chunks.store(0, Ordering::SeqCst);
+        self.chunk_bytes_written.store(0, Ordering::SeqCst);
+        self.last_error.set(None);
 
         // Recreate buffer with the specified chunk duration
-        *self.buffer.lock()
-            .map_err(|e| format!("Failed to lock buffer: {}", e))? = AudioBuffer::new(chunk_duration_secs);
+        *self.buffer.lock() = AudioBuffer::new(chunk_duration_secs);
+
+        // Record at the default input device's native rate (e.g., 44100)
+        let device = Self::default_input_device()?;
+        let (stream, device_name, sample_rate) = self.open_input(&device, None)?;
+        self.noise_suppressor.set(
+            self.noise_suppression.load(Ordering::SeqCst).then(|| NoiseSuppressor::new(sample_rate)),
+        );
+        self.agc.set(self.agc_enabled.load(Ordering::SeqCst).then(|| AutomaticGainControl::new(sample_rate)));
 
-        // Get default input device
-        let host = cpal::default_host();
-        let device = host
+        // Store stream
+        *self.stream.lock() = Some(stream);
+        self.device_name.set(Some(device_name));
+        self.recording_rate.store(sample_rate, Ordering::SeqCst);
+        self.device_rate.store(sample_rate, Ordering::SeqCst);
+        self.device_lost.store(false, Ordering::SeqCst);
+        self.quality_degraded.store(false, Ordering::SeqCst);
+
+        // Update state
+        *self.state.lock() = RecordingState::Recording;
+
+        // Clear buffer
+        self.buffer.lock().clear();
+        self.paused_at.set(None);
+        self.pause_gaps.lock().clear();
+
+        *self.system_buffer.lock() = AudioBuffer::new(chunk_duration_secs);
+        if self.system_audio.load(Ordering::SeqCst) {
+            self.start_system_audio(sample_rate);
+        }
+
+        // Start background thread to check for completed chunks
+        self.start_chunk_processor(sample_rate)?;
+
+        let streaming = self.streaming_transcription.load(Ordering::SeqCst);
+        self.buffer.lock().set_tap(streaming);
+        if streaming {
+            self.start_stream_transcriber(sample_rate)?;
+        }
+
+        self.start_device_monitor()?;
+
+        println!("✅ [AUDIO CAPTURE] Recording started");
+        Ok(())
+    }
+
+    /// Capture system audio into its own buffer at the recording's rate; on
+    /// failure the recording continues with the mic only
+    fn start_system_audio(&self, target_rate: u32) {
+        self.system_last_samples.set(Instant::now());
+        self.system_stall.set(None);
+        match self.open_system_audio(target_rate) {
+            Ok(()) => {
+                self.system_active.store(true, Ordering::SeqCst);
+                println!("🔊 [AUDIO CAPTURE] Capturing system audio");
+            }
+            Err(e) => eprintln!("⚠️  [AUDIO CAPTURE] {} - recording microphone only", e),
+        }
+    }
+
+    /// Start a ScreenCaptureKit capture feeding the system buffer
+    fn open_system_audio(&self, target_rate: u32) -> Result<(), String> {
+        let buffer = self.system_buffer.clone();
+        let state = self.state.clone();
+        let last_samples = self.system_last_samples.clone();
+        let capture = SystemAudioCapture::start(move |samples, source_rate| {
+            if !samples.is_empty() {
+                last_samples.set(Instant::now());
+            }
+            if *state.lock() == RecordingState::Recording {
+                Self::push_input(&buffer, &None, None, samples, source_rate, target_rate);
+            }
+        })?;
+        *self.system_capture.lock() = Some(capture);
+        Ok(())
+    }
+
+    /// Watchdog for system audio that stopped arriving: report the stall,
+    /// restart the capture (at most every SYSTEM_RESTART_BACKOFF) and report
+    /// the recovery once samples arrive again
+    fn check_system_audio(&self) {
+        if !self.system_active.load(Ordering::SeqCst) {
+            return;
+        }
+        let last_samples = self.system_last_samples.get();
+        let mut stall = self.system_stall.lock();
+
+        if let Some(current) = stall.as_ref() {
+            if last_samples > current.detected_at {
+                let stalled = last_samples - current.stalled_since;
+                println!(
+                    "✅ [AUDIO CAPTURE] System audio recovered after {:.0}s ({} restart(s))",
+                    stalled.as_secs_f64(),
+                    current.restarts
+                );
+                self.emit_source_status("audio-source-recovered", stalled, current.restarts);
+                *stall = None;
+                return;
+            }
+        } else {
+            let silent_for = last_samples.elapsed();
+            if silent_for < SYSTEM_STALL_AFTER {
+                return;
+            }
+            eprintln!("⚠️  [AUDIO CAPTURE] No system audio for {:.0}s", silent_for.as_secs_f64());
+            self.emit_source_status("audio-source-stalled", silent_for, 0);
+            *stall = Some(SourceStall {
+                detected_at: Instant::now(),
+                stalled_since: last_samples,
+                last_restart: None,
+                restarts: 0,
+            });
+        }
+
+        let Some(current) = stall.as_mut() else {
+            return;
+        };
+        if current.last_restart.is_some_and(|at| at.elapsed() < SYSTEM_RESTART_BACKOFF) {
+            return;
+        }
+        current.last_restart = Some(Instant::now());
+        current.restarts += 1;
+        eprintln!("🔄 [AUDIO CAPTURE] Restarting system audio capture (attempt {})", current.restarts);
+
+        // Stopping blocks until ScreenCaptureKit has let go of the old stream
+        let previous = self.system_capture.lock().take();
+        drop(previous);
+        if let Err(e) = self.open_system_audio(self.recording_rate.load(Ordering::SeqCst)) {
+            eprintln!("❌ [AUDIO CAPTURE] {}", e);
+            self.last_error.set(Some(e));
+        }
+    }
+
+    fn emit_source_status(&self, event: &str, stalled: Duration, restarts: u32) {
+        if let Some(app) = self.app_handle.get() {
+            let _ = app.emit(event, AudioSourceStatus {
+                session_id: self.session_id.get(),
+                source: "system",
+                stalled_secs: stalled.as_secs_f64(),
+                restarts,
+            });
+        }
+    }
+
+    /// Samples of one chunk as emitted: a single (mixed) track, or mic and
+    /// system audio as separate tracks
+    fn chunk_tracks(
+        mic: Vec<f32>,
+        system: Option<Vec<f32>>,
+        separate: bool,
+        balance: f32,
+    ) -> Vec<(Option<&'static str>, Vec<f32>)> {
+        match system {
+            None => vec![(None, mic)],
+            Some(system) if separate => vec![(Some("mic"), mic), (Some("system"), system)],
+            Some(system) => vec![(None, Self::mix(&mic, &system, balance))],
+        }
+    }
+
+    /// Mix mic and system audio; at 0.5 both play at full level
+    fn mix(mic: &[f32], system: &[f32], balance: f32) -> Vec<f32> {
+        let mic_gain = ((1.0 - balance) * 2.0).min(1.0);
+        let system_gain = (balance * 2.0).min(1.0);
+        (0..mic.len().max(system.len()))
+            .map(|i| {
+                let mic = mic.get(i).copied().unwrap_or(0.0);
+                let system = system.get(i).copied().unwrap_or(0.0);
+                (mic * mic_gain + system * system_gain).clamp(-1.0, 1.0)
+            })
+            .collect()
+    }
+
+    fn default_input_device() -> Result<Device, String> {
+        cpal::default_host()
             .default_input_device()
-            .ok_or_else(|| "No input device available".to_string())?;
+            .ok_or_else(|| "No input device available".to_string())
+    }
+
+    /// Open and start a stream on `device`. Samples are resampled to
+    /// `target_rate` when given (a replacement device may run at a different
+    /// rate than the recording). Returns the stream, the device name and the
+    /// device's native rate.
+    fn open_input(&self, device: &Device, target_rate: Option<u32>) -> Result<(Stream, String, u32), String> {
+        let device_name = device.name().unwrap_or_else(|_| "Unknown".to_string());
 
-        println!("🎤 [AUDIO CAPTURE] Using device: {}", device.name().unwrap_or_else(|_| "Unknown".to_string()));
+        println!("🎤 [AUDIO CAPTURE] Using device: {}", device_name);
 
         // Get device config
         let config = device
@@ -131,14 +701,14 @@ impl AudioRecorder {
         println!("🎤 [AUDIO CAPTURE] Sample format: {:?}, Sample rate: {}, Channels: {}",
             config.sample_format(), config.sample_rate().0, config.channels());
 
-        // Store sample rate (device's native rate, e.g., 44100)
-        let sample_rate = config.sample_rate().0;
+        let device_rate = config.sample_rate().0;
+        let target_rate = target_rate.unwrap_or(device_rate);
 
         // Build stream based on sample format
         let stream = match config.sample_format() {
-            SampleFormat::F32 => self.build_stream_f32(&device, config.into())?,
-            SampleFormat::I16 => self.build_stream_i16(&device, config.into())?,
-            SampleFormat::U16 => self.build_stream_u16(&device, config.into())?,
+            SampleFormat::F32 => self.build_stream_f32(device, config.into(), target_rate)?,
+            SampleFormat::I16 => self.build_stream_i16(device, config.into(), target_rate)?,
+            SampleFormat::U16 => self.build_stream_u16(device, config.into(), target_rate)?,
             _ => return Err(format!("Unsupported sample format: {:?}", config.sample_format())),
         };
 
@@ -147,45 +717,246 @@ impl AudioRecorder {
             .play()
             .map_err(|e| format!("Failed to start audio stream: {}", e))?;
 
-        // Store stream
-        *self.stream.lock()
-            .map_err(|e| format!("Failed to lock stream: {}", e))? = Some(stream);
+        Ok((stream, device_name, device_rate))
+    }
 
-        // Update state
-        *self.state.lock()
-            .map_err(|e| format!("Failed to lock state: {}", e))? = RecordingState::Recording;
+    /// Replace the running stream with one on `device`, keeping the buffer
+    /// (and the chunk in progress) and the recording's sample rate.
+    /// Returns the new device name.
+    fn replace_stream(&self, device: &Device) -> Result<String, String> {
+        // Release the old stream before opening the replacement
+        *self.stream.lock() = None;
+        let target_rate = self.recording_rate.load(Ordering::SeqCst);
+        let (stream, device_name, device_rate) = self.open_input(device, Some(target_rate))?;
+
+        // stop_recording may have run while the stream was being opened
+        if self.get_state() == RecordingState::Stopped {
+            return Err("Recording stopped".to_string());
+        }
+        *self.stream.lock() = Some(stream);
+        self.device_lost.store(false, Ordering::SeqCst);
+        self.device_rate.store(device_rate, Ordering::SeqCst);
+        self.device_name.set(Some(device_name.clone()));
+        Ok(device_name)
+    }
 
-        // Clear buffer
-        self.buffer.lock()
-            .map_err(|e| format!("Failed to lock buffer: {}", e))?.clear();
+    fn emit_device_changed(&self, previous_device: String, device: String) {
+        println!("✅ [AUDIO CAPTURE] Recording continues on '{}'", device);
+        if let Some(app) = self.app_handle.get() {
+            let _ = app.emit("audio-device-changed", AudioDeviceChanged {
+                session_id: self.session_id.get(),
+                previous_device,
+                device,
+            });
+        }
+    }
 
-        // Start background thread to check for completed chunks
-        self.start_chunk_processor(sample_rate);
+    /// Stream error callback; flags the device as lost when it disappears
+    fn stream_error_handler(&self) -> impl FnMut(cpal::StreamError) + Send + 'static {
+        let device_lost = self.device_lost.clone();
+        let last_error = self.last_error.clone();
+        move |err| {
+            eprintln!("❌ [AUDIO CAPTURE] Stream error: {}", err);
+            last_error.set(Some(format!("Stream error: {}", err)));
+            if matches!(err, cpal::StreamError::DeviceNotAvailable) {
+                device_lost.store(true, Ordering::SeqCst);
+            }
+        }
+    }
 
-        println!("✅ [AUDIO CAPTURE] Recording started");
+    /// Watch for the input device disappearing (and system audio stalling) while recording
+    fn start_device_monitor(&self) -> Result<(), String> {
+        let app = self.app_handle.get().ok_or("Audio recorder not initialized")?;
+        let registry = app.state::<Arc<TaskRegistry>>().inner().clone();
+
+        registry.spawn(DEVICE_MONITOR_TASK, |mut shutdown| async move {
+            let mut interval = tokio::time::interval(DEVICE_POLL_INTERVAL);
+            loop {
+                tokio::select! {
+                    _ = shutdown.cancelled() => break,
+                    _ = interval.tick() => {}
+                }
+
+                let recorder = app.state::<Arc<AudioRecorder>>().inner().clone();
+                if recorder.get_state() == RecordingState::Stopped {
+                    break;
+                }
+
+                // Device enumeration and stream setup block on the audio backend
+                match tokio::task::spawn_blocking(move || recorder.check_input_device()).await {
+                    Ok(Ok(())) => {}
+                    Ok(Err(e)) => eprintln!("❌ [AUDIO CAPTURE] Device fallback failed: {}", e),
+                    Err(e) => eprintln!("❌ [AUDIO CAPTURE] Device monitor failed: {}", e),
+                }
+            }
+        })
+    }
+
+    fn find_input_device(matches: impl Fn(&str) -> bool) -> Result<Option<Device>, String> {
+        let mut devices = cpal::default_host()
+            .input_devices()
+            .map_err(|e| format!("Failed to list input devices: {}", e))?;
+        Ok(devices.find(|device| device.name().is_ok_and(|name| matches(&name))))
+    }
+
+    /// Built-in microphones as named by CoreAudio / WASAPI
+    fn is_built_in_mic(name: &str) -> bool {
+        let name = name.to_lowercase();
+        name.contains("built-in")
+            || name.contains("internal")
+            || (name.contains("microphone") && ["macbook", "imac", "mac mini", "mac studio"].iter().any(|m| name.contains(m)))
+    }
+
+    fn check_input_device(&self) -> Result<(), String> {
+        self.check_system_audio();
+        self.recover_lost_device()?;
+        self.check_input_quality()
+    }
+
+    /// Move capture to the default input device if the current one is gone.
+    /// The buffer is untouched, so the chunk in progress continues on the new
+    /// device; failures are retried on the next poll.
+    fn recover_lost_device(&self) -> Result<(), String> {
+        let Some(previous_device) = self.device_name.get() else {
+            return Ok(());
+        };
+        let lost = self.device_lost.load(Ordering::SeqCst)
+            || self.stream.lock().is_none()
+            // Can't tell when devices can't be listed - leave the stream alone
+            || Self::find_input_device(|name| name == previous_device).is_ok_and(|device| device.is_none());
+        if !lost {
+            return Ok(());
+        }
+
+        eprintln!("⚠️  [AUDIO CAPTURE] Input device '{}' lost, falling back to default device", previous_device);
+
+        let device = self.replace_stream(&Self::default_input_device()?)?;
+        self.quality_degraded.store(false, Ordering::SeqCst);
+        self.emit_device_changed(previous_device, device);
         Ok(())
     }
 
+    /// Follow rate changes of the current input (e.g. a Bluetooth headset
+    /// switching from A2DP to HFP when its mic is opened) and report inputs
+    /// running below MIN_SPEECH_RATE
+    fn check_input_quality(&self) -> Result<(), String> {
+        let Some(device_name) = self.device_name.get() else {
+            return Ok(());
+        };
+        let Some(device) = Self::find_input_device(|name| name == device_name)? else {
+            return Ok(());
+        };
+        let sample_rate = device
+            .default_input_config()
+            .map_err(|e| format!("Failed to get default input config: {}", e))?
+            .sample_rate()
+            .0;
+        let previous_rate = self.device_rate.load(Ordering::SeqCst);
+
+        if sample_rate != previous_rate {
+            println!("🎤 [AUDIO CAPTURE] '{}' changed rate: {} -> {}", device_name, previous_rate, sample_rate);
+            // The old stream was configured for the previous rate
+            self.replace_stream(&device)?;
+        }
+
+        if sample_rate >= MIN_SPEECH_RATE {
+            self.quality_degraded.store(false, Ordering::SeqCst);
+            return Ok(());
+        }
+        if self.quality_degraded.swap(true, Ordering::SeqCst) {
+            return Ok(());
+        }
+
+        eprintln!("⚠️  [AUDIO CAPTURE] '{}' degraded to {} Hz", device_name, sample_rate);
+
+        let built_in = match self.prefer_built_in_mic.load(Ordering::SeqCst) {
+            true => Self::find_input_device(|name| name != device_name && Self::is_built_in_mic(name))?,
+            false => None,
+        };
+        let mut switched_to = None;
+        if let Some(built_in) = built_in {
+            let device = self.replace_stream(&built_in)?;
+            self.quality_degraded.store(false, Ordering::SeqCst);
+            self.emit_device_changed(device_name.clone(), device.clone());
+            switched_to = Some(device);
+        }
+
+        if let Some(app) = self.app_handle.get() {
+            let _ = app.emit("audio-quality-degraded", AudioQualityDegraded {
+                session_id: self.session_id.get(),
+                device: device_name,
+                sample_rate,
+                previous_sample_rate: previous_rate,
+                switched_to,
+            });
+        }
+        Ok(())
+    }
+
+    /// Realtime emitter for `audio-level` readings (None before init)
+    fn level_emitter(&self) -> Option<Arc<RealtimeEmitter>> {
+        self.app_handle
+            .lock()
+            .as_ref()
+            .map(|app| app.state::<Arc<RealtimeEmitter>>().inner().clone())
+    }
+
+    /// Store the latest `audio-level` reading (RMS/peak of one callback buffer)
+    fn report_level(levels: &Option<Arc<RealtimeEmitter>>, samples: &[f32]) {
+        let levels = match levels {
+            Some(levels) if !samples.is_empty() => levels,
+            _ => return,
+        };
+
+        let peak = samples.iter().fold(0.0f32, |max, s| max.max(s.abs()));
+        let rms = Self::rms(samples);
+        levels.push("audio-level", serde_json::json!({ "rms": rms, "peak": peak }));
+    }
+
+    /// Buffer one callback's samples at the recording's rate, through the
+    /// mic noise suppressor when one is given and enabled
+    fn push_input(
+        buffer: &SafeState<AudioBuffer>,
+        levels: &Option<Arc<RealtimeEmitter>>,
+        suppressor: Option<&SafeState<Option<NoiseSuppressor>>>,
+        input: &[f32],
+        source_rate: u32,
+        target_rate: u32,
+    ) {
+        let started = Instant::now();
+        let mut samples = match source_rate == target_rate {
+            true => Cow::Borrowed(input),
+            false => Cow::Owned(Self::resample(input, source_rate, target_rate)),
+        };
+        if let Some(suppressor) = suppressor {
+            if let Some(suppressor) = suppressor.lock().as_mut() {
+                suppressor.process(samples.to_mut());
+            }
+        }
+        Self::report_level(levels, input);
+        let mut buffer = buffer.lock();
+        buffer.push_samples(&samples);
+        buffer.busy += started.elapsed();
+    }
+
     /// Build audio stream for f32 samples
-    fn build_stream_f32(&self, device: &Device, config: StreamConfig) -> Result<Stream, String> {
+    fn build_stream_f32(&self, device: &Device, config: StreamConfig, target_rate: u32) -> Result<Stream, String> {
         let buffer = self.buffer.clone();
+        let source_rate = config.sample_rate.0;
+        let on_error = self.stream_error_handler();
+        let suppressor = self.noise_suppressor.clone();
         let state = self.state.clone();
+        let levels = self.level_emitter();
 
         let stream = device
             .build_input_stream(
                 &config,
                 move |data: &[f32], _: &cpal::InputCallbackInfo| {
-                    if let Ok(current_state) = state.lock() {
-                        if *current_state == RecordingState::Recording {
-                            if let Ok(mut buf) = buffer.lock() {
-                                for &sample in data {
-                                    buf.push_sample(sample);
-                                }
-                            }
-                        }
+                    if *state.lock() == RecordingState::Recording {
+                        Self::push_input(&buffer, &levels, Some(&suppressor), data, source_rate, target_rate);
                     }
                 },
-                |err| eprintln!("❌ [AUDIO CAPTURE] Stream error: {}", err),
+                on_error,
                 None,
             )
             .map_err(|e| format!("Failed to build input stream: {}", e))?;
@@ -194,27 +965,28 @@ impl AudioRecorder {
     }
 
     /// Build audio stream for i16 samples (convert to f32)
-    fn build_stream_i16(&self, device: &Device, config: StreamConfig) -> Result<Stream, String> {
+    fn build_stream_i16(&self, device: &Device, config: StreamConfig, target_rate: u32) -> Result<Stream, String> {
         let buffer = self.buffer.clone();
+        let source_rate = config.sample_rate.0;
+        let on_error = self.stream_error_handler();
+        let suppressor = self.noise_suppressor.clone();
         let state = self.state.clone();
+        let levels = self.level_emitter();
 
         let stream = device
             .build_input_stream(
                 &config,
                 move |data: &[i16], _: &cpal::InputCallbackInfo| {
-                    if let Ok(current_state) = state.lock() {
-                        if *current_state == RecordingState::Recording {
-                            if let Ok(mut buf) = buffer.lock() {
-                                for &sample in data {
-                                    // Convert i16 to f32
-                                    let normalized = sample as f32 / i16::MAX as f32;
-                                    buf.push_sample(normalized);
-                                }
-                            }
-                        }
+                    if *state.lock() == RecordingState::Recording {
+                        // Convert i16 to f32
+                        let normalized: Vec<f32> = data
+                            .iter()
+                            .map(|&sample| sample as f32 / i16::MAX as f32)
+                            .collect();
+                        Self::push_input(&buffer, &levels, Some(&suppressor), &normalized, source_rate, target_rate);
                     }
                 },
-                |err| eprintln!("❌ [AUDIO CAPTURE] Stream error: {}", err),
+                on_error,
                 None,
             )
             .map_err(|e| format!("Failed to build input stream: {}", e))?;
@@ -223,27 +995,28 @@ impl AudioRecorder {
     }
 
     /// Build audio stream for u16 samples (convert to f32)
-    fn build_stream_u16(&self, device: &Device, config: StreamConfig) -> Result<Stream, String> {
+    fn build_stream_u16(&self, device: &Device, config: StreamConfig, target_rate: u32) -> Result<Stream, String> {
         let buffer = self.buffer.clone();
+        let source_rate = config.sample_rate.0;
+        let on_error = self.stream_error_handler();
+        let suppressor = self.noise_suppressor.clone();
         let state = self.state.clone();
+        let levels = self.level_emitter();
 
         let stream = device
             .build_input_stream(
                 &config,
                 move |data: &[u16], _: &cpal::InputCallbackInfo| {
-                    if let Ok(current_state) = state.lock() {
-                        if *current_state == RecordingState::Recording {
-                            if let Ok(mut buf) = buffer.lock() {
-                                for &sample in data {
-                                    // Convert u16 to f32
-                                    let normalized = (sample as f32 / u16::MAX as f32) * 2.0 - 1.0;
-                                    buf.push_sample(normalized);
-                                }
-                            }
-                        }
+                    if *state.lock() == RecordingState::Recording {
+                        // Convert u16 to f32
+                        let normalized: Vec<f32> = data
+                            .iter()
+                            .map(|&sample| (sample as f32 / u16::MAX as f32) * 2.0 - 1.0)
+                            .collect();
+                        Self::push_input(&buffer, &levels, Some(&suppressor), &normalized, source_rate, target_rate);
                     }
                 },
-                |err| eprintln!("❌ [AUDIO CAPTURE] Stream error: {}", err),
+                on_error,
                 None,
             )
             .map_err(|e| format!("Failed to build input stream: {}", e))?;
@@ -252,20 +1025,42 @@ impl AudioRecorder {
     }
 
     /// Start background thread to process audio chunks
-    fn start_chunk_processor(&self, sample_rate: u32) {
+    fn start_chunk_processor(&self, sample_rate: u32) -> Result<(), String> {
+        let registry = self.app_handle.lock()
+            .as_ref()
+            .map(|app| app.state::<Arc<TaskRegistry>>().inner().clone())
+            .ok_or("Audio recorder not initialized")?;
+
         let buffer = self.buffer.clone();
         let state = self.state.clone();
         let app_handle = self.app_handle.clone();
         let session_id = self.session_id.clone();
-
-        std::thread::spawn(move || {
+        let binary_chunks = self.binary_chunks.clone();
+        let chunk_format = self.chunk_format.clone();
+        let chunk_directory = self.chunk_directory.clone();
+        let silence_threshold = self.silence_threshold.clone();
+        let pending_chunks = self.pending_chunks.clone();
+        let next_chunk_id = self.next_chunk_id.clone();
+        let dropped_chunks = self.dropped_chunks.clone();
+        let chunk_bytes_written = self.chunk_bytes_written.clone();
+        let last_error = self.last_error.clone();
+        let system_buffer = self.system_buffer.clone();
+        let system_active = self.system_active.clone();
+        let separate_tracks = self.separate_tracks.clone();
+        let balance = self.balance.clone();
+        let agc = self.agc.clone();
+        let pause_gaps = self.pause_gaps.clone();
+
+        registry.spawn(CHUNK_PROCESSOR_TASK, |mut shutdown| async move {
+            let mut chunk_started_at = chrono::Utc::now();
             loop {
-                std::thread::sleep(Duration::from_secs(1)); // Check every second
+                // Check every second
+                tokio::select! {
+                    _ = shutdown.cancelled() => break,
+                    _ = tokio::time::sleep(Duration::from_secs(1)) => {}
+                }
 
-                let current_state = match state.lock() {
-                    Ok(s) => s.clone(),
-                    Err(_) => break, // Exit on lock failure
-                };
+                let current_state = state.get();
 
                 if current_state == RecordingState::Stopped {
                     break; // Exit thread when recording stopped
@@ -276,73 +1071,271 @@ impl AudioRecorder {
                 }
 
                 // Check if chunk is ready
-                let is_ready = match buffer.lock() {
-                    Ok(b) => b.is_chunk_ready(),
-                    Err(_) => continue,
-                };
-                if !is_ready {
+                if !buffer.lock().is_chunk_ready() {
                     continue;
                 }
 
-                // Take samples from buffer
-                let samples = match buffer.lock() {
-                    Ok(mut b) => b.take_samples(),
-                    Err(_) => continue,
-                };
-                if samples.is_empty() {
-                    continue;
+                // Take samples from the buffers (one chunk per track)
+                let mut samples = buffer.lock().take_samples();
+                if let Some(agc) = agc.lock().as_mut() {
+                    agc.process(&mut samples);
                 }
+                let chunk_ended_at = chrono::Utc::now();
+                let started_at = std::mem::replace(&mut chunk_started_at, chunk_ended_at).to_rfc3339();
+                let ended_at = chunk_ended_at.to_rfc3339();
+                let pauses = std::mem::take(&mut *pause_gaps.lock());
+                let system = system_active
+                    .load(Ordering::SeqCst)
+                    .then(|| system_buffer.lock().take_samples());
+                let tracks = Self::chunk_tracks(samples, system, separate_tracks.load(Ordering::SeqCst), balance.get());
+
+                for (track, samples) in tracks {
+                    if samples.is_empty() {
+                        continue;
+                    }
 
-                println!("🎤 [AUDIO CAPTURE] Processing chunk: {} samples", samples.len());
-
-                // Convert to WAV and base64
-                match Self::samples_to_wav_base64(&samples, sample_rate, 1) {
-                    Ok(base64_data) => {
-                        // Get app handle and session ID
-                        let app = match app_handle.lock() {
-                            Ok(h) => h.clone(),
-                            Err(_) => continue,
-                        };
-                        let sess_id = match session_id.lock() {
-                            Ok(s) => s.clone(),
-                            Err(_) => continue,
-                        };
-
-                        if let (Some(app), Some(sid)) = (app, sess_id) {
-                            // Calculate duration
-                            let duration = samples.len() as f64 / sample_rate as f64;
-
-                            // Emit audio-chunk event to frontend
-                            let payload = serde_json::json!({
-                                "sessionId": sid,
-                                "audioBase64": base64_data,
-                                "duration": duration,
-                            });
-
-                            if let Err(e) = app.emit("audio-chunk", payload) {
-                                eprintln!("❌ [AUDIO CAPTURE] Failed to emit audio-chunk event: {}", e);
-                            } else {
-                                println!("✅ [AUDIO CAPTURE] Emitted audio chunk ({:.1}s)", duration);
+                    println!("🎤 [AUDIO CAPTURE] Processing chunk: {} samples", samples.len());
+
+                    // Encode (WAV, FLAC or Opus)
+                    let format = chunk_format.get();
+                    match Self::encode_chunk(&samples, sample_rate, format) {
+                        Ok(encoded) => {
+                            // Get app handle and session ID
+                            let app = app_handle.get();
+                            let sess_id = session_id.get();
+
+                            if let (Some(app), Some(sid)) = (app, sess_id) {
+                                // Calculate duration
+                                let duration = samples.len() as f64 / sample_rate as f64;
+
+                                let is_silent = silence_threshold
+                                    .get()
+                                    .is_some_and(|threshold| Self::rms(&samples) < threshold);
+
+                                // Emit audio-chunk event to frontend
+                                // File mode writes the chunk under the session directory; binary mode
+                                // queues the bytes for `take_audio_chunk`. Chunks that can't be
+                                // written / don't fit the media buffer budget fall back to inline base64
+                                let chunk_index = next_chunk_id.fetch_add(1, Ordering::SeqCst);
+                                let chunk_id = format!("{}-{}", sid, chunk_index);
+                                let byte_length = encoded.len();
+                                let delivered = if let Some(directory) = chunk_directory.get() {
+                                    let file_name = match track {
+                                        Some(track) => format!("chunk-{:04}-{}.{}", chunk_index, track, format.extension()),
+                                        None => format!("chunk-{:04}.{}", chunk_index, format.extension()),
+                                    };
+                                    Self::write_chunk_file(&directory.join(&sid), &file_name, encoded)
+                                        .await
+                                        .map(|path| {
+                                            chunk_bytes_written.fetch_add(byte_length as u64, Ordering::SeqCst);
+                                            serde_json::json!({
+                                                "chunkId": chunk_id,
+                                                "path": path,
+                                                "byteLength": byte_length,
+                                            })
+                                        })
+                                        .inspect_err(|_| last_error.set(Some(format!("Failed to write chunk {}", file_name))))
+                                } else if binary_chunks.load(Ordering::SeqCst) {
+                                    Self::queue_binary_chunk(&pending_chunks, &dropped_chunks, chunk_id.clone(), encoded)
+                                        .map(|()| serde_json::json!({
+                                            "chunkId": chunk_id,
+                                            "byteLength": byte_length,
+                                        }))
+                                } else {
+                                    Err(encoded)
+                                };
+
+                                let mut payload = delivered.unwrap_or_else(|encoded| {
+                                    let base64_data = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &encoded);
+                                    serde_json::json!({
+                                        "audioBase64": format!("data:{};base64,{}", format.mime_type(), base64_data),
+                                    })
+                                });
+                                payload["sessionId"] = serde_json::json!(sid);
+                                payload["mimeType"] = serde_json::json!(format.mime_type());
+                                payload["duration"] = serde_json::json!(duration);
+                                payload["isSilent"] = serde_json::json!(is_silent);
+                                payload["startedAt"] = serde_json::json!(started_at);
+                                payload["endedAt"] = serde_json::json!(ended_at);
+                                if !pauses.is_empty() {
+                                    payload["pauses"] = serde_json::json!(pauses);
+                                }
+                                if let Some(track) = track {
+                                    payload["track"] = serde_json::json!(track);
+                                }
+
+                                if let Err(e) = app.emit("audio-chunk", payload) {
+                                    eprintln!("❌ [AUDIO CAPTURE] Failed to emit audio-chunk event: {}", e);
+                                    last_error.set(Some(format!("Failed to deliver audio chunk: {}", e)));
+                                } else {
+                                    println!("✅ [AUDIO CAPTURE] Emitted audio chunk ({:.1}s)", duration);
+                                }
                             }
                         }
+                        Err(e) => {
+                            eprintln!("❌ [AUDIO CAPTURE] Failed to encode audio: {}", e);
+                            last_error.set(Some(format!("Failed to encode audio: {}", e)));
+                        }
                     }
+                }
+            }
+
+            println!("🛑 [AUDIO CAPTURE] Chunk processor task exiting");
+        })
+    }
+
+    /// Start the live transcript task: every STREAM_SLICE, send the new audio
+    /// to the streaming transcription API and forward the text
+    fn start_stream_transcriber(&self, sample_rate: u32) -> Result<(), String> {
+        let app = self.app_handle.get().ok_or("Audio recorder not initialized")?;
+        let registry = app.state::<Arc<TaskRegistry>>().inner().clone();
+
+        let buffer = self.buffer.clone();
+        let state = self.state.clone();
+        let session_id = self.session_id.clone();
+        let silence_threshold = self.silence_threshold.clone();
+
+        registry.spawn(STREAM_TRANSCRIBER_TASK, |mut shutdown| async move {
+            let mut slice_index: u64 = 0;
+            let mut context = String::new();
+
+            loop {
+                tokio::select! {
+                    _ = shutdown.cancelled() => break,
+                    _ = tokio::time::sleep(STREAM_SLICE) => {}
+                }
+
+                match state.get() {
+                    RecordingState::Stopped => break,
+                    RecordingState::Paused => {
+                        buffer.lock().take_tap();
+                        continue;
+                    }
+                    RecordingState::Recording => {}
+                }
+
+                // Slices that arrive while a request is in flight are sent together next time
+                let samples = buffer.lock().take_tap();
+                let Some(sid) = session_id.get() else { continue };
+                let threshold = silence_threshold.get().unwrap_or(0.0);
+                if samples.is_empty() || Self::rms(&samples) < threshold {
+                    continue;
+                }
+
+                let wav = match Self::samples_to_wav(&samples, sample_rate, 1) {
+                    Ok(wav) => wav,
                     Err(e) => {
-                        eprintln!("❌ [AUDIO CAPTURE] Failed to encode audio: {}", e);
+                        eprintln!("❌ [AUDIO CAPTURE] Failed to encode live transcript slice: {}", e);
+                        continue;
+                    }
+                };
+
+                let index = slice_index;
+                slice_index += 1;
+                let emit = |delta: &str, done: bool, text: Option<&str>, error: Option<String>| {
+                    let _ = app.emit("transcript-delta", TranscriptDelta {
+                        session_id: &sid,
+                        slice_index: index,
+                        delta,
+                        done,
+                        text,
+                        error,
+                    });
+                };
+
+                let result = tokio::select! {
+                    _ = shutdown.cancelled() => break,
+                    result = openai_api::transcribe_stream(&app, &wav, &context, |delta| emit(delta, false, None, None)) => result,
+                };
+
+                match result {
+                    Ok(text) => {
+                        emit("", true, Some(&text), None);
+                        context.push(' ');
+                        context.push_str(text.trim());
+                        let excess = context.chars().count().saturating_sub(STREAM_CONTEXT_CHARS);
+                        context = context.chars().skip(excess).collect();
+                    }
+                    Err(e) => {
+                        eprintln!("❌ [AUDIO CAPTURE] Live transcript slice failed: {}", redaction::redact(&e));
+                        emit("", true, None, Some(redaction::redact(&e)));
                     }
                 }
             }
 
-            println!("🛑 [AUDIO CAPTURE] Chunk processor thread exiting");
-        });
+            println!("🛑 [AUDIO CAPTURE] Live transcript task exiting");
+        })
+    }
+
+    /// Write an encoded chunk to `directory/file_name`; returns the path, or the
+    /// bytes back if the file can't be written
+    async fn write_chunk_file(directory: &Path, file_name: &str, encoded: Vec<u8>) -> Result<String, Vec<u8>> {
+        let path = directory.join(file_name);
+        let written = async {
+            tokio::fs::create_dir_all(directory).await?;
+            tokio::fs::write(&path, &encoded).await
+        }
+        .await;
+
+        match written {
+            Ok(()) => Ok(path.to_string_lossy().to_string()),
+            Err(e) => {
+                eprintln!("❌ [AUDIO CAPTURE] Failed to write chunk file {:?}: {}", path, e);
+                Err(encoded)
+            }
+        }
+    }
+
+    fn rms(samples: &[f32]) -> f32 {
+        if samples.is_empty() {
+            return 0.0;
+        }
+        (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt()
+    }
+
+    /// Queue an encoded chunk for the frontend, evicting unclaimed chunks as needed
+    /// Returns the bytes back if the chunk can't fit in the media buffer budget
+    fn queue_binary_chunk(
+        pending_chunks: &SafeState<PendingChunks>,
+        dropped_chunks: &AtomicU64,
+        chunk_id: String,
+        wav_bytes: Vec<u8>,
+    ) -> Result<(), Vec<u8>> {
+        let mut pending = pending_chunks.lock();
+        let budget = media_buffers::budget();
+
+        let mut reserved = budget.try_reserve(wav_bytes.len());
+        while !reserved || pending.len() >= MAX_PENDING_CHUNKS {
+            let (dropped, bytes) = match pending.pop_front() {
+                Some(entry) => entry,
+                None => break,
+            };
+            eprintln!("⚠️  [AUDIO CAPTURE] Dropping unclaimed audio chunk {}", dropped);
+            dropped_chunks.fetch_add(1, Ordering::SeqCst);
+            budget.release(bytes.len());
+            budget.record_dropped(bytes.len());
+            if !reserved {
+                reserved = budget.try_reserve(wav_bytes.len());
+            }
+        }
+
+        if !reserved {
+            return Err(wav_bytes);
+        }
+        pending.push_back((chunk_id, wav_bytes));
+        Ok(())
     }
 
     /// Resample audio from source sample rate to 16kHz using linear interpolation
-    fn resample_to_16khz(samples: &[f32], source_rate: u32) -> Vec<f32> {
-        if source_rate == 16000 {
-            return samples.to_vec(); // Already 16kHz
+    pub(crate) fn resample_to_16khz(samples: &[f32], source_rate: u32) -> Vec<f32> {
+        Self::resample(samples, source_rate, 16000)
+    }
+
+    fn resample(samples: &[f32], source_rate: u32, target_rate: u32) -> Vec<f32> {
+        if source_rate == target_rate {
+            return samples.to_vec();
         }
 
-        let target_rate = 16000;
         let ratio = source_rate as f64 / target_rate as f64;
         let output_length = (samples.len() as f64 / ratio) as usize;
         let mut resampled = Vec::with_capacity(output_length);
@@ -357,8 +1350,26 @@ impl AudioRecorder {
         resampled
     }
 
-    /// Convert audio samples to WAV format and encode as base64
-    fn samples_to_wav_base64(samples: &[f32], sample_rate: u32, channels: u16) -> Result<String, String> {
+    /// Encode one chunk at 16kHz in the configured format
+    pub(crate) fn encode_chunk(samples: &[f32], sample_rate: u32, format: ChunkFormat) -> Result<Vec<u8>, String> {
+        match format {
+            ChunkFormat::Wav => Self::samples_to_wav(samples, sample_rate, 1),
+            ChunkFormat::Flac => Ok(audio_encoding::encode_flac(&Self::pcm_16khz(samples, sample_rate), 16000)),
+            ChunkFormat::Opus => audio_encoding::encode_opus(&Self::pcm_16khz(samples, sample_rate), 16000),
+            ChunkFormat::Mp3 => audio_encoding::encode_mp3(&Self::pcm_16khz(samples, sample_rate), 16000),
+        }
+    }
+
+    /// Resample to 16kHz 16-bit PCM for the FLAC, Opus and MP3 encoders
+    fn pcm_16khz(samples: &[f32], sample_rate: u32) -> Vec<i16> {
+        Self::resample_to_16khz(samples, sample_rate)
+            .iter()
+            .map(|&sample| (sample * i16::MAX as f32) as i16)
+            .collect()
+    }
+
+    /// Convert audio samples to 16kHz WAV bytes
+    pub(crate) fn samples_to_wav(samples: &[f32], sample_rate: u32, channels: u16) -> Result<Vec<u8>, String> {
         let mut wav_buffer = Vec::new();
 
         // Resample to 16kHz for optimal speech recognition
@@ -390,32 +1401,38 @@ impl AudioRecorder {
                 .map_err(|e| format!("Failed to finalize WAV: {}", e))?;
         }
 
-        // Encode to base64
-        let base64_data = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &wav_buffer);
-        Ok(format!("data:audio/wav;base64,{}", base64_data))
+        Ok(wav_buffer)
     }
 
     /// Pause recording
     pub fn pause_recording(&self) -> Result<(), String> {
         println!("⏸️  [AUDIO CAPTURE] Pausing recording");
-        *self.state.lock()
-            .map_err(|e| format!("Failed to lock state: {}", e))? = RecordingState::Paused;
+        let mut state = self.state.lock();
+        if *state == RecordingState::Recording {
+            self.paused_at.set(Some(chrono::Utc::now()));
+        }
+        *state = RecordingState::Paused;
         Ok(())
     }
 
-    /// Resume recording
-    #[allow(dead_code)]
+    /// Resume recording; the pause is reported with the chunk it fell into
     pub fn resume_recording(&self) -> Result<(), String> {
         println!("▶️  [AUDIO CAPTURE] Resuming recording");
-        let current_state = self.state.lock()
-            .map_err(|e| format!("Failed to lock state: {}", e))?.clone();
+        let mut state = self.state.lock();
 
-        if current_state == RecordingState::Stopped {
+        if *state == RecordingState::Stopped {
             return Err("Cannot resume - recording is stopped".to_string());
         }
 
-        *self.state.lock()
-            .map_err(|e| format!("Failed to lock state: {}", e))? = RecordingState::Recording;
+        if let Some(paused_at) = self.paused_at.lock().take() {
+            let resumed_at = chrono::Utc::now();
+            self.pause_gaps.lock().push(PauseGap {
+                paused_at: paused_at.to_rfc3339(),
+                resumed_at: resumed_at.to_rfc3339(),
+                duration_secs: (resumed_at - paused_at).num_milliseconds() as f64 / 1000.0,
+            });
+        }
+        *state = RecordingState::Recording;
         Ok(())
     }
 
@@ -424,40 +1441,118 @@ impl AudioRecorder {
         println!("🛑 [AUDIO CAPTURE] Stopping recording");
 
         // Update state first to signal threads to stop
-        *self.state.lock()
-            .map_err(|e| format!("Failed to lock state: {}", e))? = RecordingState::Stopped;
+        *self.state.lock() = RecordingState::Stopped;
 
         // Drop the stream (this will stop it)
-        *self.stream.lock()
-            .map_err(|e| format!("Failed to lock stream: {}", e))? = None;
+        *self.stream.lock() = None;
 
         // Clear buffer
-        self.buffer.lock()
-            .map_err(|e| format!("Failed to lock buffer: {}", e))?.clear();
+        self.buffer.lock().clear();
+
+        // Stop the chunk processor without waiting for its next tick
+        if let Some(app) = self.app_handle.lock().as_ref() {
+            let registry = app.state::<Arc<TaskRegistry>>();
+            registry.cancel(CHUNK_PROCESSOR_TASK);
+            registry.cancel(STREAM_TRANSCRIBER_TASK);
+            registry.cancel(DEVICE_MONITOR_TASK);
+        }
+
+        // Discard chunks the frontend never collected
+        let bytes: usize = self.pending_chunks.lock().drain(..).map(|(_, bytes)| bytes.len()).sum();
+        media_buffers::budget().release(bytes);
+
+        self.noise_suppressor.set(None);
+        self.agc.set(None);
+
+        // Stop system audio (blocks until ScreenCaptureKit has stopped)
+        let system_capture = self.system_capture.lock().take();
+        drop(system_capture);
+        self.system_active.store(false, Ordering::SeqCst);
+        self.system_stall.set(None);
+        self.system_buffer.lock().clear();
 
         // Clear session ID
-        *self.session_id.lock()
-            .map_err(|e| format!("Failed to lock session_id: {}", e))? = None;
+        *self.session_id.lock() = None;
+        self.device_name.set(None);
 
         println!("✅ [AUDIO CAPTURE] Recording stopped");
         Ok(())
     }
 
     /// Get current recording state
-    #[allow(dead_code)]
     pub fn get_state(&self) -> RecordingState {
-        self.state.lock()
-            .map(|s| s.clone())
-            .unwrap_or(RecordingState::Stopped)
+        self.state.get()
     }
 
     /// Check if currently recording
     #[allow(dead_code)]
     pub fn is_recording(&self) -> bool {
-        self.state.lock()
-            .map(|s| *s == RecordingState::Recording)
-            .unwrap_or(false)
+        *self.state.lock() == RecordingState::Recording
     }
+
+    /// Snapshot of recorder state and media buffer usage
+    /// Counters since recording started (session_health.rs)
+    pub fn capture_stats(&self) -> AudioCaptureStats {
+        let (mic_overruns, mic_busy) = {
+            let buffer = self.buffer.lock();
+            (buffer.overruns, buffer.busy)
+        };
+        let (system_overruns, system_busy) = {
+            let buffer = self.system_buffer.lock();
+            (buffer.overruns, buffer.busy)
+        };
+        AudioCaptureStats {
+            buffer_overruns: mic_overruns + system_overruns,
+            dropped_chunks: self.dropped_chunks.load(Ordering::SeqCst),
+            chunk_bytes_written: self.chunk_bytes_written.load(Ordering::SeqCst),
+            busy_nanos: (mic_busy + system_busy).as_nanos() as u64,
+            last_error: self.last_error.get(),
+        }
+    }
+
+    pub fn health_status(&self) -> AudioHealthStatus {
+        AudioHealthStatus {
+            state: self.get_state(),
+            input_device: self.device_name.get(),
+            system_audio: self.system_active.load(Ordering::SeqCst),
+            system_audio_stalled: self.system_stall.lock().is_some(),
+            agc_gain: self.agc.lock().as_ref().map(|agc| agc.gain()),
+            buffered_samples: self.buffer.lock().buffered_samples(),
+            pending_chunks: self.pending_chunks.lock().len(),
+            media_buffers: media_buffers::budget().usage(),
+        }
+    }
+}
+
+/// Audio health report returned by `get_audio_health_status`
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioHealthStatus {
+    pub state: RecordingState,
+    pub input_device: Option<String>,
+    /// System audio is being captured
+    pub system_audio: bool,
+    /// System audio stopped arriving and the capture is being restarted
+    pub system_audio_stalled: bool,
+    /// Gain currently applied by AGC (linear; None when AGC is off)
+    pub agc_gain: Option<f32>,
+    pub buffered_samples: usize,
+    pub pending_chunks: usize,
+    pub media_buffers: MediaBufferUsage,
+}
+
+/// Capture counters returned by `AudioRecorder::capture_stats`
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioCaptureStats {
+    /// Times samples were dropped because the media buffer budget was full
+    pub buffer_overruns: u64,
+    /// Unclaimed binary chunks evicted
+    pub dropped_chunks: u64,
+    pub chunk_bytes_written: u64,
+    /// Time capture callbacks spent buffering samples
+    pub busy_nanos: u64,
+    pub last_error: Option<String>,
 }
 
 // No global static - we'll use Tauri's managed state instead
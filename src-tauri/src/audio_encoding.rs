@@ -0,0 +1,897 @@
+/**
+ * Audio Encoding Module
+ *
+ * Encoders for recorded audio chunks (`settings.audio.chunkFormat`):
+ * - `wav`: 16-bit PCM (hound)
+ * - `flac`: lossless FLAC, typically 40-60% of the WAV size for speech and
+ *   accepted directly by the transcription APIs
+ * - `opus`: Opus in an Ogg container (24 kbps speech), roughly a tenth of the
+ *   WAV size; also accepted by the transcription APIs
+ * - `mp3`: 32 kbps mono MP3 (LAME), about an eighth of the WAV size and
+ *   playable everywhere
+ *
+ * The FLAC encoder is a small pure-Rust implementation (mono, 16-bit):
+ * fixed predictors (order 0-4, best per block) with Rice-coded residuals,
+ * falling back to verbatim subframes when prediction doesn't pay off.
+ *
+ * Opus packets come from libopus (the pure-Rust `unsafe-libopus` port, so no
+ * C toolchain is needed); the Ogg framing follows RFC 7845. MP3 frames come
+ * from LAME, which the `mp3lame-sys` build compiles from its bundled sources.
+ */
+
+use mp3lame_encoder::{Bitrate, FlushGap, MonoPcm};
+use serde::{Deserialize, Serialize};
+use unsafe_libopus::{
+    opus_encode, opus_encoder_create, opus_encoder_ctl, opus_encoder_destroy, OPUS_APPLICATION_VOIP,
+    OPUS_GET_LOOKAHEAD_REQUEST, OPUS_OK, OPUS_SET_BITRATE_REQUEST,
+};
+
+/// Encoding used for audio chunks delivered to the frontend
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ChunkFormat {
+    #[default]
+    Wav,
+    Flac,
+    Opus,
+    Mp3,
+}
+
+impl ChunkFormat {
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            ChunkFormat::Wav => "audio/wav",
+            ChunkFormat::Flac => "audio/flac",
+            ChunkFormat::Opus => "audio/ogg",
+            ChunkFormat::Mp3 => "audio/mpeg",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ChunkFormat::Wav => "wav",
+            ChunkFormat::Flac => "flac",
+            ChunkFormat::Opus => "ogg",
+            ChunkFormat::Mp3 => "mp3",
+        }
+    }
+}
+
+/// Samples per FLAC frame
+const BLOCK_SIZE: usize = 4096;
+
+/// Largest Rice parameter representable with the 4-bit parameter field (15 = escape)
+const MAX_RICE_PARAMETER: u32 = 14;
+
+struct BitWriter {
+    bytes: Vec<u8>,
+    current: u64,
+    bits: u32,
+}
+
+impl BitWriter {
+    fn new() -> Self {
+        Self { bytes: Vec::new(), current: 0, bits: 0 }
+    }
+
+    /// Write the low `count` bits of `value` (count <= 32)
+    fn write(&mut self, value: u32, count: u32) {
+        if count == 0 {
+            return;
+        }
+        let mask = if count == 32 { u32::MAX } else { (1 << count) - 1 };
+        self.current = (self.current << count) | (value & mask) as u64;
+        self.bits += count;
+        while self.bits >= 8 {
+            self.bits -= 8;
+            self.bytes.push((self.current >> self.bits) as u8);
+        }
+        self.current &= (1 << self.bits) - 1;
+    }
+
+    fn write_unary(&mut self, zeros: u32) {
+        let mut remaining = zeros;
+        while remaining >= 32 {
+            self.write(0, 32);
+            remaining -= 32;
+        }
+        self.write(1, remaining + 1);
+    }
+
+    /// Pad with zero bits to the next byte boundary
+    fn align(&mut self) {
+        if self.bits > 0 {
+            self.write(0, 8 - self.bits);
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.bytes.len()
+    }
+}
+
+fn crc8(data: &[u8]) -> u8 {
+    data.iter().fold(0u8, |mut crc, byte| {
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 { (crc << 1) ^ 0x07 } else { crc << 1 };
+        }
+        crc
+    })
+}
+
+fn crc16(data: &[u8]) -> u16 {
+    data.iter().fold(0u16, |mut crc, byte| {
+        crc ^= (*byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 { (crc << 1) ^ 0x8005 } else { crc << 1 };
+        }
+        crc
+    })
+}
+
+/// Frame numbers use the UTF-8 style variable-length encoding
+fn write_utf8_number(writer: &mut BitWriter, value: u32) {
+    if value < 0x80 {
+        writer.write(value, 8);
+        return;
+    }
+    let continuation_bytes = match value {
+        0..=0x7FF => 1,
+        0x800..=0xFFFF => 2,
+        0x10000..=0x1F_FFFF => 3,
+        0x20_0000..=0x3FF_FFFF => 4,
+        _ => 5,
+    };
+    let lead_marker = (0xFF00u32 >> (continuation_bytes + 1)) & 0xFF;
+    writer.write(lead_marker | (value >> (6 * continuation_bytes)), 8);
+    for i in (0..continuation_bytes).rev() {
+        writer.write(0x80 | ((value >> (6 * i)) & 0x3F), 8);
+    }
+}
+
+/// Residual of the fixed predictor of `order` at position `i` (i >= order)
+fn fixed_residual(samples: &[i32], order: usize, i: usize) -> i32 {
+    let s = |k: usize| samples[i - k];
+    match order {
+        0 => s(0),
+        1 => s(0) - s(1),
+        2 => s(0) - 2 * s(1) + s(2),
+        3 => s(0) - 3 * s(1) + 3 * s(2) - s(3),
+        _ => s(0) - 4 * s(1) + 6 * s(2) - 4 * s(3) + s(4),
+    }
+}
+
+fn zigzag(value: i32) -> u32 {
+    ((value << 1) ^ (value >> 31)) as u32
+}
+
+/// Best Rice parameter for the residuals and the resulting size in bits
+fn rice_parameter(residuals: &[u32]) -> (u32, u64) {
+    (0..=MAX_RICE_PARAMETER)
+        .map(|k| {
+            let bits: u64 = residuals.iter().map(|&r| (r >> k) as u64 + 1 + k as u64).sum();
+            (k, bits)
+        })
+        .min_by_key(|&(_, bits)| bits)
+        .unwrap_or((0, u64::MAX))
+}
+
+fn write_subframe(writer: &mut BitWriter, block: &[i32]) {
+    // Pick the fixed predictor order with the smallest residual encoding
+    let best = (0..=4usize.min(block.len().saturating_sub(1)))
+        .map(|order| {
+            let residuals: Vec<u32> = (order..block.len())
+                .map(|i| zigzag(fixed_residual(block, order, i)))
+                .collect();
+            let (parameter, bits) = rice_parameter(&residuals);
+            (order, parameter, residuals, bits + 16 * order as u64)
+        })
+        .min_by_key(|(_, _, _, bits)| *bits);
+
+    let verbatim_bits = 16 * block.len() as u64;
+    match best {
+        Some((order, parameter, residuals, bits)) if bits < verbatim_bits => {
+            writer.write(0, 1); // zero padding bit
+            writer.write(0b001000 | order as u32, 6); // SUBFRAME_FIXED
+            writer.write(0, 1); // no wasted bits
+            for &sample in &block[..order] {
+                writer.write(sample as u32, 16);
+            }
+            writer.write(0b00, 2); // Rice coding, 4-bit parameters
+            writer.write(0, 4); // partition order 0
+            writer.write(parameter, 4);
+            for residual in residuals {
+                writer.write_unary(residual >> parameter);
+                writer.write(residual, parameter);
+            }
+        }
+        _ => {
+            writer.write(0, 1);
+            writer.write(0b000001, 6); // SUBFRAME_VERBATIM
+            writer.write(0, 1);
+            for &sample in block {
+                writer.write(sample as u32, 16);
+            }
+        }
+    }
+}
+
+fn sample_rate_code(sample_rate: u32) -> u32 {
+    match sample_rate {
+        8000 => 0b0100,
+        16000 => 0b0101,
+        22050 => 0b0110,
+        24000 => 0b0111,
+        32000 => 0b1000,
+        44100 => 0b1001,
+        48000 => 0b1010,
+        96000 => 0b1011,
+        _ => 0b0000, // taken from STREAMINFO
+    }
+}
+
+/// Encode mono 16-bit samples as a FLAC file
+pub fn encode_flac(samples: &[i16], sample_rate: u32) -> Vec<u8> {
+    let samples: Vec<i32> = samples.iter().map(|&s| s as i32).collect();
+    let mut frames = BitWriter::new();
+    let mut min_frame = u32::MAX;
+    let mut max_frame = 0u32;
+
+    for (frame_number, block) in samples.chunks(BLOCK_SIZE).enumerate() {
+        let start = frames.len();
+
+        let mut header = BitWriter::new();
+        header.write(0b11111111111110, 14); // sync code
+        header.write(0, 1); // reserved
+        header.write(0, 1); // fixed block size stream
+        header.write(0b0111, 4); // block size: 16-bit (size - 1) follows
+        header.write(sample_rate_code(sample_rate), 4);
+        header.write(0b0000, 4); // mono
+        header.write(0b100, 3); // 16 bits per sample
+        header.write(0, 1); // reserved
+        write_utf8_number(&mut header, frame_number as u32);
+        header.write(block.len() as u32 - 1, 16);
+        let crc = crc8(&header.bytes);
+        frames.bytes.extend_from_slice(&header.bytes);
+        frames.write(crc as u32, 8);
+
+        write_subframe(&mut frames, block);
+        frames.align();
+        let crc = crc16(&frames.bytes[start..]);
+        frames.write(crc as u32, 16);
+
+        let frame_size = (frames.len() - start) as u32;
+        min_frame = min_frame.min(frame_size);
+        max_frame = max_frame.max(frame_size);
+    }
+
+    let block_size = BLOCK_SIZE.min(samples.len().max(16)) as u32;
+    let mut output = BitWriter::new();
+    output.bytes.extend_from_slice(b"fLaC");
+    output.write(1, 1); // last metadata block
+    output.write(0, 7); // STREAMINFO
+    output.write(34, 24);
+    output.write(block_size, 16); // min block size
+    output.write(block_size, 16); // max block size
+    output.write(if samples.is_empty() { 0 } else { min_frame }, 24);
+    output.write(max_frame, 24);
+    output.write(sample_rate, 20);
+    output.write(0, 3); // channels - 1
+    output.write(15, 5); // bits per sample - 1
+    output.write((samples.len() as u64 >> 32) as u32, 4); // total samples (36 bits)
+    output.write(samples.len() as u32, 32);
+    output.bytes.extend_from_slice(&[0u8; 16]); // MD5 unknown
+
+    output.bytes.extend_from_slice(&frames.bytes);
+    output.bytes
+}
+
+/// Opus frame length (20 ms)
+const OPUS_FRAME_MS: usize = 20;
+
+/// Target bitrate for speech chunks
+const OPUS_BITRATE: i32 = 24_000;
+
+/// Largest Opus packet (RFC 6716, one 20 ms frame)
+const MAX_OPUS_PACKET: usize = 1275;
+
+/// Ogg granule positions count 48 kHz samples whatever the input rate
+const OGG_GRANULE_RATE: u64 = 48_000;
+
+/// Serial number of the single logical stream in a chunk
+const OGG_STREAM_SERIAL: u32 = 0x5441_534B;
+
+/// Audio pages are flushed once their packets pass this size
+const OGG_PAGE_TARGET: usize = 4096;
+
+/// libopus encoder handle (mono)
+struct OpusEncoder(*mut unsafe_libopus::OpusEncoder);
+
+impl OpusEncoder {
+    fn new(sample_rate: u32) -> Result<Self, String> {
+        let mut error = 0;
+        // SAFETY: `error` outlives the call; a non-null result is owned by
+        // the returned handle and freed in Drop
+        let encoder = unsafe { opus_encoder_create(sample_rate as i32, 1, OPUS_APPLICATION_VOIP, &mut error) };
+        if encoder.is_null() || error != OPUS_OK {
+            return Err(format!("Failed to create Opus encoder at {} Hz (error {})", sample_rate, error));
+        }
+        let encoder = Self(encoder);
+        // SAFETY: the handle is valid until Drop
+        let result = unsafe { opus_encoder_ctl!(encoder.0, OPUS_SET_BITRATE_REQUEST, OPUS_BITRATE) };
+        if result != OPUS_OK {
+            return Err(format!("Failed to set Opus bitrate (error {})", result));
+        }
+        Ok(encoder)
+    }
+
+    /// Samples (at the input rate) the decoder output lags behind the input
+    fn lookahead(&mut self) -> Result<usize, String> {
+        let mut lookahead = 0i32;
+        // SAFETY: the handle is valid until Drop and `lookahead` outlives the call
+        let result = unsafe { opus_encoder_ctl!(self.0, OPUS_GET_LOOKAHEAD_REQUEST, &mut lookahead) };
+        if result != OPUS_OK {
+            return Err(format!("Failed to read Opus lookahead (error {})", result));
+        }
+        Ok(lookahead.max(0) as usize)
+    }
+
+    fn encode(&mut self, frame: &[i16]) -> Result<Vec<u8>, String> {
+        let mut packet = vec![0u8; MAX_OPUS_PACKET];
+        // SAFETY: `frame` holds exactly one frame of mono samples and
+        // `packet` has room for MAX_OPUS_PACKET bytes
+        let length = unsafe {
+            opus_encode(self.0, frame.as_ptr(), frame.len() as i32, packet.as_mut_ptr(), packet.len() as i32)
+        };
+        if length < 0 {
+            return Err(format!("Opus encoding failed (error {})", length));
+        }
+        packet.truncate(length as usize);
+        Ok(packet)
+    }
+}
+
+impl Drop for OpusEncoder {
+    fn drop(&mut self) {
+        // SAFETY: created by opus_encoder_create and destroyed only here
+        unsafe { opus_encoder_destroy(self.0) };
+    }
+}
+
+/// Ogg page checksum: CRC-32 with polynomial 0x04C11DB7, unreflected, zero init
+fn ogg_crc(data: &[u8]) -> u32 {
+    data.iter().fold(0u32, |mut crc, byte| {
+        crc ^= (*byte as u32) << 24;
+        for _ in 0..8 {
+            crc = if crc & 0x8000_0000 != 0 { (crc << 1) ^ 0x04C1_1DB7 } else { crc << 1 };
+        }
+        crc
+    })
+}
+
+struct OggWriter {
+    bytes: Vec<u8>,
+    sequence: u32,
+}
+
+impl OggWriter {
+    const BEGIN_OF_STREAM: u8 = 0x02;
+    const END_OF_STREAM: u8 = 0x04;
+
+    /// Write one page holding whole packets (at most 255 lacing values)
+    fn write_page(&mut self, packets: &[Vec<u8>], granule_position: u64, flags: u8) {
+        let mut lacing = Vec::new();
+        for packet in packets {
+            lacing.extend(std::iter::repeat(255u8).take(packet.len() / 255));
+            lacing.push((packet.len() % 255) as u8);
+        }
+
+        let start = self.bytes.len();
+        self.bytes.extend_from_slice(b"OggS");
+        self.bytes.push(0); // version
+        self.bytes.push(flags);
+        self.bytes.extend_from_slice(&granule_position.to_le_bytes());
+        self.bytes.extend_from_slice(&OGG_STREAM_SERIAL.to_le_bytes());
+        self.bytes.extend_from_slice(&self.sequence.to_le_bytes());
+        self.bytes.extend_from_slice(&[0u8; 4]); // CRC, filled in below
+        self.bytes.push(lacing.len() as u8);
+        self.bytes.extend_from_slice(&lacing);
+        for packet in packets {
+            self.bytes.extend_from_slice(packet);
+        }
+
+        let crc = ogg_crc(&self.bytes[start..]);
+        self.bytes[start + 22..start + 26].copy_from_slice(&crc.to_le_bytes());
+        self.sequence += 1;
+    }
+}
+
+/// Lacing values a packet needs on an Ogg page
+fn lacing_values(packet: &[u8]) -> usize {
+    packet.len() / 255 + 1
+}
+
+/// Encode mono 16-bit samples as Ogg Opus (8, 12, 16, 24 or 48 kHz input)
+pub fn encode_opus(samples: &[i16], sample_rate: u32) -> Result<Vec<u8>, String> {
+    let mut encoder = OpusEncoder::new(sample_rate)?;
+    let lookahead = encoder.lookahead()?;
+    let granule_scale = OGG_GRANULE_RATE / sample_rate as u64;
+    let pre_skip = lookahead as u64 * granule_scale;
+
+    let mut ogg = OggWriter { bytes: Vec::new(), sequence: 0 };
+
+    let mut head = b"OpusHead".to_vec();
+    head.push(1); // version
+    head.push(1); // channels
+    head.extend_from_slice(&(pre_skip as u16).to_le_bytes());
+    head.extend_from_slice(&sample_rate.to_le_bytes());
+    head.extend_from_slice(&0i16.to_le_bytes()); // output gain
+    head.push(0); // mapping family: mono/stereo
+    ogg.write_page(&[head], 0, OggWriter::BEGIN_OF_STREAM);
+
+    let vendor = b"Taskerino";
+    let mut tags = b"OpusTags".to_vec();
+    tags.extend_from_slice(&(vendor.len() as u32).to_le_bytes());
+    tags.extend_from_slice(vendor);
+    tags.extend_from_slice(&0u32.to_le_bytes()); // no comments
+    ogg.write_page(&[tags], 0, 0);
+
+    // Pad with silence so the encoder flushes the last real samples past its lookahead
+    let frame_samples = sample_rate as usize * OPUS_FRAME_MS / 1000;
+    let frame_count = (samples.len() + lookahead).div_ceil(frame_samples).max(1);
+    let mut padded = samples.to_vec();
+    padded.resize(frame_count * frame_samples, 0);
+
+    let final_granule = pre_skip + samples.len() as u64 * granule_scale;
+    let mut page: Vec<Vec<u8>> = Vec::new();
+    let mut page_bytes = 0;
+    let mut page_lacing = 0;
+    for (index, frame) in padded.chunks(frame_samples).enumerate() {
+        let packet = encoder.encode(frame)?;
+        let is_last = index + 1 == frame_count;
+        page_bytes += packet.len();
+        page_lacing += lacing_values(&packet);
+        page.push(packet);
+
+        // Room in the segment table for another packet of the largest size
+        let next_fits = page_lacing + MAX_OPUS_PACKET / 255 < 255;
+        if is_last {
+            ogg.write_page(&page, final_granule, OggWriter::END_OF_STREAM);
+        } else if page_bytes >= OGG_PAGE_TARGET || !next_fits {
+            let granule = ((index + 1) * frame_samples) as u64 * granule_scale;
+            ogg.write_page(&page, granule, 0);
+            page.clear();
+            page_bytes = 0;
+            page_lacing = 0;
+        }
+    }
+
+    Ok(ogg.bytes)
+}
+
+/// Constant bitrate for MP3 speech chunks
+const MP3_BITRATE: Bitrate = Bitrate::Kbps32;
+
+/// Encode mono 16-bit samples as constant-bitrate MP3 (8-48 kHz input)
+pub fn encode_mp3(samples: &[i16], sample_rate: u32) -> Result<Vec<u8>, String> {
+    let mut builder = mp3lame_encoder::Builder::new().ok_or("Failed to create MP3 encoder")?;
+    builder.set_num_channels(1).map_err(|e| format!("Failed to configure MP3 encoder: {}", e))?;
+    builder
+        .set_sample_rate(sample_rate)
+        .map_err(|e| format!("Failed to configure MP3 encoder at {} Hz: {}", sample_rate, e))?;
+    builder.set_brate(MP3_BITRATE).map_err(|e| format!("Failed to set MP3 bitrate: {}", e))?;
+    builder.set_quality(mp3lame_encoder::Quality::Good).map_err(|e| format!("Failed to set MP3 quality: {}", e))?;
+    let mut encoder = builder.build().map_err(|e| format!("Failed to create MP3 encoder: {}", e))?;
+
+    let mut mp3 = Vec::with_capacity(mp3lame_encoder::max_required_buffer_size(samples.len()));
+    encoder
+        .encode_to_vec(MonoPcm(samples), &mut mp3)
+        .map_err(|e| format!("MP3 encoding failed: {}", e))?;
+    // The last frames, padded out with silence
+    mp3.reserve(mp3lame_encoder::max_required_buffer_size(0));
+    encoder
+        .flush_to_vec::<FlushGap>(&mut mp3)
+        .map_err(|e| format!("MP3 encoding failed: {}", e))?;
+    Ok(mp3)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unsafe_libopus::{opus_decode, opus_decoder_create, opus_decoder_destroy};
+
+    struct BitReader<'a> {
+        data: &'a [u8],
+        position: usize,
+    }
+
+    impl BitReader<'_> {
+        fn read(&mut self, count: u32) -> u32 {
+            (0..count).fold(0u32, |value, _| {
+                let bit = (self.data[self.position / 8] >> (7 - self.position % 8)) & 1;
+                self.position += 1;
+                (value << 1) | bit as u32
+            })
+        }
+
+        fn read_signed(&mut self, count: u32) -> i32 {
+            let value = self.read(count);
+            ((value << (32 - count)) as i32) >> (32 - count)
+        }
+
+        fn read_unary(&mut self) -> u32 {
+            let mut zeros = 0;
+            while self.read(1) == 0 {
+                zeros += 1;
+            }
+            zeros
+        }
+
+        fn byte_position(&self) -> usize {
+            assert_eq!(self.position % 8, 0, "not byte aligned");
+            self.position / 8
+        }
+
+        fn align(&mut self) {
+            self.position = self.position.div_ceil(8) * 8;
+        }
+    }
+
+    struct StreamInfo {
+        min_block: u32,
+        max_block: u32,
+        sample_rate: u32,
+        channels: u32,
+        bits_per_sample: u32,
+        total_samples: u64,
+    }
+
+    /// Decoder for the subset encode_flac writes (mono, 16-bit, fixed/verbatim
+    /// subframes), checking the frame CRCs along the way
+    fn decode_flac(data: &[u8]) -> (StreamInfo, Vec<i16>) {
+        assert_eq!(&data[..4], b"fLaC");
+        let mut reader = BitReader { data, position: 32 };
+        assert_eq!(reader.read(1), 1, "STREAMINFO should be the last metadata block");
+        assert_eq!(reader.read(7), 0, "first block should be STREAMINFO");
+        assert_eq!(reader.read(24), 34);
+        let min_block = reader.read(16);
+        let max_block = reader.read(16);
+        reader.read(24);
+        reader.read(24);
+        let info = StreamInfo {
+            min_block,
+            max_block,
+            sample_rate: reader.read(20),
+            channels: reader.read(3) + 1,
+            bits_per_sample: reader.read(5) + 1,
+            total_samples: ((reader.read(4) as u64) << 32) | reader.read(32) as u64,
+        };
+        reader.position += 128; // MD5
+
+        let mut samples = Vec::new();
+        let mut expected_frame = 0;
+        while reader.position / 8 < data.len() {
+            let start = reader.byte_position();
+            assert_eq!(reader.read(14), 0b11111111111110, "frame sync");
+            reader.read(2);
+            assert_eq!(reader.read(4), 0b0111, "16-bit block size follows");
+            reader.read(4);
+            assert_eq!(reader.read(4), 0, "mono");
+            assert_eq!(reader.read(3), 0b100, "16 bits per sample");
+            reader.read(1);
+
+            let lead = reader.read(8);
+            let ones = (lead as u8).leading_ones();
+            let mut frame_number = lead & (0xFF >> (ones + 1));
+            for _ in 0..ones.saturating_sub(1) {
+                frame_number = (frame_number << 6) | (reader.read(8) & 0x3F);
+            }
+            assert_eq!(frame_number, expected_frame);
+            expected_frame += 1;
+
+            let block_size = reader.read(16) as usize + 1;
+            let header_end = reader.byte_position();
+            assert_eq!(reader.read(8) as u8, crc8(&data[start..header_end]), "header CRC");
+
+            assert_eq!(reader.read(1), 0, "subframe padding");
+            let subframe_type = reader.read(6);
+            assert_eq!(reader.read(1), 0, "wasted bits");
+            let mut block: Vec<i32> = Vec::with_capacity(block_size);
+            if subframe_type == 0b000001 {
+                block.extend((0..block_size).map(|_| reader.read_signed(16)));
+            } else {
+                assert_eq!(subframe_type & 0b111000, 0b001000, "fixed subframe");
+                let order = (subframe_type & 0b111) as usize;
+                block.extend((0..order).map(|_| reader.read_signed(16)));
+                assert_eq!(reader.read(2), 0, "4-bit Rice parameters");
+                assert_eq!(reader.read(4), 0, "partition order");
+                let parameter = reader.read(4);
+                for i in order..block_size {
+                    let quotient = reader.read_unary();
+                    let folded = (quotient << parameter) | reader.read(parameter);
+                    let residual = ((folded >> 1) as i32) ^ -((folded & 1) as i32);
+                    // With a zero placeholder the residual function yields -prediction
+                    block.push(0);
+                    block[i] = residual - fixed_residual(&block, order, i);
+                }
+            }
+            reader.align();
+            let frame_end = reader.byte_position();
+            assert_eq!(reader.read(16) as u16, crc16(&data[start..frame_end]), "frame CRC");
+
+            samples.extend(block.iter().map(|&sample| sample as i16));
+        }
+        (info, samples)
+    }
+
+    fn sine(count: usize, sample_rate: u32, frequency: f32, amplitude: f32) -> Vec<i16> {
+        (0..count)
+            .map(|i| {
+                let t = i as f32 / sample_rate as f32;
+                ((2.0 * std::f32::consts::PI * frequency * t).sin() * amplitude) as i16
+            })
+            .collect()
+    }
+
+    fn noise(count: usize) -> Vec<i16> {
+        let mut state = 0x2545_F491u32;
+        (0..count)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                state as i16
+            })
+            .collect()
+    }
+
+    #[test]
+    fn flac_header_describes_the_stream() {
+        let samples = sine(10_000, 16000, 440.0, 8000.0);
+        let (info, _) = decode_flac(&encode_flac(&samples, 16000));
+        assert_eq!(info.sample_rate, 16000);
+        assert_eq!(info.channels, 1);
+        assert_eq!(info.bits_per_sample, 16);
+        assert_eq!(info.total_samples, 10_000);
+        assert_eq!(info.min_block, BLOCK_SIZE as u32);
+        assert_eq!(info.max_block, BLOCK_SIZE as u32);
+
+        let (info, samples) = decode_flac(&encode_flac(&[], 16000));
+        assert_eq!(info.total_samples, 0);
+        assert!(samples.is_empty());
+    }
+
+    #[test]
+    fn flac_round_trip_is_lossless() {
+        let extremes: Vec<i16> = (0..5000).map(|i| if i % 2 == 0 { i16::MAX } else { i16::MIN }).collect();
+        let cases: Vec<(&str, Vec<i16>)> = vec![
+            ("silence", vec![0; 8192]),
+            ("sine", sine(20_000, 16000, 440.0, 12_000.0)),
+            ("noise", noise(9000)),
+            ("extremes", extremes),
+            ("single sample", vec![-1234]),
+            ("short", vec![1, -2, 3, -4, 5]),
+        ];
+        for (name, samples) in cases {
+            let (info, decoded) = decode_flac(&encode_flac(&samples, 16000));
+            assert_eq!(info.total_samples, samples.len() as u64, "{}", name);
+            assert_eq!(decoded, samples, "{}", name);
+        }
+    }
+
+    #[test]
+    fn flac_compresses_speech_like_audio() {
+        let samples = sine(16000 * 5, 16000, 220.0, 6000.0);
+        let flac = encode_flac(&samples, 16000);
+        // Under half the size of the 16-bit PCM
+        assert!(flac.len() < samples.len(), "{} bytes", flac.len());
+    }
+
+    #[test]
+    fn flac_frame_numbers_use_multi_byte_encoding() {
+        // 130 frames: numbers from 128 on take two bytes
+        let samples = noise(BLOCK_SIZE * 130 + 7);
+        let (_, decoded) = decode_flac(&encode_flac(&samples, 16000));
+        assert_eq!(decoded, samples);
+    }
+
+    struct OggPage {
+        flags: u8,
+        granule_position: u64,
+        sequence: u32,
+        packets: Vec<Vec<u8>>,
+    }
+
+    fn parse_ogg(data: &[u8]) -> Vec<OggPage> {
+        let mut pages = Vec::new();
+        let mut offset = 0;
+        while offset < data.len() {
+            let page = &data[offset..];
+            assert_eq!(&page[..4], b"OggS");
+            assert_eq!(page[4], 0, "version");
+            assert_eq!(u32::from_le_bytes(page[14..18].try_into().unwrap()), OGG_STREAM_SERIAL);
+            let segments = page[26] as usize;
+            let lacing = &page[27..27 + segments];
+            let length = 27 + segments + lacing.iter().map(|&l| l as usize).sum::<usize>();
+
+            let mut unchecked = page[..length].to_vec();
+            unchecked[22..26].fill(0);
+            assert_eq!(u32::from_le_bytes(page[22..26].try_into().unwrap()), ogg_crc(&unchecked), "page CRC");
+
+            let mut packets = Vec::new();
+            let mut packet = Vec::new();
+            let mut body = 27 + segments;
+            for &value in lacing {
+                packet.extend_from_slice(&page[body..body + value as usize]);
+                body += value as usize;
+                if value < 255 {
+                    packets.push(std::mem::take(&mut packet));
+                }
+            }
+            assert!(packet.is_empty(), "packets shouldn't continue across pages");
+
+            pages.push(OggPage {
+                flags: page[5],
+                granule_position: u64::from_le_bytes(page[6..14].try_into().unwrap()),
+                sequence: u32::from_le_bytes(page[18..22].try_into().unwrap()),
+                packets,
+            });
+            offset += length;
+        }
+        pages
+    }
+
+    #[test]
+    fn ogg_crc_matches_reference() {
+        // CRC-32/CKSUM without the final inversion, check input "123456789"
+        assert_eq!(ogg_crc(b"123456789"), 0x89A1_897F);
+        assert_eq!(ogg_crc(b""), 0);
+    }
+
+    #[test]
+    fn opus_stream_has_valid_pages_and_headers() {
+        let samples = sine(16000 * 3 + 123, 16000, 440.0, 8000.0);
+        let pages = parse_ogg(&encode_opus(&samples, 16000).unwrap());
+        assert!(pages.len() >= 3);
+
+        for (index, page) in pages.iter().enumerate() {
+            assert_eq!(page.sequence, index as u32);
+            assert_eq!(page.flags & OggWriter::BEGIN_OF_STREAM != 0, index == 0);
+            assert_eq!(page.flags & OggWriter::END_OF_STREAM != 0, index == pages.len() - 1);
+        }
+
+        let head = &pages[0].packets;
+        assert_eq!(head.len(), 1);
+        let head = &head[0];
+        assert_eq!(head.len(), 19);
+        assert_eq!(&head[..8], b"OpusHead");
+        assert_eq!(head[8], 1, "version");
+        assert_eq!(head[9], 1, "channels");
+        let pre_skip = u16::from_le_bytes([head[10], head[11]]) as u64;
+        assert!(pre_skip > 0);
+        assert_eq!(u32::from_le_bytes(head[12..16].try_into().unwrap()), 16000);
+        assert_eq!(head[18], 0, "mapping family");
+        assert_eq!(pages[0].granule_position, 0);
+
+        assert_eq!(pages[1].packets.len(), 1);
+        assert_eq!(&pages[1].packets[0][..8], b"OpusTags");
+        assert_eq!(pages[1].granule_position, 0);
+
+        let audio = &pages[2..];
+        let packets: usize = audio.iter().map(|page| page.packets.len()).sum();
+        let frame_granules = (OPUS_FRAME_MS as u64) * 48;
+        let final_granule = pre_skip + samples.len() as u64 * 3;
+        assert_eq!(audio.last().unwrap().granule_position, final_granule);
+        assert!(packets as u64 * frame_granules >= final_granule, "audio is fully covered");
+        assert!(audio.windows(2).all(|pair| pair[0].granule_position < pair[1].granule_position));
+    }
+
+    /// Decode Ogg Opus packets back to 16 kHz PCM, dropping the pre-skip
+    fn decode_opus(data: &[u8]) -> Vec<i16> {
+        let pages = parse_ogg(data);
+        let head = &pages[0].packets[0];
+        let pre_skip = u16::from_le_bytes([head[10], head[11]]) as usize / 3;
+        let total = (pages.last().unwrap().granule_position / 3) as usize;
+
+        let mut error = 0;
+        let decoder = unsafe { opus_decoder_create(16000, 1, &mut error) };
+        assert_eq!(error, OPUS_OK);
+        let mut pcm = Vec::new();
+        for packet in pages[2..].iter().flat_map(|page| &page.packets) {
+            let mut frame = vec![0i16; 16000 * OPUS_FRAME_MS / 1000];
+            let decoded = unsafe {
+                opus_decode(decoder, packet.as_ptr(), packet.len() as i32, frame.as_mut_ptr(), frame.len() as i32, 0)
+            };
+            assert!(decoded > 0, "decode error {}", decoded);
+            pcm.extend_from_slice(&frame[..decoded as usize]);
+        }
+        unsafe { opus_decoder_destroy(decoder) };
+        pcm[pre_skip..total].to_vec()
+    }
+
+    #[test]
+    fn opus_round_trip_keeps_the_signal() {
+        let samples = sine(16000 * 2, 16000, 440.0, 10_000.0);
+        let decoded = decode_opus(&encode_opus(&samples, 16000).unwrap());
+        assert_eq!(decoded.len(), samples.len());
+
+        // Lossy, but the waveform should line up with the input
+        let dot = |a: &[i16], b: &[i16]| a.iter().zip(b).map(|(&x, &y)| x as f64 * y as f64).sum::<f64>();
+        let steady = 1600..samples.len() - 1600;
+        let (original, decoded) = (&samples[steady.clone()], &decoded[steady]);
+        let correlation = dot(original, decoded) / (dot(original, original).sqrt() * dot(decoded, decoded).sqrt());
+        assert!(correlation > 0.9, "correlation {}", correlation);
+    }
+
+    #[test]
+    fn opus_is_much_smaller_than_wav() {
+        let samples = sine(16000 * 10, 16000, 220.0, 6000.0);
+        let opus = encode_opus(&samples, 16000).unwrap();
+        let wav_bytes = samples.len() * 2;
+        assert!(opus.len() * 8 < wav_bytes, "{} bytes vs {} bytes of PCM", opus.len(), wav_bytes);
+    }
+
+    #[test]
+    fn opus_handles_empty_input_and_rejects_unsupported_rates() {
+        let pages = parse_ogg(&encode_opus(&[], 16000).unwrap());
+        assert_eq!(pages.len(), 3);
+        let pre_skip = u16::from_le_bytes([pages[0].packets[0][10], pages[0].packets[0][11]]) as u64;
+        assert_eq!(pages[2].granule_position, pre_skip);
+
+        assert!(encode_opus(&[0; 441], 44100).is_err());
+    }
+
+    /// (sample rate, samples per frame, frame length) of each MP3 frame, from
+    /// the frame headers
+    fn parse_mp3_frames(data: &[u8]) -> Vec<(u32, usize, usize)> {
+        let mut frames = Vec::new();
+        let mut offset = 0;
+        while offset < data.len() {
+            let header = u32::from_be_bytes(data[offset..offset + 4].try_into().unwrap());
+            assert_eq!(header >> 21, 0x7FF, "no frame sync at byte {}", offset);
+            assert_eq!((header >> 17) & 0b11, 0b01, "not Layer III");
+            let (mpeg1, rates): (bool, [u32; 3]) = match (header >> 19) & 0b11 {
+                0b11 => (true, [44100, 48000, 32000]),
+                0b10 => (false, [22050, 24000, 16000]),
+                0b00 => (false, [11025, 12000, 8000]),
+                version => panic!("reserved MPEG version {}", version),
+            };
+            let bitrates: [u32; 15] = if mpeg1 {
+                [0, 32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320]
+            } else {
+                [0, 8, 16, 24, 32, 40, 48, 56, 64, 80, 96, 112, 128, 144, 160]
+            };
+            let bitrate = bitrates[((header >> 12) & 0xF) as usize] * 1000;
+            let sample_rate = rates[((header >> 10) & 0b11) as usize];
+            let padding = ((header >> 9) & 1) as usize;
+            let samples_per_frame = if mpeg1 { 1152 } else { 576 };
+            let length = (samples_per_frame / 8) * bitrate as usize / sample_rate as usize + padding;
+            assert_eq!(bitrate, 32_000);
+            frames.push((sample_rate, samples_per_frame, length));
+            offset += length;
+        }
+        assert_eq!(offset, data.len(), "frames don't tile the stream");
+        frames
+    }
+
+    #[test]
+    fn mp3_stream_is_whole_frames_covering_the_input() {
+        let samples = sine(16000 * 3, 16000, 440.0, 10_000.0);
+        let frames = parse_mp3_frames(&encode_mp3(&samples, 16000).unwrap());
+
+        assert!(frames.iter().all(|&(sample_rate, _, _)| sample_rate == 16000));
+        let encoded_samples: usize = frames.iter().map(|&(_, samples, _)| samples).sum();
+        assert!(encoded_samples >= samples.len(), "{} of {} samples", encoded_samples, samples.len());
+        // Encoder delay and padding add at most a few frames
+        assert!(encoded_samples < samples.len() + 4 * 576);
+    }
+
+    #[test]
+    fn mp3_is_much_smaller_than_wav() {
+        let samples = sine(16000 * 10, 16000, 220.0, 6000.0);
+        let mp3 = encode_mp3(&samples, 16000).unwrap();
+        let wav_bytes = samples.len() * 2;
+        assert!(mp3.len() * 7 < wav_bytes, "{} bytes vs {} bytes of PCM", mp3.len(), wav_bytes);
+    }
+}
@@ -0,0 +1,160 @@
+/**
+ * Audio Processing Module
+ *
+ * Processing stages for the mic path in audio_capture.rs.
+ *
+ * `NoiseSuppressor` (settings.audio.noiseSuppression, toggled live with
+ * `set_noise_suppression`) is applied to samples before they are buffered:
+ * a high-pass filter for rumble and hum, then a downward expander that tracks
+ * the background noise floor and attenuates audio close to it by up to
+ * SUPPRESSION_GAIN. Speech well above the floor passes unchanged.
+ *
+ * `AutomaticGainControl` (settings.audio.agcEnabled) is applied to each mic
+ * chunk before VAD and encoding: it steers speech towards AGC_TARGET_RMS
+ * (up to AGC_MAX_GAIN), backing off quickly on loud input and recovering
+ * slowly. Frames below AGC_MIN_LEVEL hold the gain, so silence isn't lifted.
+ */
+
+/// Level tracking frame
+const FRAME_MS: u32 = 10;
+/// High-pass cutoff (below the speech fundamental)
+const HIGH_PASS_HZ: f32 = 80.0;
+/// Gain applied to audio at the noise floor (-20 dB)
+const SUPPRESSION_GAIN: f32 = 0.1;
+/// Frame level relative to the noise floor at which suppression starts / ends
+const CLOSED_RATIO: f32 = 1.5;
+const OPEN_RATIO: f32 = 4.0;
+/// Per-frame rise of the noise floor estimate (~5%/s), so speech barely lifts it
+const FLOOR_RISE: f32 = 1.0005;
+const MIN_FLOOR: f32 = 1e-5;
+/// Gain smoothing: open quickly for speech onsets, close slowly after them
+const ATTACK_SECS: f32 = 0.005;
+const RELEASE_SECS: f32 = 0.1;
+
+/// AGC output level (-20 dBFS)
+const AGC_TARGET_RMS: f32 = 0.1;
+/// Gain range (+24 dB / -12 dB)
+const AGC_MAX_GAIN: f32 = 16.0;
+const AGC_MIN_GAIN: f32 = 0.25;
+/// Frames quieter than this (-60 dBFS) are treated as silence
+const AGC_MIN_LEVEL: f32 = 0.001;
+/// Gain smoothing: reduce quickly on loud input, raise slowly
+const AGC_ATTACK_SECS: f32 = 0.01;
+const AGC_RELEASE_SECS: f32 = 0.5;
+
+pub struct NoiseSuppressor {
+    frame_len: usize,
+    frame_energy: f32,
+    frame_samples: usize,
+    high_pass_alpha: f32,
+    previous_input: f32,
+    previous_output: f32,
+    /// None until the first frame
+    noise_floor: Option<f32>,
+    target_gain: f32,
+    gain: f32,
+    attack: f32,
+    release: f32,
+}
+
+impl NoiseSuppressor {
+    pub fn new(sample_rate: u32) -> Self {
+        let rate = sample_rate.max(1) as f32;
+        let rc = 1.0 / (2.0 * std::f32::consts::PI * HIGH_PASS_HZ);
+        Self {
+            frame_len: (sample_rate * FRAME_MS / 1000).max(1) as usize,
+            frame_energy: 0.0,
+            frame_samples: 0,
+            high_pass_alpha: rc / (rc + 1.0 / rate),
+            previous_input: 0.0,
+            previous_output: 0.0,
+            noise_floor: None,
+            target_gain: 1.0,
+            gain: 1.0,
+            attack: 1.0 - (-1.0 / (ATTACK_SECS * rate)).exp(),
+            release: 1.0 - (-1.0 / (RELEASE_SECS * rate)).exp(),
+        }
+    }
+
+    /// Process samples in place; state carries over between calls
+    pub fn process(&mut self, samples: &mut [f32]) {
+        for sample in samples.iter_mut() {
+            let input = *sample;
+            let filtered = self.high_pass_alpha * (self.previous_output + input - self.previous_input);
+            self.previous_input = input;
+            self.previous_output = filtered;
+
+            self.frame_energy += filtered * filtered;
+            self.frame_samples += 1;
+            if self.frame_samples == self.frame_len {
+                self.end_frame();
+            }
+
+            let smoothing = if self.target_gain > self.gain { self.attack } else { self.release };
+            self.gain += (self.target_gain - self.gain) * smoothing;
+            *sample = filtered * self.gain;
+        }
+    }
+
+    /// Update the noise floor from the finished frame and pick the gain for the next one
+    fn end_frame(&mut self) {
+        let rms = (self.frame_energy / self.frame_samples as f32).sqrt();
+        self.frame_energy = 0.0;
+        self.frame_samples = 0;
+
+        // Falls quickly to quiet frames, rises slowly otherwise
+        let noise_floor = match self.noise_floor {
+            None => rms,
+            Some(floor) if rms < floor => floor + (rms - floor) * 0.5,
+            Some(floor) => floor * FLOOR_RISE,
+        }
+        .max(MIN_FLOOR);
+        self.noise_floor = Some(noise_floor);
+
+        let ratio = rms / noise_floor;
+        let open = ((ratio - CLOSED_RATIO) / (OPEN_RATIO - CLOSED_RATIO)).clamp(0.0, 1.0);
+        self.target_gain = SUPPRESSION_GAIN + (1.0 - SUPPRESSION_GAIN) * open;
+    }
+}
+
+pub struct AutomaticGainControl {
+    frame_len: usize,
+    target_gain: f32,
+    gain: f32,
+    attack: f32,
+    release: f32,
+}
+
+impl AutomaticGainControl {
+    pub fn new(sample_rate: u32) -> Self {
+        let rate = sample_rate.max(1) as f32;
+        Self {
+            frame_len: (sample_rate * FRAME_MS / 1000).max(1) as usize,
+            target_gain: 1.0,
+            gain: 1.0,
+            attack: 1.0 - (-1.0 / (AGC_ATTACK_SECS * rate)).exp(),
+            release: 1.0 - (-1.0 / (AGC_RELEASE_SECS * rate)).exp(),
+        }
+    }
+
+    /// Currently applied gain (linear)
+    pub fn gain(&self) -> f32 {
+        self.gain
+    }
+
+    /// Process samples in place; state carries over between calls
+    pub fn process(&mut self, samples: &mut [f32]) {
+        for frame in samples.chunks_mut(self.frame_len) {
+            let rms = (frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32).sqrt();
+            if rms >= AGC_MIN_LEVEL {
+                self.target_gain = (AGC_TARGET_RMS / rms).clamp(AGC_MIN_GAIN, AGC_MAX_GAIN);
+            }
+
+            for sample in frame.iter_mut() {
+                let smoothing = if self.target_gain < self.gain { self.attack } else { self.release };
+                self.gain += (self.target_gain - self.gain) * smoothing;
+                *sample = (*sample * self.gain).clamp(-1.0, 1.0);
+            }
+        }
+    }
+}
@@ -0,0 +1,70 @@
+/**
+ * Automation Module
+ *
+ * Rust side of the AppleScript / Shortcuts bridge (macOS only). The Cocoa
+ * script commands in ScreenRecorder/AutomationBridge.swift (dictionary in
+ * Taskerino.sdef) call back into `handle_command`, which runs the same
+ * control commands as taskerino-cli (control_server.rs):
+ *
+ *   tell application "Taskerino"
+ *       start session named "Standup"
+ *       quick capture
+ *       active session -- JSON text, or missing value
+ *       stop session
+ *   end tell
+ *
+ * Shortcuts reach these through the "Run AppleScript" action.
+ */
+
+use serde_json::{json, Value};
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::sync::OnceLock;
+use tauri::AppHandle;
+
+use crate::control_server;
+
+/// App handle for the script command callbacks (set once in setup)
+static APP: OnceLock<AppHandle> = OnceLock::new();
+
+// FFI declarations for Swift functions
+extern "C" {
+    fn automation_bridge_install(
+        handler: extern "C" fn(*const c_char, *const c_char) -> *mut c_char,
+        free: extern "C" fn(*mut c_char),
+    );
+}
+
+/// Runs on the main thread (Cocoa scripting); returns the JSON response
+/// envelope, freed by `free_response`
+extern "C" fn handle_command(command: *const c_char, args: *const c_char) -> *mut c_char {
+    let command = unsafe { CStr::from_ptr(command) }.to_string_lossy().to_string();
+    let args: Value = serde_json::from_str(&unsafe { CStr::from_ptr(args) }.to_string_lossy())
+        .unwrap_or_else(|_| json!({}));
+
+    let result = match APP.get() {
+        Some(app) => {
+            println!("🍎 [AUTOMATION] {}", command);
+            tauri::async_runtime::block_on(control_server::execute(app, &command, args))
+        }
+        None => Err("Taskerino is still starting".to_string()),
+    };
+    let response = control_server::envelope(&command, result);
+
+    CString::new(response.to_string())
+        .map(CString::into_raw)
+        .unwrap_or(std::ptr::null_mut())
+}
+
+extern "C" fn free_response(response: *mut c_char) {
+    if !response.is_null() {
+        drop(unsafe { CString::from_raw(response) });
+    }
+}
+
+/// Route AppleScript commands to this app instance
+pub fn install(app: AppHandle) {
+    if APP.set(app).is_ok() {
+        unsafe { automation_bridge_install(handle_command, free_response) };
+    }
+}
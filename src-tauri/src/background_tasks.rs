@@ -0,0 +1,213 @@
+/**
+ * Background Tasks Module
+ *
+ * Shared Tokio runtime + named task registry for long-running background work
+ * (tray countdown, audio chunk processing, ...):
+ * - One managed runtime with a tunable worker pool (settings.performance.workerThreads)
+ * - Tasks are registered by name; spawning a task with an existing name replaces it
+ * - Every task receives a `ShutdownSignal` and is expected to exit when it fires
+ * - `shutdown()` signals all tasks, waits briefly, then aborts stragglers
+ */
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::runtime::Runtime;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+use crate::command_metrics;
+
+/// Cooperative cancellation handle passed to every registered task
+#[derive(Clone)]
+pub struct ShutdownSignal {
+    receiver: watch::Receiver<bool>,
+}
+
+impl ShutdownSignal {
+    /// Resolves once the task should stop
+    pub async fn cancelled(&mut self) {
+        while !*self.receiver.borrow() {
+            if self.receiver.changed().await.is_err() {
+                return; // Sender dropped - treat as cancelled
+            }
+        }
+    }
+
+    /// Non-blocking check for synchronous work (e.g. inside spawn_blocking)
+    pub fn is_cancelled(&self) -> bool {
+        *self.receiver.borrow()
+    }
+}
+
+struct TaskEntry {
+    handle: JoinHandle<()>,
+    cancel: watch::Sender<bool>,
+    started_at: String,
+}
+
+/// Task info reported to the frontend
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskInfo {
+    pub name: String,
+    pub started_at: String,
+    pub finished: bool,
+}
+
+/// Managed registry of named background tasks
+pub struct TaskRegistry {
+    runtime: Mutex<Option<Runtime>>,
+    tasks: Mutex<HashMap<String, TaskEntry>>,
+}
+
+impl TaskRegistry {
+    pub fn new() -> Self {
+        Self {
+            runtime: Mutex::new(None),
+            tasks: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Build the worker runtime (called once in setup, after settings are loaded)
+    pub fn start(&self, worker_threads: usize) -> Result<(), String> {
+        let mut runtime = self.runtime.lock()
+            .map_err(|e| format!("Failed to lock runtime: {}", e))?;
+        if runtime.is_some() {
+            return Ok(());
+        }
+
+        *runtime = Some(
+            tokio::runtime::Builder::new_multi_thread()
+                .worker_threads(worker_threads.max(1))
+                .thread_name("taskerino-worker")
+                .enable_all()
+                .build()
+                .map_err(|e| format!("Failed to build background runtime: {}", e))?,
+        );
+
+        println!("🧵 [TASKS] Background runtime started ({} workers)", worker_threads.max(1));
+        Ok(())
+    }
+
+    /// Spawn a named task; an existing task with the same name is cancelled first
+    pub fn spawn<F, Fut>(&self, name: &str, task: F) -> Result<(), String>
+    where
+        F: FnOnce(ShutdownSignal) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let (cancel, receiver) = watch::channel(false);
+        let future = task(ShutdownSignal { receiver });
+
+        let handle = {
+            let runtime = self.runtime.lock()
+                .map_err(|e| format!("Failed to lock runtime: {}", e))?;
+            runtime
+                .as_ref()
+                .ok_or("Background runtime not started")?
+                .spawn(future)
+        };
+
+        let previous = self.tasks.lock()
+            .map_err(|e| format!("Failed to lock task registry: {}", e))?
+            .insert(name.to_string(), TaskEntry {
+                handle,
+                cancel,
+                started_at: chrono::Utc::now().to_rfc3339(),
+            });
+
+        if let Some(previous) = previous {
+            let _ = previous.cancel.send(true);
+        }
+
+        println!("🧵 [TASKS] Spawned task '{}'", name);
+        Ok(())
+    }
+
+    /// Signal a task to stop (it exits at its next cancellation point)
+    pub fn cancel(&self, name: &str) {
+        if let Ok(mut tasks) = self.tasks.lock() {
+            if let Some(entry) = tasks.remove(name) {
+                let _ = entry.cancel.send(true);
+            }
+        }
+    }
+
+    /// Drop the entry of a task that has finished on its own
+    pub fn forget(&self, name: &str) {
+        if let Ok(mut tasks) = self.tasks.lock() {
+            tasks.remove(name);
+        }
+    }
+
+    pub fn list(&self) -> Vec<TaskInfo> {
+        let tasks = match self.tasks.lock() {
+            Ok(tasks) => tasks,
+            Err(_) => return vec![],
+        };
+
+        let mut info: Vec<TaskInfo> = tasks
+            .iter()
+            .map(|(name, entry)| TaskInfo {
+                name: name.clone(),
+                started_at: entry.started_at.clone(),
+                finished: entry.handle.is_finished(),
+            })
+            .collect();
+        info.sort_by(|a, b| a.name.cmp(&b.name));
+        info
+    }
+
+    /// Signal every task, give them `grace` to finish, then tear down the runtime
+    pub fn shutdown(&self, grace: Duration) {
+        let entries: Vec<(String, TaskEntry)> = match self.tasks.lock() {
+            Ok(mut tasks) => tasks.drain().collect(),
+            Err(_) => vec![],
+        };
+
+        let runtime = match self.runtime.lock() {
+            Ok(mut runtime) => runtime.take(),
+            Err(_) => None,
+        };
+        let runtime = match runtime {
+            Some(runtime) => runtime,
+            None => return,
+        };
+
+        println!("🧵 [TASKS] Shutting down {} background tasks...", entries.len());
+        for (_, entry) in &entries {
+            let _ = entry.cancel.send(true);
+        }
+
+        runtime.block_on(async {
+            for (name, entry) in entries {
+                let abort = entry.handle.abort_handle();
+                if tokio::time::timeout(grace, entry.handle).await.is_err() {
+                    eprintln!("⚠️  [TASKS] Task '{}' did not stop in time, aborting", name);
+                    abort.abort();
+                }
+            }
+        });
+
+        runtime.shutdown_timeout(grace);
+        println!("✅ [TASKS] Background runtime stopped");
+    }
+}
+
+impl Default for TaskRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Tauri command to list registered background tasks
+#[tauri::command]
+pub fn list_background_tasks(
+    registry: tauri::State<Arc<TaskRegistry>>,
+) -> Result<Vec<TaskInfo>, String> {
+    command_metrics::track("list_background_tasks", || {
+        Ok(registry.list())
+    })
+}
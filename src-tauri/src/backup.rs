@@ -0,0 +1,470 @@
+/**
+ * Backup Module
+ *
+ * Snapshots session data to a folder (e.g. on an external drive) so a lost or
+ * corrupted data dir can be restored:
+ * - A backup is a folder `taskerino-backup-<timestamp>/` holding
+ *   `manifest.json` (size and SHA-256 of every file) and the files under
+ *   `data/`, at their paths relative to the app data dir
+ * - It covers every profile's sessions.json, session index, change logs,
+ *   diarization and attachment metadata/OCR text, plus the frontend's `db/`
+ *   collections; attachment data (screenshots, audio) only with `includeMedia`
+ * - Backups are written as `.<name>.partial` and renamed when complete, so a
+ *   backup interrupted halfway is never listed or restored
+ * - The "scheduled-backup" task creates one every settings.backup.intervalHours
+ *   while enabled (skipped while the folder's drive isn't mounted) and keeps
+ *   the newest `keep`
+ * - `restore_backup` verifies every file against the manifest before
+ *   copying anything back, then re-indexes sessions and emits
+ *   `backup-restored`
+ */
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::background_tasks::TaskRegistry;
+use crate::command_metrics;
+use crate::profiles;
+use crate::session_index::{SessionIndex, INDEX_DB_FILE};
+use crate::session_storage::SESSION_LOGS_DIR;
+use crate::settings::{BackupSettings, SettingsManager};
+
+const TASK_NAME: &str = "scheduled-backup";
+const CHECK_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+const BACKUP_FORMAT: &str = "taskerino-backup";
+const BACKUP_VERSION: u32 = 1;
+const BACKUP_PREFIX: &str = "taskerino-backup-";
+const MANIFEST_FILE: &str = "manifest.json";
+const DATA_DIR: &str = "data";
+
+/// Frontend collections (TauriFileSystemAdapter.ts) in the app data dir
+const FRONTEND_DB_DIR: &str = "db";
+
+/// One backup at a time (scheduled and manual)
+static BACKUP_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BackupFile {
+    /// Relative to the app data dir, `/`-separated
+    path: String,
+    size: u64,
+    sha256: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BackupManifest {
+    format: String,
+    version: u32,
+    created_at: String,
+    includes_media: bool,
+    files: Vec<BackupFile>,
+}
+
+/// A backup in the backup folder
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupInfo {
+    pub path: String,
+    pub created_at: String,
+    pub includes_media: bool,
+    pub files: usize,
+    pub bytes: u64,
+}
+
+/// What `restore_backup` copied back
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreReport {
+    pub files: usize,
+    pub bytes: u64,
+}
+
+fn app_data_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))
+}
+
+/// Files in `dir` (not recursive) whose names match `include`
+fn files_in(dir: &Path, include: impl Fn(&str) -> bool) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_file()))
+        .filter(|entry| include(&entry.file_name().to_string_lossy()))
+        .map(|entry| entry.path())
+        .collect()
+}
+
+/// Everything a backup covers, as absolute paths
+fn collect_files(app_data_dir: &Path, profile_dirs: &[PathBuf], include_media: bool) -> Vec<PathBuf> {
+    let mut files = files_in(&app_data_dir.join(FRONTEND_DB_DIR), |name| {
+        name.ends_with(".json") && !name.ends_with(".tmp.json") && !name.ends_with(".backup.json")
+    });
+    for dir in profile_dirs {
+        files.extend(
+            ["sessions.json", INDEX_DB_FILE]
+                .iter()
+                .map(|name| dir.join(name))
+                .filter(|path| path.is_file()),
+        );
+        files.extend(files_in(&dir.join(SESSION_LOGS_DIR), |name| name.ends_with(".jsonl")));
+        files.extend(files_in(&dir.join("diarization"), |name| name.ends_with(".json")));
+        files.extend(files_in(&dir.join("attachments"), |name| {
+            name.ends_with(".meta.json") || name.ends_with(".ocr.txt") || (include_media && name.ends_with(".dat"))
+        }));
+    }
+    files.sort();
+    files.dedup();
+    files
+}
+
+/// Copy `source` to `target`, returning its size and SHA-256
+fn copy_hashed(source: &Path, target: &Path) -> Result<(u64, String), String> {
+    let mut input = File::open(source).map_err(|e| format!("Failed to open {:?}: {}", source, e))?;
+    let mut output = File::create(target).map_err(|e| format!("Failed to create {:?}: {}", target, e))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    let mut size = 0u64;
+    loop {
+        let read = input.read(&mut buffer).map_err(|e| format!("Failed to read {:?}: {}", source, e))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+        output
+            .write_all(&buffer[..read])
+            .map_err(|e| format!("Failed to write {:?}: {}", target, e))?;
+        size += read as u64;
+    }
+    output.sync_all().map_err(|e| format!("Failed to write {:?}: {}", target, e))?;
+    Ok((size, format!("{:x}", hasher.finalize())))
+}
+
+fn hash_file(path: &Path) -> Result<(u64, String), String> {
+    let mut input = File::open(path).map_err(|e| format!("Failed to open {:?}: {}", path, e))?;
+    let mut hasher = Sha256::new();
+    let size = std::io::copy(&mut input, &mut hasher).map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+    Ok((size, format!("{:x}", hasher.finalize())))
+}
+
+/// A manifest path as a path under `root`, refusing anything that escapes it
+fn resolve(root: &Path, relative: &str) -> Result<PathBuf, String> {
+    let mut path = root.to_path_buf();
+    for part in relative.split('/') {
+        if part.is_empty() || part == "." || part == ".." || part.contains('\\') || part.contains(':') {
+            return Err(format!("Invalid path in backup manifest: {}", relative));
+        }
+        path.push(part);
+    }
+    Ok(path)
+}
+
+fn read_manifest(backup_dir: &Path) -> Result<BackupManifest, String> {
+    let content = std::fs::read_to_string(backup_dir.join(MANIFEST_FILE))
+        .map_err(|e| format!("Not a Taskerino backup ({:?}): {}", backup_dir, e))?;
+    let manifest: BackupManifest =
+        serde_json::from_str(&content).map_err(|e| format!("Invalid backup manifest: {}", e))?;
+    if manifest.format != BACKUP_FORMAT {
+        return Err("Not a Taskerino backup".to_string());
+    }
+    if manifest.version > BACKUP_VERSION {
+        return Err(format!(
+            "Backup version {} is newer than supported (v{}). Please update Taskerino.",
+            manifest.version, BACKUP_VERSION
+        ));
+    }
+    Ok(manifest)
+}
+
+fn backup_info(backup_dir: &Path, manifest: &BackupManifest) -> BackupInfo {
+    BackupInfo {
+        path: backup_dir.to_string_lossy().to_string(),
+        created_at: manifest.created_at.clone(),
+        includes_media: manifest.includes_media,
+        files: manifest.files.len(),
+        bytes: manifest.files.iter().map(|file| file.size).sum(),
+    }
+}
+
+/// Complete backups in `directory`, newest first
+fn list(directory: &Path) -> Vec<BackupInfo> {
+    let Ok(entries) = std::fs::read_dir(directory) else {
+        return Vec::new();
+    };
+    let mut backups: Vec<BackupInfo> = entries
+        .flatten()
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with(BACKUP_PREFIX))
+        .filter_map(|entry| {
+            let path = entry.path();
+            read_manifest(&path).ok().map(|manifest| backup_info(&path, &manifest))
+        })
+        .collect();
+    backups.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    backups
+}
+
+/// Delete all but the newest `keep` backups; returns how many were deleted
+fn rotate(directory: &Path, keep: usize) -> usize {
+    list(directory)
+        .into_iter()
+        .skip(keep)
+        .filter(|backup| match std::fs::remove_dir_all(&backup.path) {
+            Ok(()) => true,
+            Err(e) => {
+                eprintln!("⚠️  [BACKUP] Failed to delete old backup {}: {}", backup.path, e);
+                false
+            }
+        })
+        .count()
+}
+
+/// Write a new backup into `directory` (blocking)
+fn create(app_data_dir: &Path, profile_dirs: &[PathBuf], directory: &Path, include_media: bool) -> Result<BackupInfo, String> {
+    let _guard = BACKUP_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    std::fs::create_dir_all(directory).map_err(|e| format!("Failed to create backup folder: {}", e))?;
+
+    let name = format!("{}{}", BACKUP_PREFIX, chrono::Local::now().format("%Y%m%d-%H%M%S"));
+    let partial_dir = directory.join(format!(".{}.partial", name));
+    let backup_dir = directory.join(&name);
+    if backup_dir.exists() {
+        return Err(format!("Backup {} already exists", name));
+    }
+    let _ = std::fs::remove_dir_all(&partial_dir);
+
+    let result = (|| {
+        let mut manifest = BackupManifest {
+            format: BACKUP_FORMAT.to_string(),
+            version: BACKUP_VERSION,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            includes_media: include_media,
+            files: Vec::new(),
+        };
+        for source in collect_files(app_data_dir, profile_dirs, include_media) {
+            let Ok(relative) = source.strip_prefix(app_data_dir) else {
+                continue;
+            };
+            let relative = relative
+                .components()
+                .map(|part| part.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            let target = resolve(&partial_dir.join(DATA_DIR), &relative)?;
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {:?}: {}", parent, e))?;
+            }
+            let (size, sha256) = copy_hashed(&source, &target)?;
+            manifest.files.push(BackupFile { path: relative, size, sha256 });
+        }
+
+        let content = serde_json::to_vec_pretty(&manifest)
+            .map_err(|e| format!("Failed to serialize backup manifest: {}", e))?;
+        std::fs::write(partial_dir.join(MANIFEST_FILE), content)
+            .map_err(|e| format!("Failed to write backup manifest: {}", e))?;
+        std::fs::rename(&partial_dir, &backup_dir).map_err(|e| format!("Failed to finish backup: {}", e))?;
+        Ok(backup_info(&backup_dir, &manifest))
+    })();
+    if result.is_err() {
+        let _ = std::fs::remove_dir_all(&partial_dir);
+    }
+    result
+}
+
+/// Check every file of a backup against its manifest
+fn verify(backup_dir: &Path, manifest: &BackupManifest) -> Result<(), String> {
+    let data_dir = backup_dir.join(DATA_DIR);
+    let damaged: Vec<&str> = manifest
+        .files
+        .iter()
+        .filter(|file| {
+            resolve(&data_dir, &file.path)
+                .and_then(|path| hash_file(&path))
+                .map_or(true, |(size, sha256)| size != file.size || sha256 != file.sha256)
+        })
+        .map(|file| file.path.as_str())
+        .collect();
+    match damaged.first() {
+        None => Ok(()),
+        Some(first) => Err(format!(
+            "Backup failed verification: {} file(s) missing or corrupt (e.g. {})",
+            damaged.len(),
+            first
+        )),
+    }
+}
+
+/// Verify a backup, then copy its files back into the app data dir (blocking)
+fn restore(backup_dir: &Path, app_data_dir: &Path) -> Result<RestoreReport, String> {
+    let _guard = BACKUP_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let manifest = read_manifest(backup_dir)?;
+    verify(backup_dir, &manifest)?;
+
+    let data_dir = backup_dir.join(DATA_DIR);
+    let mut report = RestoreReport { files: 0, bytes: 0 };
+    for file in &manifest.files {
+        let source = resolve(&data_dir, &file.path)?;
+        let target = resolve(app_data_dir, &file.path)?;
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {:?}: {}", parent, e))?;
+        }
+
+        // Replace each file via temp file + rename so none is left half-written
+        let mut temp_name = target.file_name().unwrap_or_default().to_os_string();
+        temp_name.push(".restore.tmp");
+        let temp = target.with_file_name(temp_name);
+        copy_hashed(&source, &temp)?;
+        std::fs::rename(&temp, &target).map_err(|e| {
+            let _ = std::fs::remove_file(&temp);
+            format!("Failed to restore {}: {}", file.path, e)
+        })?;
+        report.files += 1;
+        report.bytes += file.size;
+    }
+    Ok(report)
+}
+
+fn backup_directory(settings: &BackupSettings, directory: Option<String>) -> Result<PathBuf, String> {
+    let directory = directory.unwrap_or_else(|| settings.directory.clone());
+    if directory.trim().is_empty() {
+        return Err("Choose a backup folder first".to_string());
+    }
+    Ok(PathBuf::from(directory))
+}
+
+/// Create a backup and rotate old ones (blocking)
+fn create_and_rotate(app: &AppHandle, directory: &Path, include_media: bool, keep: u32) -> Result<BackupInfo, String> {
+    let app_data_dir = app_data_dir(app)?;
+    let profile_dirs = profiles::all_profile_data_dirs(app)?;
+    let info = create(&app_data_dir, &profile_dirs, directory, include_media)?;
+    let removed = rotate(directory, keep as usize);
+    println!(
+        "💾 [BACKUP] Backed up {} file(s) ({} MB) to {}{}",
+        info.files,
+        info.bytes / 1024 / 1024,
+        info.path,
+        if removed > 0 { format!(", deleted {} old backup(s)", removed) } else { String::new() }
+    );
+    Ok(info)
+}
+
+/// Whether a scheduled backup is due in `directory`
+fn backup_due(directory: &Path, interval_hours: u32) -> bool {
+    let Some(newest) = list(directory).into_iter().next() else {
+        return true;
+    };
+    chrono::DateTime::parse_from_rfc3339(&newest.created_at).map_or(true, |created| {
+        chrono::Utc::now().signed_duration_since(created) >= chrono::Duration::hours(interval_hours as i64)
+    })
+}
+
+/// Start creating scheduled backups in the background
+pub fn start(app: AppHandle, registry: &TaskRegistry) -> Result<(), String> {
+    registry.spawn(TASK_NAME, |mut shutdown| async move {
+        let mut interval = tokio::time::interval(CHECK_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+
+            let settings = app.state::<Arc<SettingsManager>>().get().backup;
+            if !settings.enabled || settings.directory.trim().is_empty() {
+                continue;
+            }
+            let directory = PathBuf::from(&settings.directory);
+            // External drive not mounted: try again later (never create the
+            // folder, which would put it on the boot disk)
+            if !directory.is_dir() {
+                continue;
+            }
+
+            let app = app.clone();
+            let result = tokio::task::spawn_blocking(move || {
+                if !backup_due(&directory, settings.interval_hours) {
+                    return Ok(());
+                }
+                create_and_rotate(&app, &directory, settings.include_media, settings.keep).map(|_| ())
+            })
+            .await;
+            match result {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => eprintln!("⚠️  [BACKUP] Scheduled backup failed: {}", e),
+                Err(e) => eprintln!("⚠️  [BACKUP] Backup task failed: {}", e),
+            }
+        }
+    })
+}
+
+/// Tauri command to back up now (to settings.backup.directory unless
+/// `directory` is given; media per settings unless `include_media` is given)
+#[tauri::command]
+pub async fn create_backup(
+    app: AppHandle,
+    settings: State<'_, Arc<SettingsManager>>,
+    directory: Option<String>,
+    include_media: Option<bool>,
+) -> Result<BackupInfo, String> {
+    command_metrics::track_async("create_backup", async move {
+        let settings = settings.get().backup;
+        let directory = backup_directory(&settings, directory)?;
+        let include_media = include_media.unwrap_or(settings.include_media);
+        tokio::task::spawn_blocking(move || create_and_rotate(&app, &directory, include_media, settings.keep))
+            .await
+            .map_err(|e| format!("Backup task failed: {}", e))?
+    }).await
+}
+
+/// Tauri command to list the backups in a folder (default: the configured
+/// one), newest first
+#[tauri::command]
+pub async fn list_backups(
+    settings: State<'_, Arc<SettingsManager>>,
+    directory: Option<String>,
+) -> Result<Vec<BackupInfo>, String> {
+    command_metrics::track_async("list_backups", async move {
+        let directory = backup_directory(&settings.get().backup, directory)?;
+        tokio::task::spawn_blocking(move || list(&directory))
+            .await
+            .map_err(|e| format!("Backup task failed: {}", e))
+    }).await
+}
+
+/// Tauri command to restore a backup folder after verifying it; the frontend
+/// should reload afterwards so in-memory state doesn't overwrite the
+/// restored files
+#[tauri::command]
+pub async fn restore_backup(
+    app: AppHandle,
+    index: State<'_, Arc<SessionIndex>>,
+    path: String,
+) -> Result<RestoreReport, String> {
+    command_metrics::track_async("restore_backup", async move {
+        let index = index.inner().clone();
+        let app_data_dir = app_data_dir(&app)?;
+        let profile_dirs = profiles::all_profile_data_dirs(&app)?;
+        let report = tokio::task::spawn_blocking(move || {
+            let report = restore(Path::new(&path), &app_data_dir)?;
+            for dir in &profile_dirs {
+                index.invalidate(dir)?;
+            }
+            Ok::<_, String>(report)
+        })
+        .await
+        .map_err(|e| format!("Restore task failed: {}", e))??;
+
+        println!("♻️  [BACKUP] Restored {} file(s) from backup", report.files);
+        let _ = app.emit("backup-restored", &report);
+        Ok(report)
+    }).await
+}
@@ -0,0 +1,108 @@
+/**
+ * Benchmark Support Module
+ *
+ * Thin public wrappers around internal hot paths so the Criterion suite in
+ * `benches/hot_paths.rs` can exercise them. Only compiled with `--features bench`.
+ */
+
+use screenshots::image::{Rgba, RgbaImage};
+
+use crate::audio_capture::AudioRecorder;
+use crate::audio_encoding::ChunkFormat;
+use crate::session_index::{SessionDb, SessionQuery};
+use crate::session_models::{Session, SessionSummary};
+use crate::settings::ScreenshotSettings;
+
+/// Synthetic RGBA frame (gradient + noise-ish pattern so JPEG has real work)
+pub fn synthetic_frame(width: u32, height: u32) -> RgbaImage {
+    RgbaImage::from_fn(width, height, |x, y| {
+        let v = ((x * 7) ^ (y * 13)) as u8;
+        Rgba([(x % 256) as u8, (y % 256) as u8, v, 255])
+    })
+}
+
+/// Downscale + JPEG encode path used by `capture_all_screens_composite`
+pub fn encode_composite(frame: RgbaImage) -> Result<Vec<u8>, String> {
+    crate::encode_composite_jpeg(frame, &ScreenshotSettings::default())
+}
+
+/// Synthetic mono audio (a few mixed tones)
+pub fn synthetic_audio(seconds: u32, sample_rate: u32) -> Vec<f32> {
+    (0..seconds * sample_rate)
+        .map(|i| {
+            let t = i as f32 / sample_rate as f32;
+            0.4 * (t * 440.0 * std::f32::consts::TAU).sin() + 0.2 * (t * 1320.0 * std::f32::consts::TAU).sin()
+        })
+        .collect()
+}
+
+/// Resample to 16kHz (part of every audio chunk)
+pub fn resample(samples: &[f32], source_rate: u32) -> Vec<f32> {
+    AudioRecorder::resample_to_16khz(samples, source_rate)
+}
+
+/// Resample + 16-bit WAV encode of one audio chunk
+pub fn encode_wav_chunk(samples: &[f32], sample_rate: u32) -> Result<Vec<u8>, String> {
+    AudioRecorder::samples_to_wav(samples, sample_rate, 1)
+}
+
+/// Resample + FLAC encode of one audio chunk
+pub fn encode_flac_chunk(samples: &[f32], sample_rate: u32) -> Result<Vec<u8>, String> {
+    AudioRecorder::encode_chunk(samples, sample_rate, ChunkFormat::Flac)
+}
+
+/// Resample + Ogg Opus encode of one audio chunk
+pub fn encode_opus_chunk(samples: &[f32], sample_rate: u32) -> Result<Vec<u8>, String> {
+    AudioRecorder::encode_chunk(samples, sample_rate, ChunkFormat::Opus)
+}
+
+/// Resample + MP3 encode of one audio chunk
+pub fn encode_mp3_chunk(samples: &[f32], sample_rate: u32) -> Result<Vec<u8>, String> {
+    AudioRecorder::encode_chunk(samples, sample_rate, ChunkFormat::Mp3)
+}
+
+/// sessions.json-shaped document with `count` sessions
+pub fn synthetic_sessions_json(count: usize) -> String {
+    let sessions: Vec<serde_json::Value> = (0..count)
+        .map(|i| {
+            serde_json::json!({
+                "id": format!("session-{}", i),
+                "name": format!("Client work block {}", i),
+                "startTime": "2025-01-01T09:00:00Z",
+                "endTime": "2025-01-01T11:00:00Z",
+                "duration": 7200,
+                "category": if i % 3 == 0 { "Deep Work" } else { "Meetings" },
+                "screenshots": (0..60).map(|s| serde_json::json!({
+                    "id": format!("shot-{}-{}", i, s),
+                    "attachmentId": format!("att-{}-{}", i, s),
+                    "timestamp": "2025-01-01T09:02:00Z",
+                    "relativeTime": s as f64 * 120.0,
+                })).collect::<Vec<_>>(),
+                "notes": format!("Reviewed quarterly roadmap and follow-ups #{}", i),
+                "transcript": "Discussed priorities for the next sprint and open questions.",
+            })
+        })
+        .collect();
+    serde_json::to_string(&sessions).unwrap_or_default()
+}
+
+pub fn parse_sessions(json: &str) -> Result<Vec<Session>, String> {
+    serde_json::from_str(json).map_err(|e| e.to_string())
+}
+
+pub fn serialize_sessions(sessions: &[Session]) -> Result<String, String> {
+    serde_json::to_string(sessions).map_err(|e| e.to_string())
+}
+
+/// Build the session index (in-memory SQLite: rows + trigram full-text index)
+pub fn index_sessions(sessions: Vec<Session>) -> SessionDb {
+    let mut db = SessionDb::open_in_memory().expect("in-memory session index");
+    db.replace_sessions(sessions).expect("index sessions");
+    db
+}
+
+/// Indexed search used by `search_sessions`
+pub fn search(index: &SessionDb, query: &str) -> Vec<SessionSummary> {
+    let query = SessionQuery { query: Some(query.to_string()), ..SessionQuery::default() };
+    index.query(&query).map(|page| page.sessions).unwrap_or_default()
+}
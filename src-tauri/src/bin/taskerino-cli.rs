@@ -0,0 +1,135 @@
+/**
+ * taskerino-cli
+ *
+ * Headless control of the running Taskerino app over its local IPC socket
+ * (see control_server.rs). Results are printed as JSON on stdout; errors go
+ * to stderr with exit code 1.
+ *
+ *   taskerino-cli start [--name NAME]
+ *   taskerino-cli stop | pause | resume
+ *   taskerino-cli capture
+ *   taskerino-cli sessions [--query TEXT] [--category NAME] [--sort-by FIELD]
+ *                          [--sort-order asc|desc] [--limit N] [--offset N]
+ *   taskerino-cli session <id>
+ *   taskerino-cli active
+ */
+
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Write};
+
+/// Must match `identifier` in tauri.conf.json (Tauri's app data dir name)
+#[cfg(unix)]
+const APP_IDENTIFIER: &str = "com.taskerino.desktop";
+
+/// Must match control_server.rs
+#[cfg(unix)]
+const SOCKET_FILE: &str = "control.sock";
+#[cfg(windows)]
+const PIPE_NAME: &str = r"\\.\pipe\taskerino-control";
+
+const USAGE: &str = "Usage: taskerino-cli <command> [options]
+
+Commands:
+  start [--name NAME]     Start a session
+  stop                    Stop the active session
+  pause                   Pause the active session
+  resume                  Resume the active session
+  capture                 Add a screenshot to the active session
+  sessions [options]      List sessions (--query, --category, --sort-by,
+                          --sort-order, --limit, --offset)
+  session <id>            Show one session
+  active                  Show the active session (null if none)";
+
+/// Build the control request for the command line
+fn parse_args(args: &[String]) -> Result<Value, String> {
+    let (command, rest) = args.split_first().ok_or(USAGE)?;
+
+    let mut options = serde_json::Map::new();
+    let mut positional = Vec::new();
+    let mut rest = rest.iter();
+    while let Some(arg) = rest.next() {
+        match arg.strip_prefix("--") {
+            Some(flag) => {
+                let value = rest.next().ok_or_else(|| format!("Missing value for {}", arg))?;
+                let key = match flag {
+                    "name" | "query" | "category" | "limit" | "offset" => flag,
+                    "sort-by" => "sortBy",
+                    "sort-order" => "sortOrder",
+                    _ => return Err(format!("Unknown option: {}\n\n{}", arg, USAGE)),
+                };
+                let value = match key {
+                    "limit" | "offset" => json!(value
+                        .parse::<usize>()
+                        .map_err(|_| format!("Invalid {}: {}", arg, value))?),
+                    _ => json!(value),
+                };
+                options.insert(key.to_string(), value);
+            }
+            None => positional.push(arg.clone()),
+        }
+    }
+
+    let args = match (command.as_str(), positional.as_slice()) {
+        ("start" | "sessions", []) => Value::Object(options),
+        ("stop" | "pause" | "resume" | "capture" | "active", []) if options.is_empty() => json!({}),
+        ("session", [id]) if options.is_empty() => json!({ "id": id }),
+        ("help" | "--help" | "-h", _) => return Err(USAGE.to_string()),
+        _ => return Err(format!("Invalid arguments for {}\n\n{}", command, USAGE)),
+    };
+    Ok(json!({ "command": command, "args": args }))
+}
+
+#[cfg(unix)]
+fn connect() -> Result<std::os::unix::net::UnixStream, String> {
+    let path = dirs::data_dir()
+        .ok_or("Failed to get app data dir")?
+        .join(APP_IDENTIFIER)
+        .join(SOCKET_FILE);
+    std::os::unix::net::UnixStream::connect(&path)
+        .map_err(|e| format!("Taskerino is not running ({}: {})", path.display(), e))
+}
+
+#[cfg(windows)]
+fn connect() -> Result<std::fs::File, String> {
+    std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(PIPE_NAME)
+        .map_err(|e| format!("Taskerino is not running ({}: {})", PIPE_NAME, e))
+}
+
+fn send(request: &Value) -> Result<Value, String> {
+    let mut stream = connect()?;
+    let mut line = request.to_string();
+    line.push('\n');
+    stream
+        .write_all(line.as_bytes())
+        .map_err(|e| format!("Failed to send request: {}", e))?;
+
+    let mut response = String::new();
+    BufReader::new(stream)
+        .read_line(&mut response)
+        .map_err(|e| format!("Failed to read response: {}", e))?;
+    let response: Value = serde_json::from_str(&response)
+        .map_err(|e| format!("Invalid response from Taskerino: {}", e))?;
+
+    if response["ok"].as_bool() == Some(true) {
+        Ok(response["result"].clone())
+    } else {
+        Err(response["error"].as_str().unwrap_or("Unknown error").to_string())
+    }
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = parse_args(&args).and_then(|request| send(&request));
+    match result {
+        Ok(result) => {
+            println!("{}", serde_json::to_string_pretty(&result).unwrap_or_default());
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
+}
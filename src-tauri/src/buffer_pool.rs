@@ -0,0 +1,91 @@
+/**
+ * Buffer Pool Module
+ *
+ * Recycles pixel buffers between screenshot captures so each interval doesn't
+ * reallocate tens of MB for the composite canvas and RGB conversion:
+ * - `take(len)` hands out a zeroed buffer, reusing the best-fitting pooled one
+ * - `recycle(buf)` returns it; the pool keeps at most a few buffers
+ * - `prewarm(len)` pre-sizes a buffer for the current display configuration
+ * - Encoded output is pre-sized from the previous capture's size
+ */
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::OnceLock;
+
+use crate::safe_state::SafeState;
+
+/// Buffers kept between captures (composite RGBA + RGB conversion + spare)
+const MAX_POOLED_BUFFERS: usize = 3;
+
+pub struct BufferPool {
+    buffers: SafeState<Vec<Vec<u8>>>,
+    last_encoded_len: AtomicUsize,
+}
+
+/// Global pool shared by the capture pipeline
+pub fn pool() -> &'static BufferPool {
+    static POOL: OnceLock<BufferPool> = OnceLock::new();
+    POOL.get_or_init(|| BufferPool {
+        buffers: SafeState::new("buffer_pool", Vec::new()),
+        last_encoded_len: AtomicUsize::new(0),
+    })
+}
+
+impl BufferPool {
+    /// Zero-filled buffer of `len` bytes (smallest pooled buffer that fits, else a new one)
+    pub fn take(&self, len: usize) -> Vec<u8> {
+        let reused = {
+            let mut buffers = self.buffers.lock();
+            buffers
+                .iter()
+                .enumerate()
+                .filter(|(_, buf)| buf.capacity() >= len)
+                .min_by_key(|(_, buf)| buf.capacity())
+                .map(|(index, _)| index)
+                .map(|index| buffers.swap_remove(index))
+        };
+
+        let mut buf = reused.unwrap_or_else(|| Vec::with_capacity(len));
+        buf.clear();
+        buf.resize(len, 0);
+        buf
+    }
+
+    /// Return a buffer for reuse; the smallest buffer is evicted when the pool is full
+    pub fn recycle(&self, buf: Vec<u8>) {
+        if buf.capacity() == 0 {
+            return;
+        }
+
+        let mut buffers = self.buffers.lock();
+        buffers.push(buf);
+        if buffers.len() > MAX_POOLED_BUFFERS {
+            if let Some(index) = buffers
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, buf)| buf.capacity())
+                .map(|(index, _)| index)
+            {
+                buffers.swap_remove(index);
+            }
+        }
+    }
+
+    /// Make sure a buffer of at least `len` bytes is pooled
+    pub fn prewarm(&self, len: usize) {
+        let has_fit = self.buffers.lock().iter().any(|buf| buf.capacity() >= len);
+        if !has_fit {
+            self.recycle(Vec::with_capacity(len));
+        }
+    }
+
+    /// Output buffer sized from the previous encode (avoids regrowth while encoding)
+    pub fn encode_buffer(&self) -> Vec<u8> {
+        let last = self.last_encoded_len.load(Ordering::Relaxed);
+        Vec::with_capacity(last + last / 4)
+    }
+
+    pub fn record_encoded_len(&self, len: usize) {
+        self.last_encoded_len.store(len, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,310 @@
+/**
+ * Calendar Module
+ *
+ * Reads macOS calendars through EventKit (ScreenRecorder/CalendarBridge.swift)
+ * and can start a session when a meeting begins:
+ * - `get_calendar_permission` / `request_calendar_permission`: EventKit access
+ * - `get_upcoming_calendar_events`: events in the next few hours, with the
+ *   video-call link (Zoom, Meet, Teams, Webex) found in the URL, location or notes
+ * - With settings.calendar.autoStartMeetings, the "calendar-watcher" task
+ *   starts a session (via control_server) when an event with a call link has
+ *   just begun and nothing is recording. The session is named after the
+ *   event and tagged with "meeting" plus the attendees; each event occurrence
+ *   triggers at most once. `calendar-session-started` is emitted with the event.
+ *
+ * Other platforms report `unsupported` and never auto-start.
+ */
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashSet;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::background_tasks::TaskRegistry;
+use crate::command_metrics;
+use crate::control_server;
+use crate::settings::SettingsManager;
+
+const TASK_NAME: &str = "calendar-watcher";
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How long after an event starts it can still auto-start a session
+const START_GRACE_SECS: i64 = 5 * 60;
+
+const DEFAULT_UPCOMING_HOURS: u32 = 24;
+
+// FFI declarations for Swift functions
+#[cfg(target_os = "macos")]
+extern "C" {
+    fn calendar_authorization_status() -> i32;
+    fn calendar_request_access() -> bool;
+    fn calendar_events_json(start: f64, end: f64) -> *const std::os::raw::c_char;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub enum CalendarPermission {
+    NotDetermined,
+    Restricted,
+    Denied,
+    Authorized,
+    /// Granted without read access (macOS 14+); events can't be listed
+    WriteOnly,
+    Unsupported,
+}
+
+/// Event as returned by CalendarBridge.swift
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawEvent {
+    id: String,
+    title: String,
+    /// Unix seconds
+    start: f64,
+    end: f64,
+    all_day: bool,
+    calendar: String,
+    attendees: Vec<String>,
+    location: Option<String>,
+    notes: Option<String>,
+    url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarEvent {
+    pub id: String,
+    pub title: String,
+    /// RFC 3339
+    pub start: String,
+    pub end: String,
+    pub all_day: bool,
+    pub calendar: String,
+    /// Attendee display names
+    pub attendees: Vec<String>,
+    pub location: Option<String>,
+    /// Video-call link, if any
+    pub meeting_url: Option<String>,
+    #[serde(skip)]
+    start_secs: i64,
+    #[serde(skip)]
+    end_secs: i64,
+}
+
+fn meeting_link_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(
+            r"https://(?:[\w-]+\.)?zoom\.us/(?:j|my|w)/[^\s<>]+|https://meet\.google\.com/[a-z]+-[a-z]+-[a-z]+|https://teams\.microsoft\.com/l/meetup-join/[^\s<>]+|https://(?:[\w-]+\.)?webex\.com/[^\s<>]+",
+        )
+        .expect("invalid meeting link pattern")
+    })
+}
+
+impl From<RawEvent> for CalendarEvent {
+    fn from(raw: RawEvent) -> Self {
+        let meeting_url = [&raw.url, &raw.location, &raw.notes]
+            .into_iter()
+            .flatten()
+            .find_map(|text| meeting_link_pattern().find(text))
+            .map(|link| link.as_str().to_string());
+        let rfc3339 = |secs: i64| {
+            chrono::DateTime::from_timestamp(secs, 0)
+                .map(|time| time.to_rfc3339())
+                .unwrap_or_default()
+        };
+        let start_secs = raw.start as i64;
+        let end_secs = raw.end as i64;
+
+        Self {
+            id: raw.id,
+            title: raw.title,
+            start: rfc3339(start_secs),
+            end: rfc3339(end_secs),
+            all_day: raw.all_day,
+            calendar: raw.calendar,
+            attendees: raw.attendees,
+            location: raw.location,
+            meeting_url,
+            start_secs,
+            end_secs,
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn permission() -> CalendarPermission {
+    match unsafe { calendar_authorization_status() } {
+        0 => CalendarPermission::NotDetermined,
+        1 => CalendarPermission::Restricted,
+        2 => CalendarPermission::Denied,
+        3 => CalendarPermission::Authorized,
+        _ => CalendarPermission::WriteOnly,
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn permission() -> CalendarPermission {
+    CalendarPermission::Unsupported
+}
+
+/// Events overlapping [start, end] (Unix seconds); blocks on EventKit
+#[cfg(target_os = "macos")]
+fn fetch_events(start: i64, end: i64) -> Result<Vec<CalendarEvent>, String> {
+    if permission() != CalendarPermission::Authorized {
+        return Err("Calendar access not granted".to_string());
+    }
+
+    let pointer = unsafe { calendar_events_json(start as f64, end as f64) };
+    if pointer.is_null() {
+        return Err("Failed to read calendar events".to_string());
+    }
+    let content = unsafe { std::ffi::CStr::from_ptr(pointer).to_string_lossy().into_owned() };
+
+    // Free the C string (allocated by Swift's strdup)
+    unsafe {
+        libc::free(pointer as *mut libc::c_void);
+    }
+
+    let events: Vec<RawEvent> = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse calendar events: {}", e))?;
+    Ok(events.into_iter().map(CalendarEvent::from).collect())
+}
+
+#[cfg(not(target_os = "macos"))]
+fn fetch_events(_start: i64, _end: i64) -> Result<Vec<CalendarEvent>, String> {
+    Err("Calendar access is only available on macOS".to_string())
+}
+
+/// Meeting that has just begun on a watched calendar
+fn starting_meeting<'a>(
+    events: &'a [CalendarEvent],
+    calendars: &[String],
+    triggered: &HashSet<String>,
+    now: i64,
+) -> Option<&'a CalendarEvent> {
+    events
+        .iter()
+        .filter(|event| !event.all_day && event.meeting_url.is_some())
+        .filter(|event| event.start_secs <= now && now - event.start_secs <= START_GRACE_SECS && event.end_secs > now)
+        .filter(|event| calendars.is_empty() || calendars.iter().any(|c| c.eq_ignore_ascii_case(&event.calendar)))
+        .filter(|event| !triggered.contains(&occurrence_key(event)))
+        .min_by_key(|event| now - event.start_secs)
+}
+
+/// Recurring events share an id, so key on id + start
+fn occurrence_key(event: &CalendarEvent) -> String {
+    format!("{}@{}", event.id, event.start_secs)
+}
+
+async fn auto_start(app: &AppHandle, event: &CalendarEvent) -> Result<(), String> {
+    let mut tags = vec!["meeting".to_string()];
+    tags.extend(event.attendees.iter().cloned());
+    let description = if event.attendees.is_empty() {
+        format!("Calendar: {}", event.calendar)
+    } else {
+        format!("Attendees: {}", event.attendees.join(", "))
+    };
+
+    control_server::execute(
+        app,
+        "start",
+        json!({ "name": event.title, "description": description, "tags": tags }),
+    )
+    .await?;
+    let _ = app.emit("calendar-session-started", event);
+    Ok(())
+}
+
+/// Start the meeting watcher (macOS only)
+pub fn start(app: AppHandle, registry: &TaskRegistry) -> Result<(), String> {
+    if permission() == CalendarPermission::Unsupported {
+        return Ok(());
+    }
+
+    registry.spawn(TASK_NAME, |mut shutdown| async move {
+        let mut triggered: HashSet<String> = HashSet::new();
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+
+            let settings = app.state::<Arc<SettingsManager>>().get().calendar;
+            if !settings.auto_start_meetings || permission() != CalendarPermission::Authorized {
+                continue;
+            }
+
+            let now = chrono::Utc::now().timestamp();
+            let events = match tokio::task::spawn_blocking(move || fetch_events(now - START_GRACE_SECS, now + 60)).await {
+                Ok(Ok(events)) => events,
+                Ok(Err(e)) => {
+                    eprintln!("⚠️  [CALENDAR] {}", e);
+                    continue;
+                }
+                Err(e) => {
+                    eprintln!("⚠️  [CALENDAR] Event task failed: {}", e);
+                    continue;
+                }
+            };
+
+            if let Some(event) = starting_meeting(&events, &settings.calendars, &triggered, now) {
+                // Mark first so a failed start (e.g. a session already running) isn't retried every poll
+                triggered.insert(occurrence_key(event));
+                match auto_start(&app, event).await {
+                    Ok(()) => println!("📅 [CALENDAR] Started session for \"{}\"", event.title),
+                    Err(e) => println!("📅 [CALENDAR] Not starting \"{}\": {}", event.title, e),
+                }
+            }
+            // Keys for meetings that have ended can't match again
+            triggered.retain(|key| {
+                events.iter().any(|event| &occurrence_key(event) == key)
+            });
+        }
+    })
+}
+
+/// Tauri command to check calendar access
+#[tauri::command]
+pub fn get_calendar_permission() -> Result<CalendarPermission, String> {
+    command_metrics::track("get_calendar_permission", || {
+        Ok(permission())
+    })
+}
+
+/// Tauri command to request calendar access (shows the system dialog once)
+#[tauri::command]
+pub async fn request_calendar_permission() -> Result<bool, String> {
+    command_metrics::track_async("request_calendar_permission", async move {
+        #[cfg(target_os = "macos")]
+        {
+            tokio::task::spawn_blocking(|| unsafe { calendar_request_access() })
+                .await
+                .map_err(|e| format!("Calendar permission task failed: {}", e))
+        }
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err("Calendar access is only available on macOS".to_string())
+        }
+    }).await
+}
+
+/// Tauri command to list events starting in the next `hours` (default 24),
+/// including ones already in progress
+#[tauri::command]
+pub async fn get_upcoming_calendar_events(hours: Option<u32>) -> Result<Vec<CalendarEvent>, String> {
+    command_metrics::track_async("get_upcoming_calendar_events", async move {
+        let now = chrono::Utc::now().timestamp();
+        let end = now + i64::from(hours.unwrap_or(DEFAULT_UPCOMING_HOURS)) * 3600;
+        let mut events = tokio::task::spawn_blocking(move || fetch_events(now, end))
+            .await
+            .map_err(|e| format!("Calendar task failed: {}", e))??;
+        events.sort_by_key(|event| event.start_secs);
+        Ok(events)
+    }).await
+}
@@ -1,13 +1,17 @@
+use crate::ai_budget::{AiProvider, BudgetManager, UsageRecord};
+use crate::ai_cache::{self, AiCache};
+use crate::ai_client;
 use crate::ai_types::*;
-use reqwest::Client;
+use crate::command_metrics;
+use crate::api_keys;
+use crate::redaction::{self, eprintln_redacted, println_redacted};
 use serde_json::json;
 use futures_util::StreamExt;
-use tauri::Emitter;
-use tauri_plugin_store::StoreExt;
-use std::time::Duration;
+use tauri::{Emitter, Manager};
+use std::sync::Arc;
 
-const CLAUDE_API_BASE: &str = "https://api.anthropic.com/v1";
-const ANTHROPIC_VERSION: &str = "2023-06-01";
+pub(crate) const CLAUDE_API_BASE: &str = "https://api.anthropic.com/v1";
+pub(crate) const ANTHROPIC_VERSION: &str = "2023-06-01";
 
 /// Claude chat completion (non-streaming) with automatic retry for transient errors
 #[tauri::command]
@@ -15,22 +19,23 @@ pub async fn claude_chat_completion(
     app: tauri::AppHandle,
     request: ClaudeChatRequest,
 ) -> Result<ClaudeChatResponse, String> {
-    let store = app.store("api_keys.json")
-        .map_err(|e| format!("Failed to access store: {}", e))?;
-
-    let api_key = match store.get("claude_api_key") {
-        Some(value) => value.as_str()
-            .ok_or("Claude API key not set. Please add your API key in Settings.")?
-            .to_string(),
-        None => return Err("Claude API key not set. Please add your API key in Settings.".to_string())
-    };
-
-    let client = Client::builder()
-        .timeout(Duration::from_secs(1200))         // 20 min total timeout (for large canvas generation)
-        .connect_timeout(Duration::from_secs(30))   // 30 sec to establish connection
-        .read_timeout(Duration::from_secs(900))     // 15 min to read response (large sessions can take 5-10 min)
-        .build()
-        .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
+    command_metrics::track_async("claude_chat_completion", async move {
+        send_chat_completion(app, request)
+            .await
+            .map_err(|e| redaction::redact(&e.to_string()))
+    }).await
+}
+
+/// Non-streaming completion; also used by the AI router
+pub async fn send_chat_completion(
+    app: tauri::AppHandle,
+    request: ClaudeChatRequest,
+) -> Result<ClaudeChatResponse, ProviderError> {
+    let api_key = api_keys::get_api_key(&app, "claude_api_key")?
+        .ok_or("Claude API key not set. Please add your API key in Settings.")?;
+
+    let budget = app.state::<Arc<BudgetManager>>();
+    budget.check(AiProvider::Claude, request.priority)?;
 
     let mut request_body = json!({
         "model": request.model,
@@ -46,110 +51,81 @@ pub async fn claude_chat_completion(
         request_body["temperature"] = json!(temperature);
     }
 
-    // Retry logic for transient errors (520, 502, 503, 504)
-    let max_retries = 3;
-    let mut last_error = String::new();
-
-    for attempt in 0..max_retries {
-        if attempt > 0 {
-            // Exponential backoff: 1s, 2s, 4s
-            let delay_ms = 1000 * (2_u64.pow(attempt as u32));
-            println!("Retrying Claude API request (attempt {}/{}) after {}ms delay...", attempt + 1, max_retries, delay_ms);
-            tokio::time::sleep(tokio::time::Duration::from_millis(delay_ms)).await;
-        }
-
-        let response = match client
-            .post(&format!("{}/messages", CLAUDE_API_BASE))
+    // Rate limits, 5xx and network errors are retried by the shared client
+    let response = ai_client::send_with_retry(&app, "claude", |client| {
+        client
+            .post(format!("{}/messages", CLAUDE_API_BASE))
             .header("x-api-key", &api_key)
             .header("anthropic-version", ANTHROPIC_VERSION)
             .header("anthropic-beta", "prompt-caching-2024-07-31")
             .header("Content-Type", "application/json")
             .json(&request_body)
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => {
-                last_error = format!("Network error: {}", e);
-                continue; // Retry on network errors
-            }
-        };
+    })
+    .await?;
 
-        let status = response.status();
-        let status_code = status.as_u16();
+    let status = response.status();
+    let status_code = status.as_u16();
 
-        // Don't retry auth errors - fail immediately
-        if status_code == 401 {
-            return Err("Invalid Claude API key. Please check your key in Settings.".to_string());
-        }
+    if status_code == 401 {
+        return Err("Invalid Claude API key. Please check your key in Settings.".into());
+    }
 
-        // Don't retry rate limits - fail immediately with helpful message
-        if status_code == 429 {
-            return Err("Claude rate limit exceeded. Please try again later.".to_string());
-        }
+    if status_code == 429 {
+        return Err(ProviderError::RateLimited("Claude rate limit exceeded. Please try again later.".to_string()));
+    }
 
-        // Retry on server errors (500-599) and Cloudflare errors (520-527)
-        if status_code >= 500 || (status_code >= 520 && status_code < 530) {
-            let error_text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
-
-            last_error = match status_code {
-                502 => "Anthropic API gateway error (502). The API is temporarily unavailable.".to_string(),
-                503 => "Anthropic API service unavailable (503). The service is temporarily down.".to_string(),
-                504 => "Anthropic API timeout (504). The request took too long.".to_string(),
-                520 => "Cloudflare error (520). Anthropic's servers are temporarily unreachable.".to_string(),
-                521 => "Cloudflare error (521). Anthropic's servers are down.".to_string(),
-                _ => format!("Server error ({}): {}", status_code, error_text),
-            };
-
-            println!("Transient error on attempt {}: {}", attempt + 1, last_error);
-            continue; // Retry
-        }
+    if !status.is_success() {
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
 
-        // Other non-success status codes - fail immediately
-        if !status.is_success() {
-            let error_text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
-            return Err(format!("Claude API error ({}): {}", status, error_text));
+        return Err(match status_code {
+            502 => "Anthropic API gateway error (502). The API is temporarily unavailable. Please try again in a few moments.".to_string(),
+            503 => "Anthropic API service unavailable (503). The service is temporarily down. Please try again in a few moments.".to_string(),
+            504 => "Anthropic API timeout (504). The request took too long. Please try again in a few moments.".to_string(),
+            520 => "Cloudflare error (520). Anthropic's servers are temporarily unreachable. Please try again in a few moments.".to_string(),
+            521 => "Cloudflare error (521). Anthropic's servers are down. Please try again in a few moments.".to_string(),
+            _ => format!("Claude API error ({}): {}", status, error_text),
         }
+        .into());
+    }
 
-        // Success - parse and return response
-        let claude_response: ClaudeChatResponse = response
-            .json()
-            .await
-            .map_err(|e| format!("Failed to parse response: {}", e))?;
-
-        // Check for truncation (stop_reason: "max_tokens")
-        if let Some(stop_reason) = &claude_response.stop_reason {
-            if stop_reason == "max_tokens" {
-                eprintln!("⚠️  WARNING: Claude response truncated due to max_tokens limit!");
-                eprintln!("   Requested: {} tokens", request.max_tokens);
-                eprintln!("   Output tokens used: {}", claude_response.usage.output_tokens);
-                return Err(format!(
-                    "Response truncated: hit max_tokens limit of {}. Output used {} tokens. Increase token limit or implement chunking.",
-                    request.max_tokens,
-                    claude_response.usage.output_tokens
-                ));
-            }
-        }
+    // Success - parse and return response
+    let claude_response: ClaudeChatResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))?;
+
+    budget.record(&app, AiProvider::Claude, &claude_response.model, &UsageRecord {
+        input_tokens: claude_response.usage.input_tokens as u64,
+        output_tokens: claude_response.usage.output_tokens as u64,
+        cache_creation_input_tokens: claude_response.usage.cache_creation_input_tokens.unwrap_or(0) as u64,
+        cache_read_input_tokens: claude_response.usage.cache_read_input_tokens.unwrap_or(0) as u64,
+        ..Default::default()
+    });
 
-        return Ok(claude_response);
+    // Check for truncation (stop_reason: "max_tokens")
+    if let Some(stop_reason) = &claude_response.stop_reason {
+        if stop_reason == "max_tokens" {
+            eprintln_redacted!("⚠️  WARNING: Claude response truncated due to max_tokens limit!");
+            eprintln_redacted!("   Requested: {} tokens", request.max_tokens);
+            eprintln_redacted!("   Output tokens used: {}", claude_response.usage.output_tokens);
+            return Err(format!(
+                "Response truncated: hit max_tokens limit of {}. Output used {} tokens. Increase token limit or implement chunking.",
+                request.max_tokens,
+                claude_response.usage.output_tokens
+            ).into());
+        }
     }
 
-    // All retries exhausted
-    Err(format!(
-        "Claude API request failed after {} attempts. Last error: {}. Please try again in a few moments.",
-        max_retries,
-        last_error
-    ))
+    Ok(claude_response)
 }
 
 /// Claude chat completion with vision support (for screenshots and image attachments)
+/// Responses are cached by image content (ai_cache.rs); `force_refresh` skips the cache
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn claude_chat_completion_vision(
     app: tauri::AppHandle,
     model: String,
@@ -157,16 +133,56 @@ pub async fn claude_chat_completion_vision(
     messages: Vec<ClaudeMessage>,
     system: Option<String>,
     temperature: Option<f32>,
+    priority: Option<RequestPriority>,
+    force_refresh: Option<bool>,
 ) -> Result<ClaudeChatResponse, String> {
-    let request = ClaudeChatRequest {
-        model,
-        max_tokens,
-        messages,
-        system: system.map(|s| serde_json::Value::String(s)),
-        temperature,
-    };
-
-    claude_chat_completion(app, request).await
+    command_metrics::track_async("claude_chat_completion_vision", async move {
+        let cache = app.state::<Arc<AiCache>>().inner().clone();
+
+        // Hashing decodes every image, so keep it off the async runtime
+        let fingerprint = {
+            let (model, messages, system) = (model.clone(), messages.clone(), system.clone());
+            tokio::task::spawn_blocking(move || {
+                ai_cache::fingerprint(&model, max_tokens, &messages, system.as_deref(), temperature)
+            })
+            .await
+            .map_err(|e| format!("AI cache task failed: {}", e))?
+        };
+
+        if let (Some(fingerprint), false) = (&fingerprint, force_refresh.unwrap_or(false)) {
+            let (cache, app, fingerprint) = (cache.clone(), app.clone(), fingerprint.clone());
+            let cached = tokio::task::spawn_blocking(move || cache.lookup(&app, &fingerprint))
+                .await
+                .map_err(|e| format!("AI cache task failed: {}", e))?
+                .and_then(|response| serde_json::from_value::<ClaudeChatResponse>(response).ok());
+            if let Some(response) = cached {
+                println!("💾 [AI CACHE] Hit for vision request ({})", model);
+                return Ok(response);
+            }
+        }
+
+        let request = ClaudeChatRequest {
+            model,
+            max_tokens,
+            messages,
+            system: system.map(|s| serde_json::Value::String(s)),
+            temperature,
+            priority: priority.unwrap_or_default(),
+        };
+
+        let response = send_chat_completion(app.clone(), request)
+            .await
+            .map_err(|e| redaction::redact(&e.to_string()))?;
+
+        if let (Some(fingerprint), Ok(value)) = (fingerprint, serde_json::to_value(&response)) {
+            let stored = tokio::task::spawn_blocking(move || cache.store(&app, fingerprint, value)).await;
+            if let Ok(Err(e)) = stored {
+                eprintln!("⚠️  [AI CACHE] Failed to cache vision response: {}", e);
+            }
+        }
+
+        Ok(response)
+    }).await
 }
 
 /// Claude streaming chat completion (for Ned chat)
@@ -177,24 +193,19 @@ pub async fn claude_chat_completion_stream(
     stream_id: String,
     request: ClaudeStreamingRequest,
 ) -> Result<(), String> {
-    let store = app.store("api_keys.json")
-        .map_err(|e| format!("Failed to access store: {}", e))?;
-
-    let api_key = match store.get("claude_api_key") {
-        Some(value) => value.as_str()
-            .ok_or("Claude API key not set. Please add your API key in Settings.")?
-            .to_string(),
-        None => return Err("Claude API key not set. Please add your API key in Settings.".to_string())
-    };
-
-    // Spawn async task to handle streaming
-    tauri::async_runtime::spawn(async move {
-        if let Err(e) = stream_claude_response(app, stream_id, api_key, request).await {
-            eprintln!("Streaming error: {}", e);
-        }
-    });
+    command_metrics::track_async("claude_chat_completion_stream", async move {
+        let api_key = api_keys::get_api_key(&app, "claude_api_key")?
+            .ok_or("Claude API key not set. Please add your API key in Settings.")?;
+
+        // Spawn async task to handle streaming
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = stream_claude_response(app, stream_id, api_key, request).await {
+                eprintln_redacted!("Streaming error: {}", e);
+            }
+        });
 
-    Ok(())
+        Ok(())
+    }).await
 }
 
 /// Internal function to handle streaming Claude responses
@@ -204,13 +215,6 @@ async fn stream_claude_response(
     api_key: String,
     request: ClaudeStreamingRequest,
 ) -> Result<(), String> {
-    let client = Client::builder()
-        .timeout(Duration::from_secs(1200))         // 20 min total timeout (for large canvas generation)
-        .connect_timeout(Duration::from_secs(30))   // 30 sec to establish connection
-        .read_timeout(Duration::from_secs(900))     // 15 min to read response (large sessions can take 5-10 min)
-        .build()
-        .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
-
     let mut request_body = json!({
         "model": request.model,
         "max_tokens": request.max_tokens,
@@ -235,19 +239,20 @@ async fn stream_claude_response(
     }
 
     // DEBUG: Log the actual request being sent
-    println!("[Claude API] Request body:");
-    println!("{}", serde_json::to_string_pretty(&request_body).unwrap_or_else(|_| "Failed to serialize".to_string()));
-
-    let response = client
-        .post(&format!("{}/messages", CLAUDE_API_BASE))
-        .header("x-api-key", api_key)
-        .header("anthropic-version", ANTHROPIC_VERSION)
-        .header("anthropic-beta", "prompt-caching-2024-07-31")
-        .header("Content-Type", "application/json")
-        .json(&request_body)
-        .send()
-        .await
-        .map_err(|e| format!("Claude API request failed: {}", e))?;
+    println_redacted!("[Claude API] Request body:");
+    println_redacted!("{}", serde_json::to_string_pretty(&request_body).unwrap_or_else(|_| "Failed to serialize".to_string()));
+
+    let response = ai_client::send_with_retry(&app, "claude", |client| {
+        client
+            .post(format!("{}/messages", CLAUDE_API_BASE))
+            .header("x-api-key", &api_key)
+            .header("anthropic-version", ANTHROPIC_VERSION)
+            .header("anthropic-beta", "prompt-caching-2024-07-31")
+            .header("Content-Type", "application/json")
+            .json(&request_body)
+    })
+    .await
+    .map_err(|e| format!("Claude API request failed: {}", e))?;
 
     let status = response.status();
     if !status.is_success() {
@@ -262,7 +267,7 @@ async fn stream_claude_response(
             json!({
                 "type": "error",
                 "error": {
-                    "message": format!("Claude API error ({}): {}", status, error_text)
+                    "message": redaction::redact(&format!("Claude API error ({}): {}", status, error_text))
                 }
             }),
         );
@@ -272,6 +277,7 @@ async fn stream_claude_response(
     // Process SSE stream
     let mut stream = response.bytes_stream();
     let mut buffer = String::new();
+    let mut usage = UsageRecord::default();
 
     while let Some(chunk_result) = stream.next().await {
         match chunk_result {
@@ -298,11 +304,19 @@ async fn stream_claude_response(
                             // Parse JSON data
                             match serde_json::from_str::<serde_json::Value>(data) {
                             Ok(json_data) => {
+                                // Track token usage for cost accounting
+                                if let Some(input) = json_data["message"]["usage"]["input_tokens"].as_u64() {
+                                    usage.input_tokens = input;
+                                }
+                                if let Some(output) = json_data["usage"]["output_tokens"].as_u64() {
+                                    usage.output_tokens = output;
+                                }
+
                                 // Log event type for debugging
                                 if let Some(event_type) = json_data.get("type") {
-                                    println!("[Claude Stream] Event type: {}", event_type);
+                                    println_redacted!("[Claude Stream] Event type: {}", event_type);
                                     if event_type == "content_block_delta" {
-                                        println!("[Claude Stream] Delta: {:?}", json_data.get("delta"));
+                                        println_redacted!("[Claude Stream] Delta: {:?}", json_data.get("delta"));
                                     }
                                 }
 
@@ -313,8 +327,8 @@ async fn stream_claude_response(
                                 );
                             }
                             Err(e) => {
-                                eprintln!("Failed to parse SSE data: {}", e);
-                                eprintln!("Raw data: {}", data);
+                                eprintln_redacted!("Failed to parse SSE data: {}", e);
+                                eprintln_redacted!("Raw data: {}", data);
                             }
                         }
                         }
@@ -327,7 +341,7 @@ async fn stream_claude_response(
                     json!({
                         "type": "error",
                         "error": {
-                            "message": format!("Stream error: {}", e)
+                            "message": redaction::redact(&format!("Stream error: {}", e))
                         }
                     }),
                 );
@@ -336,6 +350,9 @@ async fn stream_claude_response(
         }
     }
 
+    app.state::<Arc<BudgetManager>>()
+        .record(&app, AiProvider::Claude, &request.model, &usage);
+
     // Emit completion event
     let _ = app.emit(
         &format!("claude-stream-{}", stream_id),
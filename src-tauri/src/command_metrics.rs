@@ -0,0 +1,152 @@
+/**
+ * Command Metrics Module
+ *
+ * Per-command tracing spans and timing metrics, queryable via `get_command_stats`:
+ * - every command body runs inside `track` / `track_async`, which opens a
+ *   `command` span and records duration and outcome (ok / error)
+ * - the invoke handler records the request payload size before dispatch
+ *
+ * Stats are aggregated in memory per command name since app start.
+ */
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::OnceLock;
+use std::time::Instant;
+use tracing::Instrument;
+
+use crate::safe_state::SafeState;
+
+/// Aggregated stats for one command (milliseconds / bytes)
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandStats {
+    pub command: String,
+    pub calls: u64,
+    pub errors: u64,
+    pub total_ms: f64,
+    pub avg_ms: f64,
+    pub max_ms: f64,
+    pub total_payload_bytes: u64,
+    pub max_payload_bytes: u64,
+}
+
+pub struct CommandMetrics {
+    stats: SafeState<HashMap<String, CommandStats>>,
+}
+
+/// Global metrics registry
+pub fn registry() -> &'static CommandMetrics {
+    static REGISTRY: OnceLock<CommandMetrics> = OnceLock::new();
+    REGISTRY.get_or_init(|| CommandMetrics {
+        stats: SafeState::new("command_metrics", HashMap::new()),
+    })
+}
+
+impl CommandMetrics {
+    fn update(&self, command: &str, apply: impl FnOnce(&mut CommandStats)) {
+        let mut stats = self.stats.lock();
+        let entry = stats.entry(command.to_string()).or_insert_with(|| CommandStats {
+            command: command.to_string(),
+            ..Default::default()
+        });
+        apply(entry);
+    }
+
+    /// Record the size of an incoming request payload
+    pub fn record_payload(&self, command: &str, bytes: usize) {
+        self.update(command, |stats| {
+            stats.total_payload_bytes += bytes as u64;
+            stats.max_payload_bytes = stats.max_payload_bytes.max(bytes as u64);
+        });
+    }
+
+    fn record_call(&self, command: &str, duration_ms: f64, ok: bool) {
+        self.update(command, |stats| {
+            stats.calls += 1;
+            if !ok {
+                stats.errors += 1;
+            }
+            stats.total_ms += duration_ms;
+            stats.avg_ms = stats.total_ms / stats.calls as f64;
+            stats.max_ms = stats.max_ms.max(duration_ms);
+        });
+    }
+
+    /// All command stats, slowest total time first
+    pub fn snapshot(&self) -> Vec<CommandStats> {
+        let mut stats: Vec<CommandStats> = self.stats.lock().values().cloned().collect();
+        stats.sort_by(|a, b| b.total_ms.total_cmp(&a.total_ms));
+        stats
+    }
+}
+
+/// Size of an invoke payload (JSON arguments or raw bytes)
+pub fn payload_size(body: &tauri::ipc::InvokeBody) -> usize {
+    match body {
+        tauri::ipc::InvokeBody::Raw(bytes) => bytes.len(),
+        tauri::ipc::InvokeBody::Json(value) => {
+            // Count the serialized bytes without buffering them
+            let mut counter = ByteCounter(0);
+            serde_json::to_writer(&mut counter, value).map(|_| counter.0).unwrap_or(0)
+        }
+    }
+}
+
+/// A writer that only counts what's written to it
+struct ByteCounter(usize);
+
+impl std::io::Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0 += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn command_span(command: &'static str) -> tracing::Span {
+    tracing::info_span!(
+        "command",
+        command,
+        duration_ms = tracing::field::Empty,
+        ok = tracing::field::Empty
+    )
+}
+
+fn finish<T>(span: &tracing::Span, command: &str, started: Instant, result: &Result<T, String>) {
+    let duration_ms = started.elapsed().as_secs_f64() * 1000.0;
+    span.record("duration_ms", duration_ms);
+    span.record("ok", result.is_ok());
+    registry().record_call(command, duration_ms, result.is_ok());
+}
+
+/// Run a synchronous command body inside a timed span
+pub fn track<T>(command: &'static str, body: impl FnOnce() -> Result<T, String>) -> Result<T, String> {
+    let span = command_span(command);
+    let started = Instant::now();
+    let result = span.in_scope(body);
+    finish(&span, command, started, &result);
+    result
+}
+
+/// Run an async command body inside a timed span
+pub async fn track_async<T>(
+    command: &'static str,
+    body: impl Future<Output = Result<T, String>>,
+) -> Result<T, String> {
+    let span = command_span(command);
+    let started = Instant::now();
+    let result = body.instrument(span.clone()).await;
+    finish(&span, command, started, &result);
+    result
+}
+
+/// Tauri command to get per-command timing stats
+#[tauri::command]
+pub fn get_command_stats() -> Result<Vec<CommandStats>, String> {
+    Ok(registry().snapshot())
+}
@@ -0,0 +1,281 @@
+/**
+ * Configuration Bundle Module
+ *
+ * Password-encrypted export/import of app configuration so a new machine can
+ * be set up in one step:
+ * - Settings (including shortcuts), profiles, saved prompts and session presets
+ * - Optionally the API keys of every profile
+ *
+ * Bundle format (JSON): Argon2id-derived key + AES-256-GCM over the payload.
+ * Session data is not included - use the storage export for that.
+ */
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use base64::Engine;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_store::StoreExt;
+
+use crate::background_tasks::TaskRegistry;
+use crate::command_metrics;
+use crate::jobs::JobRegistry;
+use crate::profiles;
+use crate::settings::SettingsManager;
+
+const BUNDLE_FORMAT: &str = "taskerino-config";
+const BUNDLE_VERSION: u32 = 1;
+const MIN_PASSWORD_LENGTH: usize = 8;
+
+/// Stores always included in a bundle
+const CONFIG_STORES: [&str; 4] = ["settings.json", "profiles.json", "prompts.json", "presets.json"];
+
+/// On-disk encrypted bundle
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EncryptedBundle {
+    format: String,
+    version: u32,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// Decrypted bundle contents: store file -> (key -> value)
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BundlePayload {
+    exported_at: String,
+    includes_api_keys: bool,
+    stores: BTreeMap<String, BTreeMap<String, serde_json::Value>>,
+}
+
+/// Summary returned to the frontend after an import
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportSummary {
+    pub exported_at: String,
+    pub stores: Vec<String>,
+    pub includes_api_keys: bool,
+}
+
+fn derive_key(password: &str, salt: &[u8]) -> Result<[u8; 32], String> {
+    let mut key = [0u8; 32];
+    argon2::Argon2::default()
+        .hash_password_into(password.as_bytes(), salt, &mut key)
+        .map_err(|e| format!("Failed to derive encryption key: {}", e))?;
+    Ok(key)
+}
+
+fn encrypt(password: &str, plaintext: &[u8]) -> Result<EncryptedBundle, String> {
+    let mut salt = [0u8; 16];
+    let mut nonce = [0u8; 12];
+    rand::thread_rng().fill_bytes(&mut salt);
+    rand::thread_rng().fill_bytes(&mut nonce);
+
+    let key = derive_key(password, &salt)?;
+    let cipher = Aes256Gcm::new(&key.into());
+    let ciphertext = cipher
+        .encrypt(&Nonce::from(nonce), plaintext)
+        .map_err(|_| "Failed to encrypt configuration".to_string())?;
+
+    let b64 = base64::engine::general_purpose::STANDARD;
+    Ok(EncryptedBundle {
+        format: BUNDLE_FORMAT.to_string(),
+        version: BUNDLE_VERSION,
+        salt: b64.encode(salt),
+        nonce: b64.encode(nonce),
+        ciphertext: b64.encode(ciphertext),
+    })
+}
+
+fn decrypt(password: &str, bundle: &EncryptedBundle) -> Result<Vec<u8>, String> {
+    if bundle.format != BUNDLE_FORMAT {
+        return Err("Not a Taskerino configuration bundle".to_string());
+    }
+    if bundle.version > BUNDLE_VERSION {
+        return Err(format!(
+            "Bundle version {} is newer than supported (v{}). Please update Taskerino.",
+            bundle.version, BUNDLE_VERSION
+        ));
+    }
+
+    let b64 = base64::engine::general_purpose::STANDARD;
+    let decode = |value: &str| {
+        b64.decode(value).map_err(|e| format!("Corrupt configuration bundle: {}", e))
+    };
+    let salt = decode(&bundle.salt)?;
+    let nonce: [u8; 12] = decode(&bundle.nonce)?
+        .try_into()
+        .map_err(|_| "Corrupt configuration bundle: invalid nonce".to_string())?;
+    let ciphertext = decode(&bundle.ciphertext)?;
+
+    let key = derive_key(password, &salt)?;
+    let cipher = Aes256Gcm::new(&key.into());
+    cipher
+        .decrypt(&Nonce::from(nonce), ciphertext.as_ref())
+        .map_err(|_| "Incorrect password or corrupt configuration bundle".to_string())
+}
+
+/// Store files to include: config stores plus each profile's API key store
+fn stores_to_export(app: &AppHandle, include_api_keys: bool) -> Result<Vec<String>, String> {
+    let mut stores: Vec<String> = CONFIG_STORES.iter().map(|s| s.to_string()).collect();
+
+    if include_api_keys {
+        stores.push("api_keys.json".to_string());
+        for profile in profiles::load_profiles(app)? {
+            if profile.name != profiles::DEFAULT_PROFILE {
+                stores.push(format!("api_keys.{}.json", profile.name));
+            }
+        }
+    }
+
+    Ok(stores)
+}
+
+fn read_store(app: &AppHandle, name: &str) -> Result<BTreeMap<String, serde_json::Value>, String> {
+    let store = app.store(name)
+        .map_err(|e| format!("Failed to access store: {}", e))?;
+    Ok(store.entries().into_iter().collect())
+}
+
+/// Tauri command to export configuration as an encrypted bundle
+/// Runs as a job (returns the job id); the `job-finished` result is the path of
+/// the written file (defaults to the Downloads folder)
+#[tauri::command]
+pub async fn export_configuration(
+    app: AppHandle,
+    jobs: tauri::State<'_, Arc<JobRegistry>>,
+    tasks: tauri::State<'_, Arc<TaskRegistry>>,
+    password: String,
+    include_api_keys: Option<bool>,
+    destination: Option<String>,
+) -> Result<String, String> {
+    command_metrics::track_async("export_configuration", async move {
+        if password.chars().count() < MIN_PASSWORD_LENGTH {
+            return Err(format!("Password must be at least {} characters", MIN_PASSWORD_LENGTH));
+        }
+        let include_api_keys = include_api_keys.unwrap_or(false);
+
+        jobs.start(app.clone(), &tasks, "export-configuration", move |ctx| async move {
+            ctx.progress(0.0, "Reading configuration");
+            let mut stores = BTreeMap::new();
+            for name in stores_to_export(&app, include_api_keys)? {
+                ctx.check_cancelled()?;
+                let entries = read_store(&app, &name)?;
+                if !entries.is_empty() {
+                    stores.insert(name, entries);
+                }
+            }
+
+            let payload = BundlePayload {
+                exported_at: chrono::Utc::now().to_rfc3339(),
+                includes_api_keys: include_api_keys,
+                stores,
+            };
+
+            let path = match destination {
+                Some(path) => PathBuf::from(path),
+                None => app
+                    .path()
+                    .download_dir()
+                    .map_err(|e| format!("Failed to get downloads dir: {}", e))?
+                    .join(format!("taskerino-config-{}.taskerino", chrono::Local::now().format("%Y%m%d-%H%M%S"))),
+            };
+
+            // Key derivation is deliberately slow - keep it off the async runtime.
+            // The bundle is written next to the target and renamed once complete.
+            ctx.progress(0.2, "Encrypting bundle");
+            let partial_path = path.with_extension("partial");
+            ctx.partial_output(partial_path.clone());
+            let job = ctx.clone();
+            let bundle_path = path.clone();
+            tokio::task::spawn_blocking(move || {
+                let plaintext = serde_json::to_vec(&payload)
+                    .map_err(|e| format!("Failed to serialize configuration: {}", e))?;
+                let bundle = encrypt(&password, &plaintext)?;
+                job.check_cancelled()?;
+                let serialized = serde_json::to_string_pretty(&bundle)
+                    .map_err(|e| format!("Failed to serialize bundle: {}", e))?;
+                job.progress(0.8, "Writing bundle");
+                std::fs::write(&partial_path, serialized)
+                    .map_err(|e| format!("Failed to write configuration bundle: {}", e))?;
+                job.check_cancelled()?;
+                std::fs::rename(&partial_path, &bundle_path)
+                    .map_err(|e| format!("Failed to write configuration bundle: {}", e))?;
+                job.commit_output(&partial_path);
+                Ok::<(), String>(())
+            })
+            .await
+            .map_err(|e| format!("Export task failed: {}", e))??;
+
+            println!("📦 [CONFIG] Exported configuration to {:?} (API keys: {})", path, include_api_keys);
+            Ok(serde_json::Value::String(path.to_string_lossy().to_string()))
+        })
+    }).await
+}
+
+/// Tauri command to import an encrypted configuration bundle
+/// Settings are validated and applied live; other stores are overwritten key by key
+#[tauri::command]
+pub async fn import_configuration(
+    app: AppHandle,
+    settings: tauri::State<'_, Arc<SettingsManager>>,
+    path: String,
+    password: String,
+) -> Result<ImportSummary, String> {
+    command_metrics::track_async("import_configuration", async move {
+        let payload: BundlePayload = tokio::task::spawn_blocking(move || {
+            let content = std::fs::read_to_string(&path)
+                .map_err(|e| format!("Failed to read configuration bundle: {}", e))?;
+            let bundle: EncryptedBundle = serde_json::from_str(&content)
+                .map_err(|_| "Not a Taskerino configuration bundle".to_string())?;
+            let plaintext = decrypt(&password, &bundle)?;
+            serde_json::from_slice::<BundlePayload>(&plaintext)
+                .map_err(|e| format!("Failed to parse configuration: {}", e))
+        })
+        .await
+        .map_err(|e| format!("Import task failed: {}", e))??;
+
+        let mut imported = Vec::new();
+        for (name, entries) in payload.stores {
+            // Only restore stores this module knows how to export
+            let is_key_store = name.starts_with("api_keys.")
+                && name.ends_with(".json")
+                && !name.contains(['/', '\\']);
+            if !CONFIG_STORES.contains(&name.as_str()) && !is_key_store {
+                eprintln!("⚠️  [CONFIG] Skipping unknown store in bundle: {}", name);
+                continue;
+            }
+
+            if name == "settings.json" {
+                if let Some(value) = entries.get("settings") {
+                    settings.update(&app, value.clone())?;
+                }
+            } else {
+                let store = app.store(name.as_str())
+                    .map_err(|e| format!("Failed to access store: {}", e))?;
+                for (key, value) in entries {
+                    store.set(key, value);
+                }
+                store.save().map_err(|e| format!("Failed to save store: {}", e))?;
+            }
+            imported.push(name);
+        }
+
+        let summary = ImportSummary {
+            exported_at: payload.exported_at,
+            stores: imported,
+            includes_api_keys: payload.includes_api_keys,
+        };
+
+        println!("📦 [CONFIG] Imported configuration ({} stores)", summary.stores.len());
+        let _ = app.emit("configuration-imported", &summary);
+
+        Ok(summary)
+    }).await
+}
@@ -0,0 +1,264 @@
+/**
+ * Control Server Module
+ *
+ * Local IPC endpoint for `taskerino-cli` (src/bin/taskerino-cli.rs), so shell
+ * scripts and keyboard launchers can drive the running app:
+ * - Unix: socket at <app data>/control.sock (owner-only permissions)
+ * - Windows: named pipe \\.\pipe\taskerino-control
+ *
+ * Newline-delimited JSON: each `{ "command": "...", "args": { ... } }` line is
+ * answered with `{ "ok": true, "result": ... }` or `{ "ok": false, "error": "..." }`.
+ * - `start` { name?, description?, tags?, preset? }, `stop`, `pause`, `resume`: forwarded to
+ *   the frontend (the tray menu events, plus `control-start-session`; `preset` carries
+ *   the sources and options of a session preset, see presets.rs)
+ * - `capture`: composite screenshot added to the active session
+ * - `sessions` { query?, category?, sortBy?, sortOrder?, limit?, offset? },
+ *   `session` { id }, `active`: same data as the REST API and MCP server
+ *
+ * The same commands back the AppleScript / Shortcuts bridge (automation.rs).
+ */
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+
+use crate::background_tasks::TaskRegistry;
+use crate::diarization;
+use crate::profiles;
+use crate::session_index::{SessionIndex, SessionQuery};
+use crate::session_models::Session;
+use crate::session_storage;
+use crate::settings::SettingsManager;
+
+const TASK_NAME: &str = "control-server";
+
+/// Must match taskerino-cli
+#[cfg(unix)]
+const SOCKET_FILE: &str = "control.sock";
+#[cfg(windows)]
+const PIPE_NAME: &str = r"\\.\pipe\taskerino-control";
+
+const DEFAULT_QUERY_LIMIT: usize = 50;
+
+#[derive(Debug, Deserialize)]
+struct ControlRequest {
+    command: String,
+    #[serde(default)]
+    args: Value,
+}
+
+async fn active_session(app: &AppHandle) -> Result<Option<Session>, String> {
+    let data_dir = profiles::profile_data_dir(app)?;
+    tokio::task::spawn_blocking(move || session_storage::read_active_session(&data_dir))
+        .await
+        .map_err(|e| format!("Session task failed: {}", e))?
+}
+
+/// Error naming the active session, if there is one
+pub async fn ensure_no_active_session(app: &AppHandle) -> Result<(), String> {
+    match active_session(app).await? {
+        Some(session) => Err(format!("Session \"{}\" is already active", session.name)),
+        None => Ok(()),
+    }
+}
+
+async fn require_active_session(app: &AppHandle) -> Result<Session, String> {
+    active_session(app).await?.ok_or_else(|| "No active session".to_string())
+}
+
+/// Run one control command
+pub async fn execute(app: &AppHandle, command: &str, args: Value) -> Result<Value, String> {
+    match command {
+        "start" => {
+            ensure_no_active_session(app).await?;
+            let payload = json!({
+                "name": args.get("name").and_then(Value::as_str),
+                "description": args.get("description").and_then(Value::as_str),
+                "tags": args.get("tags").filter(|tags| tags.is_array()),
+                "preset": args.get("preset").filter(|preset| preset.is_object()),
+            });
+            app.emit("control-start-session", payload)
+                .map_err(|e| format!("Failed to emit start: {}", e))?;
+            Ok(json!({ "requested": "start" }))
+        }
+        command @ ("stop" | "pause" | "resume") => {
+            let session = require_active_session(app).await?;
+            app.emit(&format!("menubar-{}-session", command), ())
+                .map_err(|e| format!("Failed to emit {}: {}", command, e))?;
+            Ok(json!({ "requested": command, "sessionId": session.id }))
+        }
+        "capture" => {
+            let session = require_active_session(app).await?;
+            let screenshot_settings = app.state::<Arc<SettingsManager>>().get().screenshots;
+            let jpeg = tokio::task::spawn_blocking(move || crate::capture_composite_jpeg(screenshot_settings))
+                .await
+                .map_err(|e| format!("Capture task failed: {}", e))??;
+            let data_url = format!(
+                "data:image/jpeg;base64,{}",
+                base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &jpeg)
+            );
+            app.emit("quick-capture-screenshot", data_url)
+                .map_err(|e| format!("Failed to emit capture: {}", e))?;
+            Ok(json!({ "sessionId": session.id, "bytes": jpeg.len() }))
+        }
+        "sessions" => {
+            let args = if args.is_null() { json!({}) } else { args };
+            let mut query: SessionQuery = serde_json::from_value(args)
+                .map_err(|e| format!("Invalid query: {}", e))?;
+            query.limit = Some(query.limit.unwrap_or(DEFAULT_QUERY_LIMIT).max(1));
+            let page = app
+                .state::<Arc<SessionIndex>>()
+                .inner()
+                .read(app, move |db| db.query(&query))
+                .await?;
+            Ok(json!(page))
+        }
+        "session" => {
+            let id = args
+                .get("id")
+                .and_then(Value::as_str)
+                .ok_or("Missing session id")?
+                .to_string();
+            let data_dir = profiles::profile_data_dir(app)?;
+            let session = tokio::task::spawn_blocking(move || {
+                session_storage::find_session(&data_dir, &id)?
+                    .map(|mut session| {
+                        diarization::apply_speaker_labels(&data_dir, &mut session);
+                        session
+                    })
+                    .ok_or_else(|| format!("Session {} not found", id))
+            })
+            .await
+            .map_err(|e| format!("Session task failed: {}", e))??;
+            Ok(json!(session))
+        }
+        "active" => Ok(json!(active_session(app).await?)),
+        other => Err(format!("Unknown command: {}", other)),
+    }
+}
+
+/// `{ ok, result }` / `{ ok, error }` response for a command result
+pub fn envelope(command: &str, result: Result<Value, String>) -> Value {
+    match result {
+        Ok(result) => json!({ "ok": true, "result": result }),
+        Err(e) => {
+            eprintln!("⚠️  [CONTROL] {} failed: {}", command, e);
+            json!({ "ok": false, "error": e })
+        }
+    }
+}
+
+async fn handle_connection<S>(app: AppHandle, stream: S)
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let (reader, mut writer) = tokio::io::split(stream);
+    let mut lines = BufReader::new(reader).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match serde_json::from_str::<ControlRequest>(&line) {
+            Ok(request) => envelope(&request.command, execute(&app, &request.command, request.args).await),
+            Err(e) => envelope("request", Err(format!("Invalid request: {}", e))),
+        };
+        let mut bytes = response.to_string().into_bytes();
+        bytes.push(b'\n');
+        if writer.write_all(&bytes).await.is_err() {
+            break;
+        }
+    }
+}
+
+/// Start listening for CLI connections (skipped if another instance owns the socket)
+#[cfg(unix)]
+pub fn start(app: AppHandle, registry: &TaskRegistry) -> Result<(), String> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let path = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?
+        .join(SOCKET_FILE);
+    if std::os::unix::net::UnixStream::connect(&path).is_ok() {
+        eprintln!("⚠️  [CONTROL] Another instance owns {}; CLI control disabled", path.display());
+        return Ok(());
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create app data dir: {}", e))?;
+    }
+    // Left behind by a crash
+    let _ = std::fs::remove_file(&path);
+
+    let listener = std::os::unix::net::UnixListener::bind(&path)
+        .map_err(|e| format!("Failed to bind {}: {}", path.display(), e))?;
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))
+        .map_err(|e| format!("Failed to restrict control socket: {}", e))?;
+    listener
+        .set_nonblocking(true)
+        .map_err(|e| format!("Failed to configure control socket: {}", e))?;
+
+    println!("🎛️  [CONTROL] Listening on {}", path.display());
+    registry.spawn(TASK_NAME, |mut shutdown| async move {
+        let listener = match tokio::net::UnixListener::from_std(listener) {
+            Ok(listener) => listener,
+            Err(e) => {
+                eprintln!("❌ [CONTROL] Failed to start listener: {}", e);
+                return;
+            }
+        };
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, _)) => {
+                        tokio::spawn(handle_connection(app.clone(), stream));
+                    }
+                    Err(e) => eprintln!("⚠️  [CONTROL] Failed to accept connection: {}", e),
+                },
+            }
+        }
+        let _ = std::fs::remove_file(&path);
+    })
+}
+
+/// Start listening for CLI connections
+#[cfg(windows)]
+pub fn start(app: AppHandle, registry: &TaskRegistry) -> Result<(), String> {
+    use tokio::net::windows::named_pipe::ServerOptions;
+
+    println!("🎛️  [CONTROL] Listening on {}", PIPE_NAME);
+    registry.spawn(TASK_NAME, |mut shutdown| async move {
+        let mut server = match ServerOptions::new().first_pipe_instance(true).create(PIPE_NAME) {
+            Ok(server) => server,
+            Err(e) => {
+                eprintln!("⚠️  [CONTROL] Failed to create {}: {}; CLI control disabled", PIPE_NAME, e);
+                return;
+            }
+        };
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                connected = server.connect() => {
+                    if let Err(e) = connected {
+                        eprintln!("⚠️  [CONTROL] Failed to accept connection: {}", e);
+                        continue;
+                    }
+                    // Hand the connected instance off and wait on a fresh one
+                    let next = match ServerOptions::new().create(PIPE_NAME) {
+                        Ok(next) => next,
+                        Err(e) => {
+                            eprintln!("❌ [CONTROL] Failed to create pipe instance: {}", e);
+                            break;
+                        }
+                    };
+                    let client = std::mem::replace(&mut server, next);
+                    tokio::spawn(handle_connection(app.clone(), client));
+                }
+            }
+        }
+    })
+}
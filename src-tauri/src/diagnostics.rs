@@ -0,0 +1,346 @@
+/**
+ * Diagnostics Module
+ *
+ * `run_diagnostics` checks what recording and AI features depend on and
+ * returns a pass/warn/fail report for the settings UI and for support:
+ * - Permissions: screen recording, microphone, camera, Input Monitoring
+ *   (macOS, permissions.rs)
+ * - Entitlements the signed app needs (codesign; a development build isn't
+ *   signed and only warns)
+ * - ffmpeg / ffprobe (media_tools.rs)
+ * - Free disk space against settings.storage
+ * - Audio input devices
+ * - The Swift bridge: the library answers and speaks the expected version
+ * - API keys: one lightweight authenticated request (model list) per
+ *   configured provider; 401/403 fails, network trouble only warns
+ *
+ * Checks that don't apply on this platform pass with a note.
+ */
+
+use cpal::traits::{DeviceTrait, HostTrait};
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+use crate::ai_client;
+use crate::api_keys;
+use crate::claude_api::{ANTHROPIC_VERSION, CLAUDE_API_BASE};
+use crate::command_metrics;
+use crate::disk_space::{self, DiskSpaceLevel};
+use crate::gemini_api::GEMINI_API_BASE;
+use crate::media_tools;
+use crate::openai_api::OPENAI_API_BASE;
+use crate::permissions::{self, PermissionStatus, PermissionType};
+use crate::settings::SettingsManager;
+use crate::video_recording::VideoRecorder;
+
+/// Swift C API version this build expects (ScreenRecorder.swift)
+#[cfg(target_os = "macos")]
+const SWIFT_BRIDGE_VERSION: i32 = 1;
+
+/// Entitlements the app can't capture or automate without
+#[cfg(target_os = "macos")]
+const REQUIRED_ENTITLEMENTS: &[&str] = &[
+    "com.apple.security.device.audio-input",
+    "com.apple.security.personal-information.calendars",
+    "com.apple.security.automation.apple-events",
+];
+
+const API_PING_TIMEOUT: Duration = Duration::from_secs(10);
+
+// FFI declarations for Swift functions
+#[cfg(target_os = "macos")]
+extern "C" {
+    fn screen_recorder_bridge_version() -> i32;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticCheck {
+    /// Stable identifier, e.g. "microphonePermission"
+    pub id: &'static str,
+    pub label: &'static str,
+    pub status: CheckStatus,
+    pub message: String,
+}
+
+/// `run_diagnostics` result
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticsReport {
+    /// Worst status of all checks
+    pub status: CheckStatus,
+    pub checks: Vec<DiagnosticCheck>,
+    pub app_version: String,
+    /// e.g. "macos aarch64"
+    pub platform: String,
+    pub generated_at: String,
+}
+
+fn check(id: &'static str, label: &'static str, status: CheckStatus, message: impl Into<String>) -> DiagnosticCheck {
+    DiagnosticCheck { id, label, status, message: message.into() }
+}
+
+fn screen_permission() -> DiagnosticCheck {
+    const ID: &str = "screenPermission";
+    const LABEL: &str = "Screen recording permission";
+    if !cfg!(target_os = "macos") {
+        return check(ID, LABEL, CheckStatus::Pass, "Not needed on this platform");
+    }
+    match VideoRecorder::check_permission() {
+        Ok(true) => check(ID, LABEL, CheckStatus::Pass, "Granted"),
+        Ok(false) => check(
+            ID,
+            LABEL,
+            CheckStatus::Fail,
+            "Not granted; enable Taskerino in System Settings > Privacy & Security > Screen Recording",
+        ),
+        Err(e) => check(ID, LABEL, CheckStatus::Fail, e),
+    }
+}
+
+/// Microphone, camera or Input Monitoring permission
+fn capture_permission(permission: PermissionType) -> DiagnosticCheck {
+    let (id, label, pane, required) = match permission {
+        PermissionType::Camera => ("cameraPermission", "Camera permission", "Camera", false),
+        PermissionType::InputMonitoring => ("inputMonitoringPermission", "Input Monitoring permission", "Input Monitoring", false),
+        _ => ("microphonePermission", "Microphone permission", "Microphone", true),
+    };
+    match permissions::status(permission) {
+        PermissionStatus::Authorized => check(id, label, CheckStatus::Pass, "Granted"),
+        PermissionStatus::Unsupported => check(id, label, CheckStatus::Pass, "Not needed on this platform"),
+        // Camera and keystroke capture are optional, so never having been asked is fine
+        PermissionStatus::NotDetermined if !required => check(id, label, CheckStatus::Pass, "Not requested"),
+        PermissionStatus::NotDetermined => {
+            check(id, label, CheckStatus::Warn, "Not requested yet; macOS asks when recording starts")
+        }
+        PermissionStatus::Restricted => check(id, label, CheckStatus::Fail, "Restricted by a device management profile"),
+        PermissionStatus::Denied => check(
+            id,
+            label,
+            if required { CheckStatus::Fail } else { CheckStatus::Warn },
+            format!("Denied; enable Taskerino in System Settings > Privacy & Security > {}", pane),
+        ),
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn entitlements() -> DiagnosticCheck {
+    const ID: &str = "entitlements";
+    const LABEL: &str = "Entitlements";
+    let exe = match std::env::current_exe() {
+        Ok(exe) => exe,
+        Err(e) => return check(ID, LABEL, CheckStatus::Warn, format!("Couldn't locate the app: {}", e)),
+    };
+    let output = std::process::Command::new("codesign")
+        .args(["-d", "--entitlements", ":-"])
+        .arg(&exe)
+        .output();
+    let output = match output {
+        Ok(output) if output.status.success() => output,
+        Ok(_) => return check(ID, LABEL, CheckStatus::Warn, "App isn't code signed (development build)"),
+        Err(e) => return check(ID, LABEL, CheckStatus::Warn, format!("Couldn't run codesign: {}", e)),
+    };
+    let plist = String::from_utf8_lossy(&output.stdout);
+    let missing: Vec<&str> = REQUIRED_ENTITLEMENTS
+        .iter()
+        .copied()
+        .filter(|entitlement| !plist.contains(entitlement))
+        .collect();
+    match missing.is_empty() {
+        true => check(ID, LABEL, CheckStatus::Pass, "All required entitlements present"),
+        false => check(ID, LABEL, CheckStatus::Fail, format!("Missing: {}", missing.join(", "))),
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn entitlements() -> DiagnosticCheck {
+    check("entitlements", "Entitlements", CheckStatus::Pass, "Not needed on this platform")
+}
+
+fn media_tools_check() -> DiagnosticCheck {
+    const ID: &str = "ffmpeg";
+    const LABEL: &str = "ffmpeg";
+    match (media_tools::ffmpeg(), media_tools::ffprobe()) {
+        (Some(ffmpeg), Some(_)) => check(ID, LABEL, CheckStatus::Pass, format!("Found at {}", ffmpeg.display())),
+        (Some(ffmpeg), None) => check(
+            ID,
+            LABEL,
+            CheckStatus::Warn,
+            format!("ffmpeg found at {}, but ffprobe is missing", ffmpeg.display()),
+        ),
+        (None, _) => check(ID, LABEL, CheckStatus::Warn, "Not found; merging and remuxing recordings is unavailable"),
+    }
+}
+
+fn disk_space_check(app: &AppHandle) -> DiagnosticCheck {
+    const ID: &str = "diskSpace";
+    const LABEL: &str = "Disk space";
+    let data_dir = match app.path().app_data_dir() {
+        Ok(data_dir) => data_dir,
+        Err(e) => return check(ID, LABEL, CheckStatus::Fail, format!("Failed to get app data dir: {}", e)),
+    };
+    let settings = app.state::<Arc<SettingsManager>>().get().storage;
+    match disk_space::disk_space(&data_dir, &settings) {
+        Ok(space) => {
+            let status = match space.level {
+                DiskSpaceLevel::Ok => CheckStatus::Pass,
+                DiskSpaceLevel::Low => CheckStatus::Warn,
+                DiskSpaceLevel::Critical => CheckStatus::Fail,
+            };
+            check(ID, LABEL, status, format!("{} MB free", space.available_mb))
+        }
+        Err(e) => check(ID, LABEL, CheckStatus::Warn, e),
+    }
+}
+
+fn audio_devices() -> DiagnosticCheck {
+    const ID: &str = "audioDevices";
+    const LABEL: &str = "Audio input devices";
+    let host = cpal::default_host();
+    let count = match host.input_devices() {
+        Ok(devices) => devices.count(),
+        Err(e) => return check(ID, LABEL, CheckStatus::Fail, format!("Failed to list input devices: {}", e)),
+    };
+    match host.default_input_device() {
+        Some(device) => check(
+            ID,
+            LABEL,
+            CheckStatus::Pass,
+            format!(
+                "Default: {} ({} input device(s))",
+                device.name().unwrap_or_else(|_| "Unknown".to_string()),
+                count
+            ),
+        ),
+        None if count > 0 => check(ID, LABEL, CheckStatus::Warn, format!("{} input device(s), but no default", count)),
+        None => check(ID, LABEL, CheckStatus::Fail, "No microphone found"),
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn swift_bridge() -> DiagnosticCheck {
+    const ID: &str = "swiftBridge";
+    const LABEL: &str = "Native capture library";
+    let version = unsafe { screen_recorder_bridge_version() };
+    match version == SWIFT_BRIDGE_VERSION {
+        true => check(ID, LABEL, CheckStatus::Pass, format!("Bridge version {}", version)),
+        false => check(
+            ID,
+            LABEL,
+            CheckStatus::Fail,
+            format!("Bridge version {} but {} expected; reinstall the app", version, SWIFT_BRIDGE_VERSION),
+        ),
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn swift_bridge() -> DiagnosticCheck {
+    check("swiftBridge", "Native capture library", CheckStatus::Pass, "Not used on this platform")
+}
+
+/// Checks that don't need the network; blocks
+fn local_checks(app: &AppHandle) -> Vec<DiagnosticCheck> {
+    vec![
+        screen_permission(),
+        capture_permission(PermissionType::Microphone),
+        capture_permission(PermissionType::Camera),
+        capture_permission(PermissionType::InputMonitoring),
+        entitlements(),
+        swift_bridge(),
+        media_tools_check(),
+        disk_space_check(app),
+        audio_devices(),
+    ]
+}
+
+/// One authenticated model list request with `key_name`'s key; None when
+/// the key isn't set
+async fn api_key_check(app: &AppHandle, key_name: &'static str) -> Option<DiagnosticCheck> {
+    let (id, label) = match key_name {
+        "claude_api_key" => ("claudeApiKey", "Claude API key"),
+        "openai_api_key" => ("openaiApiKey", "OpenAI API key"),
+        _ => ("geminiApiKey", "Gemini API key"),
+    };
+    let api_key = match api_keys::get_api_key(app, key_name) {
+        Ok(Some(api_key)) => api_key,
+        Ok(None) => return None,
+        Err(e) => return Some(check(id, label, CheckStatus::Fail, e)),
+    };
+    let client = match ai_client::http_client() {
+        Ok(client) => client,
+        Err(e) => return Some(check(id, label, CheckStatus::Warn, e)),
+    };
+
+    let request = match key_name {
+        "claude_api_key" => client
+            .get(format!("{}/models", CLAUDE_API_BASE))
+            .header("x-api-key", &api_key)
+            .header("anthropic-version", ANTHROPIC_VERSION),
+        "openai_api_key" => client
+            .get(format!("{}/models", OPENAI_API_BASE))
+            .header("Authorization", format!("Bearer {}", api_key)),
+        _ => client
+            .get(format!("{}/models", GEMINI_API_BASE))
+            .header("x-goog-api-key", &api_key),
+    };
+    Some(match request.timeout(API_PING_TIMEOUT).send().await {
+        Ok(response) if response.status().is_success() => check(id, label, CheckStatus::Pass, "Valid"),
+        Ok(response) if matches!(response.status().as_u16(), 401 | 403) => {
+            check(id, label, CheckStatus::Fail, "Rejected by the provider; check the key in Settings")
+        }
+        Ok(response) => check(
+            id,
+            label,
+            CheckStatus::Warn,
+            format!("Couldn't verify: the provider returned {}", response.status()),
+        ),
+        Err(e) => check(id, label, CheckStatus::Warn, format!("Couldn't reach the provider: {}", e.without_url())),
+    })
+}
+
+/// Tauri command to check permissions, tools, storage and API keys
+#[tauri::command]
+pub async fn run_diagnostics(app: AppHandle) -> Result<DiagnosticsReport, String> {
+    command_metrics::track_async("run_diagnostics", async move {
+        let local = tokio::task::spawn_blocking({
+            let app = app.clone();
+            move || local_checks(&app)
+        });
+        let (local, claude, openai, gemini) = tokio::join!(
+            local,
+            api_key_check(&app, "claude_api_key"),
+            api_key_check(&app, "openai_api_key"),
+            api_key_check(&app, "gemini_api_key"),
+        );
+        let mut checks = local.map_err(|e| format!("Diagnostics failed: {}", e))?;
+
+        let api_checks: Vec<DiagnosticCheck> = [claude, openai, gemini].into_iter().flatten().collect();
+        if api_checks.is_empty() {
+            checks.push(check(
+                "apiKeys",
+                "API keys",
+                CheckStatus::Warn,
+                "No AI provider key set; AI features are unavailable",
+            ));
+        }
+        checks.extend(api_checks);
+
+        Ok(DiagnosticsReport {
+            status: checks.iter().map(|check| check.status).max().unwrap_or(CheckStatus::Pass),
+            checks,
+            app_version: app.package_info().version.to_string(),
+            platform: format!("{} {}", std::env::consts::OS, std::env::consts::ARCH),
+            generated_at: chrono::Utc::now().to_rfc3339(),
+        })
+    }).await
+}
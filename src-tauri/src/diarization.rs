@@ -0,0 +1,498 @@
+/**
+ * Diarization Module
+ *
+ * Labels a session's audio segments with speaker ids, fully offline:
+ * - Each segment's WAV attachment is cut into WINDOW_SECS windows; windows
+ *   without enough speech (energy-based VAD) are skipped
+ * - A window's voice print is the mean / spread of its log spectral envelope
+ *   (MEL_BANDS mel-spaced Goertzel filters over 25ms frames)
+ * - Windows are clustered across the whole session (leader clustering, then
+ *   merging the closest clusters while they're within MERGE_DISTANCE or
+ *   above `maxSpeakers`); speakers are numbered in order of first appearance
+ * - Results live in <profile>/diarization/<session id>.json and are merged
+ *   into the audio segments returned by `load_session_detail`
+ *
+ * `diarize_session_audio` runs as a job (jobs.rs); its result is the stored
+ * `SessionDiarization`.
+ */
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::f32::consts::PI;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tauri::AppHandle;
+
+use crate::attachment_metadata;
+use crate::background_tasks::TaskRegistry;
+use crate::command_metrics;
+use crate::jobs::JobRegistry;
+use crate::profiles;
+use crate::session_models::{Session, SpeakerTurn};
+use crate::session_storage;
+
+const FRAME_SECS: f32 = 0.025;
+const HOP_SECS: f32 = 0.02;
+const WINDOW_SECS: f32 = 1.5;
+
+const MEL_BANDS: usize = 16;
+const MIN_BAND_HZ: f32 = 100.0;
+const MAX_BAND_HZ: f32 = 4000.0;
+
+/// Frames this far (natural log of power) above the segment's noise floor
+/// count as speech (~6dB; speech dips between syllables, so even continuous
+/// speech has a floor well below its peaks)
+const SPEECH_ABOVE_FLOOR: f32 = 1.4;
+/// Share of speech frames a window needs to get a voice print
+const MIN_SPEECH_RATIO: f32 = 0.5;
+
+/// Cosine distance under which a window joins an existing cluster
+const JOIN_DISTANCE: f32 = 0.25;
+/// Cosine distance under which two clusters are the same speaker
+const MERGE_DISTANCE: f32 = 0.4;
+/// Clusters with fewer windows are folded into the nearest larger one
+const MIN_CLUSTER_WINDOWS: usize = 3;
+
+/// Speaker labels for one audio segment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SegmentSpeakers {
+    pub segment_id: String,
+    /// Speaker with the most speech in the segment (None when it had no speech)
+    pub speaker: Option<String>,
+    pub turns: Vec<SpeakerTurn>,
+}
+
+/// Stored diarization of a session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionDiarization {
+    pub session_id: String,
+    pub speaker_count: usize,
+    pub segments: Vec<SegmentSpeakers>,
+    /// Segments whose audio couldn't be decoded (e.g. FLAC chunks)
+    pub skipped_segments: Vec<String>,
+    pub created_at: String,
+}
+
+/// Voice print of one analysis window
+struct Window {
+    segment: usize,
+    start: f32,
+    end: f32,
+    features: Vec<f32>,
+}
+
+fn diarization_path(data_dir: &Path, session_id: &str) -> PathBuf {
+    data_dir.join("diarization").join(format!("{}.json", session_id))
+}
+
+/// Merge stored speaker labels into a session's audio segments (no-op when
+/// the session hasn't been diarized)
+pub fn apply_speaker_labels(data_dir: &Path, session: &mut Session) {
+    let Some(diarization) = std::fs::read_to_string(diarization_path(data_dir, &session.id))
+        .ok()
+        .and_then(|content| serde_json::from_str::<SessionDiarization>(&content).ok())
+    else {
+        return;
+    };
+
+    let mut by_segment: HashMap<String, SegmentSpeakers> = diarization
+        .segments
+        .into_iter()
+        .map(|segment| (segment.segment_id.clone(), segment))
+        .collect();
+
+    for segment in session.audio_segments.iter_mut().flatten() {
+        if let Some(speakers) = by_segment.remove(&segment.id) {
+            segment.speaker = speakers.speaker;
+            segment.speaker_turns = Some(speakers.turns);
+        }
+    }
+}
+
+/// Decode a WAV file to mono samples
+fn decode_wav(bytes: &[u8]) -> Result<(Vec<f32>, u32), String> {
+    let mut reader = hound::WavReader::new(Cursor::new(bytes))
+        .map_err(|e| format!("Unsupported audio (WAV only): {}", e))?;
+    let spec = reader.spec();
+    let channels = spec.channels.max(1) as usize;
+
+    let interleaved: Vec<f32> = match spec.sample_format {
+        hound::SampleFormat::Float => reader
+            .samples::<f32>()
+            .collect::<Result<_, _>>()
+            .map_err(|e| format!("Failed to decode WAV: {}", e))?,
+        hound::SampleFormat::Int => {
+            let scale = (1i64 << (spec.bits_per_sample.clamp(1, 32) - 1)) as f32;
+            reader
+                .samples::<i32>()
+                .map(|sample| sample.map(|s| s as f32 / scale))
+                .collect::<Result<_, _>>()
+                .map_err(|e| format!("Failed to decode WAV: {}", e))?
+        }
+    };
+
+    let mono = interleaved
+        .chunks(channels)
+        .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+        .collect();
+    Ok((mono, spec.sample_rate))
+}
+
+/// Log power at mel-spaced band centers for every frame
+fn log_band_energies(samples: &[f32], sample_rate: u32) -> Vec<[f32; MEL_BANDS]> {
+    let rate = sample_rate as f32;
+    let frame_len = (FRAME_SECS * rate) as usize;
+    let hop = (HOP_SECS * rate) as usize;
+    if frame_len == 0 || hop == 0 || samples.len() < frame_len {
+        return Vec::new();
+    }
+
+    let mel = |hz: f32| 2595.0 * (1.0 + hz / 700.0).log10();
+    let hz = |mel: f32| 700.0 * (10f32.powf(mel / 2595.0) - 1.0);
+    let (low, high) = (mel(MIN_BAND_HZ), mel(MAX_BAND_HZ.min(rate / 2.0 - 1.0)));
+    let coefficients: Vec<f32> = (0..MEL_BANDS)
+        .map(|band| {
+            let center = hz(low + (high - low) * band as f32 / (MEL_BANDS - 1) as f32);
+            2.0 * (2.0 * PI * center / rate).cos()
+        })
+        .collect();
+    let hann: Vec<f32> = (0..frame_len)
+        .map(|i| 0.5 - 0.5 * (2.0 * PI * i as f32 / (frame_len - 1) as f32).cos())
+        .collect();
+
+    (0..=(samples.len() - frame_len) / hop)
+        .map(|frame| {
+            let frame = &samples[frame * hop..frame * hop + frame_len];
+            let mut energies = [0f32; MEL_BANDS];
+            for (energy, coefficient) in energies.iter_mut().zip(&coefficients) {
+                // Goertzel filter
+                let (mut s1, mut s2) = (0f32, 0f32);
+                for (sample, weight) in frame.iter().zip(&hann) {
+                    let s = sample * weight + coefficient * s1 - s2;
+                    s2 = s1;
+                    s1 = s;
+                }
+                let power = s1 * s1 + s2 * s2 - coefficient * s1 * s2;
+                *energy = (power.max(0.0) + 1e-10).ln();
+            }
+            energies
+        })
+        .collect()
+}
+
+/// Voice prints for the speech windows of one segment
+fn segment_windows(segment: usize, samples: &[f32], sample_rate: u32) -> Vec<Window> {
+    let frames = log_band_energies(samples, sample_rate);
+    if frames.is_empty() {
+        return Vec::new();
+    }
+
+    // Noise floor: 10th percentile of frame loudness
+    let loudness: Vec<f32> = frames.iter().map(|bands| bands.iter().sum::<f32>() / MEL_BANDS as f32).collect();
+    let mut sorted = loudness.clone();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let floor = sorted[sorted.len() / 10];
+
+    let frames_per_window = (WINDOW_SECS / HOP_SECS) as usize;
+    frames
+        .chunks(frames_per_window)
+        .zip(loudness.chunks(frames_per_window))
+        .enumerate()
+        .filter_map(|(index, (bands, loudness))| {
+            // Spectral shape of speech frames (loudness removed)
+            let shapes: Vec<[f32; MEL_BANDS]> = bands
+                .iter()
+                .zip(loudness)
+                .filter(|(_, level)| **level > floor + SPEECH_ABOVE_FLOOR)
+                .map(|(bands, level)| bands.map(|band| band - level))
+                .collect();
+            if (shapes.len() as f32) < frames_per_window as f32 * MIN_SPEECH_RATIO {
+                return None;
+            }
+
+            let count = shapes.len() as f32;
+            let mean: Vec<f32> = (0..MEL_BANDS)
+                .map(|band| shapes.iter().map(|shape| shape[band]).sum::<f32>() / count)
+                .collect();
+            let spread: Vec<f32> = (0..MEL_BANDS)
+                .map(|band| (shapes.iter().map(|shape| (shape[band] - mean[band]).powi(2)).sum::<f32>() / count).sqrt())
+                .collect();
+
+            let start = index as f32 * WINDOW_SECS;
+            Some(Window {
+                segment,
+                start,
+                end: start + bands.len() as f32 * HOP_SECS,
+                features: mean.into_iter().chain(spread).collect(),
+            })
+        })
+        .collect()
+}
+
+/// Standardize each feature across the session so no band dominates
+fn standardize(windows: &mut [Window]) {
+    let Some(dims) = windows.first().map(|window| window.features.len()) else { return };
+    let count = windows.len() as f32;
+    for dim in 0..dims {
+        let mean = windows.iter().map(|w| w.features[dim]).sum::<f32>() / count;
+        let std = (windows.iter().map(|w| (w.features[dim] - mean).powi(2)).sum::<f32>() / count).sqrt();
+        for window in windows.iter_mut() {
+            window.features[dim] = (window.features[dim] - mean) / std.max(1e-6);
+        }
+    }
+}
+
+fn cosine_distance(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm = a.iter().map(|x| x * x).sum::<f32>().sqrt() * b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm == 0.0 { 1.0 } else { 1.0 - dot / norm }
+}
+
+struct Cluster {
+    /// Sum of member features (direction = centroid)
+    sum: Vec<f32>,
+    members: Vec<usize>,
+}
+
+impl Cluster {
+    fn absorb(&mut self, other: Cluster) {
+        for (a, b) in self.sum.iter_mut().zip(&other.sum) {
+            *a += b;
+        }
+        self.members.extend(other.members);
+    }
+}
+
+/// Index of the closest pair of clusters and their distance
+fn closest_pair(clusters: &[Cluster]) -> Option<(usize, usize, f32)> {
+    let mut best: Option<(usize, usize, f32)> = None;
+    for i in 0..clusters.len() {
+        for j in i + 1..clusters.len() {
+            let distance = cosine_distance(&clusters[i].sum, &clusters[j].sum);
+            if best.map_or(true, |(_, _, d)| distance < d) {
+                best = Some((i, j, distance));
+            }
+        }
+    }
+    best
+}
+
+/// Cluster windows into speakers; returns a speaker index per window
+fn cluster(windows: &[Window], max_speakers: Option<usize>) -> Vec<usize> {
+    let mut clusters: Vec<Cluster> = Vec::new();
+    for (index, window) in windows.iter().enumerate() {
+        let nearest = clusters
+            .iter()
+            .enumerate()
+            .map(|(i, cluster)| (i, cosine_distance(&cluster.sum, &window.features)))
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+        match nearest {
+            Some((i, distance)) if distance < JOIN_DISTANCE => {
+                clusters[i].absorb(Cluster { sum: window.features.clone(), members: vec![index] });
+            }
+            _ => clusters.push(Cluster { sum: window.features.clone(), members: vec![index] }),
+        }
+    }
+
+    // Fold stray windows (coughs, noise) into the nearest real speaker
+    let (mut large, small): (Vec<Cluster>, Vec<Cluster>) = clusters
+        .into_iter()
+        .partition(|cluster| cluster.members.len() >= MIN_CLUSTER_WINDOWS);
+    if large.is_empty() {
+        large = small;
+    } else {
+        for stray in small {
+            let nearest = (0..large.len())
+                .min_by(|&a, &b| {
+                    cosine_distance(&large[a].sum, &stray.sum).total_cmp(&cosine_distance(&large[b].sum, &stray.sum))
+                })
+                .unwrap_or(0);
+            large[nearest].absorb(stray);
+        }
+    }
+
+    let max_speakers = max_speakers.unwrap_or(usize::MAX).max(1);
+    while let Some((i, j, distance)) = closest_pair(&large) {
+        if distance >= MERGE_DISTANCE && large.len() <= max_speakers {
+            break;
+        }
+        let merged = large.remove(j);
+        large[i].absorb(merged);
+    }
+
+    // Number speakers by first appearance
+    large.sort_by_key(|cluster| cluster.members.iter().min().copied().unwrap_or(usize::MAX));
+    let mut labels = vec![0; windows.len()];
+    for (speaker, cluster) in large.iter().enumerate() {
+        for &member in &cluster.members {
+            labels[member] = speaker;
+        }
+    }
+    labels
+}
+
+fn speaker_label(index: usize) -> String {
+    format!("Speaker {}", index + 1)
+}
+
+/// Consecutive windows of one speaker become a single turn
+fn segment_turns(windows: &[(&Window, usize)]) -> Vec<SpeakerTurn> {
+    let mut turns: Vec<SpeakerTurn> = Vec::new();
+    for (window, speaker) in windows {
+        let speaker = speaker_label(*speaker);
+        match turns.last_mut() {
+            Some(turn) if turn.speaker == speaker && (window.start as f64 - turn.end) < 0.01 => {
+                turn.end = window.end as f64;
+            }
+            _ => turns.push(SpeakerTurn { start: window.start as f64, end: window.end as f64, speaker }),
+        }
+    }
+    turns
+}
+
+/// Diarize every audio segment of a session and store the result
+fn diarize_session(
+    data_dir: &Path,
+    session_id: &str,
+    max_speakers: Option<usize>,
+    on_segment: impl Fn(usize, usize) + Sync,
+    is_cancelled: impl Fn() -> bool + Sync,
+) -> Result<SessionDiarization, String> {
+    let session = session_storage::read_session(data_dir, session_id)?;
+    let segments = session.audio_segments.unwrap_or_default();
+    let attachments_dir = data_dir.join("attachments");
+
+    let total = segments.len();
+    let done = AtomicUsize::new(0);
+    let decoded: Vec<Result<Vec<Window>, String>> = segments
+        .par_iter()
+        .enumerate()
+        .map(|(index, segment)| {
+            if is_cancelled() {
+                return Err("Cancelled".to_string());
+            }
+            let meta = std::fs::read_to_string(attachments_dir.join(format!("{}.meta.json", segment.attachment_id)))
+                .ok()
+                .and_then(|content| serde_json::from_str(&content).ok())
+                .unwrap_or(serde_json::Value::Null);
+            let result = attachment_metadata::read_attachment_bytes(&attachments_dir, &segment.attachment_id, &meta)
+                .and_then(|bytes| decode_wav(&bytes))
+                .map(|(samples, sample_rate)| segment_windows(index, &samples, sample_rate));
+            on_segment(done.fetch_add(1, Ordering::Relaxed) + 1, total);
+            result
+        })
+        .collect();
+
+    let mut windows: Vec<Window> = Vec::new();
+    let mut skipped_segments = Vec::new();
+    for (segment, result) in segments.iter().zip(decoded) {
+        match result {
+            Ok(segment_windows) => windows.extend(segment_windows),
+            Err(e) => {
+                eprintln!("⚠️  [DIARIZATION] Skipping segment {}: {}", segment.id, e);
+                skipped_segments.push(segment.id.clone());
+            }
+        }
+    }
+    if is_cancelled() {
+        return Err("Cancelled".to_string());
+    }
+
+    standardize(&mut windows);
+    let labels = cluster(&windows, max_speakers);
+    let speaker_count = labels.iter().max().map_or(0, |max| max + 1);
+
+    let segments: Vec<SegmentSpeakers> = segments
+        .iter()
+        .enumerate()
+        .filter(|(_, segment)| !skipped_segments.contains(&segment.id))
+        .map(|(index, segment)| {
+            let segment_windows: Vec<(&Window, usize)> = windows
+                .iter()
+                .zip(labels.iter().copied())
+                .filter(|(window, _)| window.segment == index)
+                .collect();
+
+            let mut speech: HashMap<usize, f32> = HashMap::new();
+            for (window, speaker) in &segment_windows {
+                *speech.entry(*speaker).or_default() += window.end - window.start;
+            }
+            let dominant = speech
+                .into_iter()
+                .max_by(|a, b| a.1.total_cmp(&b.1).then(b.0.cmp(&a.0)))
+                .map(|(speaker, _)| speaker_label(speaker));
+
+            SegmentSpeakers {
+                segment_id: segment.id.clone(),
+                speaker: dominant,
+                turns: segment_turns(&segment_windows),
+            }
+        })
+        .collect();
+
+    let diarization = SessionDiarization {
+        session_id: session_id.to_string(),
+        speaker_count,
+        segments,
+        skipped_segments,
+        created_at: chrono::Utc::now().to_rfc3339(),
+    };
+
+    let path = diarization_path(data_dir, session_id);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create diarization directory: {}", e))?;
+    }
+    let content = serde_json::to_string(&diarization)
+        .map_err(|e| format!("Failed to serialize diarization: {}", e))?;
+    std::fs::write(&path, content)
+        .map_err(|e| format!("Failed to write diarization: {}", e))?;
+
+    println!(
+        "🗣️  [DIARIZATION] {} speaker(s) across {} segment(s) of {}",
+        speaker_count, diarization.segments.len(), session_id
+    );
+    Ok(diarization)
+}
+
+/**
+ * Label a session's audio segments with speakers. Runs as a job (returns the
+ * job id); the `job-finished` result is the `SessionDiarization`, and the
+ * labels show up on the audio segments from `load_session_detail`.
+ */
+#[tauri::command]
+pub async fn diarize_session_audio(
+    session_id: String,
+    max_speakers: Option<usize>,
+    app_handle: AppHandle,
+    jobs: tauri::State<'_, Arc<JobRegistry>>,
+    tasks: tauri::State<'_, Arc<TaskRegistry>>,
+) -> Result<String, String> {
+    command_metrics::track_async("diarize_session_audio", async move {
+        let data_dir = profiles::profile_data_dir(&app_handle)?;
+
+        jobs.start(app_handle.clone(), &tasks, "diarize-session-audio", move |ctx| async move {
+            let job = ctx.clone();
+            let diarization = tokio::task::spawn_blocking(move || {
+                diarize_session(
+                    &data_dir,
+                    &session_id,
+                    max_speakers,
+                    |done, total| job.progress_items(done as u64, total as u64, "Analyzing speakers"),
+                    || job.is_cancelled(),
+                )
+            })
+            .await
+            .map_err(|e| format!("Diarization task failed: {}", e))?;
+            ctx.check_cancelled()?;
+
+            serde_json::to_value(diarization?)
+                .map_err(|e| format!("Failed to serialize diarization: {}", e))
+        })
+    }).await
+}
@@ -0,0 +1,238 @@
+/**
+ * Disk Space Module
+ *
+ * Keeps recordings from running the disk full:
+ * - `ensure_recording_space`: pre-flight check used by start_audio_recording
+ *   and start_video_recording; below settings.storage.minFreeSpaceMb they
+ *   refuse to start with `RecordingError::InsufficientDiskSpace`
+ * - The "disk-space-monitor" task checks free space every CHECK_INTERVAL
+ *   while audio or video is recording and emits `disk-space-warning` when it
+ *   drops below warnFreeSpaceMb (`low`) or minFreeSpaceMb (`critical`).
+ *   Each level is reported once until space recovers or recording stops,
+ *   also as a `lowDisk` notification.
+ * - `get_disk_space`: free space on the app data volume
+ */
+
+use serde::Serialize;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::audio_capture::AudioRecorder;
+use crate::background_tasks::TaskRegistry;
+use crate::command_metrics;
+use crate::notifications::{self, NotificationCategory};
+use crate::safe_state::SafeState;
+use crate::settings::{SettingsManager, StorageSettings};
+use crate::video_recording::VideoRecorder;
+
+const TASK_NAME: &str = "disk-space-monitor";
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+/// Error starting a recording; commands surface it through Display
+#[derive(Debug, Clone)]
+pub enum RecordingError {
+    InsufficientDiskSpace { available_mb: u64, required_mb: u64 },
+    Failed(String),
+}
+
+impl std::fmt::Display for RecordingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RecordingError::InsufficientDiskSpace { available_mb, required_mb } => write!(
+                f,
+                "Insufficient disk space: {} MB free, at least {} MB needed to record",
+                available_mb, required_mb
+            ),
+            RecordingError::Failed(message) => f.write_str(message),
+        }
+    }
+}
+
+impl From<String> for RecordingError {
+    fn from(message: String) -> Self {
+        RecordingError::Failed(message)
+    }
+}
+
+impl From<RecordingError> for String {
+    fn from(error: RecordingError) -> Self {
+        error.to_string()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DiskSpaceLevel {
+    Ok,
+    Low,
+    Critical,
+}
+
+impl DiskSpaceLevel {
+    fn for_space(available_mb: u64, settings: &StorageSettings) -> Self {
+        if available_mb < settings.min_free_space_mb {
+            DiskSpaceLevel::Critical
+        } else if available_mb < settings.warn_free_space_mb {
+            DiskSpaceLevel::Low
+        } else {
+            DiskSpaceLevel::Ok
+        }
+    }
+}
+
+/// `disk-space-warning` payload / `get_disk_space` result
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskSpace {
+    pub available_mb: u64,
+    pub level: DiskSpaceLevel,
+    pub min_free_space_mb: u64,
+    pub warn_free_space_mb: u64,
+}
+
+/// Free bytes for the current user on the volume holding `path`
+/// (or its nearest existing ancestor)
+pub fn available_bytes(path: &Path) -> Result<u64, String> {
+    let existing = path
+        .ancestors()
+        .find(|ancestor| ancestor.exists())
+        .ok_or_else(|| format!("No existing directory for {:?}", path))?;
+    volume_available_bytes(existing)
+}
+
+#[cfg(unix)]
+fn volume_available_bytes(path: &Path) -> Result<u64, String> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = std::ffi::CString::new(path.as_os_str().as_bytes())
+        .map_err(|_| "Failed to convert path to C string")?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return Err(format!("Failed to read free disk space: {}", std::io::Error::last_os_error()));
+    }
+    #[allow(clippy::unnecessary_cast)]
+    Ok(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+#[cfg(windows)]
+fn volume_available_bytes(path: &Path) -> Result<u64, String> {
+    use std::os::windows::ffi::OsStrExt;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetDiskFreeSpaceExW(
+            directory: *const u16,
+            free_bytes_available: *mut u64,
+            total_bytes: *mut u64,
+            total_free_bytes: *mut u64,
+        ) -> i32;
+    }
+
+    let wide: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+    let mut available = 0u64;
+    let ok = unsafe {
+        GetDiskFreeSpaceExW(wide.as_ptr(), &mut available, std::ptr::null_mut(), std::ptr::null_mut())
+    };
+    if ok == 0 {
+        return Err(format!("Failed to read free disk space: {}", std::io::Error::last_os_error()));
+    }
+    Ok(available)
+}
+
+pub(crate) fn disk_space(path: &Path, settings: &StorageSettings) -> Result<DiskSpace, String> {
+    let available_mb = available_bytes(path)? / BYTES_PER_MB;
+    Ok(DiskSpace {
+        available_mb,
+        level: DiskSpaceLevel::for_space(available_mb, settings),
+        min_free_space_mb: settings.min_free_space_mb,
+        warn_free_space_mb: settings.warn_free_space_mb,
+    })
+}
+
+/// Refuse to start a recording into `path` below the configured minimum free space
+pub fn ensure_recording_space(app: &AppHandle, path: &Path) -> Result<(), RecordingError> {
+    let settings = app.state::<Arc<SettingsManager>>().get().storage;
+    let space = disk_space(path, &settings)?;
+    if space.level == DiskSpaceLevel::Critical {
+        eprintln!(
+            "⚠️  [DISK] Not starting recording: {} MB free (minimum {} MB)",
+            space.available_mb, settings.min_free_space_mb
+        );
+        return Err(RecordingError::InsufficientDiskSpace {
+            available_mb: space.available_mb,
+            required_mb: settings.min_free_space_mb,
+        });
+    }
+    Ok(())
+}
+
+fn is_recording(app: &AppHandle) -> bool {
+    app.state::<Arc<AudioRecorder>>().is_recording()
+        || app.state::<Arc<SafeState<VideoRecorder>>>().lock().is_recording()
+}
+
+/// Start watching free space during recordings
+pub fn start(app: AppHandle, registry: &TaskRegistry) -> Result<(), String> {
+    registry.spawn(TASK_NAME, |mut shutdown| async move {
+        let mut reported = DiskSpaceLevel::Ok;
+        let mut interval = tokio::time::interval(CHECK_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+
+            if !is_recording(&app) {
+                reported = DiskSpaceLevel::Ok;
+                continue;
+            }
+            let Ok(data_dir) = app.path().app_data_dir() else {
+                continue;
+            };
+            let settings = app.state::<Arc<SettingsManager>>().get().storage;
+            let space = match disk_space(&data_dir, &settings) {
+                Ok(space) => space,
+                Err(e) => {
+                    eprintln!("⚠️  [DISK] {}", e);
+                    continue;
+                }
+            };
+
+            if space.level > reported {
+                eprintln!(
+                    "⚠️  [DISK] {:?} disk space while recording: {} MB free",
+                    space.level, space.available_mb
+                );
+                let _ = app.emit("disk-space-warning", &space);
+                let title = match space.level {
+                    DiskSpaceLevel::Critical => "Disk almost full",
+                    _ => "Low disk space",
+                };
+                notifications::notify(
+                    &app,
+                    NotificationCategory::LowDisk,
+                    "recording",
+                    title.to_string(),
+                    format!("{} MB free while recording.", space.available_mb),
+                );
+            }
+            reported = space.level;
+        }
+    })
+}
+
+/// Tauri command to get free space on the app data volume
+#[tauri::command]
+pub fn get_disk_space(app: AppHandle) -> Result<DiskSpace, String> {
+    command_metrics::track("get_disk_space", || {
+        let data_dir = app
+            .path()
+            .app_data_dir()
+            .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+        disk_space(&data_dir, &app.state::<Arc<SettingsManager>>().get().storage)
+    })
+}
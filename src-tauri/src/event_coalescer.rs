@@ -0,0 +1,165 @@
+/**
+ * Event Coalescer Module
+ *
+ * Batches high-frequency backend events (countdown-tick, activity-stats) so
+ * they cross the IPC bridge once per window (real-time meter readings use
+ * realtime_emitter.rs instead):
+ * - Events pushed within a window are merged per name (latest payload wins)
+ * - Each flush is a single `coalesced-events` emission with a sequence number
+ * - The frontend acknowledges flushes with `ack_coalesced_events`; while too many
+ *   flushes are unacknowledged, flushing pauses and only the newest readings are kept
+ * - Window is configurable via settings.performance.eventCoalesceMs
+ */
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use crate::background_tasks::TaskRegistry;
+use crate::command_metrics;
+use crate::safe_state::SafeState;
+
+/// Event carrying each coalesced batch
+pub const COALESCED_EVENT: &str = "coalesced-events";
+
+/// Flushes allowed in flight before the frontend is considered lagging
+const MAX_UNACKED_FLUSHES: u64 = 4;
+
+/// Windows skipped while lagging before a batch is sent anyway (recovers
+/// from acks that never arrive, e.g. after a webview reload)
+const MAX_SKIPPED_WINDOWS: u64 = 20;
+
+const DEFAULT_WINDOW_MS: u64 = 100;
+
+/// One merged event inside a batch
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoalescedEvent {
+    pub event: String,
+    pub payload: serde_json::Value,
+}
+
+/// Payload of `coalesced-events`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoalescedBatch {
+    pub seq: u64,
+    pub events: Vec<CoalescedEvent>,
+    /// Readings superseded before they could be delivered (since the last batch)
+    pub dropped: u64,
+}
+
+/// Managed emitter that merges events and flushes them on a timer
+pub struct CoalescingEmitter {
+    window_ms: AtomicU64,
+    pending: SafeState<HashMap<&'static str, serde_json::Value>>,
+    emitted_seq: AtomicU64,
+    acked_seq: AtomicU64,
+    dropped: AtomicU64,
+    skipped: AtomicU64,
+}
+
+impl CoalescingEmitter {
+    pub fn new() -> Self {
+        Self {
+            window_ms: AtomicU64::new(DEFAULT_WINDOW_MS),
+            pending: SafeState::new("event_coalescer", HashMap::new()),
+            emitted_seq: AtomicU64::new(0),
+            acked_seq: AtomicU64::new(0),
+            dropped: AtomicU64::new(0),
+            skipped: AtomicU64::new(0),
+        }
+    }
+
+    /// Apply the flush window from settings
+    pub fn set_window(&self, window_ms: u64) {
+        self.window_ms.store(window_ms, Ordering::SeqCst);
+    }
+
+    /// Queue an event; replaces any undelivered payload with the same name
+    pub fn push<T: Serialize>(&self, event: &'static str, payload: T) {
+        let value = match serde_json::to_value(payload) {
+            Ok(value) => value,
+            Err(e) => {
+                eprintln!("❌ [EVENTS] Failed to serialize {} payload: {}", event, e);
+                return;
+            }
+        };
+
+        if self.pending.lock().insert(event, value).is_some() {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Record that the frontend processed every batch up to `seq`
+    pub fn ack(&self, seq: u64) {
+        self.acked_seq.fetch_max(seq, Ordering::SeqCst);
+    }
+
+    /// Frontends that never acknowledge are treated as keeping up
+    fn is_lagging(&self) -> bool {
+        let acked = self.acked_seq.load(Ordering::SeqCst);
+        acked > 0 && self.emitted_seq.load(Ordering::SeqCst).saturating_sub(acked) >= MAX_UNACKED_FLUSHES
+    }
+
+    fn flush(&self, app: &AppHandle) {
+        if self.is_lagging() && self.skipped.fetch_add(1, Ordering::SeqCst) < MAX_SKIPPED_WINDOWS {
+            return; // Keep merging; stale readings are replaced until the frontend catches up
+        }
+        self.skipped.store(0, Ordering::SeqCst);
+
+        let events: Vec<CoalescedEvent> = self.pending
+            .lock()
+            .drain()
+            .map(|(event, payload)| CoalescedEvent { event: event.to_string(), payload })
+            .collect();
+        if events.is_empty() {
+            return;
+        }
+
+        let batch = CoalescedBatch {
+            seq: self.emitted_seq.fetch_add(1, Ordering::SeqCst) + 1,
+            events,
+            dropped: self.dropped.swap(0, Ordering::Relaxed),
+        };
+        if let Err(e) = app.emit(COALESCED_EVENT, &batch) {
+            eprintln!("❌ [EVENTS] Failed to emit coalesced events: {}", e);
+        }
+    }
+
+    /// Start the flush loop as the "event-coalescer" background task
+    pub fn start(self: &Arc<Self>, app: AppHandle, registry: &TaskRegistry) -> Result<(), String> {
+        let emitter = self.clone();
+        registry.spawn("event-coalescer", |mut shutdown| async move {
+            loop {
+                let window = Duration::from_millis(emitter.window_ms.load(Ordering::SeqCst));
+                tokio::select! {
+                    _ = shutdown.cancelled() => break,
+                    _ = tokio::time::sleep(window) => {}
+                }
+                emitter.flush(&app);
+            }
+        })
+    }
+}
+
+impl Default for CoalescingEmitter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Tauri command: acknowledge a `coalesced-events` batch
+#[tauri::command]
+pub fn ack_coalesced_events(
+    emitter: tauri::State<Arc<CoalescingEmitter>>,
+    seq: u64,
+) -> Result<(), String> {
+    command_metrics::track("ack_coalesced_events", || {
+        emitter.ack(seq);
+        Ok(())
+    })
+}
@@ -0,0 +1,1413 @@
+/**
+ * Export Module
+ *
+ * Turns a stored session into files that can be shared outside the app:
+ * - `export_session_html`: one self-contained HTML report (summary, activity
+ *   charts, timeline with inline base64 screenshots, transcript) that opens
+ *   in any browser without the app
+ * - `export_session_pdf`: a printable PDF (summary, key screenshots,
+ *   timestamped transcript) written by the small PDF writer below, using the
+ *   standard Helvetica fonts and JPEG-embedded screenshots
+ * - `export_session_markdown`: an Obsidian/Logseq note (YAML frontmatter,
+ *   screenshots copied next to it, transcript) in `<vault>/Taskerino/`;
+ *   `auto_export_session` does the same for settings.export once a session
+ *   has been enriched
+ * - `export_session_subtitles`: SRT or WebVTT captions from the audio segment
+ *   transcripts, timed against the merged session video (which starts with
+ *   the session and skips recording pauses)
+ *
+ * Sessions are read as raw JSON (session_storage::read_session_value) since
+ * reports need fields such as the AI summary and screenshot analysis that
+ * the lightweight session models skip.
+ */
+
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tauri::AppHandle;
+
+use crate::attachment_metadata::read_attachment_bytes;
+use crate::command_metrics;
+use crate::profiles;
+use crate::session_models::PauseGap;
+use crate::session_storage;
+use crate::settings::SettingsManager;
+
+/// Result of an export command
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportResult {
+    pub path: String,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct ExportSession {
+    id: String,
+    name: String,
+    description: String,
+    start_time: String,
+    end_time: Option<String>,
+    total_paused_time: Option<f64>,
+    category: Option<String>,
+    sub_category: Option<String>,
+    tags: Vec<String>,
+    summary: Option<ExportSummary>,
+    screenshots: Vec<ExportScreenshot>,
+    audio_segments: Vec<ExportAudioSegment>,
+    full_transcription: Option<String>,
+    transcript: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct ExportSummary {
+    narrative: String,
+    achievements: Vec<String>,
+    blockers: Vec<String>,
+    recommended_tasks: Vec<ExportTask>,
+    key_insights: Vec<ExportInsight>,
+    focus_areas: Vec<ExportFocusArea>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct ExportTask {
+    title: String,
+    priority: String,
+    context: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct ExportInsight {
+    insight: String,
+    timestamp: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct ExportFocusArea {
+    area: String,
+    /// Minutes
+    duration: f64,
+    percentage: f64,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct ExportScreenshot {
+    timestamp: String,
+    attachment_id: String,
+    ai_analysis: Option<ExportAnalysis>,
+    user_comment: Option<String>,
+    flagged: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct ExportAnalysis {
+    summary: String,
+    detected_activity: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct ExportAudioSegment {
+    timestamp: String,
+    /// Seconds
+    duration: f64,
+    transcription: String,
+    speaker: Option<String>,
+    pauses: Vec<PauseGap>,
+}
+
+impl ExportSession {
+    fn load(data_dir: &Path, session_id: &str) -> Result<Self, String> {
+        let value = session_storage::read_session_value(data_dir, session_id)?;
+        serde_json::from_value(value).map_err(|e| format!("Failed to read session {}: {}", session_id, e))
+    }
+
+    fn title(&self) -> &str {
+        if self.name.is_empty() { "Untitled session" } else { &self.name }
+    }
+
+    /// Date, active duration and category
+    fn meta_line(&self) -> String {
+        let mut meta = vec![format_time(&self.start_time, "%A, %B %-d, %Y %H:%M")];
+        if let Some(minutes) = self.duration_minutes() {
+            meta.push(format_minutes(minutes));
+        }
+        if let Some(category) = &self.category {
+            meta.push(match &self.sub_category {
+                Some(sub) => format!("{} · {}", category, sub),
+                None => category.clone(),
+            });
+        }
+        meta.join(" · ")
+    }
+
+    /// Full transcript, falling back to the per-segment transcriptions
+    fn transcript(&self) -> String {
+        if let Some(text) = self.full_transcription.as_ref().or(self.transcript.as_ref()) {
+            if !text.trim().is_empty() {
+                return text.clone();
+            }
+        }
+        self.audio_segments
+            .iter()
+            .map(|segment| segment.transcription.trim())
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
+    /// Active minutes (end - start - paused time)
+    fn duration_minutes(&self) -> Option<i64> {
+        let start = parse_time(&self.start_time)?;
+        let end = parse_time(self.end_time.as_deref()?)?;
+        let paused_ms = self.total_paused_time.unwrap_or(0.0) as i64;
+        Some(((end - start).num_milliseconds() - paused_ms).max(0) / 60_000)
+    }
+}
+
+fn parse_time(timestamp: &str) -> Option<chrono::DateTime<chrono::Local>> {
+    chrono::DateTime::parse_from_rfc3339(timestamp)
+        .ok()
+        .map(|time| time.with_timezone(&chrono::Local))
+}
+
+fn format_time(timestamp: &str, format: &str) -> String {
+    parse_time(timestamp)
+        .map(|time| time.format(format).to_string())
+        .unwrap_or_else(|| timestamp.to_string())
+}
+
+fn format_minutes(minutes: i64) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, m) => format!("{}m", m),
+        (h, m) => format!("{}h {}m", h, m),
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Stable color per activity name for the charts
+fn activity_color(activity: &str) -> String {
+    let hue = activity.bytes().fold(0u32, |hash, b| hash.wrapping_mul(31).wrapping_add(b as u32)) % 360;
+    format!("hsl({}, 60%, 55%)", hue)
+}
+
+/// Screenshot bytes and mime type, or None if its data is gone (e.g. rotated away)
+fn read_screenshot(attachments_dir: &Path, attachment_id: &str) -> Option<(Vec<u8>, String)> {
+    let meta: serde_json::Value = std::fs::read_to_string(attachments_dir.join(format!("{}.meta.json", attachment_id)))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+    let bytes = read_attachment_bytes(attachments_dir, attachment_id, &meta).ok()?;
+    let mime_type = meta.get("mimeType").and_then(|m| m.as_str()).unwrap_or("image/png");
+    Some((bytes, mime_type.to_string()))
+}
+
+fn screenshot_data_url(attachments_dir: &Path, attachment_id: &str) -> Option<String> {
+    let (bytes, mime_type) = read_screenshot(attachments_dir, attachment_id)?;
+    Some(format!(
+        "data:{};base64,{}",
+        mime_type,
+        base64::Engine::encode(&base64::engine::general_purpose::STANDARD, bytes)
+    ))
+}
+
+const REPORT_CSS: &str = r#"
+body { font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", sans-serif; max-width: 960px; margin: 0 auto; padding: 32px 24px; color: #1f2937; line-height: 1.5; }
+h1 { margin-bottom: 4px; }
+h2 { margin-top: 40px; border-bottom: 1px solid #e5e7eb; padding-bottom: 6px; }
+.meta { color: #6b7280; }
+.tag { display: inline-block; background: #eef2ff; color: #4338ca; border-radius: 999px; padding: 1px 10px; margin-right: 6px; font-size: 0.85em; }
+.bar { display: flex; align-items: center; margin: 6px 0; }
+.bar-label { width: 220px; flex-shrink: 0; }
+.bar-track { flex: 1; background: #f3f4f6; border-radius: 4px; height: 14px; }
+.bar-fill { height: 14px; border-radius: 4px; }
+.bar-value { width: 90px; text-align: right; color: #6b7280; font-size: 0.9em; }
+.legend span { display: inline-block; margin-right: 14px; font-size: 0.85em; }
+.legend i { display: inline-block; width: 10px; height: 10px; border-radius: 2px; margin-right: 4px; }
+.entry { border-left: 3px solid #e5e7eb; padding: 4px 0 12px 16px; margin-left: 4px; }
+.entry.flagged { border-left-color: #f59e0b; }
+.entry time { color: #6b7280; font-size: 0.85em; }
+.entry img { max-width: 100%; border: 1px solid #e5e7eb; border-radius: 6px; margin-top: 6px; }
+.gap { color: #9ca3af; font-style: italic; }
+.transcript { white-space: pre-wrap; background: #f9fafb; padding: 16px; border-radius: 6px; }
+footer { margin-top: 48px; color: #9ca3af; font-size: 0.8em; }
+"#;
+
+fn render_summary(html: &mut String, summary: &ExportSummary) {
+    let _ = writeln!(html, "<h2>Summary</h2>");
+    if !summary.narrative.trim().is_empty() {
+        let _ = writeln!(html, "<p>{}</p>", escape_html(&summary.narrative));
+    }
+    for (title, items) in [("Achievements", &summary.achievements), ("Blockers", &summary.blockers)] {
+        if items.is_empty() {
+            continue;
+        }
+        let _ = writeln!(html, "<h3>{}</h3><ul>", title);
+        for item in items {
+            let _ = writeln!(html, "<li>{}</li>", escape_html(item));
+        }
+        let _ = writeln!(html, "</ul>");
+    }
+    if !summary.recommended_tasks.is_empty() {
+        let _ = writeln!(html, "<h3>Recommended tasks</h3><ul>");
+        for task in &summary.recommended_tasks {
+            let _ = writeln!(
+                html,
+                "<li><strong>{}</strong> <span class=\"meta\">({})</span><br>{}</li>",
+                escape_html(&task.title),
+                escape_html(&task.priority),
+                escape_html(&task.context)
+            );
+        }
+        let _ = writeln!(html, "</ul>");
+    }
+    if !summary.key_insights.is_empty() {
+        let _ = writeln!(html, "<h3>Key insights</h3><ul>");
+        for insight in &summary.key_insights {
+            let _ = writeln!(
+                html,
+                "<li><span class=\"meta\">{}</span> {}</li>",
+                escape_html(&format_time(&insight.timestamp, "%H:%M")),
+                escape_html(&insight.insight)
+            );
+        }
+        let _ = writeln!(html, "</ul>");
+    }
+}
+
+/// Focus-area bars and an activity strip built from screenshot analysis
+fn render_activity(html: &mut String, session: &ExportSession) {
+    let focus_areas = session.summary.as_ref().map(|s| s.focus_areas.as_slice()).unwrap_or_default();
+
+    // Each screenshot's activity lasts until the next screenshot
+    let mut spans: Vec<(i64, i64, &str)> = Vec::new();
+    let points: Vec<(i64, &str)> = session
+        .screenshots
+        .iter()
+        .filter_map(|s| {
+            let activity = s.ai_analysis.as_ref().map(|a| a.detected_activity.as_str()).filter(|a| !a.is_empty())?;
+            Some((parse_time(&s.timestamp)?.timestamp_millis(), activity))
+        })
+        .collect();
+    let end = session.end_time.as_deref().and_then(parse_time).map(|t| t.timestamp_millis());
+    for (i, (start, activity)) in points.iter().enumerate() {
+        let span_end = points.get(i + 1).map(|(t, _)| *t).or(end).unwrap_or(*start);
+        if span_end > *start {
+            spans.push((*start, span_end, activity));
+        }
+    }
+
+    if focus_areas.is_empty() && spans.is_empty() {
+        return;
+    }
+    let _ = writeln!(html, "<h2>Activity</h2>");
+
+    if !focus_areas.is_empty() {
+        let _ = writeln!(html, "<h3>Focus areas</h3>");
+        for area in focus_areas {
+            let _ = writeln!(
+                html,
+                "<div class=\"bar\"><span class=\"bar-label\">{}</span><div class=\"bar-track\"><div class=\"bar-fill\" style=\"width: {:.1}%; background: {}\"></div></div><span class=\"bar-value\">{}</span></div>",
+                escape_html(&area.area),
+                area.percentage.clamp(0.0, 100.0),
+                activity_color(&area.area),
+                format_minutes(area.duration.round() as i64)
+            );
+        }
+    }
+
+    if let (Some(first), Some(last)) = (spans.first(), spans.last()) {
+        let (start, total) = (first.0, (last.1 - first.0).max(1) as f64);
+        let _ = writeln!(html, "<h3>Activity over time</h3>");
+        let _ = writeln!(html, "<svg viewBox=\"0 0 1000 32\" width=\"100%\" height=\"32\" preserveAspectRatio=\"none\">");
+        for (span_start, span_end, activity) in &spans {
+            let _ = writeln!(
+                html,
+                "<rect x=\"{:.2}\" y=\"0\" width=\"{:.2}\" height=\"32\" fill=\"{}\"><title>{}</title></rect>",
+                (span_start - start) as f64 / total * 1000.0,
+                (span_end - span_start) as f64 / total * 1000.0,
+                activity_color(activity),
+                escape_html(activity)
+            );
+        }
+        let _ = writeln!(html, "</svg><div class=\"legend\">");
+        let mut activities: Vec<&str> = spans.iter().map(|(_, _, activity)| *activity).collect();
+        activities.sort_unstable();
+        activities.dedup();
+        for activity in activities {
+            let _ = writeln!(html, "<span><i style=\"background: {}\"></i>{}</span>", activity_color(activity), escape_html(activity));
+        }
+        let _ = writeln!(html, "</div>");
+    }
+}
+
+/// Screenshots and audio segments in time order
+fn render_timeline(html: &mut String, session: &ExportSession, attachments_dir: &Path) {
+    enum Entry<'a> {
+        Screenshot(&'a ExportScreenshot),
+        Audio(&'a ExportAudioSegment),
+    }
+    let mut entries: Vec<(&str, Entry)> = session
+        .screenshots
+        .iter()
+        .map(|s| (s.timestamp.as_str(), Entry::Screenshot(s)))
+        .chain(session.audio_segments.iter().map(|a| (a.timestamp.as_str(), Entry::Audio(a))))
+        .collect();
+    if entries.is_empty() {
+        return;
+    }
+    entries.sort_by_key(|(timestamp, _)| parse_time(timestamp).map(|t| t.timestamp_millis()).unwrap_or(0));
+
+    let _ = writeln!(html, "<h2>Timeline</h2>");
+    for (timestamp, entry) in entries {
+        let time = escape_html(&format_time(timestamp, "%H:%M:%S"));
+        match entry {
+            Entry::Screenshot(screenshot) => {
+                let class = if screenshot.flagged { "entry flagged" } else { "entry" };
+                let _ = writeln!(html, "<div class=\"{}\"><time>{}</time> 📸", class, time);
+                if let Some(analysis) = &screenshot.ai_analysis {
+                    let _ = writeln!(html, "<div>{}</div>", escape_html(&analysis.summary));
+                }
+                if let Some(comment) = screenshot.user_comment.as_deref().filter(|c| !c.is_empty()) {
+                    let _ = writeln!(html, "<div><em>{}</em></div>", escape_html(comment));
+                }
+                if let Some(data_url) = screenshot_data_url(attachments_dir, &screenshot.attachment_id) {
+                    let _ = writeln!(html, "<img src=\"{}\" alt=\"Screenshot at {}\" loading=\"lazy\">", data_url, time);
+                }
+                let _ = writeln!(html, "</div>");
+            }
+            Entry::Audio(segment) => {
+                let _ = writeln!(html, "<div class=\"entry\"><time>{}</time> 🎤", time);
+                if let Some(speaker) = &segment.speaker {
+                    let _ = write!(html, " <strong>{}</strong>", escape_html(speaker));
+                }
+                let _ = writeln!(
+                    html,
+                    " <span class=\"meta\">({:.0}s)</span><div>{}</div>",
+                    segment.duration,
+                    escape_html(&segment.transcription)
+                );
+                for pause in &segment.pauses {
+                    let _ = writeln!(
+                        html,
+                        "<div class=\"gap\">(paused {})</div>",
+                        format_minutes((pause.duration_secs / 60.0).round() as i64)
+                    );
+                }
+                let _ = writeln!(html, "</div>");
+            }
+        }
+    }
+}
+
+fn render_html(session: &ExportSession, attachments_dir: &Path) -> String {
+    let mut html = String::new();
+    let title = escape_html(session.title());
+    let _ = writeln!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n<title>{}</title>\n<style>{}</style>\n</head>\n<body>",
+        title, REPORT_CSS
+    );
+
+    let _ = writeln!(html, "<h1>{}</h1>", title);
+    let _ = writeln!(html, "<div class=\"meta\">{}</div>", escape_html(&session.meta_line()));
+    if !session.tags.is_empty() {
+        let _ = write!(html, "<p>");
+        for tag in &session.tags {
+            let _ = write!(html, "<span class=\"tag\">{}</span>", escape_html(tag));
+        }
+        let _ = writeln!(html, "</p>");
+    }
+    if !session.description.trim().is_empty() {
+        let _ = writeln!(html, "<p>{}</p>", escape_html(&session.description));
+    }
+
+    if let Some(summary) = &session.summary {
+        render_summary(&mut html, summary);
+    }
+    render_activity(&mut html, session);
+    render_timeline(&mut html, session, attachments_dir);
+
+    let transcript = session.transcript();
+    if !transcript.is_empty() {
+        let _ = writeln!(html, "<h2>Transcript</h2>\n<div class=\"transcript\">{}</div>", escape_html(&transcript));
+    }
+
+    let _ = writeln!(
+        html,
+        "<footer>Exported from Taskerino on {} · session {}</footer>\n</body>\n</html>",
+        chrono::Local::now().format("%Y-%m-%d %H:%M"),
+        escape_html(&session.id)
+    );
+    html
+}
+
+/// Folder inside the vault for session notes (screenshots go to `assets/<session>/`)
+const VAULT_FOLDER: &str = "Taskerino";
+
+/// Characters that aren't allowed in file names on some platform
+fn sanitize_file_name(name: &str) -> String {
+    let sanitized: String = name
+        .chars()
+        .map(|c| if matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' | '#' | '[' | ']' | '^') || c.is_control() { '-' } else { c })
+        .collect();
+    sanitized.trim().trim_matches('.').to_string()
+}
+
+/// Tags can't contain spaces in Obsidian/Logseq
+fn markdown_tag(tag: &str) -> String {
+    tag.trim().trim_start_matches('#').split_whitespace().collect::<Vec<_>>().join("-")
+}
+
+/// YAML double-quoted scalar (JSON strings are valid YAML)
+fn yaml_string(value: &str) -> String {
+    serde_json::Value::String(value.to_string()).to_string()
+}
+
+fn image_extension(mime_type: &str) -> &'static str {
+    match mime_type {
+        "image/jpeg" | "image/jpg" => "jpg",
+        "image/webp" => "webp",
+        "image/gif" => "gif",
+        _ => "png",
+    }
+}
+
+/// Render the note and copy its screenshots into the vault; returns (note path, markdown)
+fn render_markdown(session: &ExportSession, attachments_dir: &Path, vault: &Path) -> Result<(PathBuf, String), String> {
+    let folder = vault.join(VAULT_FOLDER);
+    let date = format_time(&session.start_time, "%Y-%m-%d");
+    let note_path = folder.join(format!("{} {}.md", date, sanitize_file_name(session.title())));
+    let asset_folder = format!("assets/{}", sanitize_file_name(&session.id));
+
+    let mut md = String::new();
+    let _ = writeln!(md, "---");
+    let _ = writeln!(md, "title: {}", yaml_string(session.title()));
+    let _ = writeln!(md, "date: {}", date);
+    let _ = writeln!(md, "start: {}", yaml_string(&session.start_time));
+    if let Some(end) = &session.end_time {
+        let _ = writeln!(md, "end: {}", yaml_string(end));
+    }
+    if let Some(minutes) = session.duration_minutes() {
+        let _ = writeln!(md, "duration: {}", yaml_string(&format_minutes(minutes)));
+        let _ = writeln!(md, "duration_minutes: {}", minutes);
+    }
+    if let Some(category) = &session.category {
+        let _ = writeln!(md, "category: {}", yaml_string(category));
+    }
+    let _ = writeln!(md, "tags:");
+    for tag in std::iter::once("taskerino".to_string()).chain(session.tags.iter().map(|t| markdown_tag(t)).filter(|t| !t.is_empty())) {
+        let _ = writeln!(md, "  - {}", yaml_string(&tag));
+    }
+    let _ = writeln!(md, "session_id: {}", yaml_string(&session.id));
+    let _ = writeln!(md, "---\n");
+
+    let _ = writeln!(md, "# {}\n", session.title());
+    if !session.description.trim().is_empty() {
+        let _ = writeln!(md, "{}\n", session.description.trim());
+    }
+
+    if let Some(summary) = &session.summary {
+        let _ = writeln!(md, "## Summary\n");
+        if !summary.narrative.trim().is_empty() {
+            let _ = writeln!(md, "{}\n", summary.narrative.trim());
+        }
+        for (title, items) in [("Achievements", &summary.achievements), ("Blockers", &summary.blockers)] {
+            if !items.is_empty() {
+                let _ = writeln!(md, "### {}\n", title);
+                for item in items {
+                    let _ = writeln!(md, "- {}", item);
+                }
+                let _ = writeln!(md);
+            }
+        }
+        if !summary.recommended_tasks.is_empty() {
+            let _ = writeln!(md, "### Recommended tasks\n");
+            for task in &summary.recommended_tasks {
+                let _ = writeln!(md, "- [ ] {} ({}) - {}", task.title, task.priority, task.context);
+            }
+            let _ = writeln!(md);
+        }
+        if !summary.key_insights.is_empty() {
+            let _ = writeln!(md, "### Key insights\n");
+            for insight in &summary.key_insights {
+                let _ = writeln!(md, "- **{}** {}", format_time(&insight.timestamp, "%H:%M"), insight.insight);
+            }
+            let _ = writeln!(md);
+        }
+    }
+
+    let mut copied = 0;
+    for screenshot in &session.screenshots {
+        let Some((bytes, mime_type)) = read_screenshot(attachments_dir, &screenshot.attachment_id) else {
+            continue;
+        };
+        if copied == 0 {
+            std::fs::create_dir_all(folder.join(&asset_folder))
+                .map_err(|e| format!("Failed to create screenshot folder: {}", e))?;
+            let _ = writeln!(md, "## Screenshots\n");
+        }
+        copied += 1;
+        let time = format_time(&screenshot.timestamp, "%H:%M:%S");
+        let file_name = format!("{}-{:03}.{}", time.replace(':', ""), copied, image_extension(&mime_type));
+        std::fs::write(folder.join(&asset_folder).join(&file_name), bytes)
+            .map_err(|e| format!("Failed to write screenshot: {}", e))?;
+
+        let _ = writeln!(md, "### {}{}\n", time, if screenshot.flagged { " ⚑" } else { "" });
+        let _ = writeln!(md, "![{}]({}/{})\n", time, asset_folder, file_name);
+        if let Some(analysis) = screenshot.ai_analysis.as_ref().filter(|a| !a.summary.trim().is_empty()) {
+            let _ = writeln!(md, "{}\n", analysis.summary.trim());
+        }
+        if let Some(comment) = screenshot.user_comment.as_deref().filter(|c| !c.is_empty()) {
+            let _ = writeln!(md, "> {}\n", comment);
+        }
+    }
+
+    let segments: Vec<&ExportAudioSegment> = session
+        .audio_segments
+        .iter()
+        .filter(|segment| !segment.transcription.trim().is_empty())
+        .collect();
+    if !segments.is_empty() {
+        let _ = writeln!(md, "## Transcript\n");
+        for segment in segments {
+            let speaker = segment.speaker.as_ref().map(|s| format!(" {}:", s)).unwrap_or_default();
+            let _ = writeln!(md, "**{}**{} {}\n", format_time(&segment.timestamp, "%H:%M:%S"), speaker, segment.transcription.trim());
+        }
+    } else {
+        let transcript = session.transcript();
+        if !transcript.is_empty() {
+            let _ = writeln!(md, "## Transcript\n\n{}\n", transcript);
+        }
+    }
+
+    Ok((note_path, md))
+}
+
+fn export_markdown(data_dir: &Path, session_id: &str, vault: &Path) -> Result<ExportResult, String> {
+    if !vault.is_dir() {
+        return Err(format!("Vault folder {:?} does not exist", vault));
+    }
+    let session = ExportSession::load(data_dir, session_id)?;
+    let (path, markdown) = render_markdown(&session, &data_dir.join("attachments"), vault)?;
+    let result = write_export(&path, markdown.as_bytes())?;
+    println!("📤 [EXPORT] Session {} exported to {} ({} bytes)", session_id, result.path, result.bytes);
+    Ok(result)
+}
+
+/// Subtitle file format
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SubtitleFormat {
+    Srt,
+    Vtt,
+}
+
+/// Longest cue (two lines of SUBTITLE_LINE_CHARS)
+const SUBTITLE_CUE_CHARS: usize = 84;
+const SUBTITLE_LINE_CHARS: usize = 42;
+/// Cues ending a sentence are closed early once they are this long
+const SUBTITLE_SENTENCE_CHARS: usize = 30;
+
+struct SubtitleCue {
+    start_ms: i64,
+    end_ms: i64,
+    speaker: Option<String>,
+    text: String,
+}
+
+/// Maps wall-clock time onto the merged session video, which starts with the
+/// session and leaves paused periods out
+struct VideoTimeline {
+    start_ms: i64,
+    /// (paused, resumed) in epoch ms, sorted
+    pauses: Vec<(i64, i64)>,
+}
+
+impl VideoTimeline {
+    fn new(session: &ExportSession) -> Option<Self> {
+        let start_ms = parse_time(&session.start_time)?.timestamp_millis();
+        let mut pauses: Vec<(i64, i64)> = session
+            .audio_segments
+            .iter()
+            .flat_map(|segment| &segment.pauses)
+            .filter_map(|pause| Some((parse_time(&pause.paused_at)?.timestamp_millis(), parse_time(&pause.resumed_at)?.timestamp_millis())))
+            .collect();
+        pauses.sort_unstable();
+        pauses.dedup();
+        Some(Self { start_ms, pauses })
+    }
+
+    fn offset_ms(&self, time_ms: i64) -> i64 {
+        let paused: i64 = self
+            .pauses
+            .iter()
+            .map(|&(paused, resumed)| (resumed.min(time_ms) - paused).max(0))
+            .sum();
+        (time_ms - self.start_ms - paused).max(0)
+    }
+}
+
+/// Split a transcription into cue-sized pieces, preferring sentence ends
+fn split_cues(text: &str) -> Vec<String> {
+    let mut cues = Vec::new();
+    let mut cue = String::new();
+    for word in text.split_whitespace() {
+        if !cue.is_empty() && cue.chars().count() + 1 + word.chars().count() > SUBTITLE_CUE_CHARS {
+            cues.push(std::mem::take(&mut cue));
+        }
+        if !cue.is_empty() {
+            cue.push(' ');
+        }
+        cue.push_str(word);
+        if cue.chars().count() >= SUBTITLE_SENTENCE_CHARS && cue.ends_with(['.', '?', '!']) {
+            cues.push(std::mem::take(&mut cue));
+        }
+    }
+    if !cue.is_empty() {
+        cues.push(cue);
+    }
+    cues
+}
+
+/// Cues per audio segment, timed by spreading the segment's duration over its
+/// text (transcripts carry no word timings once stored)
+fn subtitle_cues(session: &ExportSession) -> Result<Vec<SubtitleCue>, String> {
+    let timeline = VideoTimeline::new(session).ok_or("Session has an invalid start time")?;
+    let mut segments: Vec<(i64, &ExportAudioSegment)> = session
+        .audio_segments
+        .iter()
+        .filter(|segment| !segment.transcription.trim().is_empty())
+        .filter_map(|segment| Some((parse_time(&segment.timestamp)?.timestamp_millis(), segment)))
+        .collect();
+    segments.sort_by_key(|(time, _)| *time);
+
+    let mut cues = Vec::new();
+    for (time, segment) in segments {
+        let base_ms = timeline.offset_ms(time);
+        let duration_ms = (segment.duration * 1000.0) as i64;
+        let pieces = split_cues(&segment.transcription);
+        let total_chars = pieces.iter().map(|p| p.chars().count()).sum::<usize>().max(1) as i64;
+
+        let mut chars_before = 0i64;
+        for piece in pieces {
+            let chars = piece.chars().count() as i64;
+            let start_ms = base_ms + duration_ms * chars_before / total_chars;
+            chars_before += chars;
+            let end_ms = (base_ms + duration_ms * chars_before / total_chars).max(start_ms + 500);
+            cues.push(SubtitleCue { start_ms, end_ms, speaker: segment.speaker.clone(), text: piece });
+        }
+    }
+    if cues.is_empty() {
+        return Err("Session has no transcript to export".to_string());
+    }
+    Ok(cues)
+}
+
+fn format_subtitle_time(ms: i64, separator: char) -> String {
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        ms / 3_600_000,
+        ms / 60_000 % 60,
+        ms / 1000 % 60,
+        separator,
+        ms % 1000
+    )
+}
+
+fn render_subtitles(cues: &[SubtitleCue], format: SubtitleFormat) -> String {
+    let mut out = String::new();
+    if let SubtitleFormat::Vtt = format {
+        out.push_str("WEBVTT\n\n");
+    }
+    for (index, cue) in cues.iter().enumerate() {
+        let text = wrap_text(&cue.text, SUBTITLE_LINE_CHARS).join("\n");
+        match format {
+            SubtitleFormat::Srt => {
+                let speaker = cue.speaker.as_ref().map(|s| format!("{}: ", s)).unwrap_or_default();
+                let _ = writeln!(
+                    out,
+                    "{}\n{} --> {}\n{}{}\n",
+                    index + 1,
+                    format_subtitle_time(cue.start_ms, ','),
+                    format_subtitle_time(cue.end_ms, ','),
+                    speaker,
+                    text
+                );
+            }
+            SubtitleFormat::Vtt => {
+                // Cue text is markup in WebVTT
+                let text = text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+                let speaker = cue.speaker.as_ref().map(|s| format!("<v {}>", s.replace('>', ""))).unwrap_or_default();
+                let _ = writeln!(
+                    out,
+                    "{} --> {}\n{}{}\n",
+                    format_subtitle_time(cue.start_ms, '.'),
+                    format_subtitle_time(cue.end_ms, '.'),
+                    speaker,
+                    text
+                );
+            }
+        }
+    }
+    out
+}
+
+/// PDF page size (points)
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PageSize {
+    #[default]
+    A4,
+    Letter,
+}
+
+impl PageSize {
+    fn dimensions(self) -> (f32, f32) {
+        match self {
+            PageSize::A4 => (595.28, 841.89),
+            PageSize::Letter => (612.0, 792.0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PdfExportOptions {
+    pub page_size: PageSize,
+    /// Include the timestamped transcript section
+    pub include_transcript: bool,
+    /// Flagged/commented screenshots first, then evenly spaced ones
+    pub max_screenshots: usize,
+}
+
+impl Default for PdfExportOptions {
+    fn default() -> Self {
+        Self {
+            page_size: PageSize::A4,
+            include_transcript: true,
+            max_screenshots: 12,
+        }
+    }
+}
+
+const PDF_MARGIN: f32 = 50.0;
+const PDF_LINE_HEIGHT: f32 = 1.35;
+/// Average Helvetica glyph width (em); wrapping errs on the narrow side
+const PDF_CHAR_WIDTH: f32 = 0.52;
+const PDF_BOLD_CHAR_WIDTH: f32 = 0.57;
+/// Screenshots are downscaled and re-encoded as JPEG to keep the PDF small
+const PDF_IMAGE_MAX_WIDTH: u32 = 1200;
+const PDF_IMAGE_QUALITY: u8 = 75;
+
+struct PdfImage {
+    width: u32,
+    height: u32,
+    jpeg: Vec<u8>,
+}
+
+impl PdfImage {
+    fn from_screenshot(attachments_dir: &Path, attachment_id: &str) -> Option<Self> {
+        let (bytes, _) = read_screenshot(attachments_dir, attachment_id)?;
+        let mut image = image::load_from_memory(&bytes).ok()?;
+        if image.width() > PDF_IMAGE_MAX_WIDTH {
+            image = image.resize(PDF_IMAGE_MAX_WIDTH, u32::MAX, image::imageops::FilterType::Triangle);
+        }
+        let rgb = image.to_rgb8();
+        let mut jpeg = Vec::new();
+        image::codecs::jpeg::JpegEncoder::new_with_quality(&mut jpeg, PDF_IMAGE_QUALITY)
+            .encode_image(&rgb)
+            .ok()?;
+        Some(Self { width: rgb.width(), height: rgb.height(), jpeg })
+    }
+}
+
+/// Minimal PDF 1.4 writer: flowing text in the standard Helvetica fonts
+/// (WinAnsi, so characters outside Latin-1 print as '?') and JPEG images
+struct PdfDocument {
+    width: f32,
+    height: f32,
+    pages: Vec<Vec<u8>>,
+    images: Vec<PdfImage>,
+    y: f32,
+}
+
+impl PdfDocument {
+    fn new(page_size: PageSize) -> Self {
+        let (width, height) = page_size.dimensions();
+        Self {
+            width,
+            height,
+            pages: vec![Vec::new()],
+            images: Vec::new(),
+            y: height - PDF_MARGIN,
+        }
+    }
+
+    fn content_width(&self) -> f32 {
+        self.width - 2.0 * PDF_MARGIN
+    }
+
+    fn page(&mut self) -> &mut Vec<u8> {
+        self.pages.last_mut().expect("document always has a page")
+    }
+
+    fn new_page(&mut self) {
+        self.pages.push(Vec::new());
+        self.y = self.height - PDF_MARGIN;
+    }
+
+    fn ensure_space(&mut self, needed: f32) {
+        if self.y - needed < PDF_MARGIN {
+            self.new_page();
+        }
+    }
+
+    fn space(&mut self, height: f32) {
+        self.y -= height;
+    }
+
+    /// Wrapped text; `gray` is the fill level (0 = black)
+    fn text(&mut self, text: &str, size: f32, bold: bool, gray: f32, indent: f32) {
+        let char_width = size * if bold { PDF_BOLD_CHAR_WIDTH } else { PDF_CHAR_WIDTH };
+        let max_chars = (((self.content_width() - indent) / char_width) as usize).max(1);
+        let line_height = size * PDF_LINE_HEIGHT;
+
+        for paragraph in text.lines() {
+            if paragraph.trim().is_empty() {
+                self.space(line_height * 0.5);
+                continue;
+            }
+            for line in wrap_text(paragraph, max_chars) {
+                self.ensure_space(line_height);
+                self.y -= line_height;
+                let (x, y) = (PDF_MARGIN + indent, self.y + (line_height - size) / 2.0);
+                let font = if bold { "F2" } else { "F1" };
+                let mut command = format!("{} g BT /{} {} Tf {:.2} {:.2} Td (", gray, font, size, x, y).into_bytes();
+                command.extend(encode_pdf_text(&line));
+                command.extend_from_slice(b") Tj ET\n");
+                self.page().extend(command);
+            }
+        }
+    }
+
+    fn heading(&mut self, text: &str) {
+        // Keep headings with at least a few lines of their section
+        self.ensure_space(80.0);
+        self.space(14.0);
+        self.text(text, 14.0, true, 0.0, 0.0);
+        self.space(4.0);
+    }
+
+    fn bullet(&mut self, text: &str) {
+        // Keep the marker on the same page as the first line
+        self.ensure_space(10.5 * PDF_LINE_HEIGHT);
+        let y = self.y - 10.5 * PDF_LINE_HEIGHT + 4.0;
+        let command = format!("0 g {:.2} {:.2} 3 3 re f\n", PDF_MARGIN + 3.0, y);
+        self.page().extend(command.into_bytes());
+        self.text(text, 10.5, false, 0.0, 12.0);
+    }
+
+    /// Image scaled to the content width (and at most 45% of the page height)
+    fn image(&mut self, image: PdfImage) {
+        let scale = (self.content_width() / image.width as f32).min(self.height * 0.45 / image.height as f32);
+        let (width, height) = (image.width as f32 * scale, image.height as f32 * scale);
+        self.ensure_space(height);
+        self.y -= height;
+        let command = format!("q {:.2} 0 0 {:.2} {:.2} {:.2} cm /Im{} Do Q\n", width, height, PDF_MARGIN, self.y, self.images.len());
+        self.page().extend(command.into_bytes());
+        self.images.push(image);
+    }
+
+    fn finish(self, title: &str) -> Vec<u8> {
+        let mut out = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+        let mut offsets: Vec<usize> = Vec::new();
+        let mut add_object = |out: &mut Vec<u8>, body: &[u8]| {
+            offsets.push(out.len());
+            out.extend(format!("{} 0 obj\n", offsets.len()).into_bytes());
+            out.extend_from_slice(body);
+            out.extend_from_slice(b"\nendobj\n");
+        };
+
+        // 1 catalog, 2 page tree, 3-4 fonts, 5 info, then images, then (page, content) pairs
+        let first_image = 6;
+        let first_page = first_image + self.images.len();
+        let page_ids: Vec<usize> = (0..self.pages.len()).map(|i| first_page + 2 * i).collect();
+
+        add_object(&mut out, b"<< /Type /Catalog /Pages 2 0 R >>");
+        let kids: Vec<String> = page_ids.iter().map(|id| format!("{} 0 R", id)).collect();
+        add_object(&mut out, format!("<< /Type /Pages /Kids [{}] /Count {} >>", kids.join(" "), page_ids.len()).as_bytes());
+        add_object(&mut out, b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>");
+        add_object(&mut out, b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>");
+        let mut info = b"<< /Producer (Taskerino) /Title (".to_vec();
+        info.extend(encode_pdf_text(title));
+        info.extend_from_slice(b") >>");
+        add_object(&mut out, &info);
+
+        for image in &self.images {
+            let mut body = format!(
+                "<< /Type /XObject /Subtype /Image /Width {} /Height {} /ColorSpace /DeviceRGB /BitsPerComponent 8 /Filter /DCTDecode /Length {} >>\nstream\n",
+                image.width,
+                image.height,
+                image.jpeg.len()
+            )
+            .into_bytes();
+            body.extend_from_slice(&image.jpeg);
+            body.extend_from_slice(b"\nendstream");
+            add_object(&mut out, &body);
+        }
+
+        let xobjects: String = (0..self.images.len()).map(|i| format!("/Im{} {} 0 R ", i, first_image + i)).collect();
+        for (page, id) in self.pages.iter().zip(&page_ids) {
+            add_object(
+                &mut out,
+                format!(
+                    "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {:.2} {:.2}] /Resources << /Font << /F1 3 0 R /F2 4 0 R >> /XObject << {}>> >> /Contents {} 0 R >>",
+                    self.width,
+                    self.height,
+                    xobjects,
+                    id + 1
+                )
+                .as_bytes(),
+            );
+            let mut body = format!("<< /Length {} >>\nstream\n", page.len()).into_bytes();
+            body.extend_from_slice(page);
+            body.extend_from_slice(b"\nendstream");
+            add_object(&mut out, &body);
+        }
+
+        let xref_offset = out.len();
+        out.extend(format!("xref\n0 {}\n0000000000 65535 f \n", offsets.len() + 1).into_bytes());
+        for offset in &offsets {
+            out.extend(format!("{:010} 00000 n \n", offset).into_bytes());
+        }
+        out.extend(
+            format!(
+                "trailer\n<< /Size {} /Root 1 0 R /Info 5 0 R >>\nstartxref\n{}\n%%EOF\n",
+                offsets.len() + 1,
+                xref_offset
+            )
+            .into_bytes(),
+        );
+        out
+    }
+}
+
+/// Greedy word wrap by character count; overlong words are split
+fn wrap_text(text: &str, max_chars: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        let mut word: Vec<char> = word.chars().collect();
+        while word.len() > max_chars {
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            lines.push(word.drain(..max_chars).collect());
+        }
+        let word: String = word.into_iter().collect();
+        if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > max_chars {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(&word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// Text as an escaped WinAnsi literal string body
+fn encode_pdf_text(text: &str) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(text.len());
+    for c in text.chars() {
+        let byte = match c {
+            '(' | ')' | '\\' => {
+                bytes.push(b'\\');
+                c as u8
+            }
+            ' '..='~' => c as u8,
+            '\t' => b' ',
+            '\u{a0}'..='\u{ff}' => c as u32 as u8,
+            '€' => 0x80,
+            '…' => 0x85,
+            '‘' => 0x91,
+            '’' => 0x92,
+            '“' => 0x93,
+            '”' => 0x94,
+            '•' => 0x95,
+            '–' => 0x96,
+            '—' => 0x97,
+            _ => b'?',
+        };
+        bytes.push(byte);
+    }
+    bytes
+}
+
+/// Flagged or commented screenshots first, topped up with evenly spaced ones
+fn key_screenshots(screenshots: &[ExportScreenshot], max: usize) -> Vec<&ExportScreenshot> {
+    let (mut key, rest): (Vec<&ExportScreenshot>, Vec<&ExportScreenshot>) = screenshots
+        .iter()
+        .partition(|s| s.flagged || s.user_comment.as_deref().is_some_and(|c| !c.is_empty()));
+    key.truncate(max);
+    let remaining = (max - key.len()).min(rest.len());
+    if remaining > 0 {
+        let step = rest.len() as f64 / remaining as f64;
+        key.extend((0..remaining).map(|i| rest[(i as f64 * step) as usize]));
+    }
+    key.sort_by_key(|s| parse_time(&s.timestamp).map(|t| t.timestamp_millis()).unwrap_or(0));
+    key
+}
+
+fn render_pdf(session: &ExportSession, attachments_dir: &Path, options: &PdfExportOptions) -> Vec<u8> {
+    let mut pdf = PdfDocument::new(options.page_size);
+    pdf.text(session.title(), 22.0, true, 0.0, 0.0);
+    pdf.text(&session.meta_line(), 10.0, false, 0.45, 0.0);
+    if !session.tags.is_empty() {
+        pdf.text(&session.tags.iter().map(|t| format!("#{}", t)).collect::<Vec<_>>().join("  "), 10.0, false, 0.45, 0.0);
+    }
+    if !session.description.trim().is_empty() {
+        pdf.space(6.0);
+        pdf.text(&session.description, 10.5, false, 0.0, 0.0);
+    }
+
+    if let Some(summary) = &session.summary {
+        pdf.heading("Summary");
+        if !summary.narrative.trim().is_empty() {
+            pdf.text(&summary.narrative, 10.5, false, 0.0, 0.0);
+        }
+        for (title, items) in [("Achievements", &summary.achievements), ("Blockers", &summary.blockers)] {
+            if !items.is_empty() {
+                pdf.space(6.0);
+                pdf.text(title, 11.5, true, 0.0, 0.0);
+                for item in items {
+                    pdf.bullet(item);
+                }
+            }
+        }
+        if !summary.recommended_tasks.is_empty() {
+            pdf.space(6.0);
+            pdf.text("Recommended tasks", 11.5, true, 0.0, 0.0);
+            for task in &summary.recommended_tasks {
+                pdf.bullet(&format!("{} ({}): {}", task.title, task.priority, task.context));
+            }
+        }
+        if !summary.key_insights.is_empty() {
+            pdf.space(6.0);
+            pdf.text("Key insights", 11.5, true, 0.0, 0.0);
+            for insight in &summary.key_insights {
+                pdf.bullet(&format!("{}  {}", format_time(&insight.timestamp, "%H:%M"), insight.insight));
+            }
+        }
+    }
+
+    let screenshots = key_screenshots(&session.screenshots, options.max_screenshots);
+    if !screenshots.is_empty() {
+        pdf.heading("Key screenshots");
+        for screenshot in screenshots {
+            let Some(image) = PdfImage::from_screenshot(attachments_dir, &screenshot.attachment_id) else {
+                continue;
+            };
+            pdf.ensure_space(120.0);
+            let mut caption = format_time(&screenshot.timestamp, "%H:%M:%S");
+            if let Some(analysis) = &screenshot.ai_analysis {
+                caption = format!("{}  {}", caption, analysis.summary);
+            }
+            pdf.text(&caption, 10.0, screenshot.flagged, 0.25, 0.0);
+            if let Some(comment) = screenshot.user_comment.as_deref().filter(|c| !c.is_empty()) {
+                pdf.text(&format!("“{}”", comment), 10.0, false, 0.45, 0.0);
+            }
+            pdf.space(4.0);
+            pdf.image(image);
+            pdf.space(14.0);
+        }
+    }
+
+    if options.include_transcript {
+        let segments: Vec<&ExportAudioSegment> = session
+            .audio_segments
+            .iter()
+            .filter(|segment| !segment.transcription.trim().is_empty())
+            .collect();
+        let transcript = session.transcript();
+        if !segments.is_empty() {
+            pdf.heading("Transcript");
+            for segment in segments {
+                let mut label = format_time(&segment.timestamp, "%H:%M:%S");
+                if let Some(speaker) = &segment.speaker {
+                    label = format!("{}  {}", label, speaker);
+                }
+                pdf.ensure_space(30.0);
+                pdf.text(&label, 9.5, true, 0.45, 0.0);
+                pdf.text(segment.transcription.trim(), 10.5, false, 0.0, 0.0);
+                pdf.space(6.0);
+            }
+        } else if !transcript.is_empty() {
+            pdf.heading("Transcript");
+            pdf.text(&transcript, 10.5, false, 0.0, 0.0);
+        }
+    }
+
+    pdf.finish(session.title())
+}
+
+fn write_export(path: &Path, content: &[u8]) -> Result<ExportResult, String> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create export directory: {}", e))?;
+    }
+    std::fs::write(path, content).map_err(|e| format!("Failed to write export: {}", e))?;
+    Ok(ExportResult {
+        path: path.to_string_lossy().to_string(),
+        bytes: content.len() as u64,
+    })
+}
+
+/// Tauri command to export a session as a self-contained HTML report
+#[tauri::command]
+pub async fn export_session_html(app: AppHandle, session_id: String, path: String) -> Result<ExportResult, String> {
+    command_metrics::track_async("export_session_html", async move {
+        let data_dir = profiles::profile_data_dir(&app)?;
+        let path = PathBuf::from(path);
+
+        tokio::task::spawn_blocking(move || {
+            let session = ExportSession::load(&data_dir, &session_id)?;
+            let html = render_html(&session, &data_dir.join("attachments"));
+            let result = write_export(&path, html.as_bytes())?;
+            println!("📤 [EXPORT] Session {} exported to {} ({} bytes)", session_id, result.path, result.bytes);
+            Ok(result)
+        })
+        .await
+        .map_err(|e| format!("Export task failed: {}", e))?
+    }).await
+}
+
+/// Tauri command to export transcript captions for the session video
+#[tauri::command]
+pub async fn export_session_subtitles(
+    app: AppHandle,
+    session_id: String,
+    format: SubtitleFormat,
+    path: String,
+) -> Result<ExportResult, String> {
+    command_metrics::track_async("export_session_subtitles", async move {
+        let data_dir = profiles::profile_data_dir(&app)?;
+        let path = PathBuf::from(path);
+
+        tokio::task::spawn_blocking(move || {
+            let session = ExportSession::load(&data_dir, &session_id)?;
+            let subtitles = render_subtitles(&subtitle_cues(&session)?, format);
+            let result = write_export(&path, subtitles.as_bytes())?;
+            println!("📤 [EXPORT] Session {} exported to {} ({} bytes)", session_id, result.path, result.bytes);
+            Ok(result)
+        })
+        .await
+        .map_err(|e| format!("Export task failed: {}", e))?
+    }).await
+}
+
+/// Tauri command to export a session as a printable PDF
+#[tauri::command]
+pub async fn export_session_pdf(
+    app: AppHandle,
+    session_id: String,
+    path: String,
+    options: Option<PdfExportOptions>,
+) -> Result<ExportResult, String> {
+    command_metrics::track_async("export_session_pdf", async move {
+        let data_dir = profiles::profile_data_dir(&app)?;
+        let path = PathBuf::from(path);
+        let options = options.unwrap_or_default();
+
+        tokio::task::spawn_blocking(move || {
+            let session = ExportSession::load(&data_dir, &session_id)?;
+            let pdf = render_pdf(&session, &data_dir.join("attachments"), &options);
+            let result = write_export(&path, &pdf)?;
+            println!("📤 [EXPORT] Session {} exported to {} ({} bytes)", session_id, result.path, result.bytes);
+            Ok(result)
+        })
+        .await
+        .map_err(|e| format!("Export task failed: {}", e))?
+    }).await
+}
+
+/// Tauri command to export a session as a Markdown note into an Obsidian/Logseq vault
+/// (`vault_path` defaults to settings.export.vaultPath); re-exporting overwrites the note
+#[tauri::command]
+pub async fn export_session_markdown(
+    app: AppHandle,
+    settings: tauri::State<'_, Arc<SettingsManager>>,
+    session_id: String,
+    vault_path: Option<String>,
+) -> Result<ExportResult, String> {
+    command_metrics::track_async("export_session_markdown", async move {
+        let data_dir = profiles::profile_data_dir(&app)?;
+        let vault = vault_path
+            .filter(|path| !path.trim().is_empty())
+            .unwrap_or_else(|| settings.get().export.vault_path);
+        if vault.trim().is_empty() {
+            return Err("No vault folder selected".to_string());
+        }
+
+        tokio::task::spawn_blocking(move || export_markdown(&data_dir, &session_id, Path::new(&vault)))
+            .await
+            .map_err(|e| format!("Export task failed: {}", e))?
+    }).await
+}
+
+/// Tauri command run when a session is complete: exports it to the vault if
+/// settings.export.autoExportMarkdown is on (returns None otherwise)
+#[tauri::command]
+pub async fn auto_export_session(
+    app: AppHandle,
+    settings: tauri::State<'_, Arc<SettingsManager>>,
+    session_id: String,
+) -> Result<Option<ExportResult>, String> {
+    command_metrics::track_async("auto_export_session", async move {
+        let export = settings.get().export;
+        if !export.auto_export_markdown || export.vault_path.trim().is_empty() {
+            return Ok(None);
+        }
+        let data_dir = profiles::profile_data_dir(&app)?;
+
+        tokio::task::spawn_blocking(move || export_markdown(&data_dir, &session_id, Path::new(&export.vault_path)))
+            .await
+            .map_err(|e| format!("Export task failed: {}", e))?
+            .map(Some)
+    }).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+        haystack.windows(needle.len()).position(|window| window == needle)
+    }
+
+    fn rfind(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+        haystack.windows(needle.len()).rposition(|window| window == needle)
+    }
+
+    /// Multi-page document with one image
+    fn sample_pdf() -> Vec<u8> {
+        let mut pdf = PdfDocument::new(PageSize::A4);
+        pdf.heading("Résumé (draft)");
+        for i in 0..120 {
+            pdf.bullet(&format!("Line {} with some text that wraps around the page width at least once or twice", i));
+        }
+        let mut jpeg = Vec::new();
+        image::codecs::jpeg::JpegEncoder::new(&mut jpeg)
+            .encode_image(&image::RgbImage::new(4, 4))
+            .unwrap();
+        pdf.image(PdfImage { width: 4, height: 4, jpeg });
+        assert!(pdf.pages.len() > 1);
+        pdf.finish("Title")
+    }
+
+    /// Value after `key` in a dictionary, e.g. `/Size 12`
+    fn dictionary_number(text: &str, key: &str) -> usize {
+        let start = text.find(key).unwrap_or_else(|| panic!("missing {}", key)) + key.len();
+        text[start..].split_whitespace().next().unwrap().parse().unwrap()
+    }
+
+    #[test]
+    fn xref_offsets_point_at_their_objects() {
+        let pdf = sample_pdf();
+        assert!(pdf.starts_with(b"%PDF-1.4\n"));
+
+        let startxref = rfind(&pdf, b"startxref\n").unwrap();
+        let tail = String::from_utf8_lossy(&pdf[startxref..]).to_string();
+        let xref_offset: usize = tail.lines().nth(1).unwrap().parse().unwrap();
+        assert!(pdf[xref_offset..].starts_with(b"xref\n"));
+
+        let xref = String::from_utf8_lossy(&pdf[xref_offset..startxref]).to_string();
+        let mut lines = xref.lines();
+        assert_eq!(lines.next(), Some("xref"));
+        let count: usize = lines.next().unwrap().strip_prefix("0 ").unwrap().parse().unwrap();
+        assert_eq!(lines.next(), Some("0000000000 65535 f "));
+        for id in 1..count {
+            let entry = lines.next().unwrap();
+            // Fixed 20-byte entries: 10-digit offset, generation, type, EOL
+            assert_eq!(entry.len() + 1, 20, "{:?}", entry);
+            assert!(entry.ends_with(" 00000 n "));
+            let offset: usize = entry[..10].parse().unwrap();
+            let header = format!("{} 0 obj\n", id);
+            assert!(pdf[offset..].starts_with(header.as_bytes()), "object {} not at {}", id, offset);
+        }
+
+        let trailer = String::from_utf8_lossy(&pdf[find(&pdf, b"trailer\n").unwrap()..]).to_string();
+        assert_eq!(dictionary_number(&trailer, "/Size"), count);
+        assert!(trailer.contains("/Root 1 0 R"));
+        assert!(trailer.contains("/Info 5 0 R"));
+        assert!(pdf.ends_with(b"%%EOF\n"));
+    }
+
+    #[test]
+    fn stream_lengths_match_their_data() {
+        let pdf = sample_pdf();
+        let mut at = 0;
+        let mut streams = 0;
+        while let Some(start) = find(&pdf[at..], b"stream\n").map(|i| at + i) {
+            if pdf[..start].ends_with(b"end") {
+                at = start + 7;
+                continue;
+            }
+            let dictionary = String::from_utf8_lossy(&pdf[rfind(&pdf[..start], b"<<").unwrap()..start]).to_string();
+            let length = dictionary_number(&dictionary, "/Length");
+            let data_start = start + b"stream\n".len();
+            assert!(pdf[data_start + length..].starts_with(b"\nendstream"), "stream at {}", start);
+            at = data_start + length;
+            streams += 1;
+        }
+        assert!(streams > 2);
+    }
+
+    #[test]
+    fn text_is_escaped_as_win_ansi() {
+        assert_eq!(encode_pdf_text(r"a (b) c\d"), br"a \(b\) c\\d".to_vec());
+        assert_eq!(encode_pdf_text("café €5 – “ok”…"), b"caf\xe9 \x805 \x96 \x93ok\x94\x85".to_vec());
+        assert_eq!(encode_pdf_text("日本 😀\tx"), b"?? ? x".to_vec());
+    }
+
+    #[test]
+    fn rendered_pdf_has_no_raw_utf8_text() {
+        let session = ExportSession {
+            id: "s".to_string(),
+            name: "Naïve (test) 日本".to_string(),
+            description: "Ends with a backslash \\".to_string(),
+            start_time: "2024-01-01T10:00:00Z".to_string(),
+            ..ExportSession::default()
+        };
+        let pdf = render_pdf(&session, Path::new("/nonexistent"), &PdfExportOptions::default());
+        assert!(find(&pdf, b"(Na\xefve \\(test\\) ??) Tj").is_some());
+        assert!(find(&pdf, b"backslash \\\\) Tj").is_some());
+        assert!(find(&pdf, "ï".as_bytes()).is_none());
+        assert!(find(&pdf, "日".as_bytes()).is_none());
+    }
+}
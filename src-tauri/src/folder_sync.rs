@@ -0,0 +1,463 @@
+/**
+ * Folder Sync Module
+ *
+ * Mirrors the active profile's session library to a shared folder (an iCloud
+ * Drive or Dropbox folder) so several Macs can work on the same sessions:
+ * - Each session is one file, `<folder>/Taskerino/<profile>/sessions/<id>.json`,
+ *   carrying a revision counter that every push increments; a deleted session
+ *   leaves a tombstone so the deletion reaches the other Macs
+ * - Attachment metadata and data (`attachments/<id>.meta.json` / `.dat`) are
+ *   copied in whichever direction they are missing. Attachments whose data is
+ *   a file elsewhere on disk (video) only sync their metadata.
+ * - Per profile, `sync-state.json` remembers the revision and content hash of
+ *   each session as last synced. A session changed only locally is pushed,
+ *   one changed only remotely is pulled; one changed on both sides is a
+ *   conflict and is left alone until `resolve_sync_conflict` picks a side
+ * - The "folder-sync" task syncs every SYNC_INTERVAL while settings.sync is
+ *   enabled and the folder exists; `sync_sessions_now` syncs right away
+ * - Pulled sessions are written to sessions.json and emitted as
+ *   `sessions-synced`; new conflicts are emitted as `sync-conflict`
+ *
+ * Files are written via temp file + rename so the cloud client never uploads
+ * a half-written one.
+ */
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::background_tasks::TaskRegistry;
+use crate::command_metrics;
+use crate::profiles;
+use crate::session_archive::{collect_attachment_ids, is_safe_id, new_id};
+use crate::session_storage;
+use crate::settings::SettingsManager;
+
+const TASK_NAME: &str = "folder-sync";
+const SYNC_INTERVAL: Duration = Duration::from_secs(60);
+
+const SYNC_FORMAT: &str = "taskerino-synced-session";
+const SYNC_ROOT: &str = "Taskerino";
+const STATE_FILE: &str = "sync-state.json";
+
+/// One sync at a time (scheduled, manual and conflict resolution)
+static SYNC_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// A session file in the sync folder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SyncedSession {
+    format: String,
+    revision: u64,
+    /// Device that wrote this revision
+    device: String,
+    updated_at: String,
+    /// Tombstone: the session was deleted on `device`
+    #[serde(default)]
+    deleted: bool,
+    session: Option<serde_json::Value>,
+}
+
+/// A session as last synced
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SyncedRevision {
+    revision: u64,
+    /// Content hash; empty for a deleted session
+    hash: String,
+}
+
+/// A session changed both here and on another Mac since it was last synced
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncConflict {
+    pub session_id: String,
+    pub name: String,
+    pub local_revision: u64,
+    pub remote_revision: u64,
+    pub remote_device: String,
+    /// The other Mac deleted the session
+    pub remote_deleted: bool,
+    /// This Mac deleted the session
+    pub local_deleted: bool,
+    pub detected_at: String,
+}
+
+/// Per-profile sync bookkeeping (`sync-state.json`)
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct SyncState {
+    device_id: String,
+    /// Folder the revisions below refer to; changing it starts over
+    directory: String,
+    sessions: BTreeMap<String, SyncedRevision>,
+    conflicts: BTreeMap<String, SyncConflict>,
+}
+
+/// What one sync pass did
+#[derive(Debug, Default, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncReport {
+    pub pushed: usize,
+    pub pulled: usize,
+    pub deleted: usize,
+    /// Every unresolved conflict (not just new ones)
+    pub conflicts: Vec<SyncConflict>,
+}
+
+/// `sessions-synced` payload: sessions written to sessions.json by a pull
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionsSynced {
+    pub updated: Vec<serde_json::Value>,
+    pub deleted: Vec<String>,
+}
+
+/// Where the active profile syncs to inside the shared folder
+fn sync_dir(directory: &Path, profile_name: &str) -> PathBuf {
+    let profile: String = profile_name
+        .chars()
+        .map(|c| if c.is_alphanumeric() || matches!(c, '-' | '_' | ' ') { c } else { '_' })
+        .collect();
+    directory.join(SYNC_ROOT).join(profile)
+}
+
+fn content_hash(session: &serde_json::Value) -> String {
+    let bytes = serde_json::to_vec(session).unwrap_or_default();
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+fn session_name(session: &serde_json::Value) -> String {
+    session.get("name").and_then(|name| name.as_str()).unwrap_or("Untitled session").to_string()
+}
+
+fn load_state(data_dir: &Path) -> SyncState {
+    std::fs::read_to_string(data_dir.join(STATE_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Write a file via a temp file + rename
+fn write_atomic(path: &Path, content: &[u8]) -> Result<(), String> {
+    let mut temp_name = std::ffi::OsString::from(".");
+    temp_name.push(path.file_name().unwrap_or_default());
+    temp_name.push(".tmp");
+    let temp_path = path.with_file_name(temp_name);
+    std::fs::write(&temp_path, content).map_err(|e| format!("Failed to write {:?}: {}", temp_path, e))?;
+    std::fs::rename(&temp_path, path).map_err(|e| {
+        let _ = std::fs::remove_file(&temp_path);
+        format!("Failed to replace {:?}: {}", path, e)
+    })
+}
+
+fn save_state(data_dir: &Path, state: &SyncState) -> Result<(), String> {
+    let content = serde_json::to_vec_pretty(state).map_err(|e| format!("Failed to serialize sync state: {}", e))?;
+    write_atomic(&data_dir.join(STATE_FILE), &content)
+}
+
+/// Session files in the sync folder by session id, plus the ids of files
+/// that couldn't be read (e.g. still being downloaded by the cloud client)
+fn read_remote(sessions_dir: &Path) -> (BTreeMap<String, SyncedSession>, BTreeSet<String>) {
+    let mut remote = BTreeMap::new();
+    let mut unreadable = BTreeSet::new();
+    let Ok(entries) = std::fs::read_dir(sessions_dir) else {
+        return (remote, unreadable);
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let Some(id) = name.strip_suffix(".json").filter(|id| is_safe_id(id)) else {
+            continue;
+        };
+        let synced = std::fs::read_to_string(entry.path())
+            .map_err(|e| e.to_string())
+            .and_then(|content| serde_json::from_str::<SyncedSession>(&content).map_err(|e| e.to_string()));
+        match synced {
+            Ok(synced) if synced.format == SYNC_FORMAT => {
+                remote.insert(id.to_string(), synced);
+            }
+            Ok(_) => {}
+            Err(e) => {
+                eprintln!("⚠️  [SYNC] Skipping unreadable {}: {}", name, e);
+                unreadable.insert(id.to_string());
+            }
+        }
+    }
+    (remote, unreadable)
+}
+
+/// Copy a session's attachment files from `from` to `to` where missing there
+fn copy_attachments(session: &serde_json::Value, from: &Path, to: &Path) -> Result<(), String> {
+    let mut ids = BTreeSet::new();
+    collect_attachment_ids(session, &mut ids);
+    for id in ids.iter().filter(|id| is_safe_id(id)) {
+        for file in [format!("{}.meta.json", id), format!("{}.dat", id)] {
+            let (source, target) = (from.join(&file), to.join(&file));
+            if target.exists() || !source.exists() {
+                continue;
+            }
+            std::fs::create_dir_all(to).map_err(|e| format!("Failed to create {:?}: {}", to, e))?;
+            let content = std::fs::read(&source).map_err(|e| format!("Failed to read {:?}: {}", source, e))?;
+            write_atomic(&target, &content)?;
+        }
+    }
+    Ok(())
+}
+
+/// One sync pass between a profile's data dir and its folder (blocking)
+fn sync(data_dir: &Path, sync_dir: &Path, directory: &str, encrypt_new_file: bool) -> Result<(SyncReport, SessionsSynced, Vec<SyncConflict>), String> {
+    let sessions_dir = sync_dir.join("sessions");
+    let remote_attachments = sync_dir.join("attachments");
+    let local_attachments = data_dir.join("attachments");
+    std::fs::create_dir_all(&sessions_dir).map_err(|e| format!("Failed to create sync folder: {}", e))?;
+
+    let mut state = load_state(data_dir);
+    if state.device_id.is_empty() {
+        state.device_id = new_id();
+    }
+    if state.directory != directory {
+        // A different folder: compare everything from scratch
+        state.directory = directory.to_string();
+        state.sessions.clear();
+        state.conflicts.clear();
+    }
+
+    let local: BTreeMap<String, serde_json::Value> = session_storage::read_session_values(data_dir)?
+        .into_iter()
+        .filter_map(|session| Some((session.get("id")?.as_str()?.to_string(), session)))
+        .filter(|(id, _)| is_safe_id(id))
+        .collect();
+    if local.is_empty() && state.sessions.values().any(|synced| !synced.hash.is_empty()) {
+        // Never turn a missing or wiped library into deletions on every Mac
+        return Err("Local session library is empty; not syncing until it has sessions again".to_string());
+    }
+    let (remote, unreadable) = read_remote(&sessions_dir);
+    let ids: BTreeSet<String> = local.keys().chain(remote.keys()).chain(state.sessions.keys()).cloned().collect();
+
+    let mut report = SyncReport::default();
+    let mut pulled = SessionsSynced { updated: Vec::new(), deleted: Vec::new() };
+    let mut new_conflicts = Vec::new();
+    let now = chrono::Utc::now().to_rfc3339();
+
+    for id in ids {
+        if state.conflicts.contains_key(&id) || unreadable.contains(&id) {
+            continue;
+        }
+        let local_session = local.get(&id);
+        let remote_session = remote.get(&id);
+        let synced = state.sessions.get(&id);
+
+        let local_hash = local_session.map(content_hash).unwrap_or_default();
+        let remote_hash = remote_session
+            .and_then(|remote| remote.session.as_ref().filter(|_| !remote.deleted))
+            .map(content_hash)
+            .unwrap_or_default();
+        let remote_revision = remote_session.map_or(0, |remote| remote.revision);
+
+        if local_hash == remote_hash {
+            if local_session.is_none() && remote_session.is_none() {
+                state.sessions.remove(&id);
+            } else {
+                let revision = remote_revision.max(synced.map_or(0, |synced| synced.revision));
+                state.sessions.insert(id, SyncedRevision { revision, hash: local_hash });
+            }
+            continue;
+        }
+
+        let local_changed = match synced {
+            Some(synced) => local_hash != synced.hash,
+            None => local_session.is_some(),
+        };
+        let remote_changed = match (remote_session, synced) {
+            (Some(remote), Some(synced)) => remote.revision > synced.revision,
+            (Some(remote), None) => !remote.deleted,
+            (None, _) => false,
+        };
+
+        if local_changed && remote_changed {
+            let conflict = SyncConflict {
+                session_id: id.clone(),
+                name: local_session
+                    .or(remote_session.and_then(|remote| remote.session.as_ref()))
+                    .map(session_name)
+                    .unwrap_or_default(),
+                local_revision: synced.map_or(0, |synced| synced.revision),
+                remote_revision,
+                remote_device: remote_session.map(|remote| remote.device.clone()).unwrap_or_default(),
+                remote_deleted: remote_hash.is_empty(),
+                local_deleted: local_session.is_none(),
+                detected_at: now.clone(),
+            };
+            new_conflicts.push(conflict.clone());
+            state.conflicts.insert(id, conflict);
+        } else if remote_changed {
+            let Some(remote) = remote_session else { continue };
+            match remote.session.as_ref().filter(|_| !remote.deleted) {
+                Some(session) => {
+                    copy_attachments(session, &remote_attachments, &local_attachments)?;
+                    pulled.updated.push(session.clone());
+                    report.pulled += 1;
+                }
+                None => {
+                    pulled.deleted.push(id.clone());
+                    report.deleted += 1;
+                }
+            }
+            state.sessions.insert(id, SyncedRevision { revision: remote.revision, hash: remote_hash });
+        } else {
+            // Changed here, or missing from the folder
+            if let Some(session) = local_session {
+                copy_attachments(session, &local_attachments, &remote_attachments)?;
+            }
+            let revision = remote_revision.max(synced.map_or(0, |synced| synced.revision)) + 1;
+            let file = SyncedSession {
+                format: SYNC_FORMAT.to_string(),
+                revision,
+                device: state.device_id.clone(),
+                updated_at: now.clone(),
+                deleted: local_session.is_none(),
+                session: local_session.cloned(),
+            };
+            let content = serde_json::to_vec(&file).map_err(|e| format!("Failed to serialize session {}: {}", id, e))?;
+            write_atomic(&sessions_dir.join(format!("{}.json", id)), &content)?;
+            report.pushed += 1;
+            state.sessions.insert(id, SyncedRevision { revision, hash: local_hash });
+        }
+    }
+
+    if !pulled.updated.is_empty() || !pulled.deleted.is_empty() {
+        session_storage::update_session_values(data_dir, encrypt_new_file, |sessions| {
+            sessions.retain(|session| {
+                let id = session.get("id").and_then(|id| id.as_str());
+                !id.is_some_and(|id| pulled.deleted.iter().any(|deleted| deleted == id))
+            });
+            for updated in &pulled.updated {
+                let id = updated.get("id").and_then(|id| id.as_str());
+                match sessions.iter_mut().find(|session| session.get("id").and_then(|id| id.as_str()) == id) {
+                    Some(session) => *session = updated.clone(),
+                    None => sessions.push(updated.clone()),
+                }
+            }
+            Ok(())
+        })?;
+    }
+    save_state(data_dir, &state)?;
+
+    report.conflicts = state.conflicts.into_values().collect();
+    Ok((report, pulled, new_conflicts))
+}
+
+/// Sync the active profile with the configured folder and emit what changed
+fn run_sync(app: &AppHandle) -> Result<SyncReport, String> {
+    let settings = app.state::<Arc<SettingsManager>>().get();
+    let directory = settings.sync.directory.trim().to_string();
+    if directory.is_empty() {
+        return Err("Choose a sync folder first".to_string());
+    }
+    if !Path::new(&directory).is_dir() {
+        return Err(format!("Sync folder {} is not available", directory));
+    }
+
+    let _guard = SYNC_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let data_dir = profiles::profile_data_dir(app)?;
+    let sync_dir = sync_dir(Path::new(&directory), &profiles::active_profile(app).name);
+    let (report, pulled, new_conflicts) = sync(&data_dir, &sync_dir, &directory, settings.storage.encrypt_sessions)?;
+
+    if report.pushed > 0 || report.pulled > 0 || report.deleted > 0 {
+        println!(
+            "🔄 [SYNC] Pushed {}, pulled {}, deleted {} session(s)",
+            report.pushed, report.pulled, report.deleted
+        );
+    }
+    if !pulled.updated.is_empty() || !pulled.deleted.is_empty() {
+        let _ = app.emit("sessions-synced", &pulled);
+    }
+    for conflict in &new_conflicts {
+        eprintln!("⚠️  [SYNC] Conflict in session {} ({})", conflict.session_id, conflict.name);
+        let _ = app.emit("sync-conflict", conflict);
+    }
+    Ok(report)
+}
+
+/// Start syncing with the configured folder in the background
+pub fn start(app: AppHandle, registry: &TaskRegistry) -> Result<(), String> {
+    registry.spawn(TASK_NAME, |mut shutdown| async move {
+        let mut interval = tokio::time::interval(SYNC_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+
+            let settings = app.state::<Arc<SettingsManager>>().get().sync;
+            // Folder unavailable (e.g. cloud drive not mounted): try again later
+            if !settings.enabled || !Path::new(settings.directory.trim()).is_dir() {
+                continue;
+            }
+            let app = app.clone();
+            match tokio::task::spawn_blocking(move || run_sync(&app)).await {
+                Ok(Ok(_)) => {}
+                Ok(Err(e)) => eprintln!("⚠️  [SYNC] Sync failed: {}", e),
+                Err(e) => eprintln!("⚠️  [SYNC] Sync task failed: {}", e),
+            }
+        }
+    })
+}
+
+/// Tauri command to sync with the configured folder now
+#[tauri::command]
+pub async fn sync_sessions_now(app: AppHandle) -> Result<SyncReport, String> {
+    command_metrics::track_async("sync_sessions_now", async move {
+        tokio::task::spawn_blocking(move || run_sync(&app))
+            .await
+            .map_err(|e| format!("Sync task failed: {}", e))?
+    }).await
+}
+
+/// Tauri command to resolve a conflict by keeping this Mac's version
+/// (`keep_local`) or the folder's, then sync
+#[tauri::command]
+pub async fn resolve_sync_conflict(
+    app: AppHandle,
+    settings: State<'_, Arc<SettingsManager>>,
+    session_id: String,
+    keep_local: bool,
+) -> Result<SyncReport, String> {
+    command_metrics::track_async("resolve_sync_conflict", async move {
+        let directory = settings.get().sync.directory.trim().to_string();
+        tokio::task::spawn_blocking(move || {
+            {
+                let _guard = SYNC_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+                let data_dir = profiles::profile_data_dir(&app)?;
+                let mut state = load_state(&data_dir);
+                let conflict = state
+                    .conflicts
+                    .remove(&session_id)
+                    .ok_or_else(|| format!("No sync conflict for session {}", session_id))?;
+
+                // Rewind the bookkeeping so the next pass sees a one-sided change
+                let local_hash = session_storage::read_session_values(&data_dir)?
+                    .iter()
+                    .find(|session| session.get("id").and_then(|id| id.as_str()) == Some(session_id.as_str()))
+                    .map(content_hash)
+                    .unwrap_or_default();
+                let synced = if keep_local {
+                    // Local looks changed (hash can't match), remote looks seen
+                    SyncedRevision { revision: conflict.remote_revision, hash: "conflict".to_string() }
+                } else {
+                    SyncedRevision { revision: conflict.remote_revision.saturating_sub(1), hash: local_hash }
+                };
+                state.directory = directory;
+                state.sessions.insert(session_id, synced);
+                save_state(&data_dir, &state)?;
+            }
+            run_sync(&app)
+        })
+        .await
+        .map_err(|e| format!("Sync task failed: {}", e))?
+    }).await
+}
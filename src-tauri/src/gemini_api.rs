@@ -0,0 +1,311 @@
+/**
+ * Gemini API Module
+ *
+ * Google Gemini provider with the same surface as claude_api.rs (chat, vision,
+ * streaming), e.g. for screenshot analysis with a Gemini key:
+ * - Requests take Claude-shaped messages; they're converted to Gemini
+ *   `contents` (assistant -> model, image blocks -> inline_data)
+ * - Usage is recorded against the Gemini AI budget
+ * - Streaming emits `gemini-stream-{id}` events: `content_delta` per chunk,
+ *   then `stream_end` with usage (or `error`)
+ */
+
+use crate::ai_budget::{AiProvider, BudgetManager, UsageRecord};
+use crate::ai_client;
+use crate::ai_types::*;
+use crate::api_keys;
+use crate::command_metrics;
+use crate::redaction::{self, eprintln_redacted};
+use futures_util::StreamExt;
+use serde_json::json;
+use std::sync::Arc;
+use tauri::{Emitter, Manager};
+
+pub(crate) const GEMINI_API_BASE: &str = "https://generativelanguage.googleapis.com/v1beta";
+
+fn to_gemini_parts(content: &ClaudeMessageContent) -> Vec<serde_json::Value> {
+    match content {
+        ClaudeMessageContent::Text(text) => vec![json!({ "text": text })],
+        ClaudeMessageContent::Blocks(blocks) => blocks
+            .iter()
+            .map(|block| match block {
+                ClaudeContentBlock::Text { text, .. } => json!({ "text": text }),
+                ClaudeContentBlock::Image { source, .. } => json!({
+                    "inline_data": { "mime_type": source.media_type, "data": source.data }
+                }),
+            })
+            .collect(),
+    }
+}
+
+fn request_body(request: &GeminiChatRequest) -> serde_json::Value {
+    let contents: Vec<serde_json::Value> = request
+        .messages
+        .iter()
+        .map(|message| {
+            let role = if message.role == "assistant" { "model" } else { "user" };
+            json!({ "role": role, "parts": to_gemini_parts(&message.content) })
+        })
+        .collect();
+
+    let mut generation_config = json!({ "maxOutputTokens": request.max_tokens });
+    if let Some(temperature) = request.temperature {
+        generation_config["temperature"] = json!(temperature);
+    }
+
+    let mut request_body = json!({
+        "contents": contents,
+        "generationConfig": generation_config,
+    });
+
+    if let Some(system) = &request.system {
+        request_body["systemInstruction"] = json!({ "parts": [{ "text": system }] });
+    }
+
+    request_body
+}
+
+fn usage_of(response: &serde_json::Value) -> GeminiUsage {
+    let metadata = &response["usageMetadata"];
+    GeminiUsage {
+        input_tokens: metadata["promptTokenCount"].as_u64().unwrap_or(0) as u32,
+        output_tokens: metadata["candidatesTokenCount"].as_u64().unwrap_or(0) as u32,
+    }
+}
+
+/// Text of the first candidate (empty when the chunk carries none)
+fn text_of(response: &serde_json::Value) -> String {
+    response["candidates"][0]["content"]["parts"]
+        .as_array()
+        .map(|parts| parts.iter().filter_map(|part| part["text"].as_str()).collect())
+        .unwrap_or_default()
+}
+
+fn record_usage(app: &tauri::AppHandle, model: &str, usage: &GeminiUsage) {
+    app.state::<Arc<BudgetManager>>().record(app, AiProvider::Gemini, model, &UsageRecord {
+        input_tokens: usage.input_tokens as u64,
+        output_tokens: usage.output_tokens as u64,
+        ..Default::default()
+    });
+}
+
+/// Map non-success statuses that shouldn't be retried to user-facing errors
+fn status_error(status: reqwest::StatusCode, error_text: &str) -> ProviderError {
+    if status.as_u16() == 429 {
+        return ProviderError::RateLimited("Gemini rate limit exceeded. Please try again later.".to_string());
+    }
+    if status.as_u16() == 401 || status.as_u16() == 403 || error_text.contains("API_KEY_INVALID") {
+        return "Invalid Gemini API key. Please check your key in Settings.".into();
+    }
+    format!("Gemini API error ({}): {}", status, error_text).into()
+}
+
+/// Gemini chat completion (non-streaming) with automatic retry for transient errors
+#[tauri::command]
+pub async fn gemini_chat_completion(
+    app: tauri::AppHandle,
+    request: GeminiChatRequest,
+) -> Result<GeminiChatResponse, String> {
+    command_metrics::track_async("gemini_chat_completion", async move {
+        send_chat_completion(app, request)
+            .await
+            .map_err(|e| redaction::redact(&e.to_string()))
+    }).await
+}
+
+/// Non-streaming completion; also used by the AI router
+pub async fn send_chat_completion(
+    app: tauri::AppHandle,
+    request: GeminiChatRequest,
+) -> Result<GeminiChatResponse, ProviderError> {
+    let api_key = api_keys::get_api_key(&app, "gemini_api_key")?
+        .ok_or("Gemini API key not set. Please add your API key in Settings.")?;
+
+    let budget = app.state::<Arc<BudgetManager>>();
+    budget.check(AiProvider::Gemini, request.priority)?;
+
+    let request_body = request_body(&request);
+
+    // Rate limits, 5xx and network errors are retried by the shared client
+    let response = ai_client::send_with_retry(&app, "gemini", |client| {
+        client
+            .post(format!("{}/models/{}:generateContent", GEMINI_API_BASE, request.model))
+            .header("x-goog-api-key", &api_key)
+            .json(&request_body)
+    })
+    .await?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(status_error(status, &error_text));
+    }
+
+    let body: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))?;
+
+    let usage = usage_of(&body);
+    record_usage(&app, &request.model, &usage);
+
+    if let Some(reason) = body["promptFeedback"]["blockReason"].as_str() {
+        return Err(format!("Gemini blocked the prompt ({})", reason).into());
+    }
+
+    let finish_reason = body["candidates"][0]["finishReason"].as_str().map(|s| s.to_string());
+    match finish_reason.as_deref() {
+        Some("MAX_TOKENS") => {
+            eprintln_redacted!("⚠️  WARNING: Gemini response truncated due to max_tokens limit!");
+            return Err(format!(
+                "Response truncated: hit max_tokens limit of {}. Output used {} tokens. Increase token limit or implement chunking.",
+                request.max_tokens,
+                usage.output_tokens
+            ).into());
+        }
+        Some(reason @ ("SAFETY" | "RECITATION" | "PROHIBITED_CONTENT" | "BLOCKLIST")) => {
+            return Err(format!("Gemini blocked the response ({})", reason).into());
+        }
+        _ => {}
+    }
+
+    Ok(GeminiChatResponse {
+        model: request.model,
+        text: text_of(&body),
+        finish_reason,
+        usage,
+    })
+}
+
+/// Gemini chat completion with vision support (for screenshots and image attachments)
+#[tauri::command]
+pub async fn gemini_chat_completion_vision(
+    app: tauri::AppHandle,
+    model: String,
+    max_tokens: u32,
+    messages: Vec<ClaudeMessage>,
+    system: Option<String>,
+    temperature: Option<f32>,
+    priority: Option<RequestPriority>,
+) -> Result<GeminiChatResponse, String> {
+    command_metrics::track_async("gemini_chat_completion_vision", async move {
+        let request = GeminiChatRequest {
+            model,
+            max_tokens,
+            messages,
+            system,
+            temperature,
+            priority: priority.unwrap_or_default(),
+        };
+
+        gemini_chat_completion(app, request).await
+    }).await
+}
+
+/// Gemini streaming chat completion
+/// Events are emitted on `gemini-stream-{stream_id}`
+#[tauri::command]
+pub async fn gemini_chat_completion_stream(
+    app: tauri::AppHandle,
+    stream_id: String,
+    request: GeminiChatRequest,
+) -> Result<(), String> {
+    command_metrics::track_async("gemini_chat_completion_stream", async move {
+        let api_key = api_keys::get_api_key(&app, "gemini_api_key")?
+            .ok_or("Gemini API key not set. Please add your API key in Settings.")?;
+        app.state::<Arc<BudgetManager>>().check(AiProvider::Gemini, request.priority)?;
+
+        // Spawn async task to handle streaming
+        tauri::async_runtime::spawn(async move {
+            let event = format!("gemini-stream-{}", stream_id);
+            if let Err(e) = stream_gemini_response(&app, &event, api_key, request).await {
+                let message = redaction::redact(&e);
+                eprintln_redacted!("Gemini streaming error: {}", message);
+                let _ = app.emit(&event, json!({ "type": "error", "error": { "message": message } }));
+            }
+        });
+
+        Ok(())
+    }).await
+}
+
+/// Internal function to forward Gemini's SSE stream as events
+async fn stream_gemini_response(
+    app: &tauri::AppHandle,
+    event: &str,
+    api_key: String,
+    request: GeminiChatRequest,
+) -> Result<(), String> {
+    let request_body = request_body(&request);
+    let response = ai_client::send_with_retry(app, "gemini", |client| {
+        client
+            .post(format!("{}/models/{}:streamGenerateContent?alt=sse", GEMINI_API_BASE, request.model))
+            .header("x-goog-api-key", &api_key)
+            .json(&request_body)
+    })
+    .await
+    .map_err(|e| format!("Gemini API request failed: {}", e))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(status_error(status, &error_text).to_string());
+    }
+
+    // Process SSE stream
+    let mut stream = response.bytes_stream();
+    let mut buffer = String::new();
+    let mut usage = GeminiUsage::default();
+    let mut finish_reason: Option<String> = None;
+
+    while let Some(chunk_result) = stream.next().await {
+        let chunk = chunk_result.map_err(|e| format!("Stream error: {}", e))?;
+        buffer.push_str(&String::from_utf8_lossy(&chunk).replace("\r\n", "\n"));
+
+        // Process complete SSE events
+        while let Some(event_end) = buffer.find("\n\n") {
+            let sse_event = buffer[..event_end].to_string();
+            buffer = buffer[event_end + 2..].to_string();
+
+            for data in sse_event.lines().filter_map(|line| line.strip_prefix("data: ")) {
+                let json_data: serde_json::Value = match serde_json::from_str(data) {
+                    Ok(json_data) => json_data,
+                    Err(e) => {
+                        eprintln_redacted!("Failed to parse SSE data: {}", e);
+                        continue;
+                    }
+                };
+
+                // Usage is cumulative; the last chunk has the totals
+                if json_data.get("usageMetadata").is_some() {
+                    usage = usage_of(&json_data);
+                }
+                if let Some(reason) = json_data["candidates"][0]["finishReason"].as_str() {
+                    finish_reason = Some(reason.to_string());
+                }
+
+                let text = text_of(&json_data);
+                if !text.is_empty() {
+                    let _ = app.emit(event, json!({ "type": "content_delta", "text": text }));
+                }
+            }
+        }
+    }
+
+    record_usage(app, &request.model, &usage);
+
+    // Emit completion event
+    let _ = app.emit(event, json!({
+        "type": "stream_end",
+        "finishReason": finish_reason,
+        "usage": usage,
+    }));
+
+    Ok(())
+}
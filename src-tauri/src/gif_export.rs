@@ -0,0 +1,231 @@
+/**
+ * GIF Export Module
+ *
+ * `export_gif` turns a slice of a recording into a GIF small enough to paste
+ * into Slack or GitHub (GIF_FPS frames per second, at most MAX_DURATION):
+ * - With ffmpeg (see media_tools.rs): palettegen/paletteuse filters, one
+ *   palette for the whole clip with Bayer dithering
+ * - Without it (macOS): frames are extracted with AVFoundation, one NeuQuant
+ *   palette is built from a sample of them and every frame is
+ *   Floyd-Steinberg dithered onto it
+ *
+ * Runs as a job (jobs.rs); the `job-finished` result is the GIF path
+ * (defaults to the Downloads folder).
+ */
+
+use image::RgbaImage;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+use crate::background_tasks::TaskRegistry;
+use crate::command_metrics;
+use crate::jobs::{JobContext, JobRegistry};
+use crate::media_tools;
+
+const GIF_FPS: u32 = 10;
+const MAX_DURATION: Duration = Duration::from_secs(60);
+
+const DEFAULT_MAX_WIDTH: u32 = 640;
+const MIN_WIDTH: u32 = 120;
+const MAX_WIDTH: u32 = 1920;
+
+/// Frames sampled to build the palette (native encoder)
+const PALETTE_SAMPLE_FRAMES: usize = 8;
+
+/// NeuQuant sampling factor (1 = best, 30 = fastest)
+const PALETTE_QUALITY: i32 = 10;
+
+/// Encode with ffmpeg: one palette for the clip, then dithered onto it
+fn export_with_ffmpeg(ctx: &JobContext, ffmpeg: &Path, video: &Path, start: f64, duration: f64, max_width: u32, output: &Path) -> Result<(), String> {
+    ctx.progress(0.1, "Encoding GIF");
+    let filter = format!(
+        "fps={},scale='min({},iw)':-1:flags=lanczos,split[a][b];[a]palettegen=stats_mode=diff[p];[b][p]paletteuse=dither=bayer:bayer_scale=5",
+        GIF_FPS, max_width
+    );
+    let mut command = Command::new(ffmpeg);
+    command
+        .args(["-y", "-v", "error", "-ss", &format!("{:.3}", start), "-t", &format!("{:.3}", duration), "-i"])
+        .arg(video)
+        .args(["-vf", &filter, "-loop", "0", "-f", "gif"])
+        .arg(output);
+    media_tools::run_cancellable(&mut command, || ctx.is_cancelled())
+        .map_err(|e| format!("Failed to encode GIF: {}", e))
+}
+
+#[cfg(target_os = "macos")]
+extern "C" {
+    fn screen_recorder_extract_frames(
+        path: *const std::os::raw::c_char,
+        output_dir: *const std::os::raw::c_char,
+        start: f64,
+        duration: f64,
+        fps: f64,
+        max_width: i32,
+    ) -> i32;
+}
+
+/// Extract frames as PNGs into `frames_dir` with AVFoundation; returns how many
+#[cfg(target_os = "macos")]
+fn extract_frames(video: &Path, frames_dir: &Path, start: f64, duration: f64, max_width: u32) -> Result<usize, String> {
+    use std::ffi::CString;
+
+    let c_path = CString::new(video.to_str().ok_or("Invalid video path")?).map_err(|_| "Invalid video path")?;
+    let c_dir = CString::new(frames_dir.to_str().ok_or("Invalid frames path")?).map_err(|_| "Invalid frames path")?;
+    let count = unsafe {
+        screen_recorder_extract_frames(c_path.as_ptr(), c_dir.as_ptr(), start, duration, GIF_FPS as f64, max_width as i32)
+    };
+    usize::try_from(count).map_err(|_| "Failed to extract video frames".to_string())
+}
+
+#[cfg(not(target_os = "macos"))]
+fn extract_frames(_video: &Path, _frames_dir: &Path, _start: f64, _duration: f64, _max_width: u32) -> Result<usize, String> {
+    Err("GIF export needs ffmpeg on this platform".to_string())
+}
+
+fn read_frame(frames_dir: &Path, index: usize) -> Result<RgbaImage, String> {
+    let path = frames_dir.join(format!("frame{:06}.png", index));
+    Ok(image::open(&path)
+        .map_err(|e| format!("Failed to read frame {:?}: {}", path, e))?
+        .to_rgba8())
+}
+
+/// Encode extracted frames with one shared palette and Floyd-Steinberg dithering
+fn encode_frames(ctx: &JobContext, frames_dir: &Path, count: usize, output: &Path) -> Result<(), String> {
+    if count == 0 {
+        return Err("No frames in the selected range".to_string());
+    }
+    let first = read_frame(frames_dir, 0)?;
+    let (width, height) = first.dimensions();
+    let (gif_width, gif_height) = (
+        u16::try_from(width).map_err(|_| "Frame too large for GIF")?,
+        u16::try_from(height).map_err(|_| "Frame too large for GIF")?,
+    );
+
+    ctx.progress(0.3, "Building palette");
+    let step = count.div_ceil(PALETTE_SAMPLE_FRAMES).max(1);
+    let mut sample: Vec<u8> = Vec::new();
+    for index in (0..count).step_by(step) {
+        sample.extend_from_slice(read_frame(frames_dir, index)?.as_raw());
+    }
+    let palette = color_quant::NeuQuant::new(PALETTE_QUALITY, 256, &sample);
+    drop(sample);
+
+    let file = std::fs::File::create(output).map_err(|e| format!("Failed to create {:?}: {}", output, e))?;
+    let mut encoder = gif::Encoder::new(std::io::BufWriter::new(file), gif_width, gif_height, &palette.color_map_rgb())
+        .map_err(|e| format!("Failed to write GIF: {}", e))?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(|e| format!("Failed to write GIF: {}", e))?;
+
+    for index in 0..count {
+        ctx.check_cancelled()?;
+        ctx.progress(0.3 + 0.7 * index as f64 / count as f64, "Encoding GIF");
+
+        let mut frame = if index == 0 { first.clone() } else { read_frame(frames_dir, index)? };
+        if frame.dimensions() != (width, height) {
+            frame = image::imageops::resize(&frame, width, height, image::imageops::FilterType::Triangle);
+        }
+        image::imageops::dither(&mut frame, &palette);
+        let indices = image::imageops::index_colors(&frame, &palette);
+        let gif_frame = gif::Frame {
+            width: gif_width,
+            height: gif_height,
+            delay: (100 / GIF_FPS) as u16,
+            buffer: std::borrow::Cow::Owned(indices.into_raw()),
+            ..gif::Frame::default()
+        };
+        encoder
+            .write_frame(&gif_frame)
+            .map_err(|e| format!("Failed to write GIF: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Encode without ffmpeg (blocks); frames go to a temporary folder next to the output
+fn export_native(ctx: &JobContext, video: &Path, start: f64, duration: f64, max_width: u32, output: &Path) -> Result<(), String> {
+    let frames_dir = output.with_extension("frames");
+    std::fs::create_dir_all(&frames_dir).map_err(|e| format!("Failed to create {:?}: {}", frames_dir, e))?;
+    let result = (|| {
+        ctx.progress(0.0, "Extracting frames");
+        let count = extract_frames(video, &frames_dir, start, duration, max_width)?;
+        ctx.check_cancelled()?;
+        encode_frames(ctx, &frames_dir, count, output)
+    })();
+    let _ = std::fs::remove_dir_all(&frames_dir);
+    result
+}
+
+/// Tauri command to export a slice of a recording as a GIF
+/// Runs as a job (returns the job id); the `job-finished` result is the path of
+/// the written GIF (defaults to the Downloads folder)
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn export_gif(
+    app: AppHandle,
+    jobs: tauri::State<'_, Arc<JobRegistry>>,
+    tasks: tauri::State<'_, Arc<TaskRegistry>>,
+    video_path: String,
+    start_ms: u64,
+    duration_ms: u64,
+    max_width: Option<u32>,
+    output_path: Option<String>,
+) -> Result<String, String> {
+    command_metrics::track_async("export_gif", async move {
+        let video = PathBuf::from(&video_path);
+        if !video.is_file() {
+            return Err(format!("Video not found: {}", video_path));
+        }
+        if duration_ms == 0 {
+            return Err("GIF duration must be positive".to_string());
+        }
+        if duration_ms > MAX_DURATION.as_millis() as u64 {
+            return Err(format!("GIFs are limited to {} seconds", MAX_DURATION.as_secs()));
+        }
+        let max_width = max_width.unwrap_or(DEFAULT_MAX_WIDTH).clamp(MIN_WIDTH, MAX_WIDTH);
+        let output = match output_path {
+            Some(path) => PathBuf::from(path),
+            None => app
+                .path()
+                .download_dir()
+                .map_err(|e| format!("Failed to get downloads dir: {}", e))?
+                .join(format!("taskerino-clip-{}.gif", chrono::Local::now().format("%Y%m%d-%H%M%S"))),
+        };
+        if !output.parent().is_some_and(|parent| parent.is_dir()) {
+            return Err(format!("Output folder doesn't exist: {:?}", output));
+        }
+
+        jobs.start(app.clone(), &tasks, "export-gif", move |ctx| async move {
+            // Written next to the target and renamed once complete
+            let partial_path = output.with_extension("gif.partial");
+            ctx.partial_output(partial_path.clone());
+            let job = ctx.clone();
+            let gif_path = output.clone();
+            let (start, duration) = (start_ms as f64 / 1000.0, duration_ms as f64 / 1000.0);
+            let used_ffmpeg = tokio::task::spawn_blocking(move || {
+                let ffmpeg = media_tools::ffmpeg();
+                match &ffmpeg {
+                    Some(ffmpeg) => export_with_ffmpeg(&job, ffmpeg, &video, start, duration, max_width, &partial_path)?,
+                    None => export_native(&job, &video, start, duration, max_width, &partial_path)?,
+                }
+                job.check_cancelled()?;
+                std::fs::rename(&partial_path, &gif_path).map_err(|e| format!("Failed to write GIF: {}", e))?;
+                job.commit_output(&partial_path);
+                Ok::<bool, String>(ffmpeg.is_some())
+            })
+            .await
+            .map_err(|e| format!("GIF export task failed: {}", e))??;
+
+            println!(
+                "🎞️  [GIF] Exported {} ms of {} to {:?} ({})",
+                duration_ms,
+                video_path,
+                output,
+                if used_ffmpeg { "ffmpeg" } else { "native encoder" }
+            );
+            Ok(serde_json::Value::String(output.to_string_lossy().to_string()))
+        })
+    }).await
+}
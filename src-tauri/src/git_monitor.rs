@@ -0,0 +1,331 @@
+/**
+ * Git Monitor Module
+ *
+ * Records commits and branch switches in watched repositories as timeline
+ * events of the active session, so a summary can connect what was said to
+ * what was committed:
+ * - Watched repos live in settings.git.watchedRepos (`add_watched_repo` /
+ *   `remove_watched_repo`, stored as the repo's top-level directory)
+ * - Every POLL_INTERVAL the monitor reads each repo's branch and HEAD with
+ *   the `git` CLI; changes seen while a session is active are appended to
+ *   the session's `gitEvents` (via its change log, see session_storage.rs)
+ *   and emitted as `git-activity`
+ * - HEAD and branch are tracked outside sessions too, so work done before a
+ *   session started is never attributed to it; commits pulled in with older
+ *   commit dates are skipped for the same reason
+ *
+ * Repos without the `git` CLI available (or deleted ones) are skipped quietly.
+ */
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::background_tasks::TaskRegistry;
+use crate::command_metrics;
+use crate::profiles;
+use crate::session_storage::{self, SessionChange};
+use crate::settings::SettingsManager;
+
+const TASK_NAME: &str = "git-monitor";
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Commits recorded per repo per poll (e.g. after a large rebase)
+const MAX_COMMITS_PER_POLL: usize = 50;
+
+/// Field separator in `git log` output (ASCII unit separator)
+const FIELD_SEP: char = '\u{1f}';
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum GitEventKind {
+    Commit,
+    BranchSwitch,
+}
+
+/// Timeline event stored in session.gitEvents
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitEvent {
+    pub kind: GitEventKind,
+    /// Repo top-level directory
+    pub repo: String,
+    /// Repo folder name, for display
+    pub repo_name: String,
+    pub timestamp: String,
+    /// Branch after the event ("HEAD" when detached)
+    pub branch: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_branch: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+}
+
+/// `git-activity` payload
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GitActivity {
+    session_id: String,
+    events: Vec<GitEvent>,
+}
+
+/// Last observed state of a repo
+#[derive(Debug, Clone, PartialEq)]
+struct RepoState {
+    branch: String,
+    /// None before the first commit
+    head: Option<String>,
+}
+
+fn git(repo: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git").arg("-C").arg(repo).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Top-level directory of the repo containing `path`
+fn repo_root(path: &Path) -> Option<String> {
+    git(path, &["rev-parse", "--show-toplevel"]).filter(|root| !root.is_empty())
+}
+
+fn repo_state(repo: &Path) -> Option<RepoState> {
+    let branch = git(repo, &["symbolic-ref", "--short", "-q", "HEAD"])
+        .filter(|branch| !branch.is_empty())
+        .unwrap_or_else(|| "HEAD".to_string());
+    // Fails in a repo without commits
+    let head = git(repo, &["rev-parse", "-q", "--verify", "HEAD"]);
+    if head.is_none() && !repo.join(".git").exists() {
+        return None;
+    }
+    Some(RepoState { branch, head })
+}
+
+fn repo_name(repo: &str) -> String {
+    Path::new(repo)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| repo.to_string())
+}
+
+/// Commits between `from` (exclusive) and `to`, oldest first, committed at or
+/// after `since`
+fn new_commits(
+    repo: &Path,
+    from: Option<&str>,
+    to: &str,
+    since: Option<chrono::DateTime<chrono::Utc>>,
+) -> Vec<(String, String, String, chrono::DateTime<chrono::Utc>)> {
+    let range = match from {
+        Some(from) => format!("{}..{}", from, to),
+        None => to.to_string(),
+    };
+    let format = format!("--format=%H{0}%an{0}%cI{0}%s", FIELD_SEP);
+    let max_count = format!("--max-count={}", MAX_COMMITS_PER_POLL);
+    let Some(log) = git(repo, &["log", &format, &max_count, &range]) else {
+        return Vec::new();
+    };
+
+    let mut commits: Vec<_> = log
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(4, FIELD_SEP);
+            let hash = fields.next()?.to_string();
+            let author = fields.next()?.to_string();
+            let committed_at = chrono::DateTime::parse_from_rfc3339(fields.next()?)
+                .ok()?
+                .with_timezone(&chrono::Utc);
+            let message = fields.next().unwrap_or_default().to_string();
+            Some((hash, author, message, committed_at))
+        })
+        .filter(|(_, _, _, committed_at)| since.map_or(true, |since| *committed_at >= since))
+        .collect();
+    commits.reverse();
+    commits
+}
+
+/// Events for the change from `previous` to `current`
+fn diff_events(
+    repo: &str,
+    previous: &RepoState,
+    current: &RepoState,
+    since: Option<chrono::DateTime<chrono::Utc>>,
+) -> Vec<GitEvent> {
+    let name = repo_name(repo);
+    let now = chrono::Utc::now().to_rfc3339();
+    let event = |kind| GitEvent {
+        kind,
+        repo: repo.to_string(),
+        repo_name: name.clone(),
+        timestamp: now.clone(),
+        branch: current.branch.clone(),
+        previous_branch: None,
+        hash: None,
+        message: None,
+        author: None,
+    };
+
+    if previous.branch != current.branch {
+        // Commits between the two branch tips aren't new work
+        return vec![GitEvent {
+            previous_branch: Some(previous.branch.clone()),
+            hash: current.head.clone(),
+            ..event(GitEventKind::BranchSwitch)
+        }];
+    }
+    let Some(head) = current.head.as_deref() else {
+        return Vec::new();
+    };
+    if previous.head.as_deref() == Some(head) {
+        return Vec::new();
+    }
+
+    new_commits(Path::new(repo), previous.head.as_deref(), head, since)
+        .into_iter()
+        .map(|(hash, author, message, committed_at)| GitEvent {
+            timestamp: committed_at.to_rfc3339(),
+            hash: Some(hash),
+            message: Some(message),
+            author: Some(author),
+            ..event(GitEventKind::Commit)
+        })
+        .collect()
+}
+
+fn record_events(app: &AppHandle, session_id: &str, events: &[GitEvent]) -> Result<(), String> {
+    let data_dir = profiles::profile_data_dir(app)?;
+    let items = events
+        .iter()
+        .map(serde_json::to_value)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to serialize git events: {}", e))?;
+    let encrypted = app.state::<Arc<SettingsManager>>().get().storage.encrypt_sessions;
+    session_storage::append_session_changes(
+        &data_dir,
+        session_id,
+        &[SessionChange::Append { field: "gitEvents".to_string(), items }],
+        encrypted,
+    )
+}
+
+/// One poll: refresh `known` and return new events if a session is active
+fn poll(app: &AppHandle, repos: &[String], known: &mut HashMap<String, RepoState>) {
+    known.retain(|repo, _| repos.contains(repo));
+
+    let active = profiles::profile_data_dir(app)
+        .and_then(|data_dir| session_storage::read_active_session(&data_dir))
+        .unwrap_or_else(|e| {
+            eprintln!("⚠️  [GIT] Failed to read active session: {}", e);
+            None
+        });
+    let since = active.as_ref().and_then(|session| {
+        chrono::DateTime::parse_from_rfc3339(&session.start_time)
+            .ok()
+            .map(|start| start.with_timezone(&chrono::Utc))
+    });
+
+    let mut events = Vec::new();
+    for repo in repos {
+        let Some(current) = repo_state(Path::new(repo)) else {
+            known.remove(repo);
+            continue;
+        };
+        if let Some(previous) = known.insert(repo.clone(), current.clone()) {
+            if active.is_some() && previous != current {
+                events.extend(diff_events(repo, &previous, &current, since));
+            }
+        }
+    }
+
+    let Some(session) = active else {
+        return;
+    };
+    if events.is_empty() {
+        return;
+    }
+    if let Err(e) = record_events(app, &session.id, &events) {
+        eprintln!("⚠️  [GIT] Failed to record git events: {}", e);
+        return;
+    }
+    println!("🔀 [GIT] Recorded {} git event(s) in session {}", events.len(), session.id);
+    let _ = app.emit("git-activity", &GitActivity { session_id: session.id, events });
+}
+
+/// Start polling watched repos (no-op work while none are configured)
+pub fn start(app: AppHandle, registry: &TaskRegistry) -> Result<(), String> {
+    registry.spawn(TASK_NAME, |mut shutdown| async move {
+        let mut known: HashMap<String, RepoState> = HashMap::new();
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+
+            let repos = app.state::<Arc<SettingsManager>>().get().git.watched_repos;
+            if repos.is_empty() {
+                known.clear();
+                continue;
+            }
+            let poll_app = app.clone();
+            known = tokio::task::spawn_blocking(move || {
+                poll(&poll_app, &repos, &mut known);
+                known
+            })
+            .await
+            .unwrap_or_default();
+        }
+    })
+}
+
+/// Tauri command to watch a git repo; `path` may be any folder inside it
+#[tauri::command]
+pub fn add_watched_repo(
+    app: AppHandle,
+    settings: State<Arc<SettingsManager>>,
+    path: String,
+) -> Result<Vec<String>, String> {
+    command_metrics::track("add_watched_repo", || {
+        let root = repo_root(Path::new(path.trim()))
+            .ok_or_else(|| format!("{} is not inside a git repository (or git is not installed)", path))?;
+        let mut repos = settings.get().git.watched_repos;
+        if repos.contains(&root) {
+            return Ok(repos);
+        }
+        repos.push(root.clone());
+        let repos = settings.update(&app, json!({ "git": { "watchedRepos": repos } }))?.git.watched_repos;
+        println!("🔀 [GIT] Watching {}", root);
+        Ok(repos)
+    })
+}
+
+/// Tauri command to stop watching a git repo
+#[tauri::command]
+pub fn remove_watched_repo(
+    app: AppHandle,
+    settings: State<Arc<SettingsManager>>,
+    path: String,
+) -> Result<Vec<String>, String> {
+    command_metrics::track("remove_watched_repo", || {
+        let root = repo_root(Path::new(path.trim())).unwrap_or_else(|| path.trim().to_string());
+        let mut repos = settings.get().git.watched_repos;
+        let before = repos.len();
+        repos.retain(|repo| repo != &root && repo != path.trim());
+        if repos.len() == before {
+            return Ok(repos);
+        }
+        let repos = settings.update(&app, json!({ "git": { "watchedRepos": repos } }))?.git.watched_repos;
+        println!("🔀 [GIT] Stopped watching {}", root);
+        Ok(repos)
+    })
+}
@@ -0,0 +1,407 @@
+/**
+ * Issue Tracker Module
+ *
+ * Turns a session's AI-extracted action items (summary.recommendedTasks) into
+ * Linear or Jira issues:
+ * - Linear: GraphQL `issueCreate`; `project` is a team key (e.g. "ENG") or id.
+ *   Uses a personal API key
+ * - Jira Cloud: REST v3 `POST /rest/api/3/issue`; `project` is the project key.
+ *   Basic auth with settings.issueTracker.jiraEmail + an API token
+ * - Created issues are stored on the session as `trackerIssues`; tasks already
+ *   created for the same provider and project (matched by normalized title)
+ *   are skipped, so re-running is safe
+ * - Tokens are stored in the keychain per profile (api_keys.rs)
+ */
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::path::Path;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter};
+
+use crate::ai_client;
+use crate::api_keys;
+use crate::command_metrics;
+use crate::profiles;
+use crate::session_storage;
+use crate::settings::{IssueTrackerSettings, SettingsManager};
+
+const LINEAR_API_URL: &str = "https://api.linear.app/graphql";
+const DEFAULT_JIRA_ISSUE_TYPE: &str = "Task";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TrackerProvider {
+    Linear,
+    Jira,
+}
+
+impl TrackerProvider {
+    fn token_key(self) -> &'static str {
+        match self {
+            TrackerProvider::Linear => "linear_api_key",
+            TrackerProvider::Jira => "jira_api_token",
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            TrackerProvider::Linear => "Linear",
+            TrackerProvider::Jira => "Jira",
+        }
+    }
+}
+
+/// An issue created from a session task (stored in session.trackerIssues)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackerIssue {
+    pub provider: TrackerProvider,
+    pub project: String,
+    /// Normalized task title, used to skip tasks that already have an issue
+    pub task_key: String,
+    pub task_title: String,
+    pub issue_id: String,
+    /// Human-readable key, e.g. "ENG-123"
+    pub identifier: String,
+    pub url: String,
+    pub created_at: String,
+}
+
+/// Result of `create_tasks_from_session`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackerExportReport {
+    pub session_id: String,
+    pub created: Vec<TrackerIssue>,
+    /// Tasks that already had an issue in this provider/project
+    pub skipped: usize,
+    /// Tasks that failed (title: error); successfully created issues are still saved
+    pub failed: Vec<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct SessionTask {
+    title: String,
+    priority: String,
+    context: String,
+}
+
+/// Created issue as returned by a provider
+struct CreatedIssue {
+    id: String,
+    identifier: String,
+    url: String,
+}
+
+fn task_key(title: &str) -> String {
+    title.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+/// Issue body: the task's context plus where it came from
+fn issue_description(task: &SessionTask, session: &serde_json::Value) -> String {
+    let name = session.get("name").and_then(|n| n.as_str()).unwrap_or("Untitled session");
+    let date = session
+        .get("startTime")
+        .and_then(|t| t.as_str())
+        .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+        .map(|t| t.with_timezone(&chrono::Local).format("%Y-%m-%d").to_string())
+        .unwrap_or_default();
+    format!(
+        "{}\n\nPriority: {}\nFrom Taskerino session \"{}\" ({})",
+        task.context.trim(),
+        task.priority,
+        name,
+        date
+    )
+}
+
+fn linear_priority(priority: &str) -> u8 {
+    match priority {
+        "urgent" => 1,
+        "high" => 2,
+        "medium" => 3,
+        "low" => 4,
+        _ => 0,
+    }
+}
+
+async fn linear_request(token: &str, query: &str, variables: serde_json::Value) -> Result<serde_json::Value, String> {
+    let response = ai_client::http_client()?
+        .post(LINEAR_API_URL)
+        .header("Authorization", token)
+        .json(&json!({ "query": query, "variables": variables }))
+        .send()
+        .await
+        .map_err(|e| format!("Linear request failed: {}", e))?;
+
+    let status = response.status();
+    let body: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse Linear response: {}", e))?;
+    if let Some(message) = body.pointer("/errors/0/message").and_then(|m| m.as_str()) {
+        return Err(format!("Linear API error: {}", message));
+    }
+    if !status.is_success() {
+        return Err(format!("Linear API error ({})", status));
+    }
+    Ok(body["data"].clone())
+}
+
+/// Team id for a team key ("ENG"), or the value itself if it already is an id
+async fn linear_team_id(token: &str, project: &str) -> Result<String, String> {
+    let data = linear_request(
+        token,
+        "query Teams($key: String!) { teams(filter: { key: { eqIgnoreCase: $key } }) { nodes { id } } }",
+        json!({ "key": project }),
+    )
+    .await?;
+    if let Some(id) = data.pointer("/teams/nodes/0/id").and_then(|id| id.as_str()) {
+        return Ok(id.to_string());
+    }
+    // Team ids are UUIDs
+    if project.len() == 36 && project.chars().all(|c| c.is_ascii_hexdigit() || c == '-') {
+        return Ok(project.to_string());
+    }
+    Err(format!("Linear team {} not found", project))
+}
+
+async fn create_linear_issue(token: &str, team_id: &str, task: &SessionTask, description: String) -> Result<CreatedIssue, String> {
+    let data = linear_request(
+        token,
+        "mutation IssueCreate($input: IssueCreateInput!) { issueCreate(input: $input) { success issue { id identifier url } } }",
+        json!({
+            "input": {
+                "teamId": team_id,
+                "title": task.title,
+                "description": description,
+                "priority": linear_priority(&task.priority),
+            }
+        }),
+    )
+    .await?;
+
+    let issue = data.pointer("/issueCreate/issue").filter(|issue| !issue.is_null()).ok_or("Linear did not create the issue")?;
+    let field = |name: &str| issue.get(name).and_then(|v| v.as_str()).unwrap_or_default().to_string();
+    Ok(CreatedIssue { id: field("id"), identifier: field("identifier"), url: field("url") })
+}
+
+async fn create_jira_issue(
+    settings: &IssueTrackerSettings,
+    token: &str,
+    project: &str,
+    task: &SessionTask,
+    description: String,
+) -> Result<CreatedIssue, String> {
+    let base_url = settings.jira_base_url.trim_end_matches('/');
+    let issue_type = if settings.jira_issue_type.trim().is_empty() { DEFAULT_JIRA_ISSUE_TYPE } else { settings.jira_issue_type.trim() };
+
+    // REST v3 descriptions are Atlassian Document Format: one paragraph per line
+    let paragraphs: Vec<serde_json::Value> = description
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| json!({ "type": "paragraph", "content": [{ "type": "text", "text": line }] }))
+        .collect();
+    let body = json!({
+        "fields": {
+            "project": { "key": project },
+            "summary": task.title,
+            "issuetype": { "name": issue_type },
+            "labels": ["taskerino"],
+            "description": { "type": "doc", "version": 1, "content": paragraphs },
+        }
+    });
+
+    let response = ai_client::http_client()?
+        .post(format!("{}/rest/api/3/issue", base_url))
+        .basic_auth(&settings.jira_email, Some(token))
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| format!("Jira request failed: {}", e))?;
+
+    let status = response.status();
+    let result: serde_json::Value = response.json().await.unwrap_or_default();
+    if !status.is_success() {
+        let detail = result
+            .get("errorMessages")
+            .and_then(|m| m.as_array())
+            .and_then(|m| m.first())
+            .and_then(|m| m.as_str())
+            .map(str::to_string)
+            .or_else(|| result.get("errors").filter(|e| e.as_object().is_some_and(|e| !e.is_empty())).map(|e| e.to_string()))
+            .unwrap_or_default();
+        return Err(format!("Jira API error ({}): {}", status, detail));
+    }
+
+    let key = result.get("key").and_then(|k| k.as_str()).unwrap_or_default().to_string();
+    Ok(CreatedIssue {
+        id: result.get("id").and_then(|id| id.as_str()).unwrap_or_default().to_string(),
+        url: format!("{}/browse/{}", base_url, key),
+        identifier: key,
+    })
+}
+
+/// Tasks without an issue in provider/project yet, and how many were skipped
+fn pending_tasks(
+    session: &serde_json::Value,
+    provider: TrackerProvider,
+    project: &str,
+) -> (Vec<SessionTask>, usize) {
+    let tasks: Vec<SessionTask> = session
+        .pointer("/summary/recommendedTasks")
+        .cloned()
+        .and_then(|tasks| serde_json::from_value(tasks).ok())
+        .unwrap_or_default();
+    let existing: Vec<TrackerIssue> = session
+        .get("trackerIssues")
+        .cloned()
+        .and_then(|issues| serde_json::from_value(issues).ok())
+        .unwrap_or_default();
+    let is_created = |key: &str| {
+        existing
+            .iter()
+            .any(|issue| issue.provider == provider && issue.project.eq_ignore_ascii_case(project) && issue.task_key == key)
+    };
+
+    let mut seen = Vec::new();
+    let mut skipped = 0;
+    let pending = tasks
+        .into_iter()
+        .filter(|task| {
+            let key = task_key(&task.title);
+            if key.is_empty() {
+                return false;
+            }
+            if is_created(&key) || seen.contains(&key) {
+                skipped += 1;
+                return false;
+            }
+            seen.push(key);
+            true
+        })
+        .collect();
+    (pending, skipped)
+}
+
+/// Append created issues to session.trackerIssues
+fn save_issues(data_dir: &Path, session_id: &str, issues: &[TrackerIssue]) -> Result<(), String> {
+    if issues.is_empty() {
+        return Ok(());
+    }
+    let issues = serde_json::to_value(issues).map_err(|e| format!("Failed to serialize issues: {}", e))?;
+    session_storage::update_session_value(data_dir, session_id, |session| {
+        let session = session.as_object_mut().ok_or("Session is not an object")?;
+        let stored = session.entry("trackerIssues").or_insert_with(|| json!([]));
+        match (stored.as_array_mut(), issues.as_array()) {
+            (Some(stored), Some(issues)) => stored.extend(issues.iter().cloned()),
+            _ => *stored = issues,
+        }
+        Ok(())
+    })?;
+    Ok(())
+}
+
+/// Tauri command to create Linear/Jira issues from a session's action items
+/// Emits `tracker-issues-created` with the report when anything was created
+#[tauri::command]
+pub async fn create_tasks_from_session(
+    app: AppHandle,
+    settings: tauri::State<'_, Arc<SettingsManager>>,
+    session_id: String,
+    provider: TrackerProvider,
+    project: String,
+) -> Result<TrackerExportReport, String> {
+    command_metrics::track_async("create_tasks_from_session", async move {
+        let project = project.trim().to_string();
+        if project.is_empty() {
+            return Err(format!("Choose a {} {} first", provider.name(), if provider == TrackerProvider::Linear { "team" } else { "project" }));
+        }
+        let token = api_keys::get_api_key(&app, provider.token_key())?
+            .ok_or_else(|| format!("{} API token not set. Please add it in Settings.", provider.name()))?;
+        let tracker_settings = settings.get().issue_tracker;
+        if provider == TrackerProvider::Jira && (tracker_settings.jira_base_url.is_empty() || tracker_settings.jira_email.is_empty()) {
+            return Err("Set the Jira site URL and account email in Settings first".to_string());
+        }
+
+        let data_dir = profiles::profile_data_dir(&app)?;
+        let session = session_storage::read_session_value(&data_dir, &session_id)?;
+        let (tasks, skipped) = pending_tasks(&session, provider, &project);
+
+        let mut report = TrackerExportReport {
+            session_id: session_id.clone(),
+            created: Vec::new(),
+            skipped,
+            failed: Vec::new(),
+        };
+        if tasks.is_empty() {
+            return Ok(report);
+        }
+
+        let team_id = match provider {
+            TrackerProvider::Linear => Some(linear_team_id(&token, &project).await?),
+            TrackerProvider::Jira => None,
+        };
+        for task in &tasks {
+            let description = issue_description(task, &session);
+            let created = match &team_id {
+                Some(team_id) => create_linear_issue(&token, team_id, task, description).await,
+                None => create_jira_issue(&tracker_settings, &token, &project, task, description).await,
+            };
+            match created {
+                Ok(issue) => report.created.push(TrackerIssue {
+                    provider,
+                    project: project.clone(),
+                    task_key: task_key(&task.title),
+                    task_title: task.title.clone(),
+                    issue_id: issue.id,
+                    identifier: issue.identifier,
+                    url: issue.url,
+                    created_at: chrono::Utc::now().to_rfc3339(),
+                }),
+                Err(e) => {
+                    eprintln!("❌ [ISSUE TRACKER] Failed to create issue for \"{}\": {}", task.title, e);
+                    report.failed.push(format!("{}: {}", task.title, e));
+                }
+            }
+        }
+
+        let created = report.created.clone();
+        let save_dir = data_dir.clone();
+        let save_id = session_id.clone();
+        tokio::task::spawn_blocking(move || save_issues(&save_dir, &save_id, &created))
+            .await
+            .map_err(|e| format!("Failed to save issues: {}", e))??;
+
+        println!(
+            "📋 [ISSUE TRACKER] Session {}: {} {} issues created, {} skipped, {} failed",
+            session_id,
+            report.created.len(),
+            provider.name(),
+            report.skipped,
+            report.failed.len()
+        );
+        if !report.created.is_empty() {
+            let _ = app.emit("tracker-issues-created", &report);
+        }
+        Ok(report)
+    }).await
+}
+
+/// Tauri command to save the Linear API key / Jira API token (keychain)
+#[tauri::command]
+pub fn set_issue_tracker_token(app: AppHandle, provider: TrackerProvider, token: String) -> Result<(), String> {
+    command_metrics::track("set_issue_tracker_token", || {
+        api_keys::store_api_key(&app, provider.token_key(), &token)
+    })
+}
+
+/// Tauri command to check whether a tracker token is set
+#[tauri::command]
+pub fn has_issue_tracker_token(app: AppHandle, provider: TrackerProvider) -> Result<bool, String> {
+    command_metrics::track("has_issue_tracker_token", || {
+        Ok(api_keys::get_api_key(&app, provider.token_key())?.is_some())
+    })
+}
@@ -0,0 +1,357 @@
+/**
+ * Jobs Module
+ *
+ * Cancellable long-running commands (exports, batch metadata extraction, ...):
+ * - the command returns a job id immediately; the work runs as a background
+ *   task named `job-{id}`
+ * - every state change is emitted as `job-progress` with one `JobProgress`
+ *   shape for all job kinds (so the UI can show a single background tasks
+ *   panel, seeded by `list_active_jobs`); the outcome is also emitted as
+ *   `job-finished` (with the result value on success)
+ * - `cancel_job(id)` signals the job; work checks `ctx.check_cancelled()`
+ *   between steps, and partial outputs registered with the context are
+ *   deleted when a job is cancelled or fails
+ */
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter};
+
+use crate::background_tasks::{ShutdownSignal, TaskRegistry};
+use crate::command_metrics;
+use crate::safe_state::SafeState;
+
+/// Finished jobs kept for `list_jobs`
+const MAX_FINISHED_JOBS: usize = 50;
+
+const CANCELLED: &str = "Job cancelled";
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+/// Progress of one job - the `job-progress` event payload for every job kind
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobProgress {
+    pub job_id: String,
+    pub kind: String,
+    pub status: JobStatus,
+    /// 0.0 - 1.0
+    pub progress: f64,
+    /// Item / byte counts for jobs that process a known amount of work
+    pub current: Option<u64>,
+    pub total: Option<u64>,
+    pub message: Option<String>,
+    pub started_at: String,
+    pub updated_at: String,
+    pub finished_at: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JobFinishedEvent<'a> {
+    job_id: &'a str,
+    kind: &'a str,
+    status: JobStatus,
+    result: Option<serde_json::Value>,
+    error: Option<String>,
+}
+
+/// Managed registry of jobs
+pub struct JobRegistry {
+    jobs: SafeState<HashMap<String, JobProgress>>,
+    next_id: AtomicU64,
+}
+
+/// Handle passed to job work (cheap to clone into blocking closures)
+#[derive(Clone)]
+pub struct JobContext {
+    id: String,
+    kind: &'static str,
+    app: AppHandle,
+    jobs: Arc<JobRegistry>,
+    signal: ShutdownSignal,
+    partial_outputs: Arc<SafeState<Vec<PathBuf>>>,
+}
+
+impl JobContext {
+    /// Whether `cancel_job` (or app shutdown) has been requested
+    pub fn is_cancelled(&self) -> bool {
+        self.signal.is_cancelled()
+    }
+
+    /// Cancellation point: returns Err once the job has been cancelled
+    pub fn check_cancelled(&self) -> Result<(), String> {
+        if self.is_cancelled() {
+            Err(CANCELLED.to_string())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Report progress (0.0 - 1.0)
+    pub fn progress(&self, progress: f64, message: &str) {
+        self.jobs.update(&self.app, &self.id, |job| {
+            job.progress = progress.clamp(0.0, 1.0);
+            job.message = Some(message.to_string());
+        });
+    }
+
+    /// Report progress as `current` of `total` items (or bytes)
+    pub fn progress_items(&self, current: u64, total: u64, message: &str) {
+        self.jobs.update(&self.app, &self.id, |job| {
+            job.progress = if total == 0 { 1.0 } else { (current as f64 / total as f64).min(1.0) };
+            job.current = Some(current);
+            job.total = Some(total);
+            job.message = Some(message.to_string());
+        });
+    }
+
+    /// Register a file that is deleted if the job is cancelled or fails
+    pub fn partial_output(&self, path: PathBuf) {
+        self.partial_outputs.lock().push(path);
+    }
+
+    /// Mark a registered file as complete (kept on cancellation)
+    pub fn commit_output(&self, path: &PathBuf) {
+        self.partial_outputs.lock().retain(|p| p != path);
+    }
+
+    fn clean_partial_outputs(&self) {
+        for path in self.partial_outputs.lock().drain(..) {
+            if path.exists() {
+                match std::fs::remove_file(&path) {
+                    Ok(()) => println!("🧹 [JOBS] Removed partial output {:?}", path),
+                    Err(e) => eprintln!("⚠️  [JOBS] Failed to remove partial output {:?}: {}", path, e),
+                }
+            }
+        }
+    }
+}
+
+fn task_name(id: &str) -> String {
+    format!("job-{}", id)
+}
+
+impl JobRegistry {
+    pub fn new() -> Self {
+        Self {
+            jobs: SafeState::new("jobs", HashMap::new()),
+            next_id: AtomicU64::new(0),
+        }
+    }
+
+    /// Start `work` as a job on the background runtime; returns the job id
+    pub fn start<F, Fut>(
+        self: &Arc<Self>,
+        app: AppHandle,
+        tasks: &Arc<TaskRegistry>,
+        kind: &'static str,
+        work: F,
+    ) -> Result<String, String>
+    where
+        F: FnOnce(JobContext) -> Fut,
+        Fut: Future<Output = Result<serde_json::Value, String>> + Send + 'static,
+    {
+        let id = format!(
+            "{}-{}",
+            chrono::Utc::now().timestamp_millis(),
+            self.next_id.fetch_add(1, Ordering::SeqCst)
+        );
+
+        let now = chrono::Utc::now().to_rfc3339();
+        let job = JobProgress {
+            job_id: id.clone(),
+            kind: kind.to_string(),
+            status: JobStatus::Running,
+            progress: 0.0,
+            current: None,
+            total: None,
+            message: None,
+            started_at: now.clone(),
+            updated_at: now,
+            finished_at: None,
+            error: None,
+        };
+        self.jobs.lock().insert(id.clone(), job.clone());
+        let _ = app.emit("job-progress", &job);
+
+        let app_for_event = app.clone();
+        let jobs = self.clone();
+        let task_registry = tasks.clone();
+        let job_id = id.clone();
+        let spawned = tasks.spawn(&task_name(&id), move |signal| {
+            let ctx = JobContext {
+                id: job_id,
+                kind,
+                app,
+                jobs,
+                signal,
+                partial_outputs: Arc::new(SafeState::new("job.partial_outputs", Vec::new())),
+            };
+            let future = work(ctx.clone());
+            async move {
+                let result = future.await;
+                let status = match &result {
+                    _ if ctx.is_cancelled() => JobStatus::Cancelled,
+                    Ok(_) => JobStatus::Completed,
+                    Err(_) => JobStatus::Failed,
+                };
+                if status != JobStatus::Completed {
+                    ctx.clean_partial_outputs();
+                }
+                ctx.jobs.finish(&ctx, status, result);
+                task_registry.forget(&task_name(&ctx.id));
+            }
+        });
+
+        if let Err(e) = spawned {
+            self.update(&app_for_event, &id, |job| {
+                job.status = JobStatus::Failed;
+                job.error = Some(e.clone());
+            });
+            self.jobs.lock().remove(&id);
+            return Err(e);
+        }
+
+        println!("🧰 [JOBS] Started {} job {}", kind, id);
+        Ok(id)
+    }
+
+    /// Apply a change to a job and emit the new state
+    fn update(&self, app: &AppHandle, id: &str, apply: impl FnOnce(&mut JobProgress)) {
+        let snapshot = {
+            let mut jobs = self.jobs.lock();
+            let Some(job) = jobs.get_mut(id) else { return };
+            apply(job);
+            job.updated_at = chrono::Utc::now().to_rfc3339();
+            job.clone()
+        };
+        let _ = app.emit("job-progress", &snapshot);
+    }
+
+    fn finish(&self, ctx: &JobContext, status: JobStatus, result: Result<serde_json::Value, String>) {
+        let (value, error) = match (status, result) {
+            (JobStatus::Cancelled, _) => (None, None),
+            (_, Ok(value)) => (Some(value), None),
+            (_, Err(e)) => (None, Some(e)),
+        };
+
+        self.update(&ctx.app, &ctx.id, |job| {
+            job.status = status;
+            job.finished_at = Some(chrono::Utc::now().to_rfc3339());
+            job.error = error.clone();
+            if status == JobStatus::Completed {
+                job.progress = 1.0;
+            }
+        });
+
+        {
+            // Keep only the most recent finished jobs
+            let mut jobs = self.jobs.lock();
+            let mut finished: Vec<(String, String)> = jobs
+                .values()
+                .filter(|job| job.status != JobStatus::Running)
+                .map(|job| (job.started_at.clone(), job.job_id.clone()))
+                .collect();
+            if finished.len() > MAX_FINISHED_JOBS {
+                finished.sort();
+                for (_, id) in finished.iter().take(finished.len() - MAX_FINISHED_JOBS) {
+                    jobs.remove(id);
+                }
+            }
+        }
+
+        match status {
+            JobStatus::Failed => eprintln!("❌ [JOBS] {} job {} failed: {}", ctx.kind, ctx.id, error.as_deref().unwrap_or("")),
+            _ => println!("🧰 [JOBS] {} job {} {:?}", ctx.kind, ctx.id, status),
+        }
+
+        let _ = ctx.app.emit("job-finished", JobFinishedEvent {
+            job_id: &ctx.id,
+            kind: ctx.kind,
+            status,
+            result: value,
+            error,
+        });
+    }
+
+    /// Running and recently finished jobs, newest first
+    pub fn list(&self) -> Vec<JobProgress> {
+        let mut jobs: Vec<JobProgress> = self.jobs.lock().values().cloned().collect();
+        jobs.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+        jobs
+    }
+
+    /// Running jobs, oldest first
+    pub fn active(&self) -> Vec<JobProgress> {
+        let mut jobs: Vec<JobProgress> = self
+            .jobs
+            .lock()
+            .values()
+            .filter(|job| job.status == JobStatus::Running)
+            .cloned()
+            .collect();
+        jobs.sort_by(|a, b| a.started_at.cmp(&b.started_at));
+        jobs
+    }
+
+    /// Request cancellation; returns false if the job is unknown or already finished
+    pub fn cancel(&self, tasks: &TaskRegistry, id: &str) -> bool {
+        let running = self
+            .jobs
+            .lock()
+            .get(id)
+            .is_some_and(|job| job.status == JobStatus::Running);
+        if running {
+            println!("🧰 [JOBS] Cancelling job {}", id);
+            tasks.cancel(&task_name(id));
+        }
+        running
+    }
+}
+
+impl Default for JobRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Tauri command to cancel a running job
+#[tauri::command]
+pub fn cancel_job(
+    jobs: tauri::State<Arc<JobRegistry>>,
+    tasks: tauri::State<Arc<TaskRegistry>>,
+    job_id: String,
+) -> Result<bool, String> {
+    command_metrics::track("cancel_job", || {
+        Ok(jobs.cancel(&tasks, &job_id))
+    })
+}
+
+/// Tauri command to list running and recently finished jobs
+#[tauri::command]
+pub fn list_jobs(jobs: tauri::State<Arc<JobRegistry>>) -> Result<Vec<JobProgress>, String> {
+    command_metrics::track("list_jobs", || {
+        Ok(jobs.list())
+    })
+}
+
+/// Tauri command to list running jobs (seeds the background tasks panel)
+#[tauri::command]
+pub fn list_active_jobs(jobs: tauri::State<Arc<JobRegistry>>) -> Result<Vec<JobProgress>, String> {
+    command_metrics::track("list_active_jobs", || {
+        Ok(jobs.active())
+    })
+}
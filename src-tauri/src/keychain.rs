@@ -0,0 +1,39 @@
+/**
+ * Keychain Module
+ *
+ * Secrets in the OS credential store (macOS Keychain, Windows Credential
+ * Manager, the Secret Service / libsecret keyring on Linux, e.g. GNOME
+ * Keyring or KWallet) via the `keyring` crate. All three persist across
+ * reboots. Every entry lives under the app identifier as the service name;
+ * callers pick the account.
+ *
+ * `set` reads the secret back before returning, so callers may delete any
+ * other copy (plaintext stores being migrated) once it succeeds.
+ */
+
+/// Keychain service name (matches `identifier` in tauri.conf.json)
+const SERVICE: &str = "com.taskerino.desktop";
+
+fn entry(account: &str) -> Result<keyring::Entry, String> {
+    keyring::Entry::new(SERVICE, account).map_err(|e| format!("Failed to access keychain: {}", e))
+}
+
+/// Read a secret; None when the account has no entry
+pub fn get(account: &str) -> Result<Option<String>, String> {
+    match entry(account)?.get_password() {
+        Ok(secret) => Ok(Some(secret)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(format!("Failed to read from keychain: {}", e)),
+    }
+}
+
+/// Create or replace a secret, then read it back to confirm the store kept it
+pub fn set(account: &str, secret: &str) -> Result<(), String> {
+    entry(account)?
+        .set_password(secret)
+        .map_err(|e| format!("Failed to write to keychain: {}", e))?;
+    match get(account)? {
+        Some(stored) if stored == secret => Ok(()),
+        _ => Err(format!("Keychain did not keep the secret for {}", account)),
+    }
+}
@@ -0,0 +1,240 @@
+/**
+ * Keystroke Overlay Module
+ *
+ * For demo recordings: key combos pressed while recording (e.g. ⌃⌥⇧⌘P) are
+ * drawn on the video for a moment (VideoRecorder::show_keystroke). Keys come
+ * from a listen-only CGEvent tap (macos_events.rs) and are filtered before
+ * they leave the tap thread:
+ * - `shortcuts`: combos with ⌘ or ⌃, plus Escape and function keys
+ * - `allowlist`: only the listed shortcuts (still never plain or ⇧/⌥-only keys)
+ *
+ * Typed text is never shown in either mode, and nothing is stored.
+ * Needs Input Monitoring permission (macOS only).
+ */
+
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tauri::{AppHandle, Manager, State};
+
+use crate::command_metrics;
+use crate::macos_events;
+use crate::safe_state::SafeState;
+use crate::video_recording::VideoRecorder;
+
+// CGEventFlags bits
+const FLAG_SHIFT: u64 = 0x0002_0000;
+const FLAG_CONTROL: u64 = 0x0004_0000;
+const FLAG_OPTION: u64 = 0x0008_0000;
+const FLAG_COMMAND: u64 = 0x0010_0000;
+
+/// Modifier symbols in the order macOS menus show them
+const MODIFIERS: [(u64, char); 4] = [
+    (FLAG_CONTROL, '⌃'),
+    (FLAG_OPTION, '⌥'),
+    (FLAG_SHIFT, '⇧'),
+    (FLAG_COMMAND, '⌘'),
+];
+
+/// Which key combos are shown
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeystrokeFilter {
+    /// Combos with ⌘ or ⌃, plus Escape and function keys
+    #[default]
+    Shortcuts,
+    /// Only shortcuts listed in `allowlist`
+    Allowlist,
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeystrokeOverlaySettings {
+    #[serde(default)]
+    pub filter: KeystrokeFilter,
+    /// Shortcuts like "⌘⇧P" or "cmd+shift+p" (allowlist filter)
+    #[serde(default)]
+    pub allowlist: Vec<String>,
+}
+
+/// Key label for a layout-independent virtual keycode (US layout for characters)
+fn key_label(keycode: u16) -> Option<&'static str> {
+    Some(match keycode {
+        0 => "A", 1 => "S", 2 => "D", 3 => "F", 4 => "H", 5 => "G", 6 => "Z", 7 => "X",
+        8 => "C", 9 => "V", 11 => "B", 12 => "Q", 13 => "W", 14 => "E", 15 => "R",
+        16 => "Y", 17 => "T", 18 => "1", 19 => "2", 20 => "3", 21 => "4", 22 => "6",
+        23 => "5", 24 => "=", 25 => "9", 26 => "7", 27 => "-", 28 => "8", 29 => "0",
+        30 => "]", 31 => "O", 32 => "U", 33 => "[", 34 => "I", 35 => "P", 36 => "↩",
+        37 => "L", 38 => "J", 39 => "'", 40 => "K", 41 => ";", 42 => "\\", 43 => ",",
+        44 => "/", 45 => "N", 46 => "M", 47 => ".", 48 => "⇥", 49 => "Space", 50 => "`",
+        51 => "⌫", 53 => "⎋", 76 => "⌤", 117 => "⌦", 115 => "↖", 119 => "↘", 116 => "⇞",
+        121 => "⇟", 123 => "←", 124 => "→", 125 => "↓", 126 => "↑",
+        122 => "F1", 120 => "F2", 99 => "F3", 118 => "F4", 96 => "F5", 97 => "F6",
+        98 => "F7", 100 => "F8", 101 => "F9", 109 => "F10", 103 => "F11", 111 => "F12",
+        105 => "F13", 107 => "F14", 113 => "F15", 106 => "F16", 64 => "F17", 79 => "F18",
+        80 => "F19", 90 => "F20",
+        _ => return None,
+    })
+}
+
+/// Keys shown even without ⌘/⌃ since they never produce text
+fn is_standalone_key(label: &str) -> bool {
+    label == "⎋" || (label.len() > 1 && label.starts_with('F'))
+}
+
+/// "⌃⌥⇧⌘" prefix followed by the key label
+fn combo(modifiers: u64, label: &str) -> String {
+    let mut text: String = MODIFIERS
+        .iter()
+        .filter(|(flag, _)| modifiers & flag != 0)
+        .map(|(_, symbol)| *symbol)
+        .collect();
+    text.push_str(label);
+    text
+}
+
+/// Normalize an allowlist entry ("⌘⇧P", "cmd+shift+p", "Ctrl+Space") to `combo` form
+fn parse_combo(entry: &str) -> Option<String> {
+    let mut modifiers = 0;
+    let mut key: Option<String> = None;
+    let mut rest = entry.trim();
+
+    // Leading modifier symbols ("⌘⇧P")
+    while let Some(symbol) = rest.chars().next() {
+        match MODIFIERS.iter().find(|(_, s)| *s == symbol) {
+            Some((flag, _)) => {
+                modifiers |= flag;
+                rest = &rest[symbol.len_utf8()..];
+            }
+            None => break,
+        }
+    }
+
+    for token in rest.split('+').map(str::trim).filter(|token| !token.is_empty()) {
+        let flag = match token.to_lowercase().as_str() {
+            "cmd" | "command" | "⌘" => FLAG_COMMAND,
+            "ctrl" | "control" | "⌃" => FLAG_CONTROL,
+            "alt" | "opt" | "option" | "⌥" => FLAG_OPTION,
+            "shift" | "⇧" => FLAG_SHIFT,
+            _ => {
+                if key.is_some() {
+                    return None;
+                }
+                let label = match token.to_lowercase().as_str() {
+                    "space" => "Space".to_string(),
+                    "return" | "enter" => "↩".to_string(),
+                    "tab" => "⇥".to_string(),
+                    "esc" | "escape" => "⎋".to_string(),
+                    "delete" | "backspace" => "⌫".to_string(),
+                    "forwarddelete" => "⌦".to_string(),
+                    "left" => "←".to_string(),
+                    "right" => "→".to_string(),
+                    "up" => "↑".to_string(),
+                    "down" => "↓".to_string(),
+                    "home" => "↖".to_string(),
+                    "end" => "↘".to_string(),
+                    "pageup" => "⇞".to_string(),
+                    "pagedown" => "⇟".to_string(),
+                    _ => token.to_uppercase(),
+                };
+                key = Some(label);
+                continue;
+            }
+        };
+        modifiers |= flag;
+    }
+
+    key.map(|key| combo(modifiers, &key))
+}
+
+/// The text to show for a key press, or None if it must stay hidden
+fn visible_combo(keycode: u16, flags: u64, allowlist: Option<&HashSet<String>>) -> Option<String> {
+    let label = key_label(keycode)?;
+    let shortcut = flags & (FLAG_COMMAND | FLAG_CONTROL) != 0;
+    if !shortcut && !is_standalone_key(label) {
+        return None;
+    }
+    let text = combo(flags, label);
+    match allowlist {
+        Some(allowed) if !allowed.contains(&text) => None,
+        _ => Some(text),
+    }
+}
+
+/// Keeps the running key tap (if any)
+pub struct KeystrokeOverlay {
+    running: SafeState<Option<Arc<AtomicBool>>>,
+}
+
+impl KeystrokeOverlay {
+    pub fn new() -> Self {
+        Self {
+            running: SafeState::new("keystroke_overlay.running", None),
+        }
+    }
+
+    fn stop(&self) {
+        if let Some(running) = self.running.lock().take() {
+            running.store(false, Ordering::SeqCst);
+        }
+    }
+
+    fn start(&self, app: AppHandle, settings: KeystrokeOverlaySettings) -> Result<(), String> {
+        let allowlist = match settings.filter {
+            KeystrokeFilter::Shortcuts => None,
+            KeystrokeFilter::Allowlist => {
+                let allowed = settings
+                    .allowlist
+                    .iter()
+                    .map(|entry| parse_combo(entry).ok_or_else(|| format!("Invalid shortcut: {}", entry)))
+                    .collect::<Result<HashSet<_>, _>>()?;
+                if allowed.is_empty() {
+                    return Err("The keystroke allowlist is empty".to_string());
+                }
+                Some(allowed)
+            }
+        };
+
+        self.stop();
+        let running = Arc::new(AtomicBool::new(true));
+
+        // The tap callback must return quickly, so the recorder (whose lock can be
+        // held for seconds while stitching) is updated from a separate thread
+        let (combo_tx, combo_rx) = std::sync::mpsc::channel::<String>();
+        std::thread::spawn(move || {
+            for combo in combo_rx {
+                app.state::<Arc<SafeState<VideoRecorder>>>().lock().show_keystroke(&combo);
+            }
+        });
+
+        macos_events::start_key_tap(running.clone(), move |keycode, flags| {
+            if let Some(combo) = visible_combo(keycode, flags, allowlist.as_ref()) {
+                let _ = combo_tx.send(combo);
+            }
+        })?;
+        *self.running.lock() = Some(running);
+        Ok(())
+    }
+}
+
+/// Tauri command to turn the keystroke overlay on (with `settings`) or off
+#[tauri::command]
+pub async fn set_keystroke_overlay(
+    app: AppHandle,
+    enabled: bool,
+    settings: Option<KeystrokeOverlaySettings>,
+    overlay: State<'_, Arc<KeystrokeOverlay>>,
+) -> Result<(), String> {
+    command_metrics::track_async("set_keystroke_overlay", async move {
+        if !enabled {
+            overlay.stop();
+            println!("⌨️  Keystroke overlay disabled");
+            return Ok(());
+        }
+
+        let settings = settings.unwrap_or_default();
+        overlay.start(app, settings.clone())?;
+        println!("⌨️  Keystroke overlay enabled ({:?} filter)", settings.filter);
+        Ok(())
+    }).await
+}
@@ -1,82 +1,180 @@
 mod audio_capture;
+mod audio_encoding;
+mod audio_processing;
 mod activity_monitor;
 mod macos_events;
 mod video_recording;
+mod keystroke_overlay;
 mod api_keys;
+mod profiles;
+mod settings;
 mod ai_types;
+mod ai_budget;
+mod redaction;
+mod config_bundle;
+mod background_tasks;
+mod media_buffers;
+#[cfg(feature = "bench")]
+#[doc(hidden)]
+pub mod bench;
 mod openai_api;
 mod claude_api;
+mod ollama_api;
+mod gemini_api;
+mod ai_router;
+mod ai_cache;
 // Performance optimization modules (Task 3A)
 mod session_models;
 mod session_storage;
+mod session_index;
+mod session_encryption;
+mod keychain;
 mod attachment_loader;
+mod attachment_metadata;
+mod safe_state;
+mod event_coalescer;
+mod realtime_emitter;
+mod buffer_pool;
+mod startup_profile;
+mod ai_client;
+mod media_upload;
+mod parallel_image;
+mod command_metrics;
+mod jobs;
+mod mcp_server;
+mod screenshot_scheduler;
+mod transcription_queue;
+mod video_audio_merger;
+mod media_tools;
+mod diarization;
+mod rest_api;
+mod control_server;
+mod calendar;
+mod meeting_detector;
+mod storage_budget;
+mod disk_space;
+mod notifications;
+mod presets;
+mod recording_limits;
+mod recovery;
+mod replay_buffer;
+mod session_health;
+mod diagnostics;
+mod permissions;
+mod system_audio;
+mod export;
+mod session_archive;
+mod issue_tracker;
+mod git_monitor;
+mod privacy;
+mod ocr;
+mod annotation;
+mod thumbnails;
+mod timelapse;
+mod gif_export;
+mod backup;
+mod folder_sync;
+mod remote_archive;
+mod tray_actions;
+mod tray_icon;
+#[cfg(target_os = "macos")]
+mod automation;
 
 use tauri::{
-    menu::{Menu, MenuItem},
     tray::{TrayIcon, TrayIconBuilder},
     Emitter, Manager,
 };
 use std::process::Command;
-use screenshots::{Screen, image::{ImageFormat, DynamicImage, RgbaImage, imageops}};
+use screenshots::{Screen, image::{ImageFormat, RgbaImage}};
+use rayon::prelude::*;
 use std::io::Cursor;
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 use std::time::Duration;
 use audio_capture::AudioRecorder;
-use activity_monitor::{ActivityMonitor, ActivityMetrics};
+use activity_monitor::{ActivityMonitor, ActivityMetrics, AppFocus, AppUsageTimeline};
 use macos_events::MacOSEventMonitor;
 use video_recording::VideoRecorder;
+use keystroke_overlay::KeystrokeOverlay;
+use tray_actions::TrayMenu;
+use tray_icon::TrayIconState;
+use settings::{SettingsManager, ShortcutSettings};
+use ai_budget::BudgetManager;
+use ai_router::AiRouter;
+use ai_cache::AiCache;
+use background_tasks::TaskRegistry;
+use safe_state::SafeState;
+use event_coalescer::CoalescingEmitter;
+use realtime_emitter::RealtimeEmitter;
+use session_index::SessionIndex;
+use media_upload::UploadRegistry;
+use jobs::JobRegistry;
+use screenshot_scheduler::ScreenshotScheduler;
+use transcription_queue::TranscriptionQueue;
+use video_audio_merger::MergeQueue;
+use rest_api::ApiServer;
+use meeting_detector::MeetingDetector;
+use privacy::PrivacyGuard;
+use session_health::SessionHealthMonitor;
 
 /// Request screen recording permission on macOS
 /// This will trigger the system permission dialog if not already granted
 #[cfg(target_os = "macos")]
 #[tauri::command]
 fn request_screen_recording_permission() -> Result<bool, String> {
-    // Use CGRequestScreenCaptureAccess() to request permission
-    // This is a system function that shows the permission dialog
-    extern "C" {
-        fn CGRequestScreenCaptureAccess() -> u8;
-        fn CGPreflightScreenCaptureAccess() -> u8;
-    }
+    command_metrics::track("request_screen_recording_permission", || {
+        // Use CGRequestScreenCaptureAccess() to request permission
+        // This is a system function that shows the permission dialog
+        extern "C" {
+            fn CGRequestScreenCaptureAccess() -> u8;
+            fn CGPreflightScreenCaptureAccess() -> u8;
+        }
 
-    unsafe {
-        // Check if we already have permission
-        let has_permission = CGPreflightScreenCaptureAccess() != 0;
+        unsafe {
+            // Check if we already have permission
+            let has_permission = CGPreflightScreenCaptureAccess() != 0;
 
-        if !has_permission {
-            // Request permission - this will show the system dialog
-            let granted = CGRequestScreenCaptureAccess() != 0;
-            Ok(granted)
-        } else {
-            Ok(true)
+            if !has_permission {
+                // Request permission - this will show the system dialog
+                let granted = CGRequestScreenCaptureAccess() != 0;
+                Ok(granted)
+            } else {
+                Ok(true)
+            }
         }
-    }
+    })
 }
 
 /// Check if screen recording permission is granted (macOS only)
 #[cfg(target_os = "macos")]
 #[tauri::command]
 fn check_screen_recording_permission() -> Result<bool, String> {
-    extern "C" {
-        fn CGPreflightScreenCaptureAccess() -> u8;
-    }
+    command_metrics::track("check_screen_recording_permission", || {
+        extern "C" {
+            fn CGPreflightScreenCaptureAccess() -> u8;
+        }
 
-    unsafe {
-        Ok(CGPreflightScreenCaptureAccess() != 0)
-    }
+        unsafe {
+            Ok(CGPreflightScreenCaptureAccess() != 0)
+        }
+    })
 }
 
 /// Stub for non-macOS platforms
 #[cfg(not(target_os = "macos"))]
 #[tauri::command]
 fn request_screen_recording_permission() -> Result<bool, String> {
-    Ok(true)
+    command_metrics::track("request_screen_recording_permission", || {
+        Ok(true)
+    })
 }
 
 /// Stub for non-macOS platforms
 #[cfg(not(target_os = "macos"))]
 #[tauri::command]
 fn check_screen_recording_permission() -> Result<bool, String> {
-    Ok(true)
+    command_metrics::track("check_screen_recording_permission", || {
+        Ok(true)
+    })
 }
 
 /// Helper function for retry with exponential backoff
@@ -107,9 +205,25 @@ where
     Err(format!("Screenshot capture failed after {} attempts: {}", max_retries, last_error))
 }
 
-/// Captures the primary screen and returns base64-encoded PNG data
-#[tauri::command]
-fn capture_primary_screen() -> Result<String, String> {
+/// Run screen capture / image encoding off the IPC thread
+async fn run_capture<T, F>(operation: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, String> + Send + 'static,
+{
+    tokio::task::spawn_blocking(operation)
+        .await
+        .map_err(|e| format!("Screenshot task failed: {}", e))?
+}
+
+/// Refuse session captures while a blocked app is frontmost (privacy.rs)
+async fn ensure_capture_allowed(app: &tauri::AppHandle) -> Result<(), String> {
+    let app = app.clone();
+    run_capture(move || app.state::<Arc<PrivacyGuard>>().capture_allowed(&app)).await
+}
+
+/// Capture the primary screen as PNG bytes
+fn capture_primary_png() -> Result<Vec<u8>, String> {
     capture_with_retry(|| {
         let screens = Screen::all().map_err(|e| format!("Failed to get screens: {}", e))?;
 
@@ -128,69 +242,91 @@ fn capture_primary_screen() -> Result<String, String> {
             .write_to(&mut cursor, ImageFormat::Png)
             .map_err(|e| format!("Failed to encode PNG: {}", e))?;
 
-        // Encode to base64
+        Ok(bytes)
+    }, 3)
+}
+
+/// Captures the primary screen and returns base64-encoded PNG data
+#[tauri::command]
+async fn capture_primary_screen() -> Result<String, String> {
+    command_metrics::track_async("capture_primary_screen", async move {
+        let bytes = run_capture(capture_primary_png).await?;
         let base64_data = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &bytes);
         Ok(format!("data:image/png;base64,{}", base64_data))
-    }, 3)
+    }).await
+}
+
+/// Captures the primary screen and returns raw PNG bytes (ArrayBuffer on the frontend)
+#[tauri::command]
+async fn capture_primary_screen_bytes() -> Result<tauri::ipc::Response, String> {
+    command_metrics::track_async("capture_primary_screen_bytes", async move {
+        let bytes = run_capture(capture_primary_png).await?;
+        Ok(tauri::ipc::Response::new(bytes))
+    }).await
 }
 
 /// Captures all screens and returns an array of base64-encoded PNG data
 #[tauri::command]
-fn capture_all_screens() -> Result<Vec<String>, String> {
-    capture_with_retry(|| {
-        let screens = Screen::all().map_err(|e| format!("Failed to get screens: {}", e))?;
+async fn capture_all_screens() -> Result<Vec<String>, String> {
+    command_metrics::track_async("capture_all_screens", async move {
+        run_capture(|| capture_with_retry(|| {
+            let screens = Screen::all().map_err(|e| format!("Failed to get screens: {}", e))?;
 
-        if screens.is_empty() {
-            return Err("No screens found".to_string());
-        }
+            if screens.is_empty() {
+                return Err("No screens found".to_string());
+            }
 
-        let mut results = Vec::new();
+            let mut results = Vec::new();
 
-        for screen in screens {
-            let image = screen.capture().map_err(|e| format!("Failed to capture screen: {}", e))?;
+            for screen in screens {
+                let image = screen.capture().map_err(|e| format!("Failed to capture screen: {}", e))?;
 
-            // Convert to PNG bytes
-            let mut bytes: Vec<u8> = Vec::new();
-            let mut cursor = Cursor::new(&mut bytes);
-            image
-                .write_to(&mut cursor, ImageFormat::Png)
-                .map_err(|e| format!("Failed to encode PNG: {}", e))?;
+                // Convert to PNG bytes
+                let mut bytes: Vec<u8> = Vec::new();
+                let mut cursor = Cursor::new(&mut bytes);
+                image
+                    .write_to(&mut cursor, ImageFormat::Png)
+                    .map_err(|e| format!("Failed to encode PNG: {}", e))?;
 
-            // Encode to base64
-            let base64_data = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &bytes);
-            results.push(format!("data:image/png;base64,{}", base64_data));
-        }
+                // Encode to base64
+                let base64_data = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &bytes);
+                results.push(format!("data:image/png;base64,{}", base64_data));
+            }
 
-        Ok(results)
-    }, 3)
+            Ok(results)
+        }, 3)).await
+    }).await
 }
 
 /// Get information about available screens
 #[tauri::command]
 fn get_screen_info() -> Result<Vec<serde_json::Value>, String> {
-    let screens = Screen::all().map_err(|e| format!("Failed to get screens: {}", e))?;
-
-    let info: Vec<serde_json::Value> = screens
-        .iter()
-        .enumerate()
-        .map(|(i, screen)| {
-            let display_info = screen.display_info;
-            serde_json::json!({
-                "index": i,
-                "id": display_info.id,
-                "x": display_info.x,
-                "y": display_info.y,
-                "width": display_info.width,
-                "height": display_info.height,
-                "is_primary": display_info.is_primary,
+    command_metrics::track("get_screen_info", || {
+        let screens = Screen::all().map_err(|e| format!("Failed to get screens: {}", e))?;
+
+        let info: Vec<serde_json::Value> = screens
+            .iter()
+            .enumerate()
+            .map(|(i, screen)| {
+                let display_info = screen.display_info;
+                serde_json::json!({
+                    "index": i,
+                    "id": display_info.id,
+                    "x": display_info.x,
+                    "y": display_info.y,
+                    "width": display_info.width,
+                    "height": display_info.height,
+                    "is_primary": display_info.is_primary,
+                })
             })
-        })
-        .collect();
+            .collect();
 
-    Ok(info)
+        Ok(info)
+    })
 }
 
 // Global state for menu bar countdown
+#[derive(Clone)]
 struct CountdownState {
     active: bool,
     interval_minutes: f64,
@@ -211,8 +347,128 @@ impl CountdownState {
     }
 }
 
-type CountdownStateHandle = Arc<Mutex<CountdownState>>;
-type TrayIconHandle = Arc<Mutex<Option<TrayIcon<tauri::Wry>>>>;
+/// What the tray icon, title and menu show for one countdown tick
+#[derive(Debug, Clone, PartialEq)]
+struct TrayView {
+    text: String,
+    icon: TrayIconState,
+    pause_enabled: bool,
+    resume_enabled: bool,
+    stop_enabled: bool,
+}
+
+impl TrayView {
+    fn idle() -> Self {
+        Self {
+            text: "⚫ Taskerino".to_string(),
+            icon: TrayIconState::Idle,
+            pause_enabled: false,
+            resume_enabled: false,
+            stop_enabled: false,
+        }
+    }
+
+    fn countdown(session_status: &str, remaining_ms: i64, interval_ms: i64) -> Self {
+        let status_icon = match session_status {
+            "active" => "🟢",
+            "paused" => "🟡",
+            _ => "⚫"
+        };
+
+        let text = if session_status == "paused" {
+            format!("{} Paused", status_icon)
+        } else if remaining_ms <= 0 {
+            format!("{} Soon...", status_icon)
+        } else {
+            let remaining_secs = (remaining_ms / 1000) as u32;
+            if remaining_secs >= 60 {
+                format!("{} Next: {}m {}s", status_icon, remaining_secs / 60, remaining_secs % 60)
+            } else {
+                format!("{} Next: {}s", status_icon, remaining_secs)
+            }
+        };
+
+        let icon = match session_status {
+            "active" => TrayIconState::countdown(remaining_ms, interval_ms),
+            "paused" => TrayIconState::Paused,
+            _ => TrayIconState::Idle,
+        };
+
+        Self {
+            text,
+            icon,
+            pause_enabled: session_status == "active",
+            resume_enabled: session_status == "paused",
+            stop_enabled: matches!(session_status, "active" | "paused"),
+        }
+    }
+
+    /// Push the view to the tray (rendered icon on macOS, title elsewhere) and menu items
+    fn apply(&self, app: &tauri::AppHandle, tray: &TrayIconHandle) {
+        if let Some(tray) = tray.lock().as_ref() {
+            #[cfg(target_os = "macos")]
+            let result = tray
+                .set_icon(Some(tray_icon::render(&self.icon)))
+                .and_then(|_| tray.set_icon_as_template(true));
+            #[cfg(not(target_os = "macos"))]
+            let result = tray.set_title(Some(&self.text));
+            if let Err(e) = result {
+                println!("❌ Failed to update tray: {:?}", e);
+            }
+            let _ = tray.set_tooltip(Some(&self.text));
+        }
+
+        if let Some(menu) = app.state::<Arc<TrayMenu>>().menu() {
+            if let Some(menuitem) = menu.get("countdown").and_then(|item| item.as_menuitem().cloned()) {
+                let _ = menuitem.set_text(&self.text);
+            }
+            for (id, enabled) in [
+                ("pause", self.pause_enabled),
+                ("resume", self.resume_enabled),
+                ("stop", self.stop_enabled),
+            ] {
+                if let Some(menuitem) = menu.get(id).and_then(|item| item.as_menuitem().cloned()) {
+                    let _ = menuitem.set_enabled(enabled);
+                }
+            }
+        }
+    }
+}
+
+/// Menu bar countdown; every change wakes the tray updater, which only ticks
+/// on its own while a session is running
+struct Countdown {
+    state: SafeState<CountdownState>,
+    changed: tokio::sync::Notify,
+}
+
+impl Countdown {
+    fn new() -> Self {
+        Self {
+            state: SafeState::new("countdown", CountdownState::new()),
+            changed: tokio::sync::Notify::new(),
+        }
+    }
+
+    /// Change the state and wake the tray updater
+    fn update<R>(&self, change: impl FnOnce(&mut CountdownState) -> R) -> R {
+        let result = change(&mut self.state.lock());
+        self.changed.notify_one();
+        result
+    }
+
+    fn snapshot(&self) -> CountdownState {
+        self.state.get()
+    }
+
+    /// Wake the tray updater without a state change (e.g. activity monitoring started)
+    fn wake(&self) {
+        self.changed.notify_one();
+    }
+}
+
+type CountdownStateHandle = Arc<Countdown>;
+type TrayIconHandle = Arc<SafeState<Option<TrayIcon<tauri::Wry>>>>;
 
 /// Start menu bar countdown
 #[tauri::command]
@@ -222,18 +478,21 @@ fn start_menubar_countdown(
     last_screenshot_time: String,
     session_id: String,
 ) -> Result<(), String> {
-    println!("🚀 start_menubar_countdown called: interval={}, time={}, session={}",
-        interval_minutes, last_screenshot_time, session_id);
-
-    let mut countdown = state.lock().map_err(|e| format!("Lock error: {}", e))?;
-    countdown.active = true;
-    countdown.interval_minutes = interval_minutes;
-    countdown.last_screenshot_time = last_screenshot_time.clone();
-    countdown.session_status = "active".to_string();
-    countdown.session_id = session_id;
-
-    println!("✅ Countdown state set: active={}, status={}", countdown.active, countdown.session_status);
-    Ok(())
+    command_metrics::track("start_menubar_countdown", || {
+        println!("🚀 start_menubar_countdown called: interval={}, time={}, session={}",
+            interval_minutes, last_screenshot_time, session_id);
+
+        state.update(|countdown| {
+            countdown.active = true;
+            countdown.interval_minutes = interval_minutes;
+            countdown.last_screenshot_time = last_screenshot_time.clone();
+            countdown.session_status = "active".to_string();
+            countdown.session_id = session_id;
+        });
+
+        println!("✅ Countdown started");
+        Ok(())
+    })
 }
 
 /// Update menu bar countdown (when interval changes or screenshot taken)
@@ -244,49 +503,151 @@ fn update_menubar_countdown(
     last_screenshot_time: String,
     session_status: String,
 ) -> Result<(), String> {
-    let mut countdown = state.lock().map_err(|e| format!("Lock error: {}", e))?;
-    println!("🔄 update_menubar_countdown called: active={}, interval={}, status={}, time={}",
-        countdown.active, interval_minutes, session_status, last_screenshot_time);
-    if countdown.active {
-        countdown.interval_minutes = interval_minutes;
-        countdown.last_screenshot_time = last_screenshot_time;
-        countdown.session_status = session_status;
-        println!("✅ Updated countdown state");
-    } else {
-        println!("⚠️  Skipped update - countdown not active");
-    }
-    Ok(())
+    command_metrics::track("update_menubar_countdown", || {
+        let updated = state.update(|countdown| {
+            if countdown.active {
+                countdown.interval_minutes = interval_minutes;
+                countdown.last_screenshot_time = last_screenshot_time;
+                countdown.session_status = session_status;
+            }
+            countdown.active
+        });
+        if !updated {
+            println!("⚠️  Skipped countdown update - countdown not active");
+        }
+        Ok(())
+    })
 }
 
 /// Stop menu bar countdown
 #[tauri::command]
 fn stop_menubar_countdown(state: tauri::State<CountdownStateHandle>) -> Result<(), String> {
-    println!("🛑 stop_menubar_countdown called - setting active=false");
-    let mut countdown = state.lock().map_err(|e| format!("Lock error: {}", e))?;
-    countdown.active = false;
-    countdown.session_status = "idle".to_string();
-    countdown.session_id = String::new();
-    Ok(())
+    command_metrics::track("stop_menubar_countdown", || {
+        println!("🛑 stop_menubar_countdown called - setting active=false");
+        state.update(|countdown| {
+            countdown.active = false;
+            countdown.session_status = "idle".to_string();
+            countdown.session_id = String::new();
+        });
+        Ok(())
+    })
 }
 
 /// Audio recording commands - Real implementation
 #[tauri::command]
 fn start_audio_recording(
+    app: tauri::AppHandle,
     audio_recorder: tauri::State<Arc<AudioRecorder>>,
+    settings: tauri::State<Arc<SettingsManager>>,
     session_id: String,
-    chunk_duration_secs: u64,
+    chunk_duration_secs: Option<u64>,
+    options: Option<audio_capture::AudioRecordingOptions>,
 ) -> Result<(), String> {
-    audio_recorder.start_recording(session_id, chunk_duration_secs)
+    command_metrics::track("start_audio_recording", || {
+        let options = options.unwrap_or_default();
+        disk_space::ensure_recording_space(&app, &profiles::profile_data_dir(&app)?)?;
+        let audio_settings = settings.get().audio;
+        // Fall back to the configured chunk duration when the caller doesn't specify one
+        let chunk_duration_secs = chunk_duration_secs.unwrap_or(audio_settings.chunk_duration_secs);
+        let chunk_directory = match options.file_chunks {
+            true => Some(audio_chunks_dir(&app)?),
+            false => None,
+        };
+        audio_recorder.set_binary_chunks(options.binary_chunks);
+        audio_recorder.set_chunk_directory(chunk_directory);
+        audio_recorder.set_chunk_format(audio_settings.chunk_format);
+        audio_recorder.set_silence_threshold(audio_settings.vad_enabled.then_some(audio_settings.vad_threshold));
+        audio_recorder.set_streaming_transcription(options.streaming_transcription.unwrap_or(audio_settings.streaming_transcription));
+        audio_recorder.set_prefer_built_in_mic(audio_settings.prefer_built_in_mic);
+        audio_recorder.set_system_audio(audio_settings.system_audio);
+        audio_recorder.set_separate_tracks(audio_settings.separate_tracks);
+        audio_recorder.set_balance(audio_settings.balance);
+        audio_recorder.set_noise_suppression(audio_settings.noise_suppression);
+        audio_recorder.set_agc(audio_settings.agc_enabled);
+        audio_recorder.start_recording(session_id, chunk_duration_secs)
+    })
+}
+
+/// Audio recorder state plus media buffer memory usage
+#[tauri::command]
+fn get_audio_health_status(
+    audio_recorder: tauri::State<Arc<AudioRecorder>>,
+) -> Result<audio_capture::AudioHealthStatus, String> {
+    command_metrics::track("get_audio_health_status", || {
+        Ok(audio_recorder.health_status())
+    })
+}
+
+/// Fetch a binary audio chunk announced by an `audio-chunk` event (raw encoded bytes)
+#[tauri::command]
+fn take_audio_chunk(
+    audio_recorder: tauri::State<Arc<AudioRecorder>>,
+    chunk_id: String,
+) -> Result<tauri::ipc::Response, String> {
+    command_metrics::track("take_audio_chunk", || {
+        Ok(tauri::ipc::Response::new(audio_recorder.take_chunk(&chunk_id)?))
+    })
+}
+
+/// Root of the per-session audio chunk directories (file chunk mode)
+fn audio_chunks_dir(app: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
+    Ok(profiles::profile_data_dir(app)?.join("audio-chunks"))
+}
+
+/// Read a chunk file written in file chunk mode (raw bytes)
+#[tauri::command]
+async fn read_audio_chunk_file(
+    app: tauri::AppHandle,
+    path: String,
+) -> Result<tauri::ipc::Response, String> {
+    command_metrics::track_async("read_audio_chunk_file", async move {
+        // Only files inside the audio chunk directory can be read
+        let root = tokio::fs::canonicalize(audio_chunks_dir(&app)?)
+            .await
+            .map_err(|e| format!("Failed to resolve audio chunk directory: {}", e))?;
+        let path = tokio::fs::canonicalize(&path)
+            .await
+            .map_err(|e| format!("Failed to resolve audio chunk path: {}", e))?;
+        if !path.starts_with(&root) {
+            return Err("Audio chunk path is outside the audio chunk directory".to_string());
+        }
+
+        let bytes = tokio::fs::read(&path)
+            .await
+            .map_err(|e| format!("Failed to read audio chunk: {}", e))?;
+        Ok(tauri::ipc::Response::new(bytes))
+    }).await
 }
 
 #[tauri::command]
 fn stop_audio_recording(audio_recorder: tauri::State<Arc<AudioRecorder>>) -> Result<(), String> {
-    audio_recorder.stop_recording()
+    command_metrics::track("stop_audio_recording", || {
+        audio_recorder.stop_recording()
+    })
 }
 
 #[tauri::command]
 fn pause_audio_recording(audio_recorder: tauri::State<Arc<AudioRecorder>>) -> Result<(), String> {
-    audio_recorder.pause_recording()
+    command_metrics::track("pause_audio_recording", || {
+        audio_recorder.pause_recording()
+    })
+}
+
+/// Resume a paused recording; the pause is reported in the next `audio-chunk`
+#[tauri::command]
+fn resume_audio_recording(audio_recorder: tauri::State<Arc<AudioRecorder>>) -> Result<(), String> {
+    command_metrics::track("resume_audio_recording", || {
+        audio_recorder.resume_recording()
+    })
+}
+
+/// Toggle mic noise suppression; takes effect immediately while recording
+#[tauri::command]
+fn set_noise_suppression(audio_recorder: tauri::State<Arc<AudioRecorder>>, enabled: bool) -> Result<(), String> {
+    command_metrics::track("set_noise_suppression", || {
+        audio_recorder.set_noise_suppression(enabled);
+        Ok(())
+    })
 }
 
 /// Activity monitoring commands
@@ -294,14 +655,19 @@ fn pause_audio_recording(audio_recorder: tauri::State<Arc<AudioRecorder>>) -> Re
 fn start_activity_monitoring(
     monitor: tauri::State<Arc<ActivityMonitor>>,
     event_monitor: tauri::State<Arc<MacOSEventMonitor>>,
+    countdown: tauri::State<CountdownStateHandle>,
 ) -> Result<(), String> {
-    // Start the base monitor
-    monitor.start_monitoring()?;
+    command_metrics::track("start_activity_monitoring", || {
+        // Start the base monitor
+        monitor.start_monitoring()?;
 
-    // Start macOS event monitoring
-    event_monitor.start()?;
+        // Start macOS event monitoring
+        event_monitor.start()?;
 
-    Ok(())
+        // Activity stats are emitted by the tray updater's tick
+        countdown.wake();
+        Ok(())
+    })
 }
 
 #[tauri::command]
@@ -309,13 +675,15 @@ fn stop_activity_monitoring(
     monitor: tauri::State<Arc<ActivityMonitor>>,
     event_monitor: tauri::State<Arc<MacOSEventMonitor>>,
 ) -> Result<(), String> {
-    // Stop macOS event monitoring
-    event_monitor.stop()?;
+    command_metrics::track("stop_activity_monitoring", || {
+        // Stop macOS event monitoring
+        event_monitor.stop()?;
 
-    // Stop the base monitor
-    monitor.stop_monitoring()?;
+        // Stop the base monitor
+        monitor.stop_monitoring()?;
 
-    Ok(())
+        Ok(())
+    })
 }
 
 #[tauri::command]
@@ -323,34 +691,147 @@ fn get_activity_metrics(
     monitor: tauri::State<Arc<ActivityMonitor>>,
     window_seconds: u64
 ) -> Result<ActivityMetrics, String> {
-    Ok(monitor.get_metrics(window_seconds))
+    command_metrics::track("get_activity_metrics", || {
+        Ok(monitor.get_metrics(window_seconds))
+    })
 }
 
+/// Per-app time for a session (its stored appTimeline) or for the last
+/// `window_seconds` of the current monitoring run
 #[tauri::command]
-fn record_app_switch(monitor: tauri::State<Arc<ActivityMonitor>>) {
-    monitor.increment_app_switch()
+fn get_app_usage_timeline(
+    app: tauri::AppHandle,
+    monitor: tauri::State<Arc<ActivityMonitor>>,
+    session_id: Option<String>,
+    window_seconds: Option<u64>,
+) -> Result<AppUsageTimeline, String> {
+    command_metrics::track("get_app_usage_timeline", || {
+        let Some(session_id) = session_id else {
+            let window_seconds = window_seconds.ok_or("Pass a session id or a time window")?;
+            return Ok(monitor.app_usage(window_seconds));
+        };
+
+        let session = session_storage::read_session_value(&profiles::profile_data_dir(&app)?, &session_id)?;
+        let time = |key: &str| {
+            session
+                .get(key)
+                .and_then(|value| value.as_str())
+                .and_then(|value| chrono::DateTime::parse_from_rfc3339(value).ok())
+                .map(|value| value.with_timezone(&chrono::Utc))
+        };
+        let start = time("startTime").ok_or_else(|| format!("Session {} has no start time", session_id))?;
+        let end = time("endTime");
+
+        let mut timeline: Vec<AppFocus> = session
+            .get("appTimeline")
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+            .unwrap_or_default();
+        if end.is_none() && monitor.is_monitoring() {
+            // Still running: add focus changes the frontend hasn't saved yet
+            let saved_until = timeline.iter().map(|focus| focus.timestamp.clone()).max();
+            timeline.extend(
+                monitor
+                    .focus_history()
+                    .into_iter()
+                    .filter(|focus| saved_until.as_ref().map_or(true, |saved| focus.timestamp > *saved)),
+            );
+        }
+        Ok(activity_monitor::app_usage(&timeline, start, end.unwrap_or_else(chrono::Utc::now)))
+    })
 }
 
 #[tauri::command]
-fn record_mouse_click(monitor: tauri::State<Arc<ActivityMonitor>>) {
-    monitor.increment_mouse_click()
+fn record_app_switch(monitor: tauri::State<Arc<ActivityMonitor>>) -> Result<(), String> {
+    command_metrics::track("record_app_switch", || {
+        monitor.increment_app_switch();
+        Ok(())
+    })
 }
 
 #[tauri::command]
-fn record_keyboard_event(monitor: tauri::State<Arc<ActivityMonitor>>) {
-    monitor.increment_keyboard_event()
+fn record_mouse_click(monitor: tauri::State<Arc<ActivityMonitor>>) -> Result<(), String> {
+    command_metrics::track("record_mouse_click", || {
+        monitor.increment_mouse_click();
+        Ok(())
+    })
 }
 
 #[tauri::command]
-fn record_window_focus(monitor: tauri::State<Arc<ActivityMonitor>>) {
-    monitor.increment_window_focus()
+fn record_keyboard_event(monitor: tauri::State<Arc<ActivityMonitor>>) -> Result<(), String> {
+    command_metrics::track("record_keyboard_event", || {
+        monitor.increment_keyboard_event();
+        Ok(())
+    })
 }
 
-/// Captures all screens and composites them into a single compressed JPEG image
 #[tauri::command]
-fn capture_all_screens_composite() -> Result<String, String> {
+fn record_window_focus(monitor: tauri::State<Arc<ActivityMonitor>>) -> Result<(), String> {
+    command_metrics::track("record_window_focus", || {
+        monitor.increment_window_focus();
+        Ok(())
+    })
+}
+
+/// Downscale a composite to the configured maximum size and compress to JPEG
+fn encode_composite_jpeg(
+    composite: RgbaImage,
+    screenshot_settings: &settings::ScreenshotSettings,
+) -> Result<Vec<u8>, String> {
     use image::codecs::jpeg::JpegEncoder;
 
+    let max_width = screenshot_settings.max_width;
+    let max_height = screenshot_settings.max_height;
+
+    // Resize if too large (default max 1920x1080 to keep file size reasonable)
+    let (width, height) = if composite.width() > max_width || composite.height() > max_height {
+        let scale = f32::min(max_width as f32 / composite.width() as f32, max_height as f32 / composite.height() as f32);
+        ((composite.width() as f32 * scale) as u32, (composite.height() as f32 * scale) as u32)
+    } else {
+        composite.dimensions()
+    };
+
+    // Resize and/or convert RGBA to RGB (JPEG doesn't support alpha channel) into a
+    // pooled buffer, parallelized across rows
+    let pool = buffer_pool::pool();
+    let mut rgb = pool.take(width as usize * height as usize * 3);
+    if (width, height) == composite.dimensions() {
+        parallel_image::rgba_to_rgb(&composite, &mut rgb);
+    } else {
+        parallel_image::resize_to_rgb(&composite, width, height, &mut rgb);
+    }
+    pool.recycle(composite.into_raw());
+
+    // Compress to JPEG (default quality 70, optimized for 17% file size reduction)
+    let mut bytes = pool.encode_buffer();
+    let mut encoder = JpegEncoder::new_with_quality(&mut bytes, screenshot_settings.jpeg_quality);
+    let encoded = encoder.encode(&rgb, width, height, image::ColorType::Rgb8.into());
+    pool.recycle(rgb);
+    encoded.map_err(|e| format!("Failed to encode JPEG: {}", e))?;
+
+    pool.record_encoded_len(bytes.len());
+    Ok(bytes)
+}
+
+/// Bounding box of all displays: (min_x, min_y, width, height)
+fn composite_bounds(screens: &[Screen]) -> (i32, i32, u32, u32) {
+    let mut min_x = i32::MAX;
+    let mut min_y = i32::MAX;
+    let mut max_x = i32::MIN;
+    let mut max_y = i32::MIN;
+
+    for screen in screens {
+        let info = screen.display_info;
+        min_x = min_x.min(info.x);
+        min_y = min_y.min(info.y);
+        max_x = max_x.max(info.x + info.width as i32);
+        max_y = max_y.max(info.y + info.height as i32);
+    }
+
+    (min_x, min_y, (max_x - min_x) as u32, (max_y - min_y) as u32)
+}
+
+/// Capture all screens, composite them and compress to JPEG bytes
+pub(crate) fn capture_composite_jpeg(screenshot_settings: settings::ScreenshotSettings) -> Result<Vec<u8>, String> {
     capture_with_retry(|| {
         let screens = Screen::all().map_err(|e| format!("Failed to get screens: {}", e))?;
 
@@ -364,77 +845,170 @@ fn capture_all_screens_composite() -> Result<String, String> {
             screens[0].capture().map_err(|e| format!("Failed to capture screen: {}", e))?
         } else {
             // Multiple screens - find bounding box
-            let mut min_x = i32::MAX;
-            let mut min_y = i32::MAX;
-            let mut max_x = i32::MIN;
-            let mut max_y = i32::MIN;
-
-            for screen in &screens {
-                let info = screen.display_info;
-                min_x = min_x.min(info.x);
-                min_y = min_y.min(info.y);
-                max_x = max_x.max(info.x + info.width as i32);
-                max_y = max_y.max(info.y + info.height as i32);
-            }
-
-            let composite_width = (max_x - min_x) as u32;
-            let composite_height = (max_y - min_y) as u32;
-
-            // Create composite image
-            let mut composite = RgbaImage::new(composite_width, composite_height);
-
-            // Capture and place each screen
-            for screen in screens {
-                let image = screen.capture().map_err(|e| format!("Failed to capture screen: {}", e))?;
-                let info = screen.display_info;
+            let (min_x, min_y, composite_width, composite_height) = composite_bounds(&screens);
+
+            // Create composite image on a pooled (zeroed) buffer
+            let pixels = buffer_pool::pool().take(composite_width as usize * composite_height as usize * 4);
+            let mut composite = RgbaImage::from_raw(composite_width, composite_height, pixels)
+                .ok_or("Failed to allocate composite image")?;
+
+            // Capture every screen concurrently, then place each one
+            let captures = screens
+                .par_iter()
+                .map(|screen| {
+                    screen.capture()
+                        .map(|image| (screen.display_info, image))
+                        .map_err(|e| format!("Failed to capture screen: {}", e))
+                })
+                .collect::<Result<Vec<_>, String>>()?;
 
+            for (info, image) in captures {
                 // Calculate position in composite
                 let x_offset = (info.x - min_x) as u32;
                 let y_offset = (info.y - min_y) as u32;
 
-                // Convert to RgbaImage and overlay
-                let rgba_image = DynamicImage::ImageRgba8(image).to_rgba8();
-                imageops::overlay(&mut composite, &rgba_image, x_offset as i64, y_offset as i64);
+                parallel_image::blit(&mut composite, &image, x_offset, y_offset);
+                buffer_pool::pool().recycle(image.into_raw());
             }
 
             composite
         };
 
-        // Resize if too large (max 1920x1080 to keep file size reasonable)
-        let resized = if composite.width() > 1920 || composite.height() > 1080 {
-            let scale = f32::min(1920.0 / composite.width() as f32, 1080.0 / composite.height() as f32);
-            let new_width = (composite.width() as f32 * scale) as u32;
-            let new_height = (composite.height() as f32 * scale) as u32;
+        encode_composite_jpeg(composite, &screenshot_settings)
+    }, 3)
+}
 
-            imageops::resize(&composite, new_width, new_height, imageops::FilterType::Lanczos3)
-        } else {
-            composite
+/// Captures all screens and composites them into a single compressed JPEG image
+#[tauri::command]
+async fn capture_all_screens_composite(
+    app: tauri::AppHandle,
+    settings: tauri::State<'_, Arc<SettingsManager>>,
+) -> Result<String, String> {
+    command_metrics::track_async("capture_all_screens_composite", async move {
+        ensure_capture_allowed(&app).await?;
+        let screenshot_settings = settings.get().screenshots;
+        let bytes = run_capture(move || capture_composite_jpeg(screenshot_settings)).await?;
+
+        // Encode to base64
+        let base64_data = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &bytes);
+        Ok(format!("data:image/jpeg;base64,{}", base64_data))
+    }).await
+}
+
+/// Same as `capture_all_screens_composite` but returns raw JPEG bytes,
+/// avoiding the ~33% base64 overhead and an extra copy on both sides of IPC
+#[tauri::command]
+async fn capture_all_screens_composite_bytes(
+    app: tauri::AppHandle,
+    settings: tauri::State<'_, Arc<SettingsManager>>,
+) -> Result<tauri::ipc::Response, String> {
+    command_metrics::track_async("capture_all_screens_composite_bytes", async move {
+        ensure_capture_allowed(&app).await?;
+        let screenshot_settings = settings.get().screenshots;
+        let bytes = run_capture(move || capture_composite_jpeg(screenshot_settings)).await?;
+        Ok(tauri::ipc::Response::new(bytes))
+    }).await
+}
+
+/// Encoding for region captures
+#[derive(Debug, Clone, Copy, Default, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum RegionImageFormat {
+    #[default]
+    Png,
+    Jpeg,
+}
+
+/// Capture a region of one display (coordinates relative to the display's
+/// top-left corner; the primary display when `display_id` is None)
+fn capture_region(
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+    display_id: Option<u32>,
+    format: RegionImageFormat,
+    screenshot_settings: settings::ScreenshotSettings,
+) -> Result<Vec<u8>, String> {
+    if width == 0 || height == 0 {
+        return Err("Region width and height must be greater than zero".to_string());
+    }
+
+    let image = capture_with_retry(|| {
+        let screens = Screen::all().map_err(|e| format!("Failed to get screens: {}", e))?;
+
+        let screen = match display_id {
+            Some(id) => screens
+                .iter()
+                .find(|screen| screen.display_info.id == id)
+                .ok_or_else(|| format!("Display {} not found", id))?,
+            None => screens
+                .iter()
+                .find(|screen| screen.display_info.is_primary)
+                .or(screens.first())
+                .ok_or("No screens found")?,
         };
 
-        // Convert RGBA to RGB (JPEG doesn't support alpha channel)
-        let rgb_image = DynamicImage::ImageRgba8(resized).to_rgb8();
+        screen
+            .capture_area(x, y, width, height)
+            .map_err(|e| format!("Failed to capture screen region: {}", e))
+    }, 3)?;
 
-        // Compress to JPEG with quality 70 (optimized for 17% file size reduction)
-        let mut bytes: Vec<u8> = Vec::new();
-        let mut encoder = JpegEncoder::new_with_quality(&mut bytes, 70);
-        encoder.encode(
-            &rgb_image,
-            rgb_image.width(),
-            rgb_image.height(),
-            image::ColorType::Rgb8.into(),
-        ).map_err(|e| format!("Failed to encode JPEG: {}", e))?;
+    match format {
+        RegionImageFormat::Png => {
+            let mut bytes: Vec<u8> = Vec::new();
+            image
+                .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+                .map_err(|e| format!("Failed to encode PNG: {}", e))?;
+            Ok(bytes)
+        }
+        RegionImageFormat::Jpeg => encode_composite_jpeg(image, &screenshot_settings),
+    }
+}
 
-        // Encode to base64
+/// Captures a region of a display and returns base64-encoded PNG (default)
+/// or JPEG data, so session screenshots can be limited to part of a monitor
+#[tauri::command]
+async fn capture_screen_region(
+    app: tauri::AppHandle,
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+    display_id: Option<u32>,
+    format: Option<RegionImageFormat>,
+) -> Result<String, String> {
+    command_metrics::track_async("capture_screen_region", async move {
+        ensure_capture_allowed(&app).await?;
+        let screenshot_settings = app.state::<Arc<SettingsManager>>().get().screenshots;
+        let format = format.unwrap_or_default();
+        let bytes = run_capture(move || {
+            capture_region(x, y, width, height, display_id, format, screenshot_settings)
+        }).await?;
+
+        let mime_type = match format {
+            RegionImageFormat::Png => "image/png",
+            RegionImageFormat::Jpeg => "image/jpeg",
+        };
         let base64_data = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &bytes);
-        Ok(format!("data:image/jpeg;base64,{}", base64_data))
-    }, 3)
+        Ok(format!("data:{};base64,{}", mime_type, base64_data))
+    }).await
+}
+
+/// Serve the MCP session tools over stdio instead of starting the app (`--mcp`)
+pub fn run_mcp_server() {
+    mcp_server::run_stdio();
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // Start the startup clock before anything else is initialized
+    let startup = startup_profile::profiler();
+
     // Initialize countdown state and tray icon handle
-    let countdown_state: CountdownStateHandle = Arc::new(Mutex::new(CountdownState::new()));
-    let tray_icon_handle: TrayIconHandle = Arc::new(Mutex::new(None));
+    let countdown_state: CountdownStateHandle = Arc::new(Countdown::new());
+    let tray_icon_handle: TrayIconHandle = Arc::new(SafeState::new("tray_icon", None));
+    let tray_menu = Arc::new(TrayMenu::new());
 
     // Initialize audio recorder
     let audio_recorder = Arc::new(AudioRecorder::new());
@@ -446,9 +1020,63 @@ pub fn run() {
     let macos_event_monitor = Arc::new(MacOSEventMonitor::new(activity_monitor.clone()));
 
     // Initialize video recorder
-    let video_recorder = Arc::new(Mutex::new(VideoRecorder::new()));
+    let video_recorder = Arc::new(SafeState::new("video_recorder", VideoRecorder::new()));
+    let keystroke_overlay = Arc::new(KeystrokeOverlay::new());
 
-    tauri::Builder::default()
+    // Initialize settings (loaded from disk in setup)
+    let settings_manager = Arc::new(SettingsManager::new());
+
+    // Initialize AI spend tracking / budgets (loaded from disk in setup)
+    let budget_manager = Arc::new(BudgetManager::new());
+
+    // Initialize provider routing for ai_chat_completion
+    let ai_router = Arc::new(AiRouter::new());
+
+    // Initialize the vision response cache (loaded from disk on first use)
+    let ai_cache = Arc::new(AiCache::new());
+
+    // Initialize background task registry (runtime started in setup)
+    let task_registry = Arc::new(TaskRegistry::new());
+
+    // Initialize coalescing emitter for high-frequency events (flush loop started in setup)
+    let event_coalescer = Arc::new(CoalescingEmitter::new());
+
+    // Initialize latest-value emitter for real-time readings (delivery loop started in setup)
+    let realtime_emitter = Arc::new(RealtimeEmitter::new());
+
+    // Initialize in-memory session index (built by a background watcher started in setup)
+    let session_index = Arc::new(SessionIndex::new());
+
+    // Initialize streamed media uploads
+    let upload_registry = Arc::new(UploadRegistry::new());
+    let job_registry = Arc::new(JobRegistry::new());
+
+    // Initialize native screenshot scheduling (thread started in setup)
+    let screenshot_scheduler = Arc::new(ScreenshotScheduler::new());
+    let screenshot_scheduler_for_exit = screenshot_scheduler.clone();
+
+    // Initialize the persistent transcription queue (worker started in setup)
+    let transcription_queue = Arc::new(TranscriptionQueue::new());
+
+    // Initialize the video/audio merge queue (worker started in setup)
+    let merge_queue = Arc::new(MergeQueue::new());
+
+    // Initialize the localhost REST API (started in setup when enabled in settings)
+    let api_server = Arc::new(ApiServer::new());
+
+    // Initialize meeting detection (polling task started in setup)
+    let meeting_detector = Arc::new(MeetingDetector::new());
+    let privacy_guard = Arc::new(PrivacyGuard::new());
+
+    // Initialize the replay buffer (started in setup when enabled in settings)
+    let replay_buffer = Arc::new(replay_buffer::ReplayBuffer::new());
+
+    // Initialize session health reports (task started in setup)
+    let session_health = Arc::new(SessionHealthMonitor::new());
+
+    let task_registry_for_exit = task_registry.clone();
+
+    let app = tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
@@ -456,68 +1084,399 @@ pub fn run() {
         .plugin(tauri_plugin_window_state::Builder::default().build())
         .manage(countdown_state.clone())
         .manage(tray_icon_handle.clone())
+        .manage(tray_menu.clone())
         .manage(audio_recorder.clone())
         .manage(activity_monitor.clone())
         .manage(macos_event_monitor.clone())
         .manage(video_recorder.clone())
-        .invoke_handler(tauri::generate_handler![
-            capture_primary_screen,
-            capture_all_screens,
-            capture_all_screens_composite,
-            get_screen_info,
-            request_screen_recording_permission,
-            check_screen_recording_permission,
-            start_menubar_countdown,
-            update_menubar_countdown,
-            stop_menubar_countdown,
-            start_audio_recording,
-            stop_audio_recording,
-            pause_audio_recording,
-            start_activity_monitoring,
-            stop_activity_monitoring,
-            get_activity_metrics,
-            record_app_switch,
-            record_mouse_click,
-            record_keyboard_event,
-            record_window_focus,
-            video_recording::start_video_recording,
-            video_recording::stop_video_recording,
-            video_recording::is_recording,
-            video_recording::get_current_recording_session,
-            video_recording::get_video_duration,
-            video_recording::generate_video_thumbnail,
-            // API key management
-            api_keys::set_openai_api_key,
-            api_keys::get_openai_api_key,
-            api_keys::set_claude_api_key,
-            api_keys::get_claude_api_key,
-            api_keys::has_openai_api_key,
-            api_keys::has_claude_api_key,
-            // OpenAI API
-            openai_api::openai_transcribe_audio,
-            openai_api::openai_transcribe_audio_with_timestamps,
-            openai_api::openai_analyze_full_audio,
-            // Claude API
-            claude_api::claude_chat_completion,
-            claude_api::claude_chat_completion_vision,
-            claude_api::claude_chat_completion_stream,
-            // Performance optimization - Session storage (Task 3A)
-            session_storage::load_session_summaries,
-            session_storage::load_session_detail,
-            session_storage::search_sessions,
-            session_storage::get_session_count,
-            // Performance optimization - Attachment loader (Task 3A)
-            attachment_loader::load_attachments_metadata_parallel,
-            attachment_loader::check_attachments_exist,
-            attachment_loader::get_attachments_total_size,
-            attachment_loader::count_attachments_by_type
-        ])
+        .manage(keystroke_overlay)
+        .manage(settings_manager.clone())
+        .manage(budget_manager.clone())
+        .manage(ai_router.clone())
+        .manage(ai_cache.clone())
+        .manage(task_registry.clone())
+        .manage(event_coalescer.clone())
+        .manage(realtime_emitter.clone())
+        .manage(session_index.clone())
+        .manage(upload_registry.clone())
+        .manage(job_registry.clone())
+        .manage(screenshot_scheduler.clone())
+        .manage(transcription_queue.clone())
+        .manage(merge_queue.clone())
+        .manage(api_server.clone())
+        .manage(meeting_detector.clone())
+        .manage(privacy_guard.clone())
+        .manage(replay_buffer)
+        .manage(session_health.clone())
+        .invoke_handler({
+            let handler: fn(tauri::ipc::Invoke) -> bool = tauri::generate_handler![
+                capture_primary_screen,
+                capture_all_screens,
+                capture_all_screens_composite,
+                capture_all_screens_composite_bytes,
+                capture_primary_screen_bytes,
+                capture_screen_region,
+                get_screen_info,
+                request_screen_recording_permission,
+                check_screen_recording_permission,
+                permissions::get_permission_status,
+                permissions::get_all_permissions,
+                permissions::request_permission,
+                permissions::open_permission_settings,
+                start_menubar_countdown,
+                update_menubar_countdown,
+                stop_menubar_countdown,
+                tray_actions::add_tray_action,
+                tray_actions::remove_tray_action,
+                tray_actions::list_tray_actions,
+                start_audio_recording,
+                stop_audio_recording,
+                pause_audio_recording,
+                resume_audio_recording,
+                set_noise_suppression,
+                take_audio_chunk,
+                read_audio_chunk_file,
+                get_audio_health_status,
+                session_health::get_session_health,
+                start_activity_monitoring,
+                stop_activity_monitoring,
+                get_activity_metrics,
+                get_app_usage_timeline,
+                record_app_switch,
+                record_mouse_click,
+                record_keyboard_event,
+                record_window_focus,
+                video_recording::start_video_recording,
+                video_recording::start_multi_source_recording,
+                video_recording::stop_video_recording,
+                video_recording::pause_video_recording,
+                video_recording::resume_video_recording,
+                video_recording::is_video_recording_paused,
+                video_recording::set_adaptive_framerate,
+                video_recording::set_segment_duration,
+                video_recording::switch_display,
+                keystroke_overlay::set_keystroke_overlay,
+                video_recording::is_recording,
+                video_recording::get_current_recording_session,
+                video_recording::get_video_duration,
+                video_recording::generate_video_thumbnail,
+                video_recording::extract_video_clip,
+                video_recording::get_display_recordings,
+                video_recording::get_recording_segments,
+                // Replay buffer
+                replay_buffer::save_replay_buffer,
+                replay_buffer::get_replay_buffer_status,
+                timelapse::generate_session_timelapse,
+                gif_export::export_gif,
+                // API key management
+                api_keys::set_openai_api_key,
+                api_keys::get_openai_api_key,
+                api_keys::set_claude_api_key,
+                api_keys::get_claude_api_key,
+                api_keys::has_openai_api_key,
+                api_keys::has_claude_api_key,
+                api_keys::set_gemini_api_key,
+                api_keys::get_gemini_api_key,
+                api_keys::has_gemini_api_key,
+                api_keys::get_api_key_source,
+                api_keys::migrate_api_keys_to_keychain,
+                // Settings
+                settings::get_settings,
+                settings::update_settings,
+                settings::reset_settings,
+                // Configuration export/import
+                config_bundle::export_configuration,
+                config_bundle::import_configuration,
+                // Profiles / workspaces
+                profiles::list_profiles,
+                profiles::get_active_profile,
+                profiles::save_profile,
+                profiles::delete_profile,
+                profiles::switch_profile,
+                // Session presets
+                presets::save_preset,
+                presets::list_presets,
+                presets::delete_preset,
+                presets::start_session_from_preset,
+                // Background tasks
+                background_tasks::list_background_tasks,
+                // Cancellable jobs
+                jobs::list_jobs,
+                jobs::list_active_jobs,
+                jobs::cancel_job,
+                event_coalescer::ack_coalesced_events,
+                realtime_emitter::ack_realtime_event,
+                // Startup profiling
+                startup_profile::get_startup_profile,
+                // AI spending budgets
+                ai_budget::get_ai_spending,
+                ai_budget::set_ai_budget,
+                ai_budget::override_ai_budget,
+                ai_budget::reset_ai_spending,
+                // OpenAI API
+                openai_api::openai_transcribe_audio,
+                openai_api::openai_transcribe_audio_with_timestamps,
+                openai_api::openai_analyze_full_audio,
+                // Background transcription queue
+                transcription_queue::enqueue_transcription,
+                transcription_queue::get_transcription_queue,
+                transcription_queue::cancel_session_transcriptions,
+                // Video/audio merge queue
+                video_audio_merger::merge_video_and_audio,
+                video_audio_merger::get_merge_progress,
+                video_audio_merger::list_merge_jobs,
+                video_audio_merger::cancel_merge,
+                // Media tools (ffmpeg lookup, audio concatenation)
+                media_tools::get_media_tools,
+                media_tools::concatenate_audio_files,
+                // Speaker diarization
+                diarization::diarize_session_audio,
+                // Localhost REST API
+                rest_api::start_api_server,
+                rest_api::stop_api_server,
+                rest_api::set_api_server_port,
+                rest_api::get_api_server_status,
+                rest_api::get_api_server_token,
+                rest_api::regenerate_api_server_token,
+                // Calendar (meeting auto-start)
+                calendar::get_calendar_permission,
+                calendar::request_calendar_permission,
+                calendar::get_upcoming_calendar_events,
+                // Native notifications
+                notifications::get_notification_permission,
+                notifications::request_notification_permission,
+                notifications::send_notification,
+                // Meeting detection / auto-record
+                meeting_detector::get_auto_record_policy,
+                meeting_detector::set_auto_record_policy,
+                meeting_detector::get_detected_meeting,
+                // Privacy blocklist
+                privacy::set_privacy_blocklist,
+                privacy::get_privacy_status,
+                // Screenshot OCR
+                ocr::extract_text_from_screenshot,
+                // Screenshot annotation
+                annotation::annotate_screenshot,
+                // Attachment thumbnails
+                thumbnails::get_attachment_thumbnail,
+                thumbnails::clear_attachment_thumbnails,
+                // Backups
+                backup::create_backup,
+                backup::list_backups,
+                backup::restore_backup,
+                // Folder sync
+                folder_sync::sync_sessions_now,
+                folder_sync::resolve_sync_conflict,
+                // Remote archive
+                remote_archive::set_remote_archive_secret,
+                remote_archive::test_remote_archive,
+                remote_archive::offload_old_sessions,
+                // Storage budget / media rotation, disk space
+                storage_budget::get_storage_budget,
+                storage_budget::set_storage_budget,
+                disk_space::get_disk_space,
+                // Crash recovery
+                recovery::get_recovered_sessions,
+                recovery::dismiss_recovered_session,
+                // Claude API
+                claude_api::claude_chat_completion,
+                claude_api::claude_chat_completion_vision,
+                claude_api::claude_chat_completion_stream,
+                ai_cache::clear_ai_cache,
+                // Provider-independent AI (routing + fallback)
+                ai_router::ai_chat_completion,
+                ai_router::get_ai_provider_usage,
+                // Gemini API
+                gemini_api::gemini_chat_completion,
+                gemini_api::gemini_chat_completion_vision,
+                gemini_api::gemini_chat_completion_stream,
+                // Ollama (local LLM)
+                ollama_api::ollama_chat_completion,
+                ollama_api::ollama_chat_completion_stream,
+                // Performance optimization - Session storage (Task 3A)
+                session_storage::load_session_summaries,
+                session_storage::load_session_detail,
+                session_storage::search_sessions,
+                session_storage::query_sessions,
+                session_storage::get_session_count,
+                session_storage::migrate_attachment_store,
+                session_storage::compact_sessions,
+                session_storage::load_sessions_store,
+                session_storage::save_sessions_store,
+                session_storage::log_session_changes,
+                // Session export
+                export::export_session_html,
+                export::export_session_pdf,
+                export::export_session_markdown,
+                export::auto_export_session,
+                export::export_session_subtitles,
+                session_archive::export_session_archive,
+                session_archive::import_session_archive,
+                // Issue tracker (Linear / Jira)
+                issue_tracker::create_tasks_from_session,
+                issue_tracker::set_issue_tracker_token,
+                issue_tracker::has_issue_tracker_token,
+                // Git activity during sessions
+                git_monitor::add_watched_repo,
+                git_monitor::remove_watched_repo,
+                // Session encryption at rest
+                session_encryption::get_session_encryption_status,
+                session_encryption::enable_session_encryption,
+                session_encryption::disable_session_encryption,
+                session_encryption::migrate_sessions_to_encrypted,
+                // Performance optimization - Attachment loader (Task 3A)
+                attachment_loader::load_attachments_metadata_parallel,
+                attachment_loader::check_attachments_exist,
+                attachment_loader::get_attachments_total_size,
+                attachment_loader::count_attachments_by_type,
+                // Attachment metadata extraction
+                attachment_metadata::extract_attachments_metadata,
+                attachment_metadata::extract_file_metadata,
+                // Streamed media uploads
+                media_upload::begin_media_upload,
+                media_upload::append_media_upload,
+                media_upload::finish_media_upload,
+                media_upload::abort_media_upload,
+                // Native screenshot scheduling
+                screenshot_scheduler::start_screenshot_scheduler,
+                screenshot_scheduler::stop_screenshot_scheduler,
+                screenshot_scheduler::pause_screenshot_scheduler,
+                screenshot_scheduler::resume_screenshot_scheduler,
+                screenshot_scheduler::set_screenshot_interval,
+                screenshot_scheduler::get_screenshot_scheduler_status,
+                // Diagnostics
+                diagnostics::run_diagnostics,
+                // Command metrics
+                command_metrics::get_command_stats
+            ];
+            move |invoke| {
+                command_metrics::registry().record_payload(
+                    invoke.message.command(),
+                    command_metrics::payload_size(invoke.message.payload()),
+                );
+                handler(invoke)
+            }
+        })
         .setup(move |app| {
+            startup.mark("builder + plugins");
+
+            // Let media_tools find an ffmpeg bundled in the app resources
+            media_tools::init(app.handle());
+
+            // Clear temp files from uploads interrupted by a previous run
+            let stale_uploads = upload_registry.clone();
+            std::thread::spawn(move || stale_uploads.clean_stale());
+
+            // Move plaintext API keys into the keychain (no-op once migrated)
+            let migration_handle = app.handle().clone();
+            std::thread::spawn(move || {
+                if let Err(e) = api_keys::migrate_to_keychain(&migration_handle) {
+                    eprintln!("Failed to migrate API keys to keychain: {}", e);
+                }
+            });
+
             // Initialize audio recorder with app handle
             if let Err(e) = audio_recorder.init(app.handle().clone()) {
                 eprintln!("Failed to initialize audio recorder: {}", e);
             }
 
+            // Pre-size capture buffers for the current display layout
+            std::thread::spawn(|| {
+                if let Ok(screens) = Screen::all() {
+                    if !screens.is_empty() {
+                        let (_, _, width, height) = composite_bounds(&screens);
+                        buffer_pool::pool().prewarm(width as usize * height as usize * 4);
+                    }
+                }
+            });
+
+            if let Err(e) = budget_manager.load(app.handle()) {
+                eprintln!("Failed to load AI budgets: {}", e);
+            }
+            startup.mark("audio recorder + AI budgets");
+
+            // Load settings and push them into subsystems
+            if let Err(e) = settings_manager.load(app.handle()) {
+                eprintln!("Failed to load settings: {}", e);
+            }
+            activity_monitor.set_window(settings_manager.get().activity.window_seconds);
+            activity_monitor.set_capture(&settings_manager.get().activity);
+            let performance = settings_manager.get().performance;
+            media_buffers::budget().configure(performance.media_memory_limit_mb, performance.media_overflow_policy);
+            event_coalescer.set_window(performance.event_coalesce_ms);
+            task_registry.start(settings_manager.get().performance.worker_threads)?;
+            event_coalescer.start(app.handle().clone(), &task_registry)?;
+            realtime_emitter.start(app.handle().clone(), &task_registry)?;
+            session_index.start_watcher(app.handle().clone(), &task_registry)?;
+            transcription_queue.start(app.handle().clone(), &task_registry)?;
+            merge_queue.start(app.handle().clone(), &task_registry)?;
+            api_server.apply(app.handle(), &task_registry, &settings_manager.get().api_server);
+            if let Err(e) = control_server::start(app.handle().clone(), &task_registry) {
+                eprintln!("❌ [CONTROL] {}", e);
+            }
+            #[cfg(target_os = "macos")]
+            automation::install(app.handle().clone());
+            calendar::start(app.handle().clone(), &task_registry)?;
+            meeting_detector.start(app.handle().clone(), &task_registry)?;
+            privacy_guard.start(app.handle().clone(), &task_registry)?;
+            storage_budget::start(app.handle().clone(), &task_registry)?;
+            disk_space::start(app.handle().clone(), &task_registry)?;
+            notifications::start(app.handle().clone(), &task_registry)?;
+            recording_limits::start(app.handle().clone(), &task_registry)?;
+            replay_buffer::refresh(app.handle());
+            session_health.start(app.handle().clone(), &task_registry)?;
+            recovery::start(app.handle().clone(), &task_registry)?;
+            git_monitor::start(app.handle().clone(), &task_registry)?;
+            ocr::start(app.handle().clone(), &task_registry)?;
+            thumbnails::start(app.handle().clone(), &task_registry)?;
+            session_storage::start_blob_store(app.handle().clone(), &task_registry)?;
+            session_storage::start_log_compactor(app.handle().clone(), &task_registry)?;
+            backup::start(app.handle().clone(), &task_registry)?;
+            folder_sync::start(app.handle().clone(), &task_registry)?;
+            remote_archive::start(app.handle().clone(), &task_registry)?;
+            video_recording::install_display_monitor(app.handle().clone());
+            {
+                // Keep the tray countdown in step with scheduled captures
+                let countdown_state = countdown_state.clone();
+                screenshot_scheduler.on_capture(move |session_id, timestamp| {
+                    countdown_state.update(|countdown| {
+                        if countdown.active && countdown.session_id == session_id {
+                            countdown.last_screenshot_time = timestamp.to_string();
+                        }
+                    });
+                });
+            }
+            screenshot_scheduler.start_thread(
+                app.handle().clone(),
+                settings_manager.clone(),
+                Box::new(capture_composite_jpeg),
+            )?;
+            {
+                let activity_monitor = activity_monitor.clone();
+                let countdown_state = countdown_state.clone();
+                let event_coalescer = event_coalescer.clone();
+                let api_server = api_server.clone();
+                let task_registry = task_registry.clone();
+                let app_handle = app.handle().clone();
+                settings_manager.subscribe(move |settings| {
+                    activity_monitor.set_window(settings.activity.window_seconds);
+                    activity_monitor.set_capture(&settings.activity);
+                    api_server.apply(&app_handle, &task_registry, &settings.api_server);
+                    replay_buffer::refresh(&app_handle);
+                    event_coalescer.set_window(settings.performance.event_coalesce_ms);
+                    media_buffers::budget().configure(
+                        settings.performance.media_memory_limit_mb,
+                        settings.performance.media_overflow_policy,
+                    );
+                    countdown_state.update(|countdown| {
+                        if countdown.active {
+                            countdown.interval_minutes = settings.screenshots.interval_minutes;
+                        }
+                    });
+                });
+            }
+            startup.mark("settings + background tasks");
+
             if cfg!(debug_assertions) {
                 app.handle().plugin(
                     tauri_plugin_log::Builder::default()
@@ -525,32 +1484,26 @@ pub fn run() {
                         .build(),
                 )?;
             }
+            startup.mark("log plugin");
 
-            // Create system tray menu
-            let quit_i = MenuItem::with_id(app, "quit", "Quit Taskerino", true, None::<&str>)?;
-            let show_i = MenuItem::with_id(app, "show", "Show Window", true, None::<&str>)?;
-            let hide_i = MenuItem::with_id(app, "hide", "Hide Window", true, None::<&str>)?;
-            let capture_i = MenuItem::with_id(app, "capture", "Quick Capture (⌘⇧Space)", true, None::<&str>)?;
-            let countdown_i = MenuItem::with_id(app, "countdown", "⏱️ Taskerino", false, None::<&str>)?;
-            let pause_i = MenuItem::with_id(app, "pause", "⏸ Pause Session", true, None::<&str>)?;
-            let resume_i = MenuItem::with_id(app, "resume", "▶️ Resume Session", true, None::<&str>)?;
-            let stop_i = MenuItem::with_id(app, "stop", "⏹ Stop Session", true, None::<&str>)?;
-
-            let menu = Menu::with_items(
-                app,
-                &[&countdown_i, &pause_i, &resume_i, &stop_i, &show_i, &hide_i, &capture_i, &quit_i],
-            )?;
+            // Create system tray menu (built-in items plus saved quick actions)
+            tray_menu.load(app.handle());
+            let menu = tray_menu.build(app.handle())?;
 
             // Build system tray
             let tray_handle_clone = tray_icon_handle.clone();
-            let icon = app.default_window_icon()
-                .ok_or("No default window icon")?
-                .clone();
-            let tray = TrayIconBuilder::new()
-                .icon(icon)
+            // macOS shows the state in a rendered template icon, other platforms in the title
+            #[cfg(target_os = "macos")]
+            let tray_builder = TrayIconBuilder::new()
+                .icon(tray_icon::render(&TrayIconState::Idle))
+                .icon_as_template(true);
+            #[cfg(not(target_os = "macos"))]
+            let tray_builder = TrayIconBuilder::new()
+                .icon(app.default_window_icon().ok_or("No default window icon")?.clone())
+                .title("⚫ Taskerino");
+            let tray = tray_builder
                 .menu(&menu)
                 .show_menu_on_left_click(true)
-                .title("⚫ Taskerino")
                 .tooltip("Taskerino")
                 .on_menu_event(move |app, event| match event.id.as_ref() {
                     "countdown" => {
@@ -590,27 +1543,34 @@ pub fn run() {
                             let _ = app.emit("navigate-to-capture", ());
                         }
                     }
-                    _ => {}
+                    id => {
+                        // Quick actions registered with add_tray_action
+                        tray_actions::handle_menu_event(app, id);
+                    }
                 })
                 .build(app)?;
 
             // Store tray icon for later updates
-            *tray_handle_clone.lock()
-                .map_err(|e| format!("Failed to lock tray handle: {}", e))? = Some(tray);
+            tray_handle_clone.set(Some(tray));
+            startup.mark("tray");
 
             // Register global shortcuts using the plugin
             #[cfg(desktop)]
             {
-                use tauri_plugin_global_shortcut::{Code, GlobalShortcutExt, Modifiers, Shortcut, ShortcutState};
+                use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
 
                 // Register plugin
                 app.handle().plugin(
                     tauri_plugin_global_shortcut::Builder::new()
                         .with_handler(move |app, shortcut, event| {
                             if event.state() == ShortcutState::Pressed {
+                                let shortcuts = app.state::<Arc<SettingsManager>>().get().shortcuts;
+                                let matches = |configured: &str| {
+                                    ShortcutSettings::parse(configured).is_ok_and(|s| &s == shortcut)
+                                };
                                 if let Some(window) = app.get_webview_window("main") {
-                                    // Cmd+Shift+Space for quick capture screenshot
-                                    if shortcut == &Shortcut::new(Some(Modifiers::SUPER | Modifiers::SHIFT), Code::Space) {
+                                    // Cmd+Shift+Space (default) for quick capture screenshot
+                                    if matches(&shortcuts.quick_capture) {
                                         // Generate unique temp filename
                                         let timestamp = std::time::SystemTime::now()
                                             .duration_since(std::time::UNIX_EPOCH)
@@ -647,8 +1607,8 @@ pub fn run() {
                                             }
                                         }
                                     }
-                                    // Cmd+Shift+T to toggle window visibility
-                                    else if shortcut == &Shortcut::new(Some(Modifiers::SUPER | Modifiers::SHIFT), Code::KeyT) {
+                                    // Cmd+Shift+T (default) to toggle window visibility
+                                    else if matches(&shortcuts.toggle_window) {
                                         match window.is_visible() {
                                             Ok(true) => { let _ = window.hide(); },
                                             _ => {
@@ -657,8 +1617,8 @@ pub fn run() {
                                             }
                                         }
                                     }
-                                    // Cmd+Shift+4 for screenshot capture
-                                    else if shortcut == &Shortcut::new(Some(Modifiers::SUPER | Modifiers::SHIFT), Code::Digit4) {
+                                    // Cmd+Shift+4 (default) for screenshot capture
+                                    else if matches(&shortcuts.screenshot) {
                                         // Generate unique temp filename
                                         let timestamp = std::time::SystemTime::now()
                                             .duration_since(std::time::UNIX_EPOCH)
@@ -703,187 +1663,131 @@ pub fn run() {
                         .build(),
                 )?;
 
-                // Register shortcuts
-                app.global_shortcut().register(Shortcut::new(Some(Modifiers::SUPER | Modifiers::SHIFT), Code::Space))?;
-                app.global_shortcut().register(Shortcut::new(Some(Modifiers::SUPER | Modifiers::SHIFT), Code::KeyT))?;
-                app.global_shortcut().register(Shortcut::new(Some(Modifiers::SUPER | Modifiers::SHIFT), Code::Digit4))?;
+                // Register configured shortcuts
+                for shortcut in settings_manager.get().shortcuts.all() {
+                    app.global_shortcut().register(shortcut)?;
+                }
+
+                // Re-register when shortcut settings change
+                let shortcut_app = app.handle().clone();
+                settings_manager.subscribe(move |settings| {
+                    let global_shortcut = shortcut_app.global_shortcut();
+                    if let Err(e) = global_shortcut.unregister_all() {
+                        eprintln!("Failed to unregister shortcuts: {}", e);
+                    }
+                    for shortcut in settings.shortcuts.all() {
+                        if let Err(e) = global_shortcut.register(shortcut) {
+                            eprintln!("Failed to register shortcut {:?}: {}", shortcut, e);
+                        }
+                    }
+                });
             }
+            startup.mark("global shortcuts");
 
             // Spawn background task to update countdown in menu bar
             let app_handle = app.handle().clone();
             let countdown_state_clone = countdown_state.clone();
             let tray_handle_for_thread = tray_icon_handle.clone();
-            std::thread::spawn(move || {
+            let tray_menu_for_thread = tray_menu.clone();
+            let events_for_thread = event_coalescer.clone();
+            let activity_for_thread = activity_monitor.clone();
+            task_registry.spawn("tray-countdown", |mut shutdown| async move {
+                let mut applied_view: Option<TrayView> = None;
+                let mut last_parse_error: Option<String> = None;
                 loop {
-                    std::thread::sleep(Duration::from_secs(1));
+                    // Tick every second while a session is counting down or activity
+                    // is monitored; otherwise sleep until something changes
+                    let ticking = countdown_state_clone.snapshot().session_status == "active"
+                        || activity_for_thread.is_monitoring();
+                    if ticking {
+                        tokio::select! {
+                            _ = shutdown.cancelled() => break,
+                            _ = tokio::time::sleep(Duration::from_secs(1)) => {}
+                            _ = countdown_state_clone.changed.notified() => {}
+                            _ = tray_menu_for_thread.rebuilt() => {}
+                        }
+                    } else {
+                        tokio::select! {
+                            _ = shutdown.cancelled() => break,
+                            _ = countdown_state_clone.changed.notified() => {}
+                            _ = tray_menu_for_thread.rebuilt() => {}
+                        }
+                    }
+
+                    if activity_for_thread.is_monitoring() {
+                        events_for_thread.push("activity-stats", activity_for_thread.get_current_metrics());
+                    }
+                    let focus_changes = activity_for_thread.take_app_focus_changes();
+                    if !focus_changes.is_empty() {
+                        let _ = app_handle.emit("app-focus-changes", &focus_changes);
+                    }
+                    let url_visits = activity_for_thread.take_url_visits();
+                    if !url_visits.is_empty() {
+                        let _ = app_handle.emit("browser-url-visits", &url_visits);
+                    }
 
                     // Get countdown state
-                    let state = match countdown_state_clone.lock() {
-                        Ok(s) => s,
-                        Err(_) => continue, // Skip if lock fails
-                    };
+                    let state = countdown_state_clone.snapshot();
                     let is_active = state.active;
                     let session_status = state.session_status.clone();
                     let last_time = state.last_screenshot_time.clone();
-
-                    if !is_active || last_time.is_empty() {
-                        println!("⚫ Idle state: active={}, last_time_empty={}", is_active, last_time.is_empty());
-                        // Update tray icon title to show idle state
-                        if let Ok(tray_guard) = tray_handle_for_thread.lock() {
-                            if let Some(tray) = tray_guard.as_ref() {
-                                let _ = tray.set_title(Some("⚫ Taskerino"));
-                            }
-                        }
-
-                        // Update menu to show idle state
-                        if let Some(menu) = app_handle.menu() {
-                            if let Some(item) = menu.get("countdown") {
-                                if let Some(menuitem) = item.as_menuitem() {
-                                    let _ = menuitem.set_text("⚫ Taskerino");
-                                }
+                    let countdown_session_id = state.session_id.clone();
+
+                    let view = if !is_active || last_time.is_empty() {
+                        TrayView::idle()
+                    } else {
+                        // Parse last screenshot time
+                        let interval_ms = (state.interval_minutes * 60.0 * 1000.0) as i64;
+
+                        match chrono::DateTime::parse_from_rfc3339(&last_time) {
+                            Ok(last_shot) => {
+                                let next_shot = last_shot.timestamp_millis() + interval_ms;
+                                let remaining_ms = next_shot - chrono::Utc::now().timestamp_millis();
+
+                                events_for_thread.push("countdown-tick", serde_json::json!({
+                                    "sessionId": countdown_session_id,
+                                    "status": session_status,
+                                    "remainingMs": remaining_ms.max(0),
+                                }));
+
+                                TrayView::countdown(&session_status, remaining_ms, interval_ms)
                             }
-                            // Disable all session controls when idle
-                            if let Some(item) = menu.get("pause") {
-                                if let Some(menuitem) = item.as_menuitem() {
-                                    let _ = menuitem.set_enabled(false);
-                                }
-                            }
-                            if let Some(item) = menu.get("resume") {
-                                if let Some(menuitem) = item.as_menuitem() {
-                                    let _ = menuitem.set_enabled(false);
-                                }
-                            }
-                            if let Some(item) = menu.get("stop") {
-                                if let Some(menuitem) = item.as_menuitem() {
-                                    let _ = menuitem.set_enabled(false);
+                            Err(e) => {
+                                // Log each bad timestamp once rather than every tick
+                                if last_parse_error.as_deref() != Some(last_time.as_str()) {
+                                    println!("❌ Failed to parse timestamp '{}': {:?}", last_time, e);
+                                    last_parse_error = Some(last_time);
                                 }
+                                continue;
                             }
                         }
-                        drop(state);
-                        continue;
-                    }
-
-                    // Parse last screenshot time
-                    let interval_ms = (state.interval_minutes * 60.0 * 1000.0) as i64;
-                    let last_shot_time = state.last_screenshot_time.clone();
-                    drop(state);
-
-                    // Calculate countdown
-                    let status_icon = match session_status.as_str() {
-                        "active" => "🟢",
-                        "paused" => "🟡",
-                        _ => "⚫"
                     };
 
-                    match chrono::DateTime::parse_from_rfc3339(&last_shot_time) {
-                        Ok(last_shot) => {
-                        println!("📅 Parsed time: {}, status: {}", last_shot_time, session_status);
-                        let next_shot = last_shot.timestamp_millis() + interval_ms;
-                        let now = chrono::Utc::now().timestamp_millis();
-                        let remaining_ms = next_shot - now;
-
-                        let countdown_text = if session_status == "paused" {
-                            format!("{} Paused", status_icon)
-                        } else if remaining_ms <= 0 {
-                            format!("{} Soon...", status_icon)
-                        } else {
-                            let remaining_secs = (remaining_ms / 1000) as u32;
-                            if remaining_secs >= 60 {
-                                format!("{} Next: {}m {}s", status_icon, remaining_secs / 60, remaining_secs % 60)
-                            } else {
-                                format!("{} Next: {}s", status_icon, remaining_secs)
-                            }
-                        };
-
-                        // Update tray icon title in menu bar
-                        println!("🟢 Updating tray title: {}", countdown_text);
-                        if let Ok(tray_guard) = tray_handle_for_thread.lock() {
-                            if let Some(tray) = tray_guard.as_ref() {
-                                match tray.set_title(Some(&countdown_text)) {
-                                    Ok(_) => println!("✅ Tray title set successfully"),
-                                    Err(e) => println!("❌ Failed to set tray title: {:?}", e),
-                                }
-                            } else {
-                                println!("❌ Tray icon not found in handle");
-                            }
-                        } else {
-                            println!("❌ Failed to lock tray handle");
-                        }
-
-                        // Update menu item text and controls
-                        if let Some(menu) = app_handle.menu() {
-                            if let Some(item) = menu.get("countdown") {
-                                if let Some(menuitem) = item.as_menuitem() {
-                                    let _ = menuitem.set_text(&countdown_text);
-                                }
-                            }
-
-                            // Enable/disable controls based on status
-                            match session_status.as_str() {
-                                "active" => {
-                                    if let Some(item) = menu.get("pause") {
-                                        if let Some(menuitem) = item.as_menuitem() {
-                                            let _ = menuitem.set_enabled(true);
-                                        }
-                                    }
-                                    if let Some(item) = menu.get("resume") {
-                                        if let Some(menuitem) = item.as_menuitem() {
-                                            let _ = menuitem.set_enabled(false);
-                                        }
-                                    }
-                                    if let Some(item) = menu.get("stop") {
-                                        if let Some(menuitem) = item.as_menuitem() {
-                                            let _ = menuitem.set_enabled(true);
-                                        }
-                                    }
-                                }
-                                "paused" => {
-                                    if let Some(item) = menu.get("pause") {
-                                        if let Some(menuitem) = item.as_menuitem() {
-                                            let _ = menuitem.set_enabled(false);
-                                        }
-                                    }
-                                    if let Some(item) = menu.get("resume") {
-                                        if let Some(menuitem) = item.as_menuitem() {
-                                            let _ = menuitem.set_enabled(true);
-                                        }
-                                    }
-                                    if let Some(item) = menu.get("stop") {
-                                        if let Some(menuitem) = item.as_menuitem() {
-                                            let _ = menuitem.set_enabled(true);
-                                        }
-                                    }
-                                }
-                                _ => {
-                                    if let Some(item) = menu.get("pause") {
-                                        if let Some(menuitem) = item.as_menuitem() {
-                                            let _ = menuitem.set_enabled(false);
-                                        }
-                                    }
-                                    if let Some(item) = menu.get("resume") {
-                                        if let Some(menuitem) = item.as_menuitem() {
-                                            let _ = menuitem.set_enabled(false);
-                                        }
-                                    }
-                                    if let Some(item) = menu.get("stop") {
-                                        if let Some(menuitem) = item.as_menuitem() {
-                                            let _ = menuitem.set_enabled(false);
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                        }
-                        Err(e) => {
-                            println!("❌ Failed to parse timestamp '{}': {:?}", last_shot_time, e);
-                        }
+                    // Only touch the tray/menu when the rendered state changed (or the
+                    // menu was rebuilt with its default texts)
+                    if tray_menu_for_thread.take_rebuilt() || applied_view.as_ref() != Some(&view) {
+                        view.apply(&app_handle, &tray_handle_for_thread);
+                        applied_view = Some(view);
                     }
                 }
-            });
+            })?;
+            startup.mark("tray countdown");
 
             Ok(())
         })
-        .run(tauri::generate_context!())
+        .build(tauri::generate_context!())
         .map_err(|e| eprintln!("Error running Tauri application: {}", e))
         .ok();
+
+    if let Some(app) = app {
+        app.run(move |_app_handle, event| match event {
+            tauri::RunEvent::Ready => startup.mark_ready(),
+            // Let background tasks finish cleanly before the process exits
+            tauri::RunEvent::Exit => {
+                screenshot_scheduler_for_exit.shutdown();
+                task_registry_for_exit.shutdown(Duration::from_secs(2));
+            }
+            _ => {}
+        });
+    }
 }
@@ -3,20 +3,92 @@
  *
  * Implements automatic activity tracking using a hybrid approach:
  * - NSWorkspace for app switching notifications (via cocoa crate)
- * - Polling-based mouse position tracking for click detection
+ * - Frontmost app + window title (CGWindowList, needs screen recording
+ *   permission) on each switch and title change, for the app timeline
+ * - Key presses and mouse clicks from a listen-only CGEvent tap, which needs
+ *   Input Monitoring (permissions.rs). Without it only app switches and focus
+ *   changes are counted and the metrics report
+ *   `DegradedReason::InputMonitoringDenied`; the permission is re-checked
+ *   while monitoring, so granting it starts the tap
+ * - Active browser tab via AppleScript (Safari and Chromium browsers) while a
+ *   browser is frontmost and URL capture is on; Chromium incognito windows are
+ *   skipped (Safari doesn't expose private windows, so use blocked domains)
  * - Simple and reliable implementation
- *
- * Note: This is a simplified Phase 2 implementation optimized for reliability.
- * A future Phase 3 could add CGEvent taps for more precise event capture,
- * but that requires more complex unsafe code and accessibility permissions.
  */
 
-use crate::activity_monitor::ActivityMonitor;
+use crate::activity_monitor::{ActivityMonitor, DegradedReason};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::thread;
 use std::time::Duration;
 
+#[cfg(target_os = "macos")]
+use crate::permissions::{PermissionStatus, PermissionType};
+
+/// App polls between window title checks for the app timeline
+#[cfg(target_os = "macos")]
+const TITLE_POLL_TICKS: u32 = 2;
+
+/// App polls between browser tab checks while a browser stays frontmost
+#[cfg(target_os = "macos")]
+const TAB_POLL_TICKS: u32 = 4;
+
+/// App polls between Input Monitoring checks while degraded
+#[cfg(target_os = "macos")]
+const INPUT_RETRY_TICKS: u32 = 20;
+
+/// Browsers whose active tab can be read: (bundle id, display name)
+#[cfg(target_os = "macos")]
+const BROWSERS: [(&str, &str); 8] = [
+    ("com.apple.Safari", "Safari"),
+    ("com.apple.SafariTechnologyPreview", "Safari Technology Preview"),
+    ("com.google.Chrome", "Google Chrome"),
+    ("com.microsoft.edgemac", "Microsoft Edge"),
+    ("com.brave.Browser", "Brave Browser"),
+    ("company.thebrowser.Browser", "Arc"),
+    ("com.vivaldi.Vivaldi", "Vivaldi"),
+    ("org.chromium.Chromium", "Chromium"),
+];
+
+/// URL and title of the front window's active tab (None for private windows)
+#[cfg(target_os = "macos")]
+fn active_tab(bundle_id: &str) -> Option<(String, String)> {
+    let script = if bundle_id.starts_with("com.apple.Safari") {
+        format!(
+            r#"tell application id "{}"
+                if (count of windows) = 0 then return ""
+                set t to current tab of front window
+                return (URL of t) & linefeed & (name of t)
+            end tell"#,
+            bundle_id
+        )
+    } else {
+        let private_check = if bundle_id == "company.thebrowser.Browser" {
+            "incognito of front window"
+        } else {
+            "mode of front window is \"incognito\""
+        };
+        format!(
+            r#"tell application id "{}"
+                if (count of windows) = 0 then return ""
+                if {} then return ""
+                set t to active tab of front window
+                return (URL of t) & linefeed & (title of t)
+            end tell"#,
+            bundle_id, private_check
+        )
+    };
+
+    let output = std::process::Command::new("osascript").args(["-e", &script]).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let text = String::from_utf8_lossy(&output.stdout);
+    let mut lines = text.trim_end_matches('\n').splitn(2, '\n');
+    let url = lines.next().filter(|url| !url.is_empty())?;
+    Some((url.to_string(), lines.next().unwrap_or_default().to_string()))
+}
+
 /// Event monitor for macOS activity tracking
 pub struct MacOSEventMonitor {
     monitor: Arc<ActivityMonitor>,
@@ -46,8 +118,8 @@ impl MacOSEventMonitor {
         // Start app switching monitoring
         self.start_app_monitoring()?;
 
-        // Start mouse activity polling
-        self.start_mouse_polling()?;
+        // Key presses and clicks, or app-switch-only without Input Monitoring
+        start_input_counting(&self.monitor, &self.is_running);
 
         println!("✅ [MACOS EVENTS] macOS event monitoring started");
         Ok(())
@@ -77,6 +149,9 @@ impl MacOSEventMonitor {
             // Get shared NSWorkspace instance
             let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
             let mut last_app: Option<String> = None;
+            let mut ticks_since_title_check: u32 = 0;
+            let mut ticks_since_tab_check: u32 = 0;
+            let mut ticks_since_input_check: u32 = 0;
 
             println!("✅ [MACOS EVENTS] App monitoring thread started");
 
@@ -92,11 +167,41 @@ impl MacOSEventMonitor {
                                 std::ffi::CStr::from_ptr(bundle_str).to_string_lossy().into_owned();
 
                             // Detect app switch
-                            if let Some(ref last) = last_app {
-                                if *last != current_app {
-                                    monitor.increment_app_switch();
-                                    monitor.increment_window_focus(); // App switch implies focus change
-                                    println!("🔄 [MACOS EVENTS] App switched: {} → {}", last, current_app);
+                            let switched = last_app.as_ref().is_some_and(|last| *last != current_app);
+                            if switched {
+                                monitor.increment_app_switch();
+                                monitor.increment_window_focus(); // App switch implies focus change
+                                println!("🔄 [MACOS EVENTS] App switched: {} → {}", last_app.as_deref().unwrap_or_default(), current_app);
+                            }
+
+                            // App timeline: on focus, then every TITLE_POLL_TICKS to catch window changes
+                            ticks_since_title_check += 1;
+                            if switched || last_app.is_none() || ticks_since_title_check >= TITLE_POLL_TICKS {
+                                ticks_since_title_check = 0;
+                                let name: id = msg_send![frontmost, localizedName];
+                                let name_str: *const i8 = if name != nil { msg_send![name, UTF8String] } else { std::ptr::null() };
+                                let app_name = if name_str.is_null() {
+                                    current_app.clone()
+                                } else {
+                                    std::ffi::CStr::from_ptr(name_str).to_string_lossy().into_owned()
+                                };
+                                let window_title = if monitor.is_capturing_window_titles() {
+                                    let pid: i32 = msg_send![frontmost, processIdentifier];
+                                    crate::meeting_detector::front_window_title(pid as i64).unwrap_or_default()
+                                } else {
+                                    String::new()
+                                };
+                                monitor.record_app_focus(&current_app, &app_name, &window_title);
+                            }
+
+                            // Browser tab: on focus, then every TAB_POLL_TICKS to catch tab switches
+                            ticks_since_tab_check += 1;
+                            if let Some((_, browser)) = BROWSERS.iter().find(|(id, _)| *id == current_app) {
+                                if (switched || ticks_since_tab_check >= TAB_POLL_TICKS) && monitor.is_capturing_urls() {
+                                    ticks_since_tab_check = 0;
+                                    if let Some((url, title)) = active_tab(&current_app) {
+                                        monitor.record_url_visit(browser, &url, &title);
+                                    }
                                 }
                             }
 
@@ -105,6 +210,17 @@ impl MacOSEventMonitor {
                     }
                 }
 
+                // Permission granted while degraded: start counting input
+                ticks_since_input_check += 1;
+                if ticks_since_input_check >= INPUT_RETRY_TICKS {
+                    ticks_since_input_check = 0;
+                    if monitor.is_degraded(DegradedReason::InputMonitoringDenied)
+                        && crate::permissions::status(PermissionType::InputMonitoring) == PermissionStatus::Authorized
+                    {
+                        start_input_counting(&monitor, &is_running);
+                    }
+                }
+
                 thread::sleep(Duration::from_millis(500)); // Poll every 500ms
             }
 
@@ -119,61 +235,141 @@ impl MacOSEventMonitor {
         println!("⚠️  [MACOS EVENTS] App monitoring not available on this platform");
         Ok(())
     }
+}
 
-    #[cfg(target_os = "macos")]
-    fn start_mouse_polling(&self) -> Result<(), String> {
-        use core_graphics::event::CGEvent;
-        use core_graphics::event_source::{CGEventSource, CGEventSourceStateID};
-
-        let monitor = Arc::clone(&self.monitor);
-        let is_running = Arc::clone(&self.is_running);
-
-        thread::spawn(move || {
-            let mut last_pos: Option<(f64, f64)> = None;
-            let mut movement_count = 0;
-
-            println!("✅ [MACOS EVENTS] Mouse polling thread started");
-
-            while is_running.load(Ordering::SeqCst) {
-                // Get current mouse location via CGEvent
-                if let Ok(source) = CGEventSource::new(CGEventSourceStateID::CombinedSessionState) {
-                    if let Ok(event) = CGEvent::new(source) {
-                        let location = event.location();
-
-                        // Simple heuristic: significant mouse movement suggests clicks/activity
-                        if let Some((last_x, last_y)) = last_pos {
-                            let dx = (location.x - last_x).abs();
-                            let dy = (location.y - last_y).abs();
-
-                            // If mouse moved significantly, consider it activity
-                            if dx > 50.0 || dy > 50.0 {
-                                movement_count += 1;
-
-                                // Every 5 significant movements, record as a "click" proxy
-                                if movement_count % 5 == 0 {
-                                    monitor.increment_mouse_click();
-                                }
-                            }
-                        }
-
-                        last_pos = Some((location.x, location.y));
+/// Count key presses and mouse clicks with a listen-only tap when Input
+/// Monitoring is granted; otherwise mark the metrics degraded
+#[cfg(target_os = "macos")]
+fn start_input_counting(monitor: &Arc<ActivityMonitor>, running: &Arc<AtomicBool>) {
+    use core_graphics::event::CGEventType;
+
+    let status = crate::permissions::status(PermissionType::InputMonitoring);
+    let started = if status == PermissionStatus::Authorized {
+        let counter = Arc::clone(monitor);
+        run_event_tap(
+            Arc::clone(running),
+            vec![
+                CGEventType::KeyDown,
+                CGEventType::LeftMouseDown,
+                CGEventType::RightMouseDown,
+                CGEventType::OtherMouseDown,
+            ],
+            "Input tap",
+            move |event_type, event| match event_type {
+                CGEventType::KeyDown => {
+                    if !is_autorepeat(event) {
+                        counter.increment_keyboard_event();
                     }
                 }
-
-                thread::sleep(Duration::from_millis(200)); // Poll every 200ms
+                _ => counter.increment_mouse_click(),
+            },
+        )
+    } else {
+        Err(format!("Input Monitoring permission is {:?}", status))
+    };
+
+    match started {
+        Ok(()) => monitor.set_degraded(DegradedReason::InputMonitoringDenied, false),
+        Err(e) => {
+            if !monitor.is_degraded(DegradedReason::InputMonitoringDenied) {
+                println!("⚠️  [MACOS EVENTS] {} - counting app switches only", e);
             }
+            monitor.set_degraded(DegradedReason::InputMonitoringDenied, true);
+        }
+    }
+}
 
-            println!("🛑 [MACOS EVENTS] Mouse polling thread stopped");
-        });
+#[cfg(not(target_os = "macos"))]
+fn start_input_counting(monitor: &Arc<ActivityMonitor>, _running: &Arc<AtomicBool>) {
+    monitor.set_degraded(DegradedReason::UnsupportedPlatform, true);
+}
 
-        Ok(())
-    }
+#[cfg(target_os = "macos")]
+fn is_autorepeat(event: &core_graphics::event::CGEvent) -> bool {
+    use core_graphics::event::EventField;
+    event.get_integer_value_field(EventField::KEYBOARD_EVENT_AUTOREPEAT) != 0
+}
 
-    #[cfg(not(target_os = "macos"))]
-    fn start_mouse_polling(&self) -> Result<(), String> {
-        println!("⚠️  [MACOS EVENTS] Mouse polling not available on this platform");
-        Ok(())
-    }
+/// Listen-only CGEvent tap on `events`: `on_event` runs on the tap's thread
+/// until `running` is cleared. Fails without Input Monitoring permission
+#[cfg(target_os = "macos")]
+fn run_event_tap<F>(
+    running: Arc<AtomicBool>,
+    events: Vec<core_graphics::event::CGEventType>,
+    name: &'static str,
+    on_event: F,
+) -> Result<(), String>
+where
+    F: Fn(core_graphics::event::CGEventType, &core_graphics::event::CGEvent) + Send + 'static,
+{
+    use core_foundation::runloop::{kCFRunLoopCommonModes, kCFRunLoopDefaultMode, CFRunLoop};
+    use core_graphics::event::{CGEventTap, CGEventTapLocation, CGEventTapOptions, CGEventTapPlacement};
+
+    let (ready_tx, ready_rx) = std::sync::mpsc::channel();
+    thread::spawn(move || {
+        let tap = CGEventTap::new(
+            CGEventTapLocation::Session,
+            CGEventTapPlacement::TailAppendEventTap,
+            CGEventTapOptions::ListenOnly,
+            events,
+            move |_, event_type, event| {
+                on_event(event_type, event);
+                None
+            },
+        );
+        let Ok(tap) = tap else {
+            let _ = ready_tx.send(Err(format!(
+                "{} needs Input Monitoring permission (System Settings > Privacy & Security > Input Monitoring)",
+                name
+            )));
+            return;
+        };
+        let Ok(source) = tap.mach_port.create_runloop_source(0) else {
+            let _ = ready_tx.send(Err(format!("Failed to create {} run loop source", name.to_lowercase())));
+            return;
+        };
+        CFRunLoop::get_current().add_source(&source, unsafe { kCFRunLoopCommonModes });
+        tap.enable();
+        let _ = ready_tx.send(Ok(()));
+        println!("⌨️  [MACOS EVENTS] {} started", name);
+
+        while running.load(Ordering::SeqCst) {
+            CFRunLoop::run_in_mode(unsafe { kCFRunLoopDefaultMode }, Duration::from_millis(500), false);
+        }
+        println!("🛑 [MACOS EVENTS] {} stopped", name);
+    });
+
+    ready_rx
+        .recv()
+        .map_err(|_| format!("{} thread exited unexpectedly", name))?
+}
+
+/// Listen-only CGEvent tap on key presses: `on_key(keycode, CGEventFlags bits)`
+/// runs on the tap's thread for every key down (auto-repeats excluded) until
+/// `running` is cleared. Needs Input Monitoring permission
+/// (`PermissionType::InputMonitoring`); macOS never
+/// delivers keys typed while secure input is on (password fields).
+#[cfg(target_os = "macos")]
+pub fn start_key_tap<F>(running: Arc<AtomicBool>, on_key: F) -> Result<(), String>
+where
+    F: Fn(u16, u64) + Send + 'static,
+{
+    use core_graphics::event::{CGEventType, EventField};
+
+    run_event_tap(running, vec![CGEventType::KeyDown], "Keystroke capture", move |_, event| {
+        if !is_autorepeat(event) {
+            let keycode = event.get_integer_value_field(EventField::KEYBOARD_EVENT_KEYCODE) as u16;
+            on_key(keycode, event.get_flags().bits());
+        }
+    })
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn start_key_tap<F>(_running: Arc<AtomicBool>, _on_key: F) -> Result<(), String>
+where
+    F: Fn(u16, u64) + Send + 'static,
+{
+    Err("Keystroke capture only supported on macOS".to_string())
 }
 
 // Ensure thread-safety
@@ -2,5 +2,9 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 fn main() {
+  if std::env::args().any(|arg| arg == "--mcp") {
+    app_lib::run_mcp_server();
+    return;
+  }
   app_lib::run();
 }
@@ -0,0 +1,210 @@
+/**
+ * MCP Server Module
+ *
+ * Model Context Protocol server over stdio so Claude Desktop and other agents
+ * can read session data. Started with `taskerino --mcp` (no window is created):
+ *
+ *   { "mcpServers": { "taskerino": { "command": "/path/to/taskerino", "args": ["--mcp"] } } }
+ *
+ * Tools (read-only, active profile):
+ * - `query_active_session`: the session currently being recorded (no end time)
+ * - `query_sessions`: session summaries from the SQLite session index, with
+ *   search, category filter, sorting and pagination
+ * - `get_session_by_id`: full session detail
+ *
+ * Messages are newline-delimited JSON-RPC 2.0. stdout carries protocol
+ * messages only - all logging goes to stderr.
+ */
+
+use serde_json::{json, Value};
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+
+use crate::profiles;
+use crate::session_index::{self, SessionQuery};
+use crate::session_storage;
+
+/// Must match `identifier` in tauri.conf.json (Tauri's app data dir name)
+const APP_IDENTIFIER: &str = "com.taskerino.desktop";
+
+const SUPPORTED_PROTOCOL_VERSIONS: [&str; 3] = ["2025-06-18", "2025-03-26", "2024-11-05"];
+
+const DEFAULT_QUERY_LIMIT: usize = 50;
+
+// JSON-RPC error codes
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// Active profile's data dir (sessions.json + session index)
+fn profile_dir() -> Result<PathBuf, String> {
+    let data_dir = dirs::data_dir()
+        .ok_or("Failed to get app data dir")?
+        .join(APP_IDENTIFIER);
+    Ok(profiles::profile_data_dir_from_disk(&data_dir))
+}
+
+
+fn tool_definitions() -> Value {
+    json!([
+        {
+            "name": "query_active_session",
+            "description": "Get the Taskerino session currently being recorded (screenshots, audio segments, notes, transcript). Returns null when no session is active.",
+            "inputSchema": { "type": "object", "properties": {} }
+        },
+        {
+            "name": "query_sessions",
+            "description": "List Taskerino session summaries (newest first by default). Optionally filter by a case-insensitive search over name, category and notes, or by category. Returns { sessions, total, offset, limit } for paging.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "query": { "type": "string", "description": "Search text" },
+                    "category": { "type": "string", "description": "Only sessions in this category" },
+                    "sortBy": { "type": "string", "enum": ["startTime", "name", "duration", "category"], "description": "Sort field (default startTime)" },
+                    "sortOrder": { "type": "string", "enum": ["asc", "desc"], "description": "Sort order (default desc)" },
+                    "limit": { "type": "integer", "minimum": 1, "description": "Maximum sessions to return (default 50)" },
+                    "offset": { "type": "integer", "minimum": 0, "description": "Sessions to skip (default 0)" }
+                }
+            }
+        },
+        {
+            "name": "get_session_by_id",
+            "description": "Get the full detail of one Taskerino session.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "id": { "type": "string", "description": "Session id" }
+                },
+                "required": ["id"]
+            }
+        }
+    ])
+}
+
+fn query_active_session() -> Result<Value, String> {
+    // Sessions are re-read on every call so agents always see live data
+    let active = session_storage::read_active_session(&profile_dir()?)?;
+    serde_json::to_value(active).map_err(|e| format!("Failed to serialize session: {}", e))
+}
+
+fn query_sessions(arguments: &Value) -> Result<Value, String> {
+    let mut query: SessionQuery = serde_json::from_value(arguments.clone())
+        .map_err(|e| format!("Invalid query_sessions arguments: {}", e))?;
+    query.limit = Some(query.limit.unwrap_or(DEFAULT_QUERY_LIMIT).max(1));
+
+    let page = session_index::query_profile(&profile_dir()?, &query)?;
+    serde_json::to_value(page).map_err(|e| format!("Failed to serialize sessions: {}", e))
+}
+
+fn get_session_by_id(arguments: &Value) -> Result<Value, String> {
+    let id = arguments
+        .get("id")
+        .and_then(Value::as_str)
+        .ok_or("Missing required argument: id")?;
+    let session = session_storage::read_session(&profile_dir()?, id)?;
+    serde_json::to_value(session).map_err(|e| format!("Failed to serialize session: {}", e))
+}
+
+/// Run a tool; failures are reported as tool errors (isError), not protocol errors
+fn call_tool(params: &Value) -> Result<Value, (i64, String)> {
+    let name = params
+        .get("name")
+        .and_then(Value::as_str)
+        .ok_or((INVALID_PARAMS, "Missing tool name".to_string()))?;
+    let arguments = params.get("arguments").cloned().unwrap_or_else(|| json!({}));
+
+    let result = match name {
+        "query_active_session" => query_active_session(),
+        "query_sessions" => query_sessions(&arguments),
+        "get_session_by_id" => get_session_by_id(&arguments),
+        _ => return Err((INVALID_PARAMS, format!("Unknown tool: {}", name))),
+    };
+
+    Ok(match result {
+        Ok(value) => json!({
+            "content": [{ "type": "text", "text": value.to_string() }],
+            "isError": false
+        }),
+        Err(e) => {
+            eprintln!("❌ [MCP] {} failed: {}", name, e);
+            json!({
+                "content": [{ "type": "text", "text": e }],
+                "isError": true
+            })
+        }
+    })
+}
+
+fn initialize(params: &Value) -> Value {
+    let requested = params.get("protocolVersion").and_then(Value::as_str);
+    let version = requested
+        .filter(|version| SUPPORTED_PROTOCOL_VERSIONS.contains(version))
+        .unwrap_or(SUPPORTED_PROTOCOL_VERSIONS[0]);
+
+    json!({
+        "protocolVersion": version,
+        "capabilities": { "tools": {} },
+        "serverInfo": { "name": "taskerino", "version": env!("CARGO_PKG_VERSION") }
+    })
+}
+
+/// Handle one message; None for notifications (no response)
+fn handle_message(message: &Value) -> Option<Value> {
+    let id = message.get("id").cloned()?;
+    let method = message.get("method").and_then(Value::as_str).unwrap_or("");
+    let params = message.get("params").cloned().unwrap_or_else(|| json!({}));
+
+    let result = match method {
+        "initialize" => Ok(initialize(&params)),
+        "ping" => Ok(json!({})),
+        "tools/list" => Ok(json!({ "tools": tool_definitions() })),
+        "tools/call" => call_tool(&params),
+        _ => Err((METHOD_NOT_FOUND, format!("Method not found: {}", method))),
+    };
+
+    Some(match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err((code, message)) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": code, "message": message }
+        }),
+    })
+}
+
+/// Serve MCP over stdin/stdout until stdin closes
+pub fn run_stdio() {
+    eprintln!("🔌 [MCP] Taskerino MCP server started (stdio)");
+    let stdin = std::io::stdin();
+    let mut stdout = std::io::stdout();
+
+    for line in stdin.lock().lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(e) => {
+                eprintln!("❌ [MCP] Failed to read stdin: {}", e);
+                break;
+            }
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match serde_json::from_str::<Value>(&line) {
+            Ok(message) => handle_message(&message),
+            Err(e) => Some(json!({
+                "jsonrpc": "2.0",
+                "id": null,
+                "error": { "code": PARSE_ERROR, "message": format!("Parse error: {}", e) }
+            })),
+        };
+
+        if let Some(response) = response {
+            if writeln!(stdout, "{}", response).and_then(|_| stdout.flush()).is_err() {
+                break;
+            }
+        }
+    }
+
+    eprintln!("🔌 [MCP] stdin closed, exiting");
+}
@@ -0,0 +1,177 @@
+/**
+ * Media Buffer Budget Module
+ *
+ * Global memory ceiling for in-flight capture buffers (audio samples waiting
+ * to be chunked, encoded chunks waiting for the frontend):
+ * - Buffers reserve bytes before growing; reservations fail past the limit
+ * - On failure the owner applies the overflow policy: drop the oldest data,
+ *   or spill it to a temp file and read it back when the chunk is taken
+ * - Usage counters are reported through `get_audio_health_status`
+ */
+
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+/// What to do when a buffer can't grow within the budget
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum OverflowPolicy {
+    /// Discard the oldest buffered data
+    DropOldest,
+    /// Move buffered data to a temp file
+    #[default]
+    SpillToDisk,
+}
+
+/// Snapshot of media buffer memory usage
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaBufferUsage {
+    pub used_bytes: usize,
+    pub limit_bytes: usize,
+    pub peak_bytes: usize,
+    pub dropped_bytes: u64,
+    pub spilled_bytes: u64,
+    pub policy: OverflowPolicy,
+}
+
+/// Process-wide media memory budget
+pub struct MediaMemoryBudget {
+    limit: AtomicUsize,
+    used: AtomicUsize,
+    peak: AtomicUsize,
+    dropped: AtomicU64,
+    spilled: AtomicU64,
+    policy: Mutex<OverflowPolicy>,
+}
+
+const DEFAULT_LIMIT_BYTES: usize = 256 * 1024 * 1024;
+
+/// Global budget shared by all capture buffers
+pub fn budget() -> &'static MediaMemoryBudget {
+    static BUDGET: OnceLock<MediaMemoryBudget> = OnceLock::new();
+    BUDGET.get_or_init(|| MediaMemoryBudget {
+        limit: AtomicUsize::new(DEFAULT_LIMIT_BYTES),
+        used: AtomicUsize::new(0),
+        peak: AtomicUsize::new(0),
+        dropped: AtomicU64::new(0),
+        spilled: AtomicU64::new(0),
+        policy: Mutex::new(OverflowPolicy::default()),
+    })
+}
+
+impl MediaMemoryBudget {
+    /// Apply limit/policy from settings (existing reservations are kept)
+    pub fn configure(&self, limit_mb: usize, policy: OverflowPolicy) {
+        self.limit.store(limit_mb * 1024 * 1024, Ordering::SeqCst);
+        if let Ok(mut current) = self.policy.lock() {
+            *current = policy;
+        }
+    }
+
+    pub fn policy(&self) -> OverflowPolicy {
+        self.policy.lock().map(|p| *p).unwrap_or_default()
+    }
+
+    /// Reserve `bytes`; returns false (reserving nothing) if it would exceed the limit
+    pub fn try_reserve(&self, bytes: usize) -> bool {
+        let limit = self.limit.load(Ordering::SeqCst);
+        let reserved = self.used.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |used| {
+            let next = used.saturating_add(bytes);
+            if next <= limit { Some(next) } else { None }
+        });
+
+        match reserved {
+            Ok(previous) => {
+                self.peak.fetch_max(previous + bytes, Ordering::SeqCst);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    pub fn release(&self, bytes: usize) {
+        let _ = self.used.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |used| {
+            Some(used.saturating_sub(bytes))
+        });
+    }
+
+    pub fn record_dropped(&self, bytes: usize) {
+        self.dropped.fetch_add(bytes as u64, Ordering::SeqCst);
+    }
+
+    pub fn record_spilled(&self, bytes: usize) {
+        self.spilled.fetch_add(bytes as u64, Ordering::SeqCst);
+    }
+
+    pub fn usage(&self) -> MediaBufferUsage {
+        MediaBufferUsage {
+            used_bytes: self.used.load(Ordering::SeqCst),
+            limit_bytes: self.limit.load(Ordering::SeqCst),
+            peak_bytes: self.peak.load(Ordering::SeqCst),
+            dropped_bytes: self.dropped.load(Ordering::SeqCst),
+            spilled_bytes: self.spilled.load(Ordering::SeqCst),
+            policy: self.policy(),
+        }
+    }
+}
+
+/// Append-only temp file of f32 samples used when spilling audio buffers
+pub struct SampleSpillFile {
+    path: PathBuf,
+    samples: usize,
+}
+
+impl SampleSpillFile {
+    pub fn create() -> Result<Self, String> {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+        let dir = std::env::temp_dir().join("taskerino-spill");
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create spill directory: {}", e))?;
+
+        let path = dir.join(format!(
+            "audio-{}-{}.f32",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::SeqCst)
+        ));
+        Ok(Self { path, samples: 0 })
+    }
+
+    pub fn append(&mut self, samples: &[f32]) -> Result<(), String> {
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| format!("Failed to open spill file: {}", e))?;
+
+        let bytes: Vec<u8> = samples.iter().flat_map(|s| s.to_le_bytes()).collect();
+        file.write_all(&bytes)
+            .map_err(|e| format!("Failed to write spill file: {}", e))?;
+
+        self.samples += samples.len();
+        Ok(())
+    }
+
+    /// Read back all spilled samples and delete the file
+    pub fn drain(self) -> Result<Vec<f32>, String> {
+        let mut bytes = Vec::with_capacity(self.samples * 4);
+        std::fs::File::open(&self.path)
+            .and_then(|mut file| file.read_to_end(&mut bytes))
+            .map_err(|e| format!("Failed to read spill file: {}", e))?;
+
+        Ok(bytes
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .collect())
+    }
+}
+
+impl Drop for SampleSpillFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
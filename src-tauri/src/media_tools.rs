@@ -0,0 +1,442 @@
+/**
+ * Media Tools Module
+ *
+ * Locates ffmpeg/ffprobe for merging and remuxing, so they work
+ * without the user installing ffmpeg from a terminal:
+ * - A static build bundled with the app (`<resources>/ffmpeg/`) or shipped
+ *   next to the executable is preferred
+ * - Then the usual Homebrew/MacPorts/system locations, which apps launched
+ *   from Finder don't have on their PATH
+ * - Then PATH
+ *
+ * Found tools are cached; a tool that wasn't found is looked up again on the
+ * next call (so installing it doesn't need a restart).
+ *
+ * `concatenate_audio_files` joins session audio without depending on ffmpeg:
+ * - WAV: samples copied with hound (inputs must share their format)
+ * - M4A: AVFoundation on macOS (any readable input, encoded to AAC), ffmpeg
+ *   elsewhere
+ * - MP3: ffmpeg when available, otherwise the MPEG audio frames are joined
+ *   directly (ID3 tags and VBR header frames are dropped)
+ */
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+use crate::command_metrics;
+
+/// Bundled tools live in this subfolder of the app resources
+const BUNDLED_DIR: &str = "ffmpeg";
+
+/// Install locations checked before PATH
+const KNOWN_DIRS: &[&str] = &["/opt/homebrew/bin", "/usr/local/bin", "/opt/local/bin", "/usr/bin"];
+
+/// How often `run_cancellable` checks for cancellation
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+static RESOURCE_DIR: OnceLock<PathBuf> = OnceLock::new();
+static FOUND: OnceLock<Mutex<HashMap<&'static str, PathBuf>>> = OnceLock::new();
+
+/// Where each tool was found (None if it wasn't)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaToolsStatus {
+    pub ffmpeg: Option<String>,
+    pub ffprobe: Option<String>,
+    /// Whether ffmpeg is the copy bundled with the app
+    pub bundled: bool,
+}
+
+/// Remember the app's resource folder (call once during setup)
+pub fn init(app: &AppHandle) {
+    if let Ok(dir) = app.path().resource_dir() {
+        let _ = RESOURCE_DIR.set(dir);
+    }
+}
+
+fn executable_name(tool: &str) -> String {
+    if cfg!(windows) {
+        format!("{}.exe", tool)
+    } else {
+        tool.to_string()
+    }
+}
+
+fn bundled_path(tool: &str) -> Option<PathBuf> {
+    RESOURCE_DIR.get().map(|dir| dir.join(BUNDLED_DIR).join(executable_name(tool)))
+}
+
+/// Candidate paths in lookup order
+fn candidates(tool: &str) -> Vec<PathBuf> {
+    let name = executable_name(tool);
+    let mut paths: Vec<PathBuf> = bundled_path(tool).into_iter().collect();
+    if let Some(exe_dir) = std::env::current_exe().ok().and_then(|exe| exe.parent().map(Path::to_path_buf)) {
+        paths.push(exe_dir.join(&name));
+    }
+    paths.extend(KNOWN_DIRS.iter().map(|dir| Path::new(dir).join(&name)));
+    if let Some(path_var) = std::env::var_os("PATH") {
+        paths.extend(std::env::split_paths(&path_var).map(|dir| dir.join(&name)));
+    }
+    paths
+}
+
+fn runs(path: &Path) -> bool {
+    path.is_file()
+        && Command::new(path)
+            .arg("-version")
+            .stdin(Stdio::null())
+            .output()
+            .map(|output| output.status.success())
+            .unwrap_or(false)
+}
+
+/// Path of a working `tool` (runs it once per lookup; blocks)
+fn find(tool: &'static str) -> Option<PathBuf> {
+    let found = FOUND.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Some(path) = found.lock().ok().and_then(|found| found.get(tool).cloned()) {
+        return Some(path);
+    }
+    let path = candidates(tool).into_iter().find(|path| runs(path))?;
+    println!("🎞️  [MEDIA TOOLS] Using {} at {:?}", tool, path);
+    if let Ok(mut found) = found.lock() {
+        found.insert(tool, path.clone());
+    }
+    Some(path)
+}
+
+/// ffmpeg to run, if one is bundled or installed (blocks on the first lookup)
+pub fn ffmpeg() -> Option<PathBuf> {
+    find("ffmpeg")
+}
+
+/// ffprobe to run, if one is bundled or installed (blocks on the first lookup)
+pub fn ffprobe() -> Option<PathBuf> {
+    find("ffprobe")
+}
+
+/// Run an ffmpeg command to completion, killing it once `cancelled` returns
+/// true (blocks). Errors include ffmpeg's stderr.
+pub fn run_cancellable(command: &mut Command, cancelled: impl Fn() -> bool) -> Result<(), String> {
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run ffmpeg: {}", e))?;
+
+    // Drain stderr so a chatty ffmpeg can't block on a full pipe
+    let mut stderr = child.stderr.take();
+    let errors = std::thread::spawn(move || {
+        let mut text = String::new();
+        if let Some(stderr) = stderr.as_mut() {
+            let _ = std::io::Read::read_to_string(stderr, &mut text);
+        }
+        text
+    });
+
+    let status = loop {
+        if cancelled() {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err("Cancelled".to_string());
+        }
+        match child.try_wait().map_err(|e| format!("Failed to wait for ffmpeg: {}", e))? {
+            Some(status) => break status,
+            None => std::thread::sleep(CANCEL_POLL_INTERVAL),
+        }
+    };
+    let errors = errors.join().unwrap_or_default();
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("ffmpeg exited with {}: {}", status, errors.trim()))
+    }
+}
+
+fn status() -> MediaToolsStatus {
+    let ffmpeg = ffmpeg();
+    MediaToolsStatus {
+        bundled: ffmpeg.is_some() && ffmpeg == bundled_path("ffmpeg"),
+        ffmpeg: ffmpeg.map(|path| path.to_string_lossy().to_string()),
+        ffprobe: ffprobe().map(|path| path.to_string_lossy().to_string()),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Audio concatenation
+// ---------------------------------------------------------------------------
+
+/// A parsed MPEG audio (Layer III) frame header
+struct FrameHeader {
+    sample_rate: u32,
+    length: usize,
+}
+
+fn parse_frame_header(bytes: &[u8]) -> Option<FrameHeader> {
+    const BITRATES_V1: [u32; 15] = [0, 32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320];
+    const BITRATES_V2: [u32; 15] = [0, 8, 16, 24, 32, 40, 48, 56, 64, 80, 96, 112, 128, 144, 160];
+
+    let header = bytes.get(..4)?;
+    if header[0] != 0xFF || header[1] & 0xE0 != 0xE0 {
+        return None;
+    }
+    let version = (header[1] >> 3) & 0x03; // 0 = 2.5, 2 = 2, 3 = 1
+    let layer = (header[1] >> 1) & 0x03; // 1 = Layer III
+    let bitrate_index = (header[2] >> 4) as usize;
+    let rate_index = ((header[2] >> 2) & 0x03) as usize;
+    let padding = ((header[2] >> 1) & 0x01) as usize;
+    if version == 1 || layer != 1 || bitrate_index == 0 || bitrate_index == 15 || rate_index == 3 {
+        return None;
+    }
+
+    let (bitrate, sample_rate, samples_factor) = match version {
+        3 => (BITRATES_V1[bitrate_index], [44100, 48000, 32000][rate_index], 144),
+        2 => (BITRATES_V2[bitrate_index], [22050, 24000, 16000][rate_index], 72),
+        _ => (BITRATES_V2[bitrate_index], [11025, 12000, 8000][rate_index], 72),
+    };
+    let length = (samples_factor * bitrate * 1000 / sample_rate) as usize + padding;
+    Some(FrameHeader { sample_rate, length })
+}
+
+/// Size of a leading ID3v2 tag
+fn id3v2_size(bytes: &[u8]) -> usize {
+    if bytes.len() < 10 || &bytes[..3] != b"ID3" {
+        return 0;
+    }
+    let size = bytes[6..10].iter().fold(0usize, |size, byte| (size << 7) | (*byte & 0x7F) as usize);
+    let footer = if bytes[5] & 0x10 != 0 { 10 } else { 0 };
+    10 + size + footer
+}
+
+/// Whether a frame is a Xing/Info/VBRI header frame (its totals would be
+/// wrong for the joined file)
+fn is_vbr_header_frame(frame: &[u8]) -> bool {
+    frame.windows(4).take(64).any(|tag| tag == b"Xing" || tag == b"Info" || tag == b"VBRI")
+}
+
+/// Append the audio frames of one MP3 file; returns its sample rate
+fn append_frames(bytes: &[u8], output: &mut Vec<u8>) -> Result<u32, String> {
+    let mut end = bytes.len();
+    if end >= 128 && &bytes[end - 128..end - 125] == b"TAG" {
+        end -= 128; // ID3v1
+    }
+    let bytes = &bytes[..end];
+
+    let mut position = id3v2_size(bytes);
+    let mut sample_rate = None;
+    let mut first = true;
+    while position + 4 <= bytes.len() {
+        let Some(header) = parse_frame_header(&bytes[position..]) else {
+            position += 1;
+            continue;
+        };
+        let frame_end = position + header.length;
+        // A real frame is followed by another frame (or the end of the data)
+        let confirmed = frame_end == bytes.len()
+            || (frame_end <= bytes.len() && parse_frame_header(&bytes[frame_end..]).is_some());
+        if !confirmed || frame_end > bytes.len() {
+            position += 1;
+            continue;
+        }
+
+        let frame = &bytes[position..frame_end];
+        if !(first && is_vbr_header_frame(frame)) {
+            output.extend_from_slice(frame);
+        }
+        first = false;
+        sample_rate.get_or_insert(header.sample_rate);
+        position = frame_end;
+    }
+    sample_rate.ok_or_else(|| "No MP3 audio frames found".to_string())
+}
+
+/// Join MP3 files without ffmpeg (all inputs must share a sample rate)
+fn concatenate_mp3_native(inputs: &[String], output: &Path) -> Result<(), String> {
+    let mut joined = Vec::new();
+    let mut first_rate = None;
+    for input in inputs {
+        let bytes = std::fs::read(input).map_err(|e| format!("Failed to read {}: {}", input, e))?;
+        let rate = append_frames(&bytes, &mut joined).map_err(|e| format!("{}: {}", input, e))?;
+        match first_rate {
+            Some(first) if first != rate => {
+                return Err(format!(
+                    "MP3 files have different sample rates ({} Hz and {} Hz); joining them needs ffmpeg",
+                    first, rate
+                ));
+            }
+            _ => first_rate = Some(rate),
+        }
+    }
+
+    let temp_path = output.with_extension("mp3.tmp");
+    std::fs::write(&temp_path, &joined).map_err(|e| format!("Failed to write {:?}: {}", temp_path, e))?;
+    std::fs::rename(&temp_path, output).map_err(|e| format!("Failed to write {:?}: {}", output, e))
+}
+
+/// Join WAV files with hound (all inputs must share channels, sample rate and
+/// sample format)
+fn concatenate_wav(inputs: &[String], output: &Path) -> Result<(), String> {
+    let open = |input: &String| {
+        hound::WavReader::open(input).map_err(|e| format!("Failed to read WAV {}: {}", input, e))
+    };
+    let spec = open(&inputs[0])?.spec();
+
+    let temp_path = output.with_extension("wav.tmp");
+    let mut writer = hound::WavWriter::create(&temp_path, spec)
+        .map_err(|e| format!("Failed to create {:?}: {}", temp_path, e))?;
+    let result = inputs.iter().try_for_each(|input| {
+        let mut reader = open(input)?;
+        let input_spec = reader.spec();
+        if input_spec != spec {
+            return Err(format!(
+                "{} has a different format ({} ch, {} Hz, {} bit) than the first file ({} ch, {} Hz, {} bit)",
+                input,
+                input_spec.channels,
+                input_spec.sample_rate,
+                input_spec.bits_per_sample,
+                spec.channels,
+                spec.sample_rate,
+                spec.bits_per_sample
+            ));
+        }
+        let write_error = |e: hound::Error| format!("Failed to write {:?}: {}", temp_path, e);
+        match spec.sample_format {
+            hound::SampleFormat::Float => {
+                for sample in reader.samples::<f32>() {
+                    let sample = sample.map_err(|e| format!("Failed to read WAV {}: {}", input, e))?;
+                    writer.write_sample(sample).map_err(write_error)?;
+                }
+            }
+            hound::SampleFormat::Int => {
+                for sample in reader.samples::<i32>() {
+                    let sample = sample.map_err(|e| format!("Failed to read WAV {}: {}", input, e))?;
+                    writer.write_sample(sample).map_err(write_error)?;
+                }
+            }
+        }
+        Ok(())
+    });
+    let result = result.and_then(|()| {
+        writer.finalize().map_err(|e| format!("Failed to write {:?}: {}", temp_path, e))
+    });
+    if let Err(e) = result {
+        let _ = std::fs::remove_file(&temp_path);
+        return Err(e);
+    }
+    std::fs::rename(&temp_path, output).map_err(|e| format!("Failed to write {:?}: {}", output, e))
+}
+
+#[cfg(target_os = "macos")]
+extern "C" {
+    fn screen_recorder_concat_audio(paths: *const std::os::raw::c_char, output: *const std::os::raw::c_char) -> bool;
+}
+
+/// Join audio files of any format AVFoundation reads into an AAC .m4a
+#[cfg(target_os = "macos")]
+fn concatenate_m4a_native(inputs: &[String], output: &Path) -> Result<(), String> {
+    use std::ffi::CString;
+
+    let c_paths = CString::new(inputs.join("\n")).map_err(|_| "Invalid audio path")?;
+    let c_output = CString::new(output.to_str().ok_or("Invalid output path")?)
+        .map_err(|_| "Invalid output path")?;
+    if unsafe { screen_recorder_concat_audio(c_paths.as_ptr(), c_output.as_ptr()) } {
+        Ok(())
+    } else {
+        Err("Failed to join audio files".to_string())
+    }
+}
+
+/// Join files with ffmpeg's concat demuxer; `codec` are the output codec arguments
+fn concatenate_ffmpeg(ffmpeg: &Path, inputs: &[String], output: &Path, codec: &[&str]) -> Result<(), String> {
+    let list_path = output.with_extension("concat.txt");
+    let list: String = inputs
+        .iter()
+        .map(|input| format!("file '{}'\n", input.replace('\'', "'\\''")))
+        .collect();
+    std::fs::write(&list_path, list).map_err(|e| format!("Failed to write {:?}: {}", list_path, e))?;
+
+    let result = Command::new(ffmpeg)
+        .args(["-y", "-v", "error", "-f", "concat", "-safe", "0", "-i"])
+        .arg(&list_path)
+        .args(codec)
+        .arg(output)
+        .stdin(Stdio::null())
+        .output();
+    let _ = std::fs::remove_file(&list_path);
+
+    let output_status = result.map_err(|e| format!("Failed to run ffmpeg: {}", e))?;
+    if output_status.status.success() {
+        Ok(())
+    } else {
+        let _ = std::fs::remove_file(output);
+        Err(format!(
+            "ffmpeg failed to join audio files: {}",
+            String::from_utf8_lossy(&output_status.stderr).trim()
+        ))
+    }
+}
+
+/// Output format of `concatenate_audio_files`
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AudioConcatFormat {
+    /// MP3 inputs, frames copied
+    Mp3,
+    /// WAV inputs with matching formats, samples copied
+    Wav,
+    /// Any audio inputs, encoded to AAC
+    M4a,
+}
+
+/// Join audio files in order (blocks)
+fn concatenate_audio(inputs: &[String], output: &Path, format: AudioConcatFormat) -> Result<(), String> {
+    if inputs.is_empty() {
+        return Err("No audio files to join".to_string());
+    }
+    if let Some(missing) = inputs.iter().find(|input| !Path::new(input).is_file()) {
+        return Err(format!("Audio file not found: {}", missing));
+    }
+    match format {
+        AudioConcatFormat::Wav => concatenate_wav(inputs, output),
+        AudioConcatFormat::Mp3 => match ffmpeg() {
+            Some(ffmpeg) => concatenate_ffmpeg(&ffmpeg, inputs, output, &["-c", "copy"]),
+            None => concatenate_mp3_native(inputs, output),
+        },
+        #[cfg(target_os = "macos")]
+        AudioConcatFormat::M4a => concatenate_m4a_native(inputs, output),
+        #[cfg(not(target_os = "macos"))]
+        AudioConcatFormat::M4a => match ffmpeg() {
+            Some(ffmpeg) => concatenate_ffmpeg(&ffmpeg, inputs, output, &["-c:a", "aac", "-b:a", "192k"]),
+            None => Err("Joining into M4A needs ffmpeg on this platform".to_string()),
+        },
+    }
+}
+
+/// Tauri command: where ffmpeg/ffprobe were found
+#[tauri::command]
+pub async fn get_media_tools() -> Result<MediaToolsStatus, String> {
+    command_metrics::track_async("get_media_tools", async move {
+        tokio::task::spawn_blocking(status)
+            .await
+            .map_err(|e| format!("Media tools task failed: {}", e))
+    }).await
+}
+
+/// Tauri command: join audio files in order into `output_path`
+#[tauri::command]
+pub async fn concatenate_audio_files(
+    input_paths: Vec<String>,
+    output_path: String,
+    format: AudioConcatFormat,
+) -> Result<(), String> {
+    command_metrics::track_async("concatenate_audio_files", async move {
+        tokio::task::spawn_blocking(move || concatenate_audio(&input_paths, Path::new(&output_path), format))
+            .await
+            .map_err(|e| format!("Audio concatenation task failed: {}", e))?
+    }).await
+}
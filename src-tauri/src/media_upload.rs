@@ -0,0 +1,229 @@
+/**
+ * Media Upload Module
+ *
+ * Streamed uploads for large media (multi-hundred-MB recordings, merged audio)
+ * so files never cross the webview bridge as one serialized array:
+ * - `begin_media_upload` opens a temp file and returns an upload id
+ * - `append_media_upload` takes a raw binary body (`upload-id` header) and appends it
+ * - `finish_media_upload` moves the temp file into app data (or hands back the
+ *   temp path for Rust-side processing); `abort_media_upload` discards it
+ */
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tauri::{AppHandle, Manager, State};
+
+use crate::command_metrics;
+use crate::safe_state::SafeState;
+
+struct UploadEntry {
+    path: PathBuf,
+    file: std::fs::File,
+    bytes: u64,
+}
+
+/// Result of a finished upload
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadResult {
+    pub path: String,
+    pub bytes: u64,
+}
+
+/// Managed registry of in-progress uploads
+pub struct UploadRegistry {
+    uploads: SafeState<HashMap<String, UploadEntry>>,
+    next_id: AtomicU64,
+}
+
+fn upload_dir() -> PathBuf {
+    std::env::temp_dir().join("taskerino-uploads")
+}
+
+impl UploadRegistry {
+    pub fn new() -> Self {
+        Self {
+            uploads: SafeState::new("media_uploads", HashMap::new()),
+            next_id: AtomicU64::new(0),
+        }
+    }
+
+    /// Remove temp files left behind by a previous run
+    pub fn clean_stale(&self) {
+        if let Ok(entries) = std::fs::read_dir(upload_dir()) {
+            for entry in entries.flatten() {
+                let _ = std::fs::remove_file(entry.path());
+            }
+        }
+    }
+
+    fn begin(&self) -> Result<String, String> {
+        let dir = upload_dir();
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create upload directory: {}", e))?;
+
+        let upload_id = format!(
+            "upload-{}-{}",
+            chrono::Utc::now().timestamp_millis(),
+            self.next_id.fetch_add(1, Ordering::SeqCst)
+        );
+        let path = dir.join(&upload_id);
+        let file = std::fs::File::create(&path)
+            .map_err(|e| format!("Failed to create upload file: {}", e))?;
+
+        self.uploads.lock().insert(upload_id.clone(), UploadEntry { path, file, bytes: 0 });
+        Ok(upload_id)
+    }
+
+    fn append(&self, upload_id: &str, bytes: &[u8]) -> Result<u64, String> {
+        let mut uploads = self.uploads.lock();
+        let entry = uploads
+            .get_mut(upload_id)
+            .ok_or_else(|| format!("Upload {} not found", upload_id))?;
+
+        entry.file.write_all(bytes)
+            .map_err(|e| format!("Failed to write upload chunk: {}", e))?;
+        entry.bytes += bytes.len() as u64;
+        Ok(entry.bytes)
+    }
+
+    fn take(&self, upload_id: &str) -> Result<UploadEntry, String> {
+        self.uploads
+            .lock()
+            .remove(upload_id)
+            .ok_or_else(|| format!("Upload {} not found", upload_id))
+    }
+}
+
+impl Default for UploadRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Resolve a destination relative to the app data dir, rejecting escapes
+fn resolve_destination(app: &AppHandle, destination: &str) -> Result<PathBuf, String> {
+    let relative = Path::new(destination);
+    if relative.as_os_str().is_empty()
+        || !relative.components().all(|c| matches!(c, Component::Normal(_)))
+    {
+        return Err(format!("Invalid upload destination: {}", destination));
+    }
+
+    let data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+    Ok(data_dir.join(relative))
+}
+
+/// Move a file, falling back to copy + delete across filesystems
+fn move_file(from: &Path, to: &Path) -> Result<(), String> {
+    if let Some(parent) = to.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create destination directory: {}", e))?;
+    }
+    if std::fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    std::fs::copy(from, to)
+        .map_err(|e| format!("Failed to move upload into place: {}", e))?;
+    let _ = std::fs::remove_file(from);
+    Ok(())
+}
+
+/// Tauri command: start an upload, returns its id
+#[tauri::command]
+pub async fn begin_media_upload(
+    uploads: State<'_, Arc<UploadRegistry>>,
+) -> Result<String, String> {
+    command_metrics::track_async("begin_media_upload", async move {
+        let uploads = uploads.inner().clone();
+        tokio::task::spawn_blocking(move || uploads.begin())
+            .await
+            .map_err(|e| format!("Upload task failed: {}", e))?
+    }).await
+}
+
+/// Tauri command: append a raw binary chunk (header `upload-id`); returns bytes written so far
+#[tauri::command]
+pub async fn append_media_upload(
+    request: tauri::ipc::Request<'_>,
+    uploads: State<'_, Arc<UploadRegistry>>,
+) -> Result<u64, String> {
+    command_metrics::track_async("append_media_upload", async move {
+        let upload_id = request
+            .headers()
+            .get("upload-id")
+            .and_then(|value| value.to_str().ok())
+            .ok_or("Missing upload-id header")?
+            .to_string();
+        let bytes = match request.body() {
+            tauri::ipc::InvokeBody::Raw(bytes) => bytes.clone(),
+            tauri::ipc::InvokeBody::Json(_) => return Err("Upload chunks must be sent as raw bytes".to_string()),
+        };
+
+        let uploads = uploads.inner().clone();
+        tokio::task::spawn_blocking(move || uploads.append(&upload_id, &bytes))
+            .await
+            .map_err(|e| format!("Upload task failed: {}", e))?
+    }).await
+}
+
+/// Tauri command: complete an upload
+/// With `destination` (relative to app data) the file is moved there; without it
+/// the temp file path is returned for Rust-side processing (caller owns cleanup)
+#[tauri::command]
+pub async fn finish_media_upload(
+    app: AppHandle,
+    uploads: State<'_, Arc<UploadRegistry>>,
+    upload_id: String,
+    destination: Option<String>,
+) -> Result<UploadResult, String> {
+    command_metrics::track_async("finish_media_upload", async move {
+        let entry = uploads.take(&upload_id)?;
+        let target = destination
+            .map(|destination| resolve_destination(&app, &destination))
+            .transpose()?;
+
+        tokio::task::spawn_blocking(move || {
+            entry.file.sync_all()
+                .map_err(|e| format!("Failed to flush upload: {}", e))?;
+            drop(entry.file);
+
+            let path = match target {
+                Some(target) => {
+                    move_file(&entry.path, &target)?;
+                    target
+                }
+                None => entry.path,
+            };
+
+            println!("📤 [UPLOAD] Finished {} ({} bytes) -> {:?}", upload_id, entry.bytes, path);
+            Ok(UploadResult {
+                path: path.to_string_lossy().to_string(),
+                bytes: entry.bytes,
+            })
+        })
+        .await
+        .map_err(|e| format!("Upload task failed: {}", e))?
+    }).await
+}
+
+/// Tauri command: discard an upload and its temp file
+#[tauri::command]
+pub async fn abort_media_upload(
+    uploads: State<'_, Arc<UploadRegistry>>,
+    upload_id: String,
+) -> Result<(), String> {
+    command_metrics::track_async("abort_media_upload", async move {
+        let entry = uploads.take(&upload_id)?;
+        drop(entry.file);
+        let _ = tokio::fs::remove_file(&entry.path).await;
+        Ok(())
+    }).await
+}
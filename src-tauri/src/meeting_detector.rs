@@ -0,0 +1,397 @@
+/**
+ * Meeting Detector Module
+ *
+ * Notices when a Zoom, Google Meet or Microsoft Teams call is in progress
+ * (macOS) from on-screen window titles and whether the default microphone is
+ * in use by any process:
+ * - Zoom: a "Zoom Meeting" / "Zoom Webinar" window (only exists during a call)
+ * - Teams: a Teams window titled like a meeting or call, with the mic in use
+ * - Meet: a browser window titled "Meet - <code>", with the mic in use
+ *   (while Taskerino itself records audio the mic signal is meaningless, so
+ *   only Zoom's call window counts then)
+ * - Emits `meeting-detected` when a call starts and `meeting-ended` once it
+ *   has been gone for MISSES_BEFORE_END polls
+ * - settings.autoRecord (`get_auto_record_policy` / `set_auto_record_policy`):
+ *   detection on/off, which apps count, and whether to start a session (via
+ *   control_server) when a call is detected
+ *
+ * Window titles need screen recording permission, which Taskerino already
+ * requests for screenshots. Other platforms never detect a meeting.
+ */
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::audio_capture::AudioRecorder;
+use crate::background_tasks::TaskRegistry;
+use crate::command_metrics;
+use crate::control_server;
+use crate::safe_state::SafeState;
+use crate::settings::{AutoRecordSettings, SettingsManager};
+
+const TASK_NAME: &str = "meeting-detector";
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Consecutive polls without the call before `meeting-ended`
+const MISSES_BEFORE_END: u32 = 2;
+
+const BROWSERS: [&str; 8] = [
+    "Google Chrome",
+    "Safari",
+    "Arc",
+    "Microsoft Edge",
+    "Firefox",
+    "Brave Browser",
+    "Chromium",
+    "Vivaldi",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MeetingApp {
+    Zoom,
+    GoogleMeet,
+    Teams,
+}
+
+impl MeetingApp {
+    pub const ALL: [MeetingApp; 3] = [MeetingApp::Zoom, MeetingApp::GoogleMeet, MeetingApp::Teams];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            MeetingApp::Zoom => "Zoom",
+            MeetingApp::GoogleMeet => "Google Meet",
+            MeetingApp::Teams => "Teams",
+        }
+    }
+}
+
+/// `meeting-detected` / `meeting-ended` payload
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DetectedMeeting {
+    pub app: MeetingApp,
+    pub window_title: String,
+    pub mic_in_use: bool,
+    /// RFC 3339
+    pub detected_at: String,
+}
+
+/// On-screen window (owning app name + title), front to back
+struct WindowInfo {
+    owner: String,
+    title: String,
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    owner_pid: i64,
+    /// 0 for normal app windows (menu bar items, panels etc. sit higher)
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    layer: i64,
+}
+
+#[cfg(target_os = "macos")]
+fn on_screen_windows() -> Vec<WindowInfo> {
+    use core_foundation::base::{CFType, TCFType};
+    use core_foundation::dictionary::{CFDictionary, CFDictionaryRef};
+    use core_foundation::number::CFNumber;
+    use core_foundation::string::CFString;
+    use core_graphics::window::{
+        copy_window_info, kCGNullWindowID, kCGWindowLayer, kCGWindowListExcludeDesktopElements,
+        kCGWindowListOptionOnScreenOnly, kCGWindowName, kCGWindowOwnerName, kCGWindowOwnerPID,
+    };
+
+    let Some(windows) = copy_window_info(
+        kCGWindowListOptionOnScreenOnly | kCGWindowListExcludeDesktopElements,
+        kCGNullWindowID,
+    ) else {
+        return Vec::new();
+    };
+
+    let (owner_key, title_key, pid_key, layer_key) = unsafe {
+        (
+            CFString::wrap_under_get_rule(kCGWindowOwnerName),
+            CFString::wrap_under_get_rule(kCGWindowName),
+            CFString::wrap_under_get_rule(kCGWindowOwnerPID),
+            CFString::wrap_under_get_rule(kCGWindowLayer),
+        )
+    };
+    let text = |window: &CFDictionary<CFString, CFType>, key: &CFString| -> Option<String> {
+        window
+            .find(key)
+            .and_then(|value| value.downcast::<CFString>())
+            .map(|value| value.to_string())
+    };
+    let number = |window: &CFDictionary<CFString, CFType>, key: &CFString| -> Option<i64> {
+        window
+            .find(key)
+            .and_then(|value| value.downcast::<CFNumber>())
+            .and_then(|value| value.to_i64())
+    };
+
+    windows
+        .get_all_values()
+        .into_iter()
+        .filter_map(|window| {
+            let window: CFDictionary<CFString, CFType> =
+                unsafe { CFDictionary::wrap_under_get_rule(window as CFDictionaryRef) };
+            Some(WindowInfo {
+                owner: text(&window, &owner_key)?,
+                title: text(&window, &title_key).unwrap_or_default(),
+                owner_pid: number(&window, &pid_key).unwrap_or_default(),
+                layer: number(&window, &layer_key).unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(not(target_os = "macos"))]
+fn on_screen_windows() -> Vec<WindowInfo> {
+    Vec::new()
+}
+
+/// Title of the frontmost normal window owned by `pid` (activity timeline)
+#[cfg(target_os = "macos")]
+pub(crate) fn front_window_title(pid: i64) -> Option<String> {
+    on_screen_windows()
+        .into_iter()
+        .find(|window| window.owner_pid == pid && window.layer == 0)
+        .map(|window| window.title)
+}
+
+/// Whether any process is capturing from the default input device (CoreAudio)
+#[cfg(target_os = "macos")]
+fn mic_in_use() -> bool {
+    use std::os::raw::c_void;
+
+    #[repr(C)]
+    struct AudioObjectPropertyAddress {
+        selector: u32,
+        scope: u32,
+        element: u32,
+    }
+
+    #[link(name = "CoreAudio", kind = "framework")]
+    extern "C" {
+        fn AudioObjectGetPropertyData(
+            object: u32,
+            address: *const AudioObjectPropertyAddress,
+            qualifier_size: u32,
+            qualifier: *const c_void,
+            data_size: *mut u32,
+            data: *mut c_void,
+        ) -> i32;
+    }
+
+    const SYSTEM_OBJECT: u32 = 1;
+    const DEFAULT_INPUT_DEVICE: u32 = u32::from_be_bytes(*b"dIn ");
+    const IS_RUNNING_SOMEWHERE: u32 = u32::from_be_bytes(*b"gone");
+    const SCOPE_GLOBAL: u32 = u32::from_be_bytes(*b"glob");
+    const ELEMENT_MAIN: u32 = 0;
+
+    unsafe {
+        let mut device: u32 = 0;
+        let mut size = std::mem::size_of::<u32>() as u32;
+        let address = AudioObjectPropertyAddress {
+            selector: DEFAULT_INPUT_DEVICE,
+            scope: SCOPE_GLOBAL,
+            element: ELEMENT_MAIN,
+        };
+        let status = AudioObjectGetPropertyData(
+            SYSTEM_OBJECT,
+            &address,
+            0,
+            std::ptr::null(),
+            &mut size,
+            &mut device as *mut u32 as *mut c_void,
+        );
+        if status != 0 || device == 0 {
+            return false;
+        }
+
+        let mut running: u32 = 0;
+        let mut size = std::mem::size_of::<u32>() as u32;
+        let address = AudioObjectPropertyAddress {
+            selector: IS_RUNNING_SOMEWHERE,
+            scope: SCOPE_GLOBAL,
+            element: ELEMENT_MAIN,
+        };
+        let status = AudioObjectGetPropertyData(
+            device,
+            &address,
+            0,
+            std::ptr::null(),
+            &mut size,
+            &mut running as *mut u32 as *mut c_void,
+        );
+        status == 0 && running != 0
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn mic_in_use() -> bool {
+    false
+}
+
+/// Match a call window; `needs_mic` is false for windows that only exist during a call
+fn classify(window: &WindowInfo) -> Option<(MeetingApp, bool)> {
+    let title = window.title.as_str();
+    let lower = title.to_lowercase();
+
+    if window.owner == "zoom.us" && (title == "Zoom Meeting" || title.starts_with("Zoom Webinar")) {
+        return Some((MeetingApp::Zoom, false));
+    }
+    if (window.owner.starts_with("Microsoft Teams") || window.owner == "MSTeams")
+        && (lower.contains("meeting") || lower.contains("call"))
+    {
+        return Some((MeetingApp::Teams, true));
+    }
+    if BROWSERS.contains(&window.owner.as_str()) && title.starts_with("Meet - ") {
+        return Some((MeetingApp::GoogleMeet, true));
+    }
+    None
+}
+
+/// Current call, if any; blocks on window server / CoreAudio queries
+fn detect(apps: &[MeetingApp], own_recording: bool) -> Option<DetectedMeeting> {
+    let candidates: Vec<(MeetingApp, bool, String)> = on_screen_windows()
+        .into_iter()
+        .filter_map(|window| {
+            classify(&window).map(|(app, needs_mic)| (app, needs_mic, window.title))
+        })
+        .filter(|(app, _, _)| apps.contains(app))
+        .collect();
+    if candidates.is_empty() {
+        return None;
+    }
+
+    let mic = mic_in_use();
+    candidates
+        .into_iter()
+        .find(|(_, needs_mic, _)| !needs_mic || (mic && !own_recording))
+        .map(|(app, _, window_title)| DetectedMeeting {
+            app,
+            window_title,
+            mic_in_use: mic,
+            detected_at: chrono::Utc::now().to_rfc3339(),
+        })
+}
+
+/// Managed detector state
+pub struct MeetingDetector {
+    current: SafeState<Option<DetectedMeeting>>,
+}
+
+impl MeetingDetector {
+    pub fn new() -> Self {
+        Self {
+            current: SafeState::new("meeting_detector.current", None),
+        }
+    }
+
+    /// Start polling for calls (no-op work while detection is off)
+    pub fn start(self: &Arc<Self>, app: AppHandle, registry: &TaskRegistry) -> Result<(), String> {
+        let detector = self.clone();
+        registry.spawn(TASK_NAME, |mut shutdown| async move {
+            let mut misses = 0;
+            let mut interval = tokio::time::interval(POLL_INTERVAL);
+            loop {
+                tokio::select! {
+                    _ = shutdown.cancelled() => break,
+                    _ = interval.tick() => {}
+                }
+
+                let policy = app.state::<Arc<SettingsManager>>().get().auto_record;
+                let found = if policy.detect_meetings {
+                    let apps = policy.apps.clone();
+                    let own_recording = app.state::<Arc<AudioRecorder>>().is_recording();
+                    tokio::task::spawn_blocking(move || detect(&apps, own_recording))
+                        .await
+                        .unwrap_or(None)
+                } else {
+                    None
+                };
+
+                match (found, detector.current.get()) {
+                    (Some(meeting), None) => {
+                        misses = 0;
+                        detector.meeting_started(&app, &policy, meeting).await;
+                    }
+                    (Some(_), Some(_)) => misses = 0,
+                    (None, Some(meeting)) => {
+                        misses += 1;
+                        if misses >= MISSES_BEFORE_END || !policy.detect_meetings {
+                            detector.current.set(None);
+                            println!("📞 [MEETING] {} call ended", meeting.app.label());
+                            let _ = app.emit("meeting-ended", &meeting);
+                        }
+                    }
+                    (None, None) => {}
+                }
+            }
+        })
+    }
+
+    async fn meeting_started(&self, app: &AppHandle, policy: &AutoRecordSettings, meeting: DetectedMeeting) {
+        println!("📞 [MEETING] {} call detected: {}", meeting.app.label(), meeting.window_title);
+        self.current.set(Some(meeting.clone()));
+        let _ = app.emit("meeting-detected", &meeting);
+
+        if policy.auto_start {
+            let label = meeting.app.label();
+            let result = control_server::execute(
+                app,
+                "start",
+                json!({
+                    "name": format!("{} meeting", label),
+                    "description": meeting.window_title,
+                    "tags": ["meeting", label],
+                }),
+            )
+            .await;
+            match result {
+                Ok(_) => println!("📞 [MEETING] Auto-started session for {} call", label),
+                Err(e) => println!("📞 [MEETING] Not auto-starting: {}", e),
+            }
+        }
+    }
+}
+
+impl Default for MeetingDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Tauri command to read the auto-record policy
+#[tauri::command]
+pub fn get_auto_record_policy(
+    settings: State<Arc<SettingsManager>>,
+) -> Result<AutoRecordSettings, String> {
+    command_metrics::track("get_auto_record_policy", || {
+        Ok(settings.get().auto_record)
+    })
+}
+
+/// Tauri command to replace the auto-record policy
+#[tauri::command]
+pub fn set_auto_record_policy(
+    app: AppHandle,
+    settings: State<Arc<SettingsManager>>,
+    policy: AutoRecordSettings,
+) -> Result<AutoRecordSettings, String> {
+    command_metrics::track("set_auto_record_policy", || {
+        Ok(settings.update(&app, json!({ "autoRecord": policy }))?.auto_record)
+    })
+}
+
+/// Tauri command to get the call in progress, if any
+#[tauri::command]
+pub fn get_detected_meeting(
+    detector: State<Arc<MeetingDetector>>,
+) -> Result<Option<DetectedMeeting>, String> {
+    command_metrics::track("get_detected_meeting", || {
+        Ok(detector.current.get())
+    })
+}
@@ -0,0 +1,250 @@
+/**
+ * Notifications Module
+ *
+ * Native macOS notifications through UNUserNotificationCenter
+ * (ScreenRecorder/NotificationBridge.swift). Each belongs to a category the
+ * user can turn off in settings.notifications:
+ * - `transcriptionComplete`: a session's queued audio chunks are all
+ *   transcribed after recording stopped (transcription_queue.rs)
+ * - `enrichmentFinished`: sent by the frontend through `send_notification`
+ * - `lowDisk`: free space dropped below the warning level while recording
+ *   (disk_space.rs)
+ * - `sessionReminder`: the "session-reminder" task asks whether a session
+ *   that has been running for sessionReminderHours (and every multiple of it)
+ *   is still intentional
+ *
+ * Notifications need a bundled app and permission
+ * (`request_notification_permission`); without them they are only logged.
+ * Other platforms report `unsupported`.
+ */
+
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+use crate::background_tasks::TaskRegistry;
+use crate::command_metrics;
+use crate::recording_limits::running_session;
+use crate::settings::{NotificationSettings, SettingsManager};
+
+const TASK_NAME: &str = "session-reminder";
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+// FFI declarations for Swift functions
+#[cfg(target_os = "macos")]
+extern "C" {
+    fn notification_authorization_status() -> i32;
+    fn notification_request_access() -> bool;
+    fn notification_post(
+        identifier: *const std::os::raw::c_char,
+        title: *const std::os::raw::c_char,
+        body: *const std::os::raw::c_char,
+        thread_id: *const std::os::raw::c_char,
+    ) -> bool;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum NotificationCategory {
+    TranscriptionComplete,
+    EnrichmentFinished,
+    LowDisk,
+    SessionReminder,
+}
+
+impl NotificationCategory {
+    /// Groups the category's notifications in Notification Center
+    fn thread_id(self) -> &'static str {
+        match self {
+            NotificationCategory::TranscriptionComplete => "transcription",
+            NotificationCategory::EnrichmentFinished => "enrichment",
+            NotificationCategory::LowDisk => "low-disk",
+            NotificationCategory::SessionReminder => "session-reminder",
+        }
+    }
+
+    fn enabled(self, settings: &NotificationSettings) -> bool {
+        match self {
+            NotificationCategory::TranscriptionComplete => settings.transcription_complete,
+            NotificationCategory::EnrichmentFinished => settings.enrichment_finished,
+            NotificationCategory::LowDisk => settings.low_disk,
+            NotificationCategory::SessionReminder => settings.session_reminder,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub enum NotificationPermission {
+    NotDetermined,
+    Denied,
+    Authorized,
+    /// Delivered quietly to Notification Center until the user decides
+    Provisional,
+    Unsupported,
+}
+
+#[cfg(target_os = "macos")]
+fn permission() -> NotificationPermission {
+    match unsafe { notification_authorization_status() } {
+        0 => NotificationPermission::NotDetermined,
+        1 => NotificationPermission::Denied,
+        2 => NotificationPermission::Authorized,
+        3 | 4 => NotificationPermission::Provisional,
+        _ => NotificationPermission::Unsupported,
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn permission() -> NotificationPermission {
+    NotificationPermission::Unsupported
+}
+
+/// Show a notification now (blocks until it is handed to the system)
+#[cfg(target_os = "macos")]
+fn post(category: NotificationCategory, identifier: &str, title: &str, body: &str) -> Result<(), String> {
+    use std::ffi::CString;
+
+    let to_c = |text: &str| CString::new(text).map_err(|_| "Notification text contains a NUL byte".to_string());
+    let identifier = to_c(identifier)?;
+    let title = to_c(title)?;
+    let body = to_c(body)?;
+    let thread_id = to_c(category.thread_id())?;
+    let posted = unsafe {
+        notification_post(identifier.as_ptr(), title.as_ptr(), body.as_ptr(), thread_id.as_ptr())
+    };
+    if posted {
+        Ok(())
+    } else {
+        Err("Notification not delivered (not permitted or app not bundled)".to_string())
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn post(_category: NotificationCategory, _identifier: &str, _title: &str, _body: &str) -> Result<(), String> {
+    Err("Notifications are only available on macOS".to_string())
+}
+
+fn enabled(app: &AppHandle, category: NotificationCategory) -> bool {
+    category.enabled(&app.state::<Arc<SettingsManager>>().get().notifications)
+}
+
+/// Notify in the background if the category is enabled; `key` identifies the
+/// notification within its category (a newer one with the same key replaces it)
+pub fn notify(app: &AppHandle, category: NotificationCategory, key: &str, title: String, body: String) {
+    if !enabled(app, category) {
+        return;
+    }
+    println!("🔔 [NOTIFY] {}: {}", title, body);
+    let identifier = format!("{}:{}", category.thread_id(), key);
+    tauri::async_runtime::spawn_blocking(move || {
+        if let Err(e) = post(category, &identifier, &title, &body) {
+            eprintln!("⚠️  [NOTIFY] {}", e);
+        }
+    });
+}
+
+/// "45m", "4h", "4h 30m"
+fn format_elapsed(elapsed: Duration) -> String {
+    let minutes = elapsed.as_secs() / 60;
+    match (minutes / 60, minutes % 60) {
+        (0, minutes) => format!("{}m", minutes),
+        (hours, 0) => format!("{}h", hours),
+        (hours, minutes) => format!("{}h {}m", hours, minutes),
+    }
+}
+
+/// Start the long-running session reminder
+pub fn start(app: AppHandle, registry: &TaskRegistry) -> Result<(), String> {
+    registry.spawn(TASK_NAME, |mut shutdown| async move {
+        // Session being timed, when it was first seen and reminders sent for it
+        let mut current: Option<(String, Instant, u32)> = None;
+        let mut interval = tokio::time::interval(CHECK_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+
+            let Some(session_id) = running_session(&app) else {
+                current = None;
+                continue;
+            };
+            let (timed_id, started, reminders) = current
+                .get_or_insert_with(|| (session_id.clone(), Instant::now(), 0));
+            if *timed_id != session_id {
+                *timed_id = session_id;
+                *started = Instant::now();
+                *reminders = 0;
+                continue;
+            }
+
+            let settings = app.state::<Arc<SettingsManager>>().get().notifications;
+            let every = Duration::from_secs_f64(settings.session_reminder_hours * 3600.0);
+            let elapsed = started.elapsed();
+            if elapsed < every * (*reminders + 1) {
+                continue;
+            }
+            // Skip reminders missed while asleep (or after lowering the setting)
+            *reminders = (elapsed.as_secs_f64() / every.as_secs_f64()) as u32;
+            notify(
+                &app,
+                NotificationCategory::SessionReminder,
+                timed_id,
+                format!("Recording for {}", format_elapsed(elapsed)),
+                "Still intentional? Stop the session from the menu bar if not.".to_string(),
+            );
+        }
+    })
+}
+
+/// Tauri command to check notification permission
+#[tauri::command]
+pub fn get_notification_permission() -> Result<NotificationPermission, String> {
+    command_metrics::track("get_notification_permission", || Ok(permission()))
+}
+
+/// Tauri command to request notification permission (shows the system dialog once)
+#[tauri::command]
+pub async fn request_notification_permission() -> Result<bool, String> {
+    command_metrics::track_async("request_notification_permission", async move {
+        #[cfg(target_os = "macos")]
+        {
+            tokio::task::spawn_blocking(|| unsafe { notification_request_access() })
+                .await
+                .map_err(|e| format!("Notification permission task failed: {}", e))
+        }
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err("Notifications are only available on macOS".to_string())
+        }
+    }).await
+}
+
+/// Tauri command to show a notification from the frontend (e.g. enrichment
+/// finished); returns false if the category is turned off
+#[tauri::command]
+pub async fn send_notification(
+    app: AppHandle,
+    category: NotificationCategory,
+    title: String,
+    body: String,
+    key: Option<String>,
+) -> Result<bool, String> {
+    command_metrics::track_async("send_notification", async move {
+        if !enabled(&app, category) {
+            return Ok(false);
+        }
+        let identifier = format!(
+            "{}:{}",
+            category.thread_id(),
+            key.unwrap_or_else(|| chrono::Utc::now().timestamp_millis().to_string())
+        );
+        tokio::task::spawn_blocking(move || post(category, &identifier, &title, &body))
+            .await
+            .map_err(|e| format!("Notification task failed: {}", e))??;
+        Ok(true)
+    }).await
+}
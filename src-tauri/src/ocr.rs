@@ -0,0 +1,181 @@
+/**
+ * OCR Module
+ *
+ * Recognizes text in session screenshots (Vision, via OcrBridge.swift) so
+ * `search_sessions` matches text that only appeared on screen:
+ * - Text is stored next to the attachment as `attachments/{id}.ocr.txt`
+ *   (empty when nothing was recognized, so it isn't retried)
+ * - The "screenshot-ocr" background task works through the active profile's
+ *   screenshots without text, newest sessions first, MAX_PER_PASS at a time
+ * - `extract_text_from_screenshot` (re)runs recognition for one attachment
+ * - After storing text the session index is invalidated, so the next sync
+ *   folds it into the sessions' search text (session_index.rs)
+ *
+ * Recognition runs on-device and is only available on macOS.
+ */
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager, State};
+
+use crate::attachment_metadata::read_attachment_bytes;
+use crate::background_tasks::TaskRegistry;
+use crate::command_metrics;
+use crate::profiles;
+use crate::session_index::SessionIndex;
+use crate::session_storage;
+
+const TASK_NAME: &str = "screenshot-ocr";
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Screenshots recognized per background pass (~0.2-1s each)
+const MAX_PER_PASS: usize = 20;
+
+#[cfg(target_os = "macos")]
+extern "C" {
+    fn ocr_recognize_text(bytes: *const u8, length: usize) -> *const std::os::raw::c_char;
+}
+
+fn text_path(attachments_dir: &Path, id: &str) -> PathBuf {
+    attachments_dir.join(format!("{}.ocr.txt", id))
+}
+
+/// Stored OCR text for an attachment, if it has been recognized
+pub fn read_text(attachments_dir: &Path, id: &str) -> Option<String> {
+    std::fs::read_to_string(text_path(attachments_dir, id)).ok()
+}
+
+/// Recognized lines (newline separated) in encoded image bytes; blocks on Vision
+#[cfg(target_os = "macos")]
+fn recognize_text(bytes: &[u8]) -> Result<String, String> {
+    let pointer = unsafe { ocr_recognize_text(bytes.as_ptr(), bytes.len()) };
+    if pointer.is_null() {
+        return Err("Text recognition failed".to_string());
+    }
+    let text = unsafe { std::ffi::CStr::from_ptr(pointer).to_string_lossy().into_owned() };
+
+    // Free the C string (allocated by Swift's strdup)
+    unsafe {
+        libc::free(pointer as *mut libc::c_void);
+    }
+
+    Ok(text)
+}
+
+#[cfg(not(target_os = "macos"))]
+fn recognize_text(_bytes: &[u8]) -> Result<String, String> {
+    Err("Text recognition is only available on macOS".to_string())
+}
+
+/// Recognize and store the text of one attachment
+fn extract_and_store(attachments_dir: &Path, id: &str) -> Result<String, String> {
+    let meta: serde_json::Value = std::fs::read_to_string(attachments_dir.join(format!("{}.meta.json", id)))
+        .map_err(|e| format!("Failed to read metadata for attachment {}: {}", id, e))
+        .and_then(|content| {
+            serde_json::from_str(&content).map_err(|e| format!("Failed to parse metadata: {}", e))
+        })?;
+    let bytes = read_attachment_bytes(attachments_dir, id, &meta)?;
+    let text = recognize_text(&bytes)?;
+
+    std::fs::write(text_path(attachments_dir, id), &text)
+        .map_err(|e| format!("Failed to write OCR text for {}: {}", id, e))?;
+    Ok(text)
+}
+
+/// Screenshot attachments without stored text, newest sessions first
+fn pending_screenshots(data_dir: &Path, attachments_dir: &Path) -> Result<Vec<String>, String> {
+    let mut sessions = session_storage::read_sessions(data_dir)?;
+    sessions.sort_by(|a, b| b.start_time.cmp(&a.start_time));
+    Ok(sessions
+        .into_iter()
+        .flat_map(|session| session.screenshots.unwrap_or_default())
+        .map(|screenshot| screenshot.attachment_id)
+        .filter(|id| {
+            attachments_dir.join(format!("{}.meta.json", id)).exists() && !text_path(attachments_dir, id).exists()
+        })
+        .collect())
+}
+
+/// One background pass; returns how many screenshots were recognized
+fn run_pass(data_dir: &Path, is_cancelled: impl Fn() -> bool) -> Result<usize, String> {
+    let attachments_dir = data_dir.join("attachments");
+    let mut recognized = 0;
+    for id in pending_screenshots(data_dir, &attachments_dir)?.into_iter().take(MAX_PER_PASS) {
+        if is_cancelled() {
+            break;
+        }
+        match extract_and_store(&attachments_dir, &id) {
+            Ok(_) => recognized += 1,
+            Err(e) => {
+                // Mark it done so an undecodable screenshot isn't retried every pass
+                eprintln!("⚠️  [OCR] Skipping screenshot {}: {}", id, e);
+                let _ = std::fs::write(text_path(&attachments_dir, &id), "");
+            }
+        }
+    }
+    Ok(recognized)
+}
+
+/// Start recognizing text in session screenshots in the background (macOS only)
+pub fn start(app: AppHandle, registry: &TaskRegistry) -> Result<(), String> {
+    if !cfg!(target_os = "macos") {
+        return Ok(());
+    }
+
+    registry.spawn(TASK_NAME, |mut shutdown| async move {
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+
+            let data_dir = match profiles::profile_data_dir(&app) {
+                Ok(data_dir) => data_dir,
+                Err(e) => {
+                    eprintln!("⚠️  [OCR] {}", e);
+                    continue;
+                }
+            };
+            let index = app.state::<Arc<SessionIndex>>().inner().clone();
+            let cancel = shutdown.clone();
+            let result = tokio::task::spawn_blocking(move || {
+                let start = Instant::now();
+                let recognized = run_pass(&data_dir, || cancel.is_cancelled())?;
+                if recognized > 0 {
+                    index.invalidate(&data_dir)?;
+                    println!("🔤 [OCR] Recognized text in {} screenshot(s) in {:?}", recognized, start.elapsed());
+                }
+                Ok::<_, String>(())
+            })
+            .await;
+            match result {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => eprintln!("⚠️  [OCR] Background pass failed: {}", e),
+                Err(e) => eprintln!("⚠️  [OCR] Background task failed: {}", e),
+            }
+        }
+    })
+}
+
+/// Tauri command to recognize (or re-recognize) the text in a screenshot
+/// attachment; the text is stored and returned
+#[tauri::command]
+pub async fn extract_text_from_screenshot(
+    attachment_id: String,
+    app_handle: AppHandle,
+    index: State<'_, Arc<SessionIndex>>,
+) -> Result<String, String> {
+    command_metrics::track_async("extract_text_from_screenshot", async move {
+        let data_dir = profiles::profile_data_dir(&app_handle)?;
+        let index = index.inner().clone();
+        tokio::task::spawn_blocking(move || {
+            let text = extract_and_store(&data_dir.join("attachments"), &attachment_id)?;
+            index.invalidate(&data_dir)?;
+            Ok(text)
+        })
+        .await
+        .map_err(|e| format!("OCR task failed: {}", e))?
+    }).await
+}
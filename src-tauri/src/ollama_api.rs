@@ -0,0 +1,209 @@
+/**
+ * Ollama API Module
+ *
+ * Local LLM provider, parallel to claude_api.rs / openai_api.rs, so session
+ * analysis and summarization can run fully offline:
+ * - Talks to an Ollama server (`/api/chat`) at settings.ollama.baseUrl, or a
+ *   per-request `baseUrl` override
+ * - No API key and no AI budget accounting (local inference has no cost)
+ * - Streaming emits `ollama-stream-{id}` events: `content_delta` per chunk,
+ *   then `stream_end` with token counts (or `error`)
+ */
+
+use crate::ai_client;
+use crate::ai_types::*;
+use crate::command_metrics;
+use crate::redaction::{eprintln_redacted, println_redacted};
+use crate::settings::SettingsManager;
+use futures_util::StreamExt;
+use serde_json::json;
+use std::sync::Arc;
+use tauri::{Emitter, Manager};
+
+/// Resolve the server for a request (trailing slashes trimmed)
+fn base_url(app: &tauri::AppHandle, request: &OllamaChatRequest) -> String {
+    request
+        .base_url
+        .clone()
+        .unwrap_or_else(|| app.state::<Arc<SettingsManager>>().get().ollama.base_url)
+        .trim_end_matches('/')
+        .to_string()
+}
+
+fn request_body(request: &OllamaChatRequest, stream: bool) -> serde_json::Value {
+    let mut messages = Vec::with_capacity(request.messages.len() + 1);
+    if let Some(system) = &request.system {
+        messages.push(json!({ "role": "system", "content": system }));
+    }
+    messages.extend(request.messages.iter().map(|message| json!(message)));
+
+    let mut request_body = json!({
+        "model": request.model,
+        "messages": messages,
+        "stream": stream,
+    });
+
+    let mut options = serde_json::Map::new();
+    if let Some(temperature) = request.temperature {
+        options.insert("temperature".to_string(), json!(temperature));
+    }
+    if let Some(max_tokens) = request.max_tokens {
+        options.insert("num_predict".to_string(), json!(max_tokens));
+    }
+    if !options.is_empty() {
+        request_body["options"] = json!(options);
+    }
+
+    if let Some(format) = &request.format {
+        request_body["format"] = format.clone();
+    }
+
+    request_body
+}
+
+/// POST /api/chat, mapping connection and HTTP errors to actionable messages
+async fn send(base_url: &str, request_body: &serde_json::Value) -> Result<reqwest::Response, String> {
+    let client = ai_client::http_client()?;
+
+    let response = client
+        .post(format!("{}/api/chat", base_url))
+        .json(request_body)
+        .send()
+        .await
+        .map_err(|e| {
+            if e.is_connect() {
+                format!("Could not connect to Ollama at {}. Is Ollama running?", base_url)
+            } else {
+                format!("Ollama request failed: {}", e)
+            }
+        })?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+
+        // Ollama returns {"error": "..."} (e.g. model not pulled)
+        let message = serde_json::from_str::<serde_json::Value>(&error_text)
+            .ok()
+            .and_then(|body| body["error"].as_str().map(|s| s.to_string()))
+            .unwrap_or(error_text);
+        return Err(format!("Ollama error ({}): {}", status, message));
+    }
+
+    Ok(response)
+}
+
+/// Ollama chat completion (non-streaming)
+#[tauri::command]
+pub async fn ollama_chat_completion(
+    app: tauri::AppHandle,
+    request: OllamaChatRequest,
+) -> Result<OllamaChatResponse, String> {
+    command_metrics::track_async("ollama_chat_completion", async move {
+        send_chat_completion(&app, &request).await
+    }).await
+}
+
+/// Non-streaming completion; also used by the AI router
+pub async fn send_chat_completion(
+    app: &tauri::AppHandle,
+    request: &OllamaChatRequest,
+) -> Result<OllamaChatResponse, String> {
+    let base_url = base_url(app, request);
+    let response = send(&base_url, &request_body(request, false)).await?;
+
+    let ollama_response: OllamaChatResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))?;
+
+    if ollama_response.done_reason.as_deref() == Some("length") {
+        eprintln_redacted!("⚠️  WARNING: Ollama response truncated at max_tokens ({:?})", request.max_tokens);
+    }
+
+    Ok(ollama_response)
+}
+
+/// Ollama streaming chat completion
+/// Events are emitted on `ollama-stream-{stream_id}`
+#[tauri::command]
+pub async fn ollama_chat_completion_stream(
+    app: tauri::AppHandle,
+    stream_id: String,
+    request: OllamaChatRequest,
+) -> Result<(), String> {
+    command_metrics::track_async("ollama_chat_completion_stream", async move {
+        let base_url = base_url(&app, &request);
+
+        // Spawn async task to handle streaming
+        tauri::async_runtime::spawn(async move {
+            let event = format!("ollama-stream-{}", stream_id);
+            if let Err(e) = stream_ollama_response(&app, &event, &base_url, request).await {
+                eprintln_redacted!("Ollama streaming error: {}", e);
+                let _ = app.emit(&event, json!({ "type": "error", "error": { "message": e } }));
+            }
+        });
+
+        Ok(())
+    }).await
+}
+
+/// Internal function to forward Ollama's NDJSON stream as events
+async fn stream_ollama_response(
+    app: &tauri::AppHandle,
+    event: &str,
+    base_url: &str,
+    request: OllamaChatRequest,
+) -> Result<(), String> {
+    let response = send(base_url, &request_body(&request, true)).await?;
+    println_redacted!("[Ollama] Streaming {} from {}", request.model, base_url);
+
+    let mut stream = response.bytes_stream();
+    let mut buffer: Vec<u8> = Vec::new();
+
+    while let Some(chunk_result) = stream.next().await {
+        let chunk = chunk_result.map_err(|e| format!("Stream error: {}", e))?;
+        buffer.extend_from_slice(&chunk);
+
+        // One JSON object per line; keep any partial line for the next chunk
+        while let Some(line_end) = buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = buffer.drain(..=line_end).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let data: serde_json::Value = match serde_json::from_str(line) {
+                Ok(data) => data,
+                Err(e) => {
+                    eprintln_redacted!("Failed to parse Ollama stream line: {}", e);
+                    continue;
+                }
+            };
+
+            if let Some(error) = data["error"].as_str() {
+                return Err(format!("Ollama error: {}", error));
+            }
+
+            if let Some(text) = data["message"]["content"].as_str().filter(|text| !text.is_empty()) {
+                let _ = app.emit(event, json!({ "type": "content_delta", "text": text }));
+            }
+
+            if data["done"].as_bool() == Some(true) {
+                let _ = app.emit(event, json!({
+                    "type": "stream_end",
+                    "doneReason": data["done_reason"],
+                    "promptEvalCount": data["prompt_eval_count"],
+                    "evalCount": data["eval_count"],
+                }));
+                return Ok(());
+            }
+        }
+    }
+
+    Err("Ollama stream ended before completion".to_string())
+}
@@ -1,10 +1,15 @@
+use crate::ai_budget::{AiProvider, BudgetManager, UsageRecord};
+use crate::ai_client;
 use crate::ai_types::*;
-use reqwest::Client;
+use crate::command_metrics;
+use crate::api_keys;
+use crate::redaction;
+use futures_util::StreamExt;
 use serde_json::json;
-use tauri_plugin_store::StoreExt;
-use std::time::Duration;
+use std::sync::Arc;
+use tauri::Manager;
 
-const OPENAI_API_BASE: &str = "https://api.openai.com/v1";
+pub(crate) const OPENAI_API_BASE: &str = "https://api.openai.com/v1";
 
 /// Helper function to detect audio format from base64 data URL
 fn detect_audio_format(base64_data: &str) -> Result<(&str, Vec<u8>), String> {
@@ -29,8 +34,71 @@ fn detect_audio_format(base64_data: &str) -> Result<(&str, Vec<u8>), String> {
         )
         .map_err(|e| format!("Failed to decode base64: {}", e))?;
         Ok(("mp3", bytes))
+    } else if let Some(data_part) = base64_data.strip_prefix("data:audio/flac;base64,") {
+        let bytes = base64::Engine::decode(
+            &base64::engine::general_purpose::STANDARD,
+            data_part,
+        )
+        .map_err(|e| format!("Failed to decode base64: {}", e))?;
+        Ok(("flac", bytes))
+    } else if let Some(data_part) = base64_data.strip_prefix("data:audio/ogg;base64,") {
+        let bytes = base64::Engine::decode(
+            &base64::engine::general_purpose::STANDARD,
+            data_part,
+        )
+        .map_err(|e| format!("Failed to decode base64: {}", e))?;
+        Ok(("ogg", bytes))
     } else {
-        Err("Unsupported audio format. Only WAV and MP3 are supported.".to_string())
+        Err("Unsupported audio format. Only WAV, MP3, FLAC and Ogg Opus are supported.".to_string())
+    }
+}
+
+/// Rough audio duration in minutes for Whisper cost accounting
+/// (WAV chunks are 16kHz mono 16-bit, FLAC ~50% of WAV; Ogg Opus and MP3
+/// chunks are encoded at 24kbps and 32kbps, so other MP3s come out longer)
+fn estimate_audio_minutes(format: &str, byte_len: usize) -> f64 {
+    let bytes_per_second = match format {
+        "wav" => 32_000.0,
+        "ogg" => 3_000.0,
+        "mp3" => 4_000.0,
+        _ => 16_000.0,
+    };
+    byte_len as f64 / bytes_per_second / 60.0
+}
+
+/// Streaming transcription model (Whisper doesn't support `stream`)
+const STREAMING_TRANSCRIPTION_MODEL: &str = "gpt-4o-mini-transcribe";
+
+/// Transcription upload form; rebuilt for every attempt since forms can't be cloned
+fn transcription_form(model: &str, audio_bytes: &[u8], format: &str) -> reqwest::multipart::Form {
+    let mime = match format {
+        "mp3" => "audio/mpeg",
+        "flac" => "audio/flac",
+        "ogg" => "audio/ogg",
+        _ => "audio/wav",
+    };
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert(reqwest::header::CONTENT_TYPE, reqwest::header::HeaderValue::from_static(mime));
+
+    reqwest::multipart::Form::new()
+        .part(
+            "file",
+            reqwest::multipart::Part::bytes(audio_bytes.to_vec())
+                .file_name(format!("audio.{}", format))
+                .headers(headers),
+        )
+        .text("model", model.to_string())
+        .text("language", "en")
+}
+
+fn whisper_form(audio_bytes: &[u8], format: &str, word_timestamps: bool) -> reqwest::multipart::Form {
+    let form = transcription_form("whisper-1", audio_bytes, format);
+
+    if word_timestamps {
+        form.text("response_format", "verbose_json")
+            .text("timestamp_granularities[]", "word")
+    } else {
+        form
     }
 }
 
@@ -39,45 +107,48 @@ fn detect_audio_format(base64_data: &str) -> Result<(&str, Vec<u8>), String> {
 pub async fn openai_transcribe_audio(
     app: tauri::AppHandle,
     audio_base64: String,
+    priority: Option<RequestPriority>,
 ) -> Result<String, String> {
-    let store = app.store("api_keys.json")
-        .map_err(|e| format!("Failed to access store: {}", e))?;
-
-    let api_key = match store.get("openai_api_key") {
-        Some(value) => value.as_str()
-            .ok_or("OpenAI API key not set. Please add your API key in Settings.")?
-            .to_string(),
-        None => return Err("OpenAI API key not set. Please add your API key in Settings.".to_string())
-    };
+    command_metrics::track_async("openai_transcribe_audio", async move {
+        transcribe_audio(app, audio_base64, priority)
+            .await
+            .map_err(|e| redaction::redact(&e))
+    }).await
+}
 
+async fn transcribe_audio(
+    app: tauri::AppHandle,
+    audio_base64: String,
+    priority: Option<RequestPriority>,
+) -> Result<String, String> {
     let (format, audio_bytes) = detect_audio_format(&audio_base64)?;
+    transcribe_bytes(&app, format, &audio_bytes, priority.unwrap_or_default()).await
+}
 
-    let client = Client::builder()
-        .timeout(Duration::from_secs(1200))         // 20 min total timeout (audio analysis can be slow)
-        .connect_timeout(Duration::from_secs(30))   // 30 sec to establish connection
-        .read_timeout(Duration::from_secs(900))     // 15 min to read response
-        .build()
-        .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
+/// Whisper transcription of raw audio ("wav", "mp3", "flac" or "ogg"); also used by
+/// the transcription queue
+pub async fn transcribe_bytes(
+    app: &tauri::AppHandle,
+    format: &str,
+    audio_bytes: &[u8],
+    priority: RequestPriority,
+) -> Result<String, String> {
+    let api_key = api_keys::get_api_key(app, "openai_api_key")?
+        .ok_or("OpenAI API key not set. Please add your API key in Settings.")?;
 
-    // Create multipart form for Whisper API
-    let form = reqwest::multipart::Form::new()
-        .part(
-            "file",
-            reqwest::multipart::Part::bytes(audio_bytes)
-                .file_name(format!("audio.{}", format))
-                .mime_str(&format!("audio/{}", if format == "mp3" { "mpeg" } else { format }))
-                .map_err(|e| format!("Failed to set mime type: {}", e))?,
-        )
-        .text("model", "whisper-1")
-        .text("language", "en");
-
-    let response = client
-        .post(&format!("{}/audio/transcriptions", OPENAI_API_BASE))
-        .header("Authorization", format!("Bearer {}", api_key))
-        .multipart(form)
-        .send()
-        .await
-        .map_err(|e| format!("OpenAI API request failed: {}", e))?;
+    let budget = app.state::<Arc<BudgetManager>>();
+    budget.check(AiProvider::OpenAI, priority)?;
+
+    let audio_minutes = estimate_audio_minutes(format, audio_bytes.len());
+
+    let response = ai_client::send_with_retry(app, "openai", |client| {
+        client
+            .post(format!("{}/audio/transcriptions", OPENAI_API_BASE))
+            .header("Authorization", format!("Bearer {}", api_key))
+            .multipart(whisper_form(audio_bytes, format, false))
+    })
+    .await
+    .map_err(|e| format!("OpenAI API request failed: {}", e))?;
 
     let status = response.status();
     if status.as_u16() == 401 {
@@ -97,6 +168,11 @@ pub async fn openai_transcribe_audio(
         .await
         .map_err(|e| format!("Failed to parse response: {}", e))?;
 
+    budget.record(app, AiProvider::OpenAI, "whisper-1", &UsageRecord {
+        audio_minutes,
+        ..Default::default()
+    });
+
     let mut transcription = json_response["text"]
         .as_str()
         .ok_or("No transcription in response")?
@@ -110,52 +186,127 @@ pub async fn openai_transcribe_audio(
     Ok(transcription)
 }
 
+/// Streaming transcription of a short WAV slice (live transcript mode):
+/// `on_delta` gets text as it's generated; returns the full text.
+/// `prompt` is the preceding transcript, so slices read as one text.
+pub async fn transcribe_stream(
+    app: &tauri::AppHandle,
+    wav_bytes: &[u8],
+    prompt: &str,
+    mut on_delta: impl FnMut(&str),
+) -> Result<String, String> {
+    let api_key = api_keys::get_api_key(app, "openai_api_key")?
+        .ok_or("OpenAI API key not set. Please add your API key in Settings.")?;
+
+    let budget = app.state::<Arc<BudgetManager>>();
+    budget.check(AiProvider::OpenAI, RequestPriority::User)?;
+
+    let audio_minutes = estimate_audio_minutes("wav", wav_bytes.len());
+
+    let response = ai_client::send_with_retry(app, "openai", |client| {
+        let mut form = transcription_form(STREAMING_TRANSCRIPTION_MODEL, wav_bytes, "wav")
+            .text("stream", "true");
+        if !prompt.is_empty() {
+            form = form.text("prompt", prompt.to_string());
+        }
+        client
+            .post(format!("{}/audio/transcriptions", OPENAI_API_BASE))
+            .header("Authorization", format!("Bearer {}", api_key))
+            .multipart(form)
+    })
+    .await
+    .map_err(|e| format!("OpenAI API request failed: {}", e))?;
+
+    let status = response.status();
+    if status.as_u16() == 401 {
+        return Err("Invalid OpenAI API key. Please check your key in Settings.".to_string());
+    } else if status.as_u16() == 429 {
+        return Err("OpenAI rate limit exceeded. Please try again later.".to_string());
+    } else if !status.is_success() {
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("OpenAI API error ({}): {}", status, error_text));
+    }
+
+    // SSE: transcript.text.delta events, then transcript.text.done with the full text
+    let mut stream = response.bytes_stream();
+    let mut buffer = String::new();
+    let mut text = String::new();
+    while let Some(chunk_result) = stream.next().await {
+        let chunk = chunk_result.map_err(|e| format!("Stream error: {}", e))?;
+        buffer.push_str(&String::from_utf8_lossy(&chunk).replace("\r\n", "\n"));
+
+        while let Some(event_end) = buffer.find("\n\n") {
+            let sse_event = buffer[..event_end].to_string();
+            buffer = buffer[event_end + 2..].to_string();
+
+            for data in sse_event.lines().filter_map(|line| line.strip_prefix("data: ")) {
+                let Ok(event) = serde_json::from_str::<serde_json::Value>(data) else { continue };
+                match event["type"].as_str() {
+                    Some("transcript.text.delta") => {
+                        if let Some(delta) = event["delta"].as_str() {
+                            text.push_str(delta);
+                            on_delta(delta);
+                        }
+                    }
+                    Some("transcript.text.done") => {
+                        if let Some(done) = event["text"].as_str() {
+                            text = done.to_string();
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    budget.record(app, AiProvider::OpenAI, STREAMING_TRANSCRIPTION_MODEL, &UsageRecord {
+        audio_minutes,
+        ..Default::default()
+    });
+
+    Ok(text)
+}
+
 /// Transcribe audio with word-level timestamps using OpenAI Whisper
 #[tauri::command]
 pub async fn openai_transcribe_audio_with_timestamps(
     app: tauri::AppHandle,
     audio_base64: String,
+    priority: Option<RequestPriority>,
 ) -> Result<WhisperTranscriptionResponse, String> {
-    let store = app.store("api_keys.json")
-        .map_err(|e| format!("Failed to access store: {}", e))?;
-
-    let api_key = match store.get("openai_api_key") {
-        Some(value) => value.as_str()
-            .ok_or("OpenAI API key not set. Please add your API key in Settings.")?
-            .to_string(),
-        None => return Err("OpenAI API key not set. Please add your API key in Settings.".to_string())
-    };
+    command_metrics::track_async("openai_transcribe_audio_with_timestamps", async move {
+        transcribe_audio_with_timestamps(app, audio_base64, priority)
+            .await
+            .map_err(|e| redaction::redact(&e))
+    }).await
+}
 
-    let (format, audio_bytes) = detect_audio_format(&audio_base64)?;
+async fn transcribe_audio_with_timestamps(
+    app: tauri::AppHandle,
+    audio_base64: String,
+    priority: Option<RequestPriority>,
+) -> Result<WhisperTranscriptionResponse, String> {
+    let api_key = api_keys::get_api_key(&app, "openai_api_key")?
+        .ok_or("OpenAI API key not set. Please add your API key in Settings.")?;
 
-    let client = Client::builder()
-        .timeout(Duration::from_secs(1200))         // 20 min total timeout (audio analysis can be slow)
-        .connect_timeout(Duration::from_secs(30))   // 30 sec to establish connection
-        .read_timeout(Duration::from_secs(900))     // 15 min to read response
-        .build()
-        .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
+    let budget = app.state::<Arc<BudgetManager>>();
+    budget.check(AiProvider::OpenAI, priority.unwrap_or_default())?;
 
-    // Create multipart form for Whisper API with verbose JSON and word timestamps
-    let form = reqwest::multipart::Form::new()
-        .part(
-            "file",
-            reqwest::multipart::Part::bytes(audio_bytes)
-                .file_name(format!("audio.{}", format))
-                .mime_str(&format!("audio/{}", if format == "mp3" { "mpeg" } else { format }))
-                .map_err(|e| format!("Failed to set mime type: {}", e))?,
-        )
-        .text("model", "whisper-1")
-        .text("language", "en")
-        .text("response_format", "verbose_json")
-        .text("timestamp_granularities[]", "word");
-
-    let response = client
-        .post(&format!("{}/audio/transcriptions", OPENAI_API_BASE))
-        .header("Authorization", format!("Bearer {}", api_key))
-        .multipart(form)
-        .send()
-        .await
-        .map_err(|e| format!("OpenAI API request failed: {}", e))?;
+    let (format, audio_bytes) = detect_audio_format(&audio_base64)?;
+    let audio_minutes = estimate_audio_minutes(format, audio_bytes.len());
+
+    // Verbose JSON with word timestamps
+    let response = ai_client::send_with_retry(&app, "openai", |client| {
+        client
+            .post(format!("{}/audio/transcriptions", OPENAI_API_BASE))
+            .header("Authorization", format!("Bearer {}", api_key))
+            .multipart(whisper_form(&audio_bytes, format, true))
+    })
+    .await
+    .map_err(|e| format!("OpenAI API request failed: {}", e))?;
 
     let status = response.status();
     if status.as_u16() == 401 {
@@ -175,6 +326,11 @@ pub async fn openai_transcribe_audio_with_timestamps(
         .await
         .map_err(|e| format!("Failed to parse response: {}", e))?;
 
+    budget.record(&app, AiProvider::OpenAI, "whisper-1", &UsageRecord {
+        audio_minutes,
+        ..Default::default()
+    });
+
     let text = json_response["text"]
         .as_str()
         .ok_or("No transcription in response")?
@@ -207,18 +363,31 @@ pub async fn openai_analyze_full_audio(
     app: tauri::AppHandle,
     audio_base64: String,
     context: AudioAnalysisContext,
+    priority: Option<RequestPriority>,
 ) -> Result<AudioAnalysisResponse, String> {
-    let store = app.store("api_keys.json")
-        .map_err(|e| format!("Failed to access store: {}", e))?;
-
-    let api_key = match store.get("openai_api_key") {
-        Some(value) => value.as_str()
-            .ok_or("OpenAI API key not set. Please add your API key in Settings.")?
-            .to_string(),
-        None => return Err("OpenAI API key not set. Please add your API key in Settings.".to_string())
-    };
+    command_metrics::track_async("openai_analyze_full_audio", async move {
+        analyze_full_audio(app, audio_base64, context, priority)
+            .await
+            .map_err(|e| redaction::redact(&e))
+    }).await
+}
+
+async fn analyze_full_audio(
+    app: tauri::AppHandle,
+    audio_base64: String,
+    context: AudioAnalysisContext,
+    priority: Option<RequestPriority>,
+) -> Result<AudioAnalysisResponse, String> {
+    let api_key = api_keys::get_api_key(&app, "openai_api_key")?
+        .ok_or("OpenAI API key not set. Please add your API key in Settings.")?;
+
+    let budget = app.state::<Arc<BudgetManager>>();
+    budget.check(AiProvider::OpenAI, priority.unwrap_or_default())?;
 
     let (format, _audio_bytes) = detect_audio_format(&audio_base64)?;
+    if format == "flac" || format == "ogg" {
+        return Err("Full audio analysis requires WAV or MP3 audio".to_string());
+    }
 
     // Extract base64 data without the data URL prefix
     let base64_data = if let Some(data) = audio_base64.strip_prefix(&format!("data:audio/{};base64,", if format == "mp3" { "mpeg" } else { format })) {
@@ -248,13 +417,6 @@ pub async fn openai_analyze_full_audio(
         context_str
     );
 
-    let client = Client::builder()
-        .timeout(Duration::from_secs(1200))         // 20 min total timeout (audio analysis can be slow)
-        .connect_timeout(Duration::from_secs(30))   // 30 sec to establish connection
-        .read_timeout(Duration::from_secs(900))     // 15 min to read response
-        .build()
-        .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
-
     // Build request for GPT-4o-audio-preview (using latest version)
     let request_body = json!({
         "model": "gpt-4o-audio-preview-2025-06-03",
@@ -285,14 +447,15 @@ pub async fn openai_analyze_full_audio(
         "temperature": 0.3
     });
 
-    let response = client
-        .post(&format!("{}/chat/completions", OPENAI_API_BASE))
-        .header("Authorization", format!("Bearer {}", api_key))
-        .header("Content-Type", "application/json")
-        .json(&request_body)
-        .send()
-        .await
-        .map_err(|e| format!("OpenAI API request failed: {}", e))?;
+    let response = ai_client::send_with_retry(&app, "openai", |client| {
+        client
+            .post(format!("{}/chat/completions", OPENAI_API_BASE))
+            .header("Authorization", format!("Bearer {}", api_key))
+            .header("Content-Type", "application/json")
+            .json(&request_body)
+    })
+    .await
+    .map_err(|e| format!("OpenAI API request failed: {}", e))?;
 
     let status = response.status();
     if status.as_u16() == 401 {
@@ -312,6 +475,14 @@ pub async fn openai_analyze_full_audio(
         .await
         .map_err(|e| format!("Failed to parse response: {}", e))?;
 
+    let usage = &json_response["usage"];
+    budget.record(&app, AiProvider::OpenAI, "gpt-4o-audio-preview", &UsageRecord {
+        input_tokens: usage["prompt_tokens"].as_u64().unwrap_or(0),
+        output_tokens: usage["completion_tokens"].as_u64().unwrap_or(0),
+        audio_input_tokens: usage["prompt_tokens_details"]["audio_tokens"].as_u64().unwrap_or(0),
+        ..Default::default()
+    });
+
     let content_text = json_response["choices"][0]["message"]["content"]
         .as_str()
         .ok_or("No content in response")?;
@@ -322,3 +493,65 @@ pub async fn openai_analyze_full_audio(
 
     Ok(parsed)
 }
+
+/// OpenAI chat completion (used by the AI router); `messages` are already in
+/// Chat Completions format. Returns the raw response JSON.
+pub async fn send_chat_completion(
+    app: &tauri::AppHandle,
+    model: &str,
+    messages: Vec<serde_json::Value>,
+    max_tokens: u32,
+    temperature: Option<f32>,
+    priority: RequestPriority,
+) -> Result<serde_json::Value, ProviderError> {
+    let api_key = api_keys::get_api_key(app, "openai_api_key")?
+        .ok_or("OpenAI API key not set. Please add your API key in Settings.")?;
+
+    let budget = app.state::<Arc<BudgetManager>>();
+    budget.check(AiProvider::OpenAI, priority)?;
+
+    let mut request_body = json!({
+        "model": model,
+        "messages": messages,
+        "max_completion_tokens": max_tokens,
+    });
+    if let Some(temperature) = temperature {
+        request_body["temperature"] = json!(temperature);
+    }
+
+    let response = ai_client::send_with_retry(app, "openai", |client| {
+        client
+            .post(format!("{}/chat/completions", OPENAI_API_BASE))
+            .header("Authorization", format!("Bearer {}", api_key))
+            .json(&request_body)
+    })
+    .await
+    .map_err(|e| format!("OpenAI API request failed: {}", e))?;
+
+    let status = response.status();
+    if status.as_u16() == 401 {
+        return Err("Invalid OpenAI API key. Please check your key in Settings.".into());
+    } else if status.as_u16() == 429 {
+        return Err(ProviderError::RateLimited("OpenAI rate limit exceeded. Please try again later.".to_string()));
+    } else if !status.is_success() {
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("OpenAI API error ({}): {}", status, error_text).into());
+    }
+
+    let json_response: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))?;
+
+    let usage = &json_response["usage"];
+    budget.record(app, AiProvider::OpenAI, model, &UsageRecord {
+        input_tokens: usage["prompt_tokens"].as_u64().unwrap_or(0),
+        output_tokens: usage["completion_tokens"].as_u64().unwrap_or(0),
+        ..Default::default()
+    });
+
+    Ok(json_response)
+}
@@ -0,0 +1,133 @@
+/**
+ * Parallel Image Module
+ *
+ * Rayon-parallel building blocks for the composite screenshot pipeline:
+ * - `blit`: copy an opaque screen capture into the composite (row-parallel)
+ * - `resize_to_rgb`: separable Lanczos3 downscale that writes RGB directly
+ *   (both passes split across rows)
+ * - `rgba_to_rgb`: drop the alpha channel (row-parallel)
+ */
+
+use rayon::prelude::*;
+use screenshots::image::RgbaImage;
+
+/// Copy `src` into `dst` at (x, y), clipped to `dst` (screen captures are opaque,
+/// so this matches an alpha overlay)
+pub fn blit(dst: &mut RgbaImage, src: &RgbaImage, x: u32, y: u32) {
+    let (dst_width, dst_height) = dst.dimensions();
+    if x >= dst_width || y >= dst_height {
+        return;
+    }
+
+    let copy_width = src.width().min(dst_width - x) as usize * 4;
+    let rows = src.height().min(dst_height - y) as usize;
+    let dst_stride = dst_width as usize * 4;
+    let src_stride = src.width() as usize * 4;
+    let x_bytes = x as usize * 4;
+    let src_raw = src.as_raw();
+
+    dst.par_chunks_mut(dst_stride)
+        .skip(y as usize)
+        .take(rows)
+        .enumerate()
+        .for_each(|(row, dst_row)| {
+            let src_row = &src_raw[row * src_stride..row * src_stride + copy_width];
+            dst_row[x_bytes..x_bytes + copy_width].copy_from_slice(src_row);
+        });
+}
+
+/// Drop alpha into `out` (len = width * height * 3)
+pub fn rgba_to_rgb(src: &RgbaImage, out: &mut [u8]) {
+    let row_rgba = src.width() as usize * 4;
+    let row_rgb = src.width() as usize * 3;
+
+    out.par_chunks_mut(row_rgb)
+        .zip(src.as_raw().par_chunks(row_rgba))
+        .for_each(|(dst_row, src_row)| {
+            for (dst, src) in dst_row.chunks_exact_mut(3).zip(src_row.chunks_exact(4)) {
+                dst.copy_from_slice(&src[..3]);
+            }
+        });
+}
+
+fn lanczos3(x: f32) -> f32 {
+    if x == 0.0 {
+        return 1.0;
+    }
+    if x.abs() >= 3.0 {
+        return 0.0;
+    }
+    let px = std::f32::consts::PI * x;
+    3.0 * px.sin() * (px / 3.0).sin() / (px * px)
+}
+
+/// Per-output-pixel filter taps: (first source index, normalized weights)
+fn filter_taps(src_len: u32, dst_len: u32) -> Vec<(usize, Vec<f32>)> {
+    let scale = src_len as f32 / dst_len as f32;
+    let filter_scale = scale.max(1.0);
+    let support = 3.0 * filter_scale;
+
+    (0..dst_len)
+        .map(|i| {
+            let center = (i as f32 + 0.5) * scale;
+            let start = (center - support).floor().max(0.0) as usize;
+            let end = ((center + support).ceil() as usize).min(src_len as usize);
+
+            let mut weights: Vec<f32> = (start..end)
+                .map(|j| lanczos3((j as f32 + 0.5 - center) / filter_scale))
+                .collect();
+            let sum: f32 = weights.iter().sum();
+            if sum != 0.0 {
+                weights.iter_mut().for_each(|w| *w /= sum);
+            }
+            (start, weights)
+        })
+        .collect()
+}
+
+/// Lanczos3 resize of `src` to `width` x `height`, written as RGB into `out`
+/// (len = width * height * 3)
+pub fn resize_to_rgb(src: &RgbaImage, width: u32, height: u32, out: &mut [u8]) {
+    let src_width = src.width() as usize;
+    let horizontal = filter_taps(src.width(), width);
+    let vertical = filter_taps(src.height(), height);
+    let dst_width = width as usize;
+
+    // Horizontal pass: every source row -> `width` RGB samples (f32)
+    let mut intermediate = vec![0.0f32; src.height() as usize * dst_width * 3];
+    intermediate
+        .par_chunks_mut(dst_width * 3)
+        .zip(src.as_raw().par_chunks(src_width * 4))
+        .for_each(|(dst_row, src_row)| {
+            for (x, (start, weights)) in horizontal.iter().enumerate() {
+                let mut acc = [0.0f32; 3];
+                for (k, weight) in weights.iter().enumerate() {
+                    let pixel = &src_row[(start + k) * 4..(start + k) * 4 + 3];
+                    for (a, p) in acc.iter_mut().zip(pixel) {
+                        *a += *p as f32 * weight;
+                    }
+                }
+                dst_row[x * 3..x * 3 + 3].copy_from_slice(&acc);
+            }
+        });
+
+    // Vertical pass: combine intermediate rows into each output row
+    let stride = dst_width * 3;
+    out.par_chunks_mut(stride)
+        .zip(vertical.par_iter())
+        .for_each_init(
+            || vec![0.0f32; stride],
+            |acc, (dst_row, (start, weights))| {
+                acc.iter_mut().for_each(|a| *a = 0.0);
+                for (k, weight) in weights.iter().enumerate() {
+                    let row = &intermediate[(start + k) * stride..(start + k + 1) * stride];
+                    for (a, v) in acc.iter_mut().zip(row) {
+                        *a += v * weight;
+                    }
+                }
+                for (value, a) in dst_row.iter_mut().zip(acc.iter()) {
+                    *value = a.round().clamp(0.0, 255.0) as u8;
+                }
+            },
+        );
+}
@@ -0,0 +1,217 @@
+/**
+ * Permissions Module
+ *
+ * Status, request and "open System Settings" for the macOS privacy
+ * permissions capture depends on:
+ * - Screen recording: screenshots, video, window titles (CoreGraphics)
+ * - Microphone / camera (AVFoundation, PermissionsBridge.swift)
+ * - Accessibility: reading other apps' UI for richer activity tracking
+ * - Input Monitoring: the CGEvent key tap (keystroke overlay, global key
+ *   counts in macos_events.rs)
+ *
+ * Screen recording and Accessibility only report granted or not, so "not
+ * granted" shows as `denied`; their request shows macOS's prompt pointing to
+ * System Settings and returns right away. The app usually has to be
+ * restarted before a newly granted screen recording or Input Monitoring
+ * permission takes effect. Other platforms report `unsupported`.
+ */
+
+use serde::{Deserialize, Serialize};
+
+use crate::command_metrics;
+
+// FFI declarations for Swift and system functions
+#[cfg(target_os = "macos")]
+extern "C" {
+    fn capture_authorization_status(media: i32) -> i32;
+    fn capture_request_access(media: i32) -> bool;
+    fn accessibility_is_trusted(prompt: bool) -> bool;
+    fn input_monitoring_status() -> i32;
+    fn input_monitoring_request() -> bool;
+    fn CGPreflightScreenCaptureAccess() -> u8;
+    fn CGRequestScreenCaptureAccess() -> u8;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PermissionType {
+    ScreenRecording,
+    Microphone,
+    Camera,
+    Accessibility,
+    InputMonitoring,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub enum PermissionStatus {
+    NotDetermined,
+    Restricted,
+    Denied,
+    Authorized,
+    Unsupported,
+}
+
+impl PermissionType {
+    pub const ALL: [PermissionType; 5] = [
+        PermissionType::ScreenRecording,
+        PermissionType::Microphone,
+        PermissionType::Camera,
+        PermissionType::Accessibility,
+        PermissionType::InputMonitoring,
+    ];
+
+    /// Anchor of the Privacy & Security pane in System Settings
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    fn settings_anchor(self) -> &'static str {
+        match self {
+            PermissionType::ScreenRecording => "Privacy_ScreenCapture",
+            PermissionType::Microphone => "Privacy_Microphone",
+            PermissionType::Camera => "Privacy_Camera",
+            PermissionType::Accessibility => "Privacy_Accessibility",
+            PermissionType::InputMonitoring => "Privacy_ListenEvent",
+        }
+    }
+}
+
+/// `get_all_permissions` entry
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionState {
+    pub permission: PermissionType,
+    pub status: PermissionStatus,
+}
+
+/// AVAuthorizationStatus raw value
+#[cfg(target_os = "macos")]
+fn capture_status(raw: i32) -> PermissionStatus {
+    match raw {
+        0 => PermissionStatus::NotDetermined,
+        1 => PermissionStatus::Restricted,
+        2 => PermissionStatus::Denied,
+        _ => PermissionStatus::Authorized,
+    }
+}
+
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn granted_or_denied(granted: bool) -> PermissionStatus {
+    match granted {
+        true => PermissionStatus::Authorized,
+        false => PermissionStatus::Denied,
+    }
+}
+
+/// Current status of `permission`
+#[cfg(target_os = "macos")]
+pub fn status(permission: PermissionType) -> PermissionStatus {
+    match permission {
+        PermissionType::ScreenRecording => granted_or_denied(unsafe { CGPreflightScreenCaptureAccess() } != 0),
+        PermissionType::Microphone => capture_status(unsafe { capture_authorization_status(0) }),
+        PermissionType::Camera => capture_status(unsafe { capture_authorization_status(1) }),
+        PermissionType::Accessibility => granted_or_denied(unsafe { accessibility_is_trusted(false) }),
+        // IOHIDAccessType: 0 granted, 1 denied, 2 unknown
+        PermissionType::InputMonitoring => match unsafe { input_monitoring_status() } {
+            0 => PermissionStatus::Authorized,
+            1 => PermissionStatus::Denied,
+            _ => PermissionStatus::NotDetermined,
+        },
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn status(_permission: PermissionType) -> PermissionStatus {
+    PermissionStatus::Unsupported
+}
+
+/// Ask for `permission` and return the status afterwards; blocks until the
+/// user answers where macOS shows a dialog
+#[cfg(target_os = "macos")]
+fn request(permission: PermissionType) -> PermissionStatus {
+    if status(permission) == PermissionStatus::Authorized {
+        return PermissionStatus::Authorized;
+    }
+    match permission {
+        PermissionType::ScreenRecording => {
+            unsafe { CGRequestScreenCaptureAccess() };
+        }
+        PermissionType::Microphone => {
+            unsafe { capture_request_access(0) };
+        }
+        PermissionType::Camera => {
+            unsafe { capture_request_access(1) };
+        }
+        PermissionType::Accessibility => {
+            unsafe { accessibility_is_trusted(true) };
+        }
+        PermissionType::InputMonitoring => {
+            unsafe { input_monitoring_request() };
+        }
+    }
+    status(permission)
+}
+
+#[cfg(not(target_os = "macos"))]
+fn request(_permission: PermissionType) -> PermissionStatus {
+    PermissionStatus::Unsupported
+}
+
+/// Open the System Settings pane where `permission` is granted
+#[cfg(target_os = "macos")]
+fn open_settings(permission: PermissionType) -> Result<(), String> {
+    let url = format!(
+        "x-apple.systempreferences:com.apple.preference.security?{}",
+        permission.settings_anchor()
+    );
+    std::process::Command::new("open")
+        .arg(&url)
+        .status()
+        .map_err(|e| format!("Failed to open System Settings: {}", e))
+        .and_then(|status| match status.success() {
+            true => Ok(()),
+            false => Err(format!("Failed to open System Settings ({})", status)),
+        })
+}
+
+#[cfg(not(target_os = "macos"))]
+fn open_settings(_permission: PermissionType) -> Result<(), String> {
+    Err("Permission settings are only available on macOS".to_string())
+}
+
+/// Tauri command to check one permission
+#[tauri::command]
+pub fn get_permission_status(permission: PermissionType) -> Result<PermissionStatus, String> {
+    command_metrics::track("get_permission_status", || {
+        Ok(status(permission))
+    })
+}
+
+/// Tauri command to check every permission (onboarding / settings)
+#[tauri::command]
+pub fn get_all_permissions() -> Result<Vec<PermissionState>, String> {
+    command_metrics::track("get_all_permissions", || {
+        Ok(PermissionType::ALL
+            .into_iter()
+            .map(|permission| PermissionState { permission, status: status(permission) })
+            .collect())
+    })
+}
+
+/// Tauri command to request a permission (shows the system dialog once)
+#[tauri::command]
+pub async fn request_permission(permission: PermissionType) -> Result<PermissionStatus, String> {
+    command_metrics::track_async("request_permission", async move {
+        tokio::task::spawn_blocking(move || request(permission))
+            .await
+            .map_err(|e| format!("Permission request failed: {}", e))
+    }).await
+}
+
+/// Tauri command to open System Settings where a permission is granted
+/// (for permissions that were denied, which can't be requested again)
+#[tauri::command]
+pub fn open_permission_settings(permission: PermissionType) -> Result<(), String> {
+    command_metrics::track("open_permission_settings", || {
+        open_settings(permission)
+    })
+}
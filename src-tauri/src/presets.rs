@@ -0,0 +1,215 @@
+/**
+ * Session Presets Module
+ *
+ * Named recording presets ("Deep Work", "Client call", ...) so a session can
+ * be started fully configured in one call:
+ * - Presets persisted in presets.json (tauri-plugin-store) in app data
+ * - A preset holds the sources to record (screenshots, audio, video), the
+ *   screenshot interval, an optional settings.audio patch (e.g. system audio,
+ *   noise suppression), video quality and displays, and extra AI instructions
+ * - `start_session_from_preset` applies the audio patch to settings and asks
+ *   the frontend to start the session (`control-start-session` with a
+ *   `preset` object), which then starts the screenshot scheduler and the
+ *   audio and video recorders for the enabled sources
+ */
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_store::StoreExt;
+
+use crate::command_metrics;
+use crate::control_server;
+use crate::settings::SettingsManager;
+use crate::video_recording::QualityPreset;
+
+const PRESETS_STORE: &str = "presets.json";
+
+const MAX_NAME_LEN: usize = 64;
+
+/// Screenshot interval meaning "adaptive" (frontend convention)
+const ADAPTIVE_INTERVAL: f64 = -1.0;
+
+/// What a preset records
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PresetSources {
+    pub screenshots: bool,
+    pub audio: bool,
+    pub video: bool,
+}
+
+impl Default for PresetSources {
+    fn default() -> Self {
+        Self {
+            screenshots: true,
+            audio: false,
+            video: false,
+        }
+    }
+}
+
+/// Extra instructions appended to the AI prompts of sessions started from the preset
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PresetPrompts {
+    pub screenshot_analysis: Option<String>,
+    pub summary: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionPreset {
+    pub name: String,
+    /// Name of sessions started from the preset (defaults to the preset name)
+    #[serde(default)]
+    pub session_name: Option<String>,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub sources: PresetSources,
+    /// Minutes between screenshots, or -1 for adaptive
+    #[serde(default = "default_screenshot_interval")]
+    pub screenshot_interval_minutes: f64,
+    /// Partial settings.audio applied when the preset starts
+    /// (e.g. `{ "systemAudio": true }`)
+    #[serde(default)]
+    pub audio: Option<serde_json::Value>,
+    #[serde(default)]
+    pub video_quality: Option<QualityPreset>,
+    /// Displays to record (main display when None)
+    #[serde(default)]
+    pub display_ids: Option<Vec<u32>>,
+    #[serde(default)]
+    pub ai_prompts: PresetPrompts,
+}
+
+fn default_screenshot_interval() -> f64 {
+    2.0
+}
+
+impl SessionPreset {
+    fn validate(&self, settings: &SettingsManager) -> Result<(), String> {
+        let name = self.name.trim();
+        if name.is_empty() || name.len() > MAX_NAME_LEN {
+            return Err(format!("Preset name must be 1-{} characters", MAX_NAME_LEN));
+        }
+        if self.screenshot_interval_minutes != ADAPTIVE_INTERVAL
+            && !(0.1..=120.0).contains(&self.screenshot_interval_minutes)
+        {
+            return Err("Screenshot interval must be between 0.1 and 120 minutes (or -1 for adaptive)".to_string());
+        }
+        if !(self.sources.screenshots || self.sources.audio || self.sources.video) {
+            return Err("A preset must record at least one source".to_string());
+        }
+        if self.display_ids.as_ref().is_some_and(|ids| ids.is_empty()) {
+            return Err("Preset display list is empty".to_string());
+        }
+        if let Some(audio) = &self.audio {
+            if !audio.is_object() {
+                return Err("Preset audio settings must be an object".to_string());
+            }
+            settings.preview(json!({ "audio": audio }))?;
+        }
+        Ok(())
+    }
+
+    /// `preset` object of the `control-start-session` payload
+    fn start_payload(&self) -> serde_json::Value {
+        json!({
+            "name": self.name,
+            "enableScreenshots": self.sources.screenshots,
+            "screenshotInterval": self.screenshot_interval_minutes,
+            "audioRecording": self.sources.audio,
+            "videoRecording": self.sources.video,
+            "videoQuality": self.video_quality,
+            "displayIds": self.display_ids,
+            "aiPrompts": self.ai_prompts,
+        })
+    }
+}
+
+fn load_presets(app: &AppHandle) -> Result<Vec<SessionPreset>, String> {
+    let store = app.store(PRESETS_STORE)
+        .map_err(|e| format!("Failed to access store: {}", e))?;
+
+    Ok(store
+        .get("presets")
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default())
+}
+
+fn save_presets(app: &AppHandle, presets: &[SessionPreset]) -> Result<(), String> {
+    let store = app.store(PRESETS_STORE)
+        .map_err(|e| format!("Failed to access store: {}", e))?;
+
+    store.set("presets", json!(presets));
+    store.save().map_err(|e| format!("Failed to save store: {}", e))
+}
+
+/// Tauri command to create or update a preset (matched by name)
+#[tauri::command]
+pub fn save_preset(app: AppHandle, mut preset: SessionPreset) -> Result<(), String> {
+    command_metrics::track("save_preset", || {
+        preset.name = preset.name.trim().to_string();
+        preset.validate(&app.state::<Arc<SettingsManager>>())?;
+
+        let mut presets = load_presets(&app)?;
+        match presets.iter_mut().find(|p| p.name == preset.name) {
+            Some(existing) => *existing = preset,
+            None => presets.push(preset),
+        }
+        save_presets(&app, &presets)
+    })
+}
+
+/// Tauri command to list saved presets
+#[tauri::command]
+pub fn list_presets(app: AppHandle) -> Result<Vec<SessionPreset>, String> {
+    command_metrics::track("list_presets", || load_presets(&app))
+}
+
+/// Tauri command to delete a preset; returns false if it didn't exist
+#[tauri::command]
+pub fn delete_preset(app: AppHandle, name: String) -> Result<bool, String> {
+    command_metrics::track("delete_preset", || {
+        let mut presets = load_presets(&app)?;
+        let before = presets.len();
+        presets.retain(|p| p.name != name);
+        if presets.len() == before {
+            return Ok(false);
+        }
+        save_presets(&app, &presets)?;
+        Ok(true)
+    })
+}
+
+/// Tauri command to start a session configured by a preset
+#[tauri::command]
+pub async fn start_session_from_preset(app: AppHandle, name: String) -> Result<SessionPreset, String> {
+    command_metrics::track_async("start_session_from_preset", async move {
+        let preset = load_presets(&app)?
+            .into_iter()
+            .find(|p| p.name == name)
+            .ok_or_else(|| format!("Preset '{}' not found", name))?;
+
+        // Check before touching settings; `start` checks again
+        control_server::ensure_no_active_session(&app).await?;
+        if let Some(audio) = &preset.audio {
+            app.state::<Arc<SettingsManager>>().update(&app, json!({ "audio": audio }))?;
+        }
+
+        control_server::execute(&app, "start", json!({
+            "name": preset.session_name.as_deref().unwrap_or(&preset.name),
+            "description": preset.description,
+            "tags": preset.tags,
+            "preset": preset.start_payload(),
+        }))
+        .await?;
+        println!("🎛️  Starting session from preset \"{}\"", preset.name);
+        Ok(preset)
+    }).await
+}
@@ -0,0 +1,243 @@
+/**
+ * Privacy Module
+ *
+ * Keeps sensitive apps out of session captures (macOS):
+ * - settings.privacy: blocked apps (app name or bundle id, case-insensitive)
+ *   and window title fragments (e.g. a bank's name, which browsers put in
+ *   the window title)
+ * - While a blocked app or window is frontmost, screenshot captures are
+ *   skipped (checked fresh before every capture) and, while recording, video
+ *   frames are replaced with black ones by the Swift recorder
+ * - `set_privacy_blocklist` / `get_privacy_status` commands;
+ *   `privacy-status-changed` is emitted when a recording enters or leaves a
+ *   blocked app
+ *
+ * Window titles need screen recording permission, which capture already
+ * requires. Other platforms never block.
+ */
+
+use serde::Serialize;
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::background_tasks::TaskRegistry;
+use crate::command_metrics;
+use crate::safe_state::SafeState;
+use crate::settings::{PrivacySettings, SettingsManager};
+use crate::video_recording::VideoRecorder;
+
+const TASK_NAME: &str = "privacy-guard";
+
+/// Frontmost app checks while recording video
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Privacy state reported to the frontend
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrivacyStatus {
+    pub enabled: bool,
+    /// A blocked app or window is frontmost
+    pub blocked: bool,
+    /// App (or "<app>: <window title>") that matched the blocklist
+    pub blocked_by: Option<String>,
+    /// When the current block started (RFC 3339)
+    pub since: Option<String>,
+}
+
+/// Frontmost app and its front window
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+struct FrontWindow {
+    bundle_id: String,
+    app_name: String,
+    title: String,
+}
+
+#[cfg(target_os = "macos")]
+fn front_window() -> Option<FrontWindow> {
+    use cocoa::base::{id, nil};
+    use objc::{class, msg_send, sel, sel_impl};
+
+    unsafe {
+        let text = |value: id| -> Option<String> {
+            if value == nil {
+                return None;
+            }
+            let ptr: *const i8 = msg_send![value, UTF8String];
+            (!ptr.is_null()).then(|| std::ffi::CStr::from_ptr(ptr).to_string_lossy().into_owned())
+        };
+
+        let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+        let app: id = msg_send![workspace, frontmostApplication];
+        if app == nil {
+            return None;
+        }
+        let bundle_id = text(msg_send![app, bundleIdentifier]).unwrap_or_default();
+        let app_name = text(msg_send![app, localizedName]).unwrap_or_else(|| bundle_id.clone());
+        let pid: i32 = msg_send![app, processIdentifier];
+        Some(FrontWindow {
+            bundle_id,
+            app_name,
+            title: crate::meeting_detector::front_window_title(pid as i64).unwrap_or_default(),
+        })
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn front_window() -> Option<FrontWindow> {
+    None
+}
+
+/// What in `window` matches the blocklist, if anything
+fn blocked_by(settings: &PrivacySettings, window: &FrontWindow) -> Option<String> {
+    let app_blocked = settings.blocked_apps.iter().any(|blocked| {
+        let blocked = blocked.trim();
+        !blocked.is_empty()
+            && (blocked.eq_ignore_ascii_case(&window.app_name) || blocked.eq_ignore_ascii_case(&window.bundle_id))
+    });
+    if app_blocked {
+        return Some(window.app_name.clone());
+    }
+
+    let title = window.title.to_lowercase();
+    settings
+        .blocked_titles
+        .iter()
+        .map(|fragment| fragment.trim().to_lowercase())
+        .any(|fragment| !fragment.is_empty() && title.contains(&fragment))
+        .then(|| format!("{}: {}", window.app_name, window.title))
+}
+
+/// Managed privacy state
+pub struct PrivacyGuard {
+    status: SafeState<PrivacyStatus>,
+}
+
+impl PrivacyGuard {
+    pub fn new() -> Self {
+        Self {
+            status: SafeState::new("privacy.status", PrivacyStatus::default()),
+        }
+    }
+
+    /// Evaluate the frontmost window now; blocks on window server queries
+    fn evaluate(&self, settings: &PrivacySettings) -> PrivacyStatus {
+        let blocked_by = if settings.enabled {
+            front_window().and_then(|window| blocked_by(settings, &window))
+        } else {
+            None
+        };
+        let previous = self.status.get();
+        PrivacyStatus {
+            enabled: settings.enabled,
+            blocked: blocked_by.is_some(),
+            since: match (&blocked_by, previous.blocked) {
+                (Some(_), true) => previous.since,
+                (Some(_), false) => Some(chrono::Utc::now().to_rfc3339()),
+                (None, _) => None,
+            },
+            blocked_by,
+        }
+    }
+
+    /// Err (with the reason) when a blocked app or window is frontmost
+    pub fn capture_allowed(&self, app: &AppHandle) -> Result<(), String> {
+        let status = self.evaluate(&app.state::<Arc<SettingsManager>>().get().privacy);
+        match status.blocked_by {
+            Some(blocked_by) => Err(format!("Capture skipped: {} is in the privacy blocklist", blocked_by)),
+            None => Ok(()),
+        }
+    }
+
+    fn apply(&self, app: &AppHandle, status: PrivacyStatus) {
+        if status == self.status.get() {
+            return;
+        }
+        if status.blocked != self.status.get().blocked {
+            app.state::<Arc<SafeState<VideoRecorder>>>().lock().set_blanked(status.blocked);
+            match &status.blocked_by {
+                Some(blocked_by) => println!("🙈 [PRIVACY] Blanking capture: {} is frontmost", blocked_by),
+                None => println!("👀 [PRIVACY] Capture resumed"),
+            }
+            let _ = app.emit("privacy-status-changed", &status);
+        }
+        self.status.set(status);
+    }
+
+    /// Start following the frontmost app while video is recording
+    pub fn start(self: &Arc<Self>, app: AppHandle, registry: &TaskRegistry) -> Result<(), String> {
+        let guard = self.clone();
+        registry.spawn(TASK_NAME, |mut shutdown| async move {
+            let mut interval = tokio::time::interval(POLL_INTERVAL);
+            loop {
+                tokio::select! {
+                    _ = shutdown.cancelled() => break,
+                    _ = interval.tick() => {}
+                }
+
+                let settings = app.state::<Arc<SettingsManager>>().get().privacy;
+                let recording = app.state::<Arc<SafeState<VideoRecorder>>>().lock().is_recording();
+                let status = if recording && settings.enabled {
+                    let guard = guard.clone();
+                    tokio::task::spawn_blocking(move || guard.evaluate(&settings))
+                        .await
+                        .unwrap_or_default()
+                } else {
+                    PrivacyStatus { enabled: settings.enabled, ..PrivacyStatus::default() }
+                };
+                guard.apply(&app, status);
+            }
+        })
+    }
+}
+
+impl Default for PrivacyGuard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Tauri command to replace the privacy blocklist
+#[tauri::command]
+pub fn set_privacy_blocklist(
+    app: AppHandle,
+    settings: State<Arc<SettingsManager>>,
+    apps: Vec<String>,
+    titles: Vec<String>,
+) -> Result<PrivacySettings, String> {
+    command_metrics::track("set_privacy_blocklist", || {
+        let clean = |entries: Vec<String>| -> Vec<String> {
+            let mut cleaned: Vec<String> = Vec::new();
+            for entry in entries.iter().map(|entry| entry.trim()).filter(|entry| !entry.is_empty()) {
+                if !cleaned.iter().any(|existing| existing.eq_ignore_ascii_case(entry)) {
+                    cleaned.push(entry.to_string());
+                }
+            }
+            cleaned
+        };
+        let patch = json!({ "privacy": { "blockedApps": clean(apps), "blockedTitles": clean(titles) } });
+        Ok(settings.update(&app, patch)?.privacy)
+    })
+}
+
+/// Tauri command to check whether capture is currently blocked
+#[tauri::command]
+pub async fn get_privacy_status(
+    app: AppHandle,
+    guard: State<'_, Arc<PrivacyGuard>>,
+    settings: State<'_, Arc<SettingsManager>>,
+) -> Result<PrivacyStatus, String> {
+    command_metrics::track_async("get_privacy_status", async move {
+        let guard = guard.inner().clone();
+        let settings = settings.get().privacy;
+        let status = tokio::task::spawn_blocking({
+            let guard = guard.clone();
+            move || guard.evaluate(&settings)
+        })
+        .await
+        .map_err(|e| format!("Privacy check failed: {}", e))?;
+        guard.apply(&app, status.clone());
+        Ok(status)
+    }).await
+}
@@ -0,0 +1,298 @@
+/**
+ * Profiles Module
+ *
+ * Named provider profiles / workspaces so consultants can keep separate
+ * API keys, model choices and session data per client:
+ * - Profiles persisted in profiles.json (tauri-plugin-store)
+ * - API keys isolated per profile (keychain account "<profile>/<key>"; legacy
+ *   api_keys.json / api_keys.<name>.json are migrated into the keychain)
+ * - Session data isolated under <app data>/profiles/<subdirectory>; no two
+ *   profiles may share a subdirectory
+ * - Model choices replace settings.ai.models for routed requests (ai_router.rs)
+ * - Sessions that ended more than `retentionDays` ago are deleted with their
+ *   media by the "storage-rotation" task (storage_budget.rs)
+ * - The "default" profile maps to the legacy locations for backwards compatibility
+ */
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_store::StoreExt;
+
+use crate::background_tasks::TaskRegistry;
+use crate::command_metrics;
+use crate::settings::ProviderModels;
+use crate::storage_budget;
+
+pub const DEFAULT_PROFILE: &str = "default";
+const PROFILES_STORE: &str = "profiles.json";
+
+/// A named workspace profile
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Profile {
+    pub name: String,
+    /// Claude model for routed requests in this profile (settings.ai.models when None)
+    pub claude_model: Option<String>,
+    /// OpenAI model for routed requests in this profile (settings.ai.models when None)
+    pub openai_model: Option<String>,
+    /// Data subdirectory under <app data>/profiles (defaults to the profile name)
+    pub data_subdirectory: Option<String>,
+    /// Delete sessions that ended more than this many days ago, with their
+    /// media (None = keep forever)
+    pub retention_days: Option<u32>,
+}
+
+impl Profile {
+    fn default_profile() -> Self {
+        Self {
+            name: DEFAULT_PROFILE.to_string(),
+            claude_model: None,
+            openai_model: None,
+            data_subdirectory: None,
+            retention_days: None,
+        }
+    }
+
+    fn is_default(&self) -> bool {
+        self.name == DEFAULT_PROFILE
+    }
+
+    /// Subdirectory under <app data>/profiles holding the profile's data
+    /// (None for the default profile, which uses <app data> itself)
+    fn data_subdirectory(&self) -> Option<&str> {
+        if self.is_default() {
+            return None;
+        }
+        Some(self.data_subdirectory.as_deref().unwrap_or(&self.name))
+    }
+
+    /// Replace the default models with the profile's own choices
+    pub fn apply_model_choices(&self, models: &mut ProviderModels) {
+        if let Some(model) = &self.claude_model {
+            models.claude = model.clone();
+        }
+        if let Some(model) = &self.openai_model {
+            models.openai = model.clone();
+        }
+    }
+}
+
+/// Profile names double as file/directory names, so keep them filesystem-safe
+fn validate_name(name: &str) -> Result<(), String> {
+    if name.trim().is_empty() {
+        return Err("Profile name cannot be empty".to_string());
+    }
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err("Profile name may only contain letters, numbers, '-' and '_'".to_string());
+    }
+    Ok(())
+}
+
+pub fn load_profiles(app: &AppHandle) -> Result<Vec<Profile>, String> {
+    let store = app.store(PROFILES_STORE)
+        .map_err(|e| format!("Failed to access store: {}", e))?;
+
+    let mut profiles: Vec<Profile> = store
+        .get("profiles")
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default();
+
+    // The default profile always exists
+    if !profiles.iter().any(|p| p.is_default()) {
+        profiles.insert(0, Profile::default_profile());
+    }
+
+    Ok(profiles)
+}
+
+fn save_profiles(app: &AppHandle, profiles: &[Profile]) -> Result<(), String> {
+    let store = app.store(PROFILES_STORE)
+        .map_err(|e| format!("Failed to access store: {}", e))?;
+
+    store.set("profiles", serde_json::json!(profiles));
+    store.save().map_err(|e| format!("Failed to save store: {}", e))?;
+
+    Ok(())
+}
+
+/// Get the currently active profile (falls back to default)
+pub fn active_profile(app: &AppHandle) -> Profile {
+    let active_name = app
+        .store(PROFILES_STORE)
+        .ok()
+        .and_then(|store| store.get("active_profile"))
+        .and_then(|value| value.as_str().map(|s| s.to_string()))
+        .unwrap_or_else(|| DEFAULT_PROFILE.to_string());
+
+    load_profiles(app)
+        .ok()
+        .and_then(|profiles| profiles.into_iter().find(|p| p.name == active_name))
+        .unwrap_or_else(Profile::default_profile)
+}
+
+/// Legacy store file holding a profile's API keys (keys now live in the
+/// keychain; these files are only read until migrated)
+pub fn api_keys_store(profile: &Profile) -> String {
+    if profile.is_default() {
+        "api_keys.json".to_string()
+    } else {
+        format!("api_keys.{}.json", profile.name)
+    }
+}
+
+/// Data directory for the active profile (sessions.json, attachments/, ...)
+pub fn profile_data_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+
+    Ok(profile_dir(data_dir, active_profile(app)))
+}
+
+/// Data directories of every profile (for migrations that touch all session data)
+pub fn all_profile_data_dirs(app: &AppHandle) -> Result<Vec<PathBuf>, String> {
+    let data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+
+    Ok(load_profiles(app)?
+        .into_iter()
+        .map(|profile| profile_dir(data_dir.clone(), profile))
+        .collect())
+}
+
+fn profile_dir(data_dir: PathBuf, profile: Profile) -> PathBuf {
+    match profile.data_subdirectory() {
+        Some(subdirectory) => data_dir.join("profiles").join(subdirectory),
+        None => data_dir,
+    }
+}
+
+/// Data directory for the active profile, read straight from profiles.json
+/// (for processes without a Tauri app, e.g. the MCP server)
+pub fn profile_data_dir_from_disk(data_dir: &Path) -> PathBuf {
+    let store: serde_json::Value = std::fs::read_to_string(data_dir.join(PROFILES_STORE))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+
+    let active_name = store
+        .get("active_profile")
+        .and_then(|value| value.as_str())
+        .unwrap_or(DEFAULT_PROFILE);
+    let profile = store
+        .get("profiles")
+        .and_then(|value| serde_json::from_value::<Vec<Profile>>(value.clone()).ok())
+        .and_then(|profiles| profiles.into_iter().find(|p| p.name == active_name))
+        .unwrap_or_else(Profile::default_profile);
+
+    profile_dir(data_dir.to_path_buf(), profile)
+}
+
+/// Tauri command to list all profiles
+#[tauri::command]
+pub fn list_profiles(app: AppHandle) -> Result<Vec<Profile>, String> {
+    command_metrics::track("list_profiles", || {
+        load_profiles(&app)
+    })
+}
+
+/// Tauri command to get the active profile
+#[tauri::command]
+pub fn get_active_profile(app: AppHandle) -> Result<Profile, String> {
+    command_metrics::track("get_active_profile", || {
+        Ok(active_profile(&app))
+    })
+}
+
+/// Tauri command to create or update a profile (a changed retention policy
+/// of the active profile is applied right away)
+#[tauri::command]
+pub fn save_profile(
+    app: AppHandle,
+    registry: State<Arc<TaskRegistry>>,
+    profile: Profile,
+) -> Result<(), String> {
+    command_metrics::track("save_profile", || {
+        validate_name(&profile.name)?;
+        if let Some(subdirectory) = &profile.data_subdirectory {
+            validate_name(subdirectory)?;
+        }
+
+        let mut profiles = load_profiles(&app)?;
+        // Compared case-insensitively, like the default macOS filesystem
+        if let Some(subdirectory) = profile.data_subdirectory() {
+            let shared = profiles.iter().find(|p| {
+                p.name != profile.name
+                    && p.data_subdirectory().is_some_and(|other| other.eq_ignore_ascii_case(subdirectory))
+            });
+            if let Some(other) = shared {
+                return Err(format!("Profile '{}' already keeps its data in '{}'", other.name, subdirectory));
+            }
+        }
+        let is_active = active_profile(&app).name == profile.name;
+        match profiles.iter_mut().find(|p| p.name == profile.name) {
+            Some(existing) => *existing = profile,
+            None => profiles.push(profile),
+        }
+
+        save_profiles(&app, &profiles)?;
+        if is_active {
+            storage_budget::start(app.clone(), &registry)?;
+        }
+        Ok(())
+    })
+}
+
+/// Tauri command to delete a profile (its data directory is left on disk)
+#[tauri::command]
+pub fn delete_profile(app: AppHandle, name: String) -> Result<(), String> {
+    command_metrics::track("delete_profile", || {
+        if name == DEFAULT_PROFILE {
+            return Err("The default profile cannot be deleted".to_string());
+        }
+        if active_profile(&app).name == name {
+            return Err("Cannot delete the active profile. Switch to another profile first.".to_string());
+        }
+
+        let mut profiles = load_profiles(&app)?;
+        profiles.retain(|p| p.name != name);
+        save_profiles(&app, &profiles)
+    })
+}
+
+/// Tauri command to switch the active profile
+/// Emits `profile-changed` so the frontend can reload keys and sessions
+#[tauri::command]
+pub fn switch_profile(
+    app: AppHandle,
+    registry: State<Arc<TaskRegistry>>,
+    name: String,
+) -> Result<Profile, String> {
+    command_metrics::track("switch_profile", || {
+        let profile = load_profiles(&app)?
+            .into_iter()
+            .find(|p| p.name == name)
+            .ok_or_else(|| format!("Profile '{}' not found", name))?;
+
+        let store = app.store(PROFILES_STORE)
+            .map_err(|e| format!("Failed to access store: {}", e))?;
+        store.set("active_profile", serde_json::json!(profile.name));
+        store.save().map_err(|e| format!("Failed to save store: {}", e))?;
+
+        let data_dir = profile_data_dir(&app)?;
+        std::fs::create_dir_all(&data_dir)
+            .map_err(|e| format!("Failed to create profile data dir: {}", e))?;
+
+        println!("👤 [PROFILES] Switched to profile '{}' ({:?})", profile.name, data_dir);
+        // Storage budget and retention now apply to this profile's data
+        storage_budget::start(app.clone(), &registry)?;
+        let _ = app.emit("profile-changed", &profile);
+
+        Ok(profile)
+    })
+}
@@ -0,0 +1,156 @@
+/**
+ * Realtime Emitter Module
+ *
+ * Backpressure-aware delivery for real-time readings (audio-level meters):
+ * - Each channel has one bounded latest-value slot; a new reading replaces an
+ *   undelivered one instead of queueing behind it
+ * - A channel has at most one emission in flight: the next value is sent only
+ *   after the frontend acknowledges the previous one (`ack_realtime_event`),
+ *   so a busy webview gets the newest reading when it is ready, never a burst
+ *   of old ones
+ * - Producers (audio callbacks) only write the slot; emission happens on the
+ *   "realtime-emitter" task once per frame
+ *
+ * Unlike the event coalescer this does not batch channels together - each
+ * channel is emitted under its own event name as soon as it is free.
+ */
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+use crate::background_tasks::TaskRegistry;
+use crate::command_metrics;
+use crate::safe_state::SafeState;
+
+/// Delivery check interval (one display frame)
+const FRAME: Duration = Duration::from_millis(16);
+
+/// Unacknowledged emissions older than this are treated as lost (e.g. the
+/// webview reloaded) so a channel can't stall forever
+const ACK_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Payload of a realtime channel event
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RealtimeEvent {
+    pub seq: u64,
+    pub value: serde_json::Value,
+    /// Readings superseded since the previous emission
+    pub dropped: u64,
+}
+
+#[derive(Default)]
+struct Channel {
+    latest: Option<serde_json::Value>,
+    dropped: u64,
+    seq: u64,
+    in_flight: Option<(u64, Instant)>,
+}
+
+/// Managed emitter with one latest-value slot per channel
+pub struct RealtimeEmitter {
+    channels: SafeState<HashMap<&'static str, Channel>>,
+}
+
+impl RealtimeEmitter {
+    pub fn new() -> Self {
+        Self {
+            channels: SafeState::new("realtime_emitter", HashMap::new()),
+        }
+    }
+
+    /// Store the latest reading for `channel`, superseding any undelivered one
+    pub fn push<T: Serialize>(&self, channel: &'static str, value: T) {
+        let value = match serde_json::to_value(value) {
+            Ok(value) => value,
+            Err(e) => {
+                eprintln!("❌ [EVENTS] Failed to serialize {} payload: {}", channel, e);
+                return;
+            }
+        };
+
+        let mut channels = self.channels.lock();
+        let slot = channels.entry(channel).or_default();
+        if slot.latest.replace(value).is_some() {
+            slot.dropped += 1;
+        }
+    }
+
+    /// The frontend finished handling `seq` on `channel`
+    pub fn ack(&self, channel: &str, seq: u64) {
+        if let Some(slot) = self.channels.lock().get_mut(channel) {
+            if matches!(slot.in_flight, Some((in_flight, _)) if in_flight <= seq) {
+                slot.in_flight = None;
+            }
+        }
+    }
+
+    /// Emit the latest value of every channel that is not waiting for an ack
+    fn deliver(&self, app: &AppHandle) {
+        let ready: Vec<(&'static str, RealtimeEvent)> = {
+            let mut channels = self.channels.lock();
+            channels
+                .iter_mut()
+                .filter(|(_, slot)| {
+                    slot.latest.is_some()
+                        && slot.in_flight.map_or(true, |(_, sent)| sent.elapsed() >= ACK_TIMEOUT)
+                })
+                .filter_map(|(channel, slot)| {
+                    let value = slot.latest.take()?;
+                    slot.seq += 1;
+                    slot.in_flight = Some((slot.seq, Instant::now()));
+                    let event = RealtimeEvent {
+                        seq: slot.seq,
+                        value,
+                        dropped: std::mem::take(&mut slot.dropped),
+                    };
+                    Some((*channel, event))
+                })
+                .collect()
+        };
+
+        for (channel, event) in ready {
+            if let Err(e) = app.emit(channel, &event) {
+                eprintln!("❌ [EVENTS] Failed to emit {}: {}", channel, e);
+            }
+        }
+    }
+
+    /// Start the delivery loop as the "realtime-emitter" background task
+    pub fn start(self: &Arc<Self>, app: AppHandle, registry: &TaskRegistry) -> Result<(), String> {
+        let emitter = self.clone();
+        registry.spawn("realtime-emitter", |mut shutdown| async move {
+            let mut interval = tokio::time::interval(FRAME);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                tokio::select! {
+                    _ = shutdown.cancelled() => break,
+                    _ = interval.tick() => {}
+                }
+                emitter.deliver(&app);
+            }
+        })
+    }
+}
+
+impl Default for RealtimeEmitter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Tauri command: acknowledge a realtime channel event
+#[tauri::command]
+pub fn ack_realtime_event(
+    emitter: tauri::State<Arc<RealtimeEmitter>>,
+    channel: String,
+    seq: u64,
+) -> Result<(), String> {
+    command_metrics::track("ack_realtime_event", || {
+        emitter.ack(&channel, seq);
+        Ok(())
+    })
+}
@@ -0,0 +1,186 @@
+/**
+ * Recording Limits Module
+ *
+ * Safety limits so a forgotten session can't record all night
+ * (settings.limits, 0 turns a limit off):
+ * - maxSessionHours: the session is stopped once it has been running this
+ *   long (counted from when recording was first seen, pauses included)
+ * - maxFileSizeGb: when a video file reaches this size the session is
+ *   stopped, or with fileSizeAction `rollover` the file is finished and
+ *   recording continues in `<stem>.part<N>.mp4` (`recording-rolled-over`)
+ *
+ * Stopping finalizes the video (VideoRecorder::auto_stop), stops audio and
+ * the screenshot scheduler, then emits `session-auto-stopped` so the frontend
+ * ends the session; its stop_video_recording still gets the finished file.
+ * The "recording-limits" task checks every CHECK_INTERVAL.
+ */
+
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::audio_capture::AudioRecorder;
+use crate::background_tasks::TaskRegistry;
+use crate::safe_state::SafeState;
+use crate::screenshot_scheduler::{SchedulerStatus, ScreenshotScheduler};
+use crate::settings::{FileSizeAction, SettingsManager};
+use crate::video_recording::VideoRecorder;
+
+const TASK_NAME: &str = "recording-limits";
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+const BYTES_PER_GB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AutoStopReason {
+    MaxDuration,
+    MaxFileSize,
+}
+
+/// `session-auto-stopped` payload
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoStopped {
+    pub session_id: String,
+    pub reason: AutoStopReason,
+    /// Human-readable limit, e.g. "12h" or "20 GB"
+    pub limit: String,
+    /// Finished video file, if video was recorded
+    pub video_path: Option<String>,
+}
+
+/// Id of the session being recorded, if any (paused sessions count as running)
+pub fn running_session(app: &AppHandle) -> Option<String> {
+    let info = app.state::<Arc<ScreenshotScheduler>>().info();
+    if info.status != SchedulerStatus::Idle {
+        if let Some(session_id) = info.session_id {
+            return Some(session_id);
+        }
+    }
+    let video_session = app.state::<Arc<SafeState<VideoRecorder>>>().lock().current_session_id();
+    if video_session.is_some() {
+        return video_session;
+    }
+    app.state::<Arc<AudioRecorder>>()
+        .is_recording()
+        .then(|| "recording".to_string())
+}
+
+/// Stop every recorder for `session_id` and tell the frontend why
+fn auto_stop(app: &AppHandle, session_id: &str, reason: AutoStopReason, limit: String) {
+    eprintln!("🛑 [LIMITS] Stopping session {}: {:?} ({})", session_id, reason, limit);
+
+    let video_path = {
+        let recorder = app.state::<Arc<SafeState<VideoRecorder>>>();
+        let mut recorder = recorder.lock();
+        if recorder.is_recording() || recorder.is_paused() {
+            match recorder.auto_stop() {
+                Ok(path) => Some(path.to_string_lossy().to_string()),
+                Err(e) => {
+                    eprintln!("❌ [LIMITS] Failed to finalize video: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    };
+
+    let audio = app.state::<Arc<AudioRecorder>>();
+    if audio.is_recording() {
+        if let Err(e) = audio.stop_recording() {
+            eprintln!("❌ [LIMITS] Failed to stop audio recording: {}", e);
+        }
+    }
+    app.state::<Arc<ScreenshotScheduler>>().stop();
+
+    let _ = app.emit("session-auto-stopped", AutoStopped {
+        session_id: session_id.to_string(),
+        reason,
+        limit,
+        video_path,
+    });
+}
+
+/// Start enforcing settings.limits
+pub fn start(app: AppHandle, registry: &TaskRegistry) -> Result<(), String> {
+    registry.spawn(TASK_NAME, |mut shutdown| async move {
+        // Session being timed and when it was first seen
+        let mut current: Option<(String, Instant)> = None;
+        // Session already stopped (the frontend may take a moment to end it)
+        let mut stopped: Option<String> = None;
+        let mut interval = tokio::time::interval(CHECK_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+
+            let Some(session_id) = running_session(&app) else {
+                current = None;
+                continue;
+            };
+            // Already stopped; only a new session id is checked again
+            if stopped.as_deref() == Some(session_id.as_str()) {
+                continue;
+            }
+            if current.as_ref().map(|(id, _)| id) != Some(&session_id) {
+                current = Some((session_id.clone(), Instant::now()));
+            }
+            let started = current.as_ref().map(|(_, started)| *started).unwrap_or_else(Instant::now);
+
+            let limits = app.state::<Arc<SettingsManager>>().get().limits;
+            let app = app.clone();
+            let stop = tokio::task::spawn_blocking(move || {
+                if limits.max_session_hours > 0.0
+                    && started.elapsed().as_secs_f64() >= limits.max_session_hours * 3600.0
+                {
+                    auto_stop(&app, &session_id, AutoStopReason::MaxDuration, format!("{}h", limits.max_session_hours));
+                    return Some(session_id);
+                }
+
+                if limits.max_file_size_gb <= 0.0 {
+                    return None;
+                }
+                let recorder = app.state::<Arc<SafeState<VideoRecorder>>>();
+                let size = recorder.lock().current_file_size();
+                if (size as f64) < limits.max_file_size_gb * BYTES_PER_GB {
+                    return None;
+                }
+                let limit = format!("{} GB", limits.max_file_size_gb);
+                match limits.file_size_action {
+                    FileSizeAction::Rollover => {
+                        let rolled = recorder.lock().roll_over();
+                        match rolled {
+                            Ok(path) => {
+                                let _ = app.emit("recording-rolled-over", serde_json::json!({
+                                    "sessionId": session_id,
+                                    "path": path.to_string_lossy(),
+                                }));
+                                None
+                            }
+                            Err(e) => {
+                                eprintln!("❌ [LIMITS] {}", e);
+                                auto_stop(&app, &session_id, AutoStopReason::MaxFileSize, limit);
+                                Some(session_id)
+                            }
+                        }
+                    }
+                    FileSizeAction::Stop => {
+                        auto_stop(&app, &session_id, AutoStopReason::MaxFileSize, limit);
+                        Some(session_id)
+                    }
+                }
+            })
+            .await;
+
+            match stop {
+                Ok(Some(session_id)) => stopped = Some(session_id),
+                Ok(None) => {}
+                Err(e) => eprintln!("⚠️  [LIMITS] Check failed: {}", e),
+            }
+        }
+    })
+}
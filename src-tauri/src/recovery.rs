@@ -0,0 +1,315 @@
+/**
+ * Recovery Module
+ *
+ * Salvages recordings interrupted by a crash. Runs once on startup and only
+ * looks at files from before launch:
+ * - Video: segment files (`<stem>.seg<N>.mp4`, see video_recording.rs) only
+ *   survive when stop never ran. Segments are written as fragmented MP4, so
+ *   a crashed one still has its `moov` box; segments without it are remuxed
+ *   with ffmpeg when it is available (bundled or installed, see
+ *   media_tools.rs; otherwise left in place and reported).
+ *   Usable segments are stitched into `<stem>.mp4` (and listed with their
+ *   offsets in `<stem>.segments.json` for rolling-segment recordings).
+ * - Audio: chunk files under `audio-chunks/<session id>/` (file chunk mode)
+ *   for sessions that never got an end time
+ *
+ * Each salvaged session gets a manifest in `<profile>/recovered/<id>.json`
+ * and a `session-recovered` event. Manifests stay until the frontend calls
+ * `dismiss_recovered_session`, so `get_recovered_sessions` also covers
+ * events emitted before the UI was listening.
+ */
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::SystemTime;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::background_tasks::TaskRegistry;
+use crate::command_metrics;
+use crate::media_tools;
+use crate::profiles;
+use crate::session_storage;
+use crate::video_recording::VideoRecorder;
+
+const TASK_NAME: &str = "recording-recovery";
+
+/// What was salvaged for one session (`session-recovered` payload)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoveredSession {
+    pub session_id: String,
+    pub session_name: Option<String>,
+    /// RFC 3339
+    pub recovered_at: String,
+    /// Finalized video, if any segment was usable
+    pub video_path: Option<String>,
+    pub video_segments_recovered: usize,
+    /// Segments that could not be read (kept on disk)
+    pub video_segments_lost: Vec<String>,
+    /// Audio chunk files written before the crash, in order
+    pub audio_chunks: Vec<String>,
+}
+
+/// `session-<session id>-<timestamp>` stem and segment index of a segment file
+fn parse_segment(path: &Path) -> Option<(String, usize)> {
+    let name = path.file_name()?.to_str()?;
+    let (stem, rest) = name.strip_suffix(".mp4")?.rsplit_once(".seg")?;
+    Some((stem.to_string(), rest.parse().ok()?))
+}
+
+/// `session-<id>-<timestamp>` -> `<id>`
+fn session_id_from_stem(stem: &str) -> Option<String> {
+    let (id, timestamp) = stem.strip_prefix("session-")?.rsplit_once('-')?;
+    timestamp.parse::<u64>().ok()?;
+    Some(id.to_string())
+}
+
+/// Whether the file's top-level MP4 boxes include `moov` (playable / stitchable)
+fn has_moov(path: &Path) -> bool {
+    let Ok(mut file) = std::fs::File::open(path) else {
+        return false;
+    };
+    let Ok(length) = file.metadata().map(|m| m.len()) else {
+        return false;
+    };
+
+    let mut offset = 0u64;
+    let mut header = [0u8; 16];
+    while offset + 8 <= length {
+        if file.seek(SeekFrom::Start(offset)).is_err() || file.read_exact(&mut header[..8]).is_err() {
+            return false;
+        }
+        let size = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as u64;
+        if &header[4..8] == b"moov" {
+            return true;
+        }
+        let box_size = match size {
+            // Box runs to the end of the file
+            0 => return false,
+            // 64-bit size follows the type
+            1 => {
+                if file.read_exact(&mut header[8..16]).is_err() {
+                    return false;
+                }
+                u64::from_be_bytes(header[8..16].try_into().unwrap_or_default())
+            }
+            size => size,
+        };
+        if box_size < 8 {
+            return false;
+        }
+        offset += box_size;
+    }
+    false
+}
+
+/// Remux a segment whose index is missing or damaged; returns the fixed file
+fn remux_with_ffmpeg(ffmpeg: &Path, segment: &Path) -> Option<PathBuf> {
+    let fixed = segment.with_extension("fixed.mp4");
+    let status = Command::new(ffmpeg)
+        .args(["-y", "-v", "error", "-err_detect", "ignore_err", "-i"])
+        .arg(segment)
+        .args(["-c", "copy", "-movflags", "+faststart"])
+        .arg(&fixed)
+        .status()
+        .ok()?;
+
+    if status.success() && has_moov(&fixed) {
+        let _ = std::fs::remove_file(segment);
+        Some(fixed)
+    } else {
+        let _ = std::fs::remove_file(&fixed);
+        None
+    }
+}
+
+fn modified_before(path: &Path, startup: SystemTime) -> bool {
+    std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .map(|modified| modified < startup)
+        .unwrap_or(false)
+}
+
+/// Stitch leftover video segments per recording
+fn recover_videos(videos_dir: &Path, startup: SystemTime, found: &mut BTreeMap<String, RecoveredSession>) {
+    let Ok(entries) = std::fs::read_dir(videos_dir) else {
+        return;
+    };
+
+    let mut recordings: BTreeMap<String, Vec<(usize, PathBuf)>> = BTreeMap::new();
+    for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+        if let Some((stem, index)) = parse_segment(&path) {
+            if modified_before(&path, startup) {
+                recordings.entry(stem).or_default().push((index, path));
+            }
+        }
+    }
+    if recordings.is_empty() {
+        return;
+    }
+
+    let ffmpeg = media_tools::ffmpeg();
+    for (stem, mut segments) in recordings {
+        let Some(session_id) = session_id_from_stem(&stem) else {
+            continue;
+        };
+        segments.sort_by_key(|(index, _)| *index);
+
+        let entry = found.entry(session_id.clone()).or_insert_with(|| RecoveredSession {
+            session_id,
+            ..Default::default()
+        });
+        let mut usable = Vec::new();
+        for (_, segment) in segments {
+            if has_moov(&segment) {
+                usable.push(segment);
+            } else if let Some(fixed) = ffmpeg.as_deref().and_then(|ffmpeg| remux_with_ffmpeg(ffmpeg, &segment)) {
+                usable.push(fixed);
+            } else {
+                eprintln!("⚠️  [RECOVERY] Unrecoverable video segment {:?}", segment);
+                entry.video_segments_lost.push(segment.to_string_lossy().to_string());
+            }
+        }
+        if usable.is_empty() {
+            continue;
+        }
+
+        let output = videos_dir.join(format!("{}.mp4", stem));
+        match VideoRecorder::stitch_segments(&usable, &output) {
+            Ok(()) => {
+                entry.video_segments_recovered = usable.len();
+                entry.video_path = Some(output.to_string_lossy().to_string());
+            }
+            Err(e) => eprintln!("⚠️  [RECOVERY] Failed to finalize {}: {}", stem, e),
+        }
+    }
+    if ffmpeg.is_none() && found.values().any(|s| !s.video_segments_lost.is_empty()) {
+        eprintln!("⚠️  [RECOVERY] Install ffmpeg to attempt repair of damaged video segments");
+    }
+}
+
+/// Collect chunk files of sessions that never got an end time
+fn recover_audio(chunks_dir: &Path, sessions: &[crate::session_models::Session], found: &mut BTreeMap<String, RecoveredSession>) {
+    for session in sessions.iter().filter(|s| s.end_time.is_none()) {
+        let Ok(entries) = std::fs::read_dir(chunks_dir.join(&session.id)) else {
+            continue;
+        };
+        let mut chunks: Vec<String> = entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|path| path.is_file())
+            .map(|path| path.to_string_lossy().to_string())
+            .collect();
+        if chunks.is_empty() {
+            continue;
+        }
+        chunks.sort();
+
+        found
+            .entry(session.id.clone())
+            .or_insert_with(|| RecoveredSession {
+                session_id: session.id.clone(),
+                ..Default::default()
+            })
+            .audio_chunks = chunks;
+    }
+}
+
+/// Scan for interrupted recordings and write manifests; blocks on file IO
+fn recover(app_data_dir: &Path, data_dir: &Path, startup: SystemTime) -> Result<Vec<RecoveredSession>, String> {
+    let sessions = session_storage::read_sessions(data_dir)?;
+    let mut found = BTreeMap::new();
+    recover_videos(&app_data_dir.join("videos"), startup, &mut found);
+    recover_audio(&data_dir.join("audio-chunks"), &sessions, &mut found);
+    if found.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let manifest_dir = data_dir.join("recovered");
+    std::fs::create_dir_all(&manifest_dir)
+        .map_err(|e| format!("Failed to create recovery directory: {}", e))?;
+
+    let recovered_at = chrono::Utc::now().to_rfc3339();
+    let mut recovered = Vec::new();
+    for (session_id, mut session) in found {
+        session.session_name = sessions.iter().find(|s| s.id == session_id).map(|s| s.name.clone());
+        session.recovered_at = recovered_at.clone();
+
+        let content = serde_json::to_string_pretty(&session)
+            .map_err(|e| format!("Failed to serialize recovery manifest: {}", e))?;
+        std::fs::write(manifest_dir.join(format!("{}.json", session_id)), content)
+            .map_err(|e| format!("Failed to write recovery manifest: {}", e))?;
+        recovered.push(session);
+    }
+    Ok(recovered)
+}
+
+/// Run recovery once in the background
+pub fn start(app: AppHandle, registry: &TaskRegistry) -> Result<(), String> {
+    let startup = SystemTime::now();
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+    let data_dir = profiles::profile_data_dir(&app)?;
+
+    registry.spawn(TASK_NAME, move |_shutdown| async move {
+        let result = tokio::task::spawn_blocking(move || recover(&app_data_dir, &data_dir, startup)).await;
+        match result {
+            Ok(Ok(recovered)) => {
+                for session in recovered {
+                    println!(
+                        "🩹 [RECOVERY] Session {}: video {}, {} audio chunks, {} segments lost",
+                        session.session_id,
+                        if session.video_path.is_some() { "recovered" } else { "none" },
+                        session.audio_chunks.len(),
+                        session.video_segments_lost.len()
+                    );
+                    let _ = app.emit("session-recovered", &session);
+                }
+            }
+            Ok(Err(e)) => eprintln!("⚠️  [RECOVERY] {}", e),
+            Err(e) => eprintln!("⚠️  [RECOVERY] Recovery task failed: {}", e),
+        }
+    })
+}
+
+/// Tauri command to list salvaged sessions not yet dismissed
+#[tauri::command]
+pub fn get_recovered_sessions(app: AppHandle) -> Result<Vec<RecoveredSession>, String> {
+    command_metrics::track("get_recovered_sessions", || {
+        let manifest_dir = profiles::profile_data_dir(&app)?.join("recovered");
+        let Ok(entries) = std::fs::read_dir(&manifest_dir) else {
+            return Ok(Vec::new());
+        };
+
+        let mut sessions: Vec<RecoveredSession> = entries
+            .filter_map(|e| e.ok())
+            .filter_map(|e| std::fs::read_to_string(e.path()).ok())
+            .filter_map(|content| serde_json::from_str(&content).ok())
+            .collect();
+        sessions.sort_by(|a, b| a.recovered_at.cmp(&b.recovered_at));
+        Ok(sessions)
+    })
+}
+
+/// Tauri command to forget a salvaged session once the frontend has handled it
+#[tauri::command]
+pub fn dismiss_recovered_session(app: AppHandle, session_id: String) -> Result<(), String> {
+    command_metrics::track("dismiss_recovered_session", || {
+        if session_id.contains(['/', '\\']) || session_id.contains("..") {
+            return Err("Invalid session id".to_string());
+        }
+        let path = profiles::profile_data_dir(&app)?
+            .join("recovered")
+            .join(format!("{}.json", session_id));
+        match std::fs::remove_file(path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(format!("Failed to dismiss recovered session: {}", e)),
+        }
+    })
+}
@@ -0,0 +1,63 @@
+/**
+ * Redaction Module
+ *
+ * Scrubs secrets (API keys, bearer tokens, auth headers) from strings before
+ * they are logged or returned to the frontend as error messages. Provider
+ * error bodies and reqwest errors can echo request headers, so everything
+ * coming out of `claude_api` / `openai_api` passes through here.
+ */
+
+use regex::Regex;
+use std::sync::OnceLock;
+
+fn patterns() -> &'static [(Regex, &'static str)] {
+    static PATTERNS: OnceLock<Vec<(Regex, &'static str)>> = OnceLock::new();
+    PATTERNS.get_or_init(|| {
+        [
+            // Authorization: Bearer <token>
+            (r"(?i)(bearer\s+)[A-Za-z0-9._~+/\-]+=*", "${1}[REDACTED]"),
+            // x-api-key: <key>, "api_key": "<key>", authorization=<value>
+            (
+                r#"(?i)((?:x-api-key|api[_-]?key|authorization)["']?\s*[:=]\s*["']?)[^\s"',}]+"#,
+                "${1}[REDACTED]",
+            ),
+            // Bare Anthropic keys (sk-ant-...)
+            (r"sk-ant-[A-Za-z0-9_\-]{8,}", "sk-ant-[REDACTED]"),
+            // Bare OpenAI keys (sk-..., sk-proj-..., sk-svcacct-...)
+            (r"sk-(?:proj-|svcacct-)?[A-Za-z0-9_\-]{16,}", "sk-[REDACTED]"),
+        ]
+        .into_iter()
+        .map(|(pattern, replacement)| {
+            (Regex::new(pattern).expect("invalid redaction pattern"), replacement)
+        })
+        .collect()
+    })
+}
+
+/// Replace any secrets in `input` with a redaction marker
+pub fn redact(input: &str) -> String {
+    let mut output = input.to_string();
+    for (pattern, replacement) in patterns() {
+        if pattern.is_match(&output) {
+            output = pattern.replace_all(&output, *replacement).into_owned();
+        }
+    }
+    output
+}
+
+/// `println!` with secrets redacted
+macro_rules! println_redacted {
+    ($($arg:tt)*) => {
+        println!("{}", $crate::redaction::redact(&format!($($arg)*)))
+    };
+}
+
+/// `eprintln!` with secrets redacted
+macro_rules! eprintln_redacted {
+    ($($arg:tt)*) => {
+        eprintln!("{}", $crate::redaction::redact(&format!($($arg)*)))
+    };
+}
+
+pub(crate) use eprintln_redacted;
+pub(crate) use println_redacted;
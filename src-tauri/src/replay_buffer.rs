@@ -0,0 +1,498 @@
+/**
+ * Replay Buffer Module
+ *
+ * Retroactive capture (settings.replayBuffer): while enabled, the screen and
+ * the default microphone are kept in ring buffers covering roughly the last
+ * `minutes`, and nothing is saved unless `save_replay_buffer` is called:
+ * - Screen: a VideoRecorder of its own writing one-minute rolling segments
+ *   (`<app data>/videos/replay-<ms>.seg<N>.mp4`); the "replay-buffer" task
+ *   rotates them and deletes all but the last `minutes` finished ones
+ * - Audio: mono 16 kHz samples in memory (about 2 MB per minute)
+ *
+ * `save_replay_buffer` stitches the video, writes the audio as WAV and adds a
+ * completed session with both attachments to sessions.json, then emits
+ * `replay-buffer-saved` so the frontend adds it to its state (like an imported
+ * session). Buffering then starts over. Turning the buffer off discards it;
+ * `replay-*` files left by a crash are deleted when it starts.
+ */
+
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::SampleFormat;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::audio_capture::AudioRecorder;
+use crate::background_tasks::TaskRegistry;
+use crate::command_metrics;
+use crate::profiles;
+use crate::safe_state::SafeState;
+use crate::session_archive::new_id;
+use crate::session_storage;
+use crate::settings::{ReplayBufferSettings, SettingsManager};
+use crate::video_recording::{self, VideoQuality, VideoRecorder};
+
+const TASK_NAME: &str = "replay-buffer";
+const TRIM_INTERVAL: Duration = Duration::from_secs(5);
+const SEGMENT_MINUTES: u32 = 1;
+const AUDIO_RATE: u32 = 16000;
+const FILE_PREFIX: &str = "replay-";
+
+/// Microphone samples of the last minutes
+struct AudioRing {
+    _stream: cpal::Stream,
+    samples: Arc<SafeState<VecDeque<i16>>>,
+}
+
+// SAFETY: the stream is only kept alive and dropped, never used from another
+// thread (same as AudioRecorder's stream)
+unsafe impl Send for AudioRing {}
+
+impl AudioRing {
+    /// Start buffering the default input device, keeping `seconds` of audio
+    fn start(seconds: u32) -> Result<Self, String> {
+        let device = cpal::default_host()
+            .default_input_device()
+            .ok_or("No input device available")?;
+        let config = device
+            .default_input_config()
+            .map_err(|e| format!("Failed to get default input config: {}", e))?;
+        let channels = config.channels().max(1) as usize;
+        let source_rate = config.sample_rate().0;
+        let stream_config: cpal::StreamConfig = config.clone().into();
+
+        let capacity = (seconds * AUDIO_RATE) as usize;
+        let samples = Arc::new(SafeState::new("replay.audio", VecDeque::with_capacity(capacity)));
+        let ring = samples.clone();
+        // Resample whole 10 ms blocks so no fraction of a sample is lost per callback
+        let block = (source_rate / 100).max(1) as usize;
+        let mut pending: Vec<f32> = Vec::new();
+        let mut push = move |interleaved: &mut dyn Iterator<Item = f32>| {
+            let mut frame = Vec::with_capacity(channels);
+            for sample in interleaved {
+                frame.push(sample);
+                if frame.len() == channels {
+                    pending.push(frame.iter().sum::<f32>() / channels as f32);
+                    frame.clear();
+                }
+            }
+            let whole = pending.len() - pending.len() % block;
+            if whole == 0 {
+                return;
+            }
+            let resampled = AudioRecorder::resample_to_16khz(&pending[..whole], source_rate);
+            pending.drain(..whole);
+
+            let mut ring = ring.lock();
+            let overflow = (ring.len() + resampled.len()).saturating_sub(capacity);
+            let overflow = overflow.min(ring.len());
+            ring.drain(..overflow);
+            ring.extend(resampled.iter().map(|&sample| (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16));
+        };
+        let on_error = |err: cpal::StreamError| eprintln!("❌ [REPLAY] Audio stream error: {}", err);
+
+        let stream = match config.sample_format() {
+            SampleFormat::F32 => device.build_input_stream(
+                &stream_config,
+                move |data: &[f32], _: &cpal::InputCallbackInfo| push(&mut data.iter().copied()),
+                on_error,
+                None,
+            ),
+            SampleFormat::I16 => device.build_input_stream(
+                &stream_config,
+                move |data: &[i16], _: &cpal::InputCallbackInfo| {
+                    push(&mut data.iter().map(|&sample| sample as f32 / i16::MAX as f32))
+                },
+                on_error,
+                None,
+            ),
+            format => return Err(format!("Unsupported sample format: {:?}", format)),
+        }
+        .map_err(|e| format!("Failed to build input stream: {}", e))?;
+        stream
+            .play()
+            .map_err(|e| format!("Failed to start audio stream: {}", e))?;
+
+        Ok(Self { _stream: stream, samples })
+    }
+
+    /// The last `seconds` of buffered audio (emptying the buffer)
+    fn take(&self, seconds: f64) -> Vec<f32> {
+        let mut ring = self.samples.lock();
+        let keep = ((seconds * AUDIO_RATE as f64) as usize).min(ring.len());
+        let skip = ring.len() - keep;
+        let samples = ring.iter().skip(skip).map(|&sample| sample as f32 / i16::MAX as f32).collect();
+        ring.clear();
+        samples
+    }
+}
+
+/// What is being buffered
+struct Buffering {
+    settings: ReplayBufferSettings,
+    /// Output of the screen recorder; None when the screen couldn't be recorded
+    video: Option<PathBuf>,
+    audio: Option<AudioRing>,
+    started_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayBufferStatus {
+    pub active: bool,
+    pub minutes: u32,
+    pub video: bool,
+    pub audio: bool,
+    /// When buffering (re)started; a save covers at most `minutes` before now
+    pub started_at: Option<String>,
+}
+
+/// `replay-buffer-saved` payload
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SavedReplay {
+    pub session_id: String,
+    pub duration_secs: f64,
+    /// The new session, as written to sessions.json
+    pub session: serde_json::Value,
+}
+
+pub struct ReplayBuffer {
+    recorder: Arc<SafeState<VideoRecorder>>,
+    state: SafeState<Option<Buffering>>,
+}
+
+impl ReplayBuffer {
+    pub fn new() -> Self {
+        Self {
+            recorder: Arc::new(SafeState::new("replay.recorder", VideoRecorder::new())),
+            state: SafeState::new("replay.state", None),
+        }
+    }
+
+    /// Start, restart or stop buffering to match settings.replayBuffer (blocks
+    /// on starting the screen recorder)
+    fn apply(&self, app: &AppHandle, settings: &ReplayBufferSettings) {
+        let registry = app.state::<Arc<TaskRegistry>>();
+        let mut state = self.state.lock();
+        if !settings.enabled {
+            if let Some(buffering) = state.take() {
+                registry.cancel(TASK_NAME);
+                self.discard(buffering);
+                println!("⏺️  [REPLAY] Replay buffer off, buffered media discarded");
+            }
+            return;
+        }
+        if state.as_ref().is_some_and(|buffering| buffering.settings == *settings) {
+            return;
+        }
+
+        if let Some(buffering) = state.take() {
+            self.discard(buffering);
+        }
+        match self.start(app, settings) {
+            Ok(buffering) => {
+                *state = Some(buffering);
+                if let Err(e) = self.start_trimming(&registry, settings.minutes) {
+                    eprintln!("❌ [REPLAY] {}", e);
+                }
+                println!("⏺️  [REPLAY] Buffering the last {} minutes", settings.minutes);
+            }
+            Err(e) => eprintln!("❌ [REPLAY] {}", e),
+        }
+    }
+
+    fn start(&self, app: &AppHandle, settings: &ReplayBufferSettings) -> Result<Buffering, String> {
+        let videos_dir = app
+            .path()
+            .app_data_dir()
+            .map_err(|e| format!("Failed to get app data directory: {}", e))?
+            .join("videos");
+        std::fs::create_dir_all(&videos_dir)
+            .map_err(|e| format!("Failed to create videos directory: {}", e))?;
+        remove_leftovers(&videos_dir);
+
+        let video = match self.start_video(&videos_dir) {
+            Ok(path) => Some(path),
+            Err(e) => {
+                eprintln!("⚠️  [REPLAY] Screen not buffered: {}", e);
+                None
+            }
+        };
+        let audio = if settings.audio {
+            match AudioRing::start((settings.minutes + SEGMENT_MINUTES) * 60) {
+                Ok(ring) => Some(ring),
+                Err(e) => {
+                    eprintln!("⚠️  [REPLAY] Microphone not buffered: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        if video.is_none() && audio.is_none() {
+            return Err("Replay buffer couldn't record the screen or the microphone".to_string());
+        }
+
+        Ok(Buffering {
+            settings: settings.clone(),
+            video,
+            audio,
+            started_at: chrono::Utc::now(),
+        })
+    }
+
+    fn start_video(&self, videos_dir: &Path) -> Result<PathBuf, String> {
+        let output = videos_dir.join(format!("{}{}.mp4", FILE_PREFIX, chrono::Utc::now().timestamp_millis()));
+        let mut recorder = self.recorder.lock();
+        recorder.set_segment_duration(Some(SEGMENT_MINUTES));
+        recorder.start_recording("replay-buffer".to_string(), output.clone(), VideoQuality::default())?;
+        Ok(output)
+    }
+
+    fn discard(&self, buffering: Buffering) {
+        if buffering.video.is_some() {
+            self.recorder.lock().discard();
+        }
+    }
+
+    /// Rotate the screen recording's segments and drop the ones too old to keep
+    fn start_trimming(&self, registry: &TaskRegistry, minutes: u32) -> Result<(), String> {
+        let recorder = self.recorder.clone();
+        registry.spawn(TASK_NAME, |mut shutdown| async move {
+            let mut interval = tokio::time::interval(TRIM_INTERVAL);
+            loop {
+                tokio::select! {
+                    _ = shutdown.cancelled() => break,
+                    _ = interval.tick() => {}
+                }
+
+                // Finalizing a segment blocks on Swift
+                let recorder = recorder.clone();
+                let trimmed = tokio::task::spawn_blocking(move || {
+                    let mut recorder = recorder.lock();
+                    recorder.rotate_segment_if_due()?;
+                    recorder.trim_segments((minutes / SEGMENT_MINUTES) as usize);
+                    Ok::<_, String>(())
+                })
+                .await;
+                match trimmed {
+                    Ok(Ok(())) => {}
+                    Ok(Err(e)) => eprintln!("❌ [REPLAY] {}", e),
+                    Err(e) => eprintln!("⚠️  [REPLAY] Trimming failed: {}", e),
+                }
+            }
+        })
+    }
+
+    fn status(&self) -> ReplayBufferStatus {
+        let state = self.state.lock();
+        match state.as_ref() {
+            Some(buffering) => ReplayBufferStatus {
+                active: true,
+                minutes: buffering.settings.minutes,
+                video: buffering.video.is_some(),
+                audio: buffering.audio.is_some(),
+                started_at: Some(buffering.started_at.to_rfc3339()),
+            },
+            None => ReplayBufferStatus {
+                active: false,
+                minutes: 0,
+                video: false,
+                audio: false,
+                started_at: None,
+            },
+        }
+    }
+
+    /// Turn the buffered media into a completed session (blocks on stitching)
+    fn save(&self, app: &AppHandle) -> Result<SavedReplay, String> {
+        let mut state = self.state.lock();
+        let buffering = state.as_mut().ok_or("The replay buffer is off")?;
+        let ended_at = chrono::Utc::now();
+        let limit_secs = (buffering.settings.minutes * 60) as f64;
+
+        let video = match buffering.video.take() {
+            Some(_) => {
+                let path = self.recorder.lock().stop_recording()?;
+                // Keep buffering from now on
+                let videos_dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+                match self.start_video(&videos_dir) {
+                    Ok(next) => buffering.video = Some(next),
+                    Err(e) => eprintln!("⚠️  [REPLAY] Screen buffering stopped: {}", e),
+                }
+                let duration = video_recording::probe_duration(&path).unwrap_or(limit_secs);
+                Some((path, duration))
+            }
+            None => None,
+        };
+        let duration_secs = video.as_ref().map_or(limit_secs, |(_, duration)| *duration);
+        let audio = buffering
+            .audio
+            .as_ref()
+            .map(|ring| ring.take(duration_secs))
+            .filter(|samples| !samples.is_empty());
+        buffering.started_at = ended_at;
+        drop(state);
+
+        if video.is_none() && audio.is_none() {
+            return Err("Nothing has been buffered yet".to_string());
+        }
+        let audio_secs = audio.as_ref().map_or(0.0, |samples| samples.len() as f64 / AUDIO_RATE as f64);
+        let duration_secs = if video.is_some() { duration_secs } else { audio_secs };
+
+        let session_id = new_id();
+        let stamp = ended_at.timestamp_millis();
+        let created_at = ended_at.to_rfc3339();
+        let data_dir = profiles::profile_data_dir(app)?;
+        let attachments_dir = data_dir.join("attachments");
+        std::fs::create_dir_all(&attachments_dir)
+            .map_err(|e| format!("Failed to create attachments directory: {}", e))?;
+
+        let mut session = serde_json::json!({
+            "id": session_id,
+            "name": format!("Replay {}", chrono::Local::now().format("%Y-%m-%d %H:%M")),
+            "description": "Saved from the replay buffer",
+            "status": "completed",
+            "startTime": (ended_at - chrono::Duration::milliseconds((duration_secs * 1000.0) as i64)).to_rfc3339(),
+            "endTime": created_at,
+            "screenshotInterval": 2,
+            "autoAnalysis": false,
+            "enableScreenshots": false,
+            "audioMode": "off",
+            "audioRecording": false,
+            "screenshots": [],
+            "extractedTaskIds": [],
+            "extractedNoteIds": [],
+            "audioReviewCompleted": false,
+            "tags": ["replay"],
+            "totalDuration": (duration_secs / 60.0).round(),
+            "videoRecording": video.is_some(),
+        });
+
+        if let Some((path, duration)) = &video {
+            let final_path = path.with_file_name(format!("session-{}-{}.mp4", session_id, stamp));
+            video_recording::move_recording(path, &final_path)?;
+            let attachment_id = format!("video-{}-{}", session_id, stamp);
+            write_meta(&attachments_dir, serde_json::json!({
+                "id": attachment_id,
+                "type": "video",
+                "name": "Session Recording.mp4",
+                "mimeType": "video/mp4",
+                "size": file_size(&final_path),
+                "createdAt": created_at,
+                "path": final_path.to_string_lossy(),
+                "duration": duration,
+            }))?;
+            session["video"] = serde_json::json!({
+                "id": new_id(),
+                "sessionId": session_id,
+                "fullVideoAttachmentId": attachment_id,
+                "duration": duration,
+                "chunkingStatus": "pending",
+            });
+        }
+
+        if let Some(samples) = &audio {
+            let attachment_id = format!("audio-{}-{}", session_id, stamp);
+            let path = attachments_dir.join(format!("{}-replay.wav", attachment_id));
+            let wav = AudioRecorder::samples_to_wav(samples, AUDIO_RATE, 1)?;
+            std::fs::write(&path, &wav).map_err(|e| format!("Failed to write replay audio: {}", e))?;
+            write_meta(&attachments_dir, serde_json::json!({
+                "id": attachment_id,
+                "type": "audio",
+                "name": "Replay Audio.wav",
+                "mimeType": "audio/wav",
+                "size": wav.len(),
+                "createdAt": created_at,
+                "path": path.to_string_lossy(),
+                "duration": audio_secs,
+            }))?;
+            session["fullAudioAttachmentId"] = serde_json::json!(attachment_id);
+        }
+
+        let encrypt_new_file = app.state::<Arc<SettingsManager>>().get().storage.encrypt_sessions;
+        let new_session = session.clone();
+        session_storage::update_session_values(&data_dir, encrypt_new_file, move |sessions| {
+            sessions.push(new_session);
+            Ok(())
+        })
+        .map_err(|e| format!("Saved the replay media but couldn't add the session: {}", e))?;
+
+        println!("⏺️  [REPLAY] Saved {:.0}s replay as session {}", duration_secs, session_id);
+        let saved = SavedReplay {
+            session_id,
+            duration_secs,
+            session,
+        };
+        let _ = app.emit("replay-buffer-saved", &saved);
+        Ok(saved)
+    }
+}
+
+impl Default for ReplayBuffer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Delete replay files from an earlier run (nothing is buffering yet)
+fn remove_leftovers(videos_dir: &Path) {
+    let Ok(entries) = std::fs::read_dir(videos_dir) else {
+        return;
+    };
+    for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+        if path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with(FILE_PREFIX))
+        {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+fn file_size(path: &Path) -> u64 {
+    std::fs::metadata(path).map(|metadata| metadata.len()).unwrap_or(0)
+}
+
+/// `attachments/<id>.meta.json` for a file-based attachment
+fn write_meta(attachments_dir: &Path, meta: serde_json::Value) -> Result<(), String> {
+    let id = meta["id"].as_str().unwrap_or_default();
+    let content = serde_json::to_string_pretty(&meta)
+        .map_err(|e| format!("Failed to serialize metadata: {}", e))?;
+    std::fs::write(attachments_dir.join(format!("{}.meta.json", id)), content)
+        .map_err(|e| format!("Failed to write metadata for {}: {}", id, e))
+}
+
+/// Apply the current settings.replayBuffer in the background
+pub fn refresh(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let settings = app.state::<Arc<SettingsManager>>().get().replay_buffer;
+        app.state::<Arc<ReplayBuffer>>().apply(&app, &settings);
+    });
+}
+
+/// Tauri command to keep what the replay buffer holds as a new session
+#[tauri::command]
+pub async fn save_replay_buffer(
+    app: AppHandle,
+    buffer: State<'_, Arc<ReplayBuffer>>,
+) -> Result<SavedReplay, String> {
+    command_metrics::track_async("save_replay_buffer", async move {
+        let buffer = buffer.inner().clone();
+        tokio::task::spawn_blocking(move || buffer.save(&app))
+            .await
+            .map_err(|e| format!("Replay save task failed: {}", e))?
+    }).await
+}
+
+/// Tauri command to check whether the replay buffer is running
+#[tauri::command]
+pub fn get_replay_buffer_status(buffer: State<Arc<ReplayBuffer>>) -> Result<ReplayBufferStatus, String> {
+    command_metrics::track("get_replay_buffer_status", || Ok(buffer.status()))
+}
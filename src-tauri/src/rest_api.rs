@@ -0,0 +1,617 @@
+/**
+ * REST API Module
+ *
+ * Read-only session API on localhost so scripts, Raycast and other apps can
+ * query sessions without the webview (same data as the MCP server):
+ * - `GET /sessions?query=&category=&sortBy=&sortOrder=&limit=&offset=`:
+ *   session summaries from the session index ({ sessions, total, offset, limit })
+ * - `GET /sessions/{id}`: full session detail (404 if unknown)
+ * - `GET /active`: the session being recorded, or null
+ *
+ * Every request needs `Authorization: Bearer <token>`; the token is generated
+ * on first use and kept in the OS keychain (`get_api_server_token`,
+ * `regenerate_api_server_token`). The server binds 127.0.0.1 only and follows
+ * settings.apiServer (enabled, port), so `start_api_server` / `stop_api_server`
+ * persist across restarts.
+ */
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Manager, State};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+use crate::background_tasks::TaskRegistry;
+use crate::command_metrics;
+use crate::diarization;
+use crate::keychain;
+use crate::profiles;
+use crate::safe_state::SafeState;
+use crate::session_index::{SessionIndex, SessionQuery};
+use crate::session_storage;
+use crate::settings::{ApiServerSettings, SettingsManager};
+
+const TASK_NAME: &str = "rest-api-server";
+const TOKEN_ACCOUNT: &str = "rest-api-token";
+
+const MAX_REQUEST_BYTES: usize = 16 * 1024;
+const MAX_BODY_BYTES: usize = 16 * 1024;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+const DEFAULT_QUERY_LIMIT: usize = 50;
+
+/// Reported by `get_api_server_status`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiServerStatus {
+    pub running: bool,
+    pub port: u16,
+    /// e.g. http://127.0.0.1:47821 (None while stopped)
+    pub url: Option<String>,
+    /// Why the last start failed (e.g. port in use)
+    pub error: Option<String>,
+}
+
+struct Response {
+    status: u16,
+    body: Value,
+}
+
+impl Response {
+    fn ok(body: Value) -> Self {
+        Self { status: 200, body }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self { status, body: json!({ "error": message.into() }) }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let reason = match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            401 => "Unauthorized",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            413 => "Payload Too Large",
+            _ => "Internal Server Error",
+        };
+        let body = self.body.to_string();
+        let mut head = format!(
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n",
+            self.status, reason, body.len()
+        );
+        if self.status == 401 {
+            head.push_str("WWW-Authenticate: Bearer\r\n");
+        }
+        head.push_str("\r\n");
+        [head.into_bytes(), body.into_bytes()].concat()
+    }
+}
+
+struct Request {
+    method: String,
+    target: String,
+    authorization: Option<String>,
+}
+
+/// Read the request head. Bodies are drained and ignored (every route is a
+/// GET) so closing the socket doesn't reset the connection under the client.
+async fn read_request<S: AsyncRead + Unpin>(stream: &mut S) -> Result<Request, Response> {
+    let mut buffer = Vec::with_capacity(1024);
+    let mut chunk = [0u8; 1024];
+    loop {
+        let read = read_chunk(stream, &mut chunk).await?;
+        if read == 0 {
+            return Err(Response::error(400, "Incomplete request"));
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+
+        let mut headers = [httparse::EMPTY_HEADER; 32];
+        let mut request = httparse::Request::new(&mut headers);
+        match request.parse(&buffer) {
+            Ok(httparse::Status::Complete(head_length)) => {
+                let header = |name: &str| {
+                    request
+                        .headers
+                        .iter()
+                        .find(|header| header.name.eq_ignore_ascii_case(name))
+                        .map(|header| String::from_utf8_lossy(header.value).to_string())
+                };
+                if header("transfer-encoding").is_some() {
+                    return Err(Response::error(400, "Transfer-Encoding is not supported"));
+                }
+                let content_length = content_length(request.headers)?;
+                if content_length > MAX_BODY_BYTES {
+                    return Err(Response::error(413, "Request body too large"));
+                }
+                let parsed = Request {
+                    method: request.method.unwrap_or_default().to_string(),
+                    target: request.path.unwrap_or("/").to_string(),
+                    authorization: header("authorization"),
+                };
+
+                let mut remaining = content_length.saturating_sub(buffer.len() - head_length);
+                while remaining > 0 {
+                    let read = read_chunk(stream, &mut chunk).await?;
+                    if read == 0 {
+                        return Err(Response::error(400, "Incomplete request body"));
+                    }
+                    remaining = remaining.saturating_sub(read);
+                }
+                return Ok(parsed);
+            }
+            Ok(httparse::Status::Partial) if buffer.len() < MAX_REQUEST_BYTES => continue,
+            Ok(httparse::Status::Partial) => return Err(Response::error(413, "Request too large")),
+            Err(e) => return Err(Response::error(400, format!("Malformed request: {}", e))),
+        }
+    }
+}
+
+async fn read_chunk<S: AsyncRead + Unpin>(stream: &mut S, chunk: &mut [u8]) -> Result<usize, Response> {
+    tokio::time::timeout(REQUEST_TIMEOUT, stream.read(chunk))
+        .await
+        .map_err(|_| Response::error(400, "Request timed out"))?
+        .map_err(|e| Response::error(400, format!("Failed to read request: {}", e)))
+}
+
+/// Declared body length (0 without a Content-Length header). Repeated headers
+/// must agree, otherwise the request is ambiguous and rejected.
+fn content_length(headers: &[httparse::Header]) -> Result<usize, Response> {
+    let mut length = None;
+    for header in headers.iter().filter(|header| header.name.eq_ignore_ascii_case("content-length")) {
+        let value = std::str::from_utf8(header.value)
+            .ok()
+            .map(str::trim)
+            .filter(|value| !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()))
+            .and_then(|value| value.parse::<usize>().ok())
+            .ok_or_else(|| Response::error(400, "Invalid Content-Length"))?;
+        if length.is_some_and(|length| length != value) {
+            return Err(Response::error(400, "Conflicting Content-Length headers"));
+        }
+        length = Some(value);
+    }
+    Ok(length.unwrap_or(0))
+}
+
+/// `Authorization: Bearer <token>` against the server token (never authorized
+/// while the token hasn't been loaded)
+fn is_authorized(authorization: Option<&str>, token: Option<&str>) -> bool {
+    match (authorization, token) {
+        (Some(header), Some(token)) => header
+            .strip_prefix("Bearer ")
+            .is_some_and(|provided| token_matches(provided.trim(), token)),
+        _ => false,
+    }
+}
+
+/// Compare without short-circuiting so response timing doesn't leak the token
+fn token_matches(provided: &str, expected: &str) -> bool {
+    provided.len() == expected.len()
+        && provided
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// `GET /sessions` query string as a SessionQuery
+fn session_query(url: &url::Url) -> Result<SessionQuery, String> {
+    let mut arguments = serde_json::Map::new();
+    for (key, value) in url.query_pairs() {
+        let value = match key.as_ref() {
+            "limit" | "offset" => json!(value
+                .parse::<usize>()
+                .map_err(|_| format!("Invalid {}: {}", key, value))?),
+            _ => json!(value),
+        };
+        arguments.insert(key.to_string(), value);
+    }
+
+    let mut query: SessionQuery = serde_json::from_value(Value::Object(arguments))
+        .map_err(|e| format!("Invalid query: {}", e))?;
+    query.limit = Some(query.limit.unwrap_or(DEFAULT_QUERY_LIMIT).max(1));
+    Ok(query)
+}
+
+async fn route(app: &AppHandle, request: Request) -> Response {
+    let url = match url::Url::parse(&format!("http://127.0.0.1{}", request.target)) {
+        Ok(url) => url,
+        Err(_) => return Response::error(400, "Invalid request path"),
+    };
+    let segments: Vec<&str> = url.path().trim_matches('/').split('/').collect();
+    if !matches!(segments.as_slice(), ["sessions"] | ["sessions", _] | ["active"]) {
+        return Response::error(404, "Not found");
+    }
+    if request.method != "GET" {
+        return Response::error(405, "Only GET is supported");
+    }
+
+    let data_dir = match profiles::profile_data_dir(app) {
+        Ok(data_dir) => data_dir,
+        Err(e) => return Response::error(500, e),
+    };
+
+    let result: Result<Response, String> = match segments.as_slice() {
+        ["sessions"] => match session_query(&url) {
+            Ok(query) => app
+                .state::<Arc<SessionIndex>>()
+                .inner()
+                .read(app, move |db| db.query(&query))
+                .await
+                .map(|page| Response::ok(json!(page))),
+            Err(e) => Ok(Response::error(400, e)),
+        },
+        ["sessions", id] => {
+            let id = id.to_string();
+            tokio::task::spawn_blocking(move || {
+                let session = session_storage::find_session(&data_dir, &id)?.map(|mut session| {
+                    diarization::apply_speaker_labels(&data_dir, &mut session);
+                    session
+                });
+                Ok(match session {
+                    Some(session) => Response::ok(json!(session)),
+                    None => Response::error(404, format!("Session {} not found", id)),
+                })
+            })
+            .await
+            .unwrap_or_else(|e| Err(format!("Session task failed: {}", e)))
+        }
+        _ => tokio::task::spawn_blocking(move || session_storage::read_active_session(&data_dir))
+            .await
+            .unwrap_or_else(|e| Err(format!("Session task failed: {}", e)))
+            .map(|session| Response::ok(json!(session))),
+    };
+
+    result.unwrap_or_else(|e| {
+        eprintln!("❌ [REST API] {} failed: {}", url.path(), e);
+        Response::error(500, e)
+    })
+}
+
+async fn handle_connection(app: AppHandle, server: Arc<ApiServer>, mut stream: TcpStream) {
+    let response = match read_request(&mut stream).await {
+        Ok(request) => {
+            let token = server.token.get();
+            if is_authorized(request.authorization.as_deref(), token.as_deref()) {
+                route(&app, request).await
+            } else {
+                Response::error(401, "Missing or invalid bearer token")
+            }
+        }
+        Err(response) => response,
+    };
+
+    let _ = stream.write_all(&response.to_bytes()).await;
+    let _ = stream.shutdown().await;
+}
+
+fn generate_token() -> String {
+    let bytes: [u8; 32] = rand::random();
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Managed server state
+pub struct ApiServer {
+    /// Port being served (None while stopped)
+    port: SafeState<Option<u16>>,
+    error: SafeState<Option<String>>,
+    /// Bearer token (loaded from the keychain when the server starts)
+    token: SafeState<Option<String>>,
+}
+
+impl ApiServer {
+    pub fn new() -> Self {
+        Self {
+            port: SafeState::new("rest_api.port", None),
+            error: SafeState::new("rest_api.error", None),
+            token: SafeState::new("rest_api.token", None),
+        }
+    }
+
+    /// Token from the keychain, generated on first use
+    fn load_token(&self) -> Result<String, String> {
+        if let Some(token) = self.token.get() {
+            return Ok(token);
+        }
+        let token = match keychain::get(TOKEN_ACCOUNT)? {
+            Some(token) => token,
+            None => {
+                let token = generate_token();
+                keychain::set(TOKEN_ACCOUNT, &token)?;
+                token
+            }
+        };
+        self.token.set(Some(token.clone()));
+        Ok(token)
+    }
+
+    /// Replace the token; requests with the old one are rejected immediately
+    fn regenerate_token(&self) -> Result<String, String> {
+        let token = generate_token();
+        keychain::set(TOKEN_ACCOUNT, &token)?;
+        self.token.set(Some(token.clone()));
+        println!("🌐 [REST API] Bearer token regenerated");
+        Ok(token)
+    }
+
+    /// Start, restart or stop the server to match settings.apiServer
+    pub fn apply(self: &Arc<Self>, app: &AppHandle, registry: &TaskRegistry, settings: &ApiServerSettings) {
+        let running = self.port.get();
+        if !settings.enabled {
+            if running.is_some() {
+                registry.cancel(TASK_NAME);
+                self.port.set(None);
+                println!("🌐 [REST API] Server stopped");
+            }
+            return;
+        }
+        if running == Some(settings.port) {
+            return;
+        }
+
+        match self.start(app, registry, settings.port) {
+            Ok(()) => self.error.set(None),
+            Err(e) => {
+                eprintln!("❌ [REST API] {}", e);
+                registry.cancel(TASK_NAME);
+                self.port.set(None);
+                self.error.set(Some(e));
+            }
+        }
+    }
+
+    fn start(self: &Arc<Self>, app: &AppHandle, registry: &TaskRegistry, port: u16) -> Result<(), String> {
+        self.load_token()?;
+
+        // Bind synchronously so a busy port is reported to the caller
+        let listener = std::net::TcpListener::bind(("127.0.0.1", port))
+            .map_err(|e| format!("Failed to bind 127.0.0.1:{}: {}", port, e))?;
+        listener
+            .set_nonblocking(true)
+            .map_err(|e| format!("Failed to configure listener: {}", e))?;
+
+        let server = self.clone();
+        let app = app.clone();
+        registry.spawn(TASK_NAME, |mut shutdown| async move {
+            let listener = match tokio::net::TcpListener::from_std(listener) {
+                Ok(listener) => listener,
+                Err(e) => {
+                    eprintln!("❌ [REST API] Failed to start listener: {}", e);
+                    return;
+                }
+            };
+            loop {
+                tokio::select! {
+                    _ = shutdown.cancelled() => break,
+                    accepted = listener.accept() => match accepted {
+                        Ok((stream, _)) => {
+                            tokio::spawn(handle_connection(app.clone(), server.clone(), stream));
+                        }
+                        Err(e) => eprintln!("⚠️  [REST API] Failed to accept connection: {}", e),
+                    },
+                }
+            }
+        })?;
+
+        self.port.set(Some(port));
+        println!("🌐 [REST API] Listening on http://127.0.0.1:{}", port);
+        Ok(())
+    }
+
+    fn status(&self, settings: &ApiServerSettings) -> ApiServerStatus {
+        let port = self.port.get();
+        ApiServerStatus {
+            running: port.is_some(),
+            port: port.unwrap_or(settings.port),
+            url: port.map(|port| format!("http://127.0.0.1:{}", port)),
+            error: self.error.get(),
+        }
+    }
+}
+
+impl Default for ApiServer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Persist enabled / port in settings; the settings subscription (lib.rs)
+/// brings the server in line
+fn configure(
+    app: &AppHandle,
+    server: &ApiServer,
+    settings: &SettingsManager,
+    patch: Value,
+) -> Result<ApiServerStatus, String> {
+    let updated = settings.update(app, json!({ "apiServer": patch }))?.api_server;
+    Ok(server.status(&updated))
+}
+
+/// Tauri command to start the REST API (optionally on a new port); stays on
+/// across restarts until stopped
+#[tauri::command]
+pub fn start_api_server(
+    app: AppHandle,
+    server: State<Arc<ApiServer>>,
+    settings: State<Arc<SettingsManager>>,
+    port: Option<u16>,
+) -> Result<ApiServerStatus, String> {
+    command_metrics::track("start_api_server", || {
+        let port = port.unwrap_or(settings.get().api_server.port);
+        let status = configure(&app, &server, &settings, json!({ "enabled": true, "port": port }))?;
+        match &status.error {
+            Some(e) if !status.running => Err(e.clone()),
+            _ => Ok(status),
+        }
+    })
+}
+
+/// Tauri command to stop the REST API
+#[tauri::command]
+pub fn stop_api_server(
+    app: AppHandle,
+    server: State<Arc<ApiServer>>,
+    settings: State<Arc<SettingsManager>>,
+) -> Result<ApiServerStatus, String> {
+    command_metrics::track("stop_api_server", || {
+        configure(&app, &server, &settings, json!({ "enabled": false }))
+    })
+}
+
+/// Tauri command to change the REST API port (restarts the server if running)
+#[tauri::command]
+pub fn set_api_server_port(
+    app: AppHandle,
+    server: State<Arc<ApiServer>>,
+    settings: State<Arc<SettingsManager>>,
+    port: u16,
+) -> Result<ApiServerStatus, String> {
+    command_metrics::track("set_api_server_port", || {
+        configure(&app, &server, &settings, json!({ "port": port }))
+    })
+}
+
+#[tauri::command]
+pub fn get_api_server_status(
+    server: State<Arc<ApiServer>>,
+    settings: State<Arc<SettingsManager>>,
+) -> Result<ApiServerStatus, String> {
+    command_metrics::track("get_api_server_status", || {
+        Ok(server.status(&settings.get().api_server))
+    })
+}
+
+/// Tauri command to read the bearer token (generated on first use)
+#[tauri::command]
+pub fn get_api_server_token(server: State<Arc<ApiServer>>) -> Result<String, String> {
+    command_metrics::track("get_api_server_token", || {
+        server.load_token()
+    })
+}
+
+/// Tauri command to replace the bearer token
+#[tauri::command]
+pub fn regenerate_api_server_token(server: State<Arc<ApiServer>>) -> Result<String, String> {
+    command_metrics::track("regenerate_api_server_token", || {
+        server.regenerate_token()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn parse(raw: &[u8]) -> Result<Request, u16> {
+        let mut stream = raw;
+        read_request(&mut stream).await.map_err(|response| response.status)
+    }
+
+    #[tokio::test]
+    async fn parses_request_head() {
+        let request = parse(b"GET /sessions?limit=5 HTTP/1.1\r\nHost: 127.0.0.1\r\nauthorization: Bearer abc\r\n\r\n")
+            .await
+            .unwrap();
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.target, "/sessions?limit=5");
+        assert_eq!(request.authorization.as_deref(), Some("Bearer abc"));
+    }
+
+    #[tokio::test]
+    async fn malformed_request_lines_are_rejected() {
+        for raw in [
+            &b"GARBAGE\r\n\r\n"[..],
+            b"GET /sessions HTTP/9.9\r\n\r\n",
+            b"GET /sessions\r\n\r\n",
+            b"\x00\x01\x02 / HTTP/1.1\r\n\r\n",
+            b"GET /sessions HTTP/1.1\r\nNo colon here\r\n\r\n",
+        ] {
+            assert_eq!(parse(raw).await.err(), Some(400), "{:?}", String::from_utf8_lossy(raw));
+        }
+    }
+
+    #[tokio::test]
+    async fn incomplete_request_is_rejected() {
+        assert_eq!(parse(b"").await.err(), Some(400));
+        assert_eq!(parse(b"GET /sessions HTTP/1.1\r\nHost: x\r\n").await.err(), Some(400));
+    }
+
+    #[tokio::test]
+    async fn oversized_headers_are_rejected() {
+        let mut raw = b"GET /sessions HTTP/1.1\r\nX-Padding: ".to_vec();
+        raw.extend(std::iter::repeat(b'a').take(MAX_REQUEST_BYTES));
+        raw.extend_from_slice(b"\r\n\r\n");
+        assert_eq!(parse(&raw).await.err(), Some(413));
+
+        let mut raw = b"GET /sessions HTTP/1.1\r\n".to_vec();
+        for i in 0..40 {
+            raw.extend_from_slice(format!("X-Header-{}: 1\r\n", i).as_bytes());
+        }
+        raw.extend_from_slice(b"\r\n");
+        assert_eq!(parse(&raw).await.err(), Some(400));
+    }
+
+    #[tokio::test]
+    async fn declared_body_is_drained() {
+        let body = vec![b'x'; 3000];
+        let mut raw = format!("POST /sessions HTTP/1.1\r\nContent-Length: {}\r\n\r\n", body.len()).into_bytes();
+        raw.extend_from_slice(&body);
+        raw.extend_from_slice(b"trailing");
+
+        let mut stream = &raw[..];
+        let request = read_request(&mut stream).await.unwrap_or_else(|r| panic!("{}", r.body));
+        assert_eq!(request.method, "POST");
+        assert!(stream.len() <= b"trailing".len());
+    }
+
+    #[tokio::test]
+    async fn content_length_is_validated() {
+        let request = |length: &str| format!("GET /active HTTP/1.1\r\nContent-Length: {}\r\n\r\n", length);
+
+        assert!(parse(request("0").as_bytes()).await.is_ok());
+        assert_eq!(parse(request("abc").as_bytes()).await.err(), Some(400));
+        assert_eq!(parse(request("-1").as_bytes()).await.err(), Some(400));
+        assert_eq!(parse(request("+5").as_bytes()).await.err(), Some(400));
+        assert_eq!(parse(request("99999999999999999999999").as_bytes()).await.err(), Some(400));
+        assert_eq!(parse(request(&(MAX_BODY_BYTES + 1).to_string()).as_bytes()).await.err(), Some(413));
+        // Declared but never sent
+        assert_eq!(parse(request("10").as_bytes()).await.err(), Some(400));
+
+        let conflicting = b"GET /active HTTP/1.1\r\nContent-Length: 1\r\nContent-Length: 2\r\n\r\nab";
+        assert_eq!(parse(conflicting).await.err(), Some(400));
+        let repeated = b"GET /active HTTP/1.1\r\nContent-Length: 2\r\nContent-Length: 2\r\n\r\nab";
+        assert!(parse(repeated).await.is_ok());
+
+        let chunked = b"GET /active HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n0\r\n\r\n";
+        assert_eq!(parse(chunked).await.err(), Some(400));
+    }
+
+    #[test]
+    fn bearer_token_is_required() {
+        let token = Some("secret-token");
+        assert!(is_authorized(Some("Bearer secret-token"), token));
+        assert!(is_authorized(Some("Bearer secret-token "), token));
+
+        assert!(!is_authorized(None, token));
+        assert!(!is_authorized(Some(""), token));
+        assert!(!is_authorized(Some("secret-token"), token));
+        assert!(!is_authorized(Some("Basic secret-token"), token));
+        assert!(!is_authorized(Some("Bearer "), token));
+        assert!(!is_authorized(Some("Bearer secret-tokeN"), token));
+        assert!(!is_authorized(Some("Bearer secret-token-and-more"), token));
+        // Token not loaded yet
+        assert!(!is_authorized(Some("Bearer secret-token"), None));
+    }
+
+    #[test]
+    fn unauthorized_response_asks_for_bearer() {
+        let bytes = Response::error(401, "Missing or invalid bearer token").to_bytes();
+        let text = String::from_utf8(bytes).unwrap();
+        assert!(text.starts_with("HTTP/1.1 401 Unauthorized\r\n"));
+        assert!(text.contains("WWW-Authenticate: Bearer\r\n"));
+
+        let (head, body) = text.split_once("\r\n\r\n").unwrap();
+        assert!(head.contains(&format!("Content-Length: {}\r\n", body.len())));
+    }
+}
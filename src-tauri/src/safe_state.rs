@@ -0,0 +1,55 @@
+/**
+ * Safe State Module
+ *
+ * `SafeState<T>`: a Mutex wrapper for shared subsystem state.
+ *
+ * A std Mutex is poisoned when a thread panics while holding it, after which
+ * every `lock()` returns an error. Previously each module hand-rolled its own
+ * recovery (skip, break, `format!("Failed to lock ...")`), with divergent
+ * behaviour. State guarded here is always left consistent between statements,
+ * so SafeState recovers the inner value, logs the poisoning once, and hands
+ * back the guard - callers never deal with lock errors.
+ */
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, MutexGuard};
+
+pub struct SafeState<T> {
+    name: &'static str,
+    inner: Mutex<T>,
+    poison_reported: AtomicBool,
+}
+
+impl<T> SafeState<T> {
+    /// `name` identifies the state in poisoning logs
+    pub const fn new(name: &'static str, value: T) -> Self {
+        Self {
+            name,
+            inner: Mutex::new(value),
+            poison_reported: AtomicBool::new(false),
+        }
+    }
+
+    /// Lock the state, recovering from poisoning
+    pub fn lock(&self) -> MutexGuard<'_, T> {
+        self.inner.lock().unwrap_or_else(|poisoned| {
+            if !self.poison_reported.swap(true, Ordering::SeqCst) {
+                eprintln!("⚠️  [STATE] '{}' lock was poisoned by a panic; recovering", self.name);
+            }
+            self.inner.clear_poison();
+            poisoned.into_inner()
+        })
+    }
+
+    /// Replace the value
+    pub fn set(&self, value: T) {
+        *self.lock() = value;
+    }
+}
+
+impl<T: Clone> SafeState<T> {
+    /// Clone the current value
+    pub fn get(&self) -> T {
+        self.lock().clone()
+    }
+}
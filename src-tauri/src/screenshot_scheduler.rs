@@ -0,0 +1,549 @@
+/**
+ * Screenshot Scheduler Module
+ *
+ * Fixed-interval session screenshots driven from Rust, so captures keep their
+ * schedule even when the webview is hidden or throttled:
+ * - One dedicated "screenshot-scheduler" thread sleeps until the next capture
+ *   is due; commands wake it whenever the schedule changes
+ * - Each capture is a composite JPEG (same pipeline as
+ *   `capture_all_screens_composite`) written to the active profile's
+ *   attachment store (`attachments/{id}.dat` + `{id}.meta.json`, the layout
+ *   attachmentStorage.ts reads)
+ * - Captures are skipped while a privacy-blocked app is frontmost (privacy.rs)
+ * - `screenshot-captured` is emitted with the ids of the stored attachment so
+ *   the frontend only has to add the record to its session
+ * - A capture whose difference hash (dHash) is within
+ *   settings.screenshots.unchangedThreshold bits of the last stored frame is
+ *   not stored; `screenshot-unchanged` is emitted instead so the frontend can
+ *   mark that frame as unchanged until then
+ *
+ * Adaptive (AI-timed) scheduling stays in the frontend.
+ */
+
+use serde::Serialize;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::command_metrics;
+use crate::privacy::PrivacyGuard;
+use crate::profiles;
+use crate::safe_state::SafeState;
+use crate::settings::SettingsManager;
+
+/// Delay before the first capture so the user can navigate away from the app
+const FIRST_CAPTURE_DELAY: Duration = Duration::from_secs(3);
+
+/// Shortest accepted interval (10 seconds)
+const MIN_INTERVAL_MINUTES: f64 = 10.0 / 60.0;
+
+/// Edge of the thumbnail stored in the attachment metadata
+const THUMBNAIL_SIZE: u32 = 400;
+
+/// dHash grid: (DHASH_SIZE + 1) x DHASH_SIZE grayscale pixels -> 64 bits
+const DHASH_SIZE: u32 = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SchedulerStatus {
+    Idle,
+    Active,
+    Paused,
+}
+
+/// Scheduler state reported to the frontend
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchedulerInfo {
+    pub status: SchedulerStatus,
+    pub session_id: Option<String>,
+    pub interval_minutes: f64,
+    pub last_capture_time: Option<String>,
+    /// Milliseconds until the next capture (active only)
+    pub next_capture_in_ms: Option<u64>,
+    pub capture_count: u64,
+}
+
+/// Payload of the `screenshot-captured` event
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreenshotCaptured {
+    pub session_id: String,
+    pub screenshot_id: String,
+    pub attachment_id: String,
+    pub timestamp: String,
+    pub size: usize,
+}
+
+/// Payload of the `screenshot-unchanged` event
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreenshotUnchanged {
+    pub session_id: String,
+    /// Attachment of the last stored frame, which this capture matched
+    pub attachment_id: String,
+    pub timestamp: String,
+    /// Differing dHash bits (0-64)
+    pub distance: u32,
+}
+
+enum Capture {
+    Stored(ScreenshotCaptured),
+    Unchanged(ScreenshotUnchanged),
+}
+
+impl Capture {
+    fn session_and_time(&self) -> (&str, &str) {
+        match self {
+            Capture::Stored(captured) => (&captured.session_id, &captured.timestamp),
+            Capture::Unchanged(unchanged) => (&unchanged.session_id, &unchanged.timestamp),
+        }
+    }
+}
+
+struct Schedule {
+    status: SchedulerStatus,
+    session_id: Option<String>,
+    interval: Duration,
+    next_capture: Option<Instant>,
+    /// Time left until the next capture when paused
+    paused_remaining: Option<Duration>,
+    last_capture_time: Option<String>,
+    capture_count: u64,
+    /// dHash and attachment id of the last stored frame
+    last_stored: Option<(u64, String)>,
+}
+
+enum Wake {
+    Changed,
+    Shutdown,
+}
+
+type CaptureFn = Box<dyn Fn(crate::settings::ScreenshotSettings) -> Result<Vec<u8>, String> + Send + Sync>;
+/// Called with (session id, capture time)
+type CaptureHook = Box<dyn Fn(&str, &str) + Send + Sync>;
+
+/// Managed screenshot scheduler (one thread, started in setup)
+pub struct ScreenshotScheduler {
+    schedule: SafeState<Schedule>,
+    wake: Sender<Wake>,
+    receiver: SafeState<Option<Receiver<Wake>>>,
+    hooks: SafeState<Vec<Arc<CaptureHook>>>,
+    next_id: AtomicU64,
+}
+
+fn interval_from_minutes(minutes: f64) -> Duration {
+    Duration::from_secs_f64(minutes.max(MIN_INTERVAL_MINUTES) * 60.0)
+}
+
+impl ScreenshotScheduler {
+    pub fn new() -> Self {
+        let (wake, receiver) = mpsc::channel();
+        Self {
+            schedule: SafeState::new("screenshot_scheduler", Schedule {
+                status: SchedulerStatus::Idle,
+                session_id: None,
+                interval: interval_from_minutes(2.0),
+                next_capture: None,
+                paused_remaining: None,
+                last_capture_time: None,
+                capture_count: 0,
+                last_stored: None,
+            }),
+            wake,
+            receiver: SafeState::new("screenshot_scheduler.receiver", Some(receiver)),
+            hooks: SafeState::new("screenshot_scheduler.hooks", Vec::new()),
+            next_id: AtomicU64::new(0),
+        }
+    }
+
+    /// Run `hook` with (session id, capture time) after every capture,
+    /// stored or unchanged (on the scheduler thread)
+    pub fn on_capture(&self, hook: impl Fn(&str, &str) + Send + Sync + 'static) {
+        self.hooks.lock().push(Arc::new(Box::new(hook)));
+    }
+
+    fn new_id(&self) -> String {
+        format!(
+            "{}-{}",
+            chrono::Utc::now().timestamp_millis(),
+            self.next_id.fetch_add(1, Ordering::SeqCst)
+        )
+    }
+
+    fn notify(&self) {
+        let _ = self.wake.send(Wake::Changed);
+    }
+
+    /// Start capturing for `session_id` (replaces any running schedule)
+    pub fn start(&self, session_id: String, interval_minutes: f64) {
+        {
+            let mut schedule = self.schedule.lock();
+            schedule.status = SchedulerStatus::Active;
+            schedule.session_id = Some(session_id.clone());
+            schedule.interval = interval_from_minutes(interval_minutes);
+            schedule.next_capture = Some(Instant::now() + FIRST_CAPTURE_DELAY);
+            schedule.paused_remaining = None;
+            schedule.last_capture_time = None;
+            schedule.capture_count = 0;
+            schedule.last_stored = None;
+        }
+        println!("📸 [SCHEDULER] Started for session {} (every {}m)", session_id, interval_minutes);
+        self.notify();
+    }
+
+    pub fn stop(&self) {
+        {
+            let mut schedule = self.schedule.lock();
+            schedule.status = SchedulerStatus::Idle;
+            schedule.session_id = None;
+            schedule.next_capture = None;
+            schedule.paused_remaining = None;
+        }
+        println!("📸 [SCHEDULER] Stopped");
+        self.notify();
+    }
+
+    /// Pause, keeping the time left until the next capture
+    pub fn pause(&self) -> Result<(), String> {
+        {
+            let mut schedule = self.schedule.lock();
+            if schedule.status != SchedulerStatus::Active {
+                return Err("Screenshot scheduler is not active".to_string());
+            }
+            schedule.status = SchedulerStatus::Paused;
+            schedule.paused_remaining = schedule
+                .next_capture
+                .take()
+                .map(|next| next.saturating_duration_since(Instant::now()));
+        }
+        println!("⏸️  [SCHEDULER] Paused");
+        self.notify();
+        Ok(())
+    }
+
+    pub fn resume(&self) -> Result<(), String> {
+        {
+            let mut schedule = self.schedule.lock();
+            if schedule.status != SchedulerStatus::Paused {
+                return Err("Screenshot scheduler is not paused".to_string());
+            }
+            let remaining = schedule.paused_remaining.take().unwrap_or(Duration::ZERO);
+            schedule.status = SchedulerStatus::Active;
+            schedule.next_capture = Some(Instant::now() + remaining);
+        }
+        println!("▶️  [SCHEDULER] Resumed");
+        self.notify();
+        Ok(())
+    }
+
+    /// Change the interval; the next capture is rescheduled from the last one
+    pub fn set_interval(&self, interval_minutes: f64) {
+        {
+            let mut schedule = self.schedule.lock();
+            let old_interval = schedule.interval;
+            schedule.interval = interval_from_minutes(interval_minutes);
+            let new_interval = schedule.interval;
+            let shift = |due: Instant| (due + new_interval).checked_sub(old_interval).unwrap_or(due);
+            if let Some(next) = schedule.next_capture {
+                schedule.next_capture = Some(shift(next));
+            }
+            if let Some(remaining) = schedule.paused_remaining {
+                schedule.paused_remaining = Some((remaining + new_interval).saturating_sub(old_interval));
+            }
+        }
+        println!("🔄 [SCHEDULER] Interval set to {}m", interval_minutes);
+        self.notify();
+    }
+
+    pub fn info(&self) -> SchedulerInfo {
+        let schedule = self.schedule.lock();
+        SchedulerInfo {
+            status: schedule.status,
+            session_id: schedule.session_id.clone(),
+            interval_minutes: schedule.interval.as_secs_f64() / 60.0,
+            last_capture_time: schedule.last_capture_time.clone(),
+            next_capture_in_ms: schedule
+                .next_capture
+                .map(|next| next.saturating_duration_since(Instant::now()).as_millis() as u64),
+            capture_count: schedule.capture_count,
+        }
+    }
+
+    /// Stop the scheduler thread (app exit)
+    pub fn shutdown(&self) {
+        let _ = self.wake.send(Wake::Shutdown);
+    }
+
+    /// Spawn the scheduler thread; `capture` produces one composite JPEG
+    pub fn start_thread(
+        self: &Arc<Self>,
+        app: AppHandle,
+        settings: Arc<SettingsManager>,
+        capture: CaptureFn,
+    ) -> Result<(), String> {
+        let receiver = self
+            .receiver
+            .lock()
+            .take()
+            .ok_or("Screenshot scheduler thread already started")?;
+        let scheduler = self.clone();
+
+        std::thread::Builder::new()
+            .name("screenshot-scheduler".to_string())
+            .spawn(move || scheduler.run(receiver, app, settings, capture))
+            .map_err(|e| format!("Failed to spawn screenshot scheduler thread: {}", e))?;
+        Ok(())
+    }
+
+    fn run(&self, receiver: Receiver<Wake>, app: AppHandle, settings: Arc<SettingsManager>, capture: CaptureFn) {
+        loop {
+            let next_capture = self.schedule.lock().next_capture;
+            let woken = match next_capture {
+                Some(next) => receiver.recv_timeout(next.saturating_duration_since(Instant::now())),
+                None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            };
+            match woken {
+                Ok(Wake::Changed) => continue,
+                Ok(Wake::Shutdown) | Err(RecvTimeoutError::Disconnected) => break,
+                Err(RecvTimeoutError::Timeout) => {}
+            }
+
+            // Claim the due capture and schedule the next one before capturing,
+            // so a slow capture doesn't push the whole schedule back
+            let session_id = {
+                let mut schedule = self.schedule.lock();
+                match (schedule.status, schedule.next_capture, schedule.session_id.clone()) {
+                    (SchedulerStatus::Active, Some(next), Some(session_id)) if next <= Instant::now() => {
+                        schedule.next_capture = Some(Instant::now() + schedule.interval);
+                        session_id
+                    }
+                    _ => continue,
+                }
+            };
+
+            if let Err(reason) = app.state::<Arc<PrivacyGuard>>().capture_allowed(&app) {
+                println!("🙈 [SCHEDULER] {}", reason);
+                continue;
+            }
+
+            match self.capture_once(&app, &settings, &capture, &session_id) {
+                Ok(capture) => {
+                    let (session_id, timestamp) = capture.session_and_time();
+                    {
+                        let mut schedule = self.schedule.lock();
+                        if schedule.session_id.as_deref() == Some(session_id) {
+                            schedule.last_capture_time = Some(timestamp.to_string());
+                            schedule.capture_count += 1;
+                        }
+                    }
+                    let hooks: Vec<Arc<CaptureHook>> = self.hooks.lock().clone();
+                    for hook in hooks {
+                        hook(session_id, timestamp);
+                    }
+                    let emitted = match &capture {
+                        Capture::Stored(captured) => app.emit("screenshot-captured", captured),
+                        Capture::Unchanged(unchanged) => app.emit("screenshot-unchanged", unchanged),
+                    };
+                    if let Err(e) = emitted {
+                        eprintln!("❌ [SCHEDULER] Failed to emit capture event: {}", e);
+                    }
+                }
+                // Keep the schedule running - the next capture may succeed
+                Err(e) => eprintln!("❌ [SCHEDULER] Capture failed: {}", e),
+            }
+        }
+        println!("📸 [SCHEDULER] Thread exiting");
+    }
+
+    fn capture_once(
+        &self,
+        app: &AppHandle,
+        settings: &SettingsManager,
+        capture: &CaptureFn,
+        session_id: &str,
+    ) -> Result<Capture, String> {
+        let screenshot_settings = settings.get().screenshots;
+        let jpeg = capture(screenshot_settings.clone())?;
+        let timestamp = chrono::Utc::now().to_rfc3339();
+        let image = image::load_from_memory(&jpeg)
+            .map_err(|e| eprintln!("⚠️  [SCHEDULER] Failed to decode screenshot: {}", e))
+            .ok();
+
+        let hash = image.as_ref().map(dhash);
+        if let (Some(hash), true) = (hash, screenshot_settings.unchanged_threshold > 0) {
+            let last_stored = self.schedule.lock().last_stored.clone();
+            if let Some((last_hash, attachment_id)) = last_stored {
+                let distance = (hash ^ last_hash).count_ones();
+                if distance <= screenshot_settings.unchanged_threshold {
+                    println!("📸 [SCHEDULER] Screen unchanged for session {} (distance {})", session_id, distance);
+                    return Ok(Capture::Unchanged(ScreenshotUnchanged {
+                        session_id: session_id.to_string(),
+                        attachment_id,
+                        timestamp,
+                        distance,
+                    }));
+                }
+            }
+        }
+
+        let thumbnail = image.as_ref().and_then(|image| {
+            thumbnail_data_url(image)
+                .map_err(|e| eprintln!("⚠️  [SCHEDULER] Failed to create thumbnail: {}", e))
+                .ok()
+        });
+        let now = chrono::Local::now();
+        let attachment_id = self.new_id();
+        let attachments_dir = profiles::profile_data_dir(app)?.join("attachments");
+        write_attachment(&attachments_dir, &attachment_id, &jpeg, thumbnail, &timestamp, &now.format("%-I:%M:%S %p").to_string())?;
+        {
+            let mut schedule = self.schedule.lock();
+            if schedule.session_id.as_deref() == Some(session_id) {
+                schedule.last_stored = hash.map(|hash| (hash, attachment_id.clone()));
+            }
+        }
+
+        println!("📸 [SCHEDULER] Captured screenshot for session {} ({}KB)", session_id, jpeg.len() / 1024);
+        Ok(Capture::Stored(ScreenshotCaptured {
+            session_id: session_id.to_string(),
+            screenshot_id: self.new_id(),
+            attachment_id,
+            timestamp,
+            size: jpeg.len(),
+        }))
+    }
+}
+
+impl Default for ScreenshotScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Difference hash: one bit per horizontally adjacent pixel pair of a
+/// downscaled grayscale frame, so re-encoding noise and small changes (a
+/// clock, a cursor) stay within a few bits
+fn dhash(image: &image::DynamicImage) -> u64 {
+    let small = image
+        .resize_exact(DHASH_SIZE + 1, DHASH_SIZE, image::imageops::FilterType::Triangle)
+        .to_luma8();
+    let mut hash = 0u64;
+    for y in 0..DHASH_SIZE {
+        for x in 0..DHASH_SIZE {
+            let brighter = small.get_pixel(x + 1, y)[0] > small.get_pixel(x, y)[0];
+            hash = (hash << 1) | brighter as u64;
+        }
+    }
+    hash
+}
+
+/// Downscaled JPEG data URL for the attachment list
+fn thumbnail_data_url(image: &image::DynamicImage) -> Result<String, String> {
+    let thumbnail = image.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE).to_rgb8();
+
+    let mut bytes = Vec::new();
+    image::codecs::jpeg::JpegEncoder::new_with_quality(&mut bytes, 70)
+        .encode_image(&thumbnail)
+        .map_err(|e| format!("Failed to encode thumbnail: {}", e))?;
+    Ok(format!(
+        "data:image/jpeg;base64,{}",
+        base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &bytes)
+    ))
+}
+
+/// Write a screenshot in the attachmentStorage.ts layout
+fn write_attachment(
+    dir: &Path,
+    id: &str,
+    jpeg: &[u8],
+    thumbnail: Option<String>,
+    created_at: &str,
+    local_time: &str,
+) -> Result<(), String> {
+    std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create attachments directory: {}", e))?;
+
+    let data_url = format!(
+        "data:image/jpeg;base64,{}",
+        base64::Engine::encode(&base64::engine::general_purpose::STANDARD, jpeg)
+    );
+    let metadata = serde_json::json!({
+        "id": id,
+        "type": "screenshot",
+        "name": format!("Screenshot {}.jpg", local_time),
+        "mimeType": "image/jpeg",
+        "size": data_url.len(),
+        "createdAt": created_at,
+        "thumbnail": thumbnail,
+    });
+
+    std::fs::write(dir.join(format!("{}.dat", id)), data_url)
+        .map_err(|e| format!("Failed to write screenshot: {}", e))?;
+    std::fs::write(dir.join(format!("{}.meta.json", id)), metadata.to_string())
+        .map_err(|e| format!("Failed to write screenshot metadata: {}", e))
+}
+
+/// Tauri command to start scheduled screenshots for a session
+/// (interval defaults to settings.screenshots.intervalMinutes)
+#[tauri::command]
+pub fn start_screenshot_scheduler(
+    scheduler: tauri::State<Arc<ScreenshotScheduler>>,
+    settings: tauri::State<Arc<SettingsManager>>,
+    session_id: String,
+    interval_minutes: Option<f64>,
+) -> Result<SchedulerInfo, String> {
+    command_metrics::track("start_screenshot_scheduler", || {
+        let interval_minutes = interval_minutes.unwrap_or(settings.get().screenshots.interval_minutes);
+        scheduler.start(session_id, interval_minutes);
+        Ok(scheduler.info())
+    })
+}
+
+/// Tauri command to stop scheduled screenshots
+#[tauri::command]
+pub fn stop_screenshot_scheduler(scheduler: tauri::State<Arc<ScreenshotScheduler>>) -> Result<(), String> {
+    command_metrics::track("stop_screenshot_scheduler", || {
+        scheduler.stop();
+        Ok(())
+    })
+}
+
+/// Tauri command to pause scheduled screenshots
+#[tauri::command]
+pub fn pause_screenshot_scheduler(scheduler: tauri::State<Arc<ScreenshotScheduler>>) -> Result<(), String> {
+    command_metrics::track("pause_screenshot_scheduler", || {
+        scheduler.pause()
+    })
+}
+
+/// Tauri command to resume paused screenshots
+#[tauri::command]
+pub fn resume_screenshot_scheduler(scheduler: tauri::State<Arc<ScreenshotScheduler>>) -> Result<(), String> {
+    command_metrics::track("resume_screenshot_scheduler", || {
+        scheduler.resume()
+    })
+}
+
+/// Tauri command to change the screenshot interval
+#[tauri::command]
+pub fn set_screenshot_interval(
+    scheduler: tauri::State<Arc<ScreenshotScheduler>>,
+    interval_minutes: f64,
+) -> Result<SchedulerInfo, String> {
+    command_metrics::track("set_screenshot_interval", || {
+        scheduler.set_interval(interval_minutes);
+        Ok(scheduler.info())
+    })
+}
+
+/// Tauri command to get the scheduler state
+#[tauri::command]
+pub fn get_screenshot_scheduler_status(
+    scheduler: tauri::State<Arc<ScreenshotScheduler>>,
+) -> Result<SchedulerInfo, String> {
+    command_metrics::track("get_screenshot_scheduler_status", || {
+        Ok(scheduler.info())
+    })
+}
@@ -0,0 +1,773 @@
+/**
+ * Session Archive Module
+ *
+ * Portable single-session archives for backup and moving sessions between
+ * machines. An archive is a zip file (entries stored uncompressed, since
+ * screenshots, audio and video are already compressed):
+ *
+ *   manifest.json               format, version, original session id
+ *   session.json                the session object as stored in sessions.json
+ *   attachments/<id>.meta.json  attachment metadata
+ *   attachments/<id>.dat        inline attachment data (screenshots, audio)
+ *   files/<id>/<name>           file-based attachments (video), from meta.path
+ *
+ * `import_session_archive` validates the manifest and entries, gives the
+ * session and any attachment whose id already exists a fresh id (rewriting
+ * every `*AttachmentId` reference), copies the attachments into the active
+ * profile and appends the session to sessions.json, keeping the file's
+ * encryption state (session_encryption.rs).
+ */
+
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::fs::File;
+use std::io::{BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::background_tasks::TaskRegistry;
+use crate::command_metrics;
+use crate::jobs::JobRegistry;
+use crate::profiles;
+use crate::session_encryption;
+use crate::session_storage;
+use crate::settings::SettingsManager;
+
+const ARCHIVE_FORMAT: &str = "taskerino-session-archive";
+const ARCHIVE_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ArchiveManifest {
+    format: String,
+    version: u32,
+    exported_at: String,
+    session_id: String,
+    /// Attachments included in the archive
+    attachments: Vec<String>,
+}
+
+/// Summary returned to the frontend (and emitted as `session-imported`)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionImportSummary {
+    pub session_id: String,
+    /// Id in the archive (differs from session_id if it already existed)
+    pub original_session_id: String,
+    pub attachments: usize,
+    /// Attachments given a new id because theirs already existed
+    pub renamed_attachments: usize,
+    /// The imported session, as written to sessions.json
+    pub session: serde_json::Value,
+}
+
+// ============================================================================
+// Zip (stored entries only)
+// ============================================================================
+
+const LOCAL_HEADER_SIGNATURE: u32 = 0x0403_4b50;
+const CENTRAL_HEADER_SIGNATURE: u32 = 0x0201_4b50;
+const END_OF_CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x0605_4b50;
+/// General purpose flag: file names are UTF-8
+const UTF8_NAMES: u16 = 0x0800;
+
+struct ZipEntry {
+    name: String,
+    crc: u32,
+    size: u32,
+    offset: u32,
+}
+
+struct ZipWriter<W: Write> {
+    out: W,
+    offset: u64,
+    entries: Vec<ZipEntry>,
+    dos_time: u16,
+    dos_date: u16,
+}
+
+impl<W: Write> ZipWriter<W> {
+    fn new(out: W) -> Self {
+        use chrono::{Datelike, Timelike};
+        let now = chrono::Local::now();
+        Self {
+            out,
+            offset: 0,
+            entries: Vec::new(),
+            dos_time: ((now.hour() << 11) | (now.minute() << 5) | (now.second() / 2)) as u16,
+            dos_date: (((now.year() - 1980).max(0) as u32) << 9 | (now.month() << 5) | now.day()) as u16,
+        }
+    }
+
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), String> {
+        self.out.write_all(bytes).map_err(|e| format!("Failed to write archive: {}", e))?;
+        self.offset += bytes.len() as u64;
+        Ok(())
+    }
+
+    fn start_entry(&mut self, name: &str, crc: u32, size: u64) -> Result<(), String> {
+        let size = u32::try_from(size).map_err(|_| format!("{} is too large for a session archive (4 GB limit)", name))?;
+        let offset = u32::try_from(self.offset).map_err(|_| "Session archive exceeds 4 GB".to_string())?;
+
+        let mut header = Vec::with_capacity(30 + name.len());
+        header.extend(LOCAL_HEADER_SIGNATURE.to_le_bytes());
+        header.extend(20u16.to_le_bytes()); // version needed
+        header.extend(UTF8_NAMES.to_le_bytes());
+        header.extend(0u16.to_le_bytes()); // stored
+        header.extend(self.dos_time.to_le_bytes());
+        header.extend(self.dos_date.to_le_bytes());
+        header.extend(crc.to_le_bytes());
+        header.extend(size.to_le_bytes()); // compressed
+        header.extend(size.to_le_bytes()); // uncompressed
+        header.extend((name.len() as u16).to_le_bytes());
+        header.extend(0u16.to_le_bytes()); // extra field
+        header.extend(name.as_bytes());
+        self.write_all(&header)?;
+
+        self.entries.push(ZipEntry { name: name.to_string(), crc, size, offset });
+        Ok(())
+    }
+
+    fn add_bytes(&mut self, name: &str, data: &[u8]) -> Result<(), String> {
+        self.start_entry(name, crc32fast::hash(data), data.len() as u64)?;
+        self.write_all(data)
+    }
+
+    /// Streams the file (read once for the CRC, once for the data)
+    fn add_file(&mut self, name: &str, path: &Path) -> Result<(), String> {
+        let open = || File::open(path).map_err(|e| format!("Failed to read {:?}: {}", path, e));
+        let mut hasher = crc32fast::Hasher::new();
+        let size = std::io::copy(&mut open()?, &mut HashWriter(&mut hasher))
+            .map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+        self.start_entry(name, hasher.finalize(), size)?;
+
+        let copied = std::io::copy(&mut open()?, &mut self.out)
+            .map_err(|e| format!("Failed to write archive: {}", e))?;
+        if copied != size {
+            return Err(format!("{:?} changed while it was being archived", path));
+        }
+        self.offset += copied;
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<W, String> {
+        let directory_offset = u32::try_from(self.offset).map_err(|_| "Session archive exceeds 4 GB".to_string())?;
+        let mut directory = Vec::new();
+        for entry in &self.entries {
+            directory.extend(CENTRAL_HEADER_SIGNATURE.to_le_bytes());
+            directory.extend(20u16.to_le_bytes()); // version made by
+            directory.extend(20u16.to_le_bytes()); // version needed
+            directory.extend(UTF8_NAMES.to_le_bytes());
+            directory.extend(0u16.to_le_bytes()); // stored
+            directory.extend(self.dos_time.to_le_bytes());
+            directory.extend(self.dos_date.to_le_bytes());
+            directory.extend(entry.crc.to_le_bytes());
+            directory.extend(entry.size.to_le_bytes());
+            directory.extend(entry.size.to_le_bytes());
+            directory.extend((entry.name.len() as u16).to_le_bytes());
+            directory.extend([0u8; 12]); // extra, comment, disk, internal + external attributes
+            directory.extend(entry.offset.to_le_bytes());
+            directory.extend(entry.name.as_bytes());
+        }
+
+        let count = (self.entries.len() as u16).to_le_bytes();
+        let mut end = Vec::with_capacity(22);
+        end.extend(END_OF_CENTRAL_DIRECTORY_SIGNATURE.to_le_bytes());
+        end.extend([0u8; 4]); // disk numbers
+        end.extend(count);
+        end.extend(count);
+        end.extend((directory.len() as u32).to_le_bytes());
+        end.extend(directory_offset.to_le_bytes());
+        end.extend(0u16.to_le_bytes()); // comment
+
+        self.write_all(&directory)?;
+        self.write_all(&end)?;
+        self.out.flush().map_err(|e| format!("Failed to write archive: {}", e))?;
+        Ok(self.out)
+    }
+}
+
+struct HashWriter<'a>(&'a mut crc32fast::Hasher);
+
+impl Write for HashWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+struct ZipReader {
+    file: File,
+    entries: HashMap<String, ZipEntry>,
+}
+
+/// Entry names are relative paths made of plain components (no `..`,
+/// absolute paths or backslashes), so nothing can be extracted outside the
+/// target directory
+fn is_safe_entry_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('/')
+        && !name.contains('\\')
+        && name.split('/').all(|part| !part.is_empty() && part != "." && part != "..")
+}
+
+fn read_u16(bytes: &[u8], at: usize) -> u16 {
+    u16::from_le_bytes([bytes[at], bytes[at + 1]])
+}
+
+fn read_u32(bytes: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]])
+}
+
+impl ZipReader {
+    fn open(path: &Path) -> Result<Self, String> {
+        let not_an_archive = || "Not a Taskerino session archive".to_string();
+        let mut file = File::open(path).map_err(|e| format!("Failed to open archive: {}", e))?;
+        let length = file.seek(SeekFrom::End(0)).map_err(|e| format!("Failed to read archive: {}", e))?;
+
+        // End of central directory record: last 22 bytes plus a comment of up to 64 KB
+        let tail_length = length.min(22 + u16::MAX as u64);
+        let mut tail = vec![0u8; tail_length as usize];
+        file.seek(SeekFrom::Start(length - tail_length))
+            .and_then(|_| file.read_exact(&mut tail))
+            .map_err(|e| format!("Failed to read archive: {}", e))?;
+        let end = (0..tail.len().saturating_sub(21))
+            .rev()
+            .find(|&i| read_u32(&tail, i) == END_OF_CENTRAL_DIRECTORY_SIGNATURE)
+            .ok_or_else(not_an_archive)?;
+        let count = read_u16(&tail, end + 10) as usize;
+        let directory_size = read_u32(&tail, end + 12) as usize;
+        let directory_offset = read_u32(&tail, end + 16) as u64;
+
+        let mut directory = vec![0u8; directory_size];
+        file.seek(SeekFrom::Start(directory_offset))
+            .and_then(|_| file.read_exact(&mut directory))
+            .map_err(|_| not_an_archive())?;
+
+        let mut entries = HashMap::new();
+        let mut at = 0;
+        for _ in 0..count {
+            if at + 46 > directory.len() || read_u32(&directory, at) != CENTRAL_HEADER_SIGNATURE {
+                return Err(not_an_archive());
+            }
+            let method = read_u16(&directory, at + 10);
+            let name_length = read_u16(&directory, at + 28) as usize;
+            let extra_length = read_u16(&directory, at + 30) as usize;
+            let comment_length = read_u16(&directory, at + 32) as usize;
+            let name = directory
+                .get(at + 46..at + 46 + name_length)
+                .map(|name| String::from_utf8_lossy(name).to_string())
+                .ok_or_else(not_an_archive)?;
+            if method != 0 {
+                return Err(format!("Unsupported compression for {} (archive was not created by Taskerino)", name));
+            }
+            if !is_safe_entry_name(&name) {
+                return Err(format!("Session archive contains an unsafe entry name: {:?}", name));
+            }
+            let entry = ZipEntry {
+                crc: read_u32(&directory, at + 16),
+                size: read_u32(&directory, at + 24),
+                offset: read_u32(&directory, at + 42),
+                name: name.clone(),
+            };
+            entries.insert(name, entry);
+            at += 46 + name_length + extra_length + comment_length;
+        }
+
+        Ok(Self { file, entries })
+    }
+
+    fn contains(&self, name: &str) -> bool {
+        self.entries.contains_key(name)
+    }
+
+    /// Copy an entry into `out`, verifying its CRC
+    fn copy_to(&mut self, name: &str, out: &mut impl Write) -> Result<(), String> {
+        let entry = self.entries.get(name).ok_or_else(|| format!("Archive is missing {}", name))?;
+        let (offset, size, crc) = (entry.offset as u64, entry.size as u64, entry.crc);
+        let corrupt = || format!("Archive entry {} is corrupt", name);
+
+        let mut header = [0u8; 30];
+        self.file
+            .seek(SeekFrom::Start(offset))
+            .and_then(|_| self.file.read_exact(&mut header))
+            .map_err(|_| corrupt())?;
+        if read_u32(&header, 0) != LOCAL_HEADER_SIGNATURE {
+            return Err(corrupt());
+        }
+        let data_offset = offset + 30 + read_u16(&header, 26) as u64 + read_u16(&header, 28) as u64;
+        self.file.seek(SeekFrom::Start(data_offset)).map_err(|_| corrupt())?;
+
+        let mut hasher = crc32fast::Hasher::new();
+        let mut reader = (&mut self.file).take(size);
+        let mut buffer = vec![0u8; 64 * 1024];
+        let mut copied = 0u64;
+        loop {
+            let read = reader.read(&mut buffer).map_err(|_| corrupt())?;
+            if read == 0 {
+                break;
+            }
+            hasher.update(&buffer[..read]);
+            out.write_all(&buffer[..read]).map_err(|e| format!("Failed to extract {}: {}", name, e))?;
+            copied += read as u64;
+        }
+        if copied != size || hasher.finalize() != crc {
+            return Err(corrupt());
+        }
+        Ok(())
+    }
+
+    fn read(&mut self, name: &str) -> Result<Vec<u8>, String> {
+        let mut data = Vec::new();
+        self.copy_to(name, &mut data)?;
+        Ok(data)
+    }
+
+    fn extract(&mut self, name: &str, path: &Path) -> Result<(), String> {
+        let mut out = BufWriter::new(File::create(path).map_err(|e| format!("Failed to create {:?}: {}", path, e))?);
+        self.copy_to(name, &mut out)?;
+        out.flush().map_err(|e| format!("Failed to write {:?}: {}", path, e))
+    }
+}
+
+// ============================================================================
+// Session helpers
+// ============================================================================
+
+/// Attachment references use `attachmentId`, `fullVideoAttachmentId`, ...
+fn is_attachment_key(key: &str) -> bool {
+    key == "attachmentId" || key.ends_with("AttachmentId")
+}
+
+pub(crate) fn collect_attachment_ids(value: &serde_json::Value, ids: &mut BTreeSet<String>) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map {
+                match value.as_str() {
+                    Some(id) if is_attachment_key(key) && !id.is_empty() => {
+                        ids.insert(id.to_string());
+                    }
+                    _ => collect_attachment_ids(value, ids),
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter().for_each(|item| collect_attachment_ids(item, ids)),
+        _ => {}
+    }
+}
+
+/// Point attachment references (and nested `sessionId`s) at their new ids
+fn rewrite_ids(value: &mut serde_json::Value, attachments: &HashMap<String, String>, session: (&str, &str)) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                let replacement = match value.as_str() {
+                    Some(id) if is_attachment_key(key) => attachments.get(id).cloned(),
+                    Some(id) if key == "sessionId" && id == session.0 => Some(session.1.to_string()),
+                    _ => None,
+                };
+                match replacement {
+                    Some(id) => *value = serde_json::Value::String(id),
+                    None => rewrite_ids(value, attachments, session),
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(|item| rewrite_ids(item, attachments, session)),
+        _ => {}
+    }
+}
+
+/// Ids become file names, so only allow plain ones
+pub(crate) fn is_safe_id(id: &str) -> bool {
+    !id.is_empty()
+        && !id.starts_with('.')
+        && id.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+/// Random UUID (v4) for de-duplicated ids
+pub(crate) fn new_id() -> String {
+    let mut bytes = [0u8; 16];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    format!("{}-{}-{}-{}-{}", &hex[0..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..32])
+}
+
+fn read_meta(attachments_dir: &Path, id: &str) -> Option<serde_json::Value> {
+    std::fs::read_to_string(attachments_dir.join(format!("{}.meta.json", id)))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+}
+
+/// Archive entry name of a file-based attachment
+fn file_entry(id: &str, path: &Path) -> String {
+    let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_else(|| "file".to_string());
+    format!("files/{}/{}", id, name)
+}
+
+/// Write the archive for `session` to `out`; returns the included attachment count
+fn write_archive(
+    out: File,
+    session: &serde_json::Value,
+    session_id: &str,
+    attachments_dir: &Path,
+    on_attachment: impl Fn(usize, usize) -> Result<(), String>,
+) -> Result<usize, String> {
+    let mut ids = BTreeSet::new();
+    collect_attachment_ids(session, &mut ids);
+
+    let mut zip = ZipWriter::new(BufWriter::new(out));
+    let mut included = Vec::new();
+    for (index, id) in ids.iter().enumerate() {
+        on_attachment(index, ids.len())?;
+        if !is_safe_id(id) {
+            eprintln!("⚠️  [ARCHIVE] Skipping attachment with unsupported id {:?}", id);
+            continue;
+        }
+        let meta = read_meta(attachments_dir, id);
+        let data_path = attachments_dir.join(format!("{}.dat", id));
+        let file_path = meta
+            .as_ref()
+            .and_then(|meta| meta.get("path"))
+            .and_then(|path| path.as_str())
+            .map(PathBuf::from)
+            .filter(|path| path.is_file());
+        if meta.is_none() && !data_path.exists() {
+            eprintln!("⚠️  [ARCHIVE] Attachment {} not found, skipping", id);
+            continue;
+        }
+
+        if let Some(meta) = &meta {
+            let content = serde_json::to_vec_pretty(meta).map_err(|e| format!("Failed to serialize metadata: {}", e))?;
+            zip.add_bytes(&format!("attachments/{}.meta.json", id), &content)?;
+        }
+        if data_path.exists() {
+            zip.add_file(&format!("attachments/{}.dat", id), &data_path)?;
+        }
+        if let Some(file_path) = file_path {
+            zip.add_file(&file_entry(id, &file_path), &file_path)?;
+        }
+        included.push(id.clone());
+    }
+
+    let manifest = ArchiveManifest {
+        format: ARCHIVE_FORMAT.to_string(),
+        version: ARCHIVE_VERSION,
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        session_id: session_id.to_string(),
+        attachments: included,
+    };
+    let count = manifest.attachments.len();
+    zip.add_bytes("manifest.json", &serde_json::to_vec_pretty(&manifest).map_err(|e| format!("Failed to serialize manifest: {}", e))?)?;
+    zip.add_bytes("session.json", &serde_json::to_vec_pretty(session).map_err(|e| format!("Failed to serialize session: {}", e))?)?;
+    zip.finish()?;
+    Ok(count)
+}
+
+/// Import an archive into `data_dir`; files written before a failure are removed
+fn import_archive(path: &Path, data_dir: &Path, encrypt_new_file: bool) -> Result<SessionImportSummary, String> {
+    let mut zip = ZipReader::open(path)?;
+    let manifest: ArchiveManifest = serde_json::from_slice(&zip.read("manifest.json")?)
+        .map_err(|_| "Not a Taskerino session archive".to_string())?;
+    if manifest.format != ARCHIVE_FORMAT {
+        return Err("Not a Taskerino session archive".to_string());
+    }
+    if manifest.version > ARCHIVE_VERSION {
+        return Err(format!(
+            "Session archive version {} is newer than supported (v{}). Please update Taskerino.",
+            manifest.version, ARCHIVE_VERSION
+        ));
+    }
+    if let Some(id) = manifest.attachments.iter().find(|id| !is_safe_id(id)) {
+        return Err(format!("Session archive contains an invalid attachment id: {:?}", id));
+    }
+
+    let mut session: serde_json::Value = serde_json::from_slice(&zip.read("session.json")?)
+        .map_err(|e| format!("Session archive contains invalid session data: {}", e))?;
+    let original_session_id = session
+        .get("id")
+        .and_then(|id| id.as_str())
+        .filter(|id| !id.is_empty())
+        .map(str::to_string)
+        .ok_or("Session archive contains a session without an id")?;
+
+    let sessions_path = data_dir.join("sessions.json");
+    let (mut sessions, encrypted): (Vec<serde_json::Value>, bool) = if sessions_path.exists() {
+        let (content, encrypted) = session_encryption::read_sessions_file(&sessions_path)?;
+        let sessions = serde_json::from_str(&content).map_err(|e| format!("Failed to parse sessions JSON: {}", e))?;
+        (sessions, encrypted)
+    } else {
+        (Vec::new(), encrypt_new_file)
+    };
+    let session_exists = |id: &str| sessions.iter().any(|s| s.get("id").and_then(|v| v.as_str()) == Some(id));
+    let session_id = if session_exists(&original_session_id) { new_id() } else { original_session_id.clone() };
+
+    let attachments_dir = data_dir.join("attachments");
+    std::fs::create_dir_all(&attachments_dir).map_err(|e| format!("Failed to create attachments directory: {}", e))?;
+
+    let mut written: Vec<PathBuf> = Vec::new();
+    let mut id_map = HashMap::new();
+    let result = (|| -> Result<(), String> {
+        for id in &manifest.attachments {
+            let exists = attachments_dir.join(format!("{}.meta.json", id)).exists()
+                || attachments_dir.join(format!("{}.dat", id)).exists();
+            let target_id = if exists { new_id() } else { id.clone() };
+
+            let data_entry = format!("attachments/{}.dat", id);
+            if zip.contains(&data_entry) {
+                let data_path = attachments_dir.join(format!("{}.dat", target_id));
+                written.push(data_path.clone());
+                zip.extract(&data_entry, &data_path)?;
+            }
+
+            let meta_entry = format!("attachments/{}.meta.json", id);
+            if zip.contains(&meta_entry) {
+                let mut meta: serde_json::Value = serde_json::from_slice(&zip.read(&meta_entry)?)
+                    .map_err(|e| format!("Session archive contains invalid metadata for {}: {}", id, e))?;
+                let file_prefix = format!("files/{}/", id);
+                let file_entry = zip.entries.keys().find(|name| name.starts_with(&file_prefix)).cloned();
+                if let (Some(entry), Some(meta)) = (file_entry, meta.as_object_mut()) {
+                    let file_name = Path::new(&entry[file_prefix.len()..])
+                        .file_name()
+                        .map(|n| n.to_string_lossy().to_string())
+                        .ok_or_else(|| format!("Session archive contains an invalid file entry: {}", entry))?;
+                    let file_path = attachments_dir.join(format!("{}-{}", target_id, file_name));
+                    written.push(file_path.clone());
+                    zip.extract(&entry, &file_path)?;
+                    meta.insert("path".to_string(), serde_json::Value::String(file_path.to_string_lossy().to_string()));
+                }
+                if let Some(meta) = meta.as_object_mut() {
+                    meta.insert("id".to_string(), serde_json::Value::String(target_id.clone()));
+                }
+                let meta_path = attachments_dir.join(format!("{}.meta.json", target_id));
+                written.push(meta_path.clone());
+                let content = serde_json::to_string_pretty(&meta).map_err(|e| format!("Failed to serialize metadata: {}", e))?;
+                std::fs::write(&meta_path, content).map_err(|e| format!("Failed to write {:?}: {}", meta_path, e))?;
+            }
+            id_map.insert(id.clone(), target_id);
+        }
+
+        rewrite_ids(&mut session, &id_map, (&original_session_id, &session_id));
+        if let Some(object) = session.as_object_mut() {
+            object.insert("id".to_string(), serde_json::Value::String(session_id.clone()));
+        }
+        sessions.push(session.clone());
+        let content = serde_json::to_string(&sessions).map_err(|e| format!("Failed to serialize sessions: {}", e))?;
+        session_encryption::write_sessions_file(&sessions_path, &content, encrypted)
+    })();
+
+    if let Err(e) = result {
+        for path in written {
+            let _ = std::fs::remove_file(path);
+        }
+        return Err(e);
+    }
+
+    Ok(SessionImportSummary {
+        renamed_attachments: id_map.iter().filter(|(from, to)| from != to).count(),
+        attachments: id_map.len(),
+        session_id,
+        original_session_id,
+        session,
+    })
+}
+
+/// Tauri command to export one session as a portable archive
+/// Runs as a job (returns the job id); the `job-finished` result is the path of
+/// the written archive (defaults to the Downloads folder)
+#[tauri::command]
+pub async fn export_session_archive(
+    app: AppHandle,
+    jobs: tauri::State<'_, Arc<JobRegistry>>,
+    tasks: tauri::State<'_, Arc<TaskRegistry>>,
+    session_id: String,
+    destination: Option<String>,
+) -> Result<String, String> {
+    command_metrics::track_async("export_session_archive", async move {
+        let data_dir = profiles::profile_data_dir(&app)?;
+
+        jobs.start(app.clone(), &tasks, "export-session-archive", move |ctx| async move {
+            ctx.progress(0.0, "Reading session");
+            let path = match destination {
+                Some(path) => PathBuf::from(path),
+                None => app
+                    .path()
+                    .download_dir()
+                    .map_err(|e| format!("Failed to get downloads dir: {}", e))?
+                    .join(format!("taskerino-session-{}.zip", chrono::Local::now().format("%Y%m%d-%H%M%S"))),
+            };
+
+            // Written next to the target and renamed once complete
+            let partial_path = path.with_extension("partial");
+            ctx.partial_output(partial_path.clone());
+            let job = ctx.clone();
+            let archive_path = path.clone();
+            let attachments = tokio::task::spawn_blocking(move || {
+                let session = session_storage::read_session_value(&data_dir, &session_id)?;
+                let out = File::create(&partial_path).map_err(|e| format!("Failed to create archive: {}", e))?;
+                let attachments = write_archive(out, &session, &session_id, &data_dir.join("attachments"), |current, total| {
+                    job.check_cancelled()?;
+                    job.progress_items(current as u64, total as u64, "Archiving attachments");
+                    Ok(())
+                })?;
+                job.check_cancelled()?;
+                std::fs::rename(&partial_path, &archive_path)
+                    .map_err(|e| format!("Failed to write session archive: {}", e))?;
+                job.commit_output(&partial_path);
+                Ok::<usize, String>(attachments)
+            })
+            .await
+            .map_err(|e| format!("Export task failed: {}", e))??;
+
+            println!("📦 [ARCHIVE] Exported session archive to {:?} ({} attachments)", path, attachments);
+            Ok(serde_json::Value::String(path.to_string_lossy().to_string()))
+        })
+    }).await
+}
+
+/// Tauri command to import a session archive into the active profile
+#[tauri::command]
+pub async fn import_session_archive(
+    app: AppHandle,
+    settings: tauri::State<'_, Arc<SettingsManager>>,
+    path: String,
+) -> Result<SessionImportSummary, String> {
+    command_metrics::track_async("import_session_archive", async move {
+        let data_dir = profiles::profile_data_dir(&app)?;
+        let encrypt_new_file = settings.get().storage.encrypt_sessions;
+
+        let summary = tokio::task::spawn_blocking(move || import_archive(Path::new(&path), &data_dir, encrypt_new_file))
+            .await
+            .map_err(|e| format!("Import task failed: {}", e))??;
+
+        println!(
+            "📦 [ARCHIVE] Imported session {} ({} attachments, {} renamed)",
+            summary.session_id, summary.attachments, summary.renamed_attachments
+        );
+        let _ = app.emit("session-imported", &summary);
+        Ok(summary)
+    }).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SESSION_ID: &str = "session-1";
+    const ATTACHMENT_ID: &str = "shot-1";
+
+    fn session() -> serde_json::Value {
+        serde_json::json!({
+            "id": SESSION_ID,
+            "name": "Archive test",
+            "screenshots": [{ "id": "s1", "sessionId": SESSION_ID, "attachmentId": ATTACHMENT_ID }],
+        })
+    }
+
+    /// Archive of `session()` with one inline attachment, in `dir`
+    fn write_test_archive(dir: &Path) -> PathBuf {
+        let attachments_dir = dir.join("source-attachments");
+        std::fs::create_dir_all(&attachments_dir).unwrap();
+        std::fs::write(attachments_dir.join(format!("{}.dat", ATTACHMENT_ID)), b"screenshot bytes").unwrap();
+        std::fs::write(
+            attachments_dir.join(format!("{}.meta.json", ATTACHMENT_ID)),
+            serde_json::json!({ "id": ATTACHMENT_ID, "type": "image" }).to_string(),
+        )
+        .unwrap();
+
+        let path = dir.join("session.zip");
+        let count = write_archive(File::create(&path).unwrap(), &session(), SESSION_ID, &attachments_dir, |_, _| Ok(())).unwrap();
+        assert_eq!(count, 1);
+        path
+    }
+
+    fn import_error(archive: &Path, data_dir: &Path) -> String {
+        match import_archive(archive, data_dir, false) {
+            Ok(summary) => panic!("import succeeded: {:?}", summary.session_id),
+            Err(e) => e,
+        }
+    }
+
+    #[test]
+    fn round_trip_restores_session_and_attachments() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = write_test_archive(dir.path());
+        let data_dir = dir.path().join("profile");
+
+        let summary = import_archive(&archive, &data_dir, false).unwrap();
+        assert_eq!(summary.session_id, SESSION_ID);
+        assert_eq!(summary.attachments, 1);
+        assert_eq!(summary.renamed_attachments, 0);
+
+        let stored = session_storage::read_session_value(&data_dir, SESSION_ID).unwrap();
+        assert_eq!(stored, session());
+        let attachments_dir = data_dir.join("attachments");
+        assert_eq!(std::fs::read(attachments_dir.join("shot-1.dat")).unwrap(), b"screenshot bytes");
+        assert!(attachments_dir.join("shot-1.meta.json").exists());
+
+        // Importing again renames the session and attachment and rewrites references
+        let again = import_archive(&archive, &data_dir, false).unwrap();
+        assert_ne!(again.session_id, SESSION_ID);
+        assert_eq!(again.renamed_attachments, 1);
+        let screenshot = &again.session["screenshots"][0];
+        assert_eq!(screenshot["sessionId"], again.session_id.as_str());
+        let new_attachment = screenshot["attachmentId"].as_str().unwrap();
+        assert_ne!(new_attachment, ATTACHMENT_ID);
+        assert!(attachments_dir.join(format!("{}.dat", new_attachment)).exists());
+    }
+
+    #[test]
+    fn corrupt_entry_is_rejected_and_nothing_is_left_behind() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = write_test_archive(dir.path());
+        let mut bytes = std::fs::read(&archive).unwrap();
+        let at = bytes.windows(16).position(|window| window == b"screenshot bytes").unwrap();
+        bytes[at] ^= 0xff;
+        std::fs::write(&archive, bytes).unwrap();
+
+        let data_dir = dir.path().join("profile");
+        let error = import_error(&archive, &data_dir);
+        assert!(error.contains("attachments/shot-1.dat is corrupt"), "{}", error);
+        assert!(!data_dir.join("attachments").join("shot-1.dat").exists());
+        assert!(!data_dir.join("sessions.json").exists());
+    }
+
+    #[test]
+    fn truncated_archive_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = write_test_archive(dir.path());
+        let bytes = std::fs::read(&archive).unwrap();
+        let data_dir = dir.path().join("profile");
+
+        for length in [0, 10, bytes.len() / 2, bytes.len() - 10] {
+            std::fs::write(&archive, &bytes[..length]).unwrap();
+            assert_eq!(import_error(&archive, &data_dir), "Not a Taskerino session archive", "length {}", length);
+        }
+    }
+
+    #[test]
+    fn entry_names_escaping_the_target_are_rejected() {
+        for name in ["files/shot-1/../../../evil.txt", "/etc/passwd", "files\\..\\evil.txt", "files//evil.txt", "./manifest.json"] {
+            assert!(!is_safe_entry_name(name), "{}", name);
+        }
+        assert!(is_safe_entry_name("files/shot-1/recording.mp4"));
+
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("evil.zip");
+        let mut zip = ZipWriter::new(File::create(&archive).unwrap());
+        zip.add_bytes("manifest.json", b"{}").unwrap();
+        zip.add_bytes("files/shot-1/../../../evil.txt", b"owned").unwrap();
+        zip.finish().unwrap();
+
+        let error = import_error(&archive, &dir.path().join("profile"));
+        assert!(error.contains("unsafe entry name"), "{}", error);
+        assert!(!dir.path().join("evil.txt").exists());
+    }
+}
@@ -0,0 +1,485 @@
+/**
+ * Session Encryption Module
+ *
+ * At-rest encryption for session data (sessions.json in each profile's data
+ * dir), since sessions hold screenshots metadata, notes and transcripts of
+ * potentially sensitive work:
+ * - AES-256-GCM with a random 256-bit key kept in the OS keychain
+ *   (keychain.rs), never on disk
+ * - Encrypted files are a small JSON envelope; every Rust reader goes through
+ *   `read_sessions_file`, which decrypts transparently, so plaintext and
+ *   encrypted files can coexist during migration
+ * - The frontend saves sessions through `save_sessions_store`
+ *   (session_storage.rs), so its writes are encrypted too; nothing under db/
+ *   holds sessions
+ * - Session change logs (session-logs/{id}.jsonl) are encrypted line by line
+ *   while the setting is on, each line its own envelope, so they stay
+ *   appendable
+ * - `enable_session_encryption` creates the key and turns the setting on,
+ *   `migrate_sessions_to_encrypted` encrypts existing plaintext files, and
+ *   `disable_session_encryption` decrypts everything back (the key is kept so
+ *   older encrypted backups stay readable)
+ * - While a file is encrypted the session index leaves notes out of its
+ *   search text, so they don't sit in plaintext next to it
+ * - The JSON is zstd-compressed before encryption (session_storage.rs);
+ *   uncompressed files from older versions are still read as-is
+ */
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use base64::Engine;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Arc;
+use tauri::AppHandle;
+
+use crate::command_metrics;
+use crate::keychain;
+use crate::profiles;
+use crate::session_storage;
+use crate::settings::SettingsManager;
+
+const ENVELOPE_FORMAT: &str = "taskerino-encrypted";
+const ENVELOPE_VERSION: u32 = 1;
+
+/// Keychain account holding the base64 session key
+const KEY_ACCOUNT: &str = "session-encryption-key";
+
+/// Session files encrypted by the migration
+const SESSION_FILES: [&str; 1] = ["sessions.json"];
+
+/// On-disk encrypted file
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EncryptedFile {
+    format: String,
+    version: u32,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// Encryption state reported to the frontend
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncryptionStatus {
+    pub enabled: bool,
+    pub key_in_keychain: bool,
+    /// Session files currently encrypted / still plaintext, across all profiles
+    pub encrypted_files: usize,
+    pub plaintext_files: usize,
+}
+
+/// Result of a migration run
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationReport {
+    pub converted: usize,
+    pub unchanged: usize,
+}
+
+fn load_key() -> Result<Option<[u8; 32]>, String> {
+    let Some(encoded) = keychain::get(KEY_ACCOUNT)? else {
+        return Ok(None);
+    };
+    let key = base64::engine::general_purpose::STANDARD
+        .decode(encoded)
+        .ok()
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .ok_or("Session encryption key in keychain is corrupt")?;
+    Ok(Some(key))
+}
+
+fn require_key() -> Result<[u8; 32], String> {
+    load_key()?.ok_or_else(|| "Session encryption key not found in keychain".to_string())
+}
+
+fn get_or_create_key() -> Result<[u8; 32], String> {
+    if let Some(key) = load_key()? {
+        return Ok(key);
+    }
+    let mut key = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut key);
+    keychain::set(KEY_ACCOUNT, &base64::engine::general_purpose::STANDARD.encode(key))?;
+    println!("🔐 [ENCRYPTION] Created session encryption key in keychain");
+    Ok(key)
+}
+
+/// The envelope, if `content` is an encrypted file
+fn parse_envelope(content: &[u8]) -> Option<EncryptedFile> {
+    // Plaintext session files are JSON arrays (or zstd frames)
+    if content.iter().find(|byte| !byte.is_ascii_whitespace()) != Some(&b'{') {
+        return None;
+    }
+    serde_json::from_slice::<EncryptedFile>(content)
+        .ok()
+        .filter(|envelope| envelope.format == ENVELOPE_FORMAT)
+}
+
+fn encrypt(key: &[u8; 32], plaintext: &[u8]) -> Result<String, String> {
+    let mut nonce = [0u8; 12];
+    rand::thread_rng().fill_bytes(&mut nonce);
+
+    let ciphertext = Aes256Gcm::new(key.into())
+        .encrypt(&Nonce::from(nonce), plaintext)
+        .map_err(|_| "Failed to encrypt session data".to_string())?;
+
+    let b64 = base64::engine::general_purpose::STANDARD;
+    serde_json::to_string(&EncryptedFile {
+        format: ENVELOPE_FORMAT.to_string(),
+        version: ENVELOPE_VERSION,
+        nonce: b64.encode(nonce),
+        ciphertext: b64.encode(ciphertext),
+    })
+    .map_err(|e| format!("Failed to serialize encrypted session data: {}", e))
+}
+
+fn decrypt(key: &[u8; 32], envelope: &EncryptedFile) -> Result<Vec<u8>, String> {
+    if envelope.version > ENVELOPE_VERSION {
+        return Err(format!(
+            "Encrypted session data version {} is newer than supported (v{}). Please update Taskerino.",
+            envelope.version, ENVELOPE_VERSION
+        ));
+    }
+
+    let b64 = base64::engine::general_purpose::STANDARD;
+    let nonce: [u8; 12] = b64
+        .decode(&envelope.nonce)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or("Corrupt encrypted session data: invalid nonce")?;
+    let ciphertext = b64
+        .decode(&envelope.ciphertext)
+        .map_err(|e| format!("Corrupt encrypted session data: {}", e))?;
+
+    Aes256Gcm::new(key.into())
+        .decrypt(&Nonce::from(nonce), ciphertext.as_ref())
+        .map_err(|_| "Failed to decrypt session data (wrong key or corrupt file)".to_string())
+}
+
+/// Read a session file, decrypting it if needed but leaving it compressed;
+/// returns the data and whether the file was encrypted
+pub fn read_sessions_data(path: &Path) -> Result<(Vec<u8>, bool), String> {
+    let content = std::fs::read(path)
+        .map_err(|e| format!("Failed to read sessions file: {}", e))?;
+
+    match parse_envelope(&content) {
+        Some(envelope) => Ok((decrypt(&require_key()?, &envelope)?, true)),
+        None => Ok((content, false)),
+    }
+}
+
+/// Read a session file, decrypting and decompressing it if needed; returns
+/// the JSON and whether the file was encrypted
+pub fn read_sessions_file(path: &Path) -> Result<(String, bool), String> {
+    let (data, encrypted) = read_sessions_data(path)?;
+    Ok((session_storage::decode_sessions_json(data)?, encrypted))
+}
+
+/// A change-log line, decrypted if it was written encrypted; the key is
+/// loaded into `key` on the first encrypted line
+pub(crate) fn decode_log_line(line: &str, key: &mut Option<[u8; 32]>) -> Result<String, String> {
+    let Some(envelope) = parse_envelope(line.as_bytes()) else {
+        return Ok(line.to_string());
+    };
+    let key = match key {
+        Some(key) => *key,
+        None => *key.insert(require_key()?),
+    };
+    String::from_utf8(decrypt(&key, &envelope)?)
+        .map_err(|e| format!("Corrupt encrypted session log line: {}", e))
+}
+
+/// A change-log line as an envelope (compact JSON, so still one line)
+pub(crate) fn encrypt_log_line(key: &[u8; 32], line: &str) -> Result<String, String> {
+    encrypt(key, line.as_bytes())
+}
+
+/// The session key, for encrypting change-log lines
+pub(crate) fn session_key() -> Result<[u8; 32], String> {
+    require_key()
+}
+
+/// Write a session file compressed, and encrypted when `encrypted` (mirrors
+/// read_sessions_file)
+pub fn write_sessions_file(path: &Path, content: &str, encrypted: bool) -> Result<(), String> {
+    let compressed = session_storage::compress_sessions_json(content)?;
+    if encrypted {
+        write_atomic(path, encrypt(&require_key()?, &compressed)?)
+    } else {
+        write_atomic(path, compressed)
+    }
+}
+
+/// Replace a file via a temp file + rename so a crash can't leave it half-written
+fn write_atomic(path: &Path, content: impl AsRef<[u8]>) -> Result<(), String> {
+    let temp_path = path.with_extension("json.tmp");
+    std::fs::write(&temp_path, content)
+        .map_err(|e| format!("Failed to write {:?}: {}", temp_path, e))?;
+    std::fs::rename(&temp_path, path).map_err(|e| {
+        let _ = std::fs::remove_file(&temp_path);
+        format!("Failed to replace {:?}: {}", path, e)
+    })
+}
+
+/// Session files that exist in every profile
+pub(crate) fn session_files(app: &AppHandle) -> Result<Vec<std::path::PathBuf>, String> {
+    Ok(profiles::all_profile_data_dirs(app)?
+        .into_iter()
+        .flat_map(|dir| SESSION_FILES.map(|file| dir.join(file)))
+        .filter(|path| path.exists())
+        .collect())
+}
+
+/// Encrypt (or decrypt) every session file that isn't already in that state,
+/// along with its profile's change logs; each profile is converted with
+/// session writes held off, so a save can't land between reading and
+/// replacing a file
+fn convert_all(app: &AppHandle, key: &[u8; 32], to_encrypted: bool) -> Result<MigrationReport, String> {
+    let mut report = MigrationReport { converted: 0, unchanged: 0 };
+    for path in session_files(app)? {
+        let converted = session_storage::with_sessions_locked(|| {
+            let converted = convert_file(&path, key, to_encrypted)?;
+            if let Some(data_dir) = path.parent() {
+                convert_logs(data_dir, key, to_encrypted)?;
+            }
+            Ok(converted)
+        })?;
+        if !converted {
+            report.unchanged += 1;
+            continue;
+        }
+        report.converted += 1;
+        println!("🔐 [ENCRYPTION] {} {:?}", if to_encrypted { "Encrypted" } else { "Decrypted" }, path);
+    }
+    Ok(report)
+}
+
+/// Encrypt (or decrypt) one session file; false if it was already in that state
+fn convert_file(path: &Path, key: &[u8; 32], to_encrypted: bool) -> Result<bool, String> {
+    let content = std::fs::read(path)
+        .map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+    // Compressed files stay compressed
+    let converted = match (parse_envelope(&content), to_encrypted) {
+        (None, true) => {
+            // Never replace a file we couldn't read back
+            let json = session_storage::decode_sessions_json(content.clone())?;
+            serde_json::from_str::<serde_json::Value>(&json)
+                .map_err(|e| format!("Refusing to encrypt {:?}: not valid JSON ({})", path, e))?;
+            encrypt(key, &content)?.into_bytes()
+        }
+        (Some(envelope), false) => decrypt(key, &envelope)?,
+        _ => return Ok(false),
+    };
+    write_atomic(path, &converted)?;
+    Ok(true)
+}
+
+/// Encrypt (or decrypt) every line of a profile's change logs that isn't
+/// already in that state
+fn convert_logs(data_dir: &Path, key: &[u8; 32], to_encrypted: bool) -> Result<(), String> {
+    let Ok(entries) = std::fs::read_dir(data_dir.join(session_storage::SESSION_LOGS_DIR)) else {
+        return Ok(());
+    };
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.extension().and_then(|ext| ext.to_str()) != Some("jsonl") {
+            continue;
+        }
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+        let mut converted = String::with_capacity(content.len());
+        for line in content.lines().filter(|line| !line.trim().is_empty()) {
+            match (parse_envelope(line.as_bytes()), to_encrypted) {
+                (None, true) => converted.push_str(&encrypt(key, line.as_bytes())?),
+                (Some(envelope), false) => converted.push_str(
+                    &String::from_utf8(decrypt(key, &envelope)?)
+                        .map_err(|e| format!("Corrupt encrypted line in {:?}: {}", path, e))?,
+                ),
+                _ => converted.push_str(line),
+            }
+            converted.push('\n');
+        }
+        write_atomic(&path, converted)?;
+    }
+    Ok(())
+}
+
+fn status(app: &AppHandle, settings: &SettingsManager) -> Result<EncryptionStatus, String> {
+    let mut status = EncryptionStatus {
+        enabled: settings.get().storage.encrypt_sessions,
+        key_in_keychain: load_key()?.is_some(),
+        encrypted_files: 0,
+        plaintext_files: 0,
+    };
+    for path in session_files(app)? {
+        let content = std::fs::read(&path)
+            .map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+        match parse_envelope(&content) {
+            Some(_) => status.encrypted_files += 1,
+            None => status.plaintext_files += 1,
+        }
+    }
+    Ok(status)
+}
+
+/// Tauri command to report session encryption state
+#[tauri::command]
+pub async fn get_session_encryption_status(
+    app: AppHandle,
+    settings: tauri::State<'_, Arc<SettingsManager>>,
+) -> Result<EncryptionStatus, String> {
+    command_metrics::track_async("get_session_encryption_status", async move {
+        let settings = settings.inner().clone();
+        tokio::task::spawn_blocking(move || status(&app, &settings))
+            .await
+            .map_err(|e| format!("Encryption task failed: {}", e))?
+    }).await
+}
+
+/// Tauri command to turn on session encryption (creates the keychain key);
+/// existing plaintext files are converted by `migrate_sessions_to_encrypted`
+#[tauri::command]
+pub async fn enable_session_encryption(
+    app: AppHandle,
+    settings: tauri::State<'_, Arc<SettingsManager>>,
+) -> Result<EncryptionStatus, String> {
+    command_metrics::track_async("enable_session_encryption", async move {
+        let settings = settings.inner().clone();
+        tokio::task::spawn_blocking(move || {
+            get_or_create_key()?;
+            settings.update(&app, serde_json::json!({ "storage": { "encryptSessions": true } }))?;
+            println!("🔐 [ENCRYPTION] Session encryption enabled");
+            status(&app, &settings)
+        })
+        .await
+        .map_err(|e| format!("Encryption task failed: {}", e))?
+    }).await
+}
+
+/// Tauri command to turn off session encryption, decrypting every session file
+#[tauri::command]
+pub async fn disable_session_encryption(
+    app: AppHandle,
+    settings: tauri::State<'_, Arc<SettingsManager>>,
+) -> Result<MigrationReport, String> {
+    command_metrics::track_async("disable_session_encryption", async move {
+        let settings = settings.inner().clone();
+        tokio::task::spawn_blocking(move || {
+            let report = match load_key()? {
+                Some(key) => convert_all(&app, &key, false)?,
+                None => MigrationReport { converted: 0, unchanged: session_files(&app)?.len() },
+            };
+            settings.update(&app, serde_json::json!({ "storage": { "encryptSessions": false } }))?;
+            println!("🔓 [ENCRYPTION] Session encryption disabled ({} files decrypted)", report.converted);
+            Ok(report)
+        })
+        .await
+        .map_err(|e| format!("Encryption task failed: {}", e))?
+    }).await
+}
+
+/// Tauri command to encrypt existing plaintext session files in every profile
+#[tauri::command]
+pub async fn migrate_sessions_to_encrypted(
+    app: AppHandle,
+    settings: tauri::State<'_, Arc<SettingsManager>>,
+) -> Result<MigrationReport, String> {
+    command_metrics::track_async("migrate_sessions_to_encrypted", async move {
+        if !settings.get().storage.encrypt_sessions {
+            return Err("Session encryption is not enabled".to_string());
+        }
+        tokio::task::spawn_blocking(move || {
+            let report = convert_all(&app, &require_key()?, true)?;
+            println!(
+                "🔐 [ENCRYPTION] Migration complete: {} encrypted, {} already encrypted",
+                report.converted, report.unchanged
+            );
+            Ok(report)
+        })
+        .await
+        .map_err(|e| format!("Encryption task failed: {}", e))?
+    }).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: [u8; 32] = [7; 32];
+
+    fn envelope(content: &str) -> EncryptedFile {
+        parse_envelope(content.as_bytes()).expect("should be an encrypted envelope")
+    }
+
+    #[test]
+    fn encrypt_then_decrypt_round_trips() {
+        let plaintext = session_storage::compress_sessions_json(r#"[{"id":"s1","notes":"secret"}]"#).unwrap();
+        let encrypted = encrypt(&KEY, &plaintext).unwrap();
+
+        assert!(!encrypted.contains("secret"));
+        assert_eq!(decrypt(&KEY, &envelope(&encrypted)).unwrap(), plaintext);
+        assert_eq!(
+            session_storage::decode_sessions_json(decrypt(&KEY, &envelope(&encrypted)).unwrap()).unwrap(),
+            r#"[{"id":"s1","notes":"secret"}]"#
+        );
+    }
+
+    #[test]
+    fn every_encryption_uses_a_fresh_nonce() {
+        let first = envelope(&encrypt(&KEY, b"[]").unwrap());
+        let second = envelope(&encrypt(&KEY, b"[]").unwrap());
+        assert_ne!(first.nonce, second.nonce);
+        assert_ne!(first.ciphertext, second.ciphertext);
+    }
+
+    #[test]
+    fn wrong_key_is_rejected() {
+        let encrypted = encrypt(&KEY, b"[]").unwrap();
+        let error = decrypt(&[8; 32], &envelope(&encrypted)).unwrap_err();
+        assert!(error.contains("wrong key"), "{}", error);
+    }
+
+    #[test]
+    fn tampered_or_malformed_envelopes_are_rejected() {
+        let mut file = envelope(&encrypt(&KEY, b"[1,2,3]").unwrap());
+        let b64 = base64::engine::general_purpose::STANDARD;
+        let mut ciphertext = b64.decode(&file.ciphertext).unwrap();
+        ciphertext[0] ^= 1;
+        file.ciphertext = b64.encode(&ciphertext);
+        assert!(decrypt(&KEY, &file).is_err());
+
+        let mut file = envelope(&encrypt(&KEY, b"[]").unwrap());
+        file.nonce = b64.encode([0u8; 5]);
+        assert!(decrypt(&KEY, &file).unwrap_err().contains("invalid nonce"));
+
+        let mut file = envelope(&encrypt(&KEY, b"[]").unwrap());
+        file.version = ENVELOPE_VERSION + 1;
+        assert!(decrypt(&KEY, &file).unwrap_err().contains("newer than supported"));
+    }
+
+    #[test]
+    fn only_envelopes_are_treated_as_encrypted() {
+        assert!(parse_envelope(encrypt(&KEY, b"[]").unwrap().as_bytes()).is_some());
+        assert!(parse_envelope(b"[]").is_none());
+        assert!(parse_envelope(b"  [{\"id\":\"s1\"}]").is_none());
+        assert!(parse_envelope(&session_storage::compress_sessions_json("[]").unwrap()).is_none());
+        assert!(parse_envelope(br#"{"format":"other","version":1,"nonce":"","ciphertext":""}"#).is_none());
+        assert!(parse_envelope(b"{not json").is_none());
+    }
+
+    #[test]
+    fn files_are_converted_once_each_way() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sessions.json");
+        let compressed = session_storage::compress_sessions_json(r#"[{"id":"s1","notes":"secret"}]"#).unwrap();
+        std::fs::write(&path, &compressed).unwrap();
+
+        assert!(convert_file(&path, &KEY, true).unwrap());
+        assert!(!convert_file(&path, &KEY, true).unwrap());
+        assert!(parse_envelope(&std::fs::read(&path).unwrap()).is_some());
+
+        assert!(convert_file(&path, &KEY, false).unwrap());
+        assert!(!convert_file(&path, &KEY, false).unwrap());
+        assert_eq!(std::fs::read(&path).unwrap(), compressed);
+    }
+}
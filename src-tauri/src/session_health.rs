@@ -0,0 +1,287 @@
+/**
+ * Session Health Module
+ *
+ * One report on how a recording is doing, instead of asking audio
+ * (`get_audio_health_status`) and video separately:
+ * - video: frames written and dropped (encoder behind or failing, counted by
+ *   ScreenRecorder.swift) and the drop rate
+ * - audio: buffer overruns (media buffer budget full), evicted binary chunks,
+ *   stalled system audio
+ * - disk: how fast video files and audio chunk files grow, free space
+ * - CPU: share of one core spent in the capture callbacks (ScreenCaptureKit
+ *   frame queue, cpal and system audio callbacks) and by the whole process
+ * - the last error each subsystem reported
+ *
+ * Rates cover the time since the previous periodic report (0 in the first
+ * one). The "session-health" task emits `session-health` every
+ * REPORT_INTERVAL while a session records; `get_session_health` builds a
+ * report on demand.
+ */
+
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::audio_capture::{AudioRecorder, RecordingState};
+use crate::background_tasks::TaskRegistry;
+use crate::command_metrics;
+use crate::disk_space;
+use crate::recording_limits;
+use crate::safe_state::SafeState;
+use crate::video_recording::VideoRecorder;
+
+const TASK_NAME: &str = "session-health";
+const REPORT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// `session-health` payload and `get_session_health` result
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionHealth {
+    pub session_id: String,
+    pub timestamp: String,
+    /// Seconds the rates are measured over (0 = no previous report)
+    pub interval_secs: f64,
+    pub video: VideoHealth,
+    pub audio: AudioHealth,
+    pub disk: DiskHealth,
+    /// Whole process, percent of one core (None where unsupported)
+    pub process_cpu_percent: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VideoHealth {
+    pub recording: bool,
+    pub paused: bool,
+    /// Totals since the recording started (all displays)
+    pub frames_written: u64,
+    pub frames_dropped: u64,
+    /// Share of frames dropped over the interval (over the whole recording
+    /// in the first report), 0-1
+    pub drop_rate: f64,
+    /// Frame processing on the capture queue, percent of one core
+    pub cpu_percent: f64,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioHealth {
+    pub state: RecordingState,
+    /// Totals since the recording started
+    pub buffer_overruns: u64,
+    pub dropped_chunks: u64,
+    pub system_audio_stalled: bool,
+    /// Capture callbacks, percent of one core
+    pub cpu_percent: f64,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskHealth {
+    /// Video files and audio chunk files together
+    pub write_bytes_per_sec: f64,
+    /// Size of the video files being recorded
+    pub video_bytes: u64,
+    /// Free space on the app data volume
+    pub available_bytes: Option<u64>,
+}
+
+/// Counters a report's rates are computed from
+#[derive(Debug, Clone)]
+struct Sample {
+    session_id: String,
+    at: Instant,
+    frames_written: u64,
+    frames_dropped: u64,
+    video_busy_nanos: u64,
+    audio_busy_nanos: u64,
+    bytes_written: u64,
+    process_cpu: Option<Duration>,
+}
+
+/// CPU time used by this process so far
+#[cfg(unix)]
+fn process_cpu_time() -> Option<Duration> {
+    let mut usage: libc::rusage = unsafe { std::mem::zeroed() };
+    if unsafe { libc::getrusage(libc::RUSAGE_SELF, &mut usage) } != 0 {
+        return None;
+    }
+    let time = |tv: libc::timeval| Duration::from_secs(tv.tv_sec as u64) + Duration::from_micros(tv.tv_usec as u64);
+    Some(time(usage.ru_utime) + time(usage.ru_stime))
+}
+
+#[cfg(not(unix))]
+fn process_cpu_time() -> Option<Duration> {
+    None
+}
+
+/// Percent of one core `busy_nanos` of work over `elapsed` amounts to
+fn cpu_percent(busy_nanos: u64, elapsed: Duration) -> f64 {
+    if elapsed.is_zero() {
+        return 0.0;
+    }
+    busy_nanos as f64 / elapsed.as_nanos() as f64 * 100.0
+}
+
+fn drop_rate(written: u64, dropped: u64) -> f64 {
+    match written + dropped {
+        0 => 0.0,
+        total => dropped as f64 / total as f64,
+    }
+}
+
+/// Managed session health state
+pub struct SessionHealthMonitor {
+    /// Counters at the last periodic report
+    previous: SafeState<Option<Sample>>,
+}
+
+impl SessionHealthMonitor {
+    pub fn new() -> Self {
+        Self {
+            previous: SafeState::new("session_health.previous", None),
+        }
+    }
+
+    /// Build a report for `session_id` against the last periodic sample;
+    /// blocks on the recorders' locks and file metadata
+    fn measure(&self, app: &AppHandle, session_id: &str) -> (SessionHealth, Sample) {
+        let (video_recording, video_paused, frames, video_bytes) = {
+            let recorder = app.state::<Arc<SafeState<VideoRecorder>>>();
+            let recorder = recorder.lock();
+            (recorder.is_recording(), recorder.is_paused(), recorder.frame_stats(), recorder.total_file_size())
+        };
+        let audio_recorder = app.state::<Arc<AudioRecorder>>();
+        let audio = audio_recorder.capture_stats();
+        let audio_status = audio_recorder.health_status();
+
+        let sample = Sample {
+            session_id: session_id.to_string(),
+            at: Instant::now(),
+            frames_written: frames.frames_written,
+            frames_dropped: frames.frames_dropped,
+            video_busy_nanos: frames.busy_nanos,
+            audio_busy_nanos: audio.busy_nanos,
+            bytes_written: video_bytes + audio.chunk_bytes_written,
+            process_cpu: process_cpu_time(),
+        };
+        // Counters restart with a new session; measure from zero until then
+        let previous = self.previous.get().filter(|previous| previous.session_id == session_id);
+        let baseline = previous.clone().unwrap_or_else(|| Sample {
+            session_id: session_id.to_string(),
+            at: sample.at,
+            frames_written: 0,
+            frames_dropped: 0,
+            video_busy_nanos: 0,
+            audio_busy_nanos: 0,
+            bytes_written: sample.bytes_written,
+            process_cpu: sample.process_cpu,
+        });
+        let elapsed = sample.at - baseline.at;
+
+        let available_bytes = app
+            .path()
+            .app_data_dir()
+            .ok()
+            .and_then(|data_dir| disk_space::available_bytes(&data_dir).ok());
+
+        let report = SessionHealth {
+            session_id: session_id.to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            interval_secs: elapsed.as_secs_f64(),
+            video: VideoHealth {
+                recording: video_recording,
+                paused: video_paused,
+                frames_written: frames.frames_written,
+                frames_dropped: frames.frames_dropped,
+                drop_rate: drop_rate(
+                    frames.frames_written.saturating_sub(baseline.frames_written),
+                    frames.frames_dropped.saturating_sub(baseline.frames_dropped),
+                ),
+                cpu_percent: cpu_percent(frames.busy_nanos.saturating_sub(baseline.video_busy_nanos), elapsed),
+                last_error: frames.last_error,
+            },
+            audio: AudioHealth {
+                state: audio_status.state,
+                buffer_overruns: audio.buffer_overruns,
+                dropped_chunks: audio.dropped_chunks,
+                system_audio_stalled: audio_status.system_audio_stalled,
+                cpu_percent: cpu_percent(audio.busy_nanos.saturating_sub(baseline.audio_busy_nanos), elapsed),
+                last_error: audio.last_error,
+            },
+            disk: DiskHealth {
+                // A rolled-over or stitched file shrinks the total; report 0 then
+                write_bytes_per_sec: match elapsed.is_zero() {
+                    true => 0.0,
+                    false => sample.bytes_written.saturating_sub(baseline.bytes_written) as f64 / elapsed.as_secs_f64(),
+                },
+                video_bytes,
+                available_bytes,
+            },
+            process_cpu_percent: match (sample.process_cpu, baseline.process_cpu) {
+                (Some(now), Some(before)) if previous.is_some() => {
+                    Some(cpu_percent(now.saturating_sub(before).as_nanos() as u64, elapsed))
+                }
+                _ => None,
+            },
+        };
+        (report, sample)
+    }
+
+    /// Emit `session-health` every REPORT_INTERVAL while a session records
+    pub fn start(self: &Arc<Self>, app: AppHandle, registry: &TaskRegistry) -> Result<(), String> {
+        let monitor = self.clone();
+        registry.spawn(TASK_NAME, |mut shutdown| async move {
+            let mut interval = tokio::time::interval(REPORT_INTERVAL);
+            loop {
+                tokio::select! {
+                    _ = shutdown.cancelled() => break,
+                    _ = interval.tick() => {}
+                }
+
+                let Some(session_id) = recording_limits::running_session(&app) else {
+                    monitor.previous.set(None);
+                    continue;
+                };
+                let app = app.clone();
+                let monitor = monitor.clone();
+                let measured = tokio::task::spawn_blocking(move || {
+                    let (report, sample) = monitor.measure(&app, &session_id);
+                    monitor.previous.set(Some(sample));
+                    let _ = app.emit("session-health", &report);
+                })
+                .await;
+                if let Err(e) = measured {
+                    eprintln!("⚠️  [HEALTH] Report failed: {}", e);
+                }
+            }
+        })
+    }
+}
+
+impl Default for SessionHealthMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Tauri command to get a health report for the session being recorded
+#[tauri::command]
+pub async fn get_session_health(
+    app: AppHandle,
+    monitor: State<'_, Arc<SessionHealthMonitor>>,
+    session_id: String,
+) -> Result<SessionHealth, String> {
+    command_metrics::track_async("get_session_health", async move {
+        if recording_limits::running_session(&app).as_deref() != Some(session_id.as_str()) {
+            return Err(format!("Session {} is not recording", session_id));
+        }
+        let monitor = monitor.inner().clone();
+        tokio::task::spawn_blocking(move || monitor.measure(&app, &session_id).0)
+            .await
+            .map_err(|e| format!("Health report failed: {}", e))
+    }).await
+}
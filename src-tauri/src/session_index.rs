@@ -0,0 +1,591 @@
+/**
+ * Session Index Module
+ *
+ * SQLite index of sessions.json (`sessions.index.db` in the profile data dir)
+ * so list/search/count/query are indexed queries instead of full scans:
+ * - `sessions` table: one row per session with the summary columns, indexed
+ *   for sorting (start time, name, duration, category)
+ * - `sessions_fts`: FTS5 trigram index over lowercased name/category/notes
+ *   and the OCR text of the session's screenshots (ocr.rs), so substring
+ *   search is an index lookup
+ * - The DB is derived data: sessions.json stays the source of truth. Every
+ *   write to it (frontend save, profile switch, external edit) changes its
+ *   fingerprint, and the next sync upserts changed sessions and deletes
+ *   removed ones in one transaction (unchanged rows are skipped by hash)
+ * - Migration: a missing DB, an older schema version or an unreadable file
+ *   is rebuilt from sessions.json
+ * - The "session-index-watcher" background task syncs after writes; reads
+ *   also sync first if the file changed since the last sync
+ * - OCR text lives next to the attachments, outside sessions.json; ocr.rs
+ *   calls `invalidate` after storing new text so the next sync picks it up
+ * - Encrypted sessions.json files (session_encryption.rs) are decrypted on
+ *   sync; their notes and OCR text are left out of the search text
+ */
+
+use rayon::prelude::*;
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant, UNIX_EPOCH};
+use tauri::AppHandle;
+
+use crate::background_tasks::TaskRegistry;
+use crate::ocr;
+use crate::profiles;
+use crate::safe_state::SafeState;
+use crate::session_encryption;
+use crate::session_models::{Session, SessionSummary};
+
+/// How often the watcher checks sessions.json for changes
+const WATCH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Index database file, next to sessions.json
+pub const INDEX_DB_FILE: &str = "sessions.index.db";
+
+/// Bump when the schema changes; older databases are rebuilt
+const SCHEMA_VERSION: i64 = 2;
+
+/// Separates fields in the search text so matches can't span fields
+const FIELD_SEPARATOR: char = '\u{1f}';
+
+/// Trigram search needs at least this many characters; shorter queries scan
+const MIN_TRIGRAM_QUERY_CHARS: usize = 3;
+
+const SCHEMA: &str = "
+    CREATE TABLE sessions (
+        id TEXT PRIMARY KEY,
+        name TEXT NOT NULL,
+        start_time TEXT NOT NULL,
+        end_time TEXT,
+        duration INTEGER,
+        category TEXT,
+        screenshot_count INTEGER NOT NULL,
+        audio_segment_count INTEGER NOT NULL,
+        has_video INTEGER NOT NULL,
+        has_notes INTEGER NOT NULL,
+        has_transcript INTEGER NOT NULL,
+        search_text TEXT NOT NULL,
+        content_hash INTEGER NOT NULL
+    );
+    CREATE INDEX sessions_start_time ON sessions(start_time);
+    CREATE INDEX sessions_name ON sessions(name COLLATE NOCASE);
+    CREATE INDEX sessions_duration ON sessions(duration);
+    CREATE INDEX sessions_category ON sessions(category COLLATE NOCASE);
+
+    CREATE VIRTUAL TABLE sessions_fts USING fts5(
+        search_text, content='sessions', content_rowid='rowid', tokenize='trigram'
+    );
+    CREATE TRIGGER sessions_ai AFTER INSERT ON sessions BEGIN
+        INSERT INTO sessions_fts(rowid, search_text) VALUES (new.rowid, new.search_text);
+    END;
+    CREATE TRIGGER sessions_ad AFTER DELETE ON sessions BEGIN
+        INSERT INTO sessions_fts(sessions_fts, rowid, search_text) VALUES ('delete', old.rowid, old.search_text);
+    END;
+    CREATE TRIGGER sessions_au AFTER UPDATE ON sessions BEGIN
+        INSERT INTO sessions_fts(sessions_fts, rowid, search_text) VALUES ('delete', old.rowid, old.search_text);
+        INSERT INTO sessions_fts(rowid, search_text) VALUES (new.rowid, new.search_text);
+    END;
+
+    CREATE TABLE meta (key TEXT PRIMARY KEY, value TEXT NOT NULL);
+";
+
+const DROP_SCHEMA: &str = "
+    DROP TABLE IF EXISTS sessions_fts;
+    DROP TABLE IF EXISTS sessions;
+    DROP TABLE IF EXISTS meta;
+";
+
+const SUMMARY_COLUMNS: &str = "id, name, start_time, end_time, duration, category, screenshot_count, \
+     audio_segment_count, has_video, has_notes, has_transcript";
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SortField {
+    #[default]
+    StartTime,
+    Name,
+    Duration,
+    Category,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    #[default]
+    Desc,
+}
+
+/// Filter, sort and page for `query_sessions`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SessionQuery {
+    /// Case-insensitive substring match on name, category and notes
+    pub query: Option<String>,
+    /// Exact (case-insensitive) category
+    pub category: Option<String>,
+    pub sort_by: SortField,
+    pub sort_order: SortOrder,
+    /// None returns every match
+    pub limit: Option<usize>,
+    pub offset: usize,
+}
+
+/// One page of `query_sessions` results
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionPage {
+    pub sessions: Vec<SessionSummary>,
+    /// Matches before paging
+    pub total: usize,
+    pub offset: usize,
+    pub limit: Option<usize>,
+}
+
+struct IndexRow {
+    summary: SessionSummary,
+    search_text: String,
+    content_hash: i64,
+}
+
+impl IndexRow {
+    /// `include_notes` is false for encrypted session files, so notes and
+    /// screenshot text don't end up in plaintext in the index; screenshot
+    /// OCR text is read from `attachments_dir` when given
+    fn from_session(session: Session, include_notes: bool, attachments_dir: Option<&Path>) -> Self {
+        let screenshot_text: Vec<String> = match (include_notes, attachments_dir, &session.screenshots) {
+            (true, Some(dir), Some(screenshots)) => screenshots
+                .iter()
+                .filter_map(|screenshot| ocr::read_text(dir, &screenshot.attachment_id))
+                .filter(|text| !text.trim().is_empty())
+                .collect(),
+            _ => Vec::new(),
+        };
+        let search_text = [
+            Some(session.name.as_str()),
+            session.category.as_deref(),
+            session.notes.as_deref().filter(|_| include_notes),
+        ]
+        .into_iter()
+        .flatten()
+        .chain(screenshot_text.iter().map(String::as_str))
+        .map(|field| field.to_lowercase())
+        .collect::<Vec<_>>()
+        .join(&FIELD_SEPARATOR.to_string());
+
+        let summary: SessionSummary = session.into();
+        let content_hash = fnv1a(
+            serde_json::to_string(&summary).unwrap_or_default().as_bytes(),
+            fnv1a(search_text.as_bytes(), FNV_OFFSET),
+        );
+        Self { summary, search_text, content_hash: content_hash as i64 }
+    }
+}
+
+const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+
+/// Stable content hash (unchanged sessions are not rewritten on sync)
+fn fnv1a(bytes: &[u8], seed: u64) -> u64 {
+    bytes.iter().fold(seed, |hash, byte| (hash ^ *byte as u64).wrapping_mul(0x100000001b3))
+}
+
+/// Identity of a sessions.json snapshot ("missing" when the file doesn't exist)
+fn fingerprint(path: &Path) -> String {
+    match std::fs::metadata(path) {
+        Ok(metadata) => {
+            let modified = metadata
+                .modified()
+                .ok()
+                .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+                .map_or(0, |since| since.as_nanos());
+            format!("{}:{}", modified, metadata.len())
+        }
+        Err(_) => "missing".to_string(),
+    }
+}
+
+/// Escape a query as one FTS5 phrase
+fn fts_phrase(query: &str) -> String {
+    format!("\"{}\"", query.replace('"', "\"\""))
+}
+
+/// Counts from one sync
+#[derive(Debug, Default)]
+pub struct SyncStats {
+    pub total: usize,
+    pub upserted: usize,
+    pub deleted: usize,
+}
+
+/// An open index database
+pub struct SessionDb {
+    conn: Connection,
+    path: Option<PathBuf>,
+    /// Fingerprint of the sessions.json this connection last synced from
+    synced: Option<String>,
+}
+
+impl SessionDb {
+    /// Open (or create / migrate) the index database at `path`; an unreadable
+    /// file is discarded and rebuilt since the index is derived data
+    pub fn open(path: &Path) -> Result<Self, String> {
+        match Self::open_file(path) {
+            Ok(db) => Ok(db),
+            Err(e) => {
+                eprintln!("⚠️  [SESSION INDEX] Rebuilding unreadable index {:?}: {}", path, e);
+                for suffix in ["", "-wal", "-shm"] {
+                    let _ = std::fs::remove_file(format!("{}{}", path.display(), suffix));
+                }
+                Self::open_file(path)
+            }
+        }
+    }
+
+    fn open_file(path: &Path) -> Result<Self, String> {
+        let conn = Connection::open(path).map_err(|e| format!("Failed to open session index: {}", e))?;
+        conn.busy_timeout(Duration::from_secs(2))
+            .map_err(|e| format!("Failed to configure session index: {}", e))?;
+        // WAL lets the MCP server read while the app syncs
+        conn.pragma_update(None, "journal_mode", "WAL")
+            .map_err(|e| format!("Failed to configure session index: {}", e))?;
+
+        let mut db = Self { conn, path: Some(path.to_path_buf()), synced: None };
+        db.migrate()?;
+        Ok(db)
+    }
+
+    /// In-memory index (benchmarks)
+    #[cfg(feature = "bench")]
+    pub fn open_in_memory() -> Result<Self, String> {
+        let conn = Connection::open_in_memory().map_err(|e| format!("Failed to open session index: {}", e))?;
+        let mut db = Self { conn, path: None, synced: None };
+        db.migrate()?;
+        Ok(db)
+    }
+
+    fn migrate(&mut self) -> Result<(), String> {
+        let version: i64 = self
+            .conn
+            .pragma_query_value(None, "user_version", |row| row.get(0))
+            .map_err(|e| format!("Failed to read session index version: {}", e))?;
+        if version == SCHEMA_VERSION {
+            return Ok(());
+        }
+
+        println!("🗂️  [SESSION INDEX] Migrating index schema v{} -> v{}", version, SCHEMA_VERSION);
+        let tx = self.conn.transaction().map_err(|e| format!("Failed to migrate session index: {}", e))?;
+        tx.execute_batch(DROP_SCHEMA)
+            .and_then(|_| tx.execute_batch(SCHEMA))
+            .and_then(|_| tx.pragma_update(None, "user_version", SCHEMA_VERSION))
+            .and_then(|_| tx.commit())
+            .map_err(|e| format!("Failed to migrate session index: {}", e))
+    }
+
+    fn meta(&self, key: &str) -> Result<Option<String>, String> {
+        self.conn
+            .query_row("SELECT value FROM meta WHERE key = ?1", [key], |row| row.get(0))
+            .optional()
+            .map_err(|e| format!("Failed to read session index metadata: {}", e))
+    }
+
+    /// Bring the index up to date with `sessions_path`; returns None when it
+    /// was already current
+    pub fn sync(&mut self, sessions_path: &Path) -> Result<Option<SyncStats>, String> {
+        let fingerprint = fingerprint(sessions_path);
+        if self.synced.as_deref() == Some(fingerprint.as_str()) {
+            return Ok(None);
+        }
+        if self.meta("source_fingerprint")?.as_deref() == Some(fingerprint.as_str()) {
+            self.synced = Some(fingerprint);
+            return Ok(None);
+        }
+
+        let (sessions, encrypted): (Vec<Session>, bool) = if fingerprint == "missing" {
+            (vec![], false)
+        } else {
+            let (file_content, encrypted) = session_encryption::read_sessions_file(sessions_path)?;
+            let sessions = serde_json::from_str(&file_content)
+                .map_err(|e| format!("Failed to parse sessions JSON: {}", e))?;
+            (sessions, encrypted)
+        };
+
+        let attachments_dir = sessions_path.parent().map(|dir| dir.join("attachments"));
+        let stats = self.write_sessions(sessions, Some(&fingerprint), !encrypted, attachments_dir.as_deref())?;
+        self.synced = Some(fingerprint);
+        Ok(Some(stats))
+    }
+
+    /// Make the index contain exactly `sessions` (benchmarks)
+    #[cfg(feature = "bench")]
+    pub fn replace_sessions(&mut self, sessions: Vec<Session>) -> Result<SyncStats, String> {
+        self.synced = None;
+        self.write_sessions(sessions, None, true, None)
+    }
+
+    /// Make the next sync re-index every session even if sessions.json is
+    /// unchanged (for this connection and any other opened on the file)
+    fn invalidate(&mut self) -> Result<(), String> {
+        self.synced = None;
+        self.conn
+            .execute("DELETE FROM meta WHERE key = 'source_fingerprint'", [])
+            .map_err(|e| format!("Failed to update session index: {}", e))?;
+        Ok(())
+    }
+
+    fn write_sessions(
+        &mut self,
+        sessions: Vec<Session>,
+        fingerprint: Option<&str>,
+        include_notes: bool,
+        attachments_dir: Option<&Path>,
+    ) -> Result<SyncStats, String> {
+        let rows: Vec<IndexRow> = sessions
+            .into_par_iter()
+            .map(|session| IndexRow::from_session(session, include_notes, attachments_dir))
+            .collect();
+        let write_error = |e: rusqlite::Error| format!("Failed to update session index: {}", e);
+
+        let tx = self.conn.transaction().map_err(write_error)?;
+        let existing: HashMap<String, i64> = {
+            let mut statement = tx.prepare("SELECT id, content_hash FROM sessions").map_err(write_error)?;
+            let existing = statement
+                .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+                .map_err(write_error)?
+                .collect::<Result<_, _>>()
+                .map_err(write_error)?;
+            existing
+        };
+
+        let mut stats = SyncStats { total: rows.len(), ..SyncStats::default() };
+        {
+            let mut upsert = tx
+                .prepare(
+                    "INSERT INTO sessions (id, name, start_time, end_time, duration, category, screenshot_count, \
+                     audio_segment_count, has_video, has_notes, has_transcript, search_text, content_hash) \
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13) \
+                     ON CONFLICT(id) DO UPDATE SET name = excluded.name, start_time = excluded.start_time, \
+                     end_time = excluded.end_time, duration = excluded.duration, category = excluded.category, \
+                     screenshot_count = excluded.screenshot_count, audio_segment_count = excluded.audio_segment_count, \
+                     has_video = excluded.has_video, has_notes = excluded.has_notes, \
+                     has_transcript = excluded.has_transcript, search_text = excluded.search_text, \
+                     content_hash = excluded.content_hash",
+                )
+                .map_err(write_error)?;
+            for row in &rows {
+                if existing.get(&row.summary.id) == Some(&row.content_hash) {
+                    continue;
+                }
+                let summary = &row.summary;
+                upsert
+                    .execute(params![
+                        summary.id,
+                        summary.name,
+                        summary.start_time,
+                        summary.end_time,
+                        summary.duration,
+                        summary.category,
+                        summary.screenshot_count as i64,
+                        summary.audio_segment_count as i64,
+                        summary.has_video,
+                        summary.has_notes,
+                        summary.has_transcript,
+                        row.search_text,
+                        row.content_hash,
+                    ])
+                    .map_err(write_error)?;
+                stats.upserted += 1;
+            }
+
+            let current: HashSet<&str> = rows.iter().map(|row| row.summary.id.as_str()).collect();
+            let mut delete = tx.prepare("DELETE FROM sessions WHERE id = ?1").map_err(write_error)?;
+            for id in existing.keys().filter(|id| !current.contains(id.as_str())) {
+                delete.execute([id]).map_err(write_error)?;
+                stats.deleted += 1;
+            }
+        }
+
+        match fingerprint {
+            Some(fingerprint) => tx.execute(
+                "INSERT INTO meta (key, value) VALUES ('source_fingerprint', ?1) \
+                 ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+                [fingerprint],
+            ),
+            None => tx.execute("DELETE FROM meta WHERE key = 'source_fingerprint'", []),
+        }
+        .map_err(write_error)?;
+        tx.commit().map_err(write_error)?;
+        Ok(stats)
+    }
+
+    /// Filtered, sorted, paged session summaries
+    pub fn query(&self, query: &SessionQuery) -> Result<SessionPage, String> {
+        let query_error = |e: rusqlite::Error| format!("Failed to query session index: {}", e);
+
+        let mut conditions: Vec<&str> = Vec::new();
+        let mut values: Vec<rusqlite::types::Value> = Vec::new();
+        if let Some(text) = query.query.as_deref().map(str::trim).filter(|text| !text.is_empty()) {
+            let text = text.to_lowercase();
+            if text.chars().count() >= MIN_TRIGRAM_QUERY_CHARS {
+                conditions.push("rowid IN (SELECT rowid FROM sessions_fts WHERE sessions_fts MATCH ?)");
+                values.push(fts_phrase(&text).into());
+            } else {
+                conditions.push("instr(search_text, ?) > 0");
+                values.push(text.into());
+            }
+        }
+        if let Some(category) = query.category.as_deref() {
+            conditions.push("category = ? COLLATE NOCASE");
+            values.push(category.to_string().into());
+        }
+        let where_clause = match conditions.is_empty() {
+            true => String::new(),
+            false => format!("WHERE {}", conditions.join(" AND ")),
+        };
+
+        let total: i64 = self
+            .conn
+            .query_row(
+                &format!("SELECT COUNT(*) FROM sessions {}", where_clause),
+                params_from_iter(values.iter()),
+                |row| row.get(0),
+            )
+            .map_err(query_error)?;
+
+        let sort_column = match query.sort_by {
+            SortField::StartTime => "start_time",
+            SortField::Name => "name COLLATE NOCASE",
+            SortField::Duration => "duration",
+            SortField::Category => "category COLLATE NOCASE",
+        };
+        let sort_order = match query.sort_order {
+            SortOrder::Asc => "ASC",
+            SortOrder::Desc => "DESC",
+        };
+        let sql = format!(
+            "SELECT {} FROM sessions {} ORDER BY {} {}, id {} LIMIT ? OFFSET ?",
+            SUMMARY_COLUMNS, where_clause, sort_column, sort_order, sort_order
+        );
+        values.push(query.limit.map_or(-1, |limit| limit as i64).into());
+        values.push((query.offset as i64).into());
+
+        let mut statement = self.conn.prepare(&sql).map_err(query_error)?;
+        let sessions = statement
+            .query_map(params_from_iter(values.iter()), |row| {
+                Ok(SessionSummary {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    start_time: row.get(2)?,
+                    end_time: row.get(3)?,
+                    duration: row.get(4)?,
+                    category: row.get(5)?,
+                    screenshot_count: row.get::<_, i64>(6)? as usize,
+                    audio_segment_count: row.get::<_, i64>(7)? as usize,
+                    has_video: row.get(8)?,
+                    has_notes: row.get(9)?,
+                    has_transcript: row.get(10)?,
+                })
+            })
+            .map_err(query_error)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(query_error)?;
+
+        Ok(SessionPage {
+            sessions,
+            total: total as usize,
+            offset: query.offset,
+            limit: query.limit,
+        })
+    }
+
+    pub fn count(&self) -> Result<usize, String> {
+        self.conn
+            .query_row("SELECT COUNT(*) FROM sessions", [], |row| row.get::<_, i64>(0))
+            .map(|count| count as usize)
+            .map_err(|e| format!("Failed to count sessions: {}", e))
+    }
+}
+
+/// Managed session index (one connection, reopened when the profile changes)
+pub struct SessionIndex {
+    db: SafeState<Option<SessionDb>>,
+}
+
+impl SessionIndex {
+    pub fn new() -> Self {
+        Self { db: SafeState::new("session_index", None) }
+    }
+
+    /// Run `read` against an up-to-date index for the profile in `data_dir`
+    fn with_synced<T>(&self, data_dir: &Path, read: impl FnOnce(&SessionDb) -> Result<T, String>) -> Result<T, String> {
+        let db_path = data_dir.join(INDEX_DB_FILE);
+        let mut guard = self.db.lock();
+        if guard.as_ref().and_then(|db| db.path.as_deref()) != Some(db_path.as_path()) {
+            *guard = Some(SessionDb::open(&db_path)?);
+        }
+        let db = guard.as_mut().ok_or("Session index unavailable")?;
+
+        let start = Instant::now();
+        if let Some(stats) = db.sync(&data_dir.join("sessions.json"))? {
+            println!(
+                "🗂️  [SESSION INDEX] Synced {} sessions in {:?} ({} updated, {} removed)",
+                stats.total, start.elapsed(), stats.upserted, stats.deleted
+            );
+        }
+        read(db)
+    }
+
+    /// Re-index the profile in `data_dir` on its next read (blocking)
+    pub fn invalidate(&self, data_dir: &Path) -> Result<(), String> {
+        let db_path = data_dir.join(INDEX_DB_FILE);
+        let mut guard = self.db.lock();
+        match guard.as_mut().filter(|db| db.path.as_deref() == Some(db_path.as_path())) {
+            Some(db) => db.invalidate(),
+            None => SessionDb::open(&db_path)?.invalidate(),
+        }
+    }
+
+    /// Read the active profile's index (synced first, on the blocking pool)
+    pub async fn read<T, F>(self: &Arc<Self>, app: &AppHandle, read: F) -> Result<T, String>
+    where
+        T: Send + 'static,
+        F: FnOnce(&SessionDb) -> Result<T, String> + Send + 'static,
+    {
+        let data_dir = profiles::profile_data_dir(app)?;
+        let index = self.clone();
+        tokio::task::spawn_blocking(move || index.with_synced(&data_dir, read))
+            .await
+            .map_err(|e| format!("Session index task failed: {}", e))?
+    }
+
+    /// Sync the index now and keep it current as sessions.json changes
+    pub fn start_watcher(self: &Arc<Self>, app: AppHandle, registry: &TaskRegistry) -> Result<(), String> {
+        let index = self.clone();
+        registry.spawn("session-index-watcher", |mut shutdown| async move {
+            loop {
+                if let Err(e) = index.read(&app, |_| Ok(())).await {
+                    eprintln!("❌ [SESSION INDEX] Failed to refresh index: {}", e);
+                }
+
+                tokio::select! {
+                    _ = shutdown.cancelled() => break,
+                    _ = tokio::time::sleep(WATCH_INTERVAL) => {}
+                }
+            }
+        })
+    }
+}
+
+impl Default for SessionIndex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Query a profile's index without the managed connection (MCP server)
+pub fn query_profile(data_dir: &Path, query: &SessionQuery) -> Result<SessionPage, String> {
+    let mut db = SessionDb::open(&data_dir.join(INDEX_DB_FILE))?;
+    db.sync(&data_dir.join("sessions.json"))?;
+    db.query(query)
+}
@@ -85,6 +85,31 @@ pub struct AudioSegment {
     pub duration: f64,
     #[serde(rename = "startTime")]
     pub start_time: Option<f64>,
+    /// Dominant speaker ("Speaker 1", ...) once the session has been diarized
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speaker: Option<String>,
+    #[serde(rename = "speakerTurns", default, skip_serializing_if = "Option::is_none")]
+    pub speaker_turns: Option<Vec<SpeakerTurn>>,
+    /// Recording pauses that fell inside this segment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pauses: Option<Vec<PauseGap>>,
+}
+
+/// A recording pause (timestamps are RFC 3339)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PauseGap {
+    pub paused_at: String,
+    pub resumed_at: String,
+    pub duration_secs: f64,
+}
+
+/// A stretch of one speaker within an audio segment (seconds from segment start)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeakerTurn {
+    pub start: f64,
+    pub end: f64,
+    pub speaker: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -104,4 +129,21 @@ pub struct AttachmentMeta {
     #[serde(rename = "mimeType")]
     pub mime_type: String,
     pub size: usize,
+    #[serde(rename = "mediaMetadata", default, skip_serializing_if = "Option::is_none")]
+    pub media_metadata: Option<MediaMetadata>,
+}
+
+/// Media metadata extracted in Rust (see attachment_metadata)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaMetadata {
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    /// ISO 8601 capture time from EXIF (DateTimeOriginal, falling back to DateTime)
+    pub created_at: Option<String>,
+    pub camera_make: Option<String>,
+    pub camera_model: Option<String>,
+    /// EXIF orientation (1-8)
+    pub orientation: Option<u32>,
+    pub page_count: Option<u32>,
 }
@@ -1,65 +1,295 @@
 /**
  * Session Storage Module (Task 3A)
  *
- * Parallel session loading using Rust + Rayon for multi-core processing
+ * Session loading in Rust: list/search/query/count are indexed queries on the
+ * SQLite session index (session_index.rs); detail loads parse sessions.json on demand,
+ * decrypting it first when session encryption is on (session_encryption.rs),
+ * with speaker labels from diarization.rs merged into the audio segments
  * Offloads heavy JSON parsing and data transformation from JavaScript
+ *
+ * Attachment data is content-addressed: each distinct `.dat` payload is kept
+ * once as `attachments/blobs/{sha256}.dat`, and every `attachments/{id}.dat`
+ * is a hard link to its blob, so readers (Rust and attachmentStorage.ts) keep
+ * the `{id}.dat` layout while identical screenshots share one copy on disk.
+ * A blob's refcount is its hard link count minus one; the
+ * "attachment-blob-store" task links new attachment data and removes blobs
+ * no attachment references (`migrate_attachment_store` runs a pass now).
+ * Hard links need a Unix filesystem; elsewhere attachments stay as copies.
+ *
+ * Incremental changes to an active session (from Rust, and from the frontend
+ * via `log_session_changes`) are appended to a per-session log
+ * (`session-logs/{id}.jsonl`) instead of rewriting sessions.json; readers
+ * fold the log in, and the "session-log-compactor" task merges it into
+ * sessions.json (temp file + rename) once the session has stopped, or while
+ * it's active once the log has gone idle or grown large. Logs of sessions
+ * sessions.json doesn't have yet are kept. Replaying a log is idempotent, so
+ * a crash between merging and deleting it is safe.
+ *
+ * sessions.json (including audio transcripts) is written zstd-compressed.
+ * Readers detect the zstd magic bytes, so uncompressed files written by
+ * older versions still load; `compact_sessions` compresses them in place.
+ *
+ * The frontend's 'sessions' collection is this same file: TauriFileSystemAdapter
+ * reads and writes it through `load_sessions_store` / `save_sessions_store`
+ * rather than keeping its own copy under db/.
  */
 
-use tauri::{AppHandle, Manager};
-use rayon::prelude::*;
-use std::time::Instant;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tauri::{AppHandle, State};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
 
+use crate::background_tasks::TaskRegistry;
+use crate::command_metrics;
+use crate::diarization;
+use crate::profiles;
+use crate::session_encryption;
+use crate::remote_archive;
+use crate::session_index::{SessionIndex, SessionPage, SessionQuery};
 use crate::session_models::{Session, SessionSummary};
+use crate::settings::SettingsManager;
 
-/**
- * Load session summaries (lightweight, parallel)
- * Returns only metadata without full arrays
- * Uses rayon for parallel transformation across CPU cores
- */
-#[tauri::command]
-pub async fn load_session_summaries(
-    app_handle: AppHandle
-) -> Result<Vec<SessionSummary>, String> {
-    println!("🦀 [RUST] Loading session summaries with parallel processing...");
-    let start = Instant::now();
+/// Run JSON parsing / rayon work on the blocking pool so the async runtime
+/// (and the IPC thread) stays responsive for large session files
+async fn run_blocking<T, F>(operation: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, String> + Send + 'static,
+{
+    tokio::task::spawn_blocking(operation)
+        .await
+        .map_err(|e| format!("Session task failed: {}", e))?
+}
 
-    // Get app data directory
-    let data_dir = app_handle
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+/// Every session in <profile>/sessions.json (decrypted if encrypted at rest)
+pub fn read_sessions(data_dir: &Path) -> Result<Vec<Session>, String> {
+    let sessions_path = data_dir.join("sessions.json");
+    if !sessions_path.exists() {
+        return Ok(Vec::new());
+    }
+    let (file_content, _) = session_encryption::read_sessions_file(&sessions_path)?;
+    if !has_session_logs(data_dir) {
+        // Parse JSON
+        return serde_json::from_str(&file_content)
+            .map_err(|e| format!("Failed to parse sessions JSON: {}", e));
+    }
 
+    // Fold in changes logged for sessions that haven't been compacted yet
+    let mut sessions: Vec<serde_json::Value> = serde_json::from_str(&file_content)
+        .map_err(|e| format!("Failed to parse sessions JSON: {}", e))?;
+    apply_session_logs(data_dir, &mut sessions);
+    serde_json::from_value(serde_json::Value::Array(sessions))
+        .map_err(|e| format!("Failed to parse sessions JSON: {}", e))
+}
+
+/// Every session as raw JSON, with every field the frontend stored
+pub fn read_session_values(data_dir: &Path) -> Result<Vec<serde_json::Value>, String> {
     let sessions_path = data_dir.join("sessions.json");
+    if !sessions_path.exists() {
+        return Ok(Vec::new());
+    }
+    let (file_content, _) = session_encryption::read_sessions_file(&sessions_path)?;
+    let mut sessions: Vec<serde_json::Value> = serde_json::from_str(&file_content)
+        .map_err(|e| format!("Failed to parse sessions JSON: {}", e))?;
+    apply_session_logs(data_dir, &mut sessions);
+    Ok(sessions)
+}
+
+/// One session, or None if it doesn't exist
+pub fn find_session(data_dir: &Path, session_id: &str) -> Result<Option<Session>, String> {
+    // Find session (linear search - could optimize with hash map)
+    Ok(read_sessions(data_dir)?.into_iter().find(|s| s.id == session_id))
+}
+
+pub fn read_session(data_dir: &Path, session_id: &str) -> Result<Session, String> {
+    find_session(data_dir, session_id)?.ok_or_else(|| format!("Session {} not found", session_id))
+}
 
-    // Check if file exists
+/// One session as raw JSON, with every field the frontend stored (the
+/// models above only carry what listing and loading need)
+pub fn read_session_value(data_dir: &Path, session_id: &str) -> Result<serde_json::Value, String> {
+    let sessions_path = data_dir.join("sessions.json");
     if !sessions_path.exists() {
-        println!("⚠️  [RUST] Sessions file not found, returning empty array");
-        return Ok(vec![]);
+        return Err(format!("Session {} not found", session_id));
     }
+    let (file_content, _) = session_encryption::read_sessions_file(&sessions_path)?;
+    let sessions: Vec<serde_json::Value> = serde_json::from_str(&file_content)
+        .map_err(|e| format!("Failed to parse sessions JSON: {}", e))?;
 
-    // Read file (async I/O)
-    let file_content = tokio::fs::read_to_string(&sessions_path)
-        .await
-        .map_err(|e| format!("Failed to read sessions file: {}", e))?;
+    let mut session = sessions
+        .into_iter()
+        .find(|session| session.get("id").and_then(|id| id.as_str()) == Some(session_id))
+        .ok_or_else(|| format!("Session {} not found", session_id))?;
+    apply_changes(&mut session, &read_session_log(data_dir, session_id));
+    Ok(session)
+}
 
-    // Parse JSON in Rust (faster than JavaScript for large files)
-    let sessions: Vec<Session> = serde_json::from_str(&file_content)
+/// Serializes read-modify-write updates of sessions.json from Rust
+static UPDATE_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Apply `update` to one session's raw JSON and write sessions.json back,
+/// keeping its encryption state; returns the updated session. This rewrites
+/// the whole file: frequent changes to an active session should go through
+/// `append_session_changes` instead
+pub fn update_session_value(
+    data_dir: &Path,
+    session_id: &str,
+    update: impl FnOnce(&mut serde_json::Value) -> Result<(), String>,
+) -> Result<serde_json::Value, String> {
+    let _guard = UPDATE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let sessions_path = data_dir.join("sessions.json");
+    if !sessions_path.exists() {
+        return Err(format!("Session {} not found", session_id));
+    }
+    let (file_content, encrypted) = session_encryption::read_sessions_file(&sessions_path)?;
+    let mut sessions: Vec<serde_json::Value> = serde_json::from_str(&file_content)
         .map_err(|e| format!("Failed to parse sessions JSON: {}", e))?;
 
-    println!("📦 [RUST] Parsed {} sessions from JSON", sessions.len());
+    let session = sessions
+        .iter_mut()
+        .find(|session| session.get("id").and_then(|id| id.as_str()) == Some(session_id))
+        .ok_or_else(|| format!("Session {} not found", session_id))?;
+    update(session)?;
+    let updated = session.clone();
 
-    // Transform to summaries in PARALLEL using rayon
-    // This distributes work across all CPU cores
-    let summaries: Vec<SessionSummary> = sessions
-        .into_par_iter()  // Parallel iterator - uses thread pool
-        .map(|session| session.into())  // Convert each session to summary
-        .collect();
+    let content = serde_json::to_string(&sessions)
+        .map_err(|e| format!("Failed to serialize sessions: {}", e))?;
+    session_encryption::write_sessions_file(&sessions_path, &content, encrypted)?;
+    Ok(updated)
+}
+
+/// Apply `update` to every session's raw JSON and write sessions.json back,
+/// keeping its encryption state (a new file is encrypted when
+/// `encrypt_new_file`)
+pub fn update_session_values(
+    data_dir: &Path,
+    encrypt_new_file: bool,
+    update: impl FnOnce(&mut Vec<serde_json::Value>) -> Result<(), String>,
+) -> Result<(), String> {
+    let _guard = UPDATE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let sessions_path = data_dir.join("sessions.json");
+    let (mut sessions, encrypted): (Vec<serde_json::Value>, bool) = if sessions_path.exists() {
+        let (file_content, encrypted) = session_encryption::read_sessions_file(&sessions_path)?;
+        let sessions = serde_json::from_str(&file_content)
+            .map_err(|e| format!("Failed to parse sessions JSON: {}", e))?;
+        (sessions, encrypted)
+    } else {
+        (Vec::new(), encrypt_new_file)
+    };
+    update(&mut sessions)?;
+
+    let content = serde_json::to_string(&sessions)
+        .map_err(|e| format!("Failed to serialize sessions: {}", e))?;
+    session_encryption::write_sessions_file(&sessions_path, &content, encrypted)
+}
+
+/// Replace sessions.json with the frontend's whole session list, encrypted
+/// when `encrypted` (settings.storage.encryptSessions), and drop the change
+/// logs of the sessions in it. The list already has every change the
+/// frontend logged (its log appends and saves share one write queue); changes
+/// Rust logged that it doesn't have yet (e.g. git events it hasn't merged)
+/// are folded in and stay logged until a saved list has them
+pub fn write_session_values(data_dir: &Path, mut sessions: Vec<serde_json::Value>, encrypted: bool) -> Result<(), String> {
+    let _log_guard = LOG_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let _guard = UPDATE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    std::fs::create_dir_all(data_dir)
+        .map_err(|e| format!("Failed to create {:?}: {}", data_dir, e))?;
+
+    let mut logs = Vec::new();
+    if has_session_logs(data_dir) {
+        for session in sessions.iter_mut() {
+            let Some(session_id) = session.get("id").and_then(|id| id.as_str()).map(str::to_string) else {
+                continue;
+            };
+            let unmerged: Vec<LoggedChange> = read_session_log(data_dir, &session_id)
+                .into_iter()
+                .filter(|logged| logged.backend && !change_applied(session, &logged.change))
+                .collect();
+            apply_changes(session, &unmerged);
+            logs.push((session_id, unmerged));
+        }
+    }
+
+    let content = serde_json::to_string(&sessions)
+        .map_err(|e| format!("Failed to serialize sessions: {}", e))?;
+    session_encryption::write_sessions_file(&data_dir.join("sessions.json"), &content, encrypted)?;
+
+    let key = log_key(encrypted && logs.iter().any(|(_, unmerged)| !unmerged.is_empty()))?;
+    for (session_id, unmerged) in logs {
+        let path = session_log_path(data_dir, &session_id)?;
+        if !unmerged.is_empty() {
+            std::fs::write(&path, log_lines(&unmerged, key.as_ref())?)
+                .map_err(|e| format!("Failed to rewrite log of session {}: {}", session_id, e))?;
+        } else if path.exists() {
+            std::fs::remove_file(&path)
+                .map_err(|e| format!("Failed to remove log of session {}: {}", session_id, e))?;
+        }
+    }
+    Ok(())
+}
+
+/// Run `operation` while no session data can be written from Rust: holds
+/// LOG_LOCK and then UPDATE_LOCK, the order every writer takes them in, so
+/// a read-convert-write of a session file can't interleave with a save
+pub fn with_sessions_locked<T>(operation: impl FnOnce() -> Result<T, String>) -> Result<T, String> {
+    let _log_guard = LOG_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let _guard = UPDATE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    operation()
+}
+
+/// Remove sessions from sessions.json, along with their change logs
+pub fn remove_sessions(data_dir: &Path, session_ids: &[String]) -> Result<(), String> {
+    let _log_guard = LOG_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    update_session_values(data_dir, false, |sessions| {
+        sessions.retain(|session| {
+            let id = session.get("id").and_then(|id| id.as_str());
+            !id.is_some_and(|id| session_ids.iter().any(|removed| removed == id))
+        });
+        Ok(())
+    })?;
+
+    for session_id in session_ids {
+        let path = session_log_path(data_dir, session_id)?;
+        if path.exists() {
+            std::fs::remove_file(&path)
+                .map_err(|e| format!("Failed to remove log of session {}: {}", session_id, e))?;
+        }
+    }
+    Ok(())
+}
+
+/// The session currently being recorded (latest without an end time)
+pub fn read_active_session(data_dir: &Path) -> Result<Option<Session>, String> {
+    Ok(read_sessions(data_dir)?
+        .into_iter()
+        .filter(|session| session.end_time.is_none())
+        .max_by(|a, b| a.start_time.cmp(&b.start_time)))
+}
+
+/**
+ * Load session summaries (lightweight), newest first
+ * Served from the session index; the index is synced from sessions.json
+ * only when the file has changed since the last sync
+ */
+#[tauri::command]
+pub async fn load_session_summaries(
+    app_handle: AppHandle,
+    index: State<'_, Arc<SessionIndex>>,
+) -> Result<Vec<SessionSummary>, String> {
+    command_metrics::track_async("load_session_summaries", async move {
+        let start = Instant::now();
 
-    let elapsed = start.elapsed();
-    println!("✅ [RUST] Loaded {} summaries in {:?} (parallel)", summaries.len(), elapsed);
-    println!("⚡ [PERFORMANCE] CPU cores utilized: {}", rayon::current_num_threads());
+        let summaries = index
+            .read(&app_handle, |db| db.query(&SessionQuery::default()))
+            .await?
+            .sessions;
 
-    Ok(summaries)
+        println!("✅ [RUST] Loaded {} summaries in {:?} (indexed)", summaries.len(), start.elapsed());
+        Ok(summaries)
+    }).await
 }
 
 /**
@@ -71,130 +301,856 @@ pub async fn load_session_detail(
     session_id: String,
     app_handle: AppHandle
 ) -> Result<Session, String> {
-    println!("🦀 [RUST] Loading session detail for {}...", session_id);
-    let start = Instant::now();
+    command_metrics::track_async("load_session_detail", async move {
+        println!("🦀 [RUST] Loading session detail for {}...", session_id);
+        let start = Instant::now();
 
-    // Get app data directory
-    let data_dir = app_handle
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+        // Get app data directory
+        let data_dir = profiles::profile_data_dir(&app_handle)?;
 
-    let sessions_path = data_dir.join("sessions.json");
+        let read_dir = data_dir.clone();
+        let session = run_blocking(move || {
+            let mut session = read_session(&read_dir, &session_id)?;
 
-    // Read file
-    let file_content = tokio::fs::read_to_string(&sessions_path)
-        .await
-        .map_err(|e| format!("Failed to read sessions file: {}", e))?;
+            // Speaker labels from diarize_session_audio
+            diarization::apply_speaker_labels(&read_dir, &mut session);
+            Ok(session)
+        }).await?;
 
-    // Parse JSON
-    let sessions: Vec<Session> = serde_json::from_str(&file_content)
-        .map_err(|e| format!("Failed to parse sessions JSON: {}", e))?;
+        // Media offloaded to the remote archive is fetched back on demand
+        let fetched = remote_archive::fetch_session_media(&app_handle, &data_dir, &session).await;
+        if fetched > 0 {
+            println!("☁️  [RUST] Fetched {} archived attachment(s) for session {}", fetched, session.id);
+        }
 
-    // Find session (linear search - could optimize with hash map)
-    let session = sessions
-        .into_iter()
-        .find(|s| s.id == session_id)
-        .ok_or_else(|| format!("Session {} not found", session_id))?;
+        let elapsed = start.elapsed();
+        println!("✅ [RUST] Loaded session in {:?}", elapsed);
 
-    let elapsed = start.elapsed();
-    println!("✅ [RUST] Loaded session in {:?}", elapsed);
-
-    Ok(session)
+        Ok(session)
+    }).await
 }
 
 /**
- * Search sessions (parallel full-text search)
- * Uses rayon for multi-core search across large session arrays
+ * Search sessions (substring over name, category and notes), newest first
+ * Uses the session index's trigram full-text index; `limit`/`offset` page the results
  */
 #[tauri::command]
 pub async fn search_sessions(
     query: String,
-    app_handle: AppHandle
+    limit: Option<usize>,
+    offset: Option<usize>,
+    app_handle: AppHandle,
+    index: State<'_, Arc<SessionIndex>>,
 ) -> Result<Vec<SessionSummary>, String> {
-    println!("🦀 [RUST] Searching sessions for '{}'...", query);
-    let start = Instant::now();
+    command_metrics::track_async("search_sessions", async move {
+        println!("🦀 [RUST] Searching sessions for '{}'...", query);
+        let start = Instant::now();
 
-    // Get app data directory
-    let data_dir = app_handle
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+        let session_query = SessionQuery {
+            query: Some(query),
+            limit,
+            offset: offset.unwrap_or(0),
+            ..SessionQuery::default()
+        };
+        let matching_summaries = index
+            .read(&app_handle, move |db| db.query(&session_query))
+            .await?
+            .sessions;
 
-    let sessions_path = data_dir.join("sessions.json");
+        let elapsed = start.elapsed();
+        println!("✅ [RUST] Found {} matches in {:?} (indexed search)", matching_summaries.len(), elapsed);
 
-    if !sessions_path.exists() {
-        return Ok(vec![]);
-    }
+        Ok(matching_summaries)
+    }).await
+}
 
-    // Read and parse
-    let file_content = tokio::fs::read_to_string(&sessions_path)
-        .await
-        .map_err(|e| format!("Failed to read sessions file: {}", e))?;
+/**
+ * Query sessions with filters, sorting and pagination (session index)
+ */
+#[tauri::command]
+pub async fn query_sessions(
+    query: SessionQuery,
+    app_handle: AppHandle,
+    index: State<'_, Arc<SessionIndex>>,
+) -> Result<SessionPage, String> {
+    command_metrics::track_async("query_sessions", async move {
+        let start = Instant::now();
 
-    let sessions: Vec<Session> = serde_json::from_str(&file_content)
-        .map_err(|e| format!("Failed to parse sessions JSON: {}", e))?;
+        let page = index.read(&app_handle, move |db| db.query(&query)).await?;
+
+        println!("✅ [RUST] Queried {} of {} sessions in {:?} (indexed)", page.sessions.len(), page.total, start.elapsed());
+        Ok(page)
+    }).await
+}
 
-    let query_lower = query.to_lowercase();
+/**
+ * Get session count (from the session index)
+ */
+#[tauri::command]
+pub async fn get_session_count(
+    app_handle: AppHandle,
+    index: State<'_, Arc<SessionIndex>>,
+) -> Result<usize, String> {
+    command_metrics::track_async("get_session_count", async move {
+        index.read(&app_handle, |db| db.count()).await
+    }).await
+}
 
-    // PARALLEL search using rayon
-    let matching_summaries: Vec<SessionSummary> = sessions
-        .into_par_iter()
-        .filter(|session| {
-            // Search in name
-            if session.name.to_lowercase().contains(&query_lower) {
-                return true;
+/**
+ * Load the frontend's session store (the 'sessions' collection): every
+ * session as stored, with logged changes folded in, or None before the first
+ * save. Decrypted here, so the webview never touches the file itself
+ */
+#[tauri::command]
+pub async fn load_sessions_store(app_handle: AppHandle) -> Result<Option<Vec<serde_json::Value>>, String> {
+    command_metrics::track_async("load_sessions_store", async move {
+        let data_dir = profiles::profile_data_dir(&app_handle)?;
+        run_blocking(move || {
+            if !data_dir.join("sessions.json").exists() {
+                return Ok(None);
             }
+            read_session_values(&data_dir).map(Some)
+        }).await
+    }).await
+}
+
+/**
+ * Save the frontend's session store, compressed and encrypted at rest when
+ * settings.storage.encryptSessions is on
+ */
+#[tauri::command]
+pub async fn save_sessions_store(
+    sessions: Vec<serde_json::Value>,
+    app_handle: AppHandle,
+    settings: State<'_, Arc<SettingsManager>>,
+) -> Result<(), String> {
+    command_metrics::track_async("save_sessions_store", async move {
+        let data_dir = profiles::profile_data_dir(&app_handle)?;
+        let encrypted = settings.get().storage.encrypt_sessions;
+        run_blocking(move || write_session_values(&data_dir, sessions, encrypted)).await
+    }).await
+}
+
+// ---------------------------------------------------------------------------
+// Session change logs
+// ---------------------------------------------------------------------------
+
+/// Per-session change logs in the profile data dir
+pub(crate) const SESSION_LOGS_DIR: &str = "session-logs";
 
-            // Search in category
-            if let Some(category) = &session.category {
-                if category.to_lowercase().contains(&query_lower) {
-                    return true;
+const LOG_COMPACTOR_TASK: &str = "session-log-compactor";
+const LOG_COMPACTOR_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Serializes appends against compaction, so nothing is appended to a log
+/// between merging it and deleting it
+static LOG_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// One logged change to a session's raw JSON (a line of its log)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "camelCase")]
+pub enum SessionChange {
+    /// Append items to an array field; items already in it are skipped
+    Append { field: String, items: Vec<serde_json::Value> },
+    /// Replace a field
+    Set { field: String, value: serde_json::Value },
+}
+
+/// A line of a session's log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct LoggedChange {
+    #[serde(flatten)]
+    change: SessionChange,
+    /// Logged by Rust (e.g. git events) rather than by the frontend, so the
+    /// frontend's session list may not have it yet
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    backend: bool,
+}
+
+fn session_log_path(data_dir: &Path, session_id: &str) -> Result<PathBuf, String> {
+    if session_id.is_empty() || session_id.contains(['/', '\\']) || session_id.contains("..") {
+        return Err(format!("Invalid session id: {}", session_id));
+    }
+    Ok(data_dir.join(SESSION_LOGS_DIR).join(format!("{}.jsonl", session_id)))
+}
+
+fn has_session_logs(data_dir: &Path) -> bool {
+    std::fs::read_dir(data_dir.join(SESSION_LOGS_DIR))
+        .map(|mut entries| entries.next().is_some())
+        .unwrap_or(false)
+}
+
+/// Changes logged for a session, oldest first, decrypting encrypted lines.
+/// A line that doesn't parse (e.g. cut short by a crash) is skipped.
+fn read_session_log(data_dir: &Path, session_id: &str) -> Vec<LoggedChange> {
+    let Ok(path) = session_log_path(data_dir, session_id) else {
+        return Vec::new();
+    };
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        return Vec::new();
+    };
+    let mut key = None;
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| {
+            session_encryption::decode_log_line(line, &mut key)
+                .and_then(|line| serde_json::from_str(&line).map_err(|e| e.to_string()))
+                .map_err(|e| eprintln!("⚠️  [SESSIONS] Skipping bad line in {:?}: {}", path, e))
+                .ok()
+        })
+        .collect()
+}
+
+fn apply_session_logs(data_dir: &Path, sessions: &mut [serde_json::Value]) {
+    if !has_session_logs(data_dir) {
+        return;
+    }
+    for session in sessions {
+        if let Some(id) = session.get("id").and_then(|id| id.as_str()).map(str::to_string) {
+            apply_changes(session, &read_session_log(data_dir, &id));
+        }
+    }
+}
+
+fn apply_changes(session: &mut serde_json::Value, changes: &[LoggedChange]) {
+    let Some(session) = session.as_object_mut() else {
+        return;
+    };
+    for LoggedChange { change, .. } in changes {
+        match change {
+            SessionChange::Append { field, items } => {
+                let stored = session.entry(field.as_str()).or_insert_with(|| serde_json::json!([]));
+                if !stored.is_array() {
+                    *stored = serde_json::json!([]);
                 }
+                if let Some(stored) = stored.as_array_mut() {
+                    for item in items {
+                        if !stored.contains(item) {
+                            stored.push(item.clone());
+                        }
+                    }
+                }
+            }
+            SessionChange::Set { field, value } => {
+                session.insert(field.clone(), value.clone());
             }
+        }
+    }
+}
 
-            // Search in notes
-            if let Some(notes) = &session.notes {
-                if notes.to_lowercase().contains(&query_lower) {
-                    return true;
-                }
+/// Whether a session already has everything `change` writes
+fn change_applied(session: &serde_json::Value, change: &SessionChange) -> bool {
+    match change {
+        SessionChange::Append { field, items } => {
+            let stored = session.get(field).and_then(|stored| stored.as_array());
+            items.iter().all(|item| stored.is_some_and(|stored| stored.contains(item)))
+        }
+        SessionChange::Set { field, value } => session.get(field) == Some(value),
+    }
+}
+
+/// The session key when logs are written encrypted
+fn log_key(encrypted: bool) -> Result<Option<[u8; 32]>, String> {
+    if encrypted {
+        session_encryption::session_key().map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+/// Log lines for `changes`, each encrypted with `key` if given
+fn log_lines(changes: &[LoggedChange], key: Option<&[u8; 32]>) -> Result<String, String> {
+    let mut lines = String::new();
+    for change in changes {
+        let line = serde_json::to_string(change).map_err(|e| format!("Failed to serialize session change: {}", e))?;
+        match key {
+            Some(key) => lines.push_str(&session_encryption::encrypt_log_line(key, &line)?),
+            None => lines.push_str(&line),
+        }
+        lines.push('\n');
+    }
+    Ok(lines)
+}
+
+/// Append changes made in Rust to a session's log (one JSON line each,
+/// synced to disk) instead of rewriting sessions.json; readers see them
+/// right away, and saves from the frontend keep them until it has them.
+/// Each line is encrypted with the session key when `encrypted`
+/// (settings.storage.encryptSessions)
+pub fn append_session_changes(
+    data_dir: &Path,
+    session_id: &str,
+    changes: &[SessionChange],
+    encrypted: bool,
+) -> Result<(), String> {
+    append_log_lines(data_dir, session_id, changes, true, log_key(encrypted)?.as_ref())
+}
+
+fn append_log_lines(
+    data_dir: &Path,
+    session_id: &str,
+    changes: &[SessionChange],
+    backend: bool,
+    key: Option<&[u8; 32]>,
+) -> Result<(), String> {
+    if changes.is_empty() {
+        return Ok(());
+    }
+    let path = session_log_path(data_dir, session_id)?;
+    let changes: Vec<LoggedChange> = changes
+        .iter()
+        .map(|change| LoggedChange { change: change.clone(), backend })
+        .collect();
+    let lines = log_lines(&changes, key)?;
+
+    let _guard = LOG_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    std::fs::create_dir_all(data_dir.join(SESSION_LOGS_DIR))
+        .map_err(|e| format!("Failed to create session log directory: {}", e))?;
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| format!("Failed to open {:?}: {}", path, e))?;
+    file.write_all(lines.as_bytes())
+        .and_then(|_| file.sync_data())
+        .map_err(|e| format!("Failed to append to {:?}: {}", path, e))
+}
+
+/**
+ * Log changes to a session of the active profile (the frontend's incremental
+ * writes: screenshots, audio segments, timelines) instead of saving the
+ * whole session store
+ */
+#[tauri::command]
+pub async fn log_session_changes(
+    session_id: String,
+    changes: Vec<SessionChange>,
+    app_handle: AppHandle,
+    settings: State<'_, Arc<SettingsManager>>,
+) -> Result<(), String> {
+    command_metrics::track_async("log_session_changes", async move {
+        let data_dir = profiles::profile_data_dir(&app_handle)?;
+        let encrypted = settings.get().storage.encrypt_sessions;
+        run_blocking(move || {
+            append_log_lines(&data_dir, &session_id, &changes, false, log_key(encrypted)?.as_ref())
+        }).await
+    }).await
+}
+
+/// Logs of active sessions are merged once nothing has been appended for
+/// this long...
+const LOG_IDLE_COMPACTION: Duration = Duration::from_secs(5 * 60);
+/// ...or once they've grown past this size
+const LOG_MAX_BYTES: u64 = 256 * 1024;
+
+/// Whether an active session's log should be merged now
+fn log_due_for_compaction(path: &Path) -> bool {
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return false;
+    };
+    metadata.len() >= LOG_MAX_BYTES
+        || metadata
+            .modified()
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|idle| idle >= LOG_IDLE_COMPACTION)
+}
+
+/// Merge session logs into sessions.json and delete them: every log of a
+/// stopped session, and logs of active ones that have gone idle or grown
+/// large. Logs of sessions sessions.json doesn't have (yet) are left alone;
+/// returns how many were merged
+fn compact_session_logs(data_dir: &Path) -> Result<usize, String> {
+    let Ok(entries) = std::fs::read_dir(data_dir.join(SESSION_LOGS_DIR)) else {
+        return Ok(0);
+    };
+    let logged: Vec<String> = entries
+        .flatten()
+        .filter_map(|entry| entry.file_name().to_string_lossy().strip_suffix(".jsonl").map(str::to_string))
+        .collect();
+    if logged.is_empty() {
+        return Ok(0);
+    }
+
+    let _guard = LOG_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let sessions = read_session_values(data_dir)?;
+    let mut due = Vec::new();
+    for session_id in logged {
+        let Some(session) = sessions
+            .iter()
+            .find(|session| session.get("id").and_then(|id| id.as_str()) == Some(session_id.as_str()))
+        else {
+            continue;
+        };
+        let path = session_log_path(data_dir, &session_id)?;
+        let stopped = session.get("endTime").is_some_and(|end_time| !end_time.is_null());
+        if stopped || log_due_for_compaction(&path) {
+            let changes = read_session_log(data_dir, &session_id);
+            due.push((session_id, path, changes));
+        }
+    }
+    if due.is_empty() {
+        return Ok(0);
+    }
+
+    update_session_values(data_dir, false, |sessions| {
+        for session in sessions.iter_mut() {
+            let id = session.get("id").and_then(|id| id.as_str()).map(str::to_string);
+            if let Some((_, _, changes)) = due.iter().find(|(session_id, _, _)| Some(session_id) == id.as_ref()) {
+                apply_changes(session, changes);
             }
+        }
+        Ok(())
+    })?;
+    for (session_id, path, _) in &due {
+        std::fs::remove_file(path)
+            .map_err(|e| format!("Failed to remove log of session {}: {}", session_id, e))?;
+    }
+    Ok(due.len())
+}
+
+/// Start merging session change logs into sessions.json (right away, which
+/// also picks up logs left by a crash, then periodically)
+pub fn start_log_compactor(app: AppHandle, registry: &TaskRegistry) -> Result<(), String> {
+    registry.spawn(LOG_COMPACTOR_TASK, |mut shutdown| async move {
+        let mut interval = tokio::time::interval(LOG_COMPACTOR_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+
+            let result = match profiles::profile_data_dir(&app) {
+                Ok(data_dir) => run_blocking(move || compact_session_logs(&data_dir)).await,
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(0) => {}
+                Ok(compacted) => println!("🗂️  [SESSIONS] Merged {} session log(s) into sessions.json", compacted),
+                Err(e) => eprintln!("⚠️  [SESSIONS] Session log compaction failed: {}", e),
+            }
+        }
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Compressed session files
+// ---------------------------------------------------------------------------
+
+/// Leading bytes of a zstd frame
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+
+/// Session JSON compresses ~10x at the default level in a few ms per MB
+const ZSTD_LEVEL: i32 = 3;
+
+fn is_compressed(data: &[u8]) -> bool {
+    data.starts_with(&ZSTD_MAGIC)
+}
+
+/// Compress session JSON for writing (see session_encryption::write_sessions_file)
+pub fn compress_sessions_json(content: &str) -> Result<Vec<u8>, String> {
+    zstd::encode_all(content.as_bytes(), ZSTD_LEVEL)
+        .map_err(|e| format!("Failed to compress sessions: {}", e))
+}
+
+/// Session JSON from file data, decompressing it if it's a zstd frame
+pub fn decode_sessions_json(data: Vec<u8>) -> Result<String, String> {
+    let data = if is_compressed(&data) {
+        zstd::decode_all(data.as_slice()).map_err(|e| format!("Failed to decompress sessions: {}", e))?
+    } else {
+        data
+    };
+    String::from_utf8(data).map_err(|e| format!("Session data is not UTF-8: {}", e))
+}
+
+/// Results of a `compact_sessions` run
+#[derive(Debug, Default, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompactReport {
+    /// Session files compressed by this run
+    pub compacted: usize,
+    /// Session files that were already compressed
+    pub unchanged: usize,
+    /// Size of the compacted files before and after
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+    pub bytes_reclaimed: u64,
+}
+
+fn file_size(path: &Path) -> u64 {
+    std::fs::metadata(path).map(|metadata| metadata.len()).unwrap_or(0)
+}
+
+/// Compress one uncompressed session file in place; returns its size before
+/// and after, or None if it was already compressed
+fn compact_sessions_file(path: &Path) -> Result<Option<(u64, u64)>, String> {
+    let _guard = UPDATE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let before = file_size(path);
+    let (data, encrypted) = session_encryption::read_sessions_data(path)?;
+    if is_compressed(&data) {
+        return Ok(None);
+    }
+
+    // Never replace a file we couldn't read back
+    let content = decode_sessions_json(data)?;
+    serde_json::from_str::<serde_json::Value>(&content)
+        .map_err(|e| format!("Refusing to compact {:?}: not valid JSON ({})", path, e))?;
+    session_encryption::write_sessions_file(path, &content, encrypted)?;
+    Ok(Some((before, file_size(path))))
+}
+
+/// Compress every profile's uncompressed session file (blocking)
+fn compact_all(paths: Vec<PathBuf>) -> Result<CompactReport, String> {
+    let mut report = CompactReport::default();
+    for path in paths {
+        match compact_sessions_file(&path)? {
+            Some((before, after)) => {
+                report.compacted += 1;
+                report.bytes_before += before;
+                report.bytes_after += after;
+                println!("🗜️  [SESSIONS] Compressed {:?}: {} -> {} bytes", path, before, after);
+            }
+            None => report.unchanged += 1,
+        }
+    }
+    report.bytes_reclaimed = report.bytes_before.saturating_sub(report.bytes_after);
+    Ok(report)
+}
+
+/**
+ * Compress session files written uncompressed by older versions, across all
+ * profiles, and report the space reclaimed. Encrypted files stay encrypted.
+ */
+#[tauri::command]
+pub async fn compact_sessions(app_handle: AppHandle) -> Result<CompactReport, String> {
+    command_metrics::track_async("compact_sessions", async move {
+        let paths = session_encryption::session_files(&app_handle)?;
+        run_blocking(move || compact_all(paths)).await
+    }).await
+}
 
-            false
+// ---------------------------------------------------------------------------
+// Content-addressed attachment data
+// ---------------------------------------------------------------------------
+
+/// Blob directory inside the attachments directory
+const BLOBS_DIR: &str = "blobs";
+
+const BLOB_STORE_TASK: &str = "attachment-blob-store";
+const BLOB_STORE_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Attachment data modified more recently than this may still be being
+/// written (by the frontend or the screenshot scheduler) and is left alone
+const BLOB_MIN_AGE: Duration = Duration::from_secs(60);
+
+/// Results of one blob store pass
+#[derive(Debug, Default, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlobStoreStats {
+    /// Attachment data files examined
+    pub scanned: usize,
+    /// Attachments newly linked into the store
+    pub linked: usize,
+    /// Of those, attachments whose content was already stored
+    pub deduplicated: usize,
+    /// Disk space saved by the deduplicated attachments
+    pub bytes_saved: u64,
+    /// Unreferenced blobs deleted
+    pub removed_blobs: usize,
+    pub bytes_freed: u64,
+    /// Blobs left in the store
+    pub blobs: usize,
+}
+
+#[cfg(unix)]
+fn link_count(path: &Path) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    std::fs::metadata(path).map(|metadata| metadata.nlink()).unwrap_or(0)
+}
+
+fn sha256_file(path: &Path) -> Result<String, String> {
+    let mut file = std::fs::File::open(path).map_err(|e| format!("Failed to open {:?}: {}", path, e))?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher).map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Replace an attachment's data without touching a shared blob: the new
+/// content goes to a temporary file that is renamed over `{id}.dat`, so other
+/// attachments linked to the old blob keep their content
+pub fn replace_attachment_data(attachments_dir: &Path, id: &str, content: &[u8]) -> Result<(), String> {
+    let temp_path = attachments_dir.join(format!("{}.dat.tmp", id));
+    std::fs::write(&temp_path, content)
+        .and_then(|_| std::fs::rename(&temp_path, attachments_dir.join(format!("{}.dat", id))))
+        .map_err(|e| {
+            let _ = std::fs::remove_file(&temp_path);
+            format!("Failed to write attachment {}: {}", id, e)
         })
-        .map(|session| session.into())
+}
+
+/// Link one attachment's data into the store; returns whether its content
+/// was already stored (the data file now shares that blob)
+#[cfg(unix)]
+fn link_attachment(attachments_dir: &Path, blobs_dir: &Path, data_path: &Path) -> Result<bool, String> {
+    let blob_path = blobs_dir.join(format!("{}.dat", sha256_file(data_path)?));
+    if !blob_path.exists() {
+        std::fs::hard_link(data_path, &blob_path)
+            .map_err(|e| format!("Failed to store blob {:?}: {}", blob_path, e))?;
+        return Ok(false);
+    }
+
+    // Link under a temporary name and rename over the data file, so the
+    // attachment is never missing
+    let file_name = data_path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+    let temp_path = attachments_dir.join(format!("{}.link", file_name));
+    let _ = std::fs::remove_file(&temp_path);
+    std::fs::hard_link(&blob_path, &temp_path)
+        .and_then(|_| std::fs::rename(&temp_path, data_path))
+        .map_err(|e| {
+            let _ = std::fs::remove_file(&temp_path);
+            format!("Failed to link {:?} to {:?}: {}", data_path, blob_path, e)
+        })?;
+    Ok(true)
+}
+
+/// Link unlinked attachment data into the store, then delete unreferenced
+/// blobs (blocking)
+#[cfg(unix)]
+pub fn run_blob_store_pass(data_dir: &Path) -> Result<BlobStoreStats, String> {
+    let attachments_dir = data_dir.join("attachments");
+    let blobs_dir = attachments_dir.join(BLOBS_DIR);
+    let mut stats = BlobStoreStats::default();
+    if !attachments_dir.exists() {
+        return Ok(stats);
+    }
+    std::fs::create_dir_all(&blobs_dir).map_err(|e| format!("Failed to create blob store: {}", e))?;
+
+    let now = SystemTime::now();
+    let data_files: Vec<PathBuf> = std::fs::read_dir(&attachments_dir)
+        .map_err(|e| format!("Failed to read attachments directory: {}", e))?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "dat"))
+        .collect();
+    for data_path in data_files {
+        stats.scanned += 1;
+        let Ok(metadata) = std::fs::metadata(&data_path) else { continue };
+        let settled = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| now.duration_since(modified).ok())
+            .is_some_and(|age| age >= BLOB_MIN_AGE);
+        if link_count(&data_path) > 1 || !metadata.is_file() || !settled {
+            continue;
+        }
+        match link_attachment(&attachments_dir, &blobs_dir, &data_path) {
+            Ok(deduplicated) => {
+                stats.linked += 1;
+                if deduplicated {
+                    stats.deduplicated += 1;
+                    stats.bytes_saved += metadata.len();
+                }
+            }
+            Err(e) => eprintln!("⚠️  [BLOB STORE] {}", e),
+        }
+    }
+
+    let blobs: Vec<PathBuf> = std::fs::read_dir(&blobs_dir)
+        .map_err(|e| format!("Failed to read blob store: {}", e))?
+        .flatten()
+        .map(|entry| entry.path())
         .collect();
+    for blob_path in blobs {
+        // Only the store's own link left: no attachment references it
+        if link_count(&blob_path) == 1 {
+            let size = std::fs::metadata(&blob_path).map(|metadata| metadata.len()).unwrap_or(0);
+            if std::fs::remove_file(&blob_path).is_ok() {
+                stats.removed_blobs += 1;
+                stats.bytes_freed += size;
+                continue;
+            }
+        }
+        stats.blobs += 1;
+    }
+    Ok(stats)
+}
 
-    let elapsed = start.elapsed();
-    println!("✅ [RUST] Found {} matches in {:?} (parallel search)", matching_summaries.len(), elapsed);
+#[cfg(not(unix))]
+pub fn run_blob_store_pass(_data_dir: &Path) -> Result<BlobStoreStats, String> {
+    Err("The content-addressed attachment store needs hard links (macOS/Linux)".to_string())
+}
 
-    Ok(matching_summaries)
+fn log_blob_store_pass(stats: &BlobStoreStats, elapsed: Duration) {
+    if stats.linked > 0 || stats.removed_blobs > 0 {
+        println!(
+            "🧱 [BLOB STORE] Linked {} attachment(s) ({} duplicate, {}KB saved), removed {} unreferenced blob(s) ({}KB) in {:?}",
+            stats.linked, stats.deduplicated, stats.bytes_saved / 1024, stats.removed_blobs, stats.bytes_freed / 1024, elapsed
+        );
+    }
+}
+
+/// Start linking new attachments and collecting unreferenced blobs
+pub fn start_blob_store(app: AppHandle, registry: &TaskRegistry) -> Result<(), String> {
+    if !cfg!(unix) {
+        return Ok(());
+    }
+
+    registry.spawn(BLOB_STORE_TASK, |mut shutdown| async move {
+        let mut interval = tokio::time::interval(BLOB_STORE_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+
+            let result = match profiles::profile_data_dir(&app) {
+                Ok(data_dir) => run_blocking(move || {
+                    let start = Instant::now();
+                    let stats = run_blob_store_pass(&data_dir)?;
+                    log_blob_store_pass(&stats, start.elapsed());
+                    Ok(())
+                }).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                eprintln!("⚠️  [BLOB STORE] Pass failed: {}", e);
+            }
+        }
+    })
 }
 
 /**
- * Get session count (fast, no parsing)
+ * Move the active profile's attachment data into the content-addressed store
+ * now (also done every 10 minutes in the background) and delete unreferenced
+ * blobs. Data written in the last minute is picked up by a later pass.
  */
 #[tauri::command]
-pub async fn get_session_count(
-    app_handle: AppHandle
-) -> Result<usize, String> {
-    let data_dir = app_handle
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+pub async fn migrate_attachment_store(app_handle: AppHandle) -> Result<BlobStoreStats, String> {
+    command_metrics::track_async("migrate_attachment_store", async move {
+        let data_dir = profiles::profile_data_dir(&app_handle)?;
+        run_blocking(move || {
+            let start = Instant::now();
+            let stats = run_blob_store_pass(&data_dir)?;
+            log_blob_store_pass(&stats, start.elapsed());
+            Ok(stats)
+        }).await
+    }).await
+}
 
-    let sessions_path = data_dir.join("sessions.json");
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
 
-    if !sessions_path.exists() {
-        return Ok(0);
+    fn screenshot(id: &str) -> SessionChange {
+        SessionChange::Append { field: "screenshots".to_string(), items: vec![json!({ "id": id })] }
     }
 
-    let file_content = tokio::fs::read_to_string(&sessions_path)
-        .await
-        .map_err(|e| format!("Failed to read sessions file: {}", e))?;
+    fn log_exists(data_dir: &Path, session_id: &str) -> bool {
+        session_log_path(data_dir, session_id).unwrap().exists()
+    }
 
-    let sessions: Vec<serde_json::Value> = serde_json::from_str(&file_content)
-        .map_err(|e| format!("Failed to parse JSON: {}", e))?;
+    #[test]
+    fn stopped_sessions_are_compacted() {
+        let dir = tempfile::tempdir().unwrap();
+        let sessions = vec![json!({ "id": "s1", "endTime": "2024-01-01T10:00:00Z", "screenshots": [] })];
+        write_session_values(dir.path(), sessions, false).unwrap();
+        append_session_changes(dir.path(), "s1", &[screenshot("a"), screenshot("a")], false).unwrap();
+
+        assert_eq!(compact_session_logs(dir.path()).unwrap(), 1);
+        assert!(!log_exists(dir.path(), "s1"));
+        let stored = read_session_value(dir.path(), "s1").unwrap();
+        assert_eq!(stored["screenshots"], json!([{ "id": "a" }]));
+    }
+
+    #[test]
+    fn logs_of_unknown_sessions_are_kept() {
+        let dir = tempfile::tempdir().unwrap();
+        write_session_values(dir.path(), vec![json!({ "id": "s1", "endTime": null })], false).unwrap();
+        append_session_changes(dir.path(), "unsaved", &[screenshot("a")], false).unwrap();
+
+        assert_eq!(compact_session_logs(dir.path()).unwrap(), 0);
+        assert!(log_exists(dir.path(), "unsaved"));
 
-    Ok(sessions.len())
+        // Without a sessions.json at all, nothing is known either
+        let empty = tempfile::tempdir().unwrap();
+        append_session_changes(empty.path(), "s1", &[screenshot("a")], false).unwrap();
+        assert_eq!(compact_session_logs(empty.path()).unwrap(), 0);
+        assert!(log_exists(empty.path(), "s1"));
+    }
+
+    #[test]
+    fn active_sessions_are_compacted_once_their_log_is_large() {
+        let dir = tempfile::tempdir().unwrap();
+        write_session_values(dir.path(), vec![json!({ "id": "s1", "endTime": null })], false).unwrap();
+        append_session_changes(dir.path(), "s1", &[screenshot("a")], false).unwrap();
+
+        // A small, freshly written log is left to grow
+        assert_eq!(compact_session_logs(dir.path()).unwrap(), 0);
+        assert!(log_exists(dir.path(), "s1"));
+
+        let padding = "x".repeat(LOG_MAX_BYTES as usize);
+        let change = SessionChange::Set { field: "notes".to_string(), value: json!(padding) };
+        append_session_changes(dir.path(), "s1", &[change], false).unwrap();
+        assert_eq!(compact_session_logs(dir.path()).unwrap(), 1);
+        assert!(!log_exists(dir.path(), "s1"));
+        let stored = read_session_value(dir.path(), "s1").unwrap();
+        assert_eq!(stored["screenshots"], json!([{ "id": "a" }]));
+        assert_eq!(stored["notes"].as_str().map(str::len), Some(LOG_MAX_BYTES as usize));
+    }
+
+    #[test]
+    fn saving_the_store_replaces_the_logs_of_saved_sessions() {
+        let dir = tempfile::tempdir().unwrap();
+        write_session_values(dir.path(), vec![json!({ "id": "s1" }), json!({ "id": "s2" })], false).unwrap();
+        append_log_lines(dir.path(), "s1", &[screenshot("deleted")], false, None).unwrap();
+        append_log_lines(dir.path(), "s3", &[screenshot("new")], false, None).unwrap();
+
+        // The frontend's list already has (or has since removed) what it logged
+        let saved = vec![json!({ "id": "s1", "screenshots": [] }), json!({ "id": "s2" })];
+        write_session_values(dir.path(), saved, false).unwrap();
+        assert!(!log_exists(dir.path(), "s1"));
+        assert!(log_exists(dir.path(), "s3"));
+        assert_eq!(read_session_value(dir.path(), "s1").unwrap()["screenshots"], json!([]));
+    }
+
+    #[test]
+    fn saving_the_store_keeps_changes_rust_logged_until_the_list_has_them() {
+        let dir = tempfile::tempdir().unwrap();
+        write_session_values(dir.path(), vec![json!({ "id": "s1", "gitEvents": [] })], false).unwrap();
+        let commit = SessionChange::Append { field: "gitEvents".to_string(), items: vec![json!({ "hash": "abc" })] };
+        append_session_changes(dir.path(), "s1", &[commit], false).unwrap();
+
+        // Saved before the frontend merged the event
+        write_session_values(dir.path(), vec![json!({ "id": "s1", "gitEvents": [] })], false).unwrap();
+        assert!(log_exists(dir.path(), "s1"));
+        let (stored, _) = session_encryption::read_sessions_file(&dir.path().join("sessions.json")).unwrap();
+        assert!(stored.contains("abc"));
+
+        // A stale list saved again still doesn't lose it
+        write_session_values(dir.path(), vec![json!({ "id": "s1", "gitEvents": [] })], false).unwrap();
+        assert_eq!(read_session_value(dir.path(), "s1").unwrap()["gitEvents"], json!([{ "hash": "abc" }]));
+
+        // Once the frontend has it, the log goes
+        write_session_values(dir.path(), vec![json!({ "id": "s1", "gitEvents": [{ "hash": "abc" }] })], false).unwrap();
+        assert!(!log_exists(dir.path(), "s1"));
+    }
+
+    #[test]
+    fn encrypted_logs_hold_no_plaintext() {
+        const KEY: [u8; 32] = [7; 32];
+        let dir = tempfile::tempdir().unwrap();
+        let change = SessionChange::Set { field: "notes".to_string(), value: json!("secret plans") };
+        append_log_lines(dir.path(), "s1", &[screenshot("shot-1"), change], false, Some(&KEY)).unwrap();
+
+        let content = std::fs::read_to_string(session_log_path(dir.path(), "s1").unwrap()).unwrap();
+        assert!(!content.contains("secret") && !content.contains("shot-1") && !content.contains("notes"));
+        let mut key = Some(KEY);
+        let lines: Vec<String> = content
+            .lines()
+            .map(|line| session_encryption::decode_log_line(line, &mut key).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[1].contains("secret plans"));
+    }
 }
@@ -0,0 +1,908 @@
+/**
+ * Settings Module
+ *
+ * Single typed settings subsystem for the Rust side:
+ * - Schema with defaults (every field has a default, so partial/old files load)
+ * - Validation before anything is persisted or applied; a stored file that
+ *   fails it is backed up (settings.invalid-<time>.json) before defaults apply
+ * - Versioned migration of the on-disk format (settings.json via tauri-plugin-store)
+ * - `get_settings` / `update_settings` commands
+ * - Change propagation: in-process subscribers + `settings-changed` event
+ */
+
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_global_shortcut::Shortcut;
+use tauri_plugin_store::StoreExt;
+
+use crate::ai_router::ProviderId;
+use crate::audio_encoding::ChunkFormat;
+use crate::command_metrics;
+use crate::media_buffers::OverflowPolicy;
+use crate::meeting_detector::MeetingApp;
+use crate::safe_state::SafeState;
+
+const SETTINGS_STORE: &str = "settings.json";
+pub const SETTINGS_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ScreenshotSettings {
+    pub interval_minutes: f64,
+    /// JPEG quality for composite captures (1-100)
+    pub jpeg_quality: u8,
+    /// Composites larger than this are downscaled
+    pub max_width: u32,
+    pub max_height: u32,
+    /// Scheduled captures whose perceptual hash is at most this many bits
+    /// (of 64) from the last stored one are recorded as unchanged instead of
+    /// stored; 0 stores every capture
+    pub unchanged_threshold: u32,
+}
+
+impl Default for ScreenshotSettings {
+    fn default() -> Self {
+        Self {
+            interval_minutes: 2.0,
+            jpeg_quality: 70,
+            max_width: 1920,
+            max_height: 1080,
+            unchanged_threshold: 3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AudioSettings {
+    pub chunk_duration_secs: u64,
+    pub vad_enabled: bool,
+    /// RMS threshold below which a chunk is treated as silence (0.0-1.0)
+    pub vad_threshold: f32,
+    /// Mic vs. system audio mix (0.0 = mic only, 1.0 = system only)
+    pub balance: f32,
+    /// Encoding of audio chunks sent to the frontend
+    pub chunk_format: ChunkFormat,
+    /// Live transcript: stream ~1.5s slices to OpenAI and emit
+    /// `transcript-delta` events alongside the regular chunks
+    pub streaming_transcription: bool,
+    /// Move capture to the built-in mic when the input drops to a low-rate
+    /// profile (e.g. Bluetooth headsets switching to HFP)
+    pub prefer_built_in_mic: bool,
+    /// Capture system audio alongside the mic (macOS 13+)
+    pub system_audio: bool,
+    /// Emit mic and system audio as separate chunks instead of mixing them
+    /// by `balance`
+    pub separate_tracks: bool,
+    /// Attenuate steady background noise on the mic before chunking
+    pub noise_suppression: bool,
+    /// Normalize quiet/loud mics towards a target level before VAD
+    pub agc_enabled: bool,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            chunk_duration_secs: 120,
+            vad_enabled: true,
+            vad_threshold: 0.01,
+            balance: 0.5,
+            chunk_format: ChunkFormat::Wav,
+            streaming_transcription: false,
+            prefer_built_in_mic: false,
+            system_audio: false,
+            separate_tracks: false,
+            noise_suppression: false,
+            agc_enabled: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ActivitySettings {
+    pub window_seconds: u64,
+    /// Record window titles in the app timeline (app names are always recorded)
+    pub capture_window_titles: bool,
+    /// Record the active browser tab (Safari / Chromium browsers, macOS) as the
+    /// session's URL timeline
+    pub capture_browser_urls: bool,
+    /// Domains never recorded (subdomains included), e.g. "bank.com"
+    pub blocked_domains: Vec<String>,
+    /// Drop query strings and fragments (tokens, search terms) from recorded URLs
+    pub strip_url_query: bool,
+}
+
+impl Default for ActivitySettings {
+    fn default() -> Self {
+        Self {
+            window_seconds: 60,
+            capture_window_titles: true,
+            capture_browser_urls: false,
+            blocked_domains: Vec::new(),
+            strip_url_query: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PerformanceSettings {
+    /// Worker threads for the background task runtime (applied on next launch)
+    pub worker_threads: usize,
+    /// Memory ceiling for in-flight capture buffers
+    pub media_memory_limit_mb: usize,
+    /// What happens to buffered media once the ceiling is reached
+    pub media_overflow_policy: OverflowPolicy,
+    /// Flush window for coalesced high-frequency events (countdown-tick, activity-stats)
+    pub event_coalesce_ms: u64,
+}
+
+impl Default for PerformanceSettings {
+    fn default() -> Self {
+        Self {
+            worker_threads: 4,
+            media_memory_limit_mb: 256,
+            media_overflow_policy: OverflowPolicy::SpillToDisk,
+            event_coalesce_ms: 100,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct StorageSettings {
+    /// Encrypt sessions.json at rest (toggled by enable/disable_session_encryption,
+    /// which also manage the keychain key)
+    pub encrypt_sessions: bool,
+    /// Disk budget for session media (screenshots, audio, video) in GB; 0 = unlimited
+    pub budget_gb: f64,
+    /// What happens to the oldest sessions' media over budget (storage_budget.rs)
+    pub rotation: RotationMode,
+    /// Recordings refuse to start with less free disk space than this (disk_space.rs)
+    pub min_free_space_mb: u64,
+    /// Free space below which a recording in progress gets a `low` warning
+    pub warn_free_space_mb: u64,
+}
+
+impl Default for StorageSettings {
+    fn default() -> Self {
+        Self {
+            encrypt_sessions: false,
+            budget_gb: 0.0,
+            rotation: RotationMode::default(),
+            min_free_space_mb: 2048,
+            warn_free_space_mb: 5120,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum RotationMode {
+    /// Shrink screenshots and drop video first, delete media only if still over budget
+    #[default]
+    Downsample,
+    /// Delete the oldest sessions' media outright
+    Delete,
+}
+
+/// Local LLM provider (ollama_api.rs)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct OllamaSettings {
+    /// Ollama server, e.g. a remote GPU box on the LAN
+    pub base_url: String,
+}
+
+impl Default for OllamaSettings {
+    fn default() -> Self {
+        Self {
+            base_url: "http://localhost:11434".to_string(),
+        }
+    }
+}
+
+/// Default model per provider for routed requests
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ProviderModels {
+    pub claude: String,
+    pub openai: String,
+    pub gemini: String,
+    pub ollama: String,
+}
+
+impl Default for ProviderModels {
+    fn default() -> Self {
+        Self {
+            claude: "claude-sonnet-4-5-20250929".to_string(),
+            openai: "gpt-4o".to_string(),
+            gemini: "gemini-2.5-flash".to_string(),
+            ollama: "llama3.1".to_string(),
+        }
+    }
+}
+
+impl ProviderModels {
+    pub fn for_provider(&self, provider: ProviderId) -> &str {
+        match provider {
+            ProviderId::Claude => &self.claude,
+            ProviderId::OpenAI => &self.openai,
+            ProviderId::Gemini => &self.gemini,
+            ProviderId::Ollama => &self.ollama,
+        }
+    }
+}
+
+/// Provider selection for `ai_chat_completion` (ai_router.rs)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AiSettings {
+    pub provider: ProviderId,
+    /// Tried in order when the selected provider is rate limited
+    pub fallback_providers: Vec<ProviderId>,
+    pub models: ProviderModels,
+    /// Cloud AI requests in flight at once (ai_client.rs); extra requests wait
+    pub max_concurrent_requests: u32,
+    /// Retries for rate limits (429), 5xx and network errors
+    pub max_retries: u32,
+}
+
+impl Default for AiSettings {
+    fn default() -> Self {
+        Self {
+            provider: ProviderId::Claude,
+            fallback_providers: Vec::new(),
+            models: ProviderModels::default(),
+            max_concurrent_requests: 4,
+            max_retries: 3,
+        }
+    }
+}
+
+/// Localhost REST API for sessions (rest_api.rs)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ApiServerSettings {
+    pub enabled: bool,
+    pub port: u16,
+}
+
+impl Default for ApiServerSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 47821,
+        }
+    }
+}
+
+/// Calendar-driven session start (calendar.rs)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CalendarSettings {
+    /// Start a session when an event with a video-call link begins
+    pub auto_start_meetings: bool,
+    /// Calendar titles to watch (empty watches every calendar)
+    pub calendars: Vec<String>,
+}
+
+/// Meeting detection and auto-recording policy (meeting_detector.rs)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AutoRecordSettings {
+    /// Watch for Zoom / Meet / Teams calls and emit `meeting-detected`
+    pub detect_meetings: bool,
+    /// Start a session when a call is detected and nothing is recording
+    pub auto_start: bool,
+    /// Apps whose calls count
+    pub apps: Vec<MeetingApp>,
+}
+
+impl Default for AutoRecordSettings {
+    fn default() -> Self {
+        Self {
+            detect_meetings: true,
+            auto_start: false,
+            apps: MeetingApp::ALL.to_vec(),
+        }
+    }
+}
+
+/// Session export (export.rs)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ExportSettings {
+    /// Obsidian / Logseq vault for `export_session_markdown` (empty = ask)
+    pub vault_path: String,
+    /// Write the Markdown note to the vault once a session has been enriched
+    pub auto_export_markdown: bool,
+}
+
+/// Issue tracker integration (issue_tracker.rs); API tokens live in the keychain
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct IssueTrackerSettings {
+    /// Jira Cloud site, e.g. https://acme.atlassian.net
+    pub jira_base_url: String,
+    /// Account the Jira API token belongs to
+    pub jira_email: String,
+    /// Jira issue type for created issues
+    pub jira_issue_type: String,
+}
+
+/// Git repos watched during sessions (git_monitor.rs)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GitSettings {
+    /// Repo top-level directories
+    pub watched_repos: Vec<String>,
+}
+
+/// Apps and windows kept out of captures (privacy.rs)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PrivacySettings {
+    pub enabled: bool,
+    /// App names or bundle ids (case-insensitive)
+    pub blocked_apps: Vec<String>,
+    /// Window title fragments (case-insensitive), e.g. a bank's name
+    pub blocked_titles: Vec<String>,
+}
+
+impl Default for PrivacySettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            blocked_apps: ["1Password", "1Password 7", "Bitwarden", "Dashlane", "Keychain Access", "Passwords"]
+                .map(String::from)
+                .to_vec(),
+            blocked_titles: Vec::new(),
+        }
+    }
+}
+
+/// Scheduled backups (backup.rs)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BackupSettings {
+    pub enabled: bool,
+    /// Folder backups are written to, e.g. on an external drive
+    pub directory: String,
+    /// Hours between scheduled backups
+    pub interval_hours: u32,
+    /// Backups kept in the folder; older ones are deleted
+    pub keep: u32,
+    /// Also copy attachment data (screenshots, audio), not just metadata
+    pub include_media: bool,
+}
+
+impl Default for BackupSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            directory: String::new(),
+            interval_hours: 24,
+            keep: 7,
+            include_media: false,
+        }
+    }
+}
+
+/// Session library mirrored to a shared folder (folder_sync.rs)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SyncSettings {
+    pub enabled: bool,
+    /// Shared folder, e.g. in iCloud Drive or Dropbox
+    pub directory: String,
+}
+
+/// Media of old sessions offloaded to S3-compatible storage (remote_archive.rs);
+/// the secret access key lives in the keychain
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RemoteArchiveSettings {
+    pub enabled: bool,
+    /// e.g. https://s3.us-east-1.amazonaws.com or an R2/MinIO endpoint
+    pub endpoint: String,
+    pub region: String,
+    pub bucket: String,
+    /// Key prefix inside the bucket
+    pub prefix: String,
+    pub access_key_id: String,
+    /// Offload media of sessions that ended more than this many days ago
+    pub older_than_days: u32,
+}
+
+impl Default for RemoteArchiveSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoint: String::new(),
+            region: "us-east-1".to_string(),
+            bucket: String::new(),
+            prefix: "taskerino".to_string(),
+            access_key_id: String::new(),
+            older_than_days: 90,
+        }
+    }
+}
+
+/// What happens when a recording file reaches the size limit
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum FileSizeAction {
+    /// Stop the session
+    #[default]
+    Stop,
+    /// Finish the file and continue recording into a new one
+    Rollover,
+}
+
+/// Safety limits for long recordings (recording_limits.rs)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LimitSettings {
+    /// Stop sessions after this many hours (0 = no limit)
+    pub max_session_hours: f64,
+    /// Maximum size of a video file in GB (0 = no limit)
+    pub max_file_size_gb: f64,
+    pub file_size_action: FileSizeAction,
+}
+
+impl Default for LimitSettings {
+    fn default() -> Self {
+        Self {
+            max_session_hours: 12.0,
+            max_file_size_gb: 20.0,
+            file_size_action: FileSizeAction::Stop,
+        }
+    }
+}
+
+/// Always-on ring buffer of the last minutes of screen and microphone
+/// (replay_buffer.rs)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ReplayBufferSettings {
+    pub enabled: bool,
+    /// Minutes kept (1-30)
+    pub minutes: u32,
+    /// Buffer the microphone along with the screen
+    pub audio: bool,
+}
+
+impl Default for ReplayBufferSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            minutes: 5,
+            audio: true,
+        }
+    }
+}
+
+/// Native notifications by category (notifications.rs)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NotificationSettings {
+    pub transcription_complete: bool,
+    pub enrichment_finished: bool,
+    pub low_disk: bool,
+    pub session_reminder: bool,
+    /// Ask whether a session is still intentional after this many hours
+    /// (and every multiple of it)
+    pub session_reminder_hours: f64,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            transcription_complete: true,
+            enrichment_finished: true,
+            low_disk: true,
+            session_reminder: true,
+            session_reminder_hours: 4.0,
+        }
+    }
+}
+
+/// Global shortcuts in global-hotkey syntax (e.g. "Super+Shift+Space")
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ShortcutSettings {
+    pub quick_capture: String,
+    pub toggle_window: String,
+    pub screenshot: String,
+}
+
+impl Default for ShortcutSettings {
+    fn default() -> Self {
+        Self {
+            quick_capture: "Super+Shift+Space".to_string(),
+            toggle_window: "Super+Shift+T".to_string(),
+            screenshot: "Super+Shift+Digit4".to_string(),
+        }
+    }
+}
+
+impl ShortcutSettings {
+    pub fn parse(value: &str) -> Result<Shortcut, String> {
+        Shortcut::from_str(value).map_err(|e| format!("Invalid shortcut '{}': {}", value, e))
+    }
+
+    pub fn all(&self) -> Vec<Shortcut> {
+        [&self.quick_capture, &self.toggle_window, &self.screenshot]
+            .iter()
+            .filter_map(|s| Self::parse(s).ok())
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Settings {
+    pub version: u32,
+    pub screenshots: ScreenshotSettings,
+    pub audio: AudioSettings,
+    pub activity: ActivitySettings,
+    pub shortcuts: ShortcutSettings,
+    pub performance: PerformanceSettings,
+    pub storage: StorageSettings,
+    pub ollama: OllamaSettings,
+    pub ai: AiSettings,
+    pub api_server: ApiServerSettings,
+    pub calendar: CalendarSettings,
+    pub auto_record: AutoRecordSettings,
+    pub export: ExportSettings,
+    pub issue_tracker: IssueTrackerSettings,
+    pub git: GitSettings,
+    pub privacy: PrivacySettings,
+    pub backup: BackupSettings,
+    pub sync: SyncSettings,
+    pub remote_archive: RemoteArchiveSettings,
+    pub notifications: NotificationSettings,
+    pub limits: LimitSettings,
+    pub replay_buffer: ReplayBufferSettings,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            version: SETTINGS_VERSION,
+            screenshots: ScreenshotSettings::default(),
+            audio: AudioSettings::default(),
+            activity: ActivitySettings::default(),
+            shortcuts: ShortcutSettings::default(),
+            performance: PerformanceSettings::default(),
+            storage: StorageSettings::default(),
+            ollama: OllamaSettings::default(),
+            ai: AiSettings::default(),
+            api_server: ApiServerSettings::default(),
+            calendar: CalendarSettings::default(),
+            auto_record: AutoRecordSettings::default(),
+            export: ExportSettings::default(),
+            issue_tracker: IssueTrackerSettings::default(),
+            git: GitSettings::default(),
+            privacy: PrivacySettings::default(),
+            backup: BackupSettings::default(),
+            sync: SyncSettings::default(),
+            remote_archive: RemoteArchiveSettings::default(),
+            notifications: NotificationSettings::default(),
+            limits: LimitSettings::default(),
+            replay_buffer: ReplayBufferSettings::default(),
+        }
+    }
+}
+
+impl Settings {
+    /// Validate ranges and shortcut syntax
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0.1..=120.0).contains(&self.screenshots.interval_minutes) {
+            return Err("Screenshot interval must be between 0.1 and 120 minutes".to_string());
+        }
+        if !(1..=100).contains(&self.screenshots.jpeg_quality) {
+            return Err("JPEG quality must be between 1 and 100".to_string());
+        }
+        if self.screenshots.max_width < 320 || self.screenshots.max_height < 240 {
+            return Err("Maximum screenshot size must be at least 320x240".to_string());
+        }
+        if !(5..=600).contains(&self.audio.chunk_duration_secs) {
+            return Err("Audio chunk duration must be between 5 and 600 seconds".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.audio.vad_threshold) {
+            return Err("VAD threshold must be between 0.0 and 1.0".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.audio.balance) {
+            return Err("Audio balance must be between 0.0 and 1.0".to_string());
+        }
+        if !(5..=3600).contains(&self.activity.window_seconds) {
+            return Err("Activity window must be between 5 and 3600 seconds".to_string());
+        }
+        if !(1..=32).contains(&self.performance.worker_threads) {
+            return Err("Worker threads must be between 1 and 32".to_string());
+        }
+        if !(16..=4096).contains(&self.performance.media_memory_limit_mb) {
+            return Err("Media memory limit must be between 16 and 4096 MB".to_string());
+        }
+        if !(16..=2000).contains(&self.performance.event_coalesce_ms) {
+            return Err("Event coalescing window must be between 16 and 2000 ms".to_string());
+        }
+        if !self.ollama.base_url.starts_with("http://") && !self.ollama.base_url.starts_with("https://") {
+            return Err("Ollama URL must start with http:// or https://".to_string());
+        }
+        if !(1..=16).contains(&self.ai.max_concurrent_requests) {
+            return Err("Concurrent AI requests must be between 1 and 16".to_string());
+        }
+        if self.ai.max_retries > 6 {
+            return Err("AI request retries must be between 0 and 6".to_string());
+        }
+        if ProviderId::ALL.iter().any(|&p| self.ai.models.for_provider(p).trim().is_empty()) {
+            return Err("Every AI provider needs a default model".to_string());
+        }
+        if !(0.0..=10_000.0).contains(&self.storage.budget_gb) {
+            return Err("Storage budget must be between 0 and 10000 GB".to_string());
+        }
+        if self.storage.warn_free_space_mb < self.storage.min_free_space_mb {
+            return Err("Low disk space warning must be at or above the minimum free space".to_string());
+        }
+        if self.api_server.port < 1024 {
+            return Err("API server port must be between 1024 and 65535".to_string());
+        }
+        if self.export.auto_export_markdown && self.export.vault_path.trim().is_empty() {
+            return Err("Markdown auto-export needs a vault folder".to_string());
+        }
+        if !self.issue_tracker.jira_base_url.is_empty() && !self.issue_tracker.jira_base_url.starts_with("https://") {
+            return Err("Jira URL must start with https://".to_string());
+        }
+        if self.backup.enabled && self.backup.directory.trim().is_empty() {
+            return Err("Scheduled backups need a backup folder".to_string());
+        }
+        if !(1..=720).contains(&self.backup.interval_hours) {
+            return Err("Backup interval must be between 1 and 720 hours".to_string());
+        }
+        if !(1..=100).contains(&self.backup.keep) {
+            return Err("Backups kept must be between 1 and 100".to_string());
+        }
+        if self.sync.enabled && self.sync.directory.trim().is_empty() {
+            return Err("Folder sync needs a sync folder".to_string());
+        }
+        if self.remote_archive.enabled {
+            let archive = &self.remote_archive;
+            if !archive.endpoint.starts_with("https://") && !archive.endpoint.starts_with("http://") {
+                return Err("Remote archive endpoint must start with https:// or http://".to_string());
+            }
+            if archive.bucket.trim().is_empty() || archive.access_key_id.trim().is_empty() {
+                return Err("Remote archive needs a bucket and access key".to_string());
+            }
+        }
+        if !(1..=3650).contains(&self.remote_archive.older_than_days) {
+            return Err("Remote archive age must be between 1 and 3650 days".to_string());
+        }
+        if !(0.5..=24.0).contains(&self.notifications.session_reminder_hours) {
+            return Err("Session reminder must be between 0.5 and 24 hours".to_string());
+        }
+        if self.limits.max_session_hours != 0.0 && !(0.5..=72.0).contains(&self.limits.max_session_hours) {
+            return Err("Maximum session duration must be between 0.5 and 72 hours (0 = no limit)".to_string());
+        }
+        if self.limits.max_file_size_gb != 0.0 && !(1.0..=1000.0).contains(&self.limits.max_file_size_gb) {
+            return Err("Maximum recording file size must be between 1 and 1000 GB (0 = no limit)".to_string());
+        }
+        if !(1..=30).contains(&self.replay_buffer.minutes) {
+            return Err("Replay buffer length must be between 1 and 30 minutes".to_string());
+        }
+        ShortcutSettings::parse(&self.shortcuts.quick_capture)?;
+        ShortcutSettings::parse(&self.shortcuts.toggle_window)?;
+        ShortcutSettings::parse(&self.shortcuts.screenshot)?;
+        Ok(())
+    }
+}
+
+/// Upgrade a raw settings document to the current version
+fn migrate(mut raw: serde_json::Value) -> serde_json::Value {
+    let version = raw.get("version").and_then(|v| v.as_u64()).unwrap_or(0);
+
+    if version < 1 {
+        // v0 stored flat keys written by early builds; move them into sections
+        if let Some(obj) = raw.as_object_mut() {
+            let mut migrated = serde_json::json!({});
+            if let Some(interval) = obj.remove("screenshotIntervalMinutes") {
+                migrated["screenshots"] = serde_json::json!({ "intervalMinutes": interval });
+            }
+            if let Some(chunk) = obj.remove("audioChunkDurationSecs") {
+                migrated["audio"] = serde_json::json!({ "chunkDurationSecs": chunk });
+            }
+            if let Some(window) = obj.remove("activityWindowSeconds") {
+                migrated["activity"] = serde_json::json!({ "windowSeconds": window });
+            }
+            for (key, value) in migrated.as_object().cloned().unwrap_or_default() {
+                obj.insert(key, value);
+            }
+        }
+        println!("🔄 [SETTINGS] Migrated settings from v{} to v1", version);
+    }
+
+    raw["version"] = serde_json::json!(SETTINGS_VERSION);
+    raw
+}
+
+/// Recursively merge a partial JSON patch into a document
+fn merge(target: &mut serde_json::Value, patch: serde_json::Value) {
+    match (target, patch) {
+        (serde_json::Value::Object(target), serde_json::Value::Object(patch)) => {
+            for (key, value) in patch {
+                merge(target.entry(key).or_insert(serde_json::Value::Null), value);
+            }
+        }
+        (target, patch) => *target = patch,
+    }
+}
+
+type Subscriber = Arc<dyn Fn(&Settings) + Send + Sync>;
+
+/// `settings` with a partial patch merged in, validated
+fn patched(settings: &Settings, patch: serde_json::Value) -> Result<Settings, String> {
+    let mut document = serde_json::to_value(settings)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    merge(&mut document, patch);
+
+    let mut updated: Settings = serde_json::from_value(document)
+        .map_err(|e| format!("Invalid settings: {}", e))?;
+    updated.version = SETTINGS_VERSION;
+    updated.validate()?;
+    Ok(updated)
+}
+
+/// Keep a copy of a stored settings document that failed to load, so saving
+/// the defaults doesn't lose it; returns the backup path
+fn back_up_invalid(app: &AppHandle, raw: &serde_json::Value) -> Result<std::path::PathBuf, String> {
+    let dir = app.path().app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+    let path = dir.join(format!("settings.invalid-{}.json", chrono::Utc::now().format("%Y%m%d-%H%M%S")));
+    let content = serde_json::to_string_pretty(raw)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    std::fs::write(&path, content)
+        .map_err(|e| format!("Failed to write {:?}: {}", path, e))?;
+    Ok(path)
+}
+
+/// Managed settings state shared by all subsystems
+pub struct SettingsManager {
+    settings: SafeState<Settings>,
+    subscribers: SafeState<Vec<Subscriber>>,
+}
+
+impl SettingsManager {
+    pub fn new() -> Self {
+        Self {
+            settings: SafeState::new("settings", Settings::default()),
+            subscribers: SafeState::new("settings subscribers", Vec::new()),
+        }
+    }
+
+    /// Load (and migrate) settings from disk; invalid files are backed up and
+    /// fall back to defaults
+    pub fn load(&self, app: &AppHandle) -> Result<(), String> {
+        let store = app.store(SETTINGS_STORE)
+            .map_err(|e| format!("Failed to access store: {}", e))?;
+
+        let settings = match store.get("settings") {
+            Some(raw) => {
+                let needs_save = raw.get("version").and_then(|v| v.as_u64()) != Some(SETTINGS_VERSION as u64);
+                let parsed = serde_json::from_value::<Settings>(migrate(raw.clone()))
+                    .map_err(|e| e.to_string())
+                    .and_then(|s| s.validate().map(|_| s));
+
+                match parsed {
+                    Ok(settings) => {
+                        if needs_save {
+                            store.set("settings", serde_json::json!(settings));
+                            store.save().map_err(|e| format!("Failed to save store: {}", e))?;
+                        }
+                        settings
+                    }
+                    Err(e) => {
+                        match back_up_invalid(app, &raw) {
+                            Ok(path) => eprintln!("⚠️  [SETTINGS] Stored settings invalid, using defaults (backed up to {:?}): {}", path, e),
+                            Err(backup_error) => eprintln!("⚠️  [SETTINGS] Stored settings invalid, using defaults (backup failed: {}): {}", backup_error, e),
+                        }
+                        Settings::default()
+                    }
+                }
+            }
+            None => Settings::default(),
+        };
+
+        self.settings.set(settings);
+
+        println!("⚙️  [SETTINGS] Settings loaded (v{})", SETTINGS_VERSION);
+        Ok(())
+    }
+
+    /// Current settings snapshot
+    pub fn get(&self) -> Settings {
+        self.settings.get()
+    }
+
+    /// Register a callback invoked after every successful update
+    pub fn subscribe<F: Fn(&Settings) + Send + Sync + 'static>(&self, callback: F) {
+        self.subscribers.lock().push(Arc::new(callback));
+    }
+
+    /// Settings a partial update would produce (validated, not applied)
+    pub fn preview(&self, patch: serde_json::Value) -> Result<Settings, String> {
+        patched(&self.get(), patch)
+    }
+
+    /// Apply a partial update, validate, persist, and notify subscribers
+    pub fn update(&self, app: &AppHandle, patch: serde_json::Value) -> Result<Settings, String> {
+        let updated = {
+            // Held from read to write, so concurrent updates can't drop each other
+            let mut settings = self.settings.lock();
+            let updated = patched(&settings, patch)?;
+
+            let store = app.store(SETTINGS_STORE)
+                .map_err(|e| format!("Failed to access store: {}", e))?;
+            store.set("settings", serde_json::json!(updated));
+            store.save().map_err(|e| format!("Failed to save store: {}", e))?;
+
+            *settings = updated.clone();
+            updated
+        };
+
+        // Called without any lock held: subscribers may read or update settings
+        let subscribers = self.subscribers.lock().clone();
+        for subscriber in &subscribers {
+            subscriber(&updated);
+        }
+
+        let _ = app.emit("settings-changed", &updated);
+        println!("⚙️  [SETTINGS] Settings updated");
+
+        Ok(updated)
+    }
+}
+
+impl Default for SettingsManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Tauri command to get current settings
+#[tauri::command]
+pub fn get_settings(settings: tauri::State<Arc<SettingsManager>>) -> Result<Settings, String> {
+    command_metrics::track("get_settings", || {
+        Ok(settings.get())
+    })
+}
+
+/// Tauri command to update settings with a partial patch
+/// (e.g. `{ "audio": { "chunkDurationSecs": 60 } }`)
+#[tauri::command]
+pub fn update_settings(
+    app: AppHandle,
+    settings: tauri::State<Arc<SettingsManager>>,
+    patch: serde_json::Value,
+) -> Result<Settings, String> {
+    command_metrics::track("update_settings", || {
+        settings.update(&app, patch)
+    })
+}
+
+/// Tauri command to reset all settings to defaults
+#[tauri::command]
+pub fn reset_settings(
+    app: AppHandle,
+    settings: tauri::State<Arc<SettingsManager>>,
+) -> Result<Settings, String> {
+    command_metrics::track("reset_settings", || {
+        let defaults = serde_json::to_value(Settings::default())
+            .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+        settings.update(&app, defaults)
+    })
+}
@@ -0,0 +1,138 @@
+/**
+ * Startup Profile Module
+ *
+ * Timing instrumentation for app startup, exposed via `get_startup_profile`:
+ * - `mark(phase)` records the time since the previous mark (setup phases run in order)
+ * - `mark_ready()` records when the event loop is ready (window shown)
+ * - `LazyInit<T>` defers non-critical subsystems until first use and records
+ *   how long their initialization took
+ */
+
+use serde::Serialize;
+use std::sync::OnceLock;
+use std::time::Instant;
+
+use crate::command_metrics;
+use crate::safe_state::SafeState;
+
+/// One timed startup phase (milliseconds)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartupPhase {
+    pub name: String,
+    pub started_at_ms: f64,
+    pub duration_ms: f64,
+}
+
+/// Startup timings reported to the frontend
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartupProfile {
+    /// Process start (profiler creation) to event loop ready
+    pub ready_ms: Option<f64>,
+    pub phases: Vec<StartupPhase>,
+    /// Subsystems initialized on first use (after startup)
+    pub lazy_inits: Vec<StartupPhase>,
+}
+
+pub struct StartupProfiler {
+    origin: Instant,
+    last_mark: SafeState<Instant>,
+    phases: SafeState<Vec<StartupPhase>>,
+    lazy_inits: SafeState<Vec<StartupPhase>>,
+    ready_ms: SafeState<Option<f64>>,
+}
+
+/// Global profiler; the first call (top of `run()`) marks process start
+pub fn profiler() -> &'static StartupProfiler {
+    static PROFILER: OnceLock<StartupProfiler> = OnceLock::new();
+    PROFILER.get_or_init(|| {
+        let now = Instant::now();
+        StartupProfiler {
+            origin: now,
+            last_mark: SafeState::new("startup.last_mark", now),
+            phases: SafeState::new("startup.phases", Vec::new()),
+            lazy_inits: SafeState::new("startup.lazy_inits", Vec::new()),
+            ready_ms: SafeState::new("startup.ready", None),
+        }
+    })
+}
+
+fn millis(duration: std::time::Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+impl StartupProfiler {
+    /// Record the phase that just finished (time since the previous mark)
+    pub fn mark(&self, name: &str) {
+        let now = Instant::now();
+        let started = std::mem::replace(&mut *self.last_mark.lock(), now);
+        self.phases.lock().push(StartupPhase {
+            name: name.to_string(),
+            started_at_ms: millis(started - self.origin),
+            duration_ms: millis(now - started),
+        });
+    }
+
+    /// Record that the event loop is running (only the first call counts)
+    pub fn mark_ready(&self) {
+        let mut ready = self.ready_ms.lock();
+        if ready.is_none() {
+            let ready_ms = millis(self.origin.elapsed());
+            println!("⏱️  [STARTUP] Ready in {:.0}ms", ready_ms);
+            *ready = Some(ready_ms);
+        }
+    }
+
+    fn record_lazy_init(&self, name: &str, started: Instant) {
+        let duration_ms = millis(started.elapsed());
+        println!("⏱️  [STARTUP] Lazily initialized {} ({:.1}ms)", name, duration_ms);
+        self.lazy_inits.lock().push(StartupPhase {
+            name: name.to_string(),
+            started_at_ms: millis(started - self.origin),
+            duration_ms,
+        });
+    }
+
+    pub fn snapshot(&self) -> StartupProfile {
+        StartupProfile {
+            ready_ms: self.ready_ms.get(),
+            phases: self.phases.get(),
+            lazy_inits: self.lazy_inits.get(),
+        }
+    }
+}
+
+/// Value built on first use instead of during startup
+pub struct LazyInit<T> {
+    name: &'static str,
+    value: SafeState<Option<T>>,
+}
+
+impl<T: Clone> LazyInit<T> {
+    pub const fn new(name: &'static str) -> Self {
+        Self { name, value: SafeState::new(name, None) }
+    }
+
+    /// Return the value, initializing it on the first call (failures are retried next call)
+    pub fn get_or_try_init(&self, init: impl FnOnce() -> Result<T, String>) -> Result<T, String> {
+        let mut slot = self.value.lock();
+        if let Some(value) = slot.as_ref() {
+            return Ok(value.clone());
+        }
+
+        let started = Instant::now();
+        let value = init()?;
+        profiler().record_lazy_init(self.name, started);
+        *slot = Some(value.clone());
+        Ok(value)
+    }
+}
+
+/// Tauri command to get startup timings
+#[tauri::command]
+pub fn get_startup_profile() -> Result<StartupProfile, String> {
+    command_metrics::track("get_startup_profile", || {
+        Ok(profiler().snapshot())
+    })
+}
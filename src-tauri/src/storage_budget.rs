@@ -0,0 +1,477 @@
+/**
+ * Storage Budget Module
+ *
+ * Keeps session media (screenshots, audio segments, video) under the disk
+ * budget in settings.storage (`budgetGb`, 0 = unlimited):
+ * - The "storage-rotation" task measures the active profile's session media
+ *   every ROTATION_INTERVAL (and right away when the budget changes)
+ * - Over budget, completed sessions are rotated oldest first. In
+ *   `downsample` mode screenshots are re-encoded to at most
+ *   DOWNSAMPLE_MAX_WIDTH and video is dropped (audio and transcripts stay);
+ *   whatever is still over budget, or everything in `delete` mode, has its
+ *   media files deleted. Session records, notes and transcripts are kept.
+ * - `storage-rotated` is emitted with what was freed per session
+ * - `get_storage_budget` / `set_storage_budget` read and change the budget
+ * - The same task enforces the active profile's `retentionDays`: sessions
+ *   that ended longer ago than that are deleted, records and media, and
+ *   `sessions-expired` is emitted with their ids
+ *
+ * The session being recorded is never touched.
+ */
+
+use chrono::{DateTime, Utc};
+use image::codecs::jpeg::JpegEncoder;
+use serde::Serialize;
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::attachment_metadata::read_attachment_bytes;
+use crate::background_tasks::TaskRegistry;
+use crate::command_metrics;
+use crate::profiles;
+use crate::session_storage;
+use crate::settings::{RotationMode, SettingsManager, StorageSettings};
+use crate::thumbnails;
+
+const TASK_NAME: &str = "storage-rotation";
+const ROTATION_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// Downsampled screenshots are at most this wide
+const DOWNSAMPLE_MAX_WIDTH: u32 = 960;
+const DOWNSAMPLE_JPEG_QUALITY: u8 = 60;
+
+const BYTES_PER_GB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum MediaKind {
+    Screenshot,
+    Audio,
+    Video,
+}
+
+struct MediaFile {
+    attachment_id: String,
+    kind: MediaKind,
+    bytes: u64,
+}
+
+struct SessionMedia {
+    id: String,
+    name: String,
+    start_time: String,
+    end_time: Option<String>,
+    completed: bool,
+    files: Vec<MediaFile>,
+}
+
+impl SessionMedia {
+    fn bytes(&self) -> u64 {
+        self.files.iter().map(|file| file.bytes).sum()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RotationAction {
+    Downsampled,
+    Deleted,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RotatedSession {
+    pub session_id: String,
+    pub name: String,
+    pub action: RotationAction,
+    pub freed_bytes: u64,
+}
+
+/// `storage-rotated` payload
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RotationReport {
+    pub budget_bytes: u64,
+    /// Session media after rotation
+    pub used_bytes: u64,
+    pub freed_bytes: u64,
+    pub sessions: Vec<RotatedSession>,
+}
+
+/// `sessions-expired` payload
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpiryReport {
+    pub retention_days: u32,
+    pub session_ids: Vec<String>,
+    /// Media deleted with them
+    pub freed_bytes: u64,
+}
+
+/// Budget settings plus current usage
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageBudget {
+    pub budget_gb: f64,
+    pub rotation: RotationMode,
+    pub used_bytes: u64,
+}
+
+fn file_size(path: &Path) -> u64 {
+    std::fs::metadata(path).map(|m| m.len()).unwrap_or(0)
+}
+
+fn read_meta(attachments_dir: &Path, id: &str) -> Option<serde_json::Value> {
+    let content = std::fs::read_to_string(attachments_dir.join(format!("{}.meta.json", id))).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// File-based attachments (video) keep their data at the `path` in metadata
+fn meta_file_path(meta: &Option<serde_json::Value>) -> Option<PathBuf> {
+    meta.as_ref()?
+        .get("path")
+        .and_then(|path| path.as_str())
+        .map(PathBuf::from)
+}
+
+/// Bytes on disk for an attachment (inline data plus any referenced file)
+fn attachment_size(attachments_dir: &Path, id: &str) -> u64 {
+    let inline = file_size(&attachments_dir.join(format!("{}.dat", id)));
+    let external = meta_file_path(&read_meta(attachments_dir, id))
+        .map(|path| file_size(&path))
+        .unwrap_or(0);
+    inline + external
+}
+
+/// Media of every session in the profile, oldest first
+fn session_media(data_dir: &Path) -> Result<Vec<SessionMedia>, String> {
+    let attachments_dir = data_dir.join("attachments");
+    let mut sessions: Vec<SessionMedia> = session_storage::read_sessions(data_dir)?
+        .into_iter()
+        .map(|session| {
+            let screenshots = session
+                .screenshots
+                .iter()
+                .flatten()
+                .map(|s| (s.attachment_id.clone(), MediaKind::Screenshot));
+            let audio = session
+                .audio_segments
+                .iter()
+                .flatten()
+                .map(|a| (a.attachment_id.clone(), MediaKind::Audio));
+            let video = session
+                .video
+                .iter()
+                .map(|v| (v.full_video_attachment_id.clone(), MediaKind::Video));
+
+            let files = screenshots
+                .chain(audio)
+                .chain(video)
+                .map(|(attachment_id, kind)| MediaFile {
+                    bytes: attachment_size(&attachments_dir, &attachment_id),
+                    attachment_id,
+                    kind,
+                })
+                .filter(|file| file.bytes > 0)
+                .collect();
+
+            SessionMedia {
+                id: session.id,
+                name: session.name,
+                start_time: session.start_time,
+                completed: session.end_time.is_some(),
+                end_time: session.end_time,
+                files,
+            }
+        })
+        .collect();
+    sessions.sort_by(|a, b| a.start_time.cmp(&b.start_time));
+    Ok(sessions)
+}
+
+/// Delete an attachment's data, metadata, OCR text and thumbnails; returns
+/// bytes freed
+fn remove_attachment(attachments_dir: &Path, id: &str) -> u64 {
+    let data_path = attachments_dir.join(format!("{}.dat", id));
+    let meta_path = attachments_dir.join(format!("{}.meta.json", id));
+    let mut freed = 0;
+
+    if let Some(path) = meta_file_path(&read_meta(attachments_dir, id)) {
+        let size = file_size(&path);
+        if std::fs::remove_file(&path).is_ok() {
+            freed += size;
+        }
+    }
+    let size = file_size(&data_path);
+    if std::fs::remove_file(&data_path).is_ok() {
+        freed += size;
+    }
+    let _ = std::fs::remove_file(meta_path);
+    let _ = std::fs::remove_file(attachments_dir.join(format!("{}.ocr.txt", id)));
+    if let Some(data_dir) = attachments_dir.parent() {
+        thumbnails::invalidate(data_dir, id);
+    }
+    freed
+}
+
+/// Re-encode a screenshot at DOWNSAMPLE_MAX_WIDTH; returns bytes freed
+/// (0 if it is already small enough)
+fn downsample_screenshot(attachments_dir: &Path, id: &str) -> Result<u64, String> {
+    let data_path = attachments_dir.join(format!("{}.dat", id));
+    let mut meta = read_meta(attachments_dir, id).unwrap_or_else(|| json!({}));
+    let bytes = read_attachment_bytes(attachments_dir, id, &meta)?;
+
+    let image = image::load_from_memory(&bytes)
+        .map_err(|e| format!("Failed to decode screenshot {}: {}", id, e))?;
+    if image.width() <= DOWNSAMPLE_MAX_WIDTH {
+        return Ok(0);
+    }
+
+    let resized = image.resize(DOWNSAMPLE_MAX_WIDTH, u32::MAX, image::imageops::FilterType::Triangle).to_rgb8();
+    let mut jpeg = Vec::new();
+    JpegEncoder::new_with_quality(&mut jpeg, DOWNSAMPLE_JPEG_QUALITY)
+        .encode(&resized, resized.width(), resized.height(), image::ColorType::Rgb8.into())
+        .map_err(|e| format!("Failed to encode JPEG: {}", e))?;
+
+    let old_size = file_size(&data_path);
+    let encoded = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &jpeg);
+    // The old data may be a blob shared with other attachments
+    session_storage::replace_attachment_data(attachments_dir, id, format!("data:image/jpeg;base64,{}", encoded).as_bytes())?;
+
+    if let Some(fields) = meta.as_object_mut() {
+        fields.insert("mimeType".to_string(), json!("image/jpeg"));
+        fields.insert("size".to_string(), json!(jpeg.len()));
+        if let Ok(content) = serde_json::to_string(&meta) {
+            let _ = std::fs::write(attachments_dir.join(format!("{}.meta.json", id)), content);
+        }
+    }
+
+    Ok(old_size.saturating_sub(file_size(&data_path)))
+}
+
+/// Shrink screenshots and drop video; returns bytes freed
+fn downsample_session(attachments_dir: &Path, session: &SessionMedia) -> u64 {
+    session
+        .files
+        .iter()
+        .map(|file| match file.kind {
+            MediaKind::Screenshot => downsample_screenshot(attachments_dir, &file.attachment_id)
+                .unwrap_or_else(|e| {
+                    eprintln!("⚠️  [STORAGE] {}", e);
+                    0
+                }),
+            MediaKind::Video => remove_attachment(attachments_dir, &file.attachment_id),
+            MediaKind::Audio => 0,
+        })
+        .sum()
+}
+
+fn delete_session_media(attachments_dir: &Path, session: &SessionMedia) -> u64 {
+    session
+        .files
+        .iter()
+        .map(|file| remove_attachment(attachments_dir, &file.attachment_id))
+        .sum()
+}
+
+fn budget_bytes(settings: &StorageSettings) -> u64 {
+    (settings.budget_gb * BYTES_PER_GB) as u64
+}
+
+/// Bring session media under budget; None when nothing had to be done.
+/// Blocks on file IO and image encoding.
+fn rotate(data_dir: &Path, settings: &StorageSettings) -> Result<Option<RotationReport>, String> {
+    let budget = budget_bytes(settings);
+    if budget == 0 {
+        return Ok(None);
+    }
+
+    let sessions = session_media(data_dir)?;
+    let mut used: u64 = sessions.iter().map(SessionMedia::bytes).sum();
+    if used <= budget {
+        return Ok(None);
+    }
+
+    println!(
+        "🗄️  [STORAGE] Session media uses {} MB of a {} MB budget, rotating",
+        used / 1024 / 1024,
+        budget / 1024 / 1024
+    );
+    let attachments_dir = data_dir.join("attachments");
+    let mut rotated = Vec::new();
+    let mut rotate_pass = |action: RotationAction, used: &mut u64| {
+        for session in sessions.iter().filter(|s| s.completed) {
+            if *used <= budget {
+                break;
+            }
+            let freed = match action {
+                RotationAction::Downsampled => downsample_session(&attachments_dir, session),
+                RotationAction::Deleted => delete_session_media(&attachments_dir, session),
+            };
+            if freed == 0 {
+                continue;
+            }
+            *used = used.saturating_sub(freed);
+            rotated.push(RotatedSession {
+                session_id: session.id.clone(),
+                name: session.name.clone(),
+                action,
+                freed_bytes: freed,
+            });
+        }
+    };
+
+    if settings.rotation == RotationMode::Downsample {
+        rotate_pass(RotationAction::Downsampled, &mut used);
+    }
+    rotate_pass(RotationAction::Deleted, &mut used);
+
+    let freed_bytes = rotated.iter().map(|s| s.freed_bytes).sum();
+    Ok(Some(RotationReport {
+        budget_bytes: budget,
+        used_bytes: used,
+        freed_bytes,
+        sessions: rotated,
+    }))
+}
+
+/// Delete sessions that ended more than `retention_days` ago, with their
+/// media; None when none had. Blocks on file IO.
+fn expire(data_dir: &Path, retention_days: u32, now: DateTime<Utc>) -> Result<Option<ExpiryReport>, String> {
+    let cutoff = now - chrono::Duration::days(i64::from(retention_days));
+    let expired: Vec<SessionMedia> = session_media(data_dir)?
+        .into_iter()
+        .filter(|session| {
+            session
+                .end_time
+                .as_deref()
+                .and_then(|end_time| DateTime::parse_from_rfc3339(end_time).ok())
+                .is_some_and(|end_time| end_time < cutoff)
+        })
+        .collect();
+    if expired.is_empty() {
+        return Ok(None);
+    }
+
+    let attachments_dir = data_dir.join("attachments");
+    let freed_bytes = expired
+        .iter()
+        .map(|session| delete_session_media(&attachments_dir, session))
+        .sum();
+    let session_ids: Vec<String> = expired.into_iter().map(|session| session.id).collect();
+    session_storage::remove_sessions(data_dir, &session_ids)?;
+
+    Ok(Some(ExpiryReport {
+        retention_days,
+        session_ids,
+        freed_bytes,
+    }))
+}
+
+/// Start (or restart, checking right away) the rotation task
+pub fn start(app: AppHandle, registry: &TaskRegistry) -> Result<(), String> {
+    registry.spawn(TASK_NAME, |mut shutdown| async move {
+        let mut interval = tokio::time::interval(ROTATION_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+
+            let data_dir = match profiles::profile_data_dir(&app) {
+                Ok(dir) => dir,
+                Err(e) => {
+                    eprintln!("⚠️  [STORAGE] {}", e);
+                    continue;
+                }
+            };
+
+            if let Some(retention_days) = profiles::active_profile(&app).retention_days {
+                let expiry_dir = data_dir.clone();
+                match tokio::task::spawn_blocking(move || expire(&expiry_dir, retention_days, Utc::now())).await {
+                    Ok(Ok(Some(report))) => {
+                        println!(
+                            "🗄️  [STORAGE] Deleted {} sessions older than {} days ({} MB of media)",
+                            report.session_ids.len(),
+                            report.retention_days,
+                            report.freed_bytes / 1024 / 1024
+                        );
+                        let _ = app.emit("sessions-expired", &report);
+                    }
+                    Ok(Ok(None)) => {}
+                    Ok(Err(e)) => eprintln!("⚠️  [STORAGE] Retention sweep failed: {}", e),
+                    Err(e) => eprintln!("⚠️  [STORAGE] Retention task failed: {}", e),
+                }
+            }
+
+            let settings = app.state::<Arc<SettingsManager>>().get().storage;
+            if budget_bytes(&settings) == 0 {
+                continue;
+            }
+
+            match tokio::task::spawn_blocking(move || rotate(&data_dir, &settings)).await {
+                Ok(Ok(Some(report))) => {
+                    println!(
+                        "🗄️  [STORAGE] Freed {} MB from {} sessions ({} MB used)",
+                        report.freed_bytes / 1024 / 1024,
+                        report.sessions.len(),
+                        report.used_bytes / 1024 / 1024
+                    );
+                    if report.used_bytes > report.budget_bytes {
+                        eprintln!("⚠️  [STORAGE] Still over budget - only the active session has media left");
+                    }
+                    let _ = app.emit("storage-rotated", &report);
+                }
+                Ok(Ok(None)) => {}
+                Ok(Err(e)) => eprintln!("⚠️  [STORAGE] Rotation failed: {}", e),
+                Err(e) => eprintln!("⚠️  [STORAGE] Rotation task failed: {}", e),
+            }
+        }
+    })
+}
+
+/// Tauri command to read the storage budget and current session media usage
+#[tauri::command]
+pub async fn get_storage_budget(
+    app: AppHandle,
+    settings: State<'_, Arc<SettingsManager>>,
+) -> Result<StorageBudget, String> {
+    command_metrics::track_async("get_storage_budget", async move {
+        let storage = settings.get().storage;
+        let data_dir = profiles::profile_data_dir(&app)?;
+        let used_bytes = tokio::task::spawn_blocking(move || {
+            session_media(&data_dir).map(|sessions| sessions.iter().map(SessionMedia::bytes).sum())
+        })
+        .await
+        .map_err(|e| format!("Storage usage task failed: {}", e))??;
+
+        Ok(StorageBudget {
+            budget_gb: storage.budget_gb,
+            rotation: storage.rotation,
+            used_bytes,
+        })
+    }).await
+}
+
+/// Tauri command to set the storage budget in GB (0 = unlimited) and,
+/// optionally, the rotation mode; rotation runs right away
+#[tauri::command]
+pub fn set_storage_budget(
+    app: AppHandle,
+    settings: State<Arc<SettingsManager>>,
+    registry: State<Arc<TaskRegistry>>,
+    budget_gb: f64,
+    rotation: Option<RotationMode>,
+) -> Result<StorageSettings, String> {
+    command_metrics::track("set_storage_budget", || {
+        let mut patch = json!({ "budgetGb": budget_gb });
+        if let Some(rotation) = rotation {
+            patch["rotation"] = json!(rotation);
+        }
+        let storage = settings.update(&app, json!({ "storage": patch }))?.storage;
+        start(app.clone(), &registry)?;
+        Ok(storage)
+    })
+}
@@ -0,0 +1,84 @@
+/**
+ * System Audio Module
+ *
+ * Captures what the Mac is playing through SystemAudioBridge.swift
+ * (ScreenCaptureKit, macOS 13+; this app's own output is excluded).
+ * Samples arrive as mono f32 on a ScreenCaptureKit queue and are passed to
+ * the handler given to `SystemAudioCapture::start`; capture stops when the
+ * value is dropped. ScreenCaptureKit can stop calling the handler without
+ * reporting an error, so AudioRecorder watches for that and restarts it.
+ */
+
+#[cfg(target_os = "macos")]
+use std::ffi::c_void;
+
+type SampleHandler = Box<dyn Fn(&[f32], u32) + Send + Sync>;
+
+#[cfg(target_os = "macos")]
+extern "C" {
+    fn system_audio_start(
+        handler: extern "C" fn(*const c_void, *const f32, usize, u32),
+        context: *const c_void,
+    ) -> *mut c_void;
+    fn system_audio_stop(capture: *mut c_void);
+}
+
+/// Forwards one buffer from the Swift capture queue to the handler in `context`
+#[cfg(target_os = "macos")]
+extern "C" fn on_samples(context: *const c_void, samples: *const f32, count: usize, sample_rate: u32) {
+    if context.is_null() || samples.is_null() {
+        return;
+    }
+    // SAFETY: context is the boxed handler owned by the SystemAudioCapture,
+    // which outlives the Swift capture (stopped in Drop)
+    let handler = unsafe { &*(context as *const SampleHandler) };
+    let samples = unsafe { std::slice::from_raw_parts(samples, count) };
+    handler(samples, sample_rate);
+}
+
+/// Running system audio capture
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub struct SystemAudioCapture {
+    #[cfg(target_os = "macos")]
+    capture: *mut c_void,
+    /// Boxed twice so the pointer handed to Swift stays thin and stable
+    _handler: Box<SampleHandler>,
+}
+
+// SAFETY: the Swift capture is only stopped through its C API, which can be
+// called from any thread; the handler itself is Send + Sync
+unsafe impl Send for SystemAudioCapture {}
+unsafe impl Sync for SystemAudioCapture {}
+
+impl SystemAudioCapture {
+    /// Start capturing; `handler` receives (samples, sample rate)
+    pub fn start(handler: impl Fn(&[f32], u32) + Send + Sync + 'static) -> Result<Self, String> {
+        let handler: Box<SampleHandler> = Box::new(Box::new(handler));
+
+        #[cfg(target_os = "macos")]
+        {
+            let context = &*handler as *const SampleHandler as *const c_void;
+            let capture = unsafe { system_audio_start(on_samples, context) };
+            if capture.is_null() {
+                return Err("Failed to start system audio capture (requires macOS 13+ and Screen Recording permission)".to_string());
+            }
+            Ok(Self { capture, _handler: handler })
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            drop(handler);
+            Err("System audio capture is only supported on macOS".to_string())
+        }
+    }
+}
+
+impl Drop for SystemAudioCapture {
+    fn drop(&mut self) {
+        // Blocks until ScreenCaptureKit stopped, so the handler is no longer called
+        #[cfg(target_os = "macos")]
+        unsafe {
+            system_audio_stop(self.capture)
+        };
+    }
+}
@@ -0,0 +1,263 @@
+/**
+ * Thumbnails Module
+ *
+ * Small JPEG thumbnails for attachment grids, so views don't have to load
+ * full screenshots:
+ * - `get_attachment_thumbnail(id, max_dim)` returns a JPEG data URL no larger
+ *   than max_dim on either edge, cached as `thumbnails/{id}_{max_dim}.jpg` in
+ *   the profile data dir
+ * - Images are scaled from the stored data; videos from a frame one second in
+ *   (at most 320x180, via the Swift recorder, macOS only)
+ * - A cached thumbnail older than its attachment data (e.g. a screenshot
+ *   downsampled by storage_budget.rs) is regenerated
+ * - The "attachment-thumbnailer" background task pre-generates
+ *   DEFAULT_MAX_DIM thumbnails for the active profile's session screenshots
+ *   and removes thumbnails of deleted attachments
+ * - `clear_attachment_thumbnails` drops the cache for deleted attachments
+ */
+
+use rayon::prelude::*;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+use tauri::AppHandle;
+
+use crate::attachment_metadata::read_attachment_bytes;
+use crate::background_tasks::TaskRegistry;
+use crate::command_metrics;
+use crate::profiles;
+use crate::session_storage;
+use crate::video_recording;
+
+const TASK_NAME: &str = "attachment-thumbnailer";
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Cache directory in the profile data dir
+const THUMBNAILS_DIR: &str = "thumbnails";
+
+/// Edge used by grid views and the background pass
+const DEFAULT_MAX_DIM: u32 = 256;
+
+/// Larger requests are clamped (full images are better loaded directly)
+const MAX_DIM_LIMIT: u32 = 1024;
+
+const JPEG_QUALITY: u8 = 75;
+
+/// Thumbnails generated per background pass
+const MAX_PER_PASS: usize = 100;
+
+fn thumbnail_path(data_dir: &Path, id: &str, max_dim: u32) -> PathBuf {
+    data_dir.join(THUMBNAILS_DIR).join(format!("{}_{}.jpg", id, max_dim))
+}
+
+/// Attachment ids become file names; refuse anything that could escape the cache
+fn validate_id(id: &str) -> Result<(), String> {
+    if id.is_empty() || id.contains(['/', '\\']) || id.contains("..") {
+        return Err(format!("Invalid attachment id: {}", id));
+    }
+    Ok(())
+}
+
+fn read_meta(attachments_dir: &Path, id: &str) -> Result<serde_json::Value, String> {
+    let content = std::fs::read_to_string(attachments_dir.join(format!("{}.meta.json", id)))
+        .map_err(|e| format!("Failed to read metadata for attachment {}: {}", id, e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse metadata: {}", e))
+}
+
+/// When the attachment's data last changed (inline data or referenced file)
+fn source_modified(attachments_dir: &Path, id: &str, meta: &serde_json::Value) -> Option<SystemTime> {
+    let data_path = attachments_dir.join(format!("{}.dat", id));
+    let path = if data_path.exists() {
+        data_path
+    } else {
+        PathBuf::from(meta.get("path")?.as_str()?)
+    };
+    std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+fn decode_data_url(data_url: &str) -> Result<image::DynamicImage, String> {
+    let encoded = data_url.split_once(',').map_or(data_url, |(_, data)| data);
+    let bytes = base64::Engine::decode(&base64::engine::general_purpose::STANDARD, encoded.trim())
+        .map_err(|e| format!("Failed to decode frame: {}", e))?;
+    image::load_from_memory(&bytes).map_err(|e| format!("Failed to decode frame: {}", e))
+}
+
+/// Encode a new thumbnail for an attachment (blocking)
+fn render(attachments_dir: &Path, id: &str, meta: &serde_json::Value, max_dim: u32) -> Result<Vec<u8>, String> {
+    let mime_type = meta.get("mimeType").and_then(|mime| mime.as_str()).unwrap_or("");
+    let image = if mime_type.starts_with("image/") {
+        let bytes = read_attachment_bytes(attachments_dir, id, meta)?;
+        image::load_from_memory(&bytes).map_err(|e| format!("Failed to decode image {}: {}", id, e))?
+    } else if mime_type.starts_with("video/") {
+        let path = meta
+            .get("path")
+            .and_then(|path| path.as_str())
+            .ok_or_else(|| format!("Video attachment {} has no file path", id))?;
+        decode_data_url(&video_recording::video_thumbnail(path, 1.0)?)?
+    } else {
+        return Err(format!("No thumbnail available for {} attachments", mime_type));
+    };
+
+    let thumbnail = image.thumbnail(max_dim, max_dim).to_rgb8();
+    let mut bytes = Vec::new();
+    image::codecs::jpeg::JpegEncoder::new_with_quality(&mut bytes, JPEG_QUALITY)
+        .encode_image(&thumbnail)
+        .map_err(|e| format!("Failed to encode thumbnail: {}", e))?;
+    Ok(bytes)
+}
+
+/// Cached thumbnail JPEG, generated (and cached) when missing or stale
+pub fn get_or_create(data_dir: &Path, id: &str, max_dim: u32) -> Result<Vec<u8>, String> {
+    validate_id(id)?;
+    let attachments_dir = data_dir.join("attachments");
+    let meta = read_meta(&attachments_dir, id)?;
+    let path = thumbnail_path(data_dir, id, max_dim);
+
+    let cached_at = std::fs::metadata(&path).and_then(|metadata| metadata.modified()).ok();
+    if let Some(cached_at) = cached_at {
+        let stale = source_modified(&attachments_dir, id, &meta).is_some_and(|modified| modified > cached_at);
+        if !stale {
+            if let Ok(bytes) = std::fs::read(&path) {
+                return Ok(bytes);
+            }
+        }
+    }
+
+    let bytes = render(&attachments_dir, id, &meta, max_dim)?;
+    std::fs::create_dir_all(data_dir.join(THUMBNAILS_DIR))
+        .map_err(|e| format!("Failed to create thumbnails directory: {}", e))?;
+    std::fs::write(&path, &bytes).map_err(|e| format!("Failed to write thumbnail: {}", e))?;
+    Ok(bytes)
+}
+
+/// Attachment id of a cached thumbnail file name (`{id}_{max_dim}.jpg`)
+fn thumbnail_attachment_id(file_name: &str) -> Option<&str> {
+    let (id, max_dim) = file_name.strip_suffix(".jpg")?.rsplit_once('_')?;
+    max_dim.parse::<u32>().ok().map(|_| id)
+}
+
+/// Remove cached thumbnails of attachments matching `remove`; returns how many
+fn remove_thumbnails(data_dir: &Path, remove: impl Fn(&str) -> bool) -> usize {
+    let Ok(entries) = std::fs::read_dir(data_dir.join(THUMBNAILS_DIR)) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .filter(|entry| {
+            let name = entry.file_name();
+            thumbnail_attachment_id(&name.to_string_lossy()).is_some_and(&remove)
+        })
+        .filter(|entry| std::fs::remove_file(entry.path()).is_ok())
+        .count()
+}
+
+/// Drop every cached thumbnail of an attachment (on delete)
+pub fn invalidate(data_dir: &Path, id: &str) {
+    remove_thumbnails(data_dir, |thumbnail_id| thumbnail_id == id);
+}
+
+/// One background pass: drop orphaned thumbnails, then generate missing ones
+/// for session screenshots, newest sessions first
+fn run_pass(data_dir: &Path, is_cancelled: impl Fn() -> bool + Sync) -> Result<(usize, usize), String> {
+    let attachments_dir = data_dir.join("attachments");
+    let removed = remove_thumbnails(data_dir, |id| !attachments_dir.join(format!("{}.meta.json", id)).exists());
+
+    let mut sessions = session_storage::read_sessions(data_dir)?;
+    sessions.sort_by(|a, b| b.start_time.cmp(&a.start_time));
+    let pending: Vec<String> = sessions
+        .into_iter()
+        .flat_map(|session| session.screenshots.unwrap_or_default())
+        .map(|screenshot| screenshot.attachment_id)
+        .filter(|id| {
+            validate_id(id).is_ok()
+                && !thumbnail_path(data_dir, id, DEFAULT_MAX_DIM).exists()
+                && attachments_dir.join(format!("{}.meta.json", id)).exists()
+        })
+        .take(MAX_PER_PASS)
+        .collect();
+
+    let generated = pending
+        .par_iter()
+        .filter(|id| {
+            if is_cancelled() {
+                return false;
+            }
+            get_or_create(data_dir, id, DEFAULT_MAX_DIM)
+                .map_err(|e| eprintln!("⚠️  [THUMBNAILS] Skipping {}: {}", id, e))
+                .is_ok()
+        })
+        .count();
+    Ok((generated, removed))
+}
+
+/// Start generating thumbnails in the background
+pub fn start(app: AppHandle, registry: &TaskRegistry) -> Result<(), String> {
+    registry.spawn(TASK_NAME, |mut shutdown| async move {
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+
+            let data_dir = match profiles::profile_data_dir(&app) {
+                Ok(data_dir) => data_dir,
+                Err(e) => {
+                    eprintln!("⚠️  [THUMBNAILS] {}", e);
+                    continue;
+                }
+            };
+            let cancel = shutdown.clone();
+            let result = tokio::task::spawn_blocking(move || {
+                let start = Instant::now();
+                let (generated, removed) = run_pass(&data_dir, || cancel.is_cancelled())?;
+                if generated > 0 || removed > 0 {
+                    println!(
+                        "🖼️  [THUMBNAILS] Generated {}, removed {} thumbnail(s) in {:?}",
+                        generated, removed, start.elapsed()
+                    );
+                }
+                Ok::<_, String>(())
+            })
+            .await;
+            match result {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => eprintln!("⚠️  [THUMBNAILS] Background pass failed: {}", e),
+                Err(e) => eprintln!("⚠️  [THUMBNAILS] Background task failed: {}", e),
+            }
+        }
+    })
+}
+
+/// Tauri command to get an attachment's thumbnail as a JPEG data URL
+/// (`max_dim` defaults to 256, at most 1024)
+#[tauri::command]
+pub async fn get_attachment_thumbnail(
+    id: String,
+    max_dim: Option<u32>,
+    app_handle: AppHandle,
+) -> Result<String, String> {
+    command_metrics::track_async("get_attachment_thumbnail", async move {
+        let data_dir = profiles::profile_data_dir(&app_handle)?;
+        let max_dim = max_dim.unwrap_or(DEFAULT_MAX_DIM).clamp(16, MAX_DIM_LIMIT);
+        let bytes = tokio::task::spawn_blocking(move || get_or_create(&data_dir, &id, max_dim))
+            .await
+            .map_err(|e| format!("Thumbnail task failed: {}", e))??;
+        let encoded = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &bytes);
+        Ok(format!("data:image/jpeg;base64,{}", encoded))
+    }).await
+}
+
+/// Tauri command to drop cached thumbnails of deleted attachments
+#[tauri::command]
+pub async fn clear_attachment_thumbnails(attachment_ids: Vec<String>, app_handle: AppHandle) -> Result<(), String> {
+    command_metrics::track_async("clear_attachment_thumbnails", async move {
+        let data_dir = profiles::profile_data_dir(&app_handle)?;
+        tokio::task::spawn_blocking(move || {
+            for id in &attachment_ids {
+                invalidate(&data_dir, id);
+            }
+        })
+        .await
+        .map_err(|e| format!("Thumbnail task failed: {}", e))
+    }).await
+}
@@ -0,0 +1,293 @@
+/**
+ * Timelapse Module
+ *
+ * `generate_session_timelapse` turns a session's screenshots into a timelapse
+ * (format from the output extension):
+ * - `.gif`: encoded in Rust, at most GIF_MAX_WIDTH wide
+ * - `.mp4`: H.264 via ffmpeg (see media_tools.rs), at most MP4_MAX_WIDTH wide
+ *
+ * Frames are scaled to the first screenshot's size (letterboxed when the
+ * aspect ratio differs) and stamped with their capture time.
+ *
+ * Runs as a job (jobs.rs): progress per rendered frame, cancellable, and the
+ * partial output is removed on failure. Offloaded screenshots (remote
+ * archive) are fetched back first.
+ */
+
+use ab_glyph::PxScale;
+use image::{imageops::FilterType, Rgba, RgbaImage};
+use imageproc::drawing::{draw_filled_rect_mut, draw_text_mut, text_size, Blend};
+use imageproc::rect::Rect;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Arc;
+use tauri::AppHandle;
+
+use crate::annotation;
+use crate::attachment_metadata;
+use crate::background_tasks::TaskRegistry;
+use crate::command_metrics;
+use crate::jobs::{JobContext, JobRegistry};
+use crate::media_tools;
+use crate::profiles;
+use crate::remote_archive;
+use crate::session_models::Screenshot;
+use crate::session_storage;
+
+const DEFAULT_FPS: u32 = 4;
+const MAX_FPS: u32 = 30;
+
+const GIF_MAX_WIDTH: u32 = 800;
+const MP4_MAX_WIDTH: u32 = 1920;
+
+/// Timestamp text height relative to the frame height
+const TIMESTAMP_SCALE: f32 = 0.035;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TimelapseFormat {
+    Gif,
+    Mp4,
+}
+
+impl TimelapseFormat {
+    fn from_path(path: &Path) -> Result<Self, String> {
+        match path.extension().and_then(|ext| ext.to_str()).map(str::to_ascii_lowercase).as_deref() {
+            Some("gif") => Ok(Self::Gif),
+            Some("mp4") => Ok(Self::Mp4),
+            _ => Err("Timelapse output must be a .gif or .mp4 file".to_string()),
+        }
+    }
+
+    fn max_width(self) -> u32 {
+        match self {
+            Self::Gif => GIF_MAX_WIDTH,
+            Self::Mp4 => MP4_MAX_WIDTH,
+        }
+    }
+}
+
+/// `job-finished` result of a timelapse job
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TimelapseResult {
+    path: String,
+    frames: usize,
+    /// Frames that couldn't be read (skipped)
+    skipped: usize,
+    duration_ms: u64,
+}
+
+/// Output size for a first frame of `width` x `height` (even, for H.264)
+fn frame_size(width: u32, height: u32, max_width: u32) -> (u32, u32) {
+    let scale = (max_width as f64 / width as f64).min(1.0);
+    let even = |value: f64| ((value.round() as u32) / 2 * 2).max(2);
+    (even(width as f64 * scale), even(height as f64 * scale))
+}
+
+fn load_screenshot(attachments_dir: &Path, screenshot: &Screenshot) -> Result<image::DynamicImage, String> {
+    let meta_path = attachments_dir.join(format!("{}.meta.json", screenshot.attachment_id));
+    let meta: serde_json::Value = std::fs::read_to_string(&meta_path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or(serde_json::Value::Null);
+    let bytes = attachment_metadata::read_attachment_bytes(attachments_dir, &screenshot.attachment_id, &meta)?;
+    image::load_from_memory(&bytes).map_err(|e| format!("Failed to decode screenshot {}: {}", screenshot.attachment_id, e))
+}
+
+/// Fit a screenshot into the frame (letterboxed) and stamp its capture time
+fn render_frame(image: image::DynamicImage, timestamp: &str, size: (u32, u32)) -> RgbaImage {
+    let image = image.resize(size.0, size.1, FilterType::Triangle).to_rgba8();
+    let mut frame = RgbaImage::from_pixel(size.0, size.1, Rgba([0, 0, 0, 255]));
+    let x = (size.0 - image.width()) / 2;
+    let y = (size.1 - image.height()) / 2;
+    image::imageops::overlay(&mut frame, &image, x as i64, y as i64);
+    stamp_time(&mut frame, timestamp);
+    frame
+}
+
+/// Draw the capture time in the bottom-left corner
+fn stamp_time(frame: &mut RgbaImage, timestamp: &str) {
+    let Ok(font) = annotation::font() else { return };
+    let text = chrono::DateTime::parse_from_rfc3339(timestamp)
+        .map(|time| time.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_else(|_| timestamp.to_string());
+
+    let scale = PxScale::from((frame.height() as f32 * TIMESTAMP_SCALE).max(12.0));
+    let (text_width, text_height) = text_size(scale, font, &text);
+    let padding = (scale.y / 3.0).round() as i32;
+    let x = padding;
+    let y = frame.height() as i32 - text_height as i32 - padding * 3;
+
+    let mut canvas = Blend(std::mem::take(frame));
+    let background = Rect::at(x - padding, y - padding)
+        .of_size(text_width + padding as u32 * 2, text_height + padding as u32 * 2);
+    draw_filled_rect_mut(&mut canvas, background, Rgba([0, 0, 0, 160]));
+    draw_text_mut(&mut canvas, Rgba([255, 255, 255, 255]), x, y, scale, font, &text);
+    *frame = canvas.0;
+}
+
+/// Render every frame, handing each to `write`; returns (frames, skipped)
+fn render_frames(
+    ctx: &JobContext,
+    attachments_dir: &Path,
+    screenshots: &[Screenshot],
+    max_width: u32,
+    mut write: impl FnMut(RgbaImage) -> Result<(), String>,
+) -> Result<(usize, usize), String> {
+    // The first readable screenshot sets the size
+    let mut size = None;
+    let (mut frames, mut skipped) = (0, 0);
+    for (index, screenshot) in screenshots.iter().enumerate() {
+        ctx.check_cancelled()?;
+        ctx.progress_items(index as u64, screenshots.len() as u64, "Rendering frames");
+
+        match load_screenshot(attachments_dir, screenshot) {
+            Ok(image) => {
+                let size = *size.get_or_insert_with(|| frame_size(image.width(), image.height(), max_width));
+                write(render_frame(image, &screenshot.timestamp, size))?;
+                frames += 1;
+            }
+            Err(e) => {
+                eprintln!("⚠️  [TIMELAPSE] Skipping frame: {}", e);
+                skipped += 1;
+            }
+        }
+    }
+    if frames == 0 {
+        return Err("None of the session's screenshots could be read".to_string());
+    }
+    Ok((frames, skipped))
+}
+
+fn write_gif(
+    ctx: &JobContext,
+    attachments_dir: &Path,
+    screenshots: &[Screenshot],
+    fps: u32,
+    output: &Path,
+) -> Result<(usize, usize), String> {
+    use image::codecs::gif::{GifEncoder, Repeat};
+
+    let file = std::fs::File::create(output).map_err(|e| format!("Failed to create {:?}: {}", output, e))?;
+    let mut encoder = GifEncoder::new_with_speed(std::io::BufWriter::new(file), 10);
+    encoder
+        .set_repeat(Repeat::Infinite)
+        .map_err(|e| format!("Failed to write GIF: {}", e))?;
+    let delay = image::Delay::from_numer_denom_ms(1000, fps);
+    render_frames(ctx, attachments_dir, screenshots, TimelapseFormat::Gif.max_width(), |frame| {
+        encoder
+            .encode_frame(image::Frame::from_parts(frame, 0, 0, delay))
+            .map_err(|e| format!("Failed to write GIF: {}", e))
+    })
+}
+
+fn write_mp4(
+    ctx: &JobContext,
+    attachments_dir: &Path,
+    screenshots: &[Screenshot],
+    fps: u32,
+    output: &Path,
+) -> Result<(usize, usize), String> {
+    let ffmpeg = media_tools::ffmpeg().ok_or("MP4 timelapses need ffmpeg; export a GIF instead")?;
+
+    // Frames go to a temporary folder next to the output, then ffmpeg encodes them
+    let frames_dir = output.with_extension("frames");
+    std::fs::create_dir_all(&frames_dir).map_err(|e| format!("Failed to create {:?}: {}", frames_dir, e))?;
+    let result = (|| {
+        let mut next = 0;
+        let counts = render_frames(ctx, attachments_dir, screenshots, TimelapseFormat::Mp4.max_width(), |frame| {
+            let path = frames_dir.join(format!("frame{:06}.png", next));
+            next += 1;
+            frame.save(&path).map_err(|e| format!("Failed to write frame: {}", e))
+        })?;
+
+        ctx.progress(1.0, "Encoding video");
+        let mut command = Command::new(&ffmpeg);
+        command
+            .args(["-y", "-v", "error", "-framerate", &fps.to_string(), "-i"])
+            .arg(frames_dir.join("frame%06d.png"))
+            .args(["-c:v", "libx264", "-pix_fmt", "yuv420p", "-movflags", "+faststart", "-f", "mp4"])
+            .arg(output);
+        media_tools::run_cancellable(&mut command, || ctx.is_cancelled())
+            .map_err(|e| format!("Failed to encode the timelapse: {}", e))?;
+        Ok(counts)
+    })();
+    let _ = std::fs::remove_dir_all(&frames_dir);
+    result
+}
+
+/// Tauri command to render a session's screenshots as a GIF or MP4 timelapse
+/// Runs as a job (returns the job id); the `job-finished` result has the path,
+/// frame count and duration
+#[tauri::command]
+pub async fn generate_session_timelapse(
+    app: AppHandle,
+    jobs: tauri::State<'_, Arc<JobRegistry>>,
+    tasks: tauri::State<'_, Arc<TaskRegistry>>,
+    session_id: String,
+    fps: Option<u32>,
+    output_path: String,
+) -> Result<String, String> {
+    command_metrics::track_async("generate_session_timelapse", async move {
+        let output = PathBuf::from(&output_path);
+        let format = TimelapseFormat::from_path(&output)?;
+        if !output.parent().is_some_and(|parent| parent.is_dir()) {
+            return Err(format!("Output folder doesn't exist: {}", output_path));
+        }
+        let fps = fps.unwrap_or(DEFAULT_FPS).clamp(1, MAX_FPS);
+        let data_dir = profiles::profile_data_dir(&app)?;
+
+        jobs.start(app.clone(), &tasks, "session-timelapse", move |ctx| async move {
+            ctx.progress(0.0, "Reading session");
+            let read_dir = data_dir.clone();
+            let session = tokio::task::spawn_blocking(move || session_storage::read_session(&read_dir, &session_id))
+                .await
+                .map_err(|e| format!("Session task failed: {}", e))??;
+            let mut screenshots = session.screenshots.clone().unwrap_or_default();
+            if screenshots.is_empty() {
+                return Err(format!("Session {} has no screenshots", session.id));
+            }
+            screenshots.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+
+            ctx.progress(0.0, "Fetching archived screenshots");
+            remote_archive::fetch_session_media(&app, &data_dir, &session).await;
+            ctx.check_cancelled()?;
+
+            // Written next to the target and renamed once complete
+            let partial_path = output.with_file_name(format!(
+                ".{}.partial",
+                output.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default()
+            ));
+            ctx.partial_output(partial_path.clone());
+            let job = ctx.clone();
+            let attachments_dir = data_dir.join("attachments");
+            let final_path = output.clone();
+            let (frames, skipped) = tokio::task::spawn_blocking(move || {
+                let counts = match format {
+                    TimelapseFormat::Gif => write_gif(&job, &attachments_dir, &screenshots, fps, &partial_path)?,
+                    TimelapseFormat::Mp4 => write_mp4(&job, &attachments_dir, &screenshots, fps, &partial_path)?,
+                };
+                job.check_cancelled()?;
+                std::fs::rename(&partial_path, &final_path)
+                    .map_err(|e| format!("Failed to write timelapse: {}", e))?;
+                job.commit_output(&partial_path);
+                Ok::<_, String>(counts)
+            })
+            .await
+            .map_err(|e| format!("Timelapse task failed: {}", e))??;
+
+            println!(
+                "🎞️  [TIMELAPSE] Wrote {} frame(s) of session {} to {:?} ({} skipped)",
+                frames, session.id, output, skipped
+            );
+            serde_json::to_value(TimelapseResult {
+                path: output_path,
+                frames,
+                skipped,
+                duration_ms: frames as u64 * 1000 / fps as u64,
+            })
+            .map_err(|e| format!("Failed to serialize timelapse result: {}", e))
+        })
+    }).await
+}
@@ -0,0 +1,422 @@
+/**
+ * Transcription Queue Module
+ *
+ * Rust-side Whisper transcription of recorded audio chunks, so chunks keep
+ * getting transcribed regardless of what the frontend is doing:
+ * - `enqueue_transcription` adds a chunk file (session id + chunk index)
+ * - The "transcription-queue" background task transcribes at most
+ *   MAX_CONCURRENT chunks at a time, oldest first
+ * - Failures retry with exponential backoff up to MAX_ATTEMPTS (missing or
+ *   unsupported files fail immediately)
+ * - The queue is persisted in <app data>/transcription_queue.json; chunks that
+ *   were in flight when the app quit are picked up again on the next start
+ * - Results are emitted as `transcription-complete` / `transcription-failed`
+ *   keyed by session id and chunk index
+ * - Once audio recording has stopped and a session's last queued chunk is
+ *   transcribed, a `transcriptionComplete` notification is shown
+ */
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tokio::sync::Notify;
+
+use crate::ai_types::RequestPriority;
+use crate::audio_capture::AudioRecorder;
+use crate::background_tasks::TaskRegistry;
+use crate::command_metrics;
+use crate::notifications::{self, NotificationCategory};
+use crate::openai_api;
+use crate::safe_state::SafeState;
+
+const QUEUE_FILE: &str = "transcription_queue.json";
+const TASK_NAME: &str = "transcription-queue";
+
+const MAX_CONCURRENT: usize = 2;
+const MAX_ATTEMPTS: u32 = 4;
+
+/// Delay before the first retry (doubles per attempt)
+const BASE_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// How long the worker sleeps when nothing is scheduled
+const IDLE_WAIT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TranscriptionStatus {
+    Pending,
+    Running,
+}
+
+/// One queued chunk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptionItem {
+    pub session_id: String,
+    pub chunk_index: u32,
+    pub path: String,
+    pub status: TranscriptionStatus,
+    /// Attempts made so far
+    pub attempts: u32,
+    pub last_error: Option<String>,
+    /// Unix millis
+    pub enqueued_at: i64,
+    /// Not attempted before this (Unix millis)
+    pub next_attempt_at: i64,
+}
+
+impl TranscriptionItem {
+    fn is(&self, session_id: &str, chunk_index: u32) -> bool {
+        self.session_id == session_id && self.chunk_index == chunk_index
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TranscriptionCompleteEvent<'a> {
+    session_id: &'a str,
+    chunk_index: u32,
+    path: &'a str,
+    text: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TranscriptionFailedEvent<'a> {
+    session_id: &'a str,
+    chunk_index: u32,
+    path: &'a str,
+    error: String,
+    attempts: u32,
+}
+
+/// Why a chunk failed, and whether another attempt could succeed
+struct Failure {
+    error: String,
+    retryable: bool,
+}
+
+/// Whisper upload format for a chunk file
+fn audio_format(path: &Path) -> Option<&'static str> {
+    match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+        "wav" => Some("wav"),
+        "mp3" => Some("mp3"),
+        "flac" => Some("flac"),
+        "ogg" | "opus" => Some("ogg"),
+        _ => None,
+    }
+}
+
+async fn transcribe(app: &AppHandle, item: &TranscriptionItem) -> Result<String, Failure> {
+    let path = PathBuf::from(&item.path);
+    let format = audio_format(&path).ok_or_else(|| Failure {
+        error: format!("Unsupported audio file: {}", item.path),
+        retryable: false,
+    })?;
+    let audio_bytes = tokio::fs::read(&path).await.map_err(|e| Failure {
+        retryable: e.kind() != std::io::ErrorKind::NotFound,
+        error: format!("Failed to read audio chunk: {}", e),
+    })?;
+
+    openai_api::transcribe_bytes(app, format, &audio_bytes, RequestPriority::User)
+        .await
+        .map_err(|error| Failure { error, retryable: true })
+}
+
+/// Managed transcription queue
+pub struct TranscriptionQueue {
+    items: SafeState<Vec<TranscriptionItem>>,
+    changed: Notify,
+}
+
+impl TranscriptionQueue {
+    pub fn new() -> Self {
+        Self {
+            items: SafeState::new("transcription_queue", Vec::new()),
+            changed: Notify::new(),
+        }
+    }
+
+    fn path(app: &AppHandle) -> Result<PathBuf, String> {
+        Ok(app
+            .path()
+            .app_data_dir()
+            .map_err(|e| format!("Failed to get app data dir: {}", e))?
+            .join(QUEUE_FILE))
+    }
+
+    fn save(app: &AppHandle, items: &[TranscriptionItem]) -> Result<(), String> {
+        let path = Self::path(app)?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create app data dir: {}", e))?;
+        }
+        let content = serde_json::to_string(items)
+            .map_err(|e| format!("Failed to serialize transcription queue: {}", e))?;
+        let temp_path = path.with_extension("json.tmp");
+        std::fs::write(&temp_path, content)
+            .map_err(|e| format!("Failed to write transcription queue: {}", e))?;
+        std::fs::rename(&temp_path, &path)
+            .map_err(|e| format!("Failed to write transcription queue: {}", e))
+    }
+
+    /// Apply a change to the queue and persist it
+    fn update<T>(&self, app: &AppHandle, apply: impl FnOnce(&mut Vec<TranscriptionItem>) -> T) -> T {
+        let mut items = self.items.lock();
+        let result = apply(&mut items);
+        if let Err(e) = Self::save(app, &items) {
+            eprintln!("⚠️  [TRANSCRIPTION] {}", e);
+        }
+        result
+    }
+
+    /// Add a chunk (replacing a pending entry for the same chunk); returns
+    /// false if that chunk is already being transcribed
+    fn enqueue(&self, app: &AppHandle, session_id: String, chunk_index: u32, path: String) -> bool {
+        let queued = self.update(app, |items| {
+            if items.iter().any(|item| item.is(&session_id, chunk_index) && item.status == TranscriptionStatus::Running) {
+                return false;
+            }
+            items.retain(|item| !item.is(&session_id, chunk_index));
+
+            let now = chrono::Utc::now().timestamp_millis();
+            items.push(TranscriptionItem {
+                session_id,
+                chunk_index,
+                path,
+                status: TranscriptionStatus::Pending,
+                attempts: 0,
+                last_error: None,
+                enqueued_at: now,
+                next_attempt_at: now,
+            });
+            true
+        });
+        if queued {
+            self.changed.notify_one();
+        }
+        queued
+    }
+
+    /// Remove pending chunks of a session; returns how many were removed
+    /// (chunks already in flight still finish)
+    fn cancel_session(&self, app: &AppHandle, session_id: &str) -> usize {
+        self.update(app, |items| {
+            let before = items.len();
+            items.retain(|item| item.session_id != session_id || item.status == TranscriptionStatus::Running);
+            before - items.len()
+        })
+    }
+
+    fn list(&self, session_id: Option<&str>) -> Vec<TranscriptionItem> {
+        self.items
+            .lock()
+            .iter()
+            .filter(|item| session_id.map_or(true, |id| item.session_id == id))
+            .cloned()
+            .collect()
+    }
+
+    /// Mark the oldest due chunk as running and return it
+    fn claim_next(&self, app: &AppHandle) -> Option<TranscriptionItem> {
+        let now = chrono::Utc::now().timestamp_millis();
+        self.update(app, |items| {
+            let item = items
+                .iter_mut()
+                .filter(|item| item.status == TranscriptionStatus::Pending && item.next_attempt_at <= now)
+                .min_by_key(|item| item.enqueued_at)?;
+            item.status = TranscriptionStatus::Running;
+            item.attempts += 1;
+            Some(item.clone())
+        })
+    }
+
+    /// Time until the next scheduled retry
+    fn next_retry_in(&self) -> Option<Duration> {
+        let now = chrono::Utc::now().timestamp_millis();
+        self.items
+            .lock()
+            .iter()
+            .filter(|item| item.status == TranscriptionStatus::Pending)
+            .map(|item| Duration::from_millis((item.next_attempt_at - now).max(0) as u64))
+            .min()
+    }
+
+    /// Notify once a stopped recording's queued chunks are all transcribed
+    fn notify_if_done(&self, app: &AppHandle, session_id: &str) {
+        let pending = self.items.lock().iter().any(|item| item.session_id == session_id);
+        if pending || app.state::<Arc<AudioRecorder>>().is_recording() {
+            return;
+        }
+        notifications::notify(
+            app,
+            NotificationCategory::TranscriptionComplete,
+            session_id,
+            "Transcription complete".to_string(),
+            "All recorded audio of the session has been transcribed.".to_string(),
+        );
+    }
+
+    /// Record the outcome of an attempt and emit the result
+    fn finish(&self, app: &AppHandle, item: TranscriptionItem, result: Result<String, Failure>) {
+        let failure = match result {
+            Ok(text) => {
+                self.update(app, |items| items.retain(|queued| !queued.is(&item.session_id, item.chunk_index)));
+                println!("📝 [TRANSCRIPTION] Chunk {} of {} transcribed", item.chunk_index, item.session_id);
+                let _ = app.emit("transcription-complete", TranscriptionCompleteEvent {
+                    session_id: &item.session_id,
+                    chunk_index: item.chunk_index,
+                    path: &item.path,
+                    text,
+                });
+                self.notify_if_done(app, &item.session_id);
+                return;
+            }
+            Err(failure) => failure,
+        };
+
+        if failure.retryable && item.attempts < MAX_ATTEMPTS {
+            let delay = BASE_RETRY_DELAY.saturating_mul(2u32.saturating_pow(item.attempts - 1));
+            eprintln!(
+                "⚠️  [TRANSCRIPTION] Chunk {} of {} failed (attempt {}/{}), retrying in {}s: {}",
+                item.chunk_index, item.session_id, item.attempts, MAX_ATTEMPTS, delay.as_secs(), failure.error
+            );
+            self.update(app, |items| {
+                if let Some(queued) = items.iter_mut().find(|queued| queued.is(&item.session_id, item.chunk_index)) {
+                    queued.status = TranscriptionStatus::Pending;
+                    queued.last_error = Some(failure.error.clone());
+                    queued.next_attempt_at = chrono::Utc::now().timestamp_millis() + delay.as_millis() as i64;
+                }
+            });
+            return;
+        }
+
+        self.update(app, |items| items.retain(|queued| !queued.is(&item.session_id, item.chunk_index)));
+        eprintln!(
+            "❌ [TRANSCRIPTION] Chunk {} of {} failed after {} attempt(s): {}",
+            item.chunk_index, item.session_id, item.attempts, failure.error
+        );
+        let _ = app.emit("transcription-failed", TranscriptionFailedEvent {
+            session_id: &item.session_id,
+            chunk_index: item.chunk_index,
+            path: &item.path,
+            error: failure.error,
+            attempts: item.attempts,
+        });
+    }
+
+    /// Load the persisted queue and start the worker as the
+    /// "transcription-queue" background task
+    pub fn start(self: &Arc<Self>, app: AppHandle, registry: &TaskRegistry) -> Result<(), String> {
+        let persisted: Vec<TranscriptionItem> = Self::path(&app)
+            .ok()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        if !persisted.is_empty() {
+            println!("📝 [TRANSCRIPTION] Resuming {} queued chunk(s)", persisted.len());
+        }
+        {
+            // Chunks that were in flight at shutdown start over
+            let mut items = self.items.lock();
+            let enqueued_since: Vec<TranscriptionItem> = std::mem::take(&mut *items);
+            items.extend(persisted.into_iter().map(|mut item| {
+                item.status = TranscriptionStatus::Pending;
+                item
+            }));
+            for item in enqueued_since {
+                items.retain(|queued| !queued.is(&item.session_id, item.chunk_index));
+                items.push(item);
+            }
+        }
+
+        let queue = self.clone();
+        registry.spawn(TASK_NAME, |mut shutdown| async move {
+            let mut in_flight = tokio::task::JoinSet::new();
+            loop {
+                while in_flight.len() < MAX_CONCURRENT {
+                    let Some(item) = queue.claim_next(&app) else { break };
+                    let app = app.clone();
+                    in_flight.spawn(async move {
+                        let result = transcribe(&app, &item).await;
+                        (item, result)
+                    });
+                }
+
+                let wait = if in_flight.len() < MAX_CONCURRENT {
+                    queue.next_retry_in().unwrap_or(IDLE_WAIT)
+                } else {
+                    IDLE_WAIT
+                };
+
+                tokio::select! {
+                    _ = shutdown.cancelled() => break,
+                    _ = queue.changed.notified() => {}
+                    Some(joined) = in_flight.join_next(), if !in_flight.is_empty() => {
+                        match joined {
+                            Ok((item, result)) => queue.finish(&app, item, result),
+                            Err(e) => eprintln!("❌ [TRANSCRIPTION] Worker task failed: {}", e),
+                        }
+                    }
+                    _ = tokio::time::sleep(wait) => {}
+                }
+            }
+
+            // In-flight chunks stay persisted as running and are retried on the next start
+            in_flight.abort_all();
+            println!("🛑 [TRANSCRIPTION] Queue worker exiting");
+        })
+    }
+}
+
+impl Default for TranscriptionQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Tauri command: queue an audio chunk file for transcription; returns false
+/// if the chunk is already being transcribed
+#[tauri::command]
+pub fn enqueue_transcription(
+    app: AppHandle,
+    queue: State<Arc<TranscriptionQueue>>,
+    session_id: String,
+    chunk_index: u32,
+    path: String,
+) -> Result<bool, String> {
+    command_metrics::track("enqueue_transcription", || {
+        if audio_format(Path::new(&path)).is_none() {
+            return Err(format!("Unsupported audio file: {}", path));
+        }
+        if !Path::new(&path).is_file() {
+            return Err(format!("Audio chunk not found: {}", path));
+        }
+        Ok(queue.enqueue(&app, session_id, chunk_index, path))
+    })
+}
+
+/// Tauri command: queued and running chunks, optionally for one session
+#[tauri::command]
+pub fn get_transcription_queue(
+    queue: State<Arc<TranscriptionQueue>>,
+    session_id: Option<String>,
+) -> Result<Vec<TranscriptionItem>, String> {
+    command_metrics::track("get_transcription_queue", || {
+        Ok(queue.list(session_id.as_deref()))
+    })
+}
+
+/// Tauri command: drop a session's pending chunks; returns how many were removed
+#[tauri::command]
+pub fn cancel_session_transcriptions(
+    app: AppHandle,
+    queue: State<Arc<TranscriptionQueue>>,
+    session_id: String,
+) -> Result<usize, String> {
+    command_metrics::track("cancel_session_transcriptions", || {
+        Ok(queue.cancel_session(&app, &session_id))
+    })
+}
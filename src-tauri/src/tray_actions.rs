@@ -0,0 +1,239 @@
+/**
+ * Tray Actions Module
+ *
+ * Builds the tray menu: countdown and session controls, quick actions
+ * registered at runtime (e.g. "Start Deep Work session", "New note") and the
+ * window items. Actions are added and removed with `add_tray_action` /
+ * `remove_tray_action` and kept in tray_actions.json (tauri-plugin-store, can
+ * also be edited by hand, read at startup); the menu is rebuilt on each
+ * change. Clicking an action emits its event (with its payload) to the frontend.
+ */
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tauri::menu::{Menu, MenuItem, PredefinedMenuItem};
+use tauri::tray::TrayIcon;
+use tauri::{AppHandle, Emitter, Manager, State, Wry};
+use tauri_plugin_store::StoreExt;
+
+use crate::command_metrics;
+use crate::safe_state::SafeState;
+
+const TRAY_ACTIONS_STORE: &str = "tray_actions.json";
+
+/// Menu item ids of actions are prefixed so they never clash with built-in items
+const ACTION_ID_PREFIX: &str = "action:";
+
+const MAX_ACTIONS: usize = 20;
+
+/// A quick action in the tray menu
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrayAction {
+    pub id: String,
+    pub label: String,
+    /// Event emitted to the frontend when clicked
+    pub emit_event: String,
+    #[serde(default)]
+    pub payload: serde_json::Value,
+}
+
+/// Tauri event names may only contain letters, digits, '-', '/', ':' and '_'
+fn is_valid_event_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '/' | ':' | '_'))
+}
+
+pub struct TrayMenu {
+    actions: SafeState<Vec<TrayAction>>,
+    menu: SafeState<Option<Menu<Wry>>>,
+    /// Set when the menu is rebuilt, so the countdown re-applies its state
+    rebuilt: AtomicBool,
+    rebuilt_notify: tokio::sync::Notify,
+}
+
+impl TrayMenu {
+    pub fn new() -> Self {
+        Self {
+            actions: SafeState::new("tray_menu.actions", Vec::new()),
+            menu: SafeState::new("tray_menu.menu", None),
+            rebuilt: AtomicBool::new(false),
+            rebuilt_notify: tokio::sync::Notify::new(),
+        }
+    }
+
+    /// Read saved actions (invalid entries are skipped)
+    pub fn load(&self, app: &AppHandle) {
+        let actions: Vec<TrayAction> = app
+            .store(TRAY_ACTIONS_STORE)
+            .ok()
+            .and_then(|store| store.get("actions"))
+            .and_then(|value| serde_json::from_value(value).ok())
+            .unwrap_or_default();
+        let actions: Vec<TrayAction> = actions
+            .into_iter()
+            .filter(|action| !action.id.is_empty() && is_valid_event_name(&action.emit_event))
+            .take(MAX_ACTIONS)
+            .collect();
+        if !actions.is_empty() {
+            println!("📋 Loaded {} tray actions", actions.len());
+        }
+        self.actions.set(actions);
+    }
+
+    fn save(&self, app: &AppHandle) -> Result<(), String> {
+        let store = app.store(TRAY_ACTIONS_STORE)
+            .map_err(|e| format!("Failed to access store: {}", e))?;
+        store.set("actions", serde_json::json!(*self.actions.lock()));
+        store.save().map_err(|e| format!("Failed to save store: {}", e))
+    }
+
+    /// Build the tray menu (built-in items plus actions) and keep it for updates
+    pub fn build(&self, app: &AppHandle) -> tauri::Result<Menu<Wry>> {
+        let menu = Menu::new(app)?;
+        menu.append(&MenuItem::with_id(app, "countdown", "⏱️ Taskerino", false, None::<&str>)?)?;
+        menu.append(&MenuItem::with_id(app, "pause", "⏸ Pause Session", true, None::<&str>)?)?;
+        menu.append(&MenuItem::with_id(app, "resume", "▶️ Resume Session", true, None::<&str>)?)?;
+        menu.append(&MenuItem::with_id(app, "stop", "⏹ Stop Session", true, None::<&str>)?)?;
+
+        let actions = self.actions.get();
+        if !actions.is_empty() {
+            menu.append(&PredefinedMenuItem::separator(app)?)?;
+            for action in &actions {
+                let id = format!("{}{}", ACTION_ID_PREFIX, action.id);
+                menu.append(&MenuItem::with_id(app, id, &action.label, true, None::<&str>)?)?;
+            }
+            menu.append(&PredefinedMenuItem::separator(app)?)?;
+        }
+
+        menu.append(&MenuItem::with_id(app, "show", "Show Window", true, None::<&str>)?)?;
+        menu.append(&MenuItem::with_id(app, "hide", "Hide Window", true, None::<&str>)?)?;
+        menu.append(&MenuItem::with_id(app, "capture", "Quick Capture (⌘⇧Space)", true, None::<&str>)?)?;
+        menu.append(&MenuItem::with_id(app, "quit", "Quit Taskerino", true, None::<&str>)?)?;
+
+        self.menu.set(Some(menu.clone()));
+        Ok(menu)
+    }
+
+    /// Current tray menu (None until the tray is built)
+    pub fn menu(&self) -> Option<Menu<Wry>> {
+        self.menu.get()
+    }
+
+    /// Whether the menu was rebuilt since the last call
+    pub fn take_rebuilt(&self) -> bool {
+        self.rebuilt.swap(false, Ordering::SeqCst)
+    }
+
+    /// Resolves once the menu is rebuilt (immediately if it was since the last wait)
+    pub async fn rebuilt(&self) {
+        self.rebuilt_notify.notified().await
+    }
+
+    /// Rebuild the menu after the actions changed and put it on the tray
+    fn rebuild(&self, app: &AppHandle) -> Result<(), String> {
+        let menu = self.build(app).map_err(|e| format!("Failed to build tray menu: {}", e))?;
+        let tray = app.state::<Arc<SafeState<Option<TrayIcon<Wry>>>>>();
+        if let Some(tray) = tray.lock().as_ref() {
+            tray.set_menu(Some(menu))
+                .map_err(|e| format!("Failed to update tray menu: {}", e))?;
+        }
+        self.rebuilt.store(true, Ordering::SeqCst);
+        self.rebuilt_notify.notify_one();
+        Ok(())
+    }
+}
+
+/// Emit the event of a clicked action; false if `menu_id` isn't an action
+pub fn handle_menu_event(app: &AppHandle, menu_id: &str) -> bool {
+    let Some(id) = menu_id.strip_prefix(ACTION_ID_PREFIX) else {
+        return false;
+    };
+    let action = app
+        .state::<Arc<TrayMenu>>()
+        .actions
+        .lock()
+        .iter()
+        .find(|action| action.id == id)
+        .cloned();
+    if let Some(action) = action {
+        if let Err(e) = app.emit(&action.emit_event, action.payload) {
+            eprintln!("❌ Failed to emit tray action {}: {}", action.id, e);
+        }
+    }
+    true
+}
+
+/// Tauri command to add a quick action to the tray menu (replaces one with the same id)
+#[tauri::command]
+pub fn add_tray_action(
+    app: AppHandle,
+    tray_menu: State<'_, Arc<TrayMenu>>,
+    id: String,
+    label: String,
+    emit_event: String,
+    payload: Option<serde_json::Value>,
+) -> Result<(), String> {
+    command_metrics::track("add_tray_action", || {
+        if id.trim().is_empty() || label.trim().is_empty() {
+            return Err("Tray actions need an id and a label".to_string());
+        }
+        if !is_valid_event_name(&emit_event) {
+            return Err(format!(
+                "Invalid event name '{}' (letters, digits, '-', '/', ':' and '_' only)",
+                emit_event
+            ));
+        }
+
+        {
+            let mut actions = tray_menu.actions.lock();
+            let action = TrayAction {
+                id: id.clone(),
+                label,
+                emit_event,
+                payload: payload.unwrap_or(serde_json::Value::Null),
+            };
+            match actions.iter().position(|existing| existing.id == id) {
+                Some(index) => actions[index] = action,
+                None if actions.len() >= MAX_ACTIONS => {
+                    return Err(format!("At most {} tray actions are supported", MAX_ACTIONS));
+                }
+                None => actions.push(action),
+            }
+        }
+
+        tray_menu.save(&app)?;
+        tray_menu.rebuild(&app)
+    })
+}
+
+/// Tauri command to remove a quick action from the tray menu
+#[tauri::command]
+pub fn remove_tray_action(
+    app: AppHandle,
+    tray_menu: State<'_, Arc<TrayMenu>>,
+    id: String,
+) -> Result<bool, String> {
+    command_metrics::track("remove_tray_action", || {
+        let removed = {
+            let mut actions = tray_menu.actions.lock();
+            let before = actions.len();
+            actions.retain(|action| action.id != id);
+            actions.len() != before
+        };
+        if removed {
+            tray_menu.save(&app)?;
+            tray_menu.rebuild(&app)?;
+        }
+        Ok(removed)
+    })
+}
+
+/// Tauri command to list the tray's quick actions
+#[tauri::command]
+pub fn list_tray_actions(tray_menu: State<'_, Arc<TrayMenu>>) -> Result<Vec<TrayAction>, String> {
+    command_metrics::track("list_tray_actions", || Ok(tray_menu.actions.get()))
+}
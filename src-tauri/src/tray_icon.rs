@@ -0,0 +1,146 @@
+/**
+ * Tray Icon Module
+ *
+ * Renders the tray state into the icon itself instead of emoji in the tray
+ * title (which render inconsistently across menu bar setups):
+ * - idle: thin empty ring
+ * - active: ring filling up towards the next screenshot, time left inside
+ *   (minutes, seconds in the last minute)
+ * - paused: pause bars inside the ring
+ *
+ * Icons are template images (black + alpha) that macOS tints for light and
+ * dark menu bars.
+ */
+
+use ab_glyph::PxScale;
+use image::{Rgba, RgbaImage};
+use imageproc::drawing::{draw_text_mut, text_size};
+use std::f64::consts::TAU;
+use tauri::image::Image;
+
+use crate::annotation;
+
+/// 18pt menu bar icon at @2x
+const SIZE: u32 = 36;
+
+const OUTER_RADIUS: f64 = 17.0;
+const RING_WIDTH: f64 = 3.5;
+const IDLE_RING_WIDTH: f64 = 2.0;
+
+/// Alpha of the part of the ring not filled yet
+const TRACK_ALPHA: f64 = 0.3;
+
+/// Subsamples per axis for anti-aliasing the ring
+const SUPERSAMPLE: u32 = 4;
+
+/// Steps the active ring moves in, so the icon isn't re-rendered every tick
+pub const PROGRESS_STEPS: u8 = 36;
+
+/// What the tray icon shows
+#[derive(Debug, Clone, PartialEq)]
+pub enum TrayIconState {
+    Idle,
+    Active {
+        /// 0..=PROGRESS_STEPS of the interval elapsed
+        progress: u8,
+        /// Time left, e.g. "4m" or "35s"
+        label: String,
+    },
+    Paused,
+}
+
+impl TrayIconState {
+    /// Active state `remaining_ms` before the next screenshot of an `interval_ms` interval
+    pub fn countdown(remaining_ms: i64, interval_ms: i64) -> Self {
+        let elapsed = if interval_ms > 0 {
+            1.0 - remaining_ms.clamp(0, interval_ms) as f64 / interval_ms as f64
+        } else {
+            1.0
+        };
+        let remaining_secs = (remaining_ms.max(0) + 999) / 1000;
+        let label = match remaining_secs {
+            0 => String::new(),
+            1..=59 => format!("{}s", remaining_secs),
+            _ => format!("{}m", (remaining_secs + 59) / 60),
+        };
+        TrayIconState::Active {
+            progress: (elapsed * PROGRESS_STEPS as f64).floor() as u8,
+            label,
+        }
+    }
+}
+
+/// Alpha coverage of a ring `width` wide, filled clockwise from 12 o'clock
+/// up to `filled` (0..=1) and drawn at TRACK_ALPHA beyond that
+fn ring_alpha(x: u32, y: u32, width: f64, filled: f64) -> f64 {
+    let center = SIZE as f64 / 2.0;
+    let mut total = 0.0;
+    for sy in 0..SUPERSAMPLE {
+        for sx in 0..SUPERSAMPLE {
+            let px = x as f64 + (sx as f64 + 0.5) / SUPERSAMPLE as f64 - center;
+            let py = y as f64 + (sy as f64 + 0.5) / SUPERSAMPLE as f64 - center;
+            let distance = (px * px + py * py).sqrt();
+            if distance > OUTER_RADIUS || distance < OUTER_RADIUS - width {
+                continue;
+            }
+            // Clockwise angle from 12 o'clock, as a fraction of a turn
+            let turn = (px.atan2(-py).rem_euclid(TAU)) / TAU;
+            total += if turn <= filled { 1.0 } else { TRACK_ALPHA };
+        }
+    }
+    total / (SUPERSAMPLE * SUPERSAMPLE) as f64
+}
+
+fn draw_ring(canvas: &mut RgbaImage, width: f64, filled: f64) {
+    for (x, y, pixel) in canvas.enumerate_pixels_mut() {
+        let alpha = ring_alpha(x, y, width, filled);
+        if alpha > 0.0 {
+            *pixel = Rgba([0, 0, 0, (alpha * 255.0).round() as u8]);
+        }
+    }
+}
+
+fn draw_centered_text(canvas: &mut RgbaImage, text: &str) {
+    let Ok(font) = annotation::font() else { return };
+    let mut scale = PxScale::from(14.0);
+    let (mut width, mut height) = text_size(scale, font, text);
+    let max_width = (2.0 * (OUTER_RADIUS - RING_WIDTH) - 4.0) as u32;
+    if width > max_width {
+        scale = PxScale::from(14.0 * max_width as f32 / width as f32);
+        (width, height) = text_size(scale, font, text);
+    }
+    let x = (SIZE as i32 - width as i32) / 2;
+    let y = (SIZE as i32 - height as i32) / 2;
+    draw_text_mut(canvas, Rgba([0, 0, 0, 255]), x, y, scale, font, text);
+}
+
+fn draw_pause_bars(canvas: &mut RgbaImage) {
+    let (bar_width, bar_height, gap) = (4, 12, 4);
+    let top = (SIZE - bar_height) / 2;
+    let left = (SIZE - 2 * bar_width - gap) / 2;
+    for x in (left..left + bar_width).chain(left + bar_width + gap..left + 2 * bar_width + gap) {
+        for y in top..top + bar_height {
+            canvas.put_pixel(x, y, Rgba([0, 0, 0, 255]));
+        }
+    }
+}
+
+/// Render the tray icon for `state` (set it as a template image)
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub fn render(state: &TrayIconState) -> Image<'static> {
+    let mut canvas = RgbaImage::new(SIZE, SIZE);
+    match state {
+        TrayIconState::Idle => draw_ring(&mut canvas, IDLE_RING_WIDTH, 1.0),
+        TrayIconState::Active { progress, label } => {
+            draw_ring(&mut canvas, RING_WIDTH, *progress as f64 / PROGRESS_STEPS as f64);
+            if !label.is_empty() {
+                draw_centered_text(&mut canvas, label);
+            }
+        }
+        TrayIconState::Paused => {
+            draw_ring(&mut canvas, RING_WIDTH, 0.0);
+            draw_pause_bars(&mut canvas);
+        }
+    }
+    Image::new_owned(canvas.into_raw(), SIZE, SIZE)
+}
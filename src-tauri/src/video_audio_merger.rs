@@ -0,0 +1,531 @@
+/**
+ * Video/Audio Merger Module
+ *
+ * Queue for muxing a recorded video with its audio track(s) into one file:
+ * - `merge_video_and_audio` queues a merge and returns its job id right away
+ * - The "video-audio-merger" background task runs one merge at a time (so
+ *   merges don't contend for CPU with each other or the recording), highest
+ *   priority first, then oldest first
+ * - Merges run ffmpeg (see media_tools.rs): video stream copied, audio
+ *   encoded to AAC, several audio files mixed. Output is written to a hidden
+ *   temp file next to the target and renamed into place when complete.
+ * - Every state change and progress update (with an ETA, at most every
+ *   PROGRESS_INTERVAL) is emitted as `merge-progress`; `get_merge_progress`
+ *   and `list_merge_jobs` return the same shape
+ * - `cancel_merge` drops a queued merge or kills a running one and removes
+ *   its partial output
+ */
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, State};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+use tokio::sync::Notify;
+
+use crate::background_tasks::{ShutdownSignal, TaskRegistry};
+use crate::command_metrics;
+use crate::media_tools;
+use crate::safe_state::SafeState;
+
+const TASK_NAME: &str = "video-audio-merger";
+
+/// Minimum time between `merge-progress` events of a running merge
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Finished merges kept for `get_merge_progress` / `list_merge_jobs`
+const MAX_FINISHED_MERGES: usize = 20;
+
+/// Audio bitrate of merged files
+const AUDIO_BITRATE: &str = "192k";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MergePriority {
+    /// Housekeeping (e.g. re-merging old sessions)
+    Low,
+    #[default]
+    Normal,
+    /// The user is waiting for the result
+    High,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MergeStatus {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+/// A merge request from the frontend
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeRequest {
+    pub video_path: String,
+    /// Mixed together when there is more than one
+    pub audio_paths: Vec<String>,
+    pub output_path: String,
+    #[serde(default)]
+    pub priority: MergePriority,
+    #[serde(default)]
+    pub session_id: Option<String>,
+}
+
+/// State of one merge - the `merge-progress` event payload
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeJob {
+    pub job_id: String,
+    pub session_id: Option<String>,
+    pub video_path: String,
+    pub audio_paths: Vec<String>,
+    pub output_path: String,
+    pub priority: MergePriority,
+    pub status: MergeStatus,
+    /// 0.0 - 1.0 (stays 0 if the input duration couldn't be probed)
+    pub progress: f64,
+    /// Estimated seconds until a running merge completes
+    pub eta_seconds: Option<f64>,
+    pub enqueued_at: String,
+    pub started_at: Option<String>,
+    pub finished_at: Option<String>,
+    pub error: Option<String>,
+}
+
+/// How a merge ended
+enum Outcome {
+    Completed,
+    Cancelled,
+    Failed(String),
+}
+
+/// Managed merge queue
+pub struct MergeQueue {
+    jobs: SafeState<Vec<MergeJob>>,
+    /// Cancellation of the running merge (job id, signal)
+    running: SafeState<Option<(String, Arc<Notify>)>>,
+    changed: Notify,
+    next_id: AtomicU64,
+}
+
+impl MergeQueue {
+    pub fn new() -> Self {
+        Self {
+            jobs: SafeState::new("merge_queue", Vec::new()),
+            running: SafeState::new("merge_queue.running", None),
+            changed: Notify::new(),
+            next_id: AtomicU64::new(0),
+        }
+    }
+
+    /// Apply a change to a merge and emit its new state
+    fn update(&self, app: &AppHandle, id: &str, apply: impl FnOnce(&mut MergeJob)) {
+        let snapshot = {
+            let mut jobs = self.jobs.lock();
+            let Some(job) = jobs.iter_mut().find(|job| job.job_id == id) else { return };
+            apply(job);
+            job.clone()
+        };
+        let _ = app.emit("merge-progress", &snapshot);
+    }
+
+    fn enqueue(&self, app: &AppHandle, request: MergeRequest) -> Result<String, String> {
+        let job = {
+            let mut jobs = self.jobs.lock();
+            let output_taken = jobs.iter().any(|job| {
+                matches!(job.status, MergeStatus::Queued | MergeStatus::Running) && job.output_path == request.output_path
+            });
+            if output_taken {
+                return Err(format!("A merge into {} is already queued", request.output_path));
+            }
+
+            let job = MergeJob {
+                job_id: format!(
+                    "merge-{}-{}",
+                    chrono::Utc::now().timestamp_millis(),
+                    self.next_id.fetch_add(1, Ordering::SeqCst)
+                ),
+                session_id: request.session_id,
+                video_path: request.video_path,
+                audio_paths: request.audio_paths,
+                output_path: request.output_path,
+                priority: request.priority,
+                status: MergeStatus::Queued,
+                progress: 0.0,
+                eta_seconds: None,
+                enqueued_at: chrono::Utc::now().to_rfc3339(),
+                started_at: None,
+                finished_at: None,
+                error: None,
+            };
+            jobs.push(job.clone());
+            job
+        };
+
+        println!("🎬 [MERGE] Queued {} ({:?} priority)", job.job_id, job.priority);
+        let _ = app.emit("merge-progress", &job);
+        self.changed.notify_one();
+        Ok(job.job_id)
+    }
+
+    /// Mark the next merge (highest priority, then oldest) as running
+    fn claim_next(&self, app: &AppHandle) -> Option<(MergeJob, Arc<Notify>)> {
+        let cancel = Arc::new(Notify::new());
+        let job = {
+            let mut jobs = self.jobs.lock();
+            let job = jobs
+                .iter_mut()
+                .filter(|job| job.status == MergeStatus::Queued)
+                .min_by(|a, b| b.priority.cmp(&a.priority).then_with(|| a.enqueued_at.cmp(&b.enqueued_at)))?;
+            job.status = MergeStatus::Running;
+            job.started_at = Some(chrono::Utc::now().to_rfc3339());
+            self.running.set(Some((job.job_id.clone(), cancel.clone())));
+            job.clone()
+        };
+        let _ = app.emit("merge-progress", &job);
+        Some((job, cancel))
+    }
+
+    fn finish(&self, app: &AppHandle, id: &str, outcome: Outcome) {
+        self.running.set(None);
+        self.update(app, id, |job| {
+            job.finished_at = Some(chrono::Utc::now().to_rfc3339());
+            job.eta_seconds = None;
+            match outcome {
+                Outcome::Completed => {
+                    job.status = MergeStatus::Completed;
+                    job.progress = 1.0;
+                }
+                Outcome::Cancelled => job.status = MergeStatus::Cancelled,
+                Outcome::Failed(e) => {
+                    job.status = MergeStatus::Failed;
+                    job.error = Some(e);
+                }
+            }
+        });
+        if let Some(job) = self.get(id) {
+            match job.status {
+                MergeStatus::Failed => eprintln!("❌ [MERGE] {} failed: {}", id, job.error.as_deref().unwrap_or("")),
+                status => println!("🎬 [MERGE] {} {:?}", id, status),
+            }
+        }
+        self.prune();
+    }
+
+    /// Keep only the most recent finished merges
+    fn prune(&self) {
+        let mut jobs = self.jobs.lock();
+        let finished = jobs
+            .iter()
+            .filter(|job| !matches!(job.status, MergeStatus::Queued | MergeStatus::Running))
+            .count();
+        let mut excess = finished.saturating_sub(MAX_FINISHED_MERGES);
+        // Oldest first (jobs are kept in enqueue order)
+        jobs.retain(|job| {
+            let drop = excess > 0 && !matches!(job.status, MergeStatus::Queued | MergeStatus::Running);
+            if drop {
+                excess -= 1;
+            }
+            !drop
+        });
+    }
+
+    fn get(&self, id: &str) -> Option<MergeJob> {
+        self.jobs.lock().iter().find(|job| job.job_id == id).cloned()
+    }
+
+    /// Running merge, then queued merges in execution order, then finished ones (newest first)
+    fn list(&self) -> Vec<MergeJob> {
+        let mut jobs = self.jobs.get();
+        let rank = |status: MergeStatus| match status {
+            MergeStatus::Running => 0,
+            MergeStatus::Queued => 1,
+            _ => 2,
+        };
+        jobs.sort_by(|a, b| {
+            rank(a.status).cmp(&rank(b.status)).then_with(|| match a.status {
+                MergeStatus::Queued => b.priority.cmp(&a.priority).then_with(|| a.enqueued_at.cmp(&b.enqueued_at)),
+                _ => b.enqueued_at.cmp(&a.enqueued_at),
+            })
+        });
+        jobs
+    }
+
+    /// Cancel a queued or running merge; returns false if it is unknown or already finished
+    fn cancel(&self, app: &AppHandle, id: &str) -> bool {
+        let dequeued = {
+            let mut jobs = self.jobs.lock();
+            let Some(job) = jobs.iter_mut().find(|job| job.job_id == id) else { return false };
+            match job.status {
+                MergeStatus::Queued => {
+                    job.status = MergeStatus::Cancelled;
+                    job.finished_at = Some(chrono::Utc::now().to_rfc3339());
+                    Some(job.clone())
+                }
+                MergeStatus::Running => None,
+                _ => return false,
+            }
+        };
+
+        match dequeued {
+            Some(job) => {
+                println!("🎬 [MERGE] {} cancelled before it started", id);
+                let _ = app.emit("merge-progress", &job);
+                self.prune();
+            }
+            None => {
+                if let Some((_, cancel)) = self.running.get().filter(|(running_id, _)| running_id == id) {
+                    println!("🎬 [MERGE] Cancelling {}", id);
+                    cancel.notify_one();
+                }
+            }
+        }
+        true
+    }
+
+    /// Start the worker as the "video-audio-merger" background task
+    pub fn start(self: &Arc<Self>, app: AppHandle, registry: &TaskRegistry) -> Result<(), String> {
+        let queue = self.clone();
+        registry.spawn(TASK_NAME, |mut shutdown| async move {
+            loop {
+                if let Some((job, cancel)) = queue.claim_next(&app) {
+                    let outcome = run_merge(&app, &queue, &job, &cancel, &mut shutdown).await;
+                    queue.finish(&app, &job.job_id, outcome);
+                    if shutdown.is_cancelled() {
+                        break;
+                    }
+                    continue;
+                }
+
+                tokio::select! {
+                    _ = shutdown.cancelled() => break,
+                    _ = queue.changed.notified() => {}
+                }
+            }
+            println!("🛑 [MERGE] Merge worker exiting");
+        })
+    }
+}
+
+impl Default for MergeQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Duration of a media file in seconds (ffprobe)
+async fn probe_duration(ffprobe: &Path, path: &str) -> Option<f64> {
+    let output = tokio::process::Command::new(ffprobe)
+        .args(["-v", "error", "-show_entries", "format=duration", "-of", "default=noprint_wrappers=1:nokey=1"])
+        .arg(path)
+        .output()
+        .await
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+}
+
+/// Hidden file next to the output that ffmpeg writes to (same extension, so
+/// ffmpeg picks the same container)
+fn temp_output_path(output: &Path) -> PathBuf {
+    let name = output.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+    output.with_file_name(format!(".merging-{}", name))
+}
+
+fn ffmpeg_command(ffmpeg: &Path, job: &MergeJob, temp_output: &Path) -> tokio::process::Command {
+    let mut command = tokio::process::Command::new(ffmpeg);
+    command
+        .args(["-y", "-hide_banner", "-nostats", "-v", "error", "-progress", "pipe:1"])
+        .arg("-i")
+        .arg(&job.video_path);
+    for audio in &job.audio_paths {
+        command.arg("-i").arg(audio);
+    }
+    command.args(["-map", "0:v:0"]);
+    if job.audio_paths.len() == 1 {
+        command.args(["-map", "1:a:0"]);
+    } else {
+        let inputs: String = (1..=job.audio_paths.len()).map(|index| format!("[{}:a]", index)).collect();
+        let filter = format!("{}amix=inputs={}:duration=longest[audio]", inputs, job.audio_paths.len());
+        command.args(["-filter_complex", &filter, "-map", "[audio]"]);
+    }
+    command
+        .args(["-c:v", "copy", "-c:a", "aac", "-b:a", AUDIO_BITRATE, "-movflags", "+faststart"])
+        .arg(temp_output)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    command
+}
+
+/// Run one merge, reporting progress until ffmpeg exits or the merge is cancelled
+async fn run_merge(
+    app: &AppHandle,
+    queue: &MergeQueue,
+    job: &MergeJob,
+    cancel: &Notify,
+    shutdown: &mut ShutdownSignal,
+) -> Outcome {
+    let output = PathBuf::from(&job.output_path);
+    let temp_output = temp_output_path(&output);
+
+    let tools = tokio::task::spawn_blocking(|| (media_tools::ffmpeg(), media_tools::ffprobe())).await;
+    let Ok((Some(ffmpeg), ffprobe)) = tools else {
+        return Outcome::Failed("Merging needs ffmpeg, which is neither bundled nor installed".to_string());
+    };
+
+    // The merged file is as long as the longest input
+    let mut duration: Option<f64> = None;
+    if let Some(ffprobe) = &ffprobe {
+        for path in std::iter::once(&job.video_path).chain(&job.audio_paths) {
+            if let Some(seconds) = probe_duration(ffprobe, path).await {
+                duration = Some(duration.map_or(seconds, |longest| longest.max(seconds)));
+            }
+        }
+    }
+
+    let mut child = match ffmpeg_command(&ffmpeg, job, &temp_output).spawn() {
+        Ok(child) => child,
+        Err(e) => return Outcome::Failed(format!("Failed to start ffmpeg: {}", e)),
+    };
+
+    // ffmpeg only writes errors to stderr (-v error); collect them for the failure message
+    let mut stderr = child.stderr.take();
+    let stderr_reader = tokio::spawn(async move {
+        let mut text = String::new();
+        if let Some(stderr) = stderr.as_mut() {
+            let _ = stderr.read_to_string(&mut text).await;
+        }
+        text
+    });
+
+    let mut lines = child.stdout.take().map(|stdout| BufReader::new(stdout).lines());
+    let started = Instant::now();
+    let mut last_emit: Option<Instant> = None;
+
+    let cancelled = loop {
+        tokio::select! {
+            _ = cancel.notified() => break true,
+            _ = shutdown.cancelled() => break true,
+            line = async { lines.as_mut()?.next_line().await.ok().flatten() } => {
+                let Some(line) = line else { break false };
+
+                // `-progress` reports key=value lines; out_time_us is the output position
+                let Some(position) = line
+                    .strip_prefix("out_time_us=")
+                    .and_then(|value| value.trim().parse::<f64>().ok())
+                    .map(|micros| micros / 1_000_000.0)
+                else {
+                    continue;
+                };
+                let Some(total) = duration.filter(|total| *total > 0.0) else { continue };
+                if last_emit.is_some_and(|last| last.elapsed() < PROGRESS_INTERVAL) {
+                    continue;
+                }
+                last_emit = Some(Instant::now());
+
+                let progress = (position / total).clamp(0.0, 0.999);
+                let elapsed = started.elapsed().as_secs_f64();
+                let eta = (progress > 0.01).then(|| elapsed * (1.0 - progress) / progress);
+                queue.update(app, &job.job_id, |job| {
+                    job.progress = progress;
+                    job.eta_seconds = eta;
+                });
+            }
+        }
+    };
+
+    if cancelled {
+        let _ = child.kill().await;
+        let _ = tokio::fs::remove_file(&temp_output).await;
+        return Outcome::Cancelled;
+    }
+
+    let status = child.wait().await;
+    let errors = stderr_reader.await.unwrap_or_default();
+    let failure = match status {
+        Ok(status) if status.success() => None,
+        Ok(status) => Some(format!("ffmpeg exited with {}: {}", status, errors.trim())),
+        Err(e) => Some(format!("Failed to wait for ffmpeg: {}", e)),
+    };
+    if let Some(error) = failure {
+        let _ = tokio::fs::remove_file(&temp_output).await;
+        return Outcome::Failed(error);
+    }
+
+    match tokio::fs::rename(&temp_output, &output).await {
+        Ok(()) => Outcome::Completed,
+        Err(e) => {
+            let _ = tokio::fs::remove_file(&temp_output).await;
+            Outcome::Failed(format!("Failed to move merged file to {:?}: {}", output, e))
+        }
+    }
+}
+
+fn validate(request: &MergeRequest) -> Result<(), String> {
+    if !Path::new(&request.video_path).is_file() {
+        return Err(format!("Video not found: {}", request.video_path));
+    }
+    if request.audio_paths.is_empty() {
+        return Err("No audio files to merge".to_string());
+    }
+    if let Some(missing) = request.audio_paths.iter().find(|path| !Path::new(path).is_file()) {
+        return Err(format!("Audio file not found: {}", missing));
+    }
+    let output = Path::new(&request.output_path);
+    if !output.parent().is_some_and(|parent| parent.is_dir()) {
+        return Err(format!("Output folder doesn't exist: {}", request.output_path));
+    }
+    if std::iter::once(&request.video_path).chain(&request.audio_paths).any(|input| input == &request.output_path) {
+        return Err("Output path must differ from the inputs".to_string());
+    }
+    Ok(())
+}
+
+/// Tauri command: queue merging a video with its audio; returns the job id
+/// (follow it with `merge-progress` events)
+#[tauri::command]
+pub fn merge_video_and_audio(
+    app: AppHandle,
+    queue: State<Arc<MergeQueue>>,
+    request: MergeRequest,
+) -> Result<String, String> {
+    command_metrics::track("merge_video_and_audio", || {
+        validate(&request)?;
+        queue.enqueue(&app, request)
+    })
+}
+
+/// Tauri command: current state of a merge
+#[tauri::command]
+pub fn get_merge_progress(queue: State<Arc<MergeQueue>>, job_id: String) -> Result<MergeJob, String> {
+    command_metrics::track("get_merge_progress", || {
+        queue.get(&job_id).ok_or_else(|| format!("Unknown merge job: {}", job_id))
+    })
+}
+
+/// Tauri command: running, queued (in execution order) and recently finished merges
+#[tauri::command]
+pub fn list_merge_jobs(queue: State<Arc<MergeQueue>>) -> Result<Vec<MergeJob>, String> {
+    command_metrics::track("list_merge_jobs", || {
+        Ok(queue.list())
+    })
+}
+
+/// Tauri command: cancel a queued or running merge; returns false if it
+/// already finished
+#[tauri::command]
+pub fn cancel_merge(app: AppHandle, queue: State<Arc<MergeQueue>>, job_id: String) -> Result<bool, String> {
+    command_metrics::track("cancel_merge", || {
+        Ok(queue.cancel(&app, &job_id))
+    })
+}
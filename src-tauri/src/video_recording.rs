@@ -3,6 +3,46 @@
  *
  * Captures screen recordings during sessions using ScreenCaptureKit (via Swift FFI).
  *
+ * Pausing finalizes the current segment file and resuming starts a new one, so
+ * paused periods are not recorded at all. Stopping stitches the segments
+ * (<output stem>.seg<N>.mp4) into the requested output file with
+ * AVFoundation (passthrough, no re-encode) and deletes them.
+ *
+ * Adaptive frame rate (`set_adaptive_framerate`): while ActivityMonitor has
+ * seen no input for IDLE_AFTER, Swift keeps only `idle_fps` frames per second
+ * and goes back to the full rate on the next input event. Frames carry their
+ * capture time, so the video still plays back in real time.
+ *
+ * Display loss: when the recorded display is unplugged (CGDisplay
+ * reconfiguration callback from Swift), the current segment is finalized,
+ * `recording-display-lost` is emitted and recording continues in a new
+ * segment on the main display (`recording-display-switched`). `switch_display`
+ * moves a recording to another display the same way.
+ *
+ * Multi-source recording (`start_multi_source_recording`): each display gets
+ * its own recorder and file (the first one is the session's recording, the
+ * others `<stem>.display<id>.mp4`); pause, idle and blanking apply to all.
+ * Stopping writes `<stem>.displays.json` linking the files to the session
+ * (`get_display_recordings`). An unplugged secondary display just ends its file.
+ *
+ * Overlay (`RecordingOverlay`, optional on both start commands): a label
+ * (e.g. the session name), the wall-clock time and/or a PNG logo are burned
+ * into the frames in a corner of the video.
+ *
+ * Rolling segments (`set_segment_duration`): every N minutes the current
+ * segment is finalized and recording continues in the next one, so a crash
+ * loses at most the segment being written (recovery.rs stitches the rest).
+ * `<stem>.segments.json` lists the segments while recording; once they are
+ * stitched it gives each one's offset in the final file
+ * (`get_recording_segments`), so playback/export can pick single segments.
+ *
+ * Size limit (recording_limits.rs): `roll_over` finishes the current file and
+ * continues into `<stem>.part<N>.mp4`; `auto_stop` stops like `stop_recording`
+ * and keeps the finished path for the frontend's next `stop_video_recording`.
+ *
+ * `extract_video_clip` exports part of a session's recording as an MP4 (stream
+ * copy when the clip starts on a keyframe, re-encoded otherwise).
+ *
  * **Implementation Status**: Functional via Swift ScreenRecorder module
  * **Platform**: macOS 12.3+ only
  */
@@ -10,8 +50,31 @@
 use std::ffi::CString;
 use std::os::raw::c_char;
 use std::path::PathBuf;
-use std::sync::{Arc, Mutex};
-use tauri::State;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, State};
+
+use crate::activity_monitor::ActivityMonitor;
+use crate::background_tasks::TaskRegistry;
+use crate::command_metrics;
+use crate::disk_space;
+use crate::profiles;
+use crate::remote_archive;
+use crate::session_storage;
+use crate::safe_state::SafeState;
+
+const ADAPTIVE_TASK_NAME: &str = "adaptive-framerate";
+const ADAPTIVE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// No input for this long counts as idle
+const IDLE_AFTER: Duration = Duration::from_secs(10);
+
+const DEFAULT_IDLE_FPS: u32 = 2;
+const MAX_IDLE_FPS: u32 = 5;
+
+const SEGMENT_TASK_NAME: &str = "video-segment-rotation";
+const SEGMENT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+const MAX_SEGMENT_MINUTES: u32 = 120;
 
 // FFI declarations for Swift functions
 #[cfg(target_os = "macos")]
@@ -23,14 +86,85 @@ extern "C" {
         width: i32,
         height: i32,
         fps: i32,
+        codec: i32,
+        bitrate: i32,
+        display_id: u32,
     ) -> bool;
+    fn screen_recorder_display_id(recorder: *mut std::ffi::c_void) -> u32;
+    fn display_monitor_install(handler: extern "C" fn(u32, u32));
     fn screen_recorder_stop(recorder: *mut std::ffi::c_void) -> bool;
     fn screen_recorder_is_recording(recorder: *mut std::ffi::c_void) -> bool;
+    fn screen_recorder_set_idle(recorder: *mut std::ffi::c_void, idle: bool, idle_fps: i32);
+    fn screen_recorder_set_blanked(recorder: *mut std::ffi::c_void, blanked: bool);
+    fn screen_recorder_set_overlay(
+        recorder: *mut std::ffi::c_void,
+        label: *const c_char,
+        timestamp: bool,
+        logo_path: *const c_char,
+        position: i32,
+    ) -> bool;
+    fn screen_recorder_show_keystroke(recorder: *mut std::ffi::c_void, text: *const c_char);
+    fn screen_recorder_frame_stats(recorder: *mut std::ffi::c_void, written: *mut i64, dropped: *mut i64, busy_nanos: *mut i64);
+    fn screen_recorder_last_error(recorder: *mut std::ffi::c_void) -> *const c_char;
     fn screen_recorder_destroy(recorder: *mut std::ffi::c_void);
     fn screen_recorder_check_permission() -> bool;
     fn screen_recorder_request_permission();
     fn screen_recorder_get_duration(path: *const c_char) -> f64;
     fn screen_recorder_generate_thumbnail(path: *const c_char, time: f64) -> *const c_char;
+    fn screen_recorder_concat_segments(paths: *const c_char, output: *const c_char) -> bool;
+    fn screen_recorder_extract_clip(path: *const c_char, output: *const c_char, start: f64, end: f64) -> i32;
+}
+
+/// HEVC bitrate per pixel per frame at the balanced preset (1.2 Mbps at 720p/15fps)
+const BALANCED_BITS_PER_PIXEL: f64 = 0.087;
+
+/// Lowest bitrate any preset goes down to
+const MIN_BITRATE: f64 = 250_000.0;
+
+/// Video codec; encoding uses VideoToolbox (hardware where available)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VideoCodec {
+    /// HEVC when the encoder supports it, otherwise H.264
+    #[default]
+    Auto,
+    H264,
+    /// Roughly half the size of H.264 at the same quality (falls back to H.264 if unavailable)
+    Hevc,
+}
+
+impl VideoCodec {
+    /// Value passed to Swift (`screen_recorder_start`)
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    fn ffi_value(self) -> i32 {
+        match self {
+            VideoCodec::Auto => 0,
+            VideoCodec::H264 => 1,
+            VideoCodec::Hevc => 2,
+        }
+    }
+}
+
+/// Bitrate preset, scaled by resolution and frame rate
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QualityPreset {
+    /// Smallest files; text stays readable
+    Low,
+    #[default]
+    Balanced,
+    /// For recordings that will be watched closely
+    High,
+}
+
+impl QualityPreset {
+    fn bitrate_factor(self) -> f64 {
+        match self {
+            QualityPreset::Low => 0.6,
+            QualityPreset::Balanced => 1.0,
+            QualityPreset::High => 2.0,
+        }
+    }
 }
 
 /// Video quality settings
@@ -39,6 +173,20 @@ pub struct VideoQuality {
     pub width: u32,
     pub height: u32,
     pub fps: u32,
+    #[serde(default)]
+    pub codec: VideoCodec,
+    #[serde(default)]
+    pub preset: QualityPreset,
+}
+
+impl VideoQuality {
+    /// Target average bitrate (bits/s) for HEVC; Swift doubles it when encoding H.264
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    fn bitrate(&self) -> i32 {
+        let pixels_per_second = f64::from(self.width) * f64::from(self.height) * f64::from(self.fps);
+        let bitrate = pixels_per_second * BALANCED_BITS_PER_PIXEL * self.preset.bitrate_factor();
+        bitrate.clamp(MIN_BITRATE, i32::MAX as f64 / 2.0) as i32
+    }
 }
 
 impl Default for VideoQuality {
@@ -47,7 +195,65 @@ impl Default for VideoQuality {
             width: 1280,  // 720p
             height: 720,
             fps: 15,      // Good balance for filesize/quality
+            codec: VideoCodec::default(),
+            preset: QualityPreset::default(),
+        }
+    }
+}
+
+/// Corner of the frame the overlay is drawn in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OverlayPosition {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    #[default]
+    BottomRight,
+}
+
+impl OverlayPosition {
+    /// Value passed to Swift (`screen_recorder_set_overlay`)
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    fn ffi_value(self) -> i32 {
+        match self {
+            OverlayPosition::TopLeft => 0,
+            OverlayPosition::TopRight => 1,
+            OverlayPosition::BottomLeft => 2,
+            OverlayPosition::BottomRight => 3,
+        }
+    }
+}
+
+/// Watermark burned into the recorded video (Core Image compositor in Swift)
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingOverlay {
+    /// Text shown in the overlay, typically the session name
+    #[serde(default)]
+    pub label: Option<String>,
+    /// Show the wall-clock time (updated every second)
+    #[serde(default)]
+    pub timestamp: bool,
+    /// PNG drawn above the text, scaled to 8% of the frame height
+    #[serde(default)]
+    pub logo_path: Option<String>,
+    #[serde(default)]
+    pub position: OverlayPosition,
+}
+
+impl RecordingOverlay {
+    fn validate(&self) -> Result<(), String> {
+        if let Some(logo) = &self.logo_path {
+            let path = std::path::Path::new(logo);
+            if !path.is_file() {
+                return Err(format!("Overlay logo not found: {}", logo));
+            }
+            if !path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("png")) {
+                return Err("Overlay logo must be a PNG".to_string());
+            }
         }
+        Ok(())
     }
 }
 
@@ -55,13 +261,233 @@ impl Default for VideoQuality {
 pub struct VideoRecorder {
     #[cfg(target_os = "macos")]
     swift_recorder: Option<*mut std::ffi::c_void>,
-    current_session_id: Arc<Mutex<Option<String>>>,
-    output_path: Arc<Mutex<Option<PathBuf>>>,
+    current_session_id: Option<String>,
+    output_path: Option<PathBuf>,
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    quality: VideoQuality,
+    /// Segment files written so far (the last one is being recorded unless paused)
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    segments: Vec<PathBuf>,
+    paused: bool,
+    /// Frame rate while idle; None when adaptive frame rate is off
+    adaptive_idle_fps: Option<u32>,
+    idle: bool,
+    /// Frames replaced with black ones (privacy blocklist)
+    blanked: bool,
+    /// CGDirectDisplayID being recorded; None = main display
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    display_id: Option<u32>,
+    /// Burned into every frame while set
+    overlay: Option<RecordingOverlay>,
+    /// Other displays of a multi-source recording, each writing its own file
+    secondary: Vec<VideoRecorder>,
+    /// Secondary displays already finished (unplugged mid-recording)
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    finished_sources: Vec<DisplayRecording>,
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    started_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Files finished by `roll_over`, oldest first
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    parts: Vec<PathBuf>,
+    /// Output of a recording stopped by `auto_stop`, until the frontend stops it too
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    auto_stopped: Option<PathBuf>,
+    /// Length of rolling segments; None = a new segment only on pause/resume
+    segment_duration: Option<Duration>,
+    /// When the segment being recorded was started
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    segment_started: Option<Instant>,
+    /// One entry per file in `segments`
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    segment_log: Vec<SegmentInfo>,
+    /// Counters of the segments already finished
+    finished_frames: FrameStats,
+}
+
+/// Frame counters of a recording, summed over its segments (and displays)
+#[derive(Debug, Clone, Default, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FrameStats {
+    pub frames_written: u64,
+    /// Frames lost because the encoder was behind or failed
+    pub frames_dropped: u64,
+    /// Time spent processing frames on the capture queue
+    pub busy_nanos: u64,
+    pub last_error: Option<String>,
+}
+
+impl FrameStats {
+    fn add(&mut self, other: FrameStats) {
+        self.frames_written += other.frames_written;
+        self.frames_dropped += other.frames_dropped;
+        self.busy_nanos += other.busy_nanos;
+        if other.last_error.is_some() {
+            self.last_error = other.last_error;
+        }
+    }
+}
+
+/// One display's file in a multi-source recording
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisplayRecording {
+    pub display_id: u32,
+    pub path: String,
+    /// The file used as the session's recording
+    pub primary: bool,
+}
+
+/// `<stem>.displays.json` written next to the primary file when a
+/// multi-source recording stops
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingManifest {
+    pub session_id: String,
+    pub started_at: String,
+    pub stopped_at: String,
+    pub displays: Vec<DisplayRecording>,
+}
+
+/// One segment in `<stem>.segments.json`
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SegmentInfo {
+    pub index: usize,
+    /// The segment file (gone once the manifest is `stitched`)
+    pub path: String,
+    pub started_at: String,
+    /// None while it is being recorded
+    pub ended_at: Option<String>,
+    pub duration_secs: Option<f64>,
+    /// Start of the segment in the stitched file
+    pub offset_secs: Option<f64>,
+}
+
+/// `<stem>.segments.json`: the segments of a rolling-segment recording
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SegmentManifest {
+    pub session_id: String,
+    /// Rolling segment length (0 = segments only from pause/resume)
+    pub segment_minutes: u32,
+    pub output: String,
+    /// Segments were joined into `output`; `offsetSecs` is set
+    pub stitched: bool,
+    pub segments: Vec<SegmentInfo>,
+}
+
+/// Counters of one Swift recorder
+#[cfg(target_os = "macos")]
+fn swift_frame_stats(recorder: *mut std::ffi::c_void) -> FrameStats {
+    use std::ffi::CStr;
+
+    let (mut written, mut dropped, mut busy_nanos) = (0i64, 0i64, 0i64);
+    unsafe { screen_recorder_frame_stats(recorder, &mut written, &mut dropped, &mut busy_nanos) };
+
+    let error_ptr = unsafe { screen_recorder_last_error(recorder) };
+    let last_error = (!error_ptr.is_null()).then(|| unsafe {
+        let error = CStr::from_ptr(error_ptr).to_string_lossy().into_owned();
+        // Allocated by Swift's strdup
+        libc::free(error_ptr as *mut libc::c_void);
+        error
+    });
+
+    FrameStats {
+        frames_written: written.max(0) as u64,
+        frames_dropped: dropped.max(0) as u64,
+        busy_nanos: busy_nanos.max(0) as u64,
+        last_error,
+    }
+}
+
+/// `<dir>/<stem>.seg<index>.mp4` next to the final output
+#[cfg(target_os = "macos")]
+fn segment_path(output_path: &std::path::Path, index: usize) -> PathBuf {
+    let stem = output_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "recording".to_string());
+    output_path.with_file_name(format!("{}.seg{}.mp4", stem, index))
+}
+
+/// `<dir>/<stem>.display<id>.mp4`: a secondary display's file in a multi-source recording
+#[cfg(target_os = "macos")]
+fn display_output_path(output_path: &std::path::Path, display_id: u32) -> PathBuf {
+    let stem = output_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "recording".to_string());
+    output_path.with_file_name(format!("{}.display{}.mp4", stem, display_id))
+}
+
+/// `<dir>/<stem>.part<number>.mp4`: the next file of a recording rolled over at the size limit
+#[cfg(target_os = "macos")]
+fn part_path(output_path: &std::path::Path, number: usize) -> PathBuf {
+    let stem = output_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "recording".to_string());
+    output_path.with_file_name(format!("{}.part{}.mp4", stem, number))
+}
+
+/// `<dir>/<stem>.displays.json` next to the primary file
+fn manifest_path(output_path: &std::path::Path) -> PathBuf {
+    let stem = output_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "recording".to_string());
+    output_path.with_file_name(format!("{}.displays.json", stem))
+}
+
+/// `<dir>/<stem>.segments.json` next to the final output
+fn segment_manifest_path(output_path: &std::path::Path) -> PathBuf {
+    let stem = output_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "recording".to_string());
+    output_path.with_file_name(format!("{}.segments.json", stem))
+}
+
+fn read_segment_manifest(output_path: &std::path::Path) -> Option<SegmentManifest> {
+    std::fs::read_to_string(segment_manifest_path(output_path))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+}
+
+fn write_segment_manifest(output_path: &std::path::Path, manifest: &SegmentManifest) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(manifest)
+        .map_err(|e| format!("Failed to serialize segment manifest: {}", e))?;
+    std::fs::write(segment_manifest_path(output_path), content)
+        .map_err(|e| format!("Failed to write segment manifest: {}", e))
+}
+
+/// Length of a finished video file in seconds (AVFoundation, blocks)
+#[cfg(target_os = "macos")]
+pub(crate) fn probe_duration(path: &std::path::Path) -> Option<f64> {
+    let c_path = CString::new(path.to_str()?).ok()?;
+    let duration = unsafe { screen_recorder_get_duration(c_path.as_ptr()) };
+    (duration > 0.0).then_some(duration)
+}
+
+#[cfg(not(target_os = "macos"))]
+pub(crate) fn probe_duration(_path: &std::path::Path) -> Option<f64> {
+    None
+}
+
+/// Move a finished recording, along with its `<stem>.segments.json`
+pub(crate) fn move_recording(from: &std::path::Path, to: &std::path::Path) -> Result<(), String> {
+    std::fs::rename(from, to).map_err(|e| format!("Failed to move recording to {:?}: {}", to, e))?;
+    if let Some(mut manifest) = read_segment_manifest(from) {
+        manifest.output = to.to_string_lossy().to_string();
+        write_segment_manifest(to, &manifest)?;
+        let _ = std::fs::remove_file(segment_manifest_path(from));
+    }
+    Ok(())
 }
 
 // Manual implementation of Send for VideoRecorder
 // SAFETY: swift_recorder pointer is only accessed from a single thread
-// and protected by the Arc<Mutex<VideoRecorder>> wrapper
+// and protected by the Arc<SafeState<VideoRecorder>> wrapper
 unsafe impl Send for VideoRecorder {}
 unsafe impl Sync for VideoRecorder {}
 
@@ -70,8 +496,25 @@ impl VideoRecorder {
         VideoRecorder {
             #[cfg(target_os = "macos")]
             swift_recorder: None,
-            current_session_id: Arc::new(Mutex::new(None)),
-            output_path: Arc::new(Mutex::new(None)),
+            current_session_id: None,
+            output_path: None,
+            quality: VideoQuality::default(),
+            segments: Vec::new(),
+            paused: false,
+            adaptive_idle_fps: None,
+            idle: false,
+            blanked: false,
+            display_id: None,
+            overlay: None,
+            secondary: Vec::new(),
+            finished_sources: Vec::new(),
+            started_at: None,
+            parts: Vec::new(),
+            auto_stopped: None,
+            segment_duration: None,
+            segment_started: None,
+            segment_log: Vec::new(),
+            finished_frames: FrameStats::default(),
         }
     }
 
@@ -85,7 +528,7 @@ impl VideoRecorder {
         #[cfg(target_os = "macos")]
         {
             // Check if already recording
-            if self.swift_recorder.is_some() {
+            if self.swift_recorder.is_some() || self.paused {
                 return Err("Already recording".to_string());
             }
 
@@ -94,46 +537,406 @@ impl VideoRecorder {
                 return Err("Screen recording permission not granted. Please enable in System Settings > Privacy & Security > Screen Recording".to_string());
             }
 
-            // Create Swift recorder instance
-            let recorder = unsafe { screen_recorder_create() };
-            if recorder.is_null() {
-                return Err("Failed to create screen recorder".to_string());
+            println!("🎬 Starting screen recording for session: {}", session_id);
+            println!("   Output: {:?}", output_path);
+            println!(
+                "   Quality: {}x{} @ {}fps, {:?} codec, {:?} preset ({} kbps)",
+                quality.width,
+                quality.height,
+                quality.fps,
+                quality.codec,
+                quality.preset,
+                quality.bitrate() / 1000
+            );
+
+            self.output_path = Some(output_path);
+            self.quality = quality;
+            self.segments.clear();
+            self.segment_log.clear();
+            self.finished_frames = FrameStats::default();
+            self.parts.clear();
+            self.auto_stopped = None;
+            self.current_session_id = Some(session_id);
+            if let Err(e) = self.start_segment() {
+                self.output_path = None;
+                self.current_session_id = None;
+                return Err(e);
             }
+            self.started_at = Some(chrono::Utc::now());
 
-            // Convert path to C string
-            let path_str = output_path
-                .to_str()
-                .ok_or("Invalid output path")?;
-            let c_path = CString::new(path_str)
-                .map_err(|_| "Failed to convert path to C string")?;
+            println!("✅ Screen recording started successfully");
+            Ok(())
+        }
 
-            println!("🎬 Starting screen recording for session: {}", session_id);
-            println!("   Output: {:?}", output_path);
-            println!("   Quality: {}x{} @ {}fps", quality.width, quality.height, quality.fps);
+        #[cfg(not(target_os = "macos"))]
+        {
+            let _ = (session_id, output_path, quality);
+            Err("Screen recording only supported on macOS 12.3+".to_string())
+        }
+    }
+
+    /// Record several displays at once, each into its own file: the first
+    /// display writes `output_path`, the others `<stem>.display<id>.mp4`
+    pub fn start_multi_source_recording(
+        &mut self,
+        session_id: String,
+        output_path: PathBuf,
+        display_ids: Vec<u32>,
+        quality: VideoQuality,
+    ) -> Result<(), String> {
+        #[cfg(target_os = "macos")]
+        {
+            let mut display_ids = display_ids;
+            let mut seen = std::collections::HashSet::new();
+            display_ids.retain(|id| seen.insert(*id));
+            let (&primary, others) = display_ids
+                .split_first()
+                .ok_or("No displays selected for recording")?;
+
+            let previous_display = self.display_id;
+            self.display_id = Some(primary);
+            if let Err(e) = self.start_recording(session_id.clone(), output_path.clone(), quality.clone()) {
+                self.display_id = previous_display;
+                return Err(e);
+            }
 
-            // Start recording
-            let success = unsafe {
-                screen_recorder_start(
+            for &display_id in others {
+                let mut source = VideoRecorder::new();
+                source.display_id = Some(display_id);
+                source.adaptive_idle_fps = self.adaptive_idle_fps;
+                source.idle = self.idle;
+                source.blanked = self.blanked;
+                source.overlay = self.overlay.clone();
+                source.segment_duration = self.segment_duration;
+                let path = display_output_path(&output_path, display_id);
+                if let Err(e) = source.start_recording(session_id.clone(), path, quality.clone()) {
+                    // All or nothing: drop what was already started
+                    let mut started = std::mem::take(&mut self.secondary);
+                    started.push(source);
+                    for mut source in started {
+                        if let Ok(path) = source.stop_recording() {
+                            let _ = std::fs::remove_file(path);
+                        }
+                    }
+                    if let Ok(path) = self.stop_recording() {
+                        let _ = std::fs::remove_file(path);
+                    }
+                    self.display_id = previous_display;
+                    return Err(format!("Failed to record display {}: {}", display_id, e));
+                }
+                self.secondary.push(source);
+            }
+
+            println!("🖥️  Recording {} displays into separate files", display_ids.len());
+            Ok(())
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            let _ = (session_id, output_path, display_ids, quality);
+            Err("Screen recording only supported on macOS 12.3+".to_string())
+        }
+    }
+
+    /// Start a Swift recorder writing the next segment file
+    #[cfg(target_os = "macos")]
+    fn start_segment(&mut self) -> Result<(), String> {
+        let output_path = self.output_path.as_ref().ok_or("No output path set")?;
+        // Counted from the log so indexes stay unique after `trim_segments`
+        let index = self.segment_log.last().map_or(0, |entry| entry.index + 1);
+        let path = segment_path(output_path, index);
+
+        // Create Swift recorder instance
+        let recorder = unsafe { screen_recorder_create() };
+        if recorder.is_null() {
+            return Err("Failed to create screen recorder".to_string());
+        }
+
+        // Convert path to C string
+        let path_str = path
+            .to_str()
+            .ok_or("Invalid output path")?;
+        let c_path = CString::new(path_str)
+            .map_err(|_| "Failed to convert path to C string")?;
+
+        let success = unsafe {
+            screen_recorder_start(
+                recorder,
+                c_path.as_ptr(),
+                self.quality.width as i32,
+                self.quality.height as i32,
+                self.quality.fps as i32,
+                self.quality.codec.ffi_value(),
+                self.quality.bitrate(),
+                self.display_id.unwrap_or(0),
+            )
+        };
+
+        if !success {
+            unsafe { screen_recorder_destroy(recorder) };
+            return Err("Failed to start screen recording. Check console for details.".to_string());
+        }
+
+        self.swift_recorder = Some(recorder);
+        self.segment_log.push(SegmentInfo {
+            index,
+            path: path.to_string_lossy().to_string(),
+            started_at: chrono::Utc::now().to_rfc3339(),
+            ended_at: None,
+            duration_secs: None,
+            offset_secs: None,
+        });
+        self.segments.push(path);
+        self.segment_started = Some(Instant::now());
+        self.display_id = Some(unsafe { screen_recorder_display_id(recorder) });
+        self.apply_idle_state();
+        self.apply_blanked_state();
+        self.apply_overlay();
+        self.save_segment_manifest();
+        Ok(())
+    }
+
+    /// Stop the Swift recorder, finalizing the current segment file
+    #[cfg(target_os = "macos")]
+    fn finish_segment(&mut self) {
+        if let Some(recorder) = self.swift_recorder.take() {
+            let success = unsafe { screen_recorder_stop(recorder) };
+
+            if !success {
+                println!("⚠️  Failed to stop recording gracefully, but continuing cleanup");
+            }
+            self.finished_frames.add(swift_frame_stats(recorder));
+
+            // Clean up Swift recorder
+            unsafe { screen_recorder_destroy(recorder) };
+
+            if let (Some(entry), Some(started)) = (self.segment_log.last_mut(), self.segment_started.take()) {
+                entry.ended_at = Some(chrono::Utc::now().to_rfc3339());
+                entry.duration_secs = Some(started.elapsed().as_secs_f64());
+            }
+            self.save_segment_manifest();
+        }
+    }
+
+    /// Write `<stem>.segments.json` for rolling segments (or keep one already
+    /// written current after they were turned off)
+    #[cfg(target_os = "macos")]
+    fn save_segment_manifest(&self) {
+        let Some(output_path) = &self.output_path else {
+            return;
+        };
+        if self.segment_duration.is_none() && !segment_manifest_path(output_path).is_file() {
+            return;
+        }
+        let manifest = SegmentManifest {
+            session_id: self.current_session_id.clone().unwrap_or_default(),
+            segment_minutes: self.segment_duration.map_or(0, |duration| (duration.as_secs() / 60) as u32),
+            output: output_path.to_string_lossy().to_string(),
+            stitched: false,
+            segments: self.segment_log.clone(),
+        };
+        if let Err(e) = write_segment_manifest(output_path, &manifest) {
+            eprintln!("⚠️  {}", e);
+        }
+    }
+
+    /// Delete the oldest finished segments so at most `keep` remain besides
+    /// the one being recorded (replay_buffer.rs); returns how many were removed
+    pub fn trim_segments(&mut self, keep: usize) -> usize {
+        let finished = self.segments.len().saturating_sub(usize::from(self.swift_recorder_running()));
+        let excess = finished.saturating_sub(keep);
+        for segment in self.segments.drain(..excess) {
+            let _ = std::fs::remove_file(segment);
+        }
+        self.segment_log.drain(..excess);
+        excess
+    }
+
+    fn swift_recorder_running(&self) -> bool {
+        #[cfg(target_os = "macos")]
+        {
+            self.swift_recorder.is_some()
+        }
+        #[cfg(not(target_os = "macos"))]
+        {
+            false
+        }
+    }
+
+    /// Roll into a new segment every `minutes` (None = off); applies to the
+    /// running recording and every display of a multi-source one
+    pub fn set_segment_duration(&mut self, minutes: Option<u32>) {
+        self.segment_duration = minutes.map(|minutes| Duration::from_secs(minutes as u64 * 60));
+        for source in &mut self.secondary {
+            source.set_segment_duration(minutes);
+        }
+    }
+
+    /// Finalize the current segment and continue in the next one if it has
+    /// reached the rolling segment length
+    pub fn rotate_segment_if_due(&mut self) -> Result<(), String> {
+        #[cfg(target_os = "macos")]
+        {
+            let due = match (self.segment_duration, self.segment_started) {
+                (Some(duration), Some(started)) => started.elapsed() >= duration,
+                _ => false,
+            };
+            if due && self.swift_recorder.is_some() {
+                self.finish_segment();
+                if let Err(e) = self.start_segment() {
+                    // Recording stays paused so nothing is lost; resume or stop
+                    self.paused = true;
+                    let error = format!("Failed to start the next segment: {}", e);
+                    self.finished_frames.last_error = Some(error.clone());
+                    return Err(error);
+                }
+                println!("📼 Screen recording continued in segment {}", self.segments.len());
+            }
+            for source in &mut self.secondary {
+                if let Err(e) = source.rotate_segment_if_due() {
+                    eprintln!("⚠️  Display {:?}: {}", source.display_id, e);
+                }
+            }
+            Ok(())
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Ok(())
+        }
+    }
+
+    /// Turn adaptive frame rate on (`Some(idle_fps)`) or off
+    pub fn set_adaptive_framerate(&mut self, idle_fps: Option<u32>) {
+        for source in &mut self.secondary {
+            source.set_adaptive_framerate(idle_fps);
+        }
+        self.adaptive_idle_fps = idle_fps;
+        if idle_fps.is_none() {
+            self.idle = false;
+        }
+        self.apply_idle_state();
+    }
+
+    /// Record whether the user is idle (no-op while adaptive frame rate is off)
+    pub fn set_idle(&mut self, idle: bool) {
+        if self.adaptive_idle_fps.is_none() || self.idle == idle {
+            return;
+        }
+
+        self.idle = idle;
+        if self.is_recording() {
+            match (idle, self.adaptive_idle_fps) {
+                (true, Some(fps)) => println!("💤 Screen recording idle, dropping to {}fps", fps),
+                _ => println!("⚡ Activity detected, screen recording back to {}fps", self.quality.fps),
+            }
+        }
+        self.apply_idle_state();
+        for source in &mut self.secondary {
+            source.idle = idle;
+            source.apply_idle_state();
+        }
+    }
+
+    /// Push the idle state to the Swift recorder, if one is running
+    fn apply_idle_state(&self) {
+        #[cfg(target_os = "macos")]
+        if let Some(recorder) = self.swift_recorder {
+            let idle_fps = self.adaptive_idle_fps.unwrap_or(0) as i32;
+            unsafe { screen_recorder_set_idle(recorder, self.idle, idle_fps) };
+        }
+    }
+
+    /// Black out frames while a blocked app is frontmost (privacy.rs)
+    pub fn set_blanked(&mut self, blanked: bool) {
+        if self.blanked == blanked {
+            return;
+        }
+        self.blanked = blanked;
+        self.apply_blanked_state();
+        for source in &mut self.secondary {
+            source.blanked = blanked;
+            source.apply_blanked_state();
+        }
+    }
+
+    fn apply_blanked_state(&self) {
+        #[cfg(target_os = "macos")]
+        if let Some(recorder) = self.swift_recorder {
+            unsafe { screen_recorder_set_blanked(recorder, self.blanked) };
+        }
+    }
+
+    /// Overlay burned into the video from the next frame on (None = off);
+    /// applies to every display of a multi-source recording
+    pub fn set_overlay(&mut self, overlay: Option<RecordingOverlay>) -> Result<(), String> {
+        if let Some(overlay) = &overlay {
+            overlay.validate()?;
+        }
+        for source in &mut self.secondary {
+            source.overlay = overlay.clone();
+            source.apply_overlay();
+        }
+        self.overlay = overlay;
+        self.apply_overlay();
+        Ok(())
+    }
+
+    /// Push the overlay to the Swift recorder, if one is running
+    fn apply_overlay(&self) {
+        #[cfg(target_os = "macos")]
+        if let Some(recorder) = self.swift_recorder {
+            let overlay = self.overlay.clone().unwrap_or_default();
+            let label = overlay.label.and_then(|label| CString::new(label).ok());
+            let logo = overlay.logo_path.and_then(|path| CString::new(path).ok());
+            let loaded = unsafe {
+                screen_recorder_set_overlay(
                     recorder,
-                    c_path.as_ptr(),
-                    quality.width as i32,
-                    quality.height as i32,
-                    quality.fps as i32,
+                    label.as_ref().map_or(std::ptr::null(), |label| label.as_ptr()),
+                    overlay.timestamp,
+                    logo.as_ref().map_or(std::ptr::null(), |logo| logo.as_ptr()),
+                    overlay.position.ffi_value(),
                 )
             };
-
-            if !success {
-                unsafe { screen_recorder_destroy(recorder) };
-                return Err("Failed to start screen recording. Check console for details.".to_string());
+            if !loaded {
+                eprintln!("⚠️  Overlay logo couldn't be loaded, recording without it");
             }
+        }
+    }
+
+    /// Show a key combo on the video for a moment (keystroke_overlay.rs)
+    pub fn show_keystroke(&self, combo: &str) {
+        #[cfg(target_os = "macos")]
+        if let (Some(recorder), Ok(text)) = (self.swift_recorder, CString::new(combo)) {
+            unsafe { screen_recorder_show_keystroke(recorder, text.as_ptr()) };
+        }
+        #[cfg(not(target_os = "macos"))]
+        let _ = combo;
 
-            self.swift_recorder = Some(recorder);
-            *self.current_session_id.lock()
-                .map_err(|e| format!("Failed to lock session_id: {}", e))? = Some(session_id.clone());
-            *self.output_path.lock()
-                .map_err(|e| format!("Failed to lock output_path: {}", e))? = Some(output_path.clone());
+        for source in &self.secondary {
+            source.show_keystroke(combo);
+        }
+    }
 
-            println!("✅ Screen recording started successfully");
+    /// Pause recording; nothing is captured until `resume_recording`
+    pub fn pause_recording(&mut self) -> Result<(), String> {
+        #[cfg(target_os = "macos")]
+        {
+            if self.paused {
+                return Ok(());
+            }
+            if self.swift_recorder.is_none() {
+                return Err("No active recording".to_string());
+            }
+
+            self.finish_segment();
+            self.paused = true;
+            for source in &mut self.secondary {
+                if let Err(e) = source.pause_recording() {
+                    eprintln!("⚠️  Failed to pause recording of display {:?}: {}", source.display_id, e);
+                }
+            }
+            println!("⏸️  Screen recording paused after segment {}", self.segments.len());
             Ok(())
         }
 
@@ -143,31 +946,89 @@ impl VideoRecorder {
         }
     }
 
-    /// Stop recording and save video
-    pub fn stop_recording(&mut self) -> Result<PathBuf, String> {
+    /// Resume a paused recording in a new segment
+    pub fn resume_recording(&mut self) -> Result<(), String> {
         #[cfg(target_os = "macos")]
         {
-            let recorder = self.swift_recorder
-                .take()
-                .ok_or("No active recording")?;
+            if !self.paused {
+                return if self.swift_recorder.is_some() {
+                    Ok(())
+                } else {
+                    Err("No active recording".to_string())
+                };
+            }
 
-            println!("⏹️  Stopping screen recording...");
+            self.start_segment()?;
+            self.paused = false;
+            for source in &mut self.secondary {
+                if let Err(e) = source.resume_recording() {
+                    eprintln!("⚠️  Failed to resume recording of display {:?}: {}", source.display_id, e);
+                }
+            }
+            println!("▶️  Screen recording resumed (segment {})", self.segments.len());
+            Ok(())
+        }
 
-            let success = unsafe { screen_recorder_stop(recorder) };
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err("Screen recording only supported on macOS 12.3+".to_string())
+        }
+    }
 
-            if !success {
-                println!("⚠️  Failed to stop recording gracefully, but continuing cleanup");
+    /// Stop recording and save video (segments stitched into the output path)
+    pub fn stop_recording(&mut self) -> Result<PathBuf, String> {
+        #[cfg(target_os = "macos")]
+        {
+            if self.swift_recorder.is_none() && !self.paused {
+                // Already finalized by a safety limit
+                return self.auto_stopped.take().ok_or_else(|| "No active recording".to_string());
             }
 
-            let path = self.output_path.lock()
-                .map_err(|e| format!("Failed to lock output_path: {}", e))?
+            println!("⏹️  Stopping screen recording...");
+            self.finish_segment();
+            self.paused = false;
+            let session_id = self.current_session_id.take();
+            let display_id = self.display_id.take();
+
+            let path = self.output_path
                 .take()
                 .ok_or("No output path set")?;
+            let segments = std::mem::take(&mut self.segments);
+            self.segment_log.clear();
+            let mut sources = std::mem::take(&mut self.finished_sources);
+            for mut source in std::mem::take(&mut self.secondary) {
+                let source_display = source.display_id.unwrap_or(0);
+                match source.stop_recording() {
+                    Ok(source_path) => sources.push(DisplayRecording {
+                        display_id: source_display,
+                        path: source_path.to_string_lossy().to_string(),
+                        primary: false,
+                    }),
+                    Err(e) => eprintln!("❌ Failed to save recording of display {}: {}", source_display, e),
+                }
+            }
 
-            // Clean up Swift recorder
-            unsafe { screen_recorder_destroy(recorder) };
-            *self.current_session_id.lock()
-                .map_err(|e| format!("Failed to lock session_id: {}", e))? = None;
+            Self::stitch_segments(&segments, &path)?;
+
+            if !sources.is_empty() {
+                sources.insert(0, DisplayRecording {
+                    display_id: display_id.unwrap_or(0),
+                    path: path.to_string_lossy().to_string(),
+                    primary: true,
+                });
+                let manifest = RecordingManifest {
+                    session_id: session_id.unwrap_or_default(),
+                    started_at: self.started_at.take().unwrap_or_else(chrono::Utc::now).to_rfc3339(),
+                    stopped_at: chrono::Utc::now().to_rfc3339(),
+                    displays: sources,
+                };
+                let content = serde_json::to_string_pretty(&manifest)
+                    .map_err(|e| format!("Failed to serialize recording manifest: {}", e))?;
+                std::fs::write(manifest_path(&path), content)
+                    .map_err(|e| format!("Failed to write recording manifest: {}", e))?;
+                println!("🖥️  Saved recordings of {} displays", manifest.displays.len());
+            }
+            self.started_at = None;
 
             println!("✅ Screen recording stopped, video saved to: {:?}", path);
             Ok(path)
@@ -179,7 +1040,181 @@ impl VideoRecorder {
         }
     }
 
-    /// Check if currently recording
+    /// Stop without keeping anything: the segments and their manifest are deleted
+    pub fn discard(&mut self) {
+        #[cfg(target_os = "macos")]
+        self.finish_segment();
+        for segment in std::mem::take(&mut self.segments) {
+            let _ = std::fs::remove_file(segment);
+        }
+        self.segment_log.clear();
+        if let Some(path) = self.output_path.take() {
+            let _ = std::fs::remove_file(segment_manifest_path(&path));
+        }
+        for mut source in std::mem::take(&mut self.secondary) {
+            source.discard();
+        }
+        self.finished_sources.clear();
+        self.paused = false;
+        self.current_session_id = None;
+        self.display_id = None;
+        self.started_at = None;
+    }
+
+    /// Stop for a safety limit; the next `stop_recording` (from the frontend
+    /// ending the session) returns the finished file instead of failing
+    pub fn auto_stop(&mut self) -> Result<PathBuf, String> {
+        let path = self.stop_recording()?;
+        self.auto_stopped = Some(path.clone());
+        Ok(path)
+    }
+
+    /// Size in bytes of the largest file being recorded (all displays)
+    pub fn current_file_size(&self) -> u64 {
+        let own: u64 = self
+            .segments
+            .iter()
+            .filter_map(|segment| std::fs::metadata(segment).ok())
+            .map(|metadata| metadata.len())
+            .sum();
+        self.secondary
+            .iter()
+            .map(|source| source.current_file_size())
+            .fold(own, u64::max)
+    }
+
+    /// Size in bytes of all files being recorded (all displays)
+    pub fn total_file_size(&self) -> u64 {
+        let own: u64 = self
+            .segments
+            .iter()
+            .filter_map(|segment| std::fs::metadata(segment).ok())
+            .map(|metadata| metadata.len())
+            .sum();
+        own + self.secondary.iter().map(|source| source.total_file_size()).sum::<u64>()
+    }
+
+    /// Frame counters since the recording started (all displays)
+    pub fn frame_stats(&self) -> FrameStats {
+        let mut stats = self.finished_frames.clone();
+        #[cfg(target_os = "macos")]
+        if let Some(recorder) = self.swift_recorder {
+            stats.add(swift_frame_stats(recorder));
+        }
+        for source in &self.secondary {
+            stats.add(source.frame_stats());
+        }
+        stats
+    }
+
+    /// Finish the current file and continue recording into the next part
+    /// (`<stem>.part<N>.mp4`, other displays likewise); returns the finished file
+    pub fn roll_over(&mut self) -> Result<PathBuf, String> {
+        #[cfg(target_os = "macos")]
+        {
+            if self.swift_recorder.is_none() {
+                return Err("No active recording".to_string());
+            }
+
+            self.finish_segment();
+            let finished = self.output_path.clone().ok_or("No output path set")?;
+            let segments = std::mem::take(&mut self.segments);
+            Self::stitch_segments(&segments, &finished)?;
+            self.segment_log.clear();
+            self.parts.push(finished.clone());
+
+            let next = part_path(&self.parts[0], self.parts.len() + 1);
+            self.output_path = Some(next.clone());
+            if let Err(e) = self.start_segment() {
+                // Nothing is recording any more; hand the finished part to the frontend's stop
+                self.output_path = None;
+                self.current_session_id = None;
+                self.auto_stopped = Some(finished);
+                return Err(format!("Failed to continue recording in {:?}: {}", next, e));
+            }
+            for source in &mut self.secondary {
+                if let Err(e) = source.roll_over() {
+                    eprintln!("⚠️  Failed to roll over recording of display {:?}: {}", source.display_id, e);
+                }
+            }
+            println!("📼 Recording rolled over to {:?}", next);
+            Ok(finished)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err("Screen recording only supported on macOS 12.3+".to_string())
+        }
+    }
+
+    /// Join segment files into `output_path` and remove them, recording each
+    /// segment's offset in `<stem>.segments.json` if there is one
+    /// (also used by recovery.rs for segments left behind by a crash)
+    pub fn stitch_segments(segments: &[PathBuf], output_path: &std::path::Path) -> Result<(), String> {
+        let manifest = read_segment_manifest(output_path).map(|mut manifest| {
+            // Segments lost in a crash aren't part of the output
+            manifest
+                .segments
+                .retain(|entry| segments.iter().any(|segment| segment.as_os_str() == entry.path.as_str()));
+            let mut offset = 0.0;
+            for entry in &mut manifest.segments {
+                if let Some(duration) = probe_duration(std::path::Path::new(&entry.path)) {
+                    entry.duration_secs = Some(duration);
+                }
+                entry.offset_secs = Some(offset);
+                offset += entry.duration_secs.unwrap_or(0.0);
+            }
+            manifest.output = output_path.to_string_lossy().to_string();
+            manifest.stitched = true;
+            manifest
+        });
+
+        Self::join_segments(segments, output_path)?;
+        if let Some(manifest) = manifest {
+            write_segment_manifest(output_path, &manifest)?;
+        }
+        Ok(())
+    }
+
+    fn join_segments(segments: &[PathBuf], output_path: &std::path::Path) -> Result<(), String> {
+        if let [segment] = segments {
+            return std::fs::rename(segment, output_path)
+                .map_err(|e| format!("Failed to save video: {}", e));
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err("Stitching recording segments is only supported on macOS".to_string())
+        }
+
+        #[cfg(target_os = "macos")]
+        {
+            println!("🧵 Stitching {} recording segments", segments.len());
+            let paths = segments
+                .iter()
+                .map(|segment| segment.to_str().ok_or("Invalid segment path"))
+                .collect::<Result<Vec<_>, _>>()?
+                .join("\n");
+            let c_paths = CString::new(paths)
+                .map_err(|_| "Failed to convert segment paths to C string")?;
+            let c_output = CString::new(output_path.to_str().ok_or("Invalid output path")?)
+                .map_err(|_| "Failed to convert path to C string")?;
+
+            if !unsafe { screen_recorder_concat_segments(c_paths.as_ptr(), c_output.as_ptr()) } {
+                return Err(format!(
+                    "Failed to stitch recording segments (kept next to {:?})",
+                    output_path
+                ));
+            }
+
+            for segment in segments {
+                let _ = std::fs::remove_file(segment);
+            }
+            Ok(())
+        }
+    }
+
+    /// Check if currently recording (a paused recording still counts)
     pub fn is_recording(&self) -> bool {
         #[cfg(target_os = "macos")]
         {
@@ -187,7 +1222,11 @@ impl VideoRecorder {
                 return unsafe { screen_recorder_is_recording(recorder) };
             }
         }
-        false
+        self.paused
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
     }
 
     /// Check if screen recording permission is granted
@@ -219,9 +1258,123 @@ impl VideoRecorder {
 
     /// Get current session ID if recording
     pub fn current_session_id(&self) -> Option<String> {
-        self.current_session_id.lock()
-            .ok()
-            .and_then(|s| s.clone())
+        self.current_session_id.clone()
+    }
+
+    /// Record `display_id` (None = main display) from now on; a running
+    /// recording continues in a new segment, a paused one switches on resume
+    pub fn switch_display(&mut self, display_id: Option<u32>) -> Result<(), String> {
+        #[cfg(target_os = "macos")]
+        {
+            let previous = self.display_id;
+            self.display_id = display_id;
+            if self.swift_recorder.is_none() {
+                return Ok(());
+            }
+
+            self.finish_segment();
+            if let Err(e) = self.start_segment() {
+                // Recording stays paused so nothing is lost; switch again or stop
+                self.display_id = previous;
+                self.paused = true;
+                return Err(e);
+            }
+            println!("🖥️  Screen recording switched to display {:?}", self.display_id);
+            Ok(())
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            let _ = display_id;
+            Err("Screen recording only supported on macOS 12.3+".to_string())
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+static DISPLAY_MONITOR_APP: std::sync::OnceLock<AppHandle> = std::sync::OnceLock::new();
+
+/// kCGDisplayRemoveFlag
+#[cfg(target_os = "macos")]
+const DISPLAY_REMOVED_FLAG: u32 = 1 << 5;
+
+/// Watch for the recorded display being unplugged (macOS)
+pub fn install_display_monitor(app: AppHandle) {
+    #[cfg(target_os = "macos")]
+    if DISPLAY_MONITOR_APP.set(app).is_ok() {
+        unsafe { display_monitor_install(on_display_reconfigured) };
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    let _ = app;
+}
+
+/// Called by Swift on the main thread; the work (Swift start/stop) blocks, so hand it off
+#[cfg(target_os = "macos")]
+extern "C" fn on_display_reconfigured(display_id: u32, flags: u32) {
+    if flags & DISPLAY_REMOVED_FLAG == 0 {
+        return;
+    }
+    if let Some(app) = DISPLAY_MONITOR_APP.get().cloned() {
+        tauri::async_runtime::spawn_blocking(move || display_removed(&app, display_id));
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn display_removed(app: &AppHandle, display_id: u32) {
+    use tauri::{Emitter, Manager};
+
+    let state = app.state::<Arc<SafeState<VideoRecorder>>>();
+    let mut recorder = state.lock();
+
+    // A secondary display of a multi-source recording: keep what it captured, no fallback
+    if let Some(index) = recorder.secondary.iter().position(|source| source.display_id == Some(display_id)) {
+        let mut source = recorder.secondary.remove(index);
+        println!("🖥️  Display {} disconnected, finishing its recording", display_id);
+        match source.stop_recording() {
+            Ok(path) => recorder.finished_sources.push(DisplayRecording {
+                display_id,
+                path: path.to_string_lossy().to_string(),
+                primary: false,
+            }),
+            Err(e) => eprintln!("❌ Failed to save recording of display {}: {}", display_id, e),
+        }
+        let _ = app.emit(
+            "recording-display-lost",
+            serde_json::json!({ "displayId": display_id, "fallbackDisplayId": null }),
+        );
+        return;
+    }
+
+    if recorder.display_id != Some(display_id) || (recorder.swift_recorder.is_none() && !recorder.paused) {
+        return;
+    }
+
+    let fallback = core_graphics::display::CGDisplay::main().id;
+    println!("🖥️  Recorded display {} disconnected, falling back to display {}", display_id, fallback);
+    let was_paused = recorder.paused;
+    if !was_paused {
+        // Finalize what was captured before the display went away
+        recorder.finish_segment();
+        recorder.paused = true;
+    }
+    let _ = app.emit(
+        "recording-display-lost",
+        serde_json::json!({ "displayId": display_id, "fallbackDisplayId": fallback }),
+    );
+
+    recorder.display_id = Some(fallback);
+    if was_paused {
+        return;
+    }
+    match recorder.resume_recording() {
+        Ok(()) => {
+            let _ = app.emit(
+                "recording-display-switched",
+                serde_json::json!({ "displayId": recorder.display_id }),
+            );
+        }
+        Err(e) => eprintln!("❌ Failed to continue recording on display {}: {}", fallback, e),
     }
 }
 
@@ -229,12 +1382,9 @@ impl Drop for VideoRecorder {
     fn drop(&mut self) {
         #[cfg(target_os = "macos")]
         {
-            if let Some(recorder) = self.swift_recorder.take() {
+            if self.swift_recorder.is_some() {
                 println!("🗑️  Cleaning up video recorder");
-                unsafe {
-                    screen_recorder_stop(recorder);
-                    screen_recorder_destroy(recorder);
-                }
+                self.finish_segment();
             }
         }
     }
@@ -247,103 +1397,472 @@ impl Drop for VideoRecorder {
 /// Tauri command to start video recording
 #[tauri::command]
 pub async fn start_video_recording(
+    app: tauri::AppHandle,
     session_id: String,
     output_path: String,
     quality: Option<VideoQuality>,
-    recorder: State<'_, Arc<Mutex<VideoRecorder>>>,
+    overlay: Option<RecordingOverlay>,
+    recorder: State<'_, Arc<SafeState<VideoRecorder>>>,
 ) -> Result<(), String> {
-    let mut recorder = recorder.lock()
-        .map_err(|e| format!("Failed to lock video recorder: {}", e))?;
-    let quality = quality.unwrap_or_default();
-    let path = PathBuf::from(output_path);
+    command_metrics::track_async("start_video_recording", async move {
+        let mut recorder = recorder.lock();
+        let quality = quality.unwrap_or_default();
+        let path = PathBuf::from(output_path);
+        disk_space::ensure_recording_space(&app, &path)?;
 
-    recorder.start_recording(session_id, path, quality)
+        recorder.set_overlay(overlay)?;
+        recorder.start_recording(session_id, path, quality)
+    }).await
+}
+
+/// Tauri command to record several displays into separate files
+/// (`display_ids` None = all active displays, main display first), each with
+/// the optional burned-in `overlay`
+#[tauri::command]
+pub async fn start_multi_source_recording(
+    app: tauri::AppHandle,
+    session_id: String,
+    output_path: String,
+    display_ids: Option<Vec<u32>>,
+    quality: Option<VideoQuality>,
+    overlay: Option<RecordingOverlay>,
+    recorder: State<'_, Arc<SafeState<VideoRecorder>>>,
+) -> Result<Vec<u32>, String> {
+    command_metrics::track_async("start_multi_source_recording", async move {
+        let display_ids = match display_ids {
+            Some(ids) => ids,
+            None => active_displays()?,
+        };
+        let path = PathBuf::from(output_path);
+        disk_space::ensure_recording_space(&app, &path)?;
+
+        let mut recorder = recorder.lock();
+        recorder.set_overlay(overlay)?;
+        recorder.start_multi_source_recording(session_id, path, display_ids.clone(), quality.unwrap_or_default())?;
+        Ok(display_ids)
+    }).await
+}
+
+/// Active displays, main display first
+#[cfg(target_os = "macos")]
+fn active_displays() -> Result<Vec<u32>, String> {
+    use core_graphics::display::CGDisplay;
+
+    let main = CGDisplay::main().id;
+    let mut displays = CGDisplay::active_displays()
+        .map_err(|e| format!("Failed to list displays: {:?}", e))?;
+    displays.sort_by_key(|&id| id != main);
+    Ok(displays)
+}
+
+#[cfg(not(target_os = "macos"))]
+fn active_displays() -> Result<Vec<u32>, String> {
+    Err("Screen recording only supported on macOS 12.3+".to_string())
 }
 
 /// Tauri command to stop video recording
 #[tauri::command]
 pub async fn stop_video_recording(
-    recorder: State<'_, Arc<Mutex<VideoRecorder>>>,
+    recorder: State<'_, Arc<SafeState<VideoRecorder>>>,
 ) -> Result<String, String> {
-    let mut recorder = recorder.lock()
-        .map_err(|e| format!("Failed to lock video recorder: {}", e))?;
-    let path = recorder.stop_recording()?;
-    Ok(path.to_string_lossy().to_string())
+    command_metrics::track_async("stop_video_recording", async move {
+        // Stitching segments can take a few seconds - keep it off the IPC thread
+        let recorder = recorder.inner().clone();
+        let path = tokio::task::spawn_blocking(move || recorder.lock().stop_recording())
+            .await
+            .map_err(|e| format!("Stop recording task failed: {}", e))??;
+        Ok(path.to_string_lossy().to_string())
+    }).await
+}
+
+/// Tauri command to pause video recording (paused time is left out of the video)
+#[tauri::command]
+pub async fn pause_video_recording(
+    recorder: State<'_, Arc<SafeState<VideoRecorder>>>,
+) -> Result<(), String> {
+    command_metrics::track_async("pause_video_recording", async move {
+        let mut recorder = recorder.lock();
+        recorder.pause_recording()
+    }).await
+}
+
+/// Tauri command to resume a paused video recording
+#[tauri::command]
+pub async fn resume_video_recording(
+    recorder: State<'_, Arc<SafeState<VideoRecorder>>>,
+) -> Result<(), String> {
+    command_metrics::track_async("resume_video_recording", async move {
+        let mut recorder = recorder.lock();
+        recorder.resume_recording()
+    }).await
+}
+
+/// Tauri command to check if video recording is paused
+#[tauri::command]
+pub async fn is_video_recording_paused(
+    recorder: State<'_, Arc<SafeState<VideoRecorder>>>,
+) -> Result<bool, String> {
+    command_metrics::track_async("is_video_recording_paused", async move {
+        let recorder = recorder.lock();
+        Ok(recorder.is_paused())
+    }).await
+}
+
+/// Follow ActivityMonitor and mark the recorder idle after IDLE_AFTER without input
+fn start_idle_watcher(
+    recorder: Arc<SafeState<VideoRecorder>>,
+    monitor: Arc<ActivityMonitor>,
+    registry: &TaskRegistry,
+) -> Result<(), String> {
+    registry.spawn(ADAPTIVE_TASK_NAME, |mut shutdown| async move {
+        let mut interval = tokio::time::interval(ADAPTIVE_POLL_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+
+            // Without activity monitoring there's no way to tell, so keep the full rate
+            let idle = monitor
+                .idle_duration()
+                .is_some_and(|elapsed| elapsed >= IDLE_AFTER);
+            recorder.lock().set_idle(idle);
+        }
+    })
+}
+
+/// Tauri command to turn adaptive frame rate on or off; while idle the
+/// recording drops to `idle_fps` (1-5, default 2) frames per second.
+/// Idle detection relies on activity monitoring being started.
+#[tauri::command]
+pub async fn set_adaptive_framerate(
+    enabled: bool,
+    idle_fps: Option<u32>,
+    recorder: State<'_, Arc<SafeState<VideoRecorder>>>,
+    monitor: State<'_, Arc<ActivityMonitor>>,
+    registry: State<'_, Arc<TaskRegistry>>,
+) -> Result<(), String> {
+    command_metrics::track_async("set_adaptive_framerate", async move {
+        if !enabled {
+            registry.cancel(ADAPTIVE_TASK_NAME);
+            recorder.lock().set_adaptive_framerate(None);
+            println!("🎞️  Adaptive frame rate disabled");
+            return Ok(());
+        }
+
+        let idle_fps = idle_fps.unwrap_or(DEFAULT_IDLE_FPS);
+        if !(1..=MAX_IDLE_FPS).contains(&idle_fps) {
+            return Err(format!("Idle frame rate must be between 1 and {} fps", MAX_IDLE_FPS));
+        }
+
+        recorder.lock().set_adaptive_framerate(Some(idle_fps));
+        start_idle_watcher(recorder.inner().clone(), monitor.inner().clone(), &registry)?;
+        println!("🎞️  Adaptive frame rate enabled ({}fps when idle)", idle_fps);
+        Ok(())
+    }).await
+}
+
+/// Roll the recording into the next segment whenever the current one is due
+fn start_segment_rotation(recorder: Arc<SafeState<VideoRecorder>>, registry: &TaskRegistry) -> Result<(), String> {
+    registry.spawn(SEGMENT_TASK_NAME, |mut shutdown| async move {
+        let mut interval = tokio::time::interval(SEGMENT_POLL_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+
+            // Finalizing a segment blocks on Swift
+            let recorder = recorder.clone();
+            let rotated = tokio::task::spawn_blocking(move || recorder.lock().rotate_segment_if_due()).await;
+            match rotated {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => eprintln!("❌ {}", e),
+                Err(e) => eprintln!("⚠️  Segment rotation failed: {}", e),
+            }
+        }
+    })
+}
+
+/// Tauri command to record in rolling segments of `minutes` (1-120; None or
+/// 0 = off), so a crash loses at most the last segment
+#[tauri::command]
+pub async fn set_segment_duration(
+    minutes: Option<u32>,
+    recorder: State<'_, Arc<SafeState<VideoRecorder>>>,
+    registry: State<'_, Arc<TaskRegistry>>,
+) -> Result<(), String> {
+    command_metrics::track_async("set_segment_duration", async move {
+        let Some(minutes) = minutes.filter(|&minutes| minutes > 0) else {
+            registry.cancel(SEGMENT_TASK_NAME);
+            recorder.lock().set_segment_duration(None);
+            println!("📼 Rolling video segments disabled");
+            return Ok(());
+        };
+        if minutes > MAX_SEGMENT_MINUTES {
+            return Err(format!("Segment duration must be between 1 and {} minutes", MAX_SEGMENT_MINUTES));
+        }
+
+        recorder.lock().set_segment_duration(Some(minutes));
+        start_segment_rotation(recorder.inner().clone(), &registry)?;
+        println!("📼 Recording in {}-minute segments", minutes);
+        Ok(())
+    }).await
+}
+
+/// Tauri command to move the recording to another display (None = main display)
+#[tauri::command]
+pub async fn switch_display(
+    display_id: Option<u32>,
+    recorder: State<'_, Arc<SafeState<VideoRecorder>>>,
+) -> Result<(), String> {
+    command_metrics::track_async("switch_display", async move {
+        let mut recorder = recorder.lock();
+        recorder.switch_display(display_id)
+    }).await
 }
 
 /// Tauri command to check if currently recording
 #[tauri::command]
 pub async fn is_recording(
-    recorder: State<'_, Arc<Mutex<VideoRecorder>>>,
+    recorder: State<'_, Arc<SafeState<VideoRecorder>>>,
 ) -> Result<bool, String> {
-    let recorder = recorder.lock()
-        .map_err(|e| format!("Failed to lock video recorder: {}", e))?;
-    Ok(recorder.is_recording())
+    command_metrics::track_async("is_recording", async move {
+        let recorder = recorder.lock();
+        Ok(recorder.is_recording())
+    }).await
 }
 
 /// Tauri command to get current session ID if recording
 #[tauri::command]
 pub async fn get_current_recording_session(
-    recorder: State<'_, Arc<Mutex<VideoRecorder>>>,
+    recorder: State<'_, Arc<SafeState<VideoRecorder>>>,
 ) -> Result<Option<String>, String> {
-    let recorder = recorder.lock()
-        .map_err(|e| format!("Failed to lock video recorder: {}", e))?;
-    Ok(recorder.current_session_id())
+    command_metrics::track_async("get_current_recording_session", async move {
+        let recorder = recorder.lock();
+        Ok(recorder.current_session_id())
+    }).await
 }
 
 /// Tauri command to get video duration in seconds
 #[tauri::command]
 pub async fn get_video_duration(video_path: String) -> Result<f64, String> {
-    #[cfg(target_os = "macos")]
-    {
-        let c_path = CString::new(video_path)
-            .map_err(|_| "Invalid video path")?;
+    command_metrics::track_async("get_video_duration", async move {
+        #[cfg(target_os = "macos")]
+        {
+            let c_path = CString::new(video_path)
+                .map_err(|_| "Invalid video path")?;
+
+            // AVFoundation asset loading blocks - keep it off the IPC thread
+            let duration = tokio::task::spawn_blocking(move || unsafe {
+                screen_recorder_get_duration(c_path.as_ptr())
+            })
+            .await
+            .map_err(|e| format!("Video duration task failed: {}", e))?;
+            Ok(duration)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err("Video duration extraction only supported on macOS".to_string())
+        }
+    }).await
+}
+
+/// PNG data URL of the frame at `time` seconds, at most 320x180 (blocks on
+/// AVFoundation frame extraction)
+#[cfg(target_os = "macos")]
+pub(crate) fn video_thumbnail(video_path: &str, time: f64) -> Result<String, String> {
+    use std::ffi::CStr;
 
-        let duration = unsafe { screen_recorder_get_duration(c_path.as_ptr()) };
-        Ok(duration)
+    let c_path = CString::new(video_path)
+        .map_err(|_| "Invalid video path")?;
+
+    let thumbnail_ptr = unsafe { screen_recorder_generate_thumbnail(c_path.as_ptr(), time) };
+
+    if thumbnail_ptr.is_null() {
+        return Err("Failed to generate thumbnail".to_string());
     }
 
-    #[cfg(not(target_os = "macos"))]
-    {
-        Err("Video duration extraction only supported on macOS".to_string())
+    // Convert C string to Rust String
+    let thumbnail = unsafe {
+        CStr::from_ptr(thumbnail_ptr)
+            .to_string_lossy()
+            .into_owned()
+    };
+
+    // Free the C string (allocated by Swift's strdup)
+    unsafe {
+        libc::free(thumbnail_ptr as *mut libc::c_void);
     }
+
+    Ok(thumbnail)
+}
+
+#[cfg(not(target_os = "macos"))]
+pub(crate) fn video_thumbnail(_video_path: &str, _time: f64) -> Result<String, String> {
+    Err("Thumbnail generation only supported on macOS".to_string())
 }
 
 /// Tauri command to generate video thumbnail
 #[tauri::command]
 pub async fn generate_video_thumbnail(video_path: String, time: Option<f64>) -> Result<String, String> {
-    #[cfg(target_os = "macos")]
-    {
-        use std::ffi::CStr;
+    command_metrics::track_async("generate_video_thumbnail", async move {
+        let time = time.unwrap_or(1.0); // Default to 1 second into video
 
-        let c_path = CString::new(video_path)
-            .map_err(|_| "Invalid video path")?;
+        // Frame extraction + PNG encoding blocks - run on the blocking pool
+        tokio::task::spawn_blocking(move || video_thumbnail(&video_path, time))
+            .await
+            .map_err(|e| format!("Thumbnail task failed: {}", e))?
+    }).await
+}
 
-        let time = time.unwrap_or(1.0); // Default to 1 second into video
+/// A clip written by `extract_video_clip`
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VideoClip {
+    pub path: String,
+    pub duration_ms: u64,
+    /// False when the streams were copied (clip started on a keyframe)
+    pub reencoded: bool,
+}
 
-        let thumbnail_ptr = unsafe { screen_recorder_generate_thumbnail(c_path.as_ptr(), time) };
+/// Export [start, end) seconds of a video to `output_path` (blocks on
+/// AVFoundation); returns whether it was re-encoded
+#[cfg(target_os = "macos")]
+fn extract_clip(video_path: &std::path::Path, output_path: &std::path::Path, start: f64, end: f64) -> Result<bool, String> {
+    let c_path = CString::new(video_path.to_str().ok_or("Invalid video path")?)
+        .map_err(|_| "Invalid video path")?;
+    let c_output = CString::new(output_path.to_str().ok_or("Invalid output path")?)
+        .map_err(|_| "Invalid output path")?;
 
-        if thumbnail_ptr.is_null() {
-            return Err("Failed to generate thumbnail".to_string());
-        }
+    match unsafe { screen_recorder_extract_clip(c_path.as_ptr(), c_output.as_ptr(), start, end) } {
+        1 => Ok(false),
+        2 => Ok(true),
+        _ => Err("Failed to extract video clip".to_string()),
+    }
+}
 
-        // Convert C string to Rust String
-        let thumbnail = unsafe {
-            CStr::from_ptr(thumbnail_ptr)
-                .to_string_lossy()
-                .into_owned()
-        };
+#[cfg(not(target_os = "macos"))]
+fn extract_clip(_video_path: &std::path::Path, _output_path: &std::path::Path, _start: f64, _end: f64) -> Result<bool, String> {
+    Err("Video clip extraction only supported on macOS".to_string())
+}
+
+/// Path of a session's recording, fetched back first if it was offloaded to
+/// the remote archive
+async fn session_video_path(app: &AppHandle, session_id: &str) -> Result<PathBuf, String> {
+    let data_dir = profiles::profile_data_dir(app)?;
+    let read_dir = data_dir.clone();
+    let read_id = session_id.to_string();
+    let session = tokio::task::spawn_blocking(move || session_storage::read_session(&read_dir, &read_id))
+        .await
+        .map_err(|e| format!("Session task failed: {}", e))??;
+    let attachment_id = session
+        .video
+        .as_ref()
+        .map(|video| video.full_video_attachment_id.clone())
+        .ok_or_else(|| format!("Session {} has no recording", session.id))?;
+
+    // The recording may have been offloaded to the remote archive
+    remote_archive::fetch_session_media(app, &data_dir, &session).await;
+
+    let meta_path = data_dir.join("attachments").join(format!("{}.meta.json", attachment_id));
+    std::fs::read_to_string(&meta_path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .and_then(|meta| meta.get("path")?.as_str().map(PathBuf::from))
+        .filter(|path| path.is_file())
+        .ok_or_else(|| format!("Recording of session {} not found", session.id))
+}
+
+/// Tauri command to export part of a session's recording as an MP4 clip
+/// (`start_ms`/`end_ms` from the start of the video)
+#[tauri::command]
+pub async fn extract_video_clip(
+    app: AppHandle,
+    session_id: String,
+    start_ms: u64,
+    end_ms: u64,
+    output_path: String,
+) -> Result<VideoClip, String> {
+    command_metrics::track_async("extract_video_clip", async move {
+        if end_ms <= start_ms {
+            return Err("Clip end must be after its start".to_string());
+        }
+        let output = PathBuf::from(&output_path);
+        if !output.parent().is_some_and(|parent| parent.is_dir()) {
+            return Err(format!("Output folder doesn't exist: {}", output_path));
+        }
 
-        // Free the C string (allocated by Swift's strdup)
-        unsafe {
-            libc::free(thumbnail_ptr as *mut libc::c_void);
+        let video_path = session_video_path(&app, &session_id).await?;
+        if video_path == output {
+            return Err("Output path must differ from the recording".to_string());
         }
 
-        Ok(thumbnail)
-    }
+        let (start, end) = (start_ms as f64 / 1000.0, end_ms as f64 / 1000.0);
+        let clip_output = output.clone();
+        let reencoded = tokio::task::spawn_blocking(move || extract_clip(&video_path, &clip_output, start, end))
+            .await
+            .map_err(|e| format!("Clip task failed: {}", e))??;
 
-    #[cfg(not(target_os = "macos"))]
-    {
-        Err("Thumbnail generation only supported on macOS".to_string())
-    }
+        println!(
+            "✂️  Extracted {} ms clip of session {} ({})",
+            end_ms - start_ms,
+            session_id,
+            if reencoded { "re-encoded" } else { "stream copy" }
+        );
+        Ok(VideoClip {
+            path: output_path,
+            duration_ms: end_ms - start_ms,
+            reencoded,
+        })
+    }).await
+}
+
+/// Tauri command to list the per-display files of a session's recording
+/// (just the session's recording unless it was a multi-source one)
+#[tauri::command]
+pub async fn get_display_recordings(app: AppHandle, session_id: String) -> Result<Vec<DisplayRecording>, String> {
+    command_metrics::track_async("get_display_recordings", async move {
+        let video_path = session_video_path(&app, &session_id).await?;
+        let manifest = std::fs::read_to_string(manifest_path(&video_path))
+            .ok()
+            .and_then(|content| serde_json::from_str::<RecordingManifest>(&content).ok());
+        Ok(match manifest {
+            Some(manifest) => manifest
+                .displays
+                .into_iter()
+                .filter(|display| std::path::Path::new(&display.path).is_file())
+                .collect(),
+            None => vec![DisplayRecording {
+                display_id: 0,
+                path: video_path.to_string_lossy().to_string(),
+                primary: true,
+            }],
+        })
+    }).await
+}
+
+/// Tauri command to get the segments of a session's recording (None unless
+/// it was recorded in rolling segments); works while it is still recording
+#[tauri::command]
+pub async fn get_recording_segments(
+    app: AppHandle,
+    session_id: String,
+    recorder: State<'_, Arc<SafeState<VideoRecorder>>>,
+) -> Result<Option<SegmentManifest>, String> {
+    command_metrics::track_async("get_recording_segments", async move {
+        let recording = {
+            let recorder = recorder.lock();
+            recorder
+                .output_path
+                .clone()
+                .filter(|_| recorder.current_session_id.as_deref() == Some(session_id.as_str()))
+        };
+        let video_path = match recording {
+            Some(path) => path,
+            None => session_video_path(&app, &session_id).await?,
+        };
+        Ok(read_segment_manifest(&video_path))
+    }).await
 }
@@ -219,7 +219,10 @@ export default function CaptureZone() {
     const setupListener = async () => {
       try {
         const appWindow = getCurrentWindow();
-        unlisten = await appWindow.listen<string>('screenshot-captured', async (event) => {
+        unlisten = await appWindow.listen<unknown>('screenshot-captured', async (event) => {
+          // The native screenshot scheduler emits objects on this event; only
+          // the screenshot shortcut sends a data URL
+          if (typeof event.payload !== 'string') return;
           const base64Data = event.payload;
 
           try {
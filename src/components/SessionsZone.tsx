@@ -48,6 +48,9 @@ import { SessionsTopBar } from './sessions/SessionsTopBar';
 import { SessionsListPanel } from './sessions/SessionsListPanel';
 import { groupSessionsByDate, calculateTotalStats } from '../utils/sessionHelpers';
 import { motion } from 'framer-motion';
+import { resolveAudioChunk, type AudioChunkEvent } from '../types/tauri-media-commands';
+import type { PresetStartOptions } from '../types/tauri-presets';
+import { listenSessionAutoStopped } from '../types/tauri-recording-limits';
 
 export default function SessionsZone() {
   const { sessions, activeSessionId, startSession, endSession, pauseSession, resumeSession, updateSession, deleteSession, addScreenshot, addAudioSegment, updateScreenshotAnalysis, addScreenshotComment, toggleScreenshotFlag, setActiveSession, addExtractedTask, addExtractedNote, addContextItem } = useSessions();
@@ -688,6 +691,11 @@ export default function SessionsZone() {
             });
         } else if (isAlreadyRecordingThisSession) {
           console.log('✅ [SESSIONS ZONE] Already recording video for this session:', activeSession.id);
+          // Picks up again after a pause (no-op otherwise)
+          videoRecordingService.resumeRecording()
+            .catch(error => {
+              console.error('❌ [SESSIONS ZONE] Failed to resume video recording:', error);
+            });
         } else {
           console.log('ℹ️ [SESSIONS ZONE] Video initialization already attempted for session:', activeSession.id);
         }
@@ -708,10 +716,14 @@ export default function SessionsZone() {
         }
       }
     } else if (activeSession.status === 'paused') {
-      // Paused session - pause capture and audio
-      console.log('⏸️ [SESSIONS ZONE] Session paused, pausing capture and audio');
+      // Paused session - pause capture, audio and video
+      console.log('⏸️ [SESSIONS ZONE] Session paused, pausing capture, audio and video');
       screenshotCaptureService.pauseCapture();
       audioRecordingService.pauseRecording();
+      videoRecordingService.pauseRecording()
+        .catch(error => {
+          console.error('❌ [SESSIONS ZONE] Failed to pause video recording:', error);
+        });
     } else if (activeSession.status === 'completed') {
       // Completed session - stop capture and audio, but allow grace period for pending audio
       console.log('⏹️ [SESSIONS ZONE] Session completed, stopping capture');
@@ -832,6 +844,8 @@ export default function SessionsZone() {
     let unlistenResume: (() => void) | undefined;
     let unlistenStop: (() => void) | undefined;
     let unlistenQuickCapture: (() => void) | undefined;
+    let unlistenControlStart: (() => void) | undefined;
+    let unlistenAutoStopped: (() => void) | undefined;
 
     const setupListeners = async () => {
       // Pause session from menu bar
@@ -858,7 +872,48 @@ export default function SessionsZone() {
         }
       });
 
-      // Quick capture screenshot (CMD+Shift+Space)
+      // Recording safety limit reached (recording_limits.rs already stopped the recorders;
+      // stop_video_recording still returns the finished file)
+      unlistenAutoStopped = await listenSessionAutoStopped((stopped) => {
+        console.warn(`🛑 [LIMITS] Session auto-stopped (${stopped.reason}, limit ${stopped.limit})`);
+        if (activeSession) {
+          handleEndSession(activeSession.id);
+        }
+      });
+
+      // Start session from taskerino-cli, AppleScript, a calendar meeting or a session preset
+      unlistenControlStart = await listen<{
+        name?: string | null;
+        description?: string | null;
+        tags?: string[] | null;
+        preset?: PresetStartOptions | null;
+      }>('control-start-session', (event) => {
+        console.log('📊 [CONTROL] Start session requested');
+        if (activeSession) {
+          console.warn('⚠️ [CONTROL] A session is already active - ignoring start');
+          return;
+        }
+        const preset = event.payload.preset;
+        startSession({
+          name: event.payload.name || 'CLI Session',
+          description: event.payload.description || '',
+          status: 'active',
+          screenshotInterval: preset?.screenshotInterval ?? 2,
+          enableScreenshots: preset?.enableScreenshots ?? true,
+          autoAnalysis: true,
+          tags: event.payload.tags ?? [],
+          audioRecording: preset?.audioRecording ?? false,
+          audioMode: preset?.audioRecording ? 'transcription' : 'off',
+          audioReviewCompleted: false,
+          videoRecording: preset?.videoRecording ?? false,
+          videoQuality: preset?.videoQuality ?? undefined,
+          videoDisplayIds: preset?.displayIds ?? undefined,
+          presetName: preset?.name,
+          aiPrompts: preset?.aiPrompts,
+        });
+      });
+
+      // Quick capture screenshot (CMD+Shift+Space, or taskerino-cli capture)
       unlistenQuickCapture = await listen<string>('quick-capture-screenshot', (event) => {
         console.log('📸 [QUICK CAPTURE] Screenshot captured');
         if (!activeSession) {
@@ -870,6 +925,7 @@ export default function SessionsZone() {
         (async () => {
           try {
             const base64Data = event.payload;
+            const mimeType = base64Data.match(/^data:([^;]+);/)?.[1] ?? 'image/png';
             const timestamp = new Date().toISOString();
             const screenshotId = `screenshot-${Date.now()}-${Math.random().toString(36).substring(7)}`;
             const attachmentId = `attachment-${Date.now()}-${Math.random().toString(36).substring(7)}`;
@@ -888,8 +944,8 @@ export default function SessionsZone() {
             const attachment = {
               id: attachmentId,
               type: 'screenshot' as const,
-              name: `Quick Capture ${new Date().toLocaleTimeString()}.png`,
-              mimeType: 'image/png',
+              name: `Quick Capture ${new Date().toLocaleTimeString()}.${mimeType === 'image/jpeg' ? 'jpg' : 'png'}`,
+              mimeType,
               size: base64Data.length,
               createdAt: timestamp,
               base64: base64Data,
@@ -916,8 +972,10 @@ export default function SessionsZone() {
       if (unlistenResume) unlistenResume();
       if (unlistenStop) unlistenStop();
       if (unlistenQuickCapture) unlistenQuickCapture();
+      if (unlistenControlStart) unlistenControlStart();
+      if (unlistenAutoStopped) unlistenAutoStopped();
     };
-  }, [activeSession, pauseSession, resumeSession, endSession, handleScreenshotCaptured]);
+  }, [activeSession, startSession, pauseSession, resumeSession, endSession, handleScreenshotCaptured]);
 
   /**
    * Listen for audio-chunk events from Rust audio recorder
@@ -943,10 +1001,10 @@ export default function SessionsZone() {
       // Mark listener as active
       audioListenerActiveRef.current = true;
 
-      const unlistenFn = await listen<{sessionId: string; audioBase64: string; duration: number}>('audio-chunk', async (event) => {
+      const unlistenFn = await listen<AudioChunkEvent>('audio-chunk', async (event) => {
         console.log('🎤 [AUDIO CHUNK] Received audio chunk from Rust');
 
-        const { sessionId, audioBase64, duration } = event.payload;
+        const { sessionId, duration } = event.payload;
 
         // Debug logging
         console.log('🎤 [AUDIO CHUNK] Payload sessionId:', sessionId);
@@ -973,11 +1031,15 @@ export default function SessionsZone() {
         // Process the audio chunk through OpenAI and create the segment
         // The audioRecordingService will create the SessionAudioSegment and call our callback
         try {
+          // Binary chunks are fetched as raw bytes; base64 chunks arrive inline
+          const audioBase64 = await resolveAudioChunk(event.payload);
           await audioRecordingService.processAudioChunk(
             audioBase64,
             duration,
             sessionId,
-            handler
+            handler,
+            event.payload.pauses,
+            event.payload.startedAt
           );
         } catch (error) {
           console.error('❌ [AUDIO CHUNK] Failed to process audio chunk:', error);
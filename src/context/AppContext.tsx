@@ -1704,8 +1704,8 @@ export function AppProvider({ children }: { children: ReactNode }) {
 
         console.log(`🔴 Critical action ${action.type} - saving immediately`);
         try {
+          // Sessions are saved (and logged) by SessionsContext
           const storage = await getStorage();
-          await storage.save('sessions', stateRef.current.sessions);
           await storage.save('settings', {
             aiSettings: stateRef.current.aiSettings,
             learningSettings: stateRef.current.learningSettings,
@@ -1878,8 +1878,8 @@ export function AppProvider({ children }: { children: ReactNode }) {
     const handleBeforeUnload = async () => {
       console.log('🛑 App closing - forcing final save');
       try {
+        // Sessions are saved by SessionsContext's own handler
         const storage = await getStorage();
-        await storage.save('sessions', stateRef.current.sessions);
         await storage.save('settings', {
           aiSettings: stateRef.current.aiSettings,
           learningSettings: stateRef.current.learningSettings,
@@ -1915,31 +1915,6 @@ export function AppProvider({ children }: { children: ReactNode }) {
     };
   }, []);
 
-  /**
-   * Periodic auto-save for active sessions - limits data loss to 30 seconds in case of crash
-   */
-  useEffect(() => {
-    if (!hasLoaded || !state.activeSessionId) return;
-
-    console.log('⏰ Starting periodic auto-save for active session');
-
-    const interval = setInterval(async () => {
-      console.log('⏰ Periodic auto-save for active session');
-      try {
-        const storage = await getStorage();
-        await storage.save('sessions', stateRef.current.sessions);
-        console.log('✅ Periodic auto-save complete');
-      } catch (error) {
-        console.error('❌ Periodic auto-save failed:', error);
-      }
-    }, 30000); // Every 30 seconds
-
-    return () => {
-      console.log('⏰ Stopping periodic auto-save');
-      clearInterval(interval);
-    };
-  }, [hasLoaded, state.activeSessionId]);
-
   /**
    * Load state from new storage system
    */
@@ -2131,7 +2106,7 @@ export function AppProvider({ children }: { children: ReactNode }) {
         storage.save('topics', state.topics),
         storage.save('notes', state.notes),
         storage.save('tasks', state.tasks),
-        storage.save('sessions', state.sessions),
+        // sessions: saved by SessionsContext, whose copy is the live one
         storage.save('settings', settings),
       ]);
 
@@ -2141,7 +2116,6 @@ export function AppProvider({ children }: { children: ReactNode }) {
         topics: state.topics.length,
         notes: state.notes.length,
         tasks: state.tasks.length,
-        sessions: state.sessions.length,
       });
     } catch (error) {
       console.error('❌ Failed to save to storage:', error);
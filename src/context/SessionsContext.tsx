@@ -1,12 +1,21 @@
 import React, { createContext, useContext, useReducer, useEffect, useRef } from 'react';
 import type { ReactNode } from 'react';
-import type { Session, SessionScreenshot, SessionAudioSegment, SessionContextItem } from '../types';
+import type { Session, SessionScreenshot, SessionAudioSegment, SessionContextItem, GitEvent, TrackerIssue, UrlVisit, AppFocus } from '../types';
 import { generateId } from '../utils/helpers';
-import { getStorage } from '../services/storage';
+import { getStorage, TauriFileSystemAdapter } from '../services/storage';
 import { attachmentStorage } from '../services/attachmentStorage';
 import { audioConcatenationService } from '../services/audioConcatenationService';
 import { keyMomentsDetectionService } from '../services/keyMomentsDetectionService';
 import { sessionEnrichmentService } from '../services/sessionEnrichmentService';
+import { listenGitActivity } from '../types/tauri-git-monitor';
+import { listenSessionImported } from '../types/tauri-export';
+import { listenReplayBufferSaved } from '../types/tauri-replay-buffer';
+import { listenTrackerIssuesCreated } from '../types/tauri-issue-tracker';
+import { listenAppFocusChanges, listenBrowserUrlVisits } from '../types/tauri-activity';
+import { listenUnchangedScreenshots } from '../types/tauri-screenshot-scheduler';
+import { listenSessionsSynced } from '../types/tauri-folder-sync';
+import { listenSessionsExpired } from '../types/tauri-storage-budget';
+import type { SessionChange } from '../types/tauri-performance-commands';
 import { useUI } from './UIContext';
 import { useEnrichmentContext } from './EnrichmentContext';
 
@@ -52,6 +61,14 @@ type SessionsAction =
   | { type: 'ADD_EXTRACTED_TASK_TO_SESSION'; payload: { sessionId: string; taskId: string } }
   | { type: 'ADD_EXTRACTED_NOTE_TO_SESSION'; payload: { sessionId: string; noteId: string } }
   | { type: 'ADD_SESSION_CONTEXT_ITEM'; payload: { sessionId: string; contextItem: SessionContextItem } }
+  | { type: 'ADD_SESSION_GIT_EVENTS'; payload: { sessionId: string; events: GitEvent[] } }
+  | { type: 'ADD_IMPORTED_SESSION'; payload: Session }
+  | { type: 'ADD_SESSION_TRACKER_ISSUES'; payload: { sessionId: string; issues: TrackerIssue[] } }
+  | { type: 'ADD_SESSION_URL_VISITS'; payload: { sessionId: string; visits: UrlVisit[] } }
+  | { type: 'ADD_SESSION_APP_FOCUS'; payload: { sessionId: string; changes: AppFocus[] } }
+  | { type: 'MARK_SCREENSHOT_UNCHANGED'; payload: { sessionId: string; attachmentId: string; timestamp: string } }
+  | { type: 'APPLY_SYNCED_SESSIONS'; payload: { updated: Session[]; deleted: string[] } }
+  | { type: 'REMOVE_EXPIRED_SESSIONS'; payload: string[] }
   | { type: 'UPDATE_CONTEXT_ITEM'; payload: { sessionId: string; contextItemId: string; content: string } }
   | { type: 'DELETE_CONTEXT_ITEM'; payload: { sessionId: string; contextItemId: string } }
   | { type: 'LOAD_SESSIONS'; payload: Partial<SessionsState> }
@@ -351,6 +368,117 @@ function sessionsReducer(state: SessionsState, action: SessionsAction): Sessions
       };
     }
 
+    case 'ADD_SESSION_GIT_EVENTS': {
+      // Also written to storage by git_monitor.rs; merged here so our own saves keep them
+      const { sessionId, events } = action.payload;
+      const eventKey = (e: GitEvent) => `${e.repo}|${e.kind}|${e.hash}|${e.timestamp}`;
+      return {
+        ...state,
+        sessions: state.sessions.map(session => {
+          if (session.id !== sessionId) return session;
+          const existing = new Set((session.gitEvents || []).map(eventKey));
+          const added = events.filter(e => !existing.has(eventKey(e)));
+          return added.length > 0
+            ? { ...session, gitEvents: [...(session.gitEvents || []), ...added] }
+            : session;
+        }),
+      };
+    }
+
+    case 'ADD_IMPORTED_SESSION': {
+      // Already written to storage by session_archive.rs / replay_buffer.rs; added here so our own saves keep it
+      if (state.sessions.some(session => session.id === action.payload.id)) return state;
+      return { ...state, sessions: [...state.sessions, action.payload] };
+    }
+
+    case 'ADD_SESSION_TRACKER_ISSUES': {
+      // Already written to storage by issue_tracker.rs; merged here so our own saves keep them
+      const { sessionId, issues } = action.payload;
+      return {
+        ...state,
+        sessions: state.sessions.map(session => {
+          if (session.id !== sessionId) return session;
+          const existing = new Set((session.trackerIssues || []).map(issue => issue.issueId));
+          const added = issues.filter(issue => !existing.has(issue.issueId));
+          return added.length > 0
+            ? { ...session, trackerIssues: [...(session.trackerIssues || []), ...added] }
+            : session;
+        }),
+      };
+    }
+
+    case 'ADD_SESSION_URL_VISITS': {
+      const { sessionId, visits } = action.payload;
+      return {
+        ...state,
+        sessions: state.sessions.map(session =>
+          session.id === sessionId
+            ? { ...session, urlTimeline: [...(session.urlTimeline || []), ...visits] }
+            : session
+        ),
+      };
+    }
+
+    case 'ADD_SESSION_APP_FOCUS': {
+      const { sessionId, changes } = action.payload;
+      return {
+        ...state,
+        sessions: state.sessions.map(session =>
+          session.id === sessionId
+            ? { ...session, appTimeline: [...(session.appTimeline || []), ...changes] }
+            : session
+        ),
+      };
+    }
+
+    case 'APPLY_SYNCED_SESSIONS': {
+      // Already written to storage by folder_sync.rs; applied here so our own saves keep them
+      const { updated, deleted } = action.payload;
+      const updatedById = new Map(updated.map(session => [session.id, session]));
+      const sessions = state.sessions
+        .filter(session => !deleted.includes(session.id))
+        .map(session => updatedById.get(session.id) ?? session);
+      const added = updated.filter(session => !state.sessions.some(existing => existing.id === session.id));
+      return {
+        ...state,
+        sessions: [...sessions, ...added],
+        activeSessionId: state.activeSessionId && deleted.includes(state.activeSessionId) ? undefined : state.activeSessionId,
+      };
+    }
+
+    case 'REMOVE_EXPIRED_SESSIONS': {
+      // Already deleted from storage by storage_budget.rs (profile retention); removed here so our own saves don't restore them
+      const expired = action.payload;
+      return {
+        ...state,
+        sessions: state.sessions.filter(session => !expired.includes(session.id)),
+        activeSessionId: state.activeSessionId && expired.includes(state.activeSessionId) ? undefined : state.activeSessionId,
+      };
+    }
+
+    case 'MARK_SCREENSHOT_UNCHANGED': {
+      const { sessionId, attachmentId, timestamp } = action.payload;
+      return {
+        ...state,
+        sessions: state.sessions.map(session =>
+          session.id === sessionId
+            ? {
+                ...session,
+                screenshots: (session.screenshots || []).map(screenshot =>
+                  screenshot.attachmentId === attachmentId
+                    ? {
+                        ...screenshot,
+                        unchangedUntil: timestamp,
+                        unchangedCount: (screenshot.unchangedCount || 0) + 1,
+                      }
+                    : screenshot
+                ),
+              }
+            : session
+        ),
+      };
+    }
+
     case 'LOAD_SESSIONS':
       return { ...state, ...action.payload };
 
@@ -402,18 +530,61 @@ const CRITICAL_ACTIONS = new Set([
   'DELETE_SESSION',
   'START_SESSION',
   'UPDATE_SESSION',
-  'ADD_SESSION_SCREENSHOT',
-  'ADD_SESSION_AUDIO_SEGMENT',
   'UPDATE_SCREENSHOT_ANALYSIS',
-  'ADD_SESSION_CONTEXT_ITEM',
   'MARK_SESSION_INTERRUPTED',
 ]);
 
+interface LoggedChanges {
+  sessionId: string;
+  changes: SessionChange[];
+}
+
+/**
+ * Changes an action makes to one session that are appended to the session's
+ * change log instead of saving every session; null when a full save is needed
+ */
+function loggedChanges(action: SessionsAction): LoggedChanges | null {
+  switch (action.type) {
+    case 'ADD_SESSION_SCREENSHOT': {
+      const { sessionId, screenshot } = action.payload;
+      return {
+        sessionId,
+        changes: [
+          { op: 'append', field: 'screenshots', items: [screenshot] },
+          { op: 'set', field: 'lastScreenshotTime', value: screenshot.timestamp },
+        ],
+      };
+    }
+    case 'ADD_SESSION_AUDIO_SEGMENT': {
+      const { sessionId, audioSegment } = action.payload;
+      return { sessionId, changes: [{ op: 'append', field: 'audioSegments', items: [audioSegment] }] };
+    }
+    case 'ADD_SESSION_CONTEXT_ITEM': {
+      const { sessionId, contextItem } = action.payload;
+      return { sessionId, changes: [{ op: 'append', field: 'contextItems', items: [contextItem] }] };
+    }
+    case 'ADD_SESSION_URL_VISITS': {
+      const { sessionId, visits } = action.payload;
+      return { sessionId, changes: [{ op: 'append', field: 'urlTimeline', items: visits }] };
+    }
+    case 'ADD_SESSION_APP_FOCUS': {
+      const { sessionId, changes } = action.payload;
+      return { sessionId, changes: [{ op: 'append', field: 'appTimeline', items: changes }] };
+    }
+    default:
+      return null;
+  }
+}
+
 export function SessionsProvider({ children }: { children: ReactNode }) {
   const [state, baseDispatch] = useReducer(sessionsReducer, initialState);
   const [hasLoaded, setHasLoaded] = React.useState(false);
   const saveTimeoutRef = useRef<NodeJS.Timeout | null>(null);
   const stateRef = useRef(state);
+  // Changes waiting to be logged once the state has them
+  const pendingLogRef = useRef<LoggedChanges[]>([]);
+  // Set by changes that only a full save stores (logged changes don't need one)
+  const unsavedChangesRef = useRef(false);
   const { addNotification } = useUI();
   const { startTracking, updateProgress, stopTracking } = useEnrichmentContext();
 
@@ -447,6 +618,39 @@ export function SessionsProvider({ children }: { children: ReactNode }) {
     stateRef.current = state;
   }, [state]);
 
+  // Log changes after the render that applies them, so any save queued after
+  // the log entry already has them (saves replace the session's log)
+  useEffect(() => {
+    const pending = pendingLogRef.current.splice(0);
+    if (pending.length === 0) return;
+
+    (async () => {
+      const storage = await getStorage();
+      if (!(storage instanceof TauriFileSystemAdapter)) {
+        // No change logs outside Tauri: save everything instead
+        unsavedChangesRef.current = true;
+        return;
+      }
+      for (const { sessionId, changes } of pending) {
+        if (!state.sessions.some(session => session.id === sessionId)) continue;
+        storage.appendSessionChanges(sessionId, changes).catch(error => {
+          console.error(`Failed to log changes to session ${sessionId}, falling back to a full save:`, error);
+          unsavedChangesRef.current = true;
+        });
+      }
+    })();
+  }, [state.sessions]);
+
+  // Queue an action's changes for logging, or mark the store as needing a save
+  const trackChanges = React.useCallback((action: SessionsAction) => {
+    const logged = loggedChanges(action);
+    if (logged) {
+      pendingLogRef.current.push(logged);
+    } else if (action.type !== 'LOAD_SESSIONS') {
+      unsavedChangesRef.current = true;
+    }
+  }, []);
+
   // Audio chunk queue processor (Fix #7: Backpressure handling)
   const processAudioChunkQueue = React.useCallback(async (sessionId: string) => {
     if (processingAudioRef.current.get(sessionId)) return; // Already processing
@@ -461,18 +665,21 @@ export function SessionsProvider({ children }: { children: ReactNode }) {
       if (!chunk) break;
 
       // Dispatch to reducer
-      baseDispatch({ type: 'ADD_SESSION_AUDIO_SEGMENT', payload: { sessionId, audioSegment: chunk } });
+      const action: SessionsAction = { type: 'ADD_SESSION_AUDIO_SEGMENT', payload: { sessionId, audioSegment: chunk } };
+      trackChanges(action);
+      baseDispatch(action);
 
       // Small delay to prevent overwhelming storage
       await new Promise(resolve => setTimeout(resolve, 10));
     }
 
     processingAudioRef.current.set(sessionId, false);
-  }, []);
+  }, [trackChanges]);
 
   // Custom dispatch wrapper that immediately saves for critical actions
   const dispatch = React.useCallback((action: SessionsAction) => {
     console.log('[DISPATCH] Action fired:', action.type, action.payload);
+    trackChanges(action);
     baseDispatch(action);
 
     // If critical action, save immediately (after state update)
@@ -647,7 +854,7 @@ export function SessionsProvider({ children }: { children: ReactNode }) {
         }
       });
     }
-  }, [startTracking, updateProgress, stopTracking]);
+  }, [trackChanges, startTracking, updateProgress, stopTracking]);
 
   // Load sessions on mount
   useEffect(() => {
@@ -774,13 +981,111 @@ export function SessionsProvider({ children }: { children: ReactNode }) {
     };
   }, []);
 
+  // Commits / branch switches recorded by the git monitor during the active session
+  useEffect(() => {
+    const unlisten = listenGitActivity(({ sessionId, events }) => {
+      dispatch({ type: 'ADD_SESSION_GIT_EVENTS', payload: { sessionId, events } });
+    });
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, []);
+
+  // Sessions imported from an archive (import_session_archive)
+  useEffect(() => {
+    const unlisten = listenSessionImported(({ session }) => {
+      dispatch({ type: 'ADD_IMPORTED_SESSION', payload: session });
+    });
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, []);
+
+  // Sessions saved from the replay buffer (save_replay_buffer)
+  useEffect(() => {
+    const unlisten = listenReplayBufferSaved(({ session }) => {
+      dispatch({ type: 'ADD_IMPORTED_SESSION', payload: session });
+    });
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, []);
+
+  // Linear/Jira issues created from a session's action items (create_tasks_from_session)
+  useEffect(() => {
+    const unlisten = listenTrackerIssuesCreated(({ sessionId, created }) => {
+      dispatch({ type: 'ADD_SESSION_TRACKER_ISSUES', payload: { sessionId, issues: created } });
+    });
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, []);
+
+  // Frontmost app / window changes during the active session
+  useEffect(() => {
+    const unlisten = listenAppFocusChanges(changes => {
+      const sessionId = stateRef.current.activeSessionId;
+      if (!sessionId) return;
+      dispatch({ type: 'ADD_SESSION_APP_FOCUS', payload: { sessionId, changes } });
+    });
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, []);
+
+  // Browser tabs visited during the active session (settings.activity.captureBrowserUrls)
+  useEffect(() => {
+    const unlisten = listenBrowserUrlVisits(visits => {
+      const sessionId = stateRef.current.activeSessionId;
+      if (!sessionId) return;
+      dispatch({ type: 'ADD_SESSION_URL_VISITS', payload: { sessionId, visits } });
+    });
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, []);
+
+  // Scheduled captures that matched the last stored screenshot
+  useEffect(() => {
+    const unlisten = listenUnchangedScreenshots(({ sessionId, attachmentId, timestamp }) => {
+      dispatch({ type: 'MARK_SCREENSHOT_UNCHANGED', payload: { sessionId, attachmentId, timestamp } });
+    });
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, []);
+
+  // Sessions pulled from (or deleted via) the sync folder
+  useEffect(() => {
+    const unlisten = listenSessionsSynced(({ updated, deleted }) => {
+      dispatch({ type: 'APPLY_SYNCED_SESSIONS', payload: { updated, deleted } });
+    });
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, []);
+
+  // Sessions deleted by the active profile's retention policy
+  useEffect(() => {
+    const unlisten = listenSessionsExpired(({ sessionIds }) => {
+      dispatch({ type: 'REMOVE_EXPIRED_SESSIONS', payload: sessionIds });
+    });
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, []);
+
   // Periodic auto-save for active sessions - limits data loss to 30 seconds
+  // (screenshots, audio and timelines are logged as they arrive)
   useEffect(() => {
     if (!hasLoaded || !state.activeSessionId) return;
 
     console.log('Starting periodic auto-save for active session');
 
     const interval = setInterval(async () => {
+      // Left set for the debounced save, whose snapshot is never stale
+      if (!unsavedChangesRef.current) return;
+
       console.log('Periodic auto-save for active session');
       try {
         const storage = await getStorage();
@@ -818,6 +1123,10 @@ export function SessionsProvider({ children }: { children: ReactNode }) {
           return;
         }
 
+        // Only logged changes since the last save: they're already stored
+        if (!unsavedChangesRef.current) return;
+        unsavedChangesRef.current = false;
+
         const storage = await getStorage();
         await storage.save('sessions', state.sessions);
 
@@ -831,6 +1140,7 @@ export function SessionsProvider({ children }: { children: ReactNode }) {
         console.log('Sessions saved');
       } catch (error) {
         console.error('Failed to save sessions:', error);
+        unsavedChangesRef.current = true;
       }
     }, 5000);
 
@@ -13,6 +13,7 @@
 
 import { BaseDirectory, exists, readTextFile, writeTextFile, mkdir, remove, readDir } from '@tauri-apps/plugin-fs';
 import type { Attachment } from '../types';
+import { clearAttachmentThumbnails } from '../types/tauri-thumbnails';
 
 interface CacheEntry {
   attachment: Attachment;
@@ -233,6 +234,12 @@ class AttachmentStorageService {
       // Save base64 data separately (if present)
       if (attachment.base64) {
         const dataPath = `${this.ATTACHMENTS_DIR}/${attachment.id}.dat`;
+        // Existing data may be a hard link into the shared blob store
+        // (session_storage.rs) - writing through it would change every
+        // attachment with the same content
+        if (await exists(dataPath, { baseDir: BaseDirectory.AppData })) {
+          await remove(dataPath, { baseDir: BaseDirectory.AppData });
+        }
         await writeTextFile(dataPath, attachment.base64, { baseDir: BaseDirectory.AppData });
       }
 
@@ -332,6 +339,11 @@ class AttachmentStorageService {
         await remove(dataPath, { baseDir: BaseDirectory.AppData });
       }
 
+      // Cached thumbnails are also swept in the background, so don't fail the delete
+      await clearAttachmentThumbnails([id]).catch((error) => {
+        console.warn(`Failed to clear thumbnails for ${id}:`, error);
+      });
+
       console.log(`✅ Attachment deleted: ${id}`);
     } catch (error) {
       console.error(`Failed to delete attachment ${id}:`, error);
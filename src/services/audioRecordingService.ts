@@ -11,7 +11,7 @@
  */
 
 import { invoke } from '@tauri-apps/api/core';
-import type { Session, SessionAudioSegment, AudioMode } from '../types';
+import type { Session, SessionAudioSegment, AudioMode, AudioPauseGap } from '../types';
 import { generateId } from '../utils/helpers';
 import { openAIService } from './openAIService';
 import { audioStorageService } from './audioStorageService';
@@ -20,6 +20,7 @@ import { audioCompressionService } from './audioCompressionService';
 export class AudioRecordingService {
   private activeSessionId: string | null = null;
   private isRecording: boolean = false;
+  private isPaused: boolean = false;
   private segmentCounter: number = 0; // Track chunk index for storage
 
   /**
@@ -44,6 +45,7 @@ export class AudioRecordingService {
 
     this.activeSessionId = session.id;
     this.isRecording = true;
+    this.isPaused = false;
 
     // Calculate audio chunk duration based on screenshot mode
     let chunkDurationSecs: number;
@@ -86,7 +88,7 @@ export class AudioRecordingService {
    * Keeps session ID active for 5 seconds to allow pending chunks to complete
    */
   async stopRecording(): Promise<void> {
-    if (!this.isRecording) {
+    if (!this.isRecording && !this.isPaused) {
       return;
     }
 
@@ -95,6 +97,7 @@ export class AudioRecordingService {
     try {
       await invoke('stop_audio_recording');
       this.isRecording = false;
+      this.isPaused = false;
 
       // Keep accepting audio chunks for 5 more seconds (grace period for in-flight chunks)
       const sessionIdToKeep = this.activeSessionId;
@@ -125,12 +128,25 @@ export class AudioRecordingService {
     try {
       await invoke('pause_audio_recording');
       this.isRecording = false;
+      this.isPaused = true;
       console.log('✅ [AUDIO SERVICE] Audio recording paused');
     } catch (error) {
       console.error('❌ [AUDIO SERVICE] Failed to pause audio recording:', error);
     }
   }
 
+  /**
+   * Toggle mic noise suppression (applies immediately while recording)
+   */
+  async setNoiseSuppression(enabled: boolean): Promise<void> {
+    try {
+      await invoke('set_noise_suppression', { enabled });
+      console.log(`🎤 [AUDIO SERVICE] Noise suppression ${enabled ? 'enabled' : 'disabled'}`);
+    } catch (error) {
+      console.error('❌ [AUDIO SERVICE] Failed to set noise suppression:', error);
+    }
+  }
+
   /**
    * Resume audio recording
    */
@@ -143,6 +159,19 @@ export class AudioRecordingService {
     }
 
     console.log('▶️  [AUDIO SERVICE] Resuming audio recording');
+
+    // Continue the paused capture so the pause is recorded as a gap in the chunk
+    if (this.isPaused && this.activeSessionId === session.id) {
+      try {
+        await invoke('resume_audio_recording');
+        this.isRecording = true;
+        this.isPaused = false;
+        console.log('✅ [AUDIO SERVICE] Audio recording resumed');
+        return;
+      } catch (error) {
+        console.warn('⚠️  [AUDIO SERVICE] Failed to resume, restarting capture:', error);
+      }
+    }
     await this.startRecording(session, onAudioSegmentProcessed);
   }
 
@@ -160,7 +189,9 @@ export class AudioRecordingService {
     audioBase64: string,
     duration: number,
     sessionId: string,
-    onAudioSegmentProcessed: (segment: SessionAudioSegment) => void
+    onAudioSegmentProcessed: (segment: SessionAudioSegment) => void,
+    pauses?: AudioPauseGap[],
+    startedAt?: string
   ): Promise<void> {
     if (!this.activeSessionId || this.activeSessionId !== sessionId) {
       console.warn('⚠️  [AUDIO SERVICE] Received audio for inactive session, ignoring');
@@ -190,7 +221,9 @@ export class AudioRecordingService {
       // 3. Transcribe audio using OpenAI Whisper-1 (with compressed version)
       const transcription = await openAIService.transcribeAudio(compressedAudio);
 
-      const timestamp = new Date().toISOString();
+      // Chunk start (what subtitle and transcript alignment expect); older
+      // backends don't send it, so fall back to now
+      const timestamp = startedAt ?? new Date().toISOString();
 
       console.log(`📝 [AUDIO SERVICE] Transcription: "${transcription.substring(0, 100)}..."`);
 
@@ -202,6 +235,7 @@ export class AudioRecordingService {
         duration,
         transcription,
         attachmentId: audioAttachment.id, // Link to stored audio file
+        ...(pauses?.length ? { pauses } : {}),
       };
 
       console.log(`✅ [AUDIO SERVICE] Audio segment created: ${segment.id}`);
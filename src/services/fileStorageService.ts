@@ -1,5 +1,9 @@
 import { BaseDirectory, mkdir, readFile, writeFile, exists, remove } from '@tauri-apps/plugin-fs';
 import type { Attachment, AttachmentType } from '../types';
+import { uploadMediaFile } from '../types/tauri-media-commands';
+
+/** Files above this size are streamed to Rust in chunks instead of one IPC payload */
+const STREAMED_UPLOAD_THRESHOLD = 16 * 1024 * 1024;
 
 export class FileStorageService {
   private baseDir = 'attachments';
@@ -45,7 +49,11 @@ export class FileStorageService {
     const path = `${this.baseDir}/${subDir}/${fileName}`;
 
     try {
-      await writeFile(path, fileData, { baseDir: BaseDirectory.AppData });
+      if (fileData.byteLength > STREAMED_UPLOAD_THRESHOLD) {
+        await uploadMediaFile(fileData, path);
+      } else {
+        await writeFile(path, fileData, { baseDir: BaseDirectory.AppData });
+      }
       console.log(`✅ Saved attachment to ${path}`);
       return path;
     } catch (error) {
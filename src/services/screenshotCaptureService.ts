@@ -4,17 +4,26 @@ import { generateId } from '../utils/helpers';
 import { createThumbnail, getBase64Size } from '../utils/imageCompression';
 import { attachmentStorage } from './attachmentStorage';
 import { adaptiveScreenshotScheduler } from './adaptiveScreenshotScheduler';
+import type { UnlistenFn } from '@tauri-apps/api/event';
+import {
+  listenScheduledScreenshots,
+  pauseScreenshotScheduler,
+  resumeScreenshotScheduler,
+  startScreenshotScheduler,
+  stopScreenshotScheduler,
+} from '../types/tauri-screenshot-scheduler';
 
 /**
  * ScreenshotCaptureService
  *
  * Manages automatic screenshot capture during active sessions.
- * - Captures screens at configured intervals
+ * - Captures screens at configured intervals (fixed intervals are scheduled
+ *   natively in Rust so a throttled webview doesn't miss captures)
  * - Stores screenshots as attachments (uses Tauri file system APIs)
  * - Triggers AI analysis via SessionsAgentService
  */
 export class ScreenshotCaptureService {
-  private captureInterval: NodeJS.Timeout | null = null;
+  private unlistenScheduled: UnlistenFn | null = null; // Set while the native (fixed interval) scheduler runs
   private activeSessionId: string | null = null;
   private intervalMinutes: number = 2;
   private isAdaptiveMode: boolean = false; // Track if using adaptive scheduler
@@ -126,25 +135,25 @@ export class ScreenshotCaptureService {
       console.log(`📸 [CAPTURE SERVICE] Using FIXED interval: every ${this.intervalMinutes} minutes for session "${session.name}"`);
       console.log(`🔵 [CAPTURE SERVICE] Active session ID set to: ${this.activeSessionId}`);
 
-      // Delay first screenshot by 3 seconds to give user time to navigate away
-      console.log(`⏱️ [CAPTURE SERVICE] Setting 3-second timeout for first screenshot...`);
-      setTimeout(() => {
-        if (this.activeSessionId === session.id) {
-          console.log('📸 [CAPTURE SERVICE] Capturing first screenshot (delayed 3s)...');
-          this.captureAndProcess(onScreenshotCaptured);
-        } else {
-          console.log('⚠️ [CAPTURE SERVICE] Session ID changed, skipping first screenshot');
-        }
-      }, 3000);
+      // Rust captures, stores the attachment and updates the menubar countdown;
+      // we only turn each capture into a screenshot record
+      this.unlistenScheduled = await listenScheduledScreenshots((event) => {
+        if (event.sessionId !== this.activeSessionId) return;
+        console.log(`📸 [CAPTURE SERVICE] Scheduled screenshot captured (${Math.round(event.size / 1024)}KB)`);
+        onScreenshotCaptured({
+          id: event.screenshotId,
+          sessionId: event.sessionId,
+          timestamp: event.timestamp,
+          attachmentId: event.attachmentId,
+          analysisStatus: 'pending',
+          flagged: false,
+        });
+      });
 
-      // Set up interval for subsequent captures
-      console.log(`⏱️ [CAPTURE SERVICE] Setting up interval timer...`);
-      this.captureInterval = setInterval(() => {
-        console.log('⏰ [CAPTURE SERVICE] Interval fired, capturing screenshot...');
-        this.captureAndProcess(onScreenshotCaptured);
-      }, this.intervalMinutes * 60 * 1000);
+      // First capture is delayed 3 seconds to give user time to navigate away
+      await startScreenshotScheduler(session.id, this.intervalMinutes);
 
-      console.log(`✅ [CAPTURE SERVICE] Fixed interval capture started. Interval ID: ${this.captureInterval}`);
+      console.log('✅ [CAPTURE SERVICE] Native fixed interval scheduler started');
     }
   }
 
@@ -152,10 +161,13 @@ export class ScreenshotCaptureService {
    * Stop automatic screenshot capture
    */
   stopCapture(skipMenubarUpdate: boolean = false): void {
-    // Stop fixed interval if active
-    if (this.captureInterval) {
-      clearInterval(this.captureInterval);
-      this.captureInterval = null;
+    // Stop native fixed interval scheduler if active
+    if (this.unlistenScheduled) {
+      this.unlistenScheduled();
+      this.unlistenScheduled = null;
+      stopScreenshotScheduler().catch((error) => {
+        console.error('❌ Failed to stop screenshot scheduler:', error);
+      });
       console.log('📸 Stopped fixed interval screenshot capture');
     }
 
@@ -183,10 +195,11 @@ export class ScreenshotCaptureService {
    * Pause automatic screenshot capture (keeps session ID)
    */
   pauseCapture(): void {
-    // Pause fixed interval if active
-    if (this.captureInterval) {
-      clearInterval(this.captureInterval);
-      this.captureInterval = null;
+    // Pause native fixed interval scheduler if active
+    if (this.unlistenScheduled) {
+      pauseScreenshotScheduler().catch((error) => {
+        console.error('❌ Failed to pause screenshot scheduler:', error);
+      });
       console.log('⏸️  Paused fixed interval screenshot capture');
     }
 
@@ -232,15 +245,11 @@ export class ScreenshotCaptureService {
         // Resume adaptive scheduler
         adaptiveScreenshotScheduler.resume();
         console.log('▶️  Resumed adaptive scheduler');
-      } else if (!this.captureInterval) {
-        // Resume fixed interval
-        // Capture immediately on resume
-        this.captureAndProcess(onScreenshotCaptured);
-
-        // Restart interval
-        this.captureInterval = setInterval(() => {
-          this.captureAndProcess(onScreenshotCaptured);
-        }, this.intervalMinutes * 60 * 1000);
+      } else if (this.unlistenScheduled) {
+        // Resume native fixed interval scheduler (continues the paused countdown)
+        resumeScreenshotScheduler().catch((error) => {
+          console.error('❌ Failed to resume screenshot scheduler:', error);
+        });
         console.log('▶️  Resumed fixed interval capture');
       }
     }
@@ -298,6 +307,17 @@ export class ScreenshotCaptureService {
     }
   }
 
+  /**
+   * Capture a region of one display (coordinates relative to that display;
+   * the primary display when displayId is omitted). Returns a data URL.
+   */
+  async captureRegion(
+    region: { x: number; y: number; width: number; height: number; displayId?: number },
+    format: 'png' | 'jpeg' = 'jpeg'
+  ): Promise<string> {
+    return invoke<string>('capture_screen_region', { ...region, format });
+  }
+
   /**
    * Get information about available screens
    */
@@ -423,7 +443,7 @@ export class ScreenshotCaptureService {
    * Check if capture is currently active (either fixed interval or adaptive)
    */
   isCapturing(): boolean {
-    return this.captureInterval !== null || adaptiveScreenshotScheduler.isActive();
+    return this.unlistenScheduled !== null || adaptiveScreenshotScheduler.isActive();
   }
 
   /**
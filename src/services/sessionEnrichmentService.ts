@@ -61,6 +61,8 @@ import { generateId } from '../utils/helpers';
 import { generateFlexibleSummary } from '../utils/sessionSynthesis';
 import { invoke } from '@tauri-apps/api/core';
 import { aiCanvasGenerator } from './aiCanvasGenerator';
+import { autoExportSession } from '../types/tauri-export';
+import { sendNotification } from '../types/tauri-notifications';
 
 // ============================================================================
 // Types & Interfaces
@@ -623,6 +625,15 @@ export class SessionEnrichmentService {
             summaryCompleted: result.summary?.completed,
           });
 
+          if (result.success) {
+            sendNotification(
+              'enrichmentFinished',
+              'Session enriched',
+              `"${session.name}" is ready to review.`,
+              session.id
+            ).catch((notifyError) => logger.warn('Enrichment notification failed', notifyError));
+          }
+
           return result;
         } catch (error: any) {
           // Update checkpoint with error
@@ -1712,6 +1723,17 @@ export class SessionEnrichmentService {
           result.warnings.push('Chapter verification failed - chapters may still be saved correctly');
         }
       }
+
+      // Markdown auto-export (no-op unless settings.export.autoExportMarkdown is on)
+      try {
+        const exported = await autoExportSession(session.id);
+        if (exported) {
+          logger.info('✅ Exported session note', { path: exported.path });
+        }
+      } catch (exportError: any) {
+        logger.warn('⚠️ Markdown auto-export failed (non-fatal)', exportError);
+        result.warnings.push('Markdown auto-export failed');
+      }
     } catch (error: any) {
       logger.error('Failed to update session', error);
       throw error;
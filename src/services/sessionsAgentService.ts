@@ -1,5 +1,5 @@
 import { invoke } from '@tauri-apps/api/core';
-import type { Session, SessionScreenshot, SessionAudioSegment, Note, VideoChapter, AudioInsights } from '../types';
+import type { Session, SessionScreenshot, SessionAudioSegment, Note, VideoChapter, AudioInsights, GitEvent, UrlVisit } from '../types';
 import type {
   ClaudeChatResponse,
   ClaudeMessage,
@@ -101,8 +101,11 @@ export class SessionsAgentService {
       // Get session context
       const sessionContext = this.buildSessionContext(session);
 
-      // Build prompt
-      const prompt = this.buildAnalysisPrompt(session, sessionContext);
+      // Build prompt (plus the session preset's instructions, if any)
+      let prompt = this.buildAnalysisPrompt(session, sessionContext);
+      if (session.aiPrompts?.screenshotAnalysis) {
+        prompt += `\n\nAdditional instructions for this session:\n${session.aiPrompts.screenshotAnalysis}`;
+      }
 
       // Extract base64 data from data URL if needed
       let base64Data = screenshotBase64;
@@ -312,17 +315,25 @@ export class SessionsAgentService {
     console.log(`📊 SessionsAgent: Generating session summary for "${session.name}"...`);
 
     try {
-      // Merge screenshots and audio segments chronologically
+      // Merge screenshots, audio segments and git activity chronologically
       type TimelineItem =
         | { type: 'screenshot'; timestamp: string; data: SessionScreenshot }
-        | { type: 'audio'; timestamp: string; data: SessionAudioSegment };
+        | { type: 'audio'; timestamp: string; data: SessionAudioSegment }
+        | { type: 'git'; timestamp: string; data: GitEvent }
+        | { type: 'url'; timestamp: string; data: UrlVisit };
 
+      const gitEvents = session.gitEvents || [];
+      const urlTimeline = session.urlTimeline || [];
       const timelineItems: TimelineItem[] = [
         ...screenshots
           .filter(s => s.aiAnalysis)
           .map(s => ({ type: 'screenshot' as const, timestamp: s.timestamp, data: s })),
         ...audioSegments
-          .map(a => ({ type: 'audio' as const, timestamp: a.timestamp, data: a }))
+          .map(a => ({ type: 'audio' as const, timestamp: a.timestamp, data: a })),
+        ...gitEvents
+          .map(g => ({ type: 'git' as const, timestamp: g.timestamp, data: g })),
+        ...urlTimeline
+          .map(u => ({ type: 'url' as const, timestamp: u.timestamp, data: u }))
       ].sort((a, b) => new Date(a.timestamp).getTime() - new Date(b.timestamp).getTime());
 
       // Collect all analyses (screenshots + audio transcripts)
@@ -336,6 +347,19 @@ export class SessionsAgentService {
 - Summary: ${s.aiAnalysis?.summary || 'No summary'}
 - Key Elements: ${s.aiAnalysis?.keyElements?.join(', ') || 'None'}
 ${s.userComment ? `- User Comment: ${s.userComment}` : ''}
+`;
+          } else if (item.type === 'url') {
+            const u = item.data;
+            return `**Browser** (${new Date(u.timestamp).toLocaleTimeString()}) ${u.title || 'Untitled'} — ${u.url}`;
+          } else if (item.type === 'git') {
+            const g = item.data;
+            return g.kind === 'commit'
+              ? `
+**Git Commit** (${new Date(g.timestamp).toLocaleTimeString()}) in ${g.repoName} on ${g.branch}
+- ${g.hash?.slice(0, 7)}: "${g.message}"
+`
+              : `
+**Git Branch Switch** (${new Date(g.timestamp).toLocaleTimeString()}) in ${g.repoName}: ${g.previousBranch} → ${g.branch}
 `;
           } else {
             const a = item.data as SessionAudioSegment;
@@ -423,9 +447,9 @@ ${(audioInsights.keyMoments || []).map(m => {
 
 **Session Duration:** ${this.calculateDuration(session)} minutes
 
-**Data Captured:** ${screenshotCount} screenshots, ${audioCount} audio segments${hasVideoChapters ? `, ${videoChapters.length} video chapters` : ''}${hasAudioInsights ? ', audio insights' : ''}
+**Data Captured:** ${screenshotCount} screenshots, ${audioCount} audio segments${gitEvents.length > 0 ? `, ${gitEvents.length} git events` : ''}${urlTimeline.length > 0 ? `, ${urlTimeline.length} browser pages` : ''}${hasVideoChapters ? `, ${videoChapters.length} video chapters` : ''}${hasAudioInsights ? ', audio insights' : ''}
 
-**Timeline Data (chronological - screenshots + audio transcripts):**
+**Timeline Data (chronological - screenshots + audio transcripts${gitEvents.length > 0 ? ' + git commits' : ''}${urlTimeline.length > 0 ? ' + browser pages' : ''}):**
 ${analyses}${videoChaptersSection}${audioInsightsSection}
 
 **Your Task:**
@@ -444,6 +468,8 @@ You have up to 4 complementary data sources to analyze:
 - Use audio transcripts to understand intent, frustrations, goals, and verbal context
 ${hasVideoChapters ? '- Use video chapters to understand the overall narrative arc and major transitions' : ''}
 ${hasAudioInsights ? '- Use audio insights to understand emotional state, work quality, and critical moments' : ''}
+${gitEvents.length > 0 ? '- Use git commits as concrete evidence of what was shipped, and connect them to what was said or shown just before' : ''}
+${urlTimeline.length > 0 ? '- Use browser pages to name the docs, tickets and tools the user was reading (each entry is when a tab became active)' : ''}
 - Cross-reference all sources to build a complete picture (e.g., emotional frustration in audio + error message in screenshot = blocker)
 - Filter out irrelevant audio (background noise, off-topic discussions) using your judgment
 
@@ -3,6 +3,14 @@
  *
  * Provides unlimited storage using the native file system via Tauri.
  * Files are stored in the app's data directory.
+ *
+ * The 'sessions' collection is the exception: it lives in Rust's session
+ * store (<profile>/sessions.json, session_storage.rs), which compresses it and
+ * encrypts it at rest when session encryption is on. Sessions written to
+ * db/sessions.json by older versions are moved there on first access.
+ * Incremental session changes go to Rust's per-session change logs through
+ * appendSessionChanges, on the same write queue as saves, so a save always
+ * lands after the changes logged before it (and replaces their logs).
  */
 
 import { BaseDirectory, exists, readTextFile, writeTextFile, mkdir, remove, readDir } from '@tauri-apps/plugin-fs';
@@ -10,6 +18,11 @@ import { StorageAdapter, formatBytes, validateJSON, calculateChecksum } from './
 import type { StorageInfo, BackupInfo } from './StorageAdapter';
 import JSZip from 'jszip';
 import { compressData, decompressData, isCompressed } from './compressionUtils';
+import { loadSessionsStore, saveSessionsStore, logSessionChanges } from '../../types/tauri-performance-commands';
+import type { SessionChange } from '../../types/tauri-performance-commands';
+
+/** Collection kept in the Rust session store instead of db/ */
+const SESSIONS_COLLECTION = 'sessions';
 
 /**
  * Write queue with mutex to prevent concurrent storage writes
@@ -70,6 +83,7 @@ export class TauriFileSystemAdapter extends StorageAdapter {
   private readonly BACKUP_DIR = 'backups';
   private initialized = false;
   private writeQueue = new WriteQueue();
+  private legacySessionsMigration: Promise<void> | null = null;
 
   /**
    * Initialize the storage system
@@ -99,12 +113,46 @@ export class TauriFileSystemAdapter extends StorageAdapter {
     }
   }
 
+  /**
+   * Log changes to one session instead of saving the whole 'sessions'
+   * collection (see session_storage.rs)
+   */
+  async appendSessionChanges(sessionId: string, changes: SessionChange[]): Promise<void> {
+    await this.ensureInitialized();
+
+    return this.writeQueue.enqueue(async () => {
+      try {
+        await this.migrateLegacySessions();
+        await logSessionChanges(sessionId, changes);
+      } catch (error) {
+        console.error(`❌ Failed to log changes to session ${sessionId}:`, error);
+        throw new Error(`Failed to log changes to session ${sessionId}: ${error}`);
+      }
+    });
+  }
+
   /**
    * Save data to a collection using atomic writes
    */
   async save<T>(collection: string, data: T): Promise<void> {
     await this.ensureInitialized();
 
+    if (collection === SESSIONS_COLLECTION) {
+      return this.writeQueue.enqueue(async () => {
+        try {
+          if (!Array.isArray(data)) {
+            throw new Error('sessions must be an array');
+          }
+          await this.migrateLegacySessions();
+          await saveSessionsStore(data);
+          console.log(`💾 Saved ${collection} (${data.length} sessions)`);
+        } catch (error) {
+          console.error(`❌ Failed to save ${collection}:`, error);
+          throw new Error(`Failed to save ${collection}: ${error}`);
+        }
+      });
+    }
+
     return this.writeQueue.enqueue(async () => {
       try {
         const jsonData = JSON.stringify(data, null, 2);
@@ -202,6 +250,20 @@ export class TauriFileSystemAdapter extends StorageAdapter {
   async load<T>(collection: string): Promise<T | null> {
     await this.ensureInitialized();
 
+    if (collection === SESSIONS_COLLECTION) {
+      // Errors propagate: treating an unreadable (e.g. still encrypted) store
+      // as empty would let the next save overwrite it
+      await this.migrateLegacySessions();
+      return (await loadSessionsStore()) as T | null;
+    }
+
+    return this.loadFile<T>(collection);
+  }
+
+  /**
+   * Load a collection from db/
+   */
+  private async loadFile<T>(collection: string): Promise<T | null> {
     const path = `${this.DB_DIR}/${collection}.json`;
 
     try {
@@ -325,6 +387,12 @@ export class TauriFileSystemAdapter extends StorageAdapter {
   async delete(collection: string): Promise<void> {
     await this.ensureInitialized();
 
+    if (collection === SESSIONS_COLLECTION) {
+      await this.save(SESSIONS_COLLECTION, []);
+      console.log(`🗑️  Deleted ${collection}`);
+      return;
+    }
+
     const path = `${this.DB_DIR}/${collection}.json`;
     const backupPath = `${this.DB_DIR}/${collection}.backup.json`;
 
@@ -350,6 +418,10 @@ export class TauriFileSystemAdapter extends StorageAdapter {
   async exists(collection: string): Promise<boolean> {
     await this.ensureInitialized();
 
+    if (collection === SESSIONS_COLLECTION) {
+      return (await this.load(SESSIONS_COLLECTION)) !== null;
+    }
+
     const path = `${this.DB_DIR}/${collection}.json`;
     return await exists(path, { baseDir: BaseDirectory.AppData });
   }
@@ -404,7 +476,8 @@ export class TauriFileSystemAdapter extends StorageAdapter {
   }
 
   /**
-   * Create a backup of all data
+   * Create a backup of all data in db/ (sessions are left out so they never
+   * sit in a plaintext file; backup.rs snapshots cover the session store)
    */
   async createBackup(): Promise<string> {
     await this.ensureInitialized();
@@ -567,6 +640,7 @@ export class TauriFileSystemAdapter extends StorageAdapter {
           await remove(path, { baseDir: BaseDirectory.AppData });
         }
       }
+      await this.delete(SESSIONS_COLLECTION);
 
       console.log('🗑️  Cleared all data');
     } catch (error) {
@@ -600,6 +674,12 @@ export class TauriFileSystemAdapter extends StorageAdapter {
         }
       }
 
+      // Sessions come decrypted from the Rust session store
+      const sessions = await this.load(SESSIONS_COLLECTION);
+      if (sessions !== null) {
+        zip.file(`${SESSIONS_COLLECTION}.json`, JSON.stringify(sessions, null, 2));
+      }
+
       // Add metadata
       zip.file('export-metadata.json', JSON.stringify({
         version: 1,
@@ -697,6 +777,47 @@ export class TauriFileSystemAdapter extends StorageAdapter {
     }
   }
 
+  /**
+   * Move sessions left in db/sessions.json by older versions (plaintext) into
+   * the Rust session store, keeping any sessions Rust already stored there,
+   * then delete the old files. Runs once; a failed attempt is retried on the
+   * next access.
+   */
+  private migrateLegacySessions(): Promise<void> {
+    if (!this.legacySessionsMigration) {
+      this.legacySessionsMigration = this.runLegacySessionsMigration().catch((error) => {
+        this.legacySessionsMigration = null;
+        throw error;
+      });
+    }
+    return this.legacySessionsMigration;
+  }
+
+  private async runLegacySessionsMigration(): Promise<void> {
+    const legacyPath = `${this.DB_DIR}/${SESSIONS_COLLECTION}.json`;
+    if (!await exists(legacyPath, { baseDir: BaseDirectory.AppData })) {
+      return;
+    }
+
+    const legacy = await this.loadFile<Array<{ id: string }>>(SESSIONS_COLLECTION);
+    if (legacy === null) {
+      // Unreadable and no usable backup: leave the files for manual recovery
+      throw new Error(`Could not read ${legacyPath} to move it into the session store`);
+    }
+    const stored = (await loadSessionsStore<{ id: string }>()) ?? [];
+    const legacyIds = new Set(legacy.map((session) => session.id));
+    const merged = [...legacy, ...stored.filter((session) => !legacyIds.has(session.id))];
+    await saveSessionsStore(merged);
+
+    for (const suffix of ['json', 'backup.json', 'tmp.json']) {
+      const path = `${this.DB_DIR}/${SESSIONS_COLLECTION}.${suffix}`;
+      if (await exists(path, { baseDir: BaseDirectory.AppData })) {
+        await remove(path, { baseDir: BaseDirectory.AppData });
+      }
+    }
+    console.log(`🔐 Moved ${legacy.length} sessions from ${legacyPath} into the session store`);
+  }
+
   /**
    * Ensure storage is initialized
    */
@@ -9,26 +9,102 @@
  * Recording Flow:
  * 1. Check screen recording permission
  * 2. Start recording when session starts (if enabled)
- * 3. Stop recording when session ends
- * 4. Create SessionVideo with attachment reference
+ * 3. Pause/resume with the session (paused time is left out of the video;
+ *    the backend records segments and stitches them on stop)
+ * 4. Stop recording when session ends
+ * 5. Create SessionVideo with attachment reference
  */
 
 import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
 import type { Session, SessionVideo } from '../types';
 import { generateId } from '../utils/helpers';
 import { path } from '@tauri-apps/api';
 import { videoStorageService } from './videoStorageService';
 import { BaseDirectory, mkdir, exists } from '@tauri-apps/plugin-fs';
 
+/** `auto` records HEVC when the hardware supports it, otherwise H.264 */
+export type VideoCodec = 'auto' | 'h264' | 'hevc';
+
+/** Bitrate preset, scaled by resolution and frame rate */
+export type QualityPreset = 'low' | 'balanced' | 'high';
+
 export interface VideoQuality {
   width: number;
   height: number;
   fps: number;
+  /** Defaults to 'auto' */
+  codec?: VideoCodec;
+  /** Defaults to 'balanced' */
+  preset?: QualityPreset;
+}
+
+/** Watermark burned into the recorded video */
+export interface RecordingOverlay {
+  /** Text shown in the overlay, typically the session name */
+  label?: string;
+  /** Show the wall-clock time */
+  timestamp?: boolean;
+  /** PNG drawn above the text */
+  logoPath?: string;
+  /** Defaults to 'bottomRight' */
+  position?: 'topLeft' | 'topRight' | 'bottomLeft' | 'bottomRight';
+}
+
+/**
+ * Which key combos the keystroke overlay shows; typed text never is:
+ * - shortcuts: combos with ⌘ or ⌃, plus Escape and function keys
+ * - allowlist: only the shortcuts in `allowlist` (e.g. "⌘⇧P", "cmd+shift+p")
+ */
+export interface KeystrokeOverlaySettings {
+  filter?: 'shortcuts' | 'allowlist';
+  allowlist?: string[];
+}
+
+/**
+ * `recording-display-lost` payload; recording continues on the fallback
+ * display (null for a secondary display of a multi-source recording, whose
+ * file just ends)
+ */
+export interface RecordingDisplayLost {
+  displayId: number;
+  fallbackDisplayId: number | null;
+}
+
+/** One display's file of a session recording */
+export interface DisplayRecording {
+  displayId: number;
+  path: string;
+  /** The file used as the session's recording */
+  primary: boolean;
+}
+
+/** One segment of a recording made in rolling segments */
+export interface SegmentInfo {
+  index: number;
+  /** The segment file (gone once the manifest is `stitched`) */
+  path: string;
+  startedAt: string;
+  /** null while it is being recorded */
+  endedAt: string | null;
+  durationSecs: number | null;
+  /** Start of the segment in the stitched recording */
+  offsetSecs: number | null;
+}
+
+export interface SegmentManifest {
+  sessionId: string;
+  segmentMinutes: number;
+  output: string;
+  /** Segments were joined into `output` and have `offsetSecs` */
+  stitched: boolean;
+  segments: SegmentInfo[];
 }
 
 export class VideoRecordingService {
   private activeSessionId: string | null = null;
   private isRecording: boolean = false;
+  private isPaused: boolean = false;
 
   /**
    * Ensure the videos directory exists
@@ -68,11 +144,16 @@ export class VideoRecordingService {
   }
 
   /**
-   * Start video recording for a session
+   * Start video recording for a session. With `displayIds` ('all' = every
+   * active display) each display is recorded into its own file; the first one
+   * becomes the session's recording. `overlay` is burned into every display's
+   * video.
    */
   async startRecording(
     session: Session,
-    quality?: VideoQuality
+    quality?: VideoQuality,
+    displayIds?: number[] | 'all',
+    overlay?: RecordingOverlay
   ): Promise<void> {
     console.log(`🎬 [VIDEO SERVICE] startRecording() called for session: ${session.id}`);
     console.log(`🎬 [VIDEO SERVICE] session.videoRecording = ${session.videoRecording}`);
@@ -100,15 +181,30 @@ export class VideoRecordingService {
     const defaultQuality: VideoQuality = {
       width: 1280,
       height: 720,
-      fps: 15
+      fps: 15,
+      preset: session.videoQuality
     };
+    // Sessions started from a preset may record several displays
+    displayIds = displayIds ?? session.videoDisplayIds;
 
     try {
-      await invoke('start_video_recording', {
-        sessionId: session.id,
-        outputPath,
-        quality: quality || defaultQuality
-      });
+      if (displayIds) {
+        const recorded = await invoke<number[]>('start_multi_source_recording', {
+          sessionId: session.id,
+          outputPath,
+          displayIds: displayIds === 'all' ? undefined : displayIds,
+          quality: quality || defaultQuality,
+          overlay
+        });
+        console.log(`🖥️ [VIDEO SERVICE] Recording displays ${recorded.join(', ')} into separate files`);
+      } else {
+        await invoke('start_video_recording', {
+          sessionId: session.id,
+          outputPath,
+          quality: quality || defaultQuality,
+          overlay
+        });
+      }
 
       console.log('✅ [VIDEO SERVICE] Video recording started');
     } catch (error) {
@@ -126,6 +222,42 @@ export class VideoRecordingService {
     }
   }
 
+  /**
+   * Pause video recording (no-op if not recording or already paused)
+   */
+  async pauseRecording(): Promise<void> {
+    if (!this.isRecording || this.isPaused) {
+      return;
+    }
+
+    try {
+      await invoke('pause_video_recording');
+      this.isPaused = true;
+      console.log('⏸️ [VIDEO SERVICE] Video recording paused');
+    } catch (error) {
+      console.error('❌ [VIDEO SERVICE] Failed to pause video recording:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Resume a paused video recording (no-op if not paused)
+   */
+  async resumeRecording(): Promise<void> {
+    if (!this.isRecording || !this.isPaused) {
+      return;
+    }
+
+    try {
+      await invoke('resume_video_recording');
+      this.isPaused = false;
+      console.log('▶️ [VIDEO SERVICE] Video recording resumed');
+    } catch (error) {
+      console.error('❌ [VIDEO SERVICE] Failed to resume video recording:', error);
+      throw error;
+    }
+  }
+
   /**
    * Stop video recording and create SessionVideo entity
    */
@@ -161,6 +293,7 @@ export class VideoRecordingService {
       };
 
       this.isRecording = false;
+      this.isPaused = false;
       this.activeSessionId = null;
 
       console.log(`✅ [VIDEO SERVICE] SessionVideo created:`, sessionVideo);
@@ -168,11 +301,92 @@ export class VideoRecordingService {
     } catch (error) {
       console.error('❌ [VIDEO SERVICE] Failed to stop video recording:', error);
       this.isRecording = false;
+      this.isPaused = false;
       this.activeSessionId = null;
       throw error;
     }
   }
 
+  /**
+   * Drop to `idleFps` (1-5) frames per second while there is no input
+   * activity, back to full rate on activity. Needs activity monitoring.
+   */
+  async setAdaptiveFramerate(enabled: boolean, idleFps?: number): Promise<void> {
+    try {
+      await invoke('set_adaptive_framerate', { enabled, idleFps });
+      console.log(`🎞️ [VIDEO SERVICE] Adaptive frame rate ${enabled ? 'enabled' : 'disabled'}`);
+    } catch (error) {
+      console.error('❌ [VIDEO SERVICE] Failed to set adaptive frame rate:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Record in rolling segments of `minutes` (1-120; null or 0 = off), so a
+   * crash loses at most the last segment. Applies to the running recording.
+   */
+  async setSegmentDuration(minutes: number | null): Promise<void> {
+    try {
+      await invoke('set_segment_duration', { minutes });
+      console.log(`📼 [VIDEO SERVICE] Rolling segments ${minutes ? `every ${minutes} min` : 'disabled'}`);
+    } catch (error) {
+      console.error('❌ [VIDEO SERVICE] Failed to set segment duration:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Segments of a session's recording (null unless it was recorded in
+   * rolling segments). Once stitched, export one with
+   * extractVideoClip(sessionId, offsetSecs * 1000, (offsetSecs + durationSecs) * 1000, ...).
+   */
+  async getRecordingSegments(sessionId: string): Promise<SegmentManifest | null> {
+    return await invoke<SegmentManifest | null>('get_recording_segments', { sessionId });
+  }
+
+  /**
+   * Draw pressed shortcuts on the recording (demo videos). Needs Input
+   * Monitoring permission.
+   */
+  async setKeystrokeOverlay(enabled: boolean, settings?: KeystrokeOverlaySettings): Promise<void> {
+    try {
+      await invoke('set_keystroke_overlay', { enabled, settings });
+      console.log(`⌨️ [VIDEO SERVICE] Keystroke overlay ${enabled ? 'enabled' : 'disabled'}`);
+    } catch (error) {
+      console.error('❌ [VIDEO SERVICE] Failed to set keystroke overlay:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Move the recording to another display (CGDirectDisplayID; omit for the
+   * main display). Continues in a new segment of the same video.
+   */
+  async switchDisplay(displayId?: number): Promise<void> {
+    try {
+      await invoke('switch_display', { displayId });
+      console.log(`🖥️ [VIDEO SERVICE] Recording switched to display ${displayId ?? 'main'}`);
+    } catch (error) {
+      console.error('❌ [VIDEO SERVICE] Failed to switch display:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Per-display files of a session's recording (just the session's recording
+   * unless several displays were recorded)
+   */
+  async getDisplayRecordings(sessionId: string): Promise<DisplayRecording[]> {
+    return await invoke<DisplayRecording[]>('get_display_recordings', { sessionId });
+  }
+
+  /**
+   * Listen for the recorded display being unplugged
+   */
+  async onDisplayLost(handler: (event: RecordingDisplayLost) => void): Promise<UnlistenFn> {
+    return listen<RecordingDisplayLost>('recording-display-lost', ({ payload }) => handler(payload));
+  }
+
   /**
    * Check if currently recording
    */
@@ -203,6 +417,7 @@ export class VideoRecordingService {
       if (sessionId) {
         this.activeSessionId = sessionId;
         this.isRecording = true;
+        this.isPaused = await invoke<boolean>('is_video_recording_paused');
       }
       return sessionId;
     } catch (error) {
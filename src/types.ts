@@ -1422,6 +1422,18 @@ export interface Session {
   // AI-Generated Canvas Specification (cached for fast rendering, avoids regeneration costs)
   canvasSpec?: CanvasSpec;
 
+  // Linear/Jira issues created from summary.recommendedTasks (create_tasks_from_session)
+  trackerIssues?: TrackerIssue[];
+
+  // Commits and branch switches in watched repos during the session (git_monitor.rs)
+  gitEvents?: GitEvent[];
+
+  // Frontmost app / window over time (macOS); see getAppUsageTimeline for per-app totals
+  appTimeline?: AppFocus[];
+
+  // Active browser tab over time (settings.activity.captureBrowserUrls, macOS)
+  urlTimeline?: UrlVisit[];
+
   // DEPRECATED: Legacy audio fields
   audioKeyMoments?: AudioKeyMoment[]; // Replaced by audioInsights.keyMoments
 
@@ -1435,6 +1447,16 @@ export interface Session {
   // Video Recording (Phase 1)
   video?: SessionVideo;
   videoRecording?: boolean; // Enable/disable video recording (user setting)
+  videoQuality?: 'low' | 'balanced' | 'high'; // Bitrate preset (from a session preset)
+  videoDisplayIds?: number[]; // Displays recorded into separate files (from a session preset)
+
+  // Session preset the session was started from (tauri-presets.ts)
+  presetName?: string;
+  // Extra instructions from the preset, appended to screenshot analysis / summary prompts
+  aiPrompts?: {
+    screenshotAnalysis?: string | null;
+    summary?: string | null;
+  };
 
   /**
    * Enrichment Status - Comprehensive tracking of post-session enrichment pipeline
@@ -1664,6 +1686,11 @@ export interface SessionScreenshot {
     };
   };
 
+  // Perceptual de-duplication: later captures that matched this frame were
+  // not stored (screenshot-unchanged)
+  unchangedUntil?: string; // Time of the last matching capture
+  unchangedCount?: number; // Matching captures skipped
+
   // User interaction
   userComment?: string;
   flagged?: boolean; // User can flag important moments
@@ -1692,6 +1719,13 @@ export interface SessionAudioSegment {
   draftTranscription?: string; // Original 10s chunk transcript (for comparison)
   enrichedTranscription?: string; // Word-accurate transcript from full session re-transcription
 
+  // Speaker diarization (set by diarize_session_audio, merged in by load_session_detail)
+  speaker?: string; // Dominant speaker, e.g. "Speaker 1"
+  speakerTurns?: SpeakerTurn[];
+
+  // Recording pauses inside this segment (for "(paused 3m)" gaps and audio/video alignment)
+  pauses?: AudioPauseGap[];
+
   // AI-extracted metadata
   keyPhrases?: string[]; // Important phrases from this segment
   sentiment?: 'positive' | 'neutral' | 'negative';
@@ -1704,6 +1738,61 @@ export interface SessionAudioSegment {
   model?: string; // No longer used
 }
 
+// Stretch of one speaker within an audio segment (seconds from segment start)
+export interface SpeakerTurn {
+  start: number;
+  end: number;
+  speaker: string;
+}
+
+// Recording pause (pause_audio_recording .. resume_audio_recording), RFC 3339 timestamps
+export interface AudioPauseGap {
+  pausedAt: string;
+  resumedAt: string;
+  durationSecs: number;
+}
+
+// Issue created in Linear or Jira from a recommended task
+export interface TrackerIssue {
+  provider: 'linear' | 'jira';
+  project: string; // Linear team key / Jira project key
+  taskKey: string; // Normalized task title (re-runs skip tasks that already have an issue)
+  taskTitle: string;
+  issueId: string;
+  identifier: string; // e.g. "ENG-123"
+  url: string;
+  createdAt: string;
+}
+
+// Commit or branch switch recorded by the git monitor during a session
+export interface GitEvent {
+  kind: 'commit' | 'branchSwitch';
+  repo: string; // Repo top-level directory
+  repoName: string;
+  timestamp: string; // Commit date for commits, detection time for branch switches
+  branch: string; // Branch after the event ("HEAD" when detached)
+  previousBranch?: string; // branchSwitch only
+  hash?: string;
+  message?: string; // Commit subject
+  author?: string;
+}
+
+// Frontmost app and window from `timestamp` until the next entry
+export interface AppFocus {
+  bundleId: string;
+  appName: string;
+  windowTitle: string; // Empty when untitled or settings.activity.captureWindowTitles is off
+  timestamp: string;
+}
+
+// Browser tab that became active during a session (already privacy-filtered)
+export interface UrlVisit {
+  browser: string;
+  url: string; // Query string and fragment removed unless settings.activity.stripUrlQuery is off
+  title: string;
+  timestamp: string;
+}
+
 // Audio key moment - AI-identified important timestamp
 export interface AudioKeyMoment {
   id: string;
@@ -1794,8 +1883,14 @@ export interface ActivityMetrics {
   keyboardEvents: number;        // Number of keyboard events in time window
   windowFocusChanges: number;    // Number of window focus changes in time window
   timestamp: string;             // ISO 8601 timestamp of measurement
+  degradedReasons?: ActivityDegradedReason[]; // Counts not being measured (empty when complete)
 }
 
+// Why some activity counts read zero
+// - inputMonitoringDenied: no Input Monitoring permission, so no key presses or mouse clicks
+// - unsupportedPlatform: automatic tracking is macOS only
+export type ActivityDegradedReason = 'inputMonitoringDenied' | 'unsupportedPlatform';
+
 // Legacy types (for backward compatibility during refactor)
 export type TaskStatus = 'todo' | 'in-progress' | 'done' | 'archived';
 export type TaskPriority = 'low' | 'medium' | 'high' | 'urgent';
@@ -0,0 +1,57 @@
+/**
+ * TypeScript helpers for activity monitoring (activity_monitor.rs / macos_events.rs)
+ *
+ * With settings.activity.captureBrowserUrls on, the active browser tab
+ * (Safari and Chromium browsers, macOS) is sampled while monitoring runs.
+ * Visits are filtered in Rust before they leave the backend: blocked domains
+ * and non-web pages are dropped, query strings stripped unless
+ * stripUrlQuery is off, and Chromium incognito windows skipped. New visits
+ * arrive about once a second as `browser-url-visits`; SessionsContext adds them
+ * to the active session's `urlTimeline`.
+ *
+ * The frontmost app and window title are recorded on every focus change and
+ * delivered the same way as `app-focus-changes` (session `appTimeline`).
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+import type { AppFocus, UrlVisit } from '../types';
+
+export interface AppFocusSpan extends AppFocus {
+  durationSeconds: number;
+}
+
+export interface AppUsage {
+  bundleId: string;
+  appName: string;
+  seconds: number;
+  /** Times the app came to the front */
+  activations: number;
+}
+
+export interface AppUsageTimeline {
+  from: string;
+  to: string;
+  spans: AppFocusSpan[];
+  /** Most used first */
+  apps: AppUsage[];
+  totalSeconds: number;
+}
+
+/**
+ * Per-app time for a session (its appTimeline, plus unsaved changes while it
+ * is running), or for the last `windowSeconds` of the current monitoring run
+ */
+export async function getAppUsageTimeline(
+  query: { sessionId: string } | { windowSeconds: number }
+): Promise<AppUsageTimeline> {
+  return await invoke<AppUsageTimeline>('get_app_usage_timeline', query);
+}
+
+export async function listenAppFocusChanges(handler: (changes: AppFocus[]) => void): Promise<UnlistenFn> {
+  return listen<AppFocus[]>('app-focus-changes', ({ payload }) => handler(payload));
+}
+
+export async function listenBrowserUrlVisits(handler: (visits: UrlVisit[]) => void): Promise<UnlistenFn> {
+  return listen<UrlVisit[]>('browser-url-visits', ({ payload }) => handler(payload));
+}
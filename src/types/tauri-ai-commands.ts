@@ -197,6 +197,8 @@ export interface ClaudeChatRequest {
   system?: string | ClaudeSystemBlock[];
   /** Optional temperature for response randomness (0.0-1.0) */
   temperature?: number;
+  /** Request priority - background requests are refused once the monthly budget is exceeded */
+  priority?: 'user' | 'background';
 }
 
 /**
@@ -395,3 +397,218 @@ export type ClaudeStreamChunk =
       type: "error";
       error: ClaudeStreamError;
     };
+
+// ============================================================================
+// Ollama Types
+// ============================================================================
+
+/**
+ * Message sent to or returned by a local Ollama model.
+ */
+export interface OllamaMessage {
+  /** Role of the message sender */
+  role: "system" | "user" | "assistant";
+  /** Text content */
+  content: string;
+  /** Base64 images (no data URL prefix) for vision models */
+  images?: string[];
+}
+
+/**
+ * Request for ollama_chat_completion / ollama_chat_completion_stream.
+ */
+export interface OllamaChatRequest {
+  /** Local model name (e.g., "llama3.1:8b") */
+  model: string;
+  /** Conversation messages */
+  messages: OllamaMessage[];
+  /** Optional system prompt */
+  system?: string;
+  /** Sampling temperature */
+  temperature?: number;
+  /** Maximum tokens to generate */
+  maxTokens?: number;
+  /** "json" or a JSON schema for structured output */
+  format?: "json" | Record<string, unknown>;
+  /** Overrides the Ollama URL from settings for this request */
+  baseUrl?: string;
+}
+
+/**
+ * Response from ollama_chat_completion.
+ */
+export interface OllamaChatResponse {
+  /** Model that produced the response */
+  model: string;
+  /** Assistant message */
+  message: OllamaMessage;
+  /** Why generation stopped ("stop", "length") */
+  doneReason?: string;
+  /** Prompt tokens */
+  promptEvalCount?: number;
+  /** Output tokens */
+  evalCount?: number;
+}
+
+/**
+ * Events emitted on `ollama-stream-{streamId}`.
+ */
+export type OllamaStreamEvent =
+  | {
+      type: "content_delta";
+      text: string;
+    }
+  | {
+      type: "stream_end";
+      doneReason?: string;
+      promptEvalCount?: number;
+      evalCount?: number;
+    }
+  | {
+      type: "error";
+      error: { message: string };
+    };
+
+// ============================================================================
+// Gemini Types
+// ============================================================================
+
+/**
+ * Request for gemini_chat_completion / gemini_chat_completion_stream.
+ * Messages use the Claude shape so callers can switch providers.
+ */
+export interface GeminiChatRequest {
+  /** Gemini model (e.g., "gemini-2.5-flash") */
+  model: string;
+  /** Maximum tokens to generate */
+  maxTokens: number;
+  /** Conversation messages (text and base64 image blocks) */
+  messages: ClaudeMessage[];
+  /** Optional system instruction */
+  system?: string;
+  /** Sampling temperature */
+  temperature?: number;
+  /** Background requests are refused when over budget */
+  priority?: "user" | "background";
+}
+
+/**
+ * Token usage reported by Gemini.
+ */
+export interface GeminiUsage {
+  /** Prompt tokens */
+  inputTokens: number;
+  /** Output tokens */
+  outputTokens: number;
+}
+
+/**
+ * Response from gemini_chat_completion.
+ */
+export interface GeminiChatResponse {
+  /** Model that produced the response */
+  model: string;
+  /** Response text */
+  text: string;
+  /** Why generation stopped ("STOP", "MAX_TOKENS", ...) */
+  finishReason?: string;
+  /** Token usage */
+  usage: GeminiUsage;
+}
+
+/**
+ * Events emitted on `gemini-stream-{streamId}`.
+ */
+export type GeminiStreamEvent =
+  | {
+      type: "content_delta";
+      text: string;
+    }
+  | {
+      type: "stream_end";
+      finishReason?: string;
+      usage: GeminiUsage;
+    }
+  | {
+      type: "error";
+      error: { message: string };
+    };
+
+// ============================================================================
+// AI Router Types
+// ============================================================================
+
+/**
+ * Providers reachable through ai_chat_completion.
+ */
+export type AiProviderId = "claude" | "openai" | "gemini" | "ollama";
+
+/**
+ * Provider-independent chat request (provider and model default to settings.ai).
+ */
+export interface AiChatRequest {
+  /** Conversation messages (text and base64 image blocks) */
+  messages: ClaudeMessage[];
+  /** Optional system prompt */
+  system?: string;
+  /** Maximum tokens to generate */
+  maxTokens: number;
+  /** Sampling temperature */
+  temperature?: number;
+  /** Background requests are refused when over budget */
+  priority?: "user" | "background";
+  /** Overrides the configured provider */
+  provider?: AiProviderId;
+  /** Model for the selected provider (fallbacks use their configured model) */
+  model?: string;
+}
+
+/**
+ * Response from ai_chat_completion.
+ */
+export interface AiChatResponse {
+  /** Provider that produced the response */
+  provider: AiProviderId;
+  /** Model that produced the response */
+  model: string;
+  /** Response text */
+  text: string;
+  /** Provider-specific stop reason */
+  stopReason?: string;
+  /** Token usage */
+  usage: { inputTokens: number; outputTokens: number };
+  /** Providers that were rate limited before this one answered */
+  fallbackFrom: AiProviderId[];
+}
+
+/**
+ * Per-provider usage since app start (get_ai_provider_usage).
+ */
+export interface AiProviderUsage {
+  provider: AiProviderId;
+  requests: number;
+  failures: number;
+  rateLimited: number;
+  /** Requests answered after another provider was rate limited */
+  fallbacksServed: number;
+  inputTokens: number;
+  outputTokens: number;
+  totalLatencyMs: number;
+  lastError?: string;
+}
+
+/**
+ * Payload of `ai-request-retrying`, emitted before each retry of a cloud AI request.
+ */
+export interface AiRequestRetryingEvent {
+  /** "claude", "openai" or "gemini" */
+  provider: string;
+  /** Attempt about to be made (2 = first retry) */
+  attempt: number;
+  maxAttempts: number;
+  /** Wait before the attempt */
+  delayMs: number;
+  reason: "rate_limited" | "server_error" | "network_error";
+  /** HTTP status that triggered the retry (absent for network errors) */
+  status?: number;
+}
@@ -0,0 +1,60 @@
+/**
+ * TypeScript helpers for screenshot annotation (annotation.rs)
+ *
+ * Marks up a screenshot (e.g. a quick capture) before it is attached to a
+ * session. Operations are applied in order; coordinates are image pixels and
+ * colors are hex ("#rgb", "#rrggbb" or "#rrggbbaa" for translucency).
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+
+export type Annotation =
+  | {
+      type: 'arrow';
+      fromX: number;
+      fromY: number;
+      /** Arrow head */
+      toX: number;
+      toY: number;
+      color?: string;
+      strokeWidth?: number;
+    }
+  | {
+      type: 'rectangle';
+      x: number;
+      y: number;
+      width: number;
+      height: number;
+      color?: string;
+      strokeWidth?: number;
+      /** Fill instead of outlining (e.g. a translucent highlight) */
+      filled?: boolean;
+    }
+  | {
+      type: 'text';
+      /** Top-left corner of the text */
+      x: number;
+      y: number;
+      text: string;
+      color?: string;
+      fontSize?: number;
+      /** Label background drawn behind the text */
+      background?: string;
+    }
+  | {
+      type: 'blur';
+      x: number;
+      y: number;
+      width: number;
+      height: number;
+      /** Gaussian blur strength (default 12) */
+      sigma?: number;
+    };
+
+/**
+ * Apply annotations to a screenshot (data URL or base64); returns the
+ * annotated image as a PNG data URL
+ */
+export async function annotateScreenshot(image: string, operations: Annotation[]): Promise<string> {
+  return invoke<string>('annotate_screenshot', { image, operations });
+}
@@ -0,0 +1,58 @@
+/**
+ * TypeScript helpers for the localhost REST API (rest_api.rs)
+ *
+ * A read-only session API on 127.0.0.1 for scripts and other apps:
+ * `GET /sessions`, `GET /sessions/{id}` and `GET /active`, each requiring
+ * `Authorization: Bearer <token>`. Enabled state and port live in
+ * settings.apiServer, so a started server comes back after restarts.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+
+export interface ApiServerStatus {
+  running: boolean;
+  port: number;
+  /** e.g. http://127.0.0.1:47821 (undefined while stopped) */
+  url?: string;
+  /** Why the last start failed (e.g. port in use) */
+  error?: string;
+}
+
+/**
+ * Start the server, optionally on a new port (rejects if the port can't be bound)
+ */
+export async function startApiServer(port?: number): Promise<ApiServerStatus> {
+  return await invoke<ApiServerStatus>('start_api_server', { port });
+}
+
+/**
+ * Stop the server
+ */
+export async function stopApiServer(): Promise<ApiServerStatus> {
+  return await invoke<ApiServerStatus>('stop_api_server');
+}
+
+/**
+ * Change the port (1024 or above); restarts the server if running
+ */
+export async function setApiServerPort(port: number): Promise<ApiServerStatus> {
+  return await invoke<ApiServerStatus>('set_api_server_port', { port });
+}
+
+export async function getApiServerStatus(): Promise<ApiServerStatus> {
+  return await invoke<ApiServerStatus>('get_api_server_status');
+}
+
+/**
+ * Bearer token for requests (generated on first use, stored in the keychain)
+ */
+export async function getApiServerToken(): Promise<string> {
+  return await invoke<string>('get_api_server_token');
+}
+
+/**
+ * Replace the token; existing clients must use the new one
+ */
+export async function regenerateApiServerToken(): Promise<string> {
+  return await invoke<string>('regenerate_api_server_token');
+}
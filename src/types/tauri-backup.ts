@@ -0,0 +1,59 @@
+/**
+ * TypeScript helpers for backups (backup.rs)
+ *
+ * A backup is a `taskerino-backup-<timestamp>` folder with a manifest of file
+ * hashes, covering session data and metadata for every profile (attachment
+ * data only with includeMedia). Scheduled backups are configured in
+ * settings.backup; the newest `keep` are kept.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+
+export interface BackupSettings {
+  enabled: boolean;
+  /** Folder backups are written to, e.g. on an external drive */
+  directory: string;
+  intervalHours: number;
+  /** Backups kept; older ones are deleted */
+  keep: number;
+  /** Also copy screenshots and audio, not just metadata */
+  includeMedia: boolean;
+}
+
+export interface BackupInfo {
+  /** Backup folder (pass to restoreBackup) */
+  path: string;
+  /** ISO 8601 */
+  createdAt: string;
+  includesMedia: boolean;
+  files: number;
+  bytes: number;
+}
+
+export interface RestoreReport {
+  files: number;
+  bytes: number;
+}
+
+/** Back up now (to settings.backup.directory unless `directory` is given) */
+export async function createBackup(directory?: string, includeMedia?: boolean): Promise<BackupInfo> {
+  return await invoke<BackupInfo>('create_backup', { directory, includeMedia });
+}
+
+/** Backups in a folder (default: the configured one), newest first */
+export async function listBackups(directory?: string): Promise<BackupInfo[]> {
+  return await invoke<BackupInfo[]>('list_backups', { directory });
+}
+
+/**
+ * Verify a backup and copy it back over the current data. Reload the app
+ * afterwards so in-memory state doesn't overwrite the restored files.
+ */
+export async function restoreBackup(path: string): Promise<RestoreReport> {
+  return await invoke<RestoreReport>('restore_backup', { path });
+}
+
+export async function listenBackupRestored(handler: (report: RestoreReport) => void): Promise<UnlistenFn> {
+  return listen<RestoreReport>('backup-restored', ({ payload }) => handler(payload));
+}
@@ -0,0 +1,61 @@
+/**
+ * TypeScript helpers for calendar integration (calendar.rs)
+ *
+ * macOS only: reads EventKit calendars so sessions can start automatically
+ * when a meeting with a video-call link begins (settings.calendar.autoStartMeetings).
+ * Other platforms report `unsupported`.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+
+export type CalendarPermission =
+  | 'notDetermined'
+  | 'restricted'
+  | 'denied'
+  | 'authorized'
+  | 'writeOnly'
+  | 'unsupported';
+
+export interface CalendarEvent {
+  id: string;
+  title: string;
+  /** ISO 8601 */
+  start: string;
+  end: string;
+  allDay: boolean;
+  /** Calendar title */
+  calendar: string;
+  /** Attendee display names */
+  attendees: string[];
+  location?: string;
+  /** Zoom / Meet / Teams / Webex link, if any */
+  meetingUrl?: string;
+}
+
+export async function getCalendarPermission(): Promise<CalendarPermission> {
+  return await invoke<CalendarPermission>('get_calendar_permission');
+}
+
+/**
+ * Ask for calendar access (the system dialog is only shown once)
+ */
+export async function requestCalendarPermission(): Promise<boolean> {
+  return await invoke<boolean>('request_calendar_permission');
+}
+
+/**
+ * Events in progress or starting within `hours` (default 24), soonest first
+ */
+export async function getUpcomingCalendarEvents(hours?: number): Promise<CalendarEvent[]> {
+  return await invoke<CalendarEvent[]>('get_upcoming_calendar_events', { hours });
+}
+
+/**
+ * Listen for sessions started automatically for a meeting
+ */
+export async function listenCalendarSessionStarted(
+  handler: (event: CalendarEvent) => void
+): Promise<UnlistenFn> {
+  return listen<CalendarEvent>('calendar-session-started', ({ payload }) => handler(payload));
+}
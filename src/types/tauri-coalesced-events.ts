@@ -0,0 +1,48 @@
+/**
+ * TypeScript helpers for coalesced backend events
+ *
+ * High-frequency events (`countdown-tick`, `activity-stats`) are merged in
+ * Rust (event_coalescer.rs) and delivered as one `coalesced-events` batch
+ * per window. Each batch is acknowledged so the
+ * backend can stop flushing (and keep only the newest readings) while the
+ * webview is lagging.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+
+export interface CountdownTickEvent {
+  sessionId: string;
+  status: string;
+  remainingMs: number;
+}
+
+export interface CoalescedEventMap {
+  'countdown-tick': CountdownTickEvent;
+  'activity-stats': Record<string, unknown>;
+}
+
+interface CoalescedBatch {
+  seq: number;
+  events: { event: string; payload: unknown }[];
+  dropped: number;
+}
+
+/**
+ * Listen for a coalesced event; the handler receives the latest payload of each batch
+ */
+export async function listenCoalesced<K extends keyof CoalescedEventMap>(
+  event: K,
+  handler: (payload: CoalescedEventMap[K]) => void
+): Promise<UnlistenFn> {
+  return listen<CoalescedBatch>('coalesced-events', ({ payload: batch }) => {
+    for (const entry of batch.events) {
+      if (entry.event === event) {
+        handler(entry.payload as CoalescedEventMap[K]);
+      }
+    }
+    invoke('ack_coalesced_events', { seq: batch.seq }).catch((error) => {
+      console.error('❌ [RUST] Failed to acknowledge coalesced events:', error);
+    });
+  });
+}
@@ -0,0 +1,35 @@
+/**
+ * TypeScript helpers for self-diagnostics (diagnostics.rs)
+ *
+ * `runDiagnostics` checks permissions, entitlements, ffmpeg, disk space,
+ * audio devices, the native capture library and the configured API keys
+ * (one lightweight request per provider). The report can be shown in
+ * Settings or copied for support.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+
+export type CheckStatus = 'pass' | 'warn' | 'fail';
+
+export interface DiagnosticCheck {
+  /** e.g. "screenPermission", "diskSpace", "claudeApiKey" */
+  id: string;
+  label: string;
+  status: CheckStatus;
+  message: string;
+}
+
+export interface DiagnosticsReport {
+  /** Worst status of all checks */
+  status: CheckStatus;
+  checks: DiagnosticCheck[];
+  appVersion: string;
+  /** e.g. "macos aarch64" */
+  platform: string;
+  generatedAt: string;
+}
+
+/** Takes a few seconds when API keys are set (network requests) */
+export async function runDiagnostics(): Promise<DiagnosticsReport> {
+  return await invoke<DiagnosticsReport>('run_diagnostics');
+}
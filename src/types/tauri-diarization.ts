@@ -0,0 +1,40 @@
+/**
+ * TypeScript helpers for speaker diarization (diarization.rs)
+ *
+ * `diarizeSessionAudio` runs as a backend job over a session's WAV audio
+ * segments. Labels are stored per session and merged into the audio segments
+ * returned by `load_session_detail` (`speaker` / `speakerTurns`).
+ */
+
+import type { SpeakerTurn } from '../types';
+import { runJob, type JobProgress, type RunningJob } from './tauri-jobs';
+
+/** Speaker labels for one audio segment */
+export interface SegmentSpeakers {
+  segmentId: string;
+  /** Speaker with the most speech in the segment (absent when it had no speech) */
+  speaker?: string;
+  turns: SpeakerTurn[];
+}
+
+/** Stored diarization of a session (the job result) */
+export interface SessionDiarization {
+  sessionId: string;
+  speakerCount: number;
+  segments: SegmentSpeakers[];
+  /** Segments whose audio couldn't be decoded (e.g. FLAC chunks) */
+  skippedSegments: string[];
+  createdAt: string;
+}
+
+/**
+ * Label a session's audio segments with speakers; `maxSpeakers` caps the
+ * number of speakers found
+ */
+export async function diarizeSessionAudio(
+  sessionId: string,
+  maxSpeakers?: number,
+  onProgress?: (event: JobProgress) => void
+): Promise<RunningJob<SessionDiarization>> {
+  return runJob<SessionDiarization>('diarize_session_audio', { sessionId, maxSpeakers }, onProgress);
+}
@@ -0,0 +1,38 @@
+/**
+ * TypeScript helpers for the low-disk-space guard (disk_space.rs)
+ *
+ * start_audio_recording / start_video_recording refuse to start below
+ * settings.storage.minFreeSpaceMb; their error message then starts with
+ * "Insufficient disk space". While recording, `disk-space-warning` fires when
+ * free space drops below warnFreeSpaceMb (`low`) or minFreeSpaceMb (`critical`).
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+
+export type DiskSpaceLevel = 'ok' | 'low' | 'critical';
+
+/** `disk-space-warning` payload */
+export interface DiskSpace {
+  availableMb: number;
+  level: DiskSpaceLevel;
+  minFreeSpaceMb: number;
+  warnFreeSpaceMb: number;
+}
+
+/**
+ * Free space on the app data volume
+ */
+export async function getDiskSpace(): Promise<DiskSpace> {
+  return await invoke<DiskSpace>('get_disk_space');
+}
+
+export function isInsufficientDiskSpaceError(error: unknown): boolean {
+  return String(error).startsWith('Insufficient disk space');
+}
+
+export async function listenDiskSpaceWarning(
+  handler: (space: DiskSpace) => void
+): Promise<UnlistenFn> {
+  return listen<DiskSpace>('disk-space-warning', ({ payload }) => handler(payload));
+}
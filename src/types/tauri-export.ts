@@ -0,0 +1,107 @@
+/**
+ * TypeScript helpers for session export (export.rs)
+ *
+ * Exports are written by the backend straight to `path` (e.g. picked with a
+ * save dialog), so large sessions never pass through the webview.
+ *
+ * Session archives (session_archive.rs) are zip files holding one session
+ * with its attachments, audio and video, for backup or moving a session to
+ * another machine. Importing gives the session (and any clashing
+ * attachment) a fresh id, writes it to storage and emits `session-imported`.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+import type { Session } from '../types';
+
+export interface ExportResult {
+  path: string;
+  bytes: number;
+}
+
+/**
+ * Export a session as one self-contained HTML file (summary, activity charts,
+ * timeline with inline screenshots, transcript)
+ */
+export async function exportSessionHtml(sessionId: string, path: string): Promise<ExportResult> {
+  return await invoke<ExportResult>('export_session_html', { sessionId, path });
+}
+
+export interface PdfExportOptions {
+  /** Default `a4` */
+  pageSize?: 'a4' | 'letter';
+  /** Include the timestamped transcript (default true) */
+  includeTranscript?: boolean;
+  /** Flagged/commented screenshots first, then evenly spaced ones (default 12) */
+  maxScreenshots?: number;
+}
+
+/**
+ * Export a session as a printable PDF (summary, key screenshots, transcript)
+ */
+export async function exportSessionPdf(
+  sessionId: string,
+  path: string,
+  options?: PdfExportOptions
+): Promise<ExportResult> {
+  return await invoke<ExportResult>('export_session_pdf', { sessionId, path, options });
+}
+
+/**
+ * Export a session as an Obsidian/Logseq note into `<vault>/Taskerino/`
+ * (YAML frontmatter, screenshots under `assets/<sessionId>/`, transcript).
+ * `vaultPath` defaults to settings.export.vaultPath.
+ */
+export async function exportSessionMarkdown(sessionId: string, vaultPath?: string): Promise<ExportResult> {
+  return await invoke<ExportResult>('export_session_markdown', { sessionId, vaultPath });
+}
+
+/**
+ * Export a completed session to the vault if settings.export.autoExportMarkdown
+ * is on (null otherwise). Called once enrichment has saved the session.
+ */
+export async function autoExportSession(sessionId: string): Promise<ExportResult | null> {
+  return await invoke<ExportResult | null>('auto_export_session', { sessionId });
+}
+
+/**
+ * Export transcript captions (SRT or WebVTT) timed against the merged
+ * session video, for loading the recording with captions in any player
+ */
+export async function exportSessionSubtitles(
+  sessionId: string,
+  format: 'srt' | 'vtt',
+  path: string
+): Promise<ExportResult> {
+  return await invoke<ExportResult>('export_session_subtitles', { sessionId, format, path });
+}
+
+/** `session-imported` payload */
+export interface SessionImportSummary {
+  sessionId: string;
+  /** Id in the archive (differs from sessionId if it already existed) */
+  originalSessionId: string;
+  attachments: number;
+  /** Attachments given a new id because theirs already existed */
+  renamedAttachments: number;
+  session: Session;
+}
+
+/**
+ * Export a session with all its media as a zip archive. Runs as a job
+ * (returns the job id, see tauri-jobs.ts); the `job-finished` result is the
+ * archive path (defaults to the Downloads folder)
+ */
+export async function exportSessionArchive(sessionId: string, destination?: string): Promise<string> {
+  return await invoke<string>('export_session_archive', { sessionId, destination });
+}
+
+export async function importSessionArchive(path: string): Promise<SessionImportSummary> {
+  return await invoke<SessionImportSummary>('import_session_archive', { path });
+}
+
+export async function listenSessionImported(
+  handler: (summary: SessionImportSummary) => void
+): Promise<UnlistenFn> {
+  return listen<SessionImportSummary>('session-imported', ({ payload }) => handler(payload));
+}
@@ -0,0 +1,63 @@
+/**
+ * TypeScript helpers for folder sync (folder_sync.rs)
+ *
+ * Mirrors the session library to a shared folder (iCloud Drive, Dropbox) so
+ * several Macs can use it. Each session carries a revision counter; a session
+ * changed on two Macs between syncs is reported as a conflict and left alone
+ * until resolveSyncConflict picks a side. Configured in settings.sync.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+import type { Session } from '../types';
+
+export interface SyncSettings {
+  enabled: boolean;
+  /** Shared folder, e.g. in iCloud Drive */
+  directory: string;
+}
+
+export interface SyncConflict {
+  sessionId: string;
+  name: string;
+  localRevision: number;
+  remoteRevision: number;
+  remoteDevice: string;
+  /** The other Mac deleted the session */
+  remoteDeleted: boolean;
+  /** This Mac deleted the session */
+  localDeleted: boolean;
+  /** ISO 8601 */
+  detectedAt: string;
+}
+
+export interface SyncReport {
+  pushed: number;
+  pulled: number;
+  deleted: number;
+  /** Every unresolved conflict */
+  conflicts: SyncConflict[];
+}
+
+/** Sessions written to storage by a sync (`sessions-synced`) */
+export interface SessionsSyncedEvent {
+  updated: Session[];
+  deleted: string[];
+}
+
+export async function syncSessionsNow(): Promise<SyncReport> {
+  return await invoke<SyncReport>('sync_sessions_now');
+}
+
+/** Keep this Mac's version (`keepLocal`) or the sync folder's, then sync */
+export async function resolveSyncConflict(sessionId: string, keepLocal: boolean): Promise<SyncReport> {
+  return await invoke<SyncReport>('resolve_sync_conflict', { sessionId, keepLocal });
+}
+
+export async function listenSessionsSynced(handler: (event: SessionsSyncedEvent) => void): Promise<UnlistenFn> {
+  return listen<SessionsSyncedEvent>('sessions-synced', ({ payload }) => handler(payload));
+}
+
+export async function listenSyncConflicts(handler: (conflict: SyncConflict) => void): Promise<UnlistenFn> {
+  return listen<SyncConflict>('sync-conflict', ({ payload }) => handler(payload));
+}
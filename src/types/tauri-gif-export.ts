@@ -0,0 +1,29 @@
+/**
+ * TypeScript helper for GIF export (gif_export.rs)
+ *
+ * Converts a slice of a recording (up to 60 seconds) into a palette-optimized
+ * GIF for pasting into Slack or GitHub. Uses ffmpeg when available, otherwise
+ * the native encoder (macOS). Runs as a cancellable backend job (see
+ * tauri-jobs.ts).
+ */
+
+import { runJob, type JobProgress, type RunningJob } from './tauri-jobs';
+
+/**
+ * Start exporting [startMs, startMs + durationMs) of `videoPath` as a GIF.
+ * `maxWidth` defaults to 640px; the job result is the GIF path (Downloads
+ * folder unless `outputPath` is given).
+ */
+export async function exportGif(
+  videoPath: string,
+  startMs: number,
+  durationMs: number,
+  options: { maxWidth?: number; outputPath?: string } = {},
+  onProgress?: (event: JobProgress) => void
+): Promise<RunningJob<string>> {
+  return await runJob<string>(
+    'export_gif',
+    { videoPath, startMs, durationMs, maxWidth: options.maxWidth, outputPath: options.outputPath },
+    onProgress
+  );
+}
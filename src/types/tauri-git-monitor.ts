@@ -0,0 +1,33 @@
+/**
+ * TypeScript helpers for the git activity monitor (git_monitor.rs)
+ *
+ * While a session is active, commits and branch switches in watched repos are
+ * appended to the session's `gitEvents` and emitted as `git-activity`.
+ * Watched repos are kept in settings.git.watchedRepos as top-level directories.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+import type { GitEvent } from '../types';
+
+/** `git-activity` payload */
+export interface GitActivity {
+  sessionId: string;
+  events: GitEvent[];
+}
+
+/**
+ * Watch the repo containing `path` (any folder inside it); returns the
+ * updated list of watched repos
+ */
+export async function addWatchedRepo(path: string): Promise<string[]> {
+  return await invoke<string[]>('add_watched_repo', { path });
+}
+
+export async function removeWatchedRepo(path: string): Promise<string[]> {
+  return await invoke<string[]>('remove_watched_repo', { path });
+}
+
+export async function listenGitActivity(handler: (activity: GitActivity) => void): Promise<UnlistenFn> {
+  return listen<GitActivity>('git-activity', ({ payload }) => handler(payload));
+}
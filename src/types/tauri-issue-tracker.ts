@@ -0,0 +1,51 @@
+/**
+ * TypeScript helpers for the Linear / Jira integration (issue_tracker.rs)
+ *
+ * `createTasksFromSession` turns a session's summary.recommendedTasks into
+ * issues and stores them on the session as `trackerIssues`; tasks that already
+ * have an issue in the same provider and project are skipped, so it is safe to
+ * run again. Jira also needs settings.issueTracker.jiraBaseUrl / jiraEmail.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+import type { TrackerIssue } from '../types';
+
+export type TrackerProvider = TrackerIssue['provider'];
+
+/** Result of createTasksFromSession (`tracker-issues-created` payload) */
+export interface TrackerExportReport {
+  sessionId: string;
+  created: TrackerIssue[];
+  /** Tasks that already had an issue in this provider/project */
+  skipped: number;
+  /** "<task title>: <error>" per task that failed */
+  failed: string[];
+}
+
+/**
+ * Create issues from a session's action items
+ * @param project - Linear team key (e.g. "ENG") or Jira project key
+ */
+export async function createTasksFromSession(
+  sessionId: string,
+  provider: TrackerProvider,
+  project: string
+): Promise<TrackerExportReport> {
+  return await invoke<TrackerExportReport>('create_tasks_from_session', { sessionId, provider, project });
+}
+
+/** Save the Linear API key / Jira API token (OS keychain) */
+export async function setIssueTrackerToken(provider: TrackerProvider, token: string): Promise<void> {
+  await invoke('set_issue_tracker_token', { provider, token });
+}
+
+export async function hasIssueTrackerToken(provider: TrackerProvider): Promise<boolean> {
+  return await invoke<boolean>('has_issue_tracker_token', { provider });
+}
+
+export async function listenTrackerIssuesCreated(
+  handler: (report: TrackerExportReport) => void
+): Promise<UnlistenFn> {
+  return listen<TrackerExportReport>('tracker-issues-created', ({ payload }) => handler(payload));
+}
@@ -0,0 +1,137 @@
+/**
+ * TypeScript helpers for cancellable backend jobs (jobs.rs)
+ *
+ * Long-running commands (configuration export, attachment metadata
+ * extraction) return a job id immediately, report `job-progress` events and
+ * deliver their result with `job-finished`. `cancelJob` stops a job and the
+ * backend removes any partial output.
+ *
+ * Every job kind reports the same `JobProgress` shape, so a single background
+ * tasks panel can seed itself with `listActiveJobs()` and stay current with
+ * `listenJobProgress()`.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+
+export type JobStatus = 'running' | 'completed' | 'failed' | 'cancelled';
+
+/** Progress of one job (`job-progress` payload for every job kind) */
+export interface JobProgress {
+  jobId: string;
+  kind: string;
+  status: JobStatus;
+  /** 0 - 1 */
+  progress: number;
+  /** Item / byte counts for jobs that process a known amount of work */
+  current?: number;
+  total?: number;
+  message?: string;
+  startedAt: string;
+  updatedAt: string;
+  finishedAt?: string;
+  error?: string;
+}
+
+interface JobFinishedEvent {
+  jobId: string;
+  kind: string;
+  status: JobStatus;
+  result?: unknown;
+  error?: string;
+}
+
+export interface RunningJob<T> {
+  jobId: string;
+  /** Resolves with the job result; rejects on failure or cancellation */
+  result: Promise<T>;
+}
+
+/**
+ * Start a job command and track it until it finishes
+ */
+export async function runJob<T>(
+  command: string,
+  args: Record<string, unknown>,
+  onProgress?: (event: JobProgress) => void
+): Promise<RunningJob<T>> {
+  // Listen before invoking so a fast job can't finish unobserved
+  let jobId: string | null = null;
+  const pending: JobFinishedEvent[] = [];
+  let settle: ((event: JobFinishedEvent) => void) | null = null;
+
+  const unlistenProgress = await listen<JobProgress>('job-progress', ({ payload }) => {
+    if (payload.jobId === jobId) {
+      onProgress?.(payload);
+    }
+  });
+  const unlistenFinished = await listen<JobFinishedEvent>('job-finished', ({ payload }) => {
+    if (jobId === null) {
+      pending.push(payload);
+    } else if (payload.jobId === jobId) {
+      settle?.(payload);
+    }
+  });
+
+  const cleanup = () => {
+    unlistenProgress();
+    unlistenFinished();
+  };
+
+  try {
+    jobId = await invoke<string>(command, args);
+  } catch (error) {
+    cleanup();
+    throw error;
+  }
+
+  const result = new Promise<T>((resolve, reject) => {
+    const finish = (event: JobFinishedEvent) => {
+      cleanup();
+      if (event.status === 'completed') {
+        resolve(event.result as T);
+      } else if (event.status === 'cancelled') {
+        reject(new Error('Job cancelled'));
+      } else {
+        reject(new Error(event.error ?? 'Job failed'));
+      }
+    };
+    settle = finish;
+    const early = pending.find((event) => event.jobId === jobId);
+    if (early) {
+      finish(early);
+    }
+  });
+
+  return { jobId: jobId as string, result };
+}
+
+/**
+ * Cancel a running job; returns false if it already finished
+ */
+export async function cancelJob(jobId: string): Promise<boolean> {
+  return await invoke<boolean>('cancel_job', { jobId });
+}
+
+/**
+ * List running and recently finished jobs
+ */
+export async function listJobs(): Promise<JobProgress[]> {
+  return await invoke<JobProgress[]>('list_jobs');
+}
+
+/**
+ * List running jobs (oldest first)
+ */
+export async function listActiveJobs(): Promise<JobProgress[]> {
+  return await invoke<JobProgress[]>('list_active_jobs');
+}
+
+/**
+ * Listen for progress of every job
+ */
+export async function listenJobProgress(
+  handler: (progress: JobProgress) => void
+): Promise<UnlistenFn> {
+  return listen<JobProgress>('job-progress', ({ payload }) => handler(payload));
+}
@@ -0,0 +1,223 @@
+/**
+ * TypeScript wrappers for binary media commands
+ *
+ * These commands return raw bytes via `tauri::ipc::Response`, which arrive
+ * as ArrayBuffers instead of base64 data URLs (no ~33% size overhead and
+ * no string decode on the JS side). Matching Rust commands live in lib.rs.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+import type { AudioPauseGap } from '../types';
+
+/** `options` of start_audio_recording (all optional) */
+export interface AudioRecordingOptions {
+  binaryChunks?: boolean;
+  fileChunks?: boolean;
+  /** Defaults to settings.audio.streamingTranscription */
+  streamingTranscription?: boolean;
+}
+
+/**
+ * Payload of the `audio-chunk` event.
+ * `audioBase64` is set in the default mode; `chunkId`/`byteLength` are set
+ * when recording was started with `options.binaryChunks`; `path` is set (and
+ * nothing else is sent) when recording was started with `options.fileChunks`.
+ * `mimeType` follows settings.audio.chunkFormat (`audio/wav`, `audio/flac`,
+ * `audio/ogg` for Opus or `audio/mpeg` for MP3).
+ * `isSilent` is true when the chunk's RMS is below the VAD threshold.
+ * With settings.audio.systemAudio and separateTracks, mic and system audio
+ * arrive as separate chunks tagged with `track`; otherwise system audio is
+ * mixed into the (untagged) chunk by settings.audio.balance.
+ */
+export interface AudioChunkEvent {
+  sessionId: string;
+  duration: number;
+  mimeType?: string;
+  isSilent?: boolean;
+  track?: 'mic' | 'system';
+  /** Wall-clock span of the chunk (RFC 3339), including any pauses */
+  startedAt?: string;
+  endedAt?: string;
+  /** Pauses that fell inside this chunk */
+  pauses?: AudioPauseGap[];
+  audioBase64?: string;
+  chunkId?: string;
+  byteLength?: number;
+  path?: string;
+}
+
+/**
+ * Payload of the `transcript-delta` event (live transcript, enabled with
+ * settings.audio.streamingTranscription or `options.streamingTranscription`
+ * on start_audio_recording). Each ~1.5s slice emits `delta` text as it
+ * arrives, then one event with `done: true` and the slice's full `text`
+ * (or `error`).
+ */
+export interface TranscriptDeltaEvent {
+  sessionId: string;
+  sliceIndex: number;
+  delta: string;
+  done: boolean;
+  text?: string;
+  error?: string;
+}
+
+/**
+ * Payload of the `audio-device-changed` event: the input device disconnected
+ * mid-recording and capture moved to the default device. The chunk in
+ * progress continues, so no audio before the switch is lost.
+ */
+export interface AudioDeviceChangedEvent {
+  sessionId: string | null;
+  previousDevice: string;
+  device: string;
+}
+
+export async function listenAudioDeviceChanged(
+  handler: (event: AudioDeviceChangedEvent) => void
+): Promise<UnlistenFn> {
+  return listen<AudioDeviceChangedEvent>('audio-device-changed', ({ payload }) => handler(payload));
+}
+
+/**
+ * Payload of the `audio-quality-degraded` event: the input dropped below
+ * ~22kHz (e.g. a Bluetooth headset switched to HFP), which hurts
+ * transcription. `switchedTo` is set when settings.audio.preferBuiltInMic
+ * moved capture to the built-in mic.
+ */
+export interface AudioQualityDegradedEvent {
+  sessionId: string | null;
+  device: string;
+  sampleRate: number;
+  previousSampleRate: number;
+  switchedTo: string | null;
+}
+
+export async function listenAudioQualityDegraded(
+  handler: (event: AudioQualityDegradedEvent) => void
+): Promise<UnlistenFn> {
+  return listen<AudioQualityDegradedEvent>('audio-quality-degraded', ({ payload }) => handler(payload));
+}
+
+/**
+ * Payload of the `audio-source-stalled` / `audio-source-recovered` events:
+ * system audio stopped delivering samples and the backend is restarting the
+ * capture, or samples are arriving again. `stalledSecs` is how long the
+ * source had been silent (stalled) or how long the stall lasted (recovered).
+ */
+export interface AudioSourceStatusEvent {
+  sessionId: string | null;
+  source: 'system';
+  stalledSecs: number;
+  restarts: number;
+}
+
+export async function listenAudioSourceStalled(
+  handler: (event: AudioSourceStatusEvent) => void
+): Promise<UnlistenFn> {
+  return listen<AudioSourceStatusEvent>('audio-source-stalled', ({ payload }) => handler(payload));
+}
+
+export async function listenAudioSourceRecovered(
+  handler: (event: AudioSourceStatusEvent) => void
+): Promise<UnlistenFn> {
+  return listen<AudioSourceStatusEvent>('audio-source-recovered', ({ payload }) => handler(payload));
+}
+
+/**
+ * Capture all screens as a single composite JPEG (raw bytes)
+ */
+export async function captureCompositeScreenshotBytes(): Promise<ArrayBuffer> {
+  return invoke<ArrayBuffer>('capture_all_screens_composite_bytes');
+}
+
+/**
+ * Capture the primary screen as PNG (raw bytes)
+ */
+export async function capturePrimaryScreenBytes(): Promise<ArrayBuffer> {
+  return invoke<ArrayBuffer>('capture_primary_screen_bytes');
+}
+
+/**
+ * Fetch the encoded bytes for a binary audio chunk (each chunk can be taken once)
+ */
+export async function takeAudioChunk(chunkId: string): Promise<ArrayBuffer> {
+  return invoke<ArrayBuffer>('take_audio_chunk', { chunkId });
+}
+
+/**
+ * Read a chunk file written in file chunk mode
+ */
+export async function readAudioChunkFile(path: string): Promise<ArrayBuffer> {
+  return invoke<ArrayBuffer>('read_audio_chunk_file', { path });
+}
+
+/**
+ * Convert raw bytes to a data URL for code paths that still expect base64
+ */
+export function bytesToDataUrl(bytes: ArrayBuffer, mimeType: string): string {
+  const view = new Uint8Array(bytes);
+  let binary = '';
+  const chunkSize = 0x8000;
+  for (let i = 0; i < view.length; i += chunkSize) {
+    binary += String.fromCharCode(...view.subarray(i, i + chunkSize));
+  }
+  return `data:${mimeType};base64,${btoa(binary)}`;
+}
+
+/**
+ * Resolve an `audio-chunk` event to a data URL regardless of delivery mode
+ */
+export async function resolveAudioChunk(chunk: AudioChunkEvent): Promise<string> {
+  if (chunk.audioBase64) {
+    return chunk.audioBase64;
+  }
+  const mimeType = chunk.mimeType ?? 'audio/wav';
+  if (chunk.path) {
+    return bytesToDataUrl(await readAudioChunkFile(chunk.path), mimeType);
+  }
+  if (!chunk.chunkId) {
+    throw new Error('Audio chunk event has neither audioBase64, path nor chunkId');
+  }
+  return bytesToDataUrl(await takeAudioChunk(chunk.chunkId), mimeType);
+}
+
+/**
+ * Result of a streamed upload (`path` is absolute)
+ */
+export interface UploadResult {
+  path: string;
+  bytes: number;
+}
+
+/** Chunk size for streamed uploads (each chunk is one raw-body IPC call) */
+const UPLOAD_CHUNK_SIZE = 8 * 1024 * 1024;
+
+/**
+ * Stream a large file to disk in raw-byte chunks instead of one serialized array.
+ * With `destination` (relative to app data) the file is moved there; without it
+ * the returned temp path can be handed to other Rust commands.
+ */
+export async function uploadMediaFile(
+  data: Blob | Uint8Array,
+  destination?: string
+): Promise<UploadResult> {
+  const uploadId = await invoke<string>('begin_media_upload');
+
+  try {
+    const size = data instanceof Blob ? data.size : data.byteLength;
+    for (let offset = 0; offset < size; offset += UPLOAD_CHUNK_SIZE) {
+      const end = Math.min(offset + UPLOAD_CHUNK_SIZE, size);
+      const chunk = data instanceof Blob
+        ? new Uint8Array(await data.slice(offset, end).arrayBuffer())
+        : data.subarray(offset, end);
+      await invoke('append_media_upload', chunk, { headers: { 'upload-id': uploadId } });
+    }
+
+    return await invoke<UploadResult>('finish_media_upload', { uploadId, destination });
+  } catch (error) {
+    await invoke('abort_media_upload', { uploadId }).catch(() => {});
+    throw error;
+  }
+}
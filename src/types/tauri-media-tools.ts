@@ -0,0 +1,38 @@
+/**
+ * TypeScript helpers for media tools (media_tools.rs)
+ *
+ * ffmpeg is looked up in the app bundle first, then in the usual install
+ * locations. Audio concatenation works without it (WAV and MP3 everywhere,
+ * M4A on macOS).
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+
+export interface MediaToolsStatus {
+  /** Path of the ffmpeg in use, if any */
+  ffmpeg?: string;
+  ffprobe?: string;
+  /** Whether ffmpeg is the copy bundled with the app */
+  bundled: boolean;
+}
+
+export async function getMediaTools(): Promise<MediaToolsStatus> {
+  return await invoke<MediaToolsStatus>('get_media_tools');
+}
+
+/**
+ * Output format of `concatenateAudioFiles`:
+ * - wav: WAV inputs with matching channels / sample rate / bit depth
+ * - mp3: MP3 inputs (sample rates must match when ffmpeg is missing)
+ * - m4a: any audio inputs, encoded to AAC
+ */
+export type AudioConcatFormat = 'wav' | 'mp3' | 'm4a';
+
+/** Join audio files in order into `outputPath` */
+export async function concatenateAudioFiles(
+  inputPaths: string[],
+  outputPath: string,
+  format: AudioConcatFormat
+): Promise<void> {
+  return await invoke<void>('concatenate_audio_files', { inputPaths, outputPath, format });
+}
@@ -0,0 +1,59 @@
+/**
+ * TypeScript helpers for meeting detection / auto-record (meeting_detector.rs)
+ *
+ * macOS only: Zoom, Google Meet and Teams calls are detected from window
+ * titles plus microphone activity. `meeting-detected` / `meeting-ended`
+ * events fire as calls start and stop; the auto-record policy can also start
+ * a session automatically.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+
+export type MeetingApp = 'zoom' | 'googleMeet' | 'teams';
+
+/** settings.autoRecord */
+export interface AutoRecordPolicy {
+  /** Watch for calls and emit events */
+  detectMeetings: boolean;
+  /** Start a session when a call is detected and nothing is recording */
+  autoStart: boolean;
+  /** Apps whose calls count */
+  apps: MeetingApp[];
+}
+
+/** `meeting-detected` / `meeting-ended` payload */
+export interface DetectedMeeting {
+  app: MeetingApp;
+  windowTitle: string;
+  micInUse: boolean;
+  /** ISO 8601 */
+  detectedAt: string;
+}
+
+export async function getAutoRecordPolicy(): Promise<AutoRecordPolicy> {
+  return await invoke<AutoRecordPolicy>('get_auto_record_policy');
+}
+
+export async function setAutoRecordPolicy(policy: AutoRecordPolicy): Promise<AutoRecordPolicy> {
+  return await invoke<AutoRecordPolicy>('set_auto_record_policy', { policy });
+}
+
+/**
+ * The call in progress, or null
+ */
+export async function getDetectedMeeting(): Promise<DetectedMeeting | null> {
+  return await invoke<DetectedMeeting | null>('get_detected_meeting');
+}
+
+export async function listenMeetingDetected(
+  handler: (meeting: DetectedMeeting) => void
+): Promise<UnlistenFn> {
+  return listen<DetectedMeeting>('meeting-detected', ({ payload }) => handler(payload));
+}
+
+export async function listenMeetingEnded(
+  handler: (meeting: DetectedMeeting) => void
+): Promise<UnlistenFn> {
+  return listen<DetectedMeeting>('meeting-ended', ({ payload }) => handler(payload));
+}
@@ -0,0 +1,54 @@
+/**
+ * TypeScript helpers for native notifications (notifications.rs)
+ *
+ * Transcription complete, low disk and long-running session reminders are
+ * sent by the backend; enrichment finished is sent from the frontend. Each
+ * category can be turned off in settings.notifications.
+ * macOS only; needs a bundled app and notification permission.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+
+export type NotificationCategory =
+  | 'transcriptionComplete'
+  | 'enrichmentFinished'
+  | 'lowDisk'
+  | 'sessionReminder';
+
+export type NotificationPermission =
+  | 'notDetermined'
+  | 'denied'
+  | 'authorized'
+  | 'provisional'
+  | 'unsupported';
+
+export interface NotificationSettings {
+  transcriptionComplete: boolean;
+  enrichmentFinished: boolean;
+  lowDisk: boolean;
+  sessionReminder: boolean;
+  /** Reminder after this many hours of recording (0.5-24), repeated every multiple */
+  sessionReminderHours: number;
+}
+
+export async function getNotificationPermission(): Promise<NotificationPermission> {
+  return await invoke<NotificationPermission>('get_notification_permission');
+}
+
+/** Shows the system dialog once; resolves to whether notifications are allowed */
+export async function requestNotificationPermission(): Promise<boolean> {
+  return await invoke<boolean>('request_notification_permission');
+}
+
+/**
+ * Show a notification; resolves to false if the category is turned off.
+ * A later notification with the same `key` replaces this one.
+ */
+export async function sendNotification(
+  category: NotificationCategory,
+  title: string,
+  body: string,
+  key?: string
+): Promise<boolean> {
+  return await invoke<boolean>('send_notification', { category, title, body, key });
+}
@@ -4,10 +4,13 @@
  * These types match the Rust structs in:
  * - session_models.rs
  * - session_storage.rs
+ * - session_index.rs
  * - attachment_loader.rs
+ * - attachment_metadata.rs
  */
 
 import { invoke } from '@tauri-apps/api/core';
+import { runJob, type JobProgress } from './tauri-jobs';
 
 // ============================================================================
 // Session Types (matching Rust session_models.rs)
@@ -27,6 +30,26 @@ export interface SessionSummary {
   hasTranscript: boolean;
 }
 
+/** Filter, sort and page for `querySessions` (session_index.rs) */
+export interface SessionQuery {
+  /** Case-insensitive substring match on name, category and notes */
+  query?: string;
+  category?: string;
+  sortBy?: 'startTime' | 'name' | 'duration' | 'category';
+  sortOrder?: 'asc' | 'desc';
+  /** Omit to return every match */
+  limit?: number;
+  offset?: number;
+}
+
+export interface SessionPage {
+  sessions: SessionSummary[];
+  /** Matches before paging */
+  total: number;
+  offset: number;
+  limit?: number;
+}
+
 export interface Session {
   id: string;
   name: string;
@@ -67,6 +90,17 @@ export interface AttachmentMeta {
   name: string;
   mimeType: string;
   size: number;
+  mediaMetadata?: MediaMetadata;
+}
+
+export interface MediaMetadata {
+  width?: number;
+  height?: number;
+  createdAt?: string;
+  cameraMake?: string;
+  cameraModel?: string;
+  orientation?: number;
+  pageCount?: number;
 }
 
 export interface AttachmentCounts {
@@ -119,10 +153,13 @@ export async function loadSessionDetail(sessionId: string): Promise<Session> {
 }
 
 /**
- * Search sessions using Rust backend (parallel full-text search)
+ * Search sessions using Rust backend (indexed full-text search), newest first
  * Searches across name, category, and notes fields
  */
-export async function searchSessions(query: string): Promise<SessionSummary[]> {
+export async function searchSessions(
+  query: string,
+  page: { limit?: number; offset?: number } = {}
+): Promise<SessionSummary[]> {
   if (!query.trim()) {
     return [];
   }
@@ -131,7 +168,7 @@ export async function searchSessions(query: string): Promise<SessionSummary[]> {
   const startTime = performance.now();
 
   try {
-    const results = await invoke<SessionSummary[]>('search_sessions', { query });
+    const results = await invoke<SessionSummary[]>('search_sessions', { query, ...page });
     const searchTime = performance.now() - startTime;
     console.log(`✅ [RUST] Found ${results.length} matches in ${searchTime.toFixed(0)}ms`);
     return results;
@@ -141,6 +178,18 @@ export async function searchSessions(query: string): Promise<SessionSummary[]> {
   }
 }
 
+/**
+ * Query sessions with filters, sorting and pagination (SQLite session index)
+ */
+export async function querySessions(query: SessionQuery = {}): Promise<SessionPage> {
+  try {
+    return await invoke<SessionPage>('query_sessions', { query });
+  } catch (error) {
+    console.error('❌ [RUST] Session query failed:', error);
+    throw error;
+  }
+}
+
 /**
  * Get session count (fast, no full parsing)
  */
@@ -154,6 +203,38 @@ export async function getSessionCount(): Promise<number> {
   }
 }
 
+/**
+ * Load the whole session store (the 'sessions' collection), decrypted by
+ * Rust when session encryption is on; null before the first save
+ */
+export async function loadSessionsStore<T = unknown>(): Promise<T[] | null> {
+  return invoke<T[] | null>('load_sessions_store');
+}
+
+/**
+ * Replace the session store; Rust compresses it and encrypts it at rest when
+ * settings.storage.encryptSessions is on
+ */
+export async function saveSessionsStore<T = unknown>(sessions: T[]): Promise<void> {
+  await invoke('save_sessions_store', { sessions });
+}
+
+/**
+ * One change to a session, appended to its change log (session-logs/{id}.jsonl)
+ * instead of rewriting the store. Appended items already in the field are skipped
+ */
+export type SessionChange =
+  | { op: 'append'; field: string; items: unknown[] }
+  | { op: 'set'; field: string; value: unknown };
+
+/**
+ * Log changes to a session; loads see them right away, and Rust merges the
+ * log into the store later (the next saveSessionsStore replaces it)
+ */
+export async function logSessionChanges(sessionId: string, changes: SessionChange[]): Promise<void> {
+  await invoke('log_session_changes', { sessionId, changes });
+}
+
 // ============================================================================
 // Attachment Loader Commands (Rust backend - parallel processing)
 // ============================================================================
@@ -199,6 +280,60 @@ export async function checkAttachmentsExist(
   }
 }
 
+/** Results of a content-addressed attachment store pass (session_storage.rs) */
+export interface BlobStoreStats {
+  /** Attachment data files examined */
+  scanned: number;
+  /** Attachments newly linked into the store */
+  linked: number;
+  /** Of those, attachments whose content was already stored */
+  deduplicated: number;
+  bytesSaved: number;
+  /** Unreferenced blobs deleted */
+  removedBlobs: number;
+  bytesFreed: number;
+  /** Blobs left in the store */
+  blobs: number;
+}
+
+/**
+ * Move attachment data into the content-addressed store now (identical
+ * content is stored once; `{id}.dat` stays readable as a hard link) and
+ * delete unreferenced blobs. Also runs every 10 minutes in the background.
+ */
+export async function migrateAttachmentStore(): Promise<BlobStoreStats> {
+  try {
+    return await invoke<BlobStoreStats>('migrate_attachment_store');
+  } catch (error) {
+    console.error('❌ [RUST] Attachment store migration failed:', error);
+    throw error;
+  }
+}
+
+/** Results of a session file compaction (session_storage.rs) */
+export interface CompactReport {
+  /** Session files compressed by this run */
+  compacted: number;
+  /** Session files that were already compressed */
+  unchanged: number;
+  bytesBefore: number;
+  bytesAfter: number;
+  bytesReclaimed: number;
+}
+
+/**
+ * Compress uncompressed session files (written by older versions) with zstd,
+ * across all profiles. Encrypted files stay encrypted.
+ */
+export async function compactSessions(): Promise<CompactReport> {
+  try {
+    return await invoke<CompactReport>('compact_sessions');
+  } catch (error) {
+    console.error('❌ [RUST] Session compaction failed:', error);
+    throw error;
+  }
+}
+
 /**
  * Get total size of all attachments (for storage analytics)
  */
@@ -228,3 +363,41 @@ export async function countAttachmentsByType(): Promise<AttachmentCounts> {
     throw error;
   }
 }
+
+// ============================================================================
+// Attachment Metadata Commands (Rust backend - EXIF/dimensions/page count)
+// ============================================================================
+
+/**
+ * Extract media metadata (dimensions, EXIF, PDF page count) for attachments
+ * and persist it into each attachment's meta.json
+ * Runs as a cancellable backend job (see tauri-jobs.ts)
+ */
+export async function extractAttachmentsMetadata(
+  attachmentIds: string[],
+  onProgress?: (event: JobProgress) => void
+): Promise<[string, MediaMetadata][]> {
+  try {
+    const job = await runJob<[string, MediaMetadata][]>(
+      'extract_attachments_metadata',
+      { attachmentIds },
+      onProgress
+    );
+    return await job.result;
+  } catch (error) {
+    console.error('❌ [RUST] Failed to extract attachment metadata:', error);
+    throw error;
+  }
+}
+
+/**
+ * Extract media metadata from a file on disk before importing it
+ */
+export async function extractFileMetadata(path: string, mimeType: string): Promise<MediaMetadata> {
+  try {
+    return await invoke<MediaMetadata>('extract_file_metadata', { path, mimeType });
+  } catch (error) {
+    console.error('❌ [RUST] Failed to extract file metadata:', error);
+    throw error;
+  }
+}
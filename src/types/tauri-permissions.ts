@@ -0,0 +1,45 @@
+/**
+ * TypeScript helpers for macOS privacy permissions (permissions.rs)
+ *
+ * Check, request and open System Settings for the permissions capture
+ * depends on. A denied permission can't be requested again; send the user
+ * to System Settings with `openPermissionSettings` instead. Screen recording
+ * and Accessibility only report 'authorized' or 'denied', and their request
+ * only shows macOS's prompt pointing to System Settings. Other platforms
+ * report 'unsupported'.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+
+export type PermissionType =
+  | 'screenRecording'
+  | 'microphone'
+  | 'camera'
+  /** Reading other apps' UI for richer activity tracking */
+  | 'accessibility'
+  /** Global key events (keystroke overlay, key counts) */
+  | 'inputMonitoring';
+
+export type PermissionStatus = 'notDetermined' | 'restricted' | 'denied' | 'authorized' | 'unsupported';
+
+export interface PermissionState {
+  permission: PermissionType;
+  status: PermissionStatus;
+}
+
+export async function getPermissionStatus(permission: PermissionType): Promise<PermissionStatus> {
+  return await invoke<PermissionStatus>('get_permission_status', { permission });
+}
+
+export async function getAllPermissions(): Promise<PermissionState[]> {
+  return await invoke<PermissionState[]>('get_all_permissions');
+}
+
+/** Shows the system dialog where there is one; resolves with the status afterwards */
+export async function requestPermission(permission: PermissionType): Promise<PermissionStatus> {
+  return await invoke<PermissionStatus>('request_permission', { permission });
+}
+
+export async function openPermissionSettings(permission: PermissionType): Promise<void> {
+  await invoke('open_permission_settings', { permission });
+}
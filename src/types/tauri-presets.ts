@@ -0,0 +1,74 @@
+/**
+ * TypeScript helpers for session presets (presets.rs)
+ *
+ * A preset starts a session with its sources (screenshots, audio, video),
+ * screenshot interval, audio settings, video quality / displays and extra AI
+ * instructions in one call. Presets are saved in presets.json and included
+ * in configuration exports.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import type { QualityPreset } from '../services/videoRecordingService';
+
+export interface PresetSources {
+  screenshots: boolean;
+  audio: boolean;
+  video: boolean;
+}
+
+/** Appended to the session's screenshot analysis / summary prompts */
+export interface PresetPrompts {
+  screenshotAnalysis?: string | null;
+  summary?: string | null;
+}
+
+export interface SessionPreset {
+  name: string;
+  /** Defaults to the preset name */
+  sessionName?: string | null;
+  description?: string;
+  tags?: string[];
+  sources?: PresetSources;
+  /** Minutes between screenshots, or -1 for adaptive (default 2) */
+  screenshotIntervalMinutes?: number;
+  /** Partial settings.audio applied when the preset starts, e.g. `{ systemAudio: true }` */
+  audio?: Record<string, unknown> | null;
+  videoQuality?: QualityPreset | null;
+  /** Displays recorded into separate files (main display when omitted) */
+  displayIds?: number[] | null;
+  aiPrompts?: PresetPrompts;
+}
+
+/** `preset` in the `control-start-session` payload */
+export interface PresetStartOptions {
+  name: string;
+  enableScreenshots: boolean;
+  screenshotInterval: number;
+  audioRecording: boolean;
+  videoRecording: boolean;
+  videoQuality: QualityPreset | null;
+  displayIds: number[] | null;
+  aiPrompts: PresetPrompts;
+}
+
+/** Create or update a preset (matched by name) */
+export async function savePreset(preset: SessionPreset): Promise<void> {
+  return await invoke<void>('save_preset', { preset });
+}
+
+export async function listPresets(): Promise<SessionPreset[]> {
+  return await invoke<SessionPreset[]>('list_presets');
+}
+
+/** Returns false if there was no preset with that name */
+export async function deletePreset(name: string): Promise<boolean> {
+  return await invoke<boolean>('delete_preset', { name });
+}
+
+/**
+ * Start a session from a preset; rejects if a session is already active.
+ * The session itself is started through `control-start-session`.
+ */
+export async function startSessionFromPreset(name: string): Promise<SessionPreset> {
+  return await invoke<SessionPreset>('start_session_from_preset', { name });
+}
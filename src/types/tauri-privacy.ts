@@ -0,0 +1,40 @@
+/**
+ * TypeScript helpers for the privacy blocklist (privacy.rs)
+ *
+ * While a blocked app (name or bundle id) or a window whose title contains a
+ * blocked fragment is frontmost, screenshot captures are skipped and recorded
+ * video frames are blacked out. The blocklist lives in settings.privacy.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+
+export interface PrivacySettings {
+  enabled: boolean;
+  blockedApps: string[];
+  blockedTitles: string[];
+}
+
+export interface PrivacyStatus {
+  enabled: boolean;
+  /** A blocked app or window is frontmost */
+  blocked: boolean;
+  /** App (or "<app>: <window title>") that matched the blocklist */
+  blockedBy: string | null;
+  /** When the current block started (ISO 8601) */
+  since: string | null;
+}
+
+/** Replace the blocklist; returns the saved privacy settings */
+export async function setPrivacyBlocklist(apps: string[], titles: string[]): Promise<PrivacySettings> {
+  return await invoke<PrivacySettings>('set_privacy_blocklist', { apps, titles });
+}
+
+export async function getPrivacyStatus(): Promise<PrivacyStatus> {
+  return await invoke<PrivacyStatus>('get_privacy_status');
+}
+
+/** Emitted while recording when capture is blanked or resumed */
+export async function listenPrivacyStatusChanged(handler: (status: PrivacyStatus) => void): Promise<UnlistenFn> {
+  return listen<PrivacyStatus>('privacy-status-changed', ({ payload }) => handler(payload));
+}
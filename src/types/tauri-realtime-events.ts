@@ -0,0 +1,43 @@
+/**
+ * TypeScript helpers for real-time backend readings
+ *
+ * Channels such as `audio-level` are delivered by realtime_emitter.rs with
+ * one emission in flight at a time: each event is acknowledged after the
+ * handler runs, and readings that arrive while the webview is busy are
+ * replaced by newer ones instead of queueing (so meters never animate
+ * through stale data).
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+
+export interface AudioLevelEvent {
+  rms: number;
+  peak: number;
+}
+
+export interface RealtimeEventMap {
+  'audio-level': AudioLevelEvent;
+}
+
+interface RealtimeEvent<T> {
+  seq: number;
+  value: T;
+  /** Readings superseded since the previous event */
+  dropped: number;
+}
+
+/**
+ * Listen for a real-time channel; the handler always receives the newest reading
+ */
+export async function listenRealtime<K extends keyof RealtimeEventMap>(
+  channel: K,
+  handler: (value: RealtimeEventMap[K]) => void
+): Promise<UnlistenFn> {
+  return listen<RealtimeEvent<RealtimeEventMap[K]>>(channel, ({ payload }) => {
+    handler(payload.value);
+    invoke('ack_realtime_event', { channel, seq: payload.seq }).catch((error) => {
+      console.error('❌ [RUST] Failed to acknowledge realtime event:', error);
+    });
+  });
+}
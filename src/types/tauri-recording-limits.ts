@@ -0,0 +1,52 @@
+/**
+ * TypeScript helpers for recording safety limits (recording_limits.rs)
+ *
+ * settings.limits caps the session duration and the size of video files
+ * (0 = no limit). When a limit is hit the backend stops the recorders and
+ * emits `session-auto-stopped`; the frontend then ends the session as usual.
+ * With fileSizeAction 'rollover' a full video file is finished instead and
+ * recording continues in the next part (`recording-rolled-over`).
+ */
+
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+
+export type FileSizeAction = 'stop' | 'rollover';
+
+export interface LimitSettings {
+  /** 0.5-72, or 0 for no limit (default 12) */
+  maxSessionHours: number;
+  /** 1-1000, or 0 for no limit (default 20) */
+  maxFileSizeGb: number;
+  fileSizeAction: FileSizeAction;
+}
+
+export type AutoStopReason = 'maxDuration' | 'maxFileSize';
+
+/** `session-auto-stopped` payload */
+export interface SessionAutoStopped {
+  sessionId: string;
+  reason: AutoStopReason;
+  /** e.g. "12h" or "20 GB" */
+  limit: string;
+  /** Finished video file, if video was recorded */
+  videoPath: string | null;
+}
+
+/** `recording-rolled-over` payload */
+export interface RecordingRolledOver {
+  sessionId: string;
+  /** The finished video file */
+  path: string;
+}
+
+export async function listenSessionAutoStopped(
+  handler: (stopped: SessionAutoStopped) => void
+): Promise<UnlistenFn> {
+  return listen<SessionAutoStopped>('session-auto-stopped', ({ payload }) => handler(payload));
+}
+
+export async function listenRecordingRolledOver(
+  handler: (rolled: RecordingRolledOver) => void
+): Promise<UnlistenFn> {
+  return listen<RecordingRolledOver>('recording-rolled-over', ({ payload }) => handler(payload));
+}
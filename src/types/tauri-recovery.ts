@@ -0,0 +1,40 @@
+/**
+ * TypeScript helpers for crash recovery (recovery.rs)
+ *
+ * On startup, recordings interrupted by a crash are salvaged: leftover video
+ * segments are finalized into one MP4 and audio chunk files of unfinished
+ * sessions are collected. `session-recovered` fires per session; since it can
+ * fire before the UI listens, also check `getRecoveredSessions()` on mount and
+ * dismiss each one once handled.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+
+export interface RecoveredSession {
+  sessionId: string;
+  sessionName?: string;
+  /** ISO 8601 */
+  recoveredAt: string;
+  /** Finalized video file, if any segment was usable */
+  videoPath?: string;
+  videoSegmentsRecovered: number;
+  /** Segment files that could not be read (left on disk) */
+  videoSegmentsLost: string[];
+  /** Audio chunk files written before the crash, in order */
+  audioChunks: string[];
+}
+
+export async function getRecoveredSessions(): Promise<RecoveredSession[]> {
+  return await invoke<RecoveredSession[]>('get_recovered_sessions');
+}
+
+export async function dismissRecoveredSession(sessionId: string): Promise<void> {
+  await invoke('dismiss_recovered_session', { sessionId });
+}
+
+export async function listenSessionRecovered(
+  handler: (session: RecoveredSession) => void
+): Promise<UnlistenFn> {
+  return listen<RecoveredSession>('session-recovered', ({ payload }) => handler(payload));
+}
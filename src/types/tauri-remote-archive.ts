@@ -0,0 +1,45 @@
+/**
+ * TypeScript helpers for the S3-compatible remote archive (remote_archive.rs)
+ *
+ * With settings.remoteArchive enabled, screenshots, audio and video of
+ * sessions that ended more than `olderThanDays` ago are uploaded to the bucket
+ * and removed locally. Loading a session's detail fetches its media back.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+
+export interface RemoteArchiveSettings {
+  enabled: boolean;
+  /** e.g. https://s3.us-east-1.amazonaws.com or a MinIO/R2 endpoint */
+  endpoint: string;
+  region: string;
+  bucket: string;
+  /** Object key prefix inside the bucket */
+  prefix: string;
+  accessKeyId: string;
+  olderThanDays: number;
+}
+
+export interface OffloadReport {
+  sessions: number;
+  attachments: number;
+  /** Local disk space freed */
+  bytesFreed: number;
+  /** Attachments that failed to upload (left local) */
+  failed: number;
+}
+
+/** Save the secret access key (stored in the keychain, not in settings) */
+export async function setRemoteArchiveSecret(secretAccessKey: string): Promise<void> {
+  return await invoke<void>('set_remote_archive_secret', { secretAccessKey });
+}
+
+/** Check the endpoint, bucket and credentials; rejects with the S3 error */
+export async function testRemoteArchive(): Promise<void> {
+  return await invoke<void>('test_remote_archive');
+}
+
+/** Offload old sessions' media now instead of waiting for the next pass */
+export async function offloadOldSessions(): Promise<OffloadReport> {
+  return await invoke<OffloadReport>('offload_old_sessions');
+}
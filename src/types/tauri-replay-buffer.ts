@@ -0,0 +1,53 @@
+/**
+ * TypeScript helpers for the replay buffer (replay_buffer.rs)
+ *
+ * With settings.replayBuffer enabled the backend keeps the last `minutes` of
+ * screen and microphone without saving anything. `saveReplayBuffer` turns
+ * what it holds into a completed session (video attachment plus the audio as
+ * `fullAudioAttachmentId`) and buffering starts over; SessionsContext adds the
+ * session from `replay-buffer-saved`.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+import type { Session } from '../types';
+
+export interface ReplayBufferSettings {
+  enabled: boolean;
+  /** Minutes kept, 1-30 (default 5) */
+  minutes: number;
+  /** Buffer the microphone along with the screen (default true) */
+  audio: boolean;
+}
+
+export interface ReplayBufferStatus {
+  active: boolean;
+  minutes: number;
+  /** The screen is being buffered (needs screen recording permission) */
+  video: boolean;
+  audio: boolean;
+  /** When buffering (re)started */
+  startedAt: string | null;
+}
+
+/** `replay-buffer-saved` payload, also returned by saveReplayBuffer */
+export interface SavedReplay {
+  sessionId: string;
+  durationSecs: number;
+  session: Session;
+}
+
+/** Keep the buffered minutes as a new session; rejects if the buffer is off */
+export async function saveReplayBuffer(): Promise<SavedReplay> {
+  return await invoke<SavedReplay>('save_replay_buffer');
+}
+
+export async function getReplayBufferStatus(): Promise<ReplayBufferStatus> {
+  return await invoke<ReplayBufferStatus>('get_replay_buffer_status');
+}
+
+export async function listenReplayBufferSaved(
+  handler: (saved: SavedReplay) => void
+): Promise<UnlistenFn> {
+  return listen<SavedReplay>('replay-buffer-saved', ({ payload }) => handler(payload));
+}
@@ -0,0 +1,107 @@
+/**
+ * TypeScript wrappers for the native screenshot scheduler (screenshot_scheduler.rs)
+ *
+ * Fixed-interval session screenshots are timed and captured in Rust, so a
+ * throttled webview no longer misses captures. Each capture is already saved
+ * to the attachment store when `screenshot-captured` fires; the frontend only
+ * adds the screenshot record to its session.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+
+export type ScreenshotSchedulerStatus = 'idle' | 'active' | 'paused';
+
+export interface ScreenshotSchedulerInfo {
+  status: ScreenshotSchedulerStatus;
+  sessionId?: string;
+  intervalMinutes: number;
+  lastCaptureTime?: string;
+  /** Milliseconds until the next capture (active only) */
+  nextCaptureInMs?: number;
+  captureCount: number;
+}
+
+/** Payload of `screenshot-captured` events from the scheduler */
+export interface ScheduledScreenshotEvent {
+  sessionId: string;
+  screenshotId: string;
+  attachmentId: string;
+  timestamp: string;
+  /** Stored (base64) size in bytes */
+  size: number;
+}
+
+/**
+ * Payload of `screenshot-unchanged`: a capture within
+ * settings.screenshots.unchangedThreshold dHash bits of the last stored frame,
+ * which was not stored
+ */
+export interface UnchangedScreenshotEvent {
+  sessionId: string;
+  /** Attachment of the last stored frame */
+  attachmentId: string;
+  timestamp: string;
+  /** Differing hash bits (0-64) */
+  distance: number;
+}
+
+/**
+ * Start scheduled captures for a session (first capture after 3 seconds).
+ * The interval defaults to settings.screenshots.intervalMinutes.
+ */
+export async function startScreenshotScheduler(
+  sessionId: string,
+  intervalMinutes?: number
+): Promise<ScreenshotSchedulerInfo> {
+  return invoke<ScreenshotSchedulerInfo>('start_screenshot_scheduler', { sessionId, intervalMinutes });
+}
+
+export async function stopScreenshotScheduler(): Promise<void> {
+  await invoke('stop_screenshot_scheduler');
+}
+
+export async function pauseScreenshotScheduler(): Promise<void> {
+  await invoke('pause_screenshot_scheduler');
+}
+
+export async function resumeScreenshotScheduler(): Promise<void> {
+  await invoke('resume_screenshot_scheduler');
+}
+
+export async function setScreenshotInterval(intervalMinutes: number): Promise<ScreenshotSchedulerInfo> {
+  return invoke<ScreenshotSchedulerInfo>('set_screenshot_interval', { intervalMinutes });
+}
+
+export async function getScreenshotSchedulerStatus(): Promise<ScreenshotSchedulerInfo> {
+  return invoke<ScreenshotSchedulerInfo>('get_screenshot_scheduler_status');
+}
+
+/**
+ * Listen for scheduled captures. `screenshot-captured` is also emitted with a
+ * PNG data URL by the screenshot shortcut; those payloads are ignored here.
+ */
+export async function listenScheduledScreenshots(
+  handler: (event: ScheduledScreenshotEvent) => void
+): Promise<UnlistenFn> {
+  return listen<ScheduledScreenshotEvent | string>('screenshot-captured', ({ payload }) => {
+    if (typeof payload !== 'string') {
+      handler(payload);
+    }
+  });
+}
+
+export async function listenUnchangedScreenshots(
+  handler: (event: UnchangedScreenshotEvent) => void
+): Promise<UnlistenFn> {
+  return listen<UnchangedScreenshotEvent>('screenshot-unchanged', ({ payload }) => handler(payload));
+}
+
+/**
+ * Recognize (or re-recognize) the text in a screenshot attachment (ocr.rs,
+ * macOS only). Screenshots are also recognized in the background; the text
+ * is stored next to the attachment and included in session search.
+ */
+export async function extractTextFromScreenshot(attachmentId: string): Promise<string> {
+  return invoke<string>('extract_text_from_screenshot', { attachmentId });
+}
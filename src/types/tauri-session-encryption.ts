@@ -0,0 +1,44 @@
+/**
+ * TypeScript wrappers for at-rest session encryption (session_encryption.rs)
+ *
+ * sessions.json is encrypted with AES-256-GCM using a key kept in the OS
+ * keychain. Rust session commands (load_session_detail, search, the session
+ * index) decrypt transparently, so callers don't need to know which mode is on.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+
+export interface SessionEncryptionStatus {
+  enabled: boolean;
+  keyInKeychain: boolean;
+  /** Session files across all profiles, by on-disk state */
+  encryptedFiles: number;
+  plaintextFiles: number;
+}
+
+export interface SessionEncryptionMigrationReport {
+  converted: number;
+  unchanged: number;
+}
+
+export async function getSessionEncryptionStatus(): Promise<SessionEncryptionStatus> {
+  return invoke<SessionEncryptionStatus>('get_session_encryption_status');
+}
+
+/**
+ * Turn encryption on (creates the keychain key). Existing plaintext sessions
+ * stay readable; call migrateSessionsToEncrypted() to encrypt them.
+ */
+export async function enableSessionEncryption(): Promise<SessionEncryptionStatus> {
+  return invoke<SessionEncryptionStatus>('enable_session_encryption');
+}
+
+/** Turn encryption off, decrypting every profile's sessions */
+export async function disableSessionEncryption(): Promise<SessionEncryptionMigrationReport> {
+  return invoke<SessionEncryptionMigrationReport>('disable_session_encryption');
+}
+
+/** Encrypt existing plaintext sessions in every profile (encryption must be enabled) */
+export async function migrateSessionsToEncrypted(): Promise<SessionEncryptionMigrationReport> {
+  return invoke<SessionEncryptionMigrationReport>('migrate_sessions_to_encrypted');
+}
@@ -0,0 +1,66 @@
+/**
+ * TypeScript helpers for session health reports (session_health.rs)
+ *
+ * One report covering video frame drops, audio buffer overruns, disk write
+ * throughput, capture CPU usage and the last error of each subsystem. The
+ * backend emits `session-health` every 5s while a session records; rates
+ * cover the time since the previous report (`intervalSecs`, 0 in the first).
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+
+export interface VideoHealth {
+  recording: boolean;
+  paused: boolean;
+  /** Totals since recording started (all displays) */
+  framesWritten: number;
+  framesDropped: number;
+  /** Share of frames dropped over the interval, 0-1 */
+  dropRate: number;
+  /** Frame processing, percent of one core */
+  cpuPercent: number;
+  lastError: string | null;
+}
+
+export interface AudioHealth {
+  state: 'stopped' | 'recording' | 'paused';
+  /** Times samples were dropped because the media buffer budget was full */
+  bufferOverruns: number;
+  /** Unclaimed binary chunks evicted */
+  droppedChunks: number;
+  systemAudioStalled: boolean;
+  /** Capture callbacks, percent of one core */
+  cpuPercent: number;
+  lastError: string | null;
+}
+
+export interface DiskHealth {
+  /** Video files and audio chunk files together */
+  writeBytesPerSec: number;
+  videoBytes: number;
+  availableBytes: number | null;
+}
+
+/** `session-health` payload, also returned by getSessionHealth */
+export interface SessionHealth {
+  sessionId: string;
+  timestamp: string;
+  intervalSecs: number;
+  video: VideoHealth;
+  audio: AudioHealth;
+  disk: DiskHealth;
+  /** Whole app, percent of one core */
+  processCpuPercent: number | null;
+}
+
+/** Rejects if `sessionId` isn't the session being recorded */
+export async function getSessionHealth(sessionId: string): Promise<SessionHealth> {
+  return await invoke<SessionHealth>('get_session_health', { sessionId });
+}
+
+export async function listenSessionHealth(
+  handler: (health: SessionHealth) => void
+): Promise<UnlistenFn> {
+  return listen<SessionHealth>('session-health', ({ payload }) => handler(payload));
+}
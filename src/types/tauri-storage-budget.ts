@@ -0,0 +1,86 @@
+/**
+ * TypeScript helpers for the storage budget (storage_budget.rs)
+ *
+ * Keeps session media (screenshots, audio, video) under a disk budget by
+ * rotating the oldest completed sessions: `downsample` shrinks screenshots and
+ * drops video first, `delete` removes their media files. Session records,
+ * notes and transcripts are kept. Runs every 15 minutes and right after the
+ * budget changes.
+ *
+ * The same task deletes sessions (records and media) that ended longer ago
+ * than the active profile's retentionDays, emitting `sessions-expired`.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+
+export type RotationMode = 'downsample' | 'delete';
+
+export interface StorageBudget {
+  /** 0 = unlimited */
+  budgetGb: number;
+  rotation: RotationMode;
+  /** Current session media usage */
+  usedBytes: number;
+}
+
+/** settings.storage */
+export interface StorageSettings {
+  encryptSessions: boolean;
+  budgetGb: number;
+  rotation: RotationMode;
+  /** Recordings refuse to start below this much free space */
+  minFreeSpaceMb: number;
+  /** `low` disk-space-warning threshold while recording */
+  warnFreeSpaceMb: number;
+}
+
+export interface RotatedSession {
+  sessionId: string;
+  name: string;
+  action: 'downsampled' | 'deleted';
+  freedBytes: number;
+}
+
+/** `storage-rotated` payload */
+export interface StorageRotationReport {
+  budgetBytes: number;
+  /** Session media after rotation */
+  usedBytes: number;
+  freedBytes: number;
+  sessions: RotatedSession[];
+}
+
+/** `sessions-expired` payload */
+export interface SessionsExpiredEvent {
+  retentionDays: number;
+  sessionIds: string[];
+  /** Media deleted with them */
+  freedBytes: number;
+}
+
+export async function getStorageBudget(): Promise<StorageBudget> {
+  return await invoke<StorageBudget>('get_storage_budget');
+}
+
+/**
+ * Set the budget in GB (0 = unlimited); rotation runs right away
+ */
+export async function setStorageBudget(
+  budgetGb: number,
+  rotation?: RotationMode
+): Promise<StorageSettings> {
+  return await invoke<StorageSettings>('set_storage_budget', { budgetGb, rotation });
+}
+
+export async function listenStorageRotated(
+  handler: (report: StorageRotationReport) => void
+): Promise<UnlistenFn> {
+  return listen<StorageRotationReport>('storage-rotated', ({ payload }) => handler(payload));
+}
+
+export async function listenSessionsExpired(
+  handler: (event: SessionsExpiredEvent) => void
+): Promise<UnlistenFn> {
+  return listen<SessionsExpiredEvent>('sessions-expired', ({ payload }) => handler(payload));
+}
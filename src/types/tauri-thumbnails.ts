@@ -0,0 +1,22 @@
+/**
+ * TypeScript helpers for attachment thumbnails (thumbnails.rs)
+ *
+ * Small cached JPEG thumbnails for attachment grids, generated from
+ * screenshots and video frames so views don't load full images. Session
+ * screenshots are also thumbnailed in the background.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * Thumbnail no larger than `maxDim` on either edge (default 256, at most
+ * 1024), as a JPEG data URL
+ */
+export async function getAttachmentThumbnail(id: string, maxDim?: number): Promise<string> {
+  return invoke<string>('get_attachment_thumbnail', { id, maxDim });
+}
+
+/** Drop cached thumbnails of deleted attachments */
+export async function clearAttachmentThumbnails(attachmentIds: string[]): Promise<void> {
+  return invoke<void>('clear_attachment_thumbnails', { attachmentIds });
+}
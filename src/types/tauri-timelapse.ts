@@ -0,0 +1,31 @@
+/**
+ * TypeScript helper for session timelapses (timelapse.rs)
+ *
+ * Renders a session's screenshots with timestamp overlays into a GIF or MP4
+ * (chosen by the output extension; MP4 needs ffmpeg). Runs as a cancellable
+ * backend job (see tauri-jobs.ts).
+ */
+
+import { runJob, type JobProgress, type RunningJob } from './tauri-jobs';
+
+export interface TimelapseResult {
+  path: string;
+  frames: number;
+  /** Screenshots that couldn't be read */
+  skipped: number;
+  durationMs: number;
+}
+
+/** Start rendering a timelapse to `outputPath` (.gif or .mp4) at `fps` (default 4) */
+export async function generateSessionTimelapse(
+  sessionId: string,
+  outputPath: string,
+  fps?: number,
+  onProgress?: (event: JobProgress) => void
+): Promise<RunningJob<TimelapseResult>> {
+  return await runJob<TimelapseResult>(
+    'generate_session_timelapse',
+    { sessionId, fps, outputPath },
+    onProgress
+  );
+}
@@ -0,0 +1,89 @@
+/**
+ * TypeScript helpers for the background transcription queue (transcription_queue.rs)
+ *
+ * Audio chunk files are queued with `enqueueTranscription` and transcribed by
+ * the backend with bounded concurrency and retries. The queue survives app
+ * restarts; results arrive as `transcription-complete` / `transcription-failed`
+ * events keyed by session id and chunk index.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+
+export type TranscriptionStatus = 'pending' | 'running';
+
+/** One queued chunk */
+export interface TranscriptionItem {
+  sessionId: string;
+  chunkIndex: number;
+  path: string;
+  status: TranscriptionStatus;
+  /** Attempts made so far */
+  attempts: number;
+  lastError?: string;
+  /** Unix millis */
+  enqueuedAt: number;
+  /** Not attempted before this (Unix millis) */
+  nextAttemptAt: number;
+}
+
+/** `transcription-complete` payload */
+export interface TranscriptionCompleteEvent {
+  sessionId: string;
+  chunkIndex: number;
+  path: string;
+  text: string;
+}
+
+/** `transcription-failed` payload (sent once retries are exhausted) */
+export interface TranscriptionFailedEvent {
+  sessionId: string;
+  chunkIndex: number;
+  path: string;
+  error: string;
+  attempts: number;
+}
+
+/**
+ * Queue a WAV / MP3 / FLAC chunk file; returns false if that chunk is
+ * already being transcribed
+ */
+export async function enqueueTranscription(
+  sessionId: string,
+  chunkIndex: number,
+  path: string
+): Promise<boolean> {
+  return await invoke<boolean>('enqueue_transcription', { sessionId, chunkIndex, path });
+}
+
+/**
+ * Queued and running chunks, optionally for one session
+ */
+export async function getTranscriptionQueue(sessionId?: string): Promise<TranscriptionItem[]> {
+  return await invoke<TranscriptionItem[]>('get_transcription_queue', { sessionId });
+}
+
+/**
+ * Drop a session's pending chunks; returns how many were removed
+ */
+export async function cancelSessionTranscriptions(sessionId: string): Promise<number> {
+  return await invoke<number>('cancel_session_transcriptions', { sessionId });
+}
+
+/**
+ * Listen for finished transcriptions
+ */
+export async function listenTranscriptionComplete(
+  handler: (event: TranscriptionCompleteEvent) => void
+): Promise<UnlistenFn> {
+  return listen<TranscriptionCompleteEvent>('transcription-complete', ({ payload }) => handler(payload));
+}
+
+/**
+ * Listen for chunks that failed after all retries
+ */
+export async function listenTranscriptionFailed(
+  handler: (event: TranscriptionFailedEvent) => void
+): Promise<UnlistenFn> {
+  return listen<TranscriptionFailedEvent>('transcription-failed', ({ payload }) => handler(payload));
+}
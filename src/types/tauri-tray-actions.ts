@@ -0,0 +1,49 @@
+/**
+ * TypeScript helpers for tray quick actions (tray_actions.rs)
+ *
+ * Actions appear in the tray menu between the session controls and the window
+ * items; clicking one emits `emitEvent` (with `payload`) to the frontend.
+ * They are saved in tray_actions.json and restored on launch.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+
+export interface TrayAction {
+  id: string;
+  label: string;
+  emitEvent: string;
+  payload: unknown;
+}
+
+/** Add an action (or replace the one with the same id) */
+export async function addTrayAction(
+  id: string,
+  label: string,
+  emitEvent: string,
+  payload?: unknown
+): Promise<void> {
+  return await invoke<void>('add_tray_action', { id, label, emitEvent, payload });
+}
+
+/** Returns false if there was no action with that id */
+export async function removeTrayAction(id: string): Promise<boolean> {
+  return await invoke<boolean>('remove_tray_action', { id });
+}
+
+export async function listTrayActions(): Promise<TrayAction[]> {
+  return await invoke<TrayAction[]>('list_tray_actions');
+}
+
+/** Register an action and listen for its clicks */
+export async function registerTrayAction<T = unknown>(
+  id: string,
+  label: string,
+  emitEvent: string,
+  handler: (payload: T) => void,
+  payload?: T
+): Promise<UnlistenFn> {
+  const unlisten = await listen<T>(emitEvent, ({ payload }) => handler(payload));
+  await addTrayAction(id, label, emitEvent, payload);
+  return unlisten;
+}
@@ -0,0 +1,26 @@
+/**
+ * TypeScript helper for extracting a clip from a session recording
+ * (`extract_video_clip` in video_recording.rs, macOS only)
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+
+export interface VideoClip {
+  path: string;
+  durationMs: number;
+  /** False when the streams were copied (clip started on a keyframe) */
+  reencoded: boolean;
+}
+
+/**
+ * Export [startMs, endMs) of a session's recording to an MP4 at `outputPath`.
+ * Media offloaded to the remote archive is fetched first.
+ */
+export async function extractVideoClip(
+  sessionId: string,
+  startMs: number,
+  endMs: number,
+  outputPath: string
+): Promise<VideoClip> {
+  return await invoke<VideoClip>('extract_video_clip', { sessionId, startMs, endMs, outputPath });
+}
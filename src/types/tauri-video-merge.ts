@@ -0,0 +1,66 @@
+/**
+ * TypeScript helpers for the video/audio merge queue (video_audio_merger.rs)
+ *
+ * Merges run one at a time in the background, highest priority first (ffmpeg
+ * must be installed). Follow a merge with `listenMergeProgress` instead of
+ * polling `getMergeProgress`.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+
+export type MergePriority = 'low' | 'normal' | 'high';
+
+export type MergeStatus = 'queued' | 'running' | 'completed' | 'failed' | 'cancelled';
+
+export interface MergeRequest {
+  videoPath: string;
+  /** Mixed together when there is more than one */
+  audioPaths: string[];
+  outputPath: string;
+  /** Default 'normal' */
+  priority?: MergePriority;
+  sessionId?: string;
+}
+
+/** State of one merge (`merge-progress` payload) */
+export interface MergeJob {
+  jobId: string;
+  sessionId?: string;
+  videoPath: string;
+  audioPaths: string[];
+  outputPath: string;
+  priority: MergePriority;
+  status: MergeStatus;
+  /** 0 - 1 */
+  progress: number;
+  /** Estimated seconds until a running merge completes */
+  etaSeconds?: number;
+  enqueuedAt: string;
+  startedAt?: string;
+  finishedAt?: string;
+  error?: string;
+}
+
+/** Queue a merge; returns the job id */
+export async function mergeVideoAndAudio(request: MergeRequest): Promise<string> {
+  return await invoke<string>('merge_video_and_audio', { request });
+}
+
+export async function getMergeProgress(jobId: string): Promise<MergeJob> {
+  return await invoke<MergeJob>('get_merge_progress', { jobId });
+}
+
+/** Running merge, queued merges in execution order, then recently finished ones */
+export async function listMergeJobs(): Promise<MergeJob[]> {
+  return await invoke<MergeJob[]>('list_merge_jobs');
+}
+
+/** Cancel a queued or running merge; false if it already finished */
+export async function cancelMerge(jobId: string): Promise<boolean> {
+  return await invoke<boolean>('cancel_merge', { jobId });
+}
+
+export async function listenMergeProgress(handler: (job: MergeJob) => void): Promise<UnlistenFn> {
+  return listen<MergeJob>('merge-progress', ({ payload }) => handler(payload));
+}
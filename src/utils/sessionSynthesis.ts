@@ -686,7 +686,10 @@ export async function generateFlexibleSummary(
     } : null,
   };
 
-  const prompt = buildFlexibleSummaryPrompt(session, screenshots, audioSegments, enrichedContext);
+  let prompt = buildFlexibleSummaryPrompt(session, screenshots, audioSegments, enrichedContext);
+  if (session.aiPrompts?.summary) {
+    prompt += `\n\nAdditional instructions for this session:\n${session.aiPrompts.summary}`;
+  }
 
   try {
     console.log('📊 SessionSynthesis: Generating flexible summary (Phase 2)...');